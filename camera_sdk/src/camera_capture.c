@@ -38,6 +38,25 @@
 #include "PixelType.h"
 #include "camera_api.h"
 
+// per-frame grab timeout, adjustable at runtime via camera_set_frame_timeout()
+static unsigned int g_frame_timeout_ms = TIMEOUT_MS;
+
+/**
+ * @brief set per-frame grab timeout for camera_get_frame()
+ * 
+ * @param timeout_ms timeout in milliseconds (must be > 0)
+ * @return int error code (MV_OK if success)
+ */
+int camera_set_frame_timeout(unsigned int timeout_ms) {
+    if (timeout_ms == 0) {
+        printf("camera_set_frame_timeout: Invalid timeout %u ms\n", timeout_ms);
+        return MV_E_PARAMETER;
+    }
+    g_frame_timeout_ms = timeout_ms;
+    printf("camera_set_frame_timeout: Frame timeout set to %u ms\n", timeout_ms);
+    return MV_OK;
+}
+
 /**
  * @brief start grabbing image continuously from all cameras
  * 
@@ -82,7 +101,7 @@ int free_image_buffer(MV_FRAME_OUT stFrame[], unsigned int count) {
 /**
  * @brief get current frame from all cameras
  * 
- * TIMEOUT_MS超时时间
+ * g_frame_timeout_ms超时时间（默认TIMEOUT_MS，可由camera_set_frame_timeout()修改）
  * 
  * @param out_bufs[] output buffer pointer array, point to one frame image data
  * @param out_sizes[] output buffer size array stores captured frames length
@@ -114,7 +133,7 @@ int camera_get_frame(uint8_t* out_bufs[], uint32_t out_sizes[]) {
             printf("Fail to Get Frame from Camera %d", i);
             return -1;
         }
-        nRet = MV_CC_GetImageBuffer(cameras[i].handle, &stFrame[i], TIMEOUT_MS);
+        nRet = MV_CC_GetImageBuffer(cameras[i].handle, &stFrame[i], g_frame_timeout_ms);
         if (MV_OK != nRet) {
             printf("Fail to GetImageBuffer from Camera %d: 0x%x\n", i, nRet);
             // free buffer
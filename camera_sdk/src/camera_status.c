@@ -183,4 +183,71 @@ int camera_get_status(unsigned int cam_index, float* fps_actual, uint32_t* frame
     return MV_OK;
 }
 
-// 已删除 camera_set_exposure_time() 和 camera_set_gain() - 参数在camera_init.c中写死
\ No newline at end of file
+/**
+ * @brief 设置相机曝光时间（自动曝光扫描使用，运行中可调用）
+ * @param cam_index 相机索引
+ * @param exposure_us 曝光时间（微秒）
+ * @return 错误码（0=成功）
+ */
+int camera_set_exposure_time(unsigned int cam_index, float exposure_us) {
+    if (cam_index >= CAMERA_NUM) {
+        printf("camera_set_exposure_time: Invalid camera index %d\n", cam_index);
+        return ERR_INVALID_CAMERA_INDEX;
+    }
+    
+    if (!cameras[cam_index].opened || NULL == cameras[cam_index].handle) {
+        printf("camera_set_exposure_time: Camera %d not initialized\n", cam_index);
+        return ERR_CAMERA_NOT_INITIALIZED;
+    }
+    
+    if (exposure_us <= 0.0f) {
+        printf("camera_set_exposure_time: Invalid exposure %.1f us\n", exposure_us);
+        return MV_E_PARAMETER;
+    }
+    
+    int nRet = MV_CC_SetFloatValue(cameras[cam_index].handle, "ExposureTime", exposure_us);
+    if (MV_OK != nRet) {
+        printf("camera_set_exposure_time: Camera %d exposure setting failed: 0x%x\n", cam_index, nRet);
+        return nRet;
+    }
+    
+    printf("camera_set_exposure_time: Camera %d exposure time set to %.1f us\n", cam_index, exposure_us);
+    return MV_OK;
+}
+
+/**
+ * @brief 读取相机当前曝光时间
+ * @param cam_index 相机索引
+ * @param exposure_us 曝光时间输出（微秒）
+ * @return 错误码（0=成功）
+ */
+int camera_get_exposure_time(unsigned int cam_index, float* exposure_us) {
+    if (cam_index >= CAMERA_NUM) {
+        printf("camera_get_exposure_time: Invalid camera index %d\n", cam_index);
+        return ERR_INVALID_CAMERA_INDEX;
+    }
+    
+    if (!cameras[cam_index].opened || NULL == cameras[cam_index].handle) {
+        printf("camera_get_exposure_time: Camera %d not initialized\n", cam_index);
+        return ERR_CAMERA_NOT_INITIALIZED;
+    }
+    
+    if (NULL == exposure_us) {
+        printf("camera_get_exposure_time: Invalid output parameter\n");
+        return MV_E_PARAMETER;
+    }
+    
+    MVCC_FLOATVALUE stFloatValue;
+    memset(&stFloatValue, 0, sizeof(MVCC_FLOATVALUE));
+    
+    int nRet = MV_CC_GetFloatValue(cameras[cam_index].handle, "ExposureTime", &stFloatValue);
+    if (MV_OK != nRet) {
+        printf("camera_get_exposure_time: Camera %d exposure query failed: 0x%x\n", cam_index, nRet);
+        return nRet;
+    }
+    
+    *exposure_us = stFloatValue.fCurValue;
+    return MV_OK;
+}
+
+// 已删除 camera_set_gain() - 增益在camera_init.c中写死
\ No newline at end of file
@@ -79,6 +79,15 @@ typedef struct {
     uint32_t frame_rate;      // Target frame rate (fps)
 } Camera;
 
+// Detected device info (camera_enumerate)
+#define CAMERA_ENUM_MAX 16
+
+typedef struct {
+    uint32_t index;            // Enumeration index
+    char serial[64];           // Serial number
+    char name[64];             // Model name (user defined name if set)
+} CameraDeviceInfo;
+
 // Global variables
 extern Camera cameras[CAMERA_NUM];
 extern uint32_t g_frame_buf_size;
@@ -93,6 +102,39 @@ int camera_get_frame(uint8_t* out_bufs[], uint32_t out_sizes[]);
 uint32_t camera_get_frame_buf_size();
 int camera_release();
 
+/**
+ * @brief Set per-frame grab timeout used by camera_get_frame (default TIMEOUT_MS)
+ * @param timeout_ms Timeout in milliseconds (must be > 0)
+ * @return Error code (0=success)
+ */
+int camera_set_frame_timeout(unsigned int timeout_ms);
+
+/**
+ * @brief Enumerate attached USB3 cameras (does not open them)
+ * @param out_devices Output array
+ * @param max_count Capacity of out_devices
+ * @param out_count Number of devices written
+ * @return Error code (0=success)
+ */
+int camera_enumerate(CameraDeviceInfo* out_devices, unsigned int max_count, unsigned int* out_count);
+
+/**
+ * @brief Select left/right cameras by serial for the next camera_init
+ * @param left_serial Left camera serial (NULL or "" restores LEFT_CAMERA_SERIAL)
+ * @param right_serial Right camera serial (NULL or "" restores RIGHT_CAMERA_SERIAL)
+ * @return Error code (0=success)
+ */
+int camera_set_serials(const char* left_serial, const char* right_serial);
+
+/**
+ * @brief Serial of the opened camera in slot cam_index (0/1, as returned by camera_get_frame)
+ * @param cam_index Camera slot
+ * @param out_serial Output buffer
+ * @param max_len Capacity of out_serial
+ * @return Error code (0=success)
+ */
+int camera_get_serial(unsigned int cam_index, char* out_serial, unsigned int max_len);
+
 // === Configuration API ===
 // [配置系统 - 已注释]
 // /**
@@ -121,6 +163,22 @@ int camera_get_status(unsigned int cam_index, float* fps_actual, uint32_t* frame
  */
 int camera_configure_for_stage(const char* stage_name);
 
+/**
+ * @brief Set exposure time at runtime (used by auto exposure scan)
+ * @param cam_index Camera index
+ * @param exposure_us Exposure time in microseconds
+ * @return Error code (0=success)
+ */
+int camera_set_exposure_time(unsigned int cam_index, float exposure_us);
+
+/**
+ * @brief Get current exposure time
+ * @param cam_index Camera index
+ * @param exposure_us Exposure time output (microseconds)
+ * @return Error code (0=success)
+ */
+int camera_get_exposure_time(unsigned int cam_index, float* exposure_us);
+
 // 已删除软件配置函数 - 所有参数在camera_init.c中写死配置
 
 
@@ -4,6 +4,7 @@
 use std::path::Path;
 use opencv::{core, imgcodecs, prelude::*};
 use merging_image_lib::modules::alignment::AlignmentSystem;
+use merging_image_lib::paths;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("🚀 启动AlignmentSystem集成测试");
@@ -26,10 +27,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // 使用yaml_last_param_file目录中的参数文件
     let mut alignment_system = AlignmentSystem::new(
         image_size,
-        "yaml_last_param_file/left_camera_params.yaml",
-        "yaml_last_param_file/right_camera_params.yaml", 
-        "yaml_last_param_file/stereo_params.yaml",
-        "yaml_last_param_file/rectify_params.yaml",
+        &paths::params_path("left_camera_params.yaml"),
+        &paths::params_path("right_camera_params.yaml"), 
+        &paths::params_path("stereo_params.yaml"),
+        &paths::params_path("rectify_params.yaml"),
     )?;
     
     println!("✓ AlignmentSystem创建成功");
@@ -52,7 +53,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     match alignment_system.detect_circles_grid(
         &left_image,
         &right_image,
-        "yaml_last_param_file/rectify_maps.yaml"
+        &paths::params_path("rectify_maps.yaml")
     ) {
         Ok((corners_left, corners_right)) => {
             let detection_time = detection_start.elapsed();
@@ -5,6 +5,7 @@ use std::path::Path;
 use std::time::{Duration, Instant};
 use opencv::{core, imgcodecs, prelude::*};
 use merging_image_lib::modules::alignment_pipeline::AlignmentPipeline;
+use merging_image_lib::paths;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("🚀 启动AlignmentPipeline集成测试");
@@ -26,11 +27,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     let mut pipeline = AlignmentPipeline::new(
         image_size,
-        "yaml_last_param_file/left_camera_params.yaml",
-        "yaml_last_param_file/right_camera_params.yaml",
-        "yaml_last_param_file/stereo_params.yaml",
-        "yaml_last_param_file/rectify_params.yaml",
-        "yaml_last_param_file/rectify_maps.yaml",
+        &paths::params_path("left_camera_params.yaml"),
+        &paths::params_path("right_camera_params.yaml"),
+        &paths::params_path("stereo_params.yaml"),
+        &paths::params_path("rectify_params.yaml"),
+        &paths::params_path("rectify_maps.yaml"),
     )?;
     
     println!("✅ AlignmentPipeline创建成功");
@@ -5,6 +5,7 @@
 use std::time::Instant;
 use opencv::{core, imgcodecs, prelude::*};
 use merging_image_lib::modules::alignment::{AlignmentSystem, SingleEyePoseResult, DualEyeAlignmentResult, CenteringResult, AdjustmentVectors};
+use merging_image_lib::paths;
 
 /// 重构功能测试器
 pub struct AlignmentRefactorTest {
@@ -34,12 +35,12 @@ impl AlignmentRefactorTest {
         // let stereo_params = src_tauri_dir.join("stereo_params.yaml");
         // let rectify_params = src_tauri_dir.join("rectify_params.yaml");
         
-        let left_cam_params = src_tauri_dir.join("yaml_last_param_file/left_camera_params.yaml");
-        let right_cam_params = src_tauri_dir.join("yaml_last_param_file/right_camera_params.yaml");
-        let stereo_params = src_tauri_dir.join("yaml_last_param_file/stereo_params.yaml");
-        let rectify_params = src_tauri_dir.join("yaml_last_param_file/rectify_params.yaml");
+        let left_cam_params = paths::params_dir().join("left_camera_params.yaml");
+        let right_cam_params = paths::params_dir().join("right_camera_params.yaml");
+        let stereo_params = paths::params_dir().join("stereo_params.yaml");
+        let rectify_params = paths::params_dir().join("rectify_params.yaml");
         // 🔧 修正重映射矩阵路径 - 使用yaml_last_param_file目录
-        let rectify_maps = src_tauri_dir.join("yaml_last_param_file/rectify_maps.yaml");
+        let rectify_maps = paths::params_dir().join("rectify_maps.yaml");
         
         // 加载测试图像
         println!("📁 加载测试图像...");
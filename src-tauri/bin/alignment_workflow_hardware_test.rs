@@ -140,6 +140,7 @@ impl AlignmentWorkflowHardwareTest {
                 vec![0.0, 0.0, 0.0, 2000.0],
                 vec![0.0, 0.0, -0.01, 0.0]  // 1/基线距离
             ],
+            valid_roi: None,
         };
         
         // 保存参数文件
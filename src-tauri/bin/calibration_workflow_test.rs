@@ -25,6 +25,7 @@
 //! @date 2025-01-15
 
 use merging_image_lib::modules::calibration_workflow::*;
+use merging_image_lib::paths;
 use std::fs;
 use std::path::PathBuf;
 
@@ -33,16 +34,16 @@ fn cleanup_test_environment() {
     println!("🧹 清理测试环境...");
     // 清理可能存在的测试目录
     let test_dirs = vec![
-        "captures",
-        "yaml_last_param_file",
+        paths::captures_dir(),
+        paths::params_dir(),
     ];
     
     for dir in test_dirs {
-        if PathBuf::from(dir).exists() {
-            if let Err(e) = fs::remove_dir_all(dir) {
-                println!("⚠️ 清理目录 {} 失败: {}", dir, e);
+        if dir.exists() {
+            if let Err(e) = fs::remove_dir_all(&dir) {
+                println!("⚠️ 清理目录 {} 失败: {}", dir.display(), e);
             } else {
-                println!("✅ 清理目录: {}", dir);
+                println!("✅ 清理目录: {}", dir.display());
             }
         }
     }
@@ -53,10 +54,10 @@ fn setup_test_environment() {
     cleanup_test_environment();
     
     // 创建必要的目录
-    if let Err(e) = fs::create_dir_all("captures") {
+    if let Err(e) = fs::create_dir_all(paths::captures_dir()) {
         println!("⚠️ 创建测试目录失败: {}", e);
     } else {
-        println!("✅ 创建测试目录: captures");
+        println!("✅ 创建测试目录: {}", paths::captures_dir().display());
     }
 }
 
@@ -103,7 +104,7 @@ fn test_calibration_config() -> Result<(), String> {
     assert_eq!(config.pattern_size.height, 10);
     assert_eq!(config.error_threshold, 2.0);
     assert_eq!(config.target_image_count, 10);
-    assert_eq!(config.save_directory, "captures");
+    assert_eq!(config.save_directory, paths::captures_dir().to_string_lossy());
     
     println!("✅ CalibrationConfig默认值验证通过");
     Ok(())
@@ -185,6 +186,9 @@ fn test_calibration_result_structure() -> Result<(), String> {
         error_threshold: 2.0,
         error_message: None,
         calibration_time: "2025-01-15T10:30:00Z".to_string(),
+        report: None,
+        per_view_errors: Vec::new(),
+        worst_pair_ids: Vec::new(),
     };
     
     // 验证JSON序列化
@@ -211,7 +215,7 @@ fn test_directory_management() -> Result<(), String> {
     
     // 测试会话目录创建
     let session_id = "test_session_123456789";
-    let save_directory = format!("captures/calibration_{}", session_id);
+    let save_directory = paths::captures_path(&format!("calibration_{}", session_id));
     
     fs::create_dir_all(&save_directory)
         .map_err(|e| format!("创建会话目录失败: {}", e))?;
@@ -222,14 +226,14 @@ fn test_directory_management() -> Result<(), String> {
     println!("  ✅ 会话目录创建成功: {}", save_directory);
     
     // 测试参数保存目录创建
-    let param_directory = "yaml_last_param_file";
-    fs::create_dir_all(param_directory)
+    let param_directory = paths::params_dir();
+    fs::create_dir_all(&param_directory)
         .map_err(|e| format!("创建参数目录失败: {}", e))?;
         
-    if !PathBuf::from(param_directory).exists() {
+    if !param_directory.exists() {
         return Err("参数目录创建后不存在".to_string());
     }
-    println!("  ✅ 参数目录创建成功: {}", param_directory);
+    println!("  ✅ 参数目录创建成功: {}", param_directory.display());
     
     println!("✅ 目录管理功能验证通过");
     Ok(())
@@ -5,6 +5,7 @@
 use std::fs;
 use opencv::{core::Size, imgcodecs, prelude::*};
 use merging_image_lib::modules::calibration_circles::*;
+use merging_image_lib::paths;
 
 fn main() {
     println!("🔍 对比两个检测函数的差异");
@@ -60,7 +61,7 @@ fn test_get_image_points_and_obj_points_pairs(calibrator: &mut Calibrator) {
 
 fn test_detect_and_get_points_from_paths(calibrator: &mut Calibrator) {
     // 生成采集图像的路径
-    let captured_folder = r"D:\rust_projects\merging_image\src-tauri\captures\calibration_calibration_1755064325";
+    let captured_folder = &paths::captures_path("calibration_calibration_1755064325");
     let mut left_paths = Vec::new();
     
     for i in 1..=10 {
@@ -83,7 +84,7 @@ fn test_single_image_comparison(calibrator: &mut Calibrator) {
     // 测试一张已知成功的BMP图像
     let bmp_path = r"C:\Users\Y000010\MVS\Data\point_5_4\l_0.bmp";
     // 测试一张PNG图像
-    let png_path = r"D:\rust_projects\merging_image\src-tauri\captures\calibration_calibration_1755064325\calib_left_01.png";
+    let png_path = &paths::captures_path("calibration_calibration_1755064325/calib_left_01.png");
     
     println!("🧪 测试BMP图像: {}", bmp_path);
     test_single_image(calibrator, bmp_path);
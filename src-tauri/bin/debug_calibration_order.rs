@@ -5,6 +5,7 @@
 use std::fs;
 use opencv::{core::{Size, Vector, Point2f, Point3f, Scalar}, imgcodecs, imgproc, prelude::*};
 use merging_image_lib::modules::calibration_circles::*;
+use merging_image_lib::paths;
 
 fn main() {
     println!("🔍 调试标定板圆点检测顺序");
@@ -12,7 +13,7 @@ fn main() {
     
     // 测试两组图像
     let good_image = r"C:\Users\Y000010\MVS\Data\point_5_4\png\l_0.png";
-    let bad_image = r"D:\rust_projects\merging_image\src-tauri\captures\calibration_calibration_1755503179\calib_left_01.png";
+    let bad_image = &paths::captures_path("calibration_calibration_1755503179/calib_left_01.png");
     
     println!("\n📸 第一组（成功）: {}", good_image);
     analyze_calibration_image(good_image, "good");
@@ -2,6 +2,7 @@
 // 检查右相机图像质量、检测精度和可能的相机移动
 
 use merging_image_lib::modules::calibration_circles::{Calibrator, CameraType};
+use merging_image_lib::paths;
 use opencv::core::Size;
 use opencv::prelude::MatTraitConst;
 
@@ -18,7 +19,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         1.0,
     )?;
 
-    let test_folder = r"D:\rust_projects\merging_image\src-tauri\captures\calibration_calibration_1755064325";
+    let test_folder = &paths::captures_path("calibration_calibration_1755064325");
     
     // 检查右相机图像
     println!("📊 检查右相机图像质量:");
@@ -6,7 +6,9 @@
 use std::time::{Duration, Instant};
 use std::path::Path;
 use opencv::{core, imgcodecs, prelude::*};
-use merging_image_lib::modules::alignment::AlignmentSystem;
+use merging_image_lib::modules::alignment::{AlignmentSystem, StageTimings};
+use merging_image_lib::modules::benchmark::{BenchmarkSummary, TARGET_FRAME_TIME_10FPS_MS};
+use merging_image_lib::paths;
 
 /// 性能测试结果统计
 #[derive(Debug, Clone)]
@@ -76,19 +78,12 @@ impl DetectionBenchmark {
         // 构造绝对路径
         let img_path_left = src_tauri_dir.join("src/tests/data/benchmark/left_calibration.bmp");
         let img_path_right = src_tauri_dir.join("src/tests/data/benchmark/right_calibration.bmp");
-        // 🔧 修正参数文件路径 - 使用yaml_last_param_file目录
-        // 旧路径 (注释掉):
-        // let left_cam_params = src_tauri_dir.join("left_camera_params.yaml");
-        // let right_cam_params = src_tauri_dir.join("right_camera_params.yaml");
-        // let stereo_params = src_tauri_dir.join("stereo_params.yaml");
-        // let rectify_params = src_tauri_dir.join("rectify_params.yaml");
-        
-        let left_cam_params = src_tauri_dir.join("yaml_last_param_file/left_camera_params.yaml");
-        let right_cam_params = src_tauri_dir.join("yaml_last_param_file/right_camera_params.yaml");
-        let stereo_params = src_tauri_dir.join("yaml_last_param_file/stereo_params.yaml");
-        let rectify_params = src_tauri_dir.join("yaml_last_param_file/rectify_params.yaml");
-        // 🔧 修正重映射矩阵路径 - 使用yaml_last_param_file目录
-        let rectify_maps = src_tauri_dir.join("yaml_last_param_file/rectify_maps.yaml");
+        // 参数文件从数据目录解析（可用 COSONIC_DATA_DIR 覆盖，见 paths.rs）
+        let left_cam_params = paths::params_path("left_camera_params.yaml");
+        let right_cam_params = paths::params_path("right_camera_params.yaml");
+        let stereo_params = paths::params_path("stereo_params.yaml");
+        let rectify_params = paths::params_path("rectify_params.yaml");
+        let rectify_maps = paths::rectify_maps_path();
         
         // 加载测试图像
         println!("📁 加载测试图像...");
@@ -116,11 +111,11 @@ impl DetectionBenchmark {
         println!("🔧 初始化优化的合像检测系统（含预加载）...");
         let alignment_system = AlignmentSystem::new_with_preload(
             image_size,
-            left_cam_params.to_str().unwrap(),
-            right_cam_params.to_str().unwrap(), 
-            stereo_params.to_str().unwrap(),
-            rectify_params.to_str().unwrap(),
-            rectify_maps.to_str().unwrap(),
+            &left_cam_params,
+            &right_cam_params,
+            &stereo_params,
+            &rectify_params,
+            &rectify_maps,
         )?;
         
         println!("✓ 优化的合像检测系统初始化完成");
@@ -155,11 +150,11 @@ impl DetectionBenchmark {
             test_image_left,
             test_image_right,
             results,
-            rectify_maps_path: rectify_maps.to_string_lossy().to_string(),
-            left_cam_params_path: left_cam_params.to_string_lossy().to_string(),
-            right_cam_params_path: right_cam_params.to_string_lossy().to_string(),
-            stereo_params_path: stereo_params.to_string_lossy().to_string(),
-            rectify_params_path: rectify_params.to_string_lossy().to_string(),
+            rectify_maps_path: rectify_maps,
+            left_cam_params_path: left_cam_params,
+            right_cam_params_path: right_cam_params,
+            stereo_params_path: stereo_params,
+            rectify_params_path: rectify_params,
         })
     }
     
@@ -289,6 +284,7 @@ impl DetectionBenchmark {
         println!("🔍 测试后续检测性能（{}次）...", count);
         
         let mut detection_times = Vec::new();
+        let mut stage_timings = Vec::new();
         let mut successful_detections = 0;
         
         for i in 1..=count {
@@ -306,6 +302,7 @@ impl DetectionBenchmark {
             match result {
                 Ok(_) => {
                     detection_times.push(detection_time);
+                    stage_timings.push(self.alignment_system.get_last_stage_timings());
                     successful_detections += 1;
                     println!(" 成功 ({:.1} ms)", detection_time.as_millis());
                 },
@@ -315,23 +312,13 @@ impl DetectionBenchmark {
             }
         }
         
-        self.results.subsequent_detection_times = detection_times;
-        
         if successful_detections > 0 {
-            let avg_time = self.results.subsequent_detection_times.iter()
-                .sum::<Duration>() / successful_detections as u32;
-            let max_time = self.results.subsequent_detection_times.iter().max().unwrap();
-            let min_time = self.results.subsequent_detection_times.iter().min().unwrap();
-            
-            println!("📊 后续检测统计:");
-            println!("   成功率: {}/{} ({:.1}%)", 
-                    successful_detections, count, 
-                    successful_detections as f64 / count as f64 * 100.0);
-            println!("   平均耗时: {:.1} ms", avg_time.as_millis());
-            println!("   最大耗时: {:.1} ms", max_time.as_millis());
-            println!("   最小耗时: {:.1} ms", min_time.as_millis());
+            BenchmarkSummary::from_samples(count, &detection_times, &stage_timings)
+                .print_report("后续检测统计");
         }
         
+        self.results.subsequent_detection_times = detection_times;
+        
         Ok(())
     }
     
@@ -339,9 +326,6 @@ impl DetectionBenchmark {
     fn test_stage_breakdown(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         println!("🔍 分析各阶段耗时...");
         
-        // 由于现有的AlignmentSystem没有暴露各阶段的时间，
-        // 这里通过多次调用和时间差估算
-        
         // 估算懒加载时间（首次 - 平均后续）
         if !self.results.subsequent_detection_times.is_empty() {
             let avg_subsequent = self.results.subsequent_detection_times.iter()
@@ -356,22 +340,55 @@ impl DetectionBenchmark {
                 };
         }
         
-        // 对于其他阶段，由于没有细分时间，使用估算值
-        let total_detection_time = if !self.results.subsequent_detection_times.is_empty() {
-            self.results.subsequent_detection_times.iter()
-                .sum::<Duration>() / self.results.subsequent_detection_times.len() as u32
-        } else {
-            self.results.first_detection_time
-        };
+        // 其他阶段使用AlignmentSystem返回的实测分阶段耗时（多次取平均）
+        let rounds = 5;
+        let mut sum = StageTimings::default();
+        let mut measured = 0;
+        for _ in 0..rounds {
+            let (corners_left, corners_right) = match self.alignment_system.detect_circles_grid(
+                &self.test_image_left,
+                &self.test_image_right,
+                &self.rectify_maps_path,
+            ) {
+                Ok(corners) => corners,
+                Err(e) => {
+                    println!("⚠️  分阶段测量检测失败: {}", e);
+                    continue;
+                }
+            };
+            let mut timings = self.alignment_system.get_last_stage_timings();
+            
+            let pose_start = Instant::now();
+            self.alignment_system.check_left_eye_pose(&corners_left)?;
+            self.alignment_system.check_right_eye_pose(&corners_right)?;
+            timings.pose_ms = pose_start.elapsed().as_secs_f64() * 1000.0;
+            
+            let alignment_start = Instant::now();
+            self.alignment_system.check_dual_eye_alignment(&corners_left, &corners_right, false)?;
+            timings.alignment_ms = alignment_start.elapsed().as_secs_f64() * 1000.0;
+            
+            sum.remap_ms += timings.remap_ms;
+            sum.detect_ms += timings.detect_ms;
+            sum.sort_ms += timings.sort_ms;
+            sum.pose_ms += timings.pose_ms;
+            sum.alignment_ms += timings.alignment_ms;
+            measured += 1;
+        }
+        
+        if measured == 0 {
+            return Err("分阶段测量全部失败".into());
+        }
         
-        // 基于经验估算各阶段比例
-        let total_ms = total_detection_time.as_millis() as f64;
-        self.results.stage_breakdown.remap_time = Duration::from_millis((total_ms * 0.3) as u64);
-        self.results.stage_breakdown.detection_time = Duration::from_millis((total_ms * 0.4) as u64);
-        self.results.stage_breakdown.pose_calculation_time = Duration::from_millis((total_ms * 0.2) as u64);
-        self.results.stage_breakdown.alignment_analysis_time = Duration::from_millis((total_ms * 0.1) as u64);
+        let n = measured as f64;
+        let ms_to_duration = |ms: f64| Duration::from_secs_f64(ms / n / 1000.0);
+        self.results.stage_breakdown.remap_time = ms_to_duration(sum.remap_ms);
+        // 圆心检测阶段包含连通域检测 + 排序
+        self.results.stage_breakdown.detection_time = ms_to_duration(sum.detect_ms + sum.sort_ms);
+        self.results.stage_breakdown.pose_calculation_time = ms_to_duration(sum.pose_ms);
+        self.results.stage_breakdown.alignment_analysis_time = ms_to_duration(sum.alignment_ms);
+        let total_ms = sum.total_ms() / n;
         
-        println!("📊 各阶段耗时估算:");
+        println!("📊 各阶段实测耗时 (平均{}次):", measured);
         println!("   懒加载: {:.1} ms ({:.1}%)", 
                 self.results.stage_breakdown.lazy_loading_time.as_millis(),
                 self.results.stage_breakdown.lazy_loading_time.as_millis() as f64 / 
@@ -480,7 +497,7 @@ impl DetectionBenchmark {
         
         // 10fps兼容性分析
         println!("\n🎯 10fps兼容性分析:");
-        let fps_10_threshold = 100.0; // 100ms
+        let fps_10_threshold = TARGET_FRAME_TIME_10FPS_MS;
         
         let avg_detection_time = if !self.results.subsequent_detection_times.is_empty() {
             self.results.subsequent_detection_times.iter()
@@ -4,6 +4,7 @@
 use std::time::Instant;
 use opencv::{core, imgcodecs, prelude::*};
 use merging_image_lib::modules::alignment::AlignmentSystem;
+use merging_image_lib::paths;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("🚀 启动简化合像检测测试");
@@ -20,7 +21,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let img_left = src_tauri_dir.join("src/tests/data/benchmark/left_calibration.bmp");
     let img_right = src_tauri_dir.join("src/tests/data/benchmark/right_calibration.bmp");
             // 🔧 修正重映射矩阵路径 - 使用yaml_last_param_file目录
-        let rectify_maps = src_tauri_dir.join("yaml_last_param_file/rectify_maps.yaml");
+        let rectify_maps = paths::params_dir().join("rectify_maps.yaml");
     
     println!("\n📁 检查文件存在性:");
     println!("   左图: {:?} - {}", img_left, if img_left.exists() { "✓" } else { "❌" });
@@ -62,10 +63,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         // let stereo_params = src_tauri_dir.join("stereo_params.yaml");
         // let rectify_params = src_tauri_dir.join("rectify_params.yaml");
         
-        let left_params = src_tauri_dir.join("yaml_last_param_file/left_camera_params.yaml");
-        let right_params = src_tauri_dir.join("yaml_last_param_file/right_camera_params.yaml");
-        let stereo_params = src_tauri_dir.join("yaml_last_param_file/stereo_params.yaml");
-        let rectify_params = src_tauri_dir.join("yaml_last_param_file/rectify_params.yaml");
+        let left_params = paths::params_dir().join("left_camera_params.yaml");
+        let right_params = paths::params_dir().join("right_camera_params.yaml");
+        let stereo_params = paths::params_dir().join("stereo_params.yaml");
+        let rectify_params = paths::params_dir().join("rectify_params.yaml");
     
     // 检查标定参数文件
     let param_files = [
@@ -17,6 +17,7 @@ use std::{thread, time::Duration};
 
 // 导入我们的SimpleCameraManager
 use merging_image_lib::camera_manager::{SimpleCameraManager, CameraError};
+use merging_image_lib::paths;
 
 fn main() {
     println!("=== SimpleCameraManager 功能测试程序 ===");
@@ -165,7 +166,7 @@ fn test_save_functionality() -> Result<(), Box<dyn std::error::Error>> {
     
     // 检查文件是否存在
     println!("   检查保存的文件...");
-    let captures_dir = std::path::Path::new("captures");
+    let captures_dir = paths::captures_dir();
     if captures_dir.exists() {
         let entries: Vec<_> = std::fs::read_dir(captures_dir)?
             .filter_map(|entry| entry.ok())
@@ -246,7 +247,7 @@ fn test_continuous_capture_performance() -> Result<(), Box<dyn std::error::Error
 /// 辅助函数：清理测试文件
 #[allow(dead_code)]
 fn cleanup_test_files() -> Result<(), Box<dyn std::error::Error>> {
-    let captures_dir = std::path::Path::new("captures");
+    let captures_dir = paths::captures_dir();
     if captures_dir.exists() {
         std::fs::remove_dir_all(captures_dir)?;
         println!("🧹 清理测试文件完成");
@@ -2,6 +2,7 @@
 // 专门针对暗光照、杂乱背景等恶劣条件进行测试
 
 use merging_image_lib::modules::calibration_circles::{Calibrator, CameraType};
+use merging_image_lib::paths;
 use opencv::core::Size;
 use opencv::prelude::MatTraitConst;
 
@@ -35,8 +36,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
 fn test_recent_images(calibrator: &mut Calibrator) -> Result<(), Box<dyn std::error::Error>> {
     let test_images = vec![
-        r"D:\rust_projects\merging_image\src-tauri\captures\calibration_calibration_1755078298\calib_left_01.png",
-        r"D:\rust_projects\merging_image\src-tauri\captures\calibration_calibration_1755078298\calib_left_02.png",
+        paths::captures_path("calibration_calibration_1755078298/calib_left_01.png"),
+        paths::captures_path("calibration_calibration_1755078298/calib_left_02.png"),
     ];
 
     let mut success_count = 0;
@@ -82,7 +83,7 @@ fn test_recent_images(calibrator: &mut Calibrator) -> Result<(), Box<dyn std::er
 }
 
 fn test_full_image_set(calibrator: &mut Calibrator) -> Result<(), Box<dyn std::error::Error>> {
-    let test_folder = r"D:\rust_projects\merging_image\src-tauri\captures\calibration_calibration_1755064325";
+    let test_folder = &paths::captures_path("calibration_calibration_1755064325");
     
     // 构建10张左相机图像路径
     let mut left_paths = Vec::new();
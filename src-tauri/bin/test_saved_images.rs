@@ -274,7 +274,7 @@ fn test_full_calibration(left_images: &[String], right_images: &[String]) {
     };
     
     let (r, t, stereo_error) = match stereo_result {
-        StereoCalibResult::Success { r, t, error } => {
+        StereoCalibResult::Success { r, t, error, .. } => {
             println!("✅ 双目标定成功，RMS误差: {:.4}", error);
             (r, t, error)
         },
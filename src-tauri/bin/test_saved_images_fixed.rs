@@ -332,7 +332,7 @@ fn test_full_calibration(left_images: &[String], right_images: &[String]) {
     };
     
     let (r, t, stereo_error) = match stereo_result {
-        StereoCalibResult::Success { r, t, error } => {
+        StereoCalibResult::Success { r, t, error, .. } => {
             println!("✅ 双目标定成功，RMS误差: {:.4}", error);
             (r, t, error)
         },
@@ -364,7 +364,7 @@ fn test_full_calibration(left_images: &[String], right_images: &[String]) {
         );
         
         match outlier_result {
-            Ok(StereoCalibResult::Success { r: _, t: _, error }) => {
+            Ok(StereoCalibResult::Success { error, .. }) => {
                 println!("✅ 异常值剔除后双目标定成功，RMS误差: {:.4}", error);
                 println!("   改善效果: {:.4} → {:.4} ({:.1}%)", 
                         stereo_error, error, 
@@ -2,6 +2,7 @@
 // 对比workflow层面和直接算法层面的差异
 
 use merging_image_lib::modules::calibration_workflow::*;
+use merging_image_lib::paths;
 use std::path::Path;
 use opencv::prelude::*;
 
@@ -24,7 +25,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 }
 
 fn test_workflow_detection_logic() -> Result<(), Box<dyn std::error::Error>> {
-    let test_folder = r"D:\rust_projects\merging_image\src-tauri\captures\calibration_calibration_1755064325";
+    let test_folder = &paths::captures_path("calibration_calibration_1755064325");
     
     // 创建CalibrationWorkflow实例（但不启动相机）
     println!("🔧 创建CalibrationWorkflow实例...");
@@ -100,7 +101,7 @@ fn test_full_workflow_calibration() -> Result<(), Box<dyn std::error::Error>> {
     println!("🔧 准备完整workflow标定流程测试...");
     
     // 创建模拟的ImagePair列表
-    let test_folder = r"D:\rust_projects\merging_image\src-tauri\captures\calibration_calibration_1755064325";
+    let test_folder = &paths::captures_path("calibration_calibration_1755064325");
     let mut image_pairs = Vec::new();
     
     for i in 1..=10 {
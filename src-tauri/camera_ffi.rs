@@ -151,6 +151,25 @@ pub struct Camera {
     pub frame_rate: c_uint,        //target frame rate
 }
 
+/// 枚举上限，与 camera_api.h 中 CAMERA_ENUM_MAX 一致
+pub const CAMERA_ENUM_MAX: usize = 16;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct CameraDeviceInfo {
+    pub index: c_uint,             //enumeration index
+    pub serial: [c_char; 64],      //serial number
+    pub name: [c_char; 64],        //model name (user defined name if set)
+}
+
+/// 检测到的相机设备（list_cameras 返回）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CameraDevice {
+    pub index: u32,      // 枚举序号（不保证稳定，按序列号分配左右）
+    pub serial: String,
+    pub name: String,
+}
+
 //#[link(name = "camera_sdk")]
 unsafe extern "C" {
     // === 原有API ===
@@ -160,12 +179,20 @@ unsafe extern "C" {
     pub fn camera_get_frame(out_bufs: *mut *mut c_uchar, out_sizes: *mut c_uint,) -> c_int;
     pub fn camera_get_frame_buf_size() -> c_uint;
     pub fn camera_release() -> c_int;
+    pub fn camera_set_frame_timeout(timeout_ms: c_uint) -> c_int;
+    pub fn camera_enumerate(out_devices: *mut CameraDeviceInfo, max_count: c_uint, out_count: *mut c_uint) -> c_int;
+    pub fn camera_set_serials(left_serial: *const c_char, right_serial: *const c_char) -> c_int;
+    pub fn camera_get_serial(cam_index: c_uint, out_serial: *mut c_char, max_len: c_uint) -> c_int;
     
     // === 配置API ===
     // [配置系统 - 已注释] pub fn set_camera_mode(mode: c_int);
     
     // === 保留的监控API ===
     pub fn camera_get_status(cam_index: c_uint, fps_actual: *mut f32, frames_dropped: *mut c_uint) -> c_int;
+    
+    // === 曝光控制API ===
+    pub fn camera_set_exposure_time(cam_index: c_uint, exposure_us: f32) -> c_int;
+    pub fn camera_get_exposure_time(cam_index: c_uint, exposure_us: *mut f32) -> c_int;
     // pub fn camera_configure_for_stage(stage_name: *const c_char) -> c_int; // 已删除，使用SimpleCameraManager替代
     
 
@@ -320,6 +347,68 @@ impl CameraHandle {
         }
     }
 
+    /// 设置单帧采集超时 (毫秒)，camera_get_frame 超过该时间返回错误
+    pub fn camera_set_frame_timeout_ffi(&self, timeout_ms: u32) -> Result<(), i32> {
+        let code = unsafe {
+            camera_set_frame_timeout(timeout_ms)
+        };
+        if code == 0 {
+            Ok(())
+        } else {
+            Err(code)
+        }
+    }
+
+    /// 枚举已连接的相机（不打开设备）
+    pub fn camera_enumerate_ffi() -> Result<Vec<CameraDevice>, i32> {
+        let empty = CameraDeviceInfo { index: 0, serial: [0; 64], name: [0; 64] };
+        let mut devices = [empty; CAMERA_ENUM_MAX];
+        let mut count: c_uint = 0;
+        let code = unsafe {
+            camera_enumerate(devices.as_mut_ptr(), CAMERA_ENUM_MAX as c_uint, &mut count)
+        };
+        if code != 0 {
+            return Err(code);
+        }
+        let to_string = |chars: &[c_char; 64]| unsafe {
+            std::ffi::CStr::from_ptr(chars.as_ptr()).to_string_lossy().into_owned()
+        };
+        Ok(devices[..count as usize].iter().map(|device| CameraDevice {
+            index: device.index,
+            serial: to_string(&device.serial),
+            name: to_string(&device.name),
+        }).collect())
+    }
+
+    /// 设置下次 camera_init 使用的左右相机序列号，None 恢复 camera_api.h 中的默认序列号
+    pub fn camera_set_serials_ffi(serials: Option<(&str, &str)>) -> Result<(), i32> {
+        let code = match serials {
+            Some((left, right)) => {
+                let left = std::ffi::CString::new(left).map_err(|_| -1)?;
+                let right = std::ffi::CString::new(right).map_err(|_| -1)?;
+                unsafe { camera_set_serials(left.as_ptr(), right.as_ptr()) }
+            }
+            None => unsafe { camera_set_serials(std::ptr::null(), std::ptr::null()) },
+        };
+        if code == 0 {
+            Ok(())
+        } else {
+            Err(code)
+        }
+    }
+
+    /// 读取已打开相机槽位（camera_get_frame 的输出顺序 0/1）的序列号
+    pub fn camera_get_serial_ffi(&self, cam_index: u32) -> Result<String, i32> {
+        let mut serial: [c_char; 64] = [0; 64];
+        let code = unsafe {
+            camera_get_serial(cam_index, serial.as_mut_ptr(), serial.len() as c_uint)
+        };
+        if code != 0 {
+            return Err(code);
+        }
+        Ok(unsafe { std::ffi::CStr::from_ptr(serial.as_ptr()) }.to_string_lossy().into_owned())
+    }
+
     // === 新增FFI函数 ===
 
     // 已删除触发模式、帧率设置和软触发函数 - 新架构下不再需要
@@ -340,6 +429,31 @@ impl CameraHandle {
         }
     }
 
+    /// 设置曝光时间 (微秒)
+    pub fn camera_set_exposure_time_ffi(&self, cam_index: u32, exposure_us: f32) -> Result<(), i32> {
+        let code = unsafe {
+            camera_set_exposure_time(cam_index, exposure_us)
+        };
+        if code == 0 {
+            Ok(())
+        } else {
+            Err(code)
+        }
+    }
+
+    /// 读取当前曝光时间 (微秒)
+    pub fn camera_get_exposure_time_ffi(&self, cam_index: u32) -> Result<f32, i32> {
+        let mut exposure_us: f32 = 0.0;
+        let code = unsafe {
+            camera_get_exposure_time(cam_index, &mut exposure_us)
+        };
+        if code == 0 {
+            Ok(exposure_us)
+        } else {
+            Err(code)
+        }
+    }
+
     // === 已删除的工作流程配置函数 ===
     // 这些函数已被SimpleCameraManager替代，不再需要
     /*
@@ -361,6 +475,56 @@ impl CameraHandle {
     // 已删除曝光时间、增益设置和软件帧率控制函数 - 参数在camera_init.c中写死
 }
 
+/// SimpleCameraManager 使用的相机底层接口
+/// 
+/// 正常运行时由 CameraHandle 通过C层实现；测试中可注入桩实现模拟超时、断连等情况
+pub trait CameraBackend: Send {
+    /// 重新初始化相机（断连重连时使用，release 之后调用）
+    fn camera_reinit_ffi(&self) -> Result<(), i32>;
+    fn camera_start_ffi(&self) -> Result<(), i32>;
+    fn camera_get_frame_ffi(&self, out_bufs: &mut [*mut c_uchar; 2], out_sizes: &mut [c_uint; 2]) -> Result<(), i32>;
+    fn camera_release_ffi(&self) -> Result<(), i32>;
+    fn camera_set_frame_timeout_ffi(&self, timeout_ms: u32) -> Result<(), i32>;
+    fn camera_set_exposure_time_ffi(&self, cam_index: u32, exposure_us: f32) -> Result<(), i32>;
+    fn camera_get_exposure_time_ffi(&self, cam_index: u32) -> Result<f32, i32>;
+    /// 槽位 cam_index（取帧输出顺序）上相机的序列号
+    fn camera_get_serial_ffi(&self, cam_index: u32) -> Result<String, i32>;
+}
+
+impl CameraBackend for CameraHandle {
+    fn camera_reinit_ffi(&self) -> Result<(), i32> {
+        CameraHandle::camera_init_ffi().map(|_| ())
+    }
+
+    fn camera_start_ffi(&self) -> Result<(), i32> {
+        CameraHandle::camera_start_ffi(self)
+    }
+
+    fn camera_get_frame_ffi(&self, out_bufs: &mut [*mut c_uchar; 2], out_sizes: &mut [c_uint; 2]) -> Result<(), i32> {
+        CameraHandle::camera_get_frame_ffi(self, out_bufs, out_sizes)
+    }
+
+    fn camera_release_ffi(&self) -> Result<(), i32> {
+        CameraHandle::camera_release_ffi(self)
+    }
+
+    fn camera_set_frame_timeout_ffi(&self, timeout_ms: u32) -> Result<(), i32> {
+        CameraHandle::camera_set_frame_timeout_ffi(self, timeout_ms)
+    }
+
+    fn camera_set_exposure_time_ffi(&self, cam_index: u32, exposure_us: f32) -> Result<(), i32> {
+        CameraHandle::camera_set_exposure_time_ffi(self, cam_index, exposure_us)
+    }
+
+    fn camera_get_exposure_time_ffi(&self, cam_index: u32) -> Result<f32, i32> {
+        CameraHandle::camera_get_exposure_time_ffi(self, cam_index)
+    }
+
+    fn camera_get_serial_ffi(&self, cam_index: u32) -> Result<String, i32> {
+        CameraHandle::camera_get_serial_ffi(self, cam_index)
+    }
+}
+
 /// 基于实际硬件测试的性能参数常量
 pub mod performance_constants {
     use std::time::Duration;
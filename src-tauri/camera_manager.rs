@@ -21,22 +21,142 @@
  * @author Camera Simplification Expert
  */
 
-use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
+use std::sync::{Arc, Mutex, atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering}};
 // use std::os::raw::{c_uchar, c_uint}; // 暂时未使用
-use crate::camera_ffi::CameraHandle;
+use serde::{Serialize, Deserialize};
+use crate::camera_ffi::{CameraHandle, CameraBackend, CameraDevice};
+
+/// 默认单帧采集超时 (ms)，与 camera_api.h 中 TIMEOUT_MS 一致
+pub const DEFAULT_FRAME_TIMEOUT_MS: u32 = 1000;
+
+/// 采集超时与自动重连配置
+/// 
+/// 连续 reconnect_after_failures 次取帧失败（USB链路抖动时通常表现为超时）后，
+/// 自动执行 release → init → start 重连；重连期间 get_current_frame 返回
+/// CameraError::Reconnecting，采集线程照常重试即可
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FrameRecoveryConfig {
+    pub frame_timeout_ms: u32,           // 单帧采集超时 (ms)
+    pub reconnect_after_failures: u32,   // 连续失败多少次后重连，0 表示不自动重连
+}
+
+impl Default for FrameRecoveryConfig {
+    fn default() -> Self {
+        Self {
+            frame_timeout_ms: DEFAULT_FRAME_TIMEOUT_MS,
+            reconnect_after_failures: 3,
+        }
+    }
+}
+
+impl FrameRecoveryConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.frame_timeout_ms == 0 {
+            return Err("单帧采集超时必须大于0ms".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// 启动预热默认丢弃帧数
+pub const DEFAULT_WARMUP_DISCARD_FRAMES: u32 = 3;
+
+/// 启动预热配置
+/// 
+/// start() 之后的前几帧常因曝光尚未稳定而偏暗，工作流在报告就绪前先取出并丢弃
+/// discard_frames 帧，且预热总时长不少于 min_duration_ms
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CameraWarmupConfig {
+    pub discard_frames: u32,     // 丢弃帧数，0 表示不预热
+    pub min_duration_ms: u32,    // 最短预热时长 (ms)
+}
+
+impl Default for CameraWarmupConfig {
+    fn default() -> Self {
+        Self {
+            discard_frames: DEFAULT_WARMUP_DISCARD_FRAMES,
+            min_duration_ms: 0,
+        }
+    }
+}
+
+impl CameraWarmupConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.discard_frames > 100 {
+            return Err(format!("预热丢弃帧数不能超过100: {}", self.discard_frames));
+        }
+        if self.min_duration_ms > 10_000 {
+            return Err(format!("最短预热时长不能超过10000ms: {}", self.min_duration_ms));
+        }
+        Ok(())
+    }
+}
+
+/// 按序列号指定左右相机（取自相机配置，未加载配置时沿用 camera_api.h 中的序列号）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CameraAssignment {
+    pub left_serial: String,
+    pub right_serial: String,
+}
+
+impl CameraAssignment {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.left_serial.trim().is_empty() || self.right_serial.trim().is_empty() {
+            return Err("左右相机序列号不能为空".to_string());
+        }
+        if self.left_serial == self.right_serial {
+            return Err(format!("左右相机不能为同一台: {}", self.left_serial));
+        }
+        Ok(())
+    }
+}
+
+static CAMERA_ASSIGNMENT: Mutex<Option<CameraAssignment>> = Mutex::new(None);
+
+/// 设置左右相机分配（启动时及配置修改时调用），下次 SimpleCameraManager::new 生效
+pub fn set_camera_assignment(assignment: Option<CameraAssignment>) {
+    *CAMERA_ASSIGNMENT.lock().unwrap() = assignment;
+}
+
+/// 当前左右相机分配
+pub fn camera_assignment() -> Option<CameraAssignment> {
+    CAMERA_ASSIGNMENT.lock().unwrap().clone()
+}
+
+/// 枚举已连接的相机（序列号/型号），供选择左右相机
+pub fn list_cameras() -> Result<Vec<CameraDevice>, CameraError> {
+    CameraHandle::camera_enumerate_ffi().map_err(CameraError::InitFailed)
+}
 
 /// 简化的相机管理器
 /// 
 /// 基于硬件10fps连续采集，提供统一的图像获取接口
 pub struct SimpleCameraManager {
-    /// 相机FFI句柄
-    cam_handle: CameraHandle,
+    /// 相机底层接口（正常为C层FFI，测试时可注入桩实现）
+    cam_handle: Box<dyn CameraBackend>,
     /// 运行状态标志
     running: Arc<AtomicBool>,
     /// 帧缓冲区大小
     frame_buf_size: u32,
     /// 帧计数器（用于文件命名）
     frame_counter: Arc<Mutex<u32>>,
+    /// 采集超时与自动重连配置
+    recovery_config: Mutex<FrameRecoveryConfig>,
+    /// 连续取帧失败次数
+    consecutive_failures: AtomicU32,
+    /// 已执行的重连次数
+    reconnect_count: AtomicU32,
+    /// 左右眼互换：物理左光机接在相机1时开启，get_current_frame 按逻辑左右返回
+    swap_eyes: AtomicBool,
+    /// 启动预热配置
+    warmup_config: Mutex<CameraWarmupConfig>,
+    /// 按序列号确定的左相机所在槽位（0/1），与USB枚举顺序无关
+    left_slot: AtomicUsize,
+    /// 已校验的左右相机序列号（重连后按此重新确认槽位）
+    camera_roles: Mutex<Option<CameraAssignment>>,
+    /// 设定的曝光时间（微秒），None 时使用C层初始化的默认曝光；启动时重新下发
+    exposure_us: Mutex<Option<f32>>,
 }
 
 /// 相机管理错误类型
@@ -56,6 +176,12 @@ pub enum CameraError {
     AlreadyStarted,
     /// 文件保存失败
     SaveFailed(String),
+    /// 相机参数设置/读取失败
+    ParamFailed(i32),
+    /// 连续取帧失败，正在/刚刚执行自动重连（本次无帧，稍后重试）
+    Reconnecting { attempt: u32, last_error: i32 },
+    /// 配置的左右相机序列号与已打开的相机不符
+    SerialMismatch(String),
 }
 
 impl std::fmt::Display for CameraError {
@@ -68,6 +194,9 @@ impl std::fmt::Display for CameraError {
             CameraError::NotStarted => write!(f, "Camera not started"),
             CameraError::AlreadyStarted => write!(f, "Camera already started"),
             CameraError::SaveFailed(msg) => write!(f, "File save failed: {}", msg),
+            CameraError::ParamFailed(code) => write!(f, "Camera parameter access failed: 0x{:x}", code),
+            CameraError::Reconnecting { attempt, last_error } => write!(f, "Camera reconnecting (attempt {}, last error: 0x{:x})", attempt, last_error),
+            CameraError::SerialMismatch(msg) => write!(f, "Camera serial mismatch: {}", msg),
         }
     }
 }
@@ -88,6 +217,17 @@ impl SimpleCameraManager {
     pub fn new() -> Result<Self, CameraError> {
         println!("🏗️ SimpleCameraManager::new: 初始化相机管理器...");
         
+        // 0. 按配置序列号选择左右相机（未加载配置时使用C层默认序列号）
+        let assignment = camera_assignment();
+        if let Some(ref assignment) = assignment {
+            println!("   - 指定相机: 左 {}, 右 {}", assignment.left_serial, assignment.right_serial);
+        }
+        CameraHandle::camera_set_serials_ffi(assignment.as_ref().map(|a| (a.left_serial.as_str(), a.right_serial.as_str())))
+            .map_err(|e| {
+                eprintln!("❌ SimpleCameraManager::new: 设置相机序列号失败: 0x{:x}", e);
+                CameraError::InitFailed(e)
+            })?;
+        
         // 1. 初始化相机硬件
         let cam_handle = CameraHandle::camera_init_ffi()
             .map_err(|e| {
@@ -106,12 +246,37 @@ impl SimpleCameraManager {
         println!("   - 帧缓冲区大小: {} bytes", frame_buf_size);
         println!("   - 硬件配置: 10fps连续采集模式");
         
-        Ok(Self {
+        let manager = Self::with_backend(Box::new(cam_handle), frame_buf_size);
+        
+        // 3. 读取设备实际序列号确认左右槽位，配置的相机未全部找到时释放并报错
+        if let Some(assignment) = assignment {
+            if let Err(e) = manager.set_camera_roles(&assignment.left_serial, &assignment.right_serial) {
+                let _ = manager.cam_handle.camera_release_ffi();
+                return Err(e);
+            }
+        }
+        
+        Ok(manager)
+    }
+    
+    /// 使用指定的底层接口创建（底层已初始化）
+    /// 
+    /// 测试中用于注入模拟超时/断连的桩实现
+    pub fn with_backend(cam_handle: Box<dyn CameraBackend>, frame_buf_size: u32) -> Self {
+        Self {
             cam_handle,
             running: Arc::new(AtomicBool::new(false)),
             frame_buf_size,
             frame_counter: Arc::new(Mutex::new(0)),
-        })
+            recovery_config: Mutex::new(FrameRecoveryConfig::default()),
+            consecutive_failures: AtomicU32::new(0),
+            reconnect_count: AtomicU32::new(0),
+            swap_eyes: AtomicBool::new(false),
+            warmup_config: Mutex::new(CameraWarmupConfig::default()),
+            left_slot: AtomicUsize::new(0),
+            camera_roles: Mutex::new(None),
+            exposure_us: Mutex::new(None),
+        }
     }
     
     /// 启动连续采集
@@ -144,6 +309,9 @@ impl SimpleCameraManager {
         
         // 设置运行状态
         self.running.store(true, Ordering::SeqCst);
+        self.consecutive_failures.store(0, Ordering::SeqCst);
+        self.apply_frame_timeout();
+        self.apply_exposure_time();
         
         println!("✅ SimpleCameraManager::start: 连续采集已启动");
         println!("   - 模式: 10fps硬件帧率控制");
@@ -155,6 +323,8 @@ impl SimpleCameraManager {
     /// 获取当前帧数据（纯内存操作）
     /// 
     /// 从连续采集中获取当前帧数据，不进行任何磁盘操作。
+    /// 单帧等待不超过配置的 frame_timeout_ms；连续失败达到阈值时自动重连，
+    /// 并返回 `CameraError::Reconnecting`。
     /// 
     /// # 返回值
     /// - `Ok((left_data, right_data))`: 成功获取的图像数据
@@ -180,11 +350,11 @@ impl SimpleCameraManager {
         let mut out_sizes = [0u32; 2];
         
         // 调用C层获取图像
-        self.cam_handle.camera_get_frame_ffi(&mut out_bufs, &mut out_sizes)
-            .map_err(|e| {
-                eprintln!("❌ SimpleCameraManager::get_current_frame: 获取帧数据失败: 0x{:x}", e);
-                CameraError::CaptureFailed(e)
-            })?;
+        if let Err(e) = self.cam_handle.camera_get_frame_ffi(&mut out_bufs, &mut out_sizes) {
+            eprintln!("❌ SimpleCameraManager::get_current_frame: 获取帧数据失败: 0x{:x}", e);
+            return Err(self.handle_capture_failure(e));
+        }
+        self.consecutive_failures.store(0, Ordering::SeqCst);
         
         // 调整缓冲区大小到实际数据大小
         left_buffer.truncate(out_sizes[0] as usize);
@@ -193,6 +363,15 @@ impl SimpleCameraManager {
         println!("✅ SimpleCameraManager::get_current_frame: 获取帧数据成功 (Left: {} bytes, Right: {} bytes)", 
                  out_sizes[0], out_sizes[1]);
         
+        // 先按序列号角色还原左右相机，再按逻辑左右眼返回（相机与光机左右对应关系由 swap_eyes 决定）
+        let (left_buffer, right_buffer) = if self.left_slot.load(Ordering::SeqCst) == 1 {
+            (right_buffer, left_buffer)
+        } else {
+            (left_buffer, right_buffer)
+        };
+        if self.swap_eyes.load(Ordering::Relaxed) {
+            return Ok((right_buffer, left_buffer));
+        }
         Ok((left_buffer, right_buffer))
     }
 
@@ -272,8 +451,220 @@ impl SimpleCameraManager {
         self.frame_buf_size
     }
     
+    /// 设置左右相机曝光时间（微秒），采集运行中立即生效
+    /// 
+    /// 设定值会被记录，C层初始化会恢复默认曝光，之后每次启动采集时重新下发
+    pub fn set_exposure_time(&self, exposure_us: f32) -> Result<(), CameraError> {
+        *self.exposure_us.lock().unwrap() = Some(exposure_us);
+        if !self.running.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        self.write_exposure_time(exposure_us)
+    }
+    
+    /// 已设定的曝光时间（微秒），未设定时为 None（使用相机默认曝光）
+    pub fn configured_exposure_time(&self) -> Option<f32> {
+        *self.exposure_us.lock().unwrap()
+    }
+    
+    /// 将曝光时间写入左右相机
+    fn write_exposure_time(&self, exposure_us: f32) -> Result<(), CameraError> {
+        for cam_index in 0..2 {
+            self.cam_handle.camera_set_exposure_time_ffi(cam_index, exposure_us)
+                .map_err(CameraError::ParamFailed)?;
+        }
+        Ok(())
+    }
+    
+    /// 读取当前曝光时间（微秒，以左相机为准）
+    pub fn get_exposure_time(&self) -> Result<f32, CameraError> {
+        self.cam_handle.camera_get_exposure_time_ffi(self.left_slot.load(Ordering::SeqCst) as u32)
+            .map_err(CameraError::ParamFailed)
+    }
+    
+    /// 设置采集超时与自动重连配置，采集运行中立即生效
+    pub fn set_frame_recovery_config(&self, config: FrameRecoveryConfig) -> Result<(), String> {
+        config.validate()?;
+        *self.recovery_config.lock().unwrap() = config;
+        if self.running.load(Ordering::SeqCst) {
+            self.apply_frame_timeout();
+        }
+        println!("⏱️ SimpleCameraManager: 单帧超时 {} ms, 连续失败 {} 次后重连",
+                 config.frame_timeout_ms, config.reconnect_after_failures);
+        Ok(())
+    }
+    
+    pub fn get_frame_recovery_config(&self) -> FrameRecoveryConfig {
+        *self.recovery_config.lock().unwrap()
+    }
+    
+    /// 设置启动预热（丢弃帧数/最短时长），下次 warm_up 生效
+    pub fn set_warmup_config(&self, config: CameraWarmupConfig) -> Result<(), String> {
+        config.validate()?;
+        *self.warmup_config.lock().unwrap() = config;
+        println!("🔥 SimpleCameraManager: 启动预热丢弃 {} 帧, 最短 {} ms",
+                 config.discard_frames, config.min_duration_ms);
+        Ok(())
+    }
+    
+    pub fn get_warmup_config(&self) -> CameraWarmupConfig {
+        *self.warmup_config.lock().unwrap()
+    }
+    
+    /// 启动预热：取出并丢弃前 discard_frames 帧，总时长不少于 min_duration_ms
+    /// 
+    /// 每取一帧（无论成功与否）回调 on_progress(已尝试, 总数)；预热期间取帧失败只告警，
+    /// 仅相机未启动时返回错误。返回实际丢弃的帧数
+    pub fn warm_up(&self, mut on_progress: impl FnMut(u32, u32)) -> Result<u32, CameraError> {
+        let config = self.get_warmup_config();
+        let start = std::time::Instant::now();
+        let mut discarded = 0;
+        
+        for attempt in 1..=config.discard_frames {
+            match self.get_current_frame() {
+                Ok(_) => discarded += 1,
+                Err(CameraError::NotStarted) => return Err(CameraError::NotStarted),
+                Err(e) => eprintln!("⚠️ SimpleCameraManager::warm_up: 预热取帧失败: {}", e),
+            }
+            on_progress(attempt, config.discard_frames);
+        }
+        
+        let min_duration = std::time::Duration::from_millis(config.min_duration_ms as u64);
+        if let Some(remaining) = min_duration.checked_sub(start.elapsed()) {
+            std::thread::sleep(remaining);
+        }
+        
+        if config.discard_frames > 0 || config.min_duration_ms > 0 {
+            println!("🔥 SimpleCameraManager::warm_up: 预热完成，丢弃 {} 帧，耗时 {} ms",
+                     discarded, start.elapsed().as_millis());
+        }
+        Ok(discarded)
+    }
+    
+    /// 设置左右眼互换（物理左光机对应相机1时开启），立即对后续取帧生效
+    pub fn set_swap_eyes(&self, swap: bool) {
+        self.swap_eyes.store(swap, Ordering::Relaxed);
+        println!("🔀 SimpleCameraManager: 左右眼互换 {}", if swap { "开启 (相机1=左眼)" } else { "关闭 (相机0=左眼)" });
+    }
+    
+    pub fn is_eyes_swapped(&self) -> bool {
+        self.swap_eyes.load(Ordering::Relaxed)
+    }
+    
+    /// 按序列号指定左右相机角色
+    /// 
+    /// 读取两个槽位上已打开相机的序列号并确定左相机所在槽位，此后 get_current_frame
+    /// 始终按 (左, 右) 返回，与USB枚举顺序无关；任一序列号未找到时返回 SerialMismatch 且不改变当前分配
+    pub fn set_camera_roles(&self, left_serial: &str, right_serial: &str) -> Result<(), CameraError> {
+        let assignment = CameraAssignment {
+            left_serial: left_serial.trim().to_string(),
+            right_serial: right_serial.trim().to_string(),
+        };
+        assignment.validate().map_err(CameraError::SerialMismatch)?;
+        
+        let left_slot = self.verify_camera_roles(&assignment)?;
+        self.left_slot.store(left_slot, Ordering::SeqCst);
+        println!("🎥 SimpleCameraManager: 左相机 {} (槽位{}), 右相机 {} (槽位{})",
+                 assignment.left_serial, left_slot, assignment.right_serial, 1 - left_slot);
+        *self.camera_roles.lock().unwrap() = Some(assignment);
+        Ok(())
+    }
+    
+    /// 当前按序列号确定的左右相机（未设置时为 None，按C层槽位顺序返回）
+    pub fn camera_roles(&self) -> Option<CameraAssignment> {
+        self.camera_roles.lock().unwrap().clone()
+    }
+    
+    /// 左相机所在槽位（0/1）
+    pub fn left_camera_slot(&self) -> usize {
+        self.left_slot.load(Ordering::SeqCst)
+    }
+    
+    /// 当前连续取帧失败次数
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures.load(Ordering::SeqCst)
+    }
+    
+    /// 累计自动重连次数
+    pub fn reconnect_count(&self) -> u32 {
+        self.reconnect_count.load(Ordering::SeqCst)
+    }
+    
     // ==================== 内部方法 ====================
     
+    /// 读取两个槽位的序列号，确认左右相机均已找到，返回左相机所在槽位
+    fn verify_camera_roles(&self, assignment: &CameraAssignment) -> Result<usize, CameraError> {
+        let serials = (0..2)
+            .map(|slot| self.cam_handle.camera_get_serial_ffi(slot).map_err(CameraError::ParamFailed))
+            .collect::<Result<Vec<_>, _>>()?;
+        let slot_of = |serial: &str| serials.iter().position(|s| s.trim() == serial);
+        
+        match (slot_of(&assignment.left_serial), slot_of(&assignment.right_serial)) {
+            (Some(left), Some(right)) if left != right => Ok(left),
+            (left, right) => Err(CameraError::SerialMismatch(format!(
+                "左相机 {} {}，右相机 {} {}（已打开: {}）",
+                assignment.left_serial, if left.is_some() { "已找到" } else { "未找到" },
+                assignment.right_serial, if right.is_some() { "已找到" } else { "未找到" },
+                serials.join(", ")))),
+        }
+    }
+    
+    /// 将配置的单帧超时下发到C层
+    fn apply_frame_timeout(&self) {
+        let timeout_ms = self.recovery_config.lock().unwrap().frame_timeout_ms;
+        if let Err(e) = self.cam_handle.camera_set_frame_timeout_ffi(timeout_ms) {
+            eprintln!("⚠️ SimpleCameraManager: 设置单帧超时失败: 0x{:x}", e);
+        }
+    }
+    
+    /// 将设定的曝光时间下发到相机（未设定时保持C层默认）
+    fn apply_exposure_time(&self) {
+        let Some(exposure_us) = self.configured_exposure_time() else { return };
+        match self.write_exposure_time(exposure_us) {
+            Ok(()) => println!("   - 曝光时间: {:.0} μs", exposure_us),
+            Err(e) => eprintln!("⚠️ SimpleCameraManager: 设置曝光时间失败: {}", e),
+        }
+    }
+    
+    /// 记录一次取帧失败，达到阈值时执行 release → init → start 重连
+    /// 
+    /// camera_init 会恢复默认曝光，重连后重新下发单帧超时与设定的曝光时间；
+    /// 增益在 camera_init.c 中固定、ROI 尚未由C层支持，重新初始化后与启动时一致
+    fn handle_capture_failure(&self, code: i32) -> CameraError {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::SeqCst) + 1;
+        let threshold = self.recovery_config.lock().unwrap().reconnect_after_failures;
+        if threshold == 0 || failures < threshold {
+            return CameraError::CaptureFailed(code);
+        }
+        
+        self.consecutive_failures.store(0, Ordering::SeqCst);
+        let attempt = self.reconnect_count.fetch_add(1, Ordering::SeqCst) + 1;
+        println!("🔌 SimpleCameraManager: 连续 {} 次取帧失败，尝试重连相机 (第{}次)...", failures, attempt);
+        
+        // 释放失败不影响重连（链路中断时关闭设备本身可能报错）
+        if let Err(e) = self.cam_handle.camera_release_ffi() {
+            eprintln!("⚠️ SimpleCameraManager: 重连前释放资源失败: 0x{:x}", e);
+        }
+        let result = self.cam_handle.camera_reinit_ffi()
+            .and_then(|_| self.cam_handle.camera_start_ffi());
+        match result {
+            Ok(()) => {
+                self.apply_frame_timeout();
+                self.apply_exposure_time();
+                // 重连后重新确认左右槽位
+                if let Some(assignment) = self.camera_roles() {
+                    match self.verify_camera_roles(&assignment) {
+                        Ok(left_slot) => self.left_slot.store(left_slot, Ordering::SeqCst),
+                        Err(e) => eprintln!("⚠️ SimpleCameraManager: 重连后左右相机校验失败: {}", e),
+                    }
+                }
+                println!("✅ SimpleCameraManager: 相机重连成功");
+            }
+            Err(e) => eprintln!("❌ SimpleCameraManager: 相机重连失败: 0x{:x}，将在后续失败后再次尝试", e),
+        }
+        CameraError::Reconnecting { attempt, last_error: code }
+    }
+    
     /// 保存帧数据到磁盘（内部方法）
     fn save_frame_to_disk(&self, left_data: &[u8], right_data: &[u8]) -> Result<(), CameraError> {
         println!("💾 SimpleCameraManager::save_frame_to_disk: 保存帧数据到磁盘");
@@ -291,13 +682,13 @@ impl SimpleCameraManager {
             .unwrap()
             .as_secs();
         
-        // 使用项目根目录下的captures目录
-        let captures_dir = "captures";
+        // 使用数据目录下的captures目录
+        let captures_dir = crate::paths::captures_dir().to_string_lossy().to_string();
         let left_filename = format!("{}/frame_{}_{:06}_L.raw", captures_dir, timestamp, frame_number);
         let right_filename = format!("{}/frame_{}_{:06}_R.raw", captures_dir, timestamp, frame_number);
         
         // 确保目录存在
-        std::fs::create_dir_all(captures_dir)
+        std::fs::create_dir_all(&captures_dir)
             .map_err(|e| CameraError::SaveFailed(format!("Failed to create directory: {}", e)))?;
         
         // 保存文件
@@ -314,6 +705,44 @@ impl SimpleCameraManager {
     }
 }
 
+/// 原始帧分辨率（8位灰度，每像素1字节）
+/// 
+/// 工作流按配置的分辨率解析原始帧，长度不符时报错而不是按字节数猜测尺寸
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameResolution {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Default for FrameResolution {
+    fn default() -> Self {
+        Self::new(2448, 2048)
+    }
+}
+
+impl FrameResolution {
+    pub const fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+
+    /// 单帧字节数
+    pub fn byte_len(&self) -> usize {
+        self.width as usize * self.height as usize
+    }
+
+    /// 校验原始帧长度与分辨率一致
+    pub fn check_frame(&self, data: &[u8]) -> Result<(), String> {
+        if self.width == 0 || self.height == 0 {
+            return Err(format!("帧分辨率无效: {}×{}", self.width, self.height));
+        }
+        if data.len() != self.byte_len() {
+            return Err(format!("图像数据大小与分辨率 {}×{} 不符: 期望 {} 字节，实际 {} 字节",
+                               self.width, self.height, self.byte_len(), data.len()));
+        }
+        Ok(())
+    }
+}
+
 impl Drop for SimpleCameraManager {
     /// 析构函数：确保C层资源正确释放
     fn drop(&mut self) {
@@ -7,7 +7,15 @@ use std::sync::{Arc, Mutex};
 use std::collections::HashMap;
 use base64::{Engine as _, engine::general_purpose};
 
-use crate::modules::alignment_workflow::{AlignmentWorkflow, DetectionStage, DetectionResult};
+use crate::modules::alignment_workflow::{AlignmentWorkflow, DetectionStage, DetectionResult, PreviewTransport, LatencyStats, ExposureScanResult, DetectionRetryConfig, AutomatedCycleVerdict, WorkflowInitState, FastCheckReport, SyntheticDetectionReport, simulate_synthetic_detection, ScrewTurnReport, RepeatabilityReport, AlignmentMeasurement, GoldenComparison, GOLDEN_REFERENCE_FILE, RECTIFY_MAPS_FILE, param_file_path, save_golden_reference, load_golden_reference, DebugOverlayConfig, MeasurementArchiveSummary, MAX_DETECTION_DECIMATION};
+use crate::modules::alignment::{DebugRenderConfig, BlankFrameConfig, PoseConvention, StandoffRange, ConvergenceRange, ConvergenceCheck, SyntheticGridParams, MicrometerCalibration, ErrorHistogram, AcceptanceThresholds, ReevaluationVerdict, CenteringTargets, AlignmentReport, RobustStatsConfig, PnpMethod, MAX_POSE_AVERAGING_FRAMES};
+use crate::config::{AlignmentConfig, CameraConfig, ConfigManager};
+use crate::modules::param_io::{check_calibration_dir_serials, check_rectify_maps_file, RectifyMapsSizeCheck};
+use crate::modules::benchmark::BenchmarkSummary;
+use crate::modules::result_log::AlignmentLogRecord;
+use crate::modules::pose_filter::PoseKalmanConfig;
+use crate::modules::result_metrics::{source_metrics, MetricDescriptor, MetricSource};
+use crate::modules::alignment_circles_detection::{DetectionDiagnostics, DetectionPreprocessing, DetectorConfig, ExclusionRegion};
 
 // ==================== 数据结构定义 ====================
 
@@ -20,6 +28,17 @@ pub struct CameraPreviewData {
     pub width: u32,                    // 图像宽度
     pub height: u32,                   // 图像高度
     pub fps: f32,                      // 当前帧率
+    #[serde(default)]
+    pub stale: bool,                   // 采集暂无新帧，返回的是缓存的上一帧
+    #[serde(default)]
+    pub age_ms: u64,                   // 缓存帧距编码时的时长 (毫秒)，非缓存帧为0
+}
+
+/// 采集帧率信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcquisitionFpsInfo {
+    pub target_fps: f64,               // 目标帧率 (配置值)
+    pub achieved_fps: f64,             // 实际采集帧率 (最近1秒，未运行时为0)
 }
 
 /// 合像检测状态
@@ -29,6 +48,9 @@ pub struct AlignmentStatus {
     pub current_stage: DetectionStage, // 当前检测阶段
     pub workflow_running: bool,        // 工作流是否运行中
     pub last_update: u64,              // 最后更新时间戳
+    pub detector_config: Option<DetectorConfig>, // 当前生效的圆点检测参数
+    #[serde(default)]
+    pub detection_paused: bool,        // 检测是否暂停（预览照常）
 }
 
 /// 单光机偏差显示数据
@@ -74,6 +96,18 @@ impl AlignmentWorkflowState {
             last_result: None,
         }
     }
+    
+    /// 生效的标定参数目录：工作流已创建时取其参数目录，否则为默认数据目录
+    pub fn param_dir(&self) -> std::path::PathBuf {
+        self.workflow.as_ref()
+            .map(|workflow| workflow.param_dir().to_path_buf())
+            .unwrap_or_else(crate::paths::params_dir)
+    }
+}
+
+/// 读取生效的标定参数目录（不持有状态锁）
+pub(crate) fn active_param_dir(state: &Arc<Mutex<AlignmentWorkflowState>>) -> Result<std::path::PathBuf, String> {
+    Ok(state.lock().map_err(|e| format!("状态锁定失败: {}", e))?.param_dir())
 }
 
 // ==================== Tauri 命令实现 ====================
@@ -83,6 +117,7 @@ impl AlignmentWorkflowState {
 pub async fn start_alignment_camera(
     app_handle: AppHandle,
     state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
 ) -> Result<AlignmentStatus, String> {
     println!("🚀 启动合像检测相机...");
     
@@ -94,20 +129,13 @@ pub async fn start_alignment_camera(
             current_stage: DetectionStage::Preview,
             workflow_running: true,
             last_update: chrono::Utc::now().timestamp_millis() as u64,
+            detector_config: workflow_state.workflow.as_ref().and_then(|workflow| workflow.get_detector_config()),
+            detection_paused: workflow_state.workflow.as_ref().map_or(false, |workflow| workflow.is_detection_paused()),
         });
     }
     
-    // 创建工作流实例
-    let mut workflow = AlignmentWorkflow::new(app_handle.clone())
-        .map_err(|e| format!("创建工作流失败: {}", e))?;
-    
-    // 初始化合像检测系统
-    workflow.initialize_alignment_system()
-        .map_err(|e| format!("初始化检测系统失败: {}", e))?;
-    
-    // 启动工作流
-    workflow.start_workflow()
-        .map_err(|e| format!("启动工作流失败: {}", e))?;
+    let workflow = create_started_workflow(&app_handle, &config_manager)?;
+    let detector_config = workflow.get_detector_config();
     
     workflow_state.workflow = Some(workflow);
     workflow_state.is_active = true;
@@ -122,9 +150,160 @@ pub async fn start_alignment_camera(
         current_stage: DetectionStage::Preview,
         workflow_running: true,
         last_update: chrono::Utc::now().timestamp_millis() as u64,
+        detector_config,
+        detection_paused: false,
     })
 }
 
+/// 按当前配置创建并启动合像工作流（标定序列号校验 → 初始化检测系统 → 启动采集/处理线程）
+fn create_started_workflow(
+    app_handle: &AppHandle,
+    config_manager: &Arc<Mutex<ConfigManager>>,
+) -> Result<AlignmentWorkflow, String> {
+    // 相机与标定不匹配时拒绝启动，避免用错误的校正参数做检测
+    let serial_check = {
+        let manager = config_manager.lock().unwrap();
+        let (left_serial, right_serial) = manager.camera_config.get_camera_serials();
+        check_calibration_dir_serials(crate::paths::params_dir(), &left_serial, &right_serial)
+    };
+    if serial_check.is_mismatch() {
+        return Err(serial_check.warning.unwrap_or_else(|| "相机与当前标定不匹配".to_string()));
+    }
+    if let Some(warning) = &serial_check.warning {
+        println!("⚠️ {}", warning);
+    }
+    
+    // 创建工作流实例
+    let mut workflow = AlignmentWorkflow::new(app_handle.clone())
+        .map_err(|e| format!("创建工作流失败: {}", e))?;
+    
+    // 应用配置（检测参数、相机参数）后启动
+    let (alignment_config, camera_config) = {
+        let manager = config_manager.lock().unwrap();
+        (manager.alignment_config.clone(), manager.camera_config.clone())
+    };
+    configure_workflow(&mut workflow, &alignment_config, &camera_config)?;
+    
+    // 应用配置中的PLC输出 (Modbus/TCP)
+    workflow.start_plc_modbus(&alignment_config.plc_modbus)
+        .map_err(|e| format!("启动PLC输出失败: {}", e))?;
+    
+    // 启动工作流
+    workflow.start_workflow()
+        .map_err(|e| format!("启动工作流失败: {}", e))?;
+    
+    Ok(workflow)
+}
+
+/// 将合像配置与相机配置应用到新建的工作流（初始化检测系统，尚未启动采集）
+fn configure_workflow(
+    workflow: &mut AlignmentWorkflow,
+    config: &AlignmentConfig,
+    camera_config: &CameraConfig,
+) -> Result<(), String> {
+    // 应用配置中的采集帧率
+    workflow.set_target_fps(config.acquisition_target_fps)
+        .map_err(|e| format!("设置采集帧率失败: {}", e))?;
+    
+    // 应用配置中的采集超时/自动重连
+    workflow.set_frame_recovery_config(camera_config.frame_recovery)
+        .map_err(|e| format!("设置采集超时失败: {}", e))?;
+    
+    // 应用配置中的相机启动预热
+    workflow.set_camera_warmup_config(camera_config.warmup)
+        .map_err(|e| format!("设置相机预热失败: {}", e))?;
+    
+    // 应用配置中的曝光时间（自动曝光扫描保存的最佳曝光，启动采集时下发）
+    if let Some(exposure_us) = camera_config.configured_exposure_us() {
+        workflow.set_exposure_time(exposure_us)
+            .map_err(|e| format!("设置曝光时间失败: {}", e))?;
+    }
+    
+    // 应用配置中的左右眼分配
+    workflow.set_swap_eyes(camera_config.swap_eyes);
+    
+    // 应用配置中的图像分辨率（ROI启用时为ROI尺寸），原始帧按此解析
+    workflow.set_frame_resolution(camera_config.frame_resolution())
+        .map_err(|e| format!("设置帧分辨率失败: {}", e))?;
+    
+    // 应用配置中的预览缓存帧沿用时间
+    workflow.set_preview_stale_timeout(config.preview_stale_timeout_ms);
+    
+    // 应用配置中的标定板规格（检测系统按此创建）
+    workflow.set_pattern_size(config.pattern_size)
+        .map_err(|e| format!("设置标定板规格失败: {}", e))?;
+    
+    // 初始化合像检测系统
+    workflow.initialize_alignment_system()
+        .map_err(|e| format!("初始化检测系统失败: {}", e))?;
+    
+    // 应用配置中的分位误差分位数
+    workflow.set_error_percentile(config.alignment_thresholds.error_percentile)
+        .map_err(|e| format!("设置分位数失败: {}", e))?;
+    
+    // 应用配置中的姿态/合像判定阈值
+    workflow.set_acceptance_thresholds(config.acceptance_thresholds())
+        .map_err(|e| format!("设置判定阈值失败: {}", e))?;
+    
+    // 应用配置中的居中期望位置
+    workflow.set_centering_targets(config.centering_targets_for(camera_config.active_resolution()))
+        .map_err(|e| format!("设置居中期望位置失败: {}", e))?;
+    
+    // 应用配置中的姿态坐标约定
+    workflow.set_pose_convention(config.pose_convention)
+        .map_err(|e| format!("设置姿态坐标约定失败: {}", e))?;
+    
+    // 应用配置中的姿态多帧平均帧数
+    workflow.set_pose_averaging_frames(config.pose_averaging_frames)
+        .map_err(|e| format!("设置姿态平均帧数失败: {}", e))?;
+    
+    // 应用配置中的工作距离合理范围
+    workflow.set_standoff_range(config.standoff_range)
+        .map_err(|e| format!("设置工作距离范围失败: {}", e))?;
+    
+    // 应用配置中的虚像距离合格范围
+    workflow.set_convergence_range(config.convergence_range)
+        .map_err(|e| format!("设置虚像距离范围失败: {}", e))?;
+    
+    // 应用配置中的姿态重投影校验上限
+    workflow.set_pose_reprojection_max_px(config.pose_reprojection_max_px)
+        .map_err(|e| format!("设置重投影RMS上限失败: {}", e))?;
+    
+    // 应用配置中的 solvePnP 求解方法
+    workflow.set_pnp_method(config.pnp_method)
+        .map_err(|e| format!("设置solvePnP求解方法失败: {}", e))?;
+    
+    // 应用配置中的放大倍率不一致判定上限
+    workflow.set_magnification_mismatch_max(config.magnification_mismatch_max)
+        .map_err(|e| format!("设置放大倍率偏差上限失败: {}", e))?;
+    
+    // 应用配置中的合像稳健统计
+    workflow.set_robust_stats(config.robust_statistics)
+        .map_err(|e| format!("设置稳健统计失败: {}", e))?;
+    
+    // 应用配置中的检测抽帧间隔
+    workflow.set_detection_decimation(config.detection_decimation)
+        .map_err(|e| format!("设置检测抽帧间隔失败: {}", e))?;
+    
+    // 应用配置中的姿态显示滤波
+    workflow.set_pose_kalman_config(config.pose_kalman)
+        .map_err(|e| format!("设置姿态显示滤波失败: {}", e))?;
+    
+    // 应用配置中的检测失败重试
+    workflow.set_detection_retry_config(config.detection_retry)
+        .map_err(|e| format!("设置检测失败重试失败: {}", e))?;
+    
+    // 应用配置中的检测排除区域
+    workflow.set_detection_exclusion_regions(config.detection_exclusion_regions.clone())
+        .map_err(|e| format!("设置检测排除区域失败: {}", e))?;
+    
+    // 应用配置中的连通域检测参数（面积窗口/连通性等）
+    workflow.set_detector_config(&config.circle_detector)
+        .map_err(|e| format!("设置检测参数失败: {}", e))?;
+    
+    Ok(())
+}
+
 /// 关闭相机并结束合像检测
 #[tauri::command]
 pub async fn stop_alignment_camera(
@@ -141,6 +320,8 @@ pub async fn stop_alignment_camera(
             current_stage: DetectionStage::Idle,
             workflow_running: false,
             last_update: chrono::Utc::now().timestamp_millis() as u64,
+            detector_config: None,
+            detection_paused: false,
         });
     }
     
@@ -164,6 +345,8 @@ pub async fn stop_alignment_camera(
         current_stage: DetectionStage::Idle,
         workflow_running: false,
         last_update: chrono::Utc::now().timestamp_millis() as u64,
+        detector_config: None,
+        detection_paused: false,
     })
 }
 
@@ -174,10 +357,10 @@ pub async fn get_alignment_status(
 ) -> Result<AlignmentStatus, String> {
     let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
     
-    let current_stage = if let Some(ref workflow) = workflow_state.workflow {
-        workflow.get_current_stage()
+    let (current_stage, detector_config, detection_paused) = if let Some(ref workflow) = workflow_state.workflow {
+        (workflow.get_current_stage(), workflow.get_detector_config(), workflow.is_detection_paused())
     } else {
-        DetectionStage::Idle
+        (DetectionStage::Idle, None, false)
     };
     
     Ok(AlignmentStatus {
@@ -185,9 +368,71 @@ pub async fn get_alignment_status(
         current_stage,
         workflow_running: workflow_state.is_active,
         last_update: chrono::Utc::now().timestamp_millis() as u64,
+        detector_config,
+        detection_paused,
     })
 }
 
+/// 暂停/恢复检测（两次测量之间保持预览，节省CPU，无需重新初始化工作流）
+#[tauri::command]
+pub async fn set_detection_paused(
+    paused: bool,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<AlignmentStatus, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if !workflow_state.is_active {
+        return Err("相机未启动".to_string());
+    }
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        workflow.set_detection_paused(paused);
+        Ok(AlignmentStatus {
+            is_camera_active: true,
+            current_stage: workflow.get_current_stage(),
+            workflow_running: true,
+            last_update: chrono::Utc::now().timestamp_millis() as u64,
+            detector_config: workflow.get_detector_config(),
+            detection_paused: workflow.is_detection_paused(),
+        })
+    } else {
+        Err("工作流未初始化".to_string())
+    }
+}
+
+/// 获取工作流生命周期状态，供前端启用/禁用按钮
+#[tauri::command]
+pub async fn get_init_state(
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<WorkflowInitState, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    Ok(workflow_state.workflow.as_ref()
+        .map(|workflow| workflow.init_state())
+        .unwrap_or(WorkflowInitState::NotCreated))
+}
+
+/// 校验重映射矩阵尺寸与当前分辨率是否一致
+/// 
+/// 检测系统已加载矩阵时校验内存中的矩阵，否则读取参数目录中的矩阵文件与相机配置分辨率比对
+#[tauri::command]
+pub async fn validate_rectify_maps(
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<RectifyMapsSizeCheck, String> {
+    let param_dir = {
+        let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+        if let Some(check) = workflow_state.workflow.as_ref().and_then(|workflow| workflow.check_rectify_maps_size()) {
+            return Ok(check);
+        }
+        workflow_state.param_dir()
+    };
+    
+    let resolution = config_manager.lock().unwrap().camera_config.active_resolution();
+    check_rectify_maps_file(param_file_path(&param_dir, RECTIFY_MAPS_FILE), resolution)
+        .map_err(|e| format!("读取重映射矩阵失败: {}", e))
+}
+
 /// 获取左右相机实时图像预览
 #[tauri::command]
 pub async fn get_camera_preview(
@@ -241,6 +486,36 @@ pub async fn get_alignment_deviation(
     }
 }
 
+/// 自动化工位单次检测（无界面脚本调用）
+/// 
+/// 相机未启动时按当前配置自动启动，随后取一帧新图同步完成左右姿态、居中、合像检测，
+/// 直接返回完整判定，不依赖预览或事件推送
+#[tauri::command]
+pub async fn run_automated_cycle(
+    app_handle: AppHandle,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<AutomatedCycleVerdict, String> {
+    println!("🤖 自动化工位检测...");
+    
+    let session = {
+        let mut workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+        
+        if !workflow_state.is_active {
+            let workflow = create_started_workflow(&app_handle, &config_manager)?;
+            workflow_state.workflow = Some(workflow);
+            workflow_state.is_active = true;
+            println!("✓ 自动化检测: 相机已启动");
+        }
+        
+        workflow_state.workflow.as_ref().ok_or("工作流未初始化")?.live_session()
+    };
+    
+    // 等待新帧与检测期间不占用工作流状态锁
+    session.run_automated_cycle()
+        .map_err(|e| format!("自动化检测失败: {}", e))
+}
+
 /// 手动触发单次合像检测
 #[tauri::command]
 pub async fn trigger_alignment_detection(
@@ -320,114 +595,1274 @@ pub async fn get_alignment_performance(
     }
 }
 
-// ==================== 辅助函数 ====================
+/// 获取端到端延迟分布（采集 → 结果发送）
+/// 
+/// reset: 读取后清空统计窗口（用于调参前后对比）
+#[tauri::command]
+pub async fn get_alignment_latency(
+    reset: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<LatencyStats, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        let stats = workflow.get_latency_stats();
+        if reset.unwrap_or(false) {
+            workflow.reset_latency_stats();
+        }
+        Ok(stats)
+    } else {
+        Err("工作流未启动".to_string())
+    }
+}
 
-/// 将原始图像数据转换为Base64缩略图
-fn create_thumbnail_base64(image_data: &[u8], width: u32, height: u32, thumbnail_size: u32) -> Result<String, String> {
-    // 这里应该实现图像缩放和Base64编码
-    // 为了简化，暂时返回占位符
-    // TODO: 实现真实的图像处理逻辑
+/// 按新阈值重新判定最近一次结果
+/// 
+/// 使用缓存的最近单点残差与左右眼姿态角重新计算通过/不通过，无需重新测量；
+/// 未指定的阈值沿用当前生效值。仅返回判定，不改变实时检测所用阈值
+#[tauri::command]
+pub async fn reevaluate_last_with_thresholds(
+    rms: Option<f64>,
+    p95: Option<f64>,
+    max: Option<f64>,
+    roll: Option<f64>,
+    pitch_yaw: Option<f64>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<ReevaluationVerdict, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    if let Some(ref workflow) = workflow_state.workflow {
+        let active = workflow.get_acceptance_thresholds()
+            .map_err(|e| format!("获取判定阈值失败: {}", e))?;
+        let thresholds = AcceptanceThresholds {
+            roll_deg: roll.unwrap_or(active.roll_deg),
+            pitch_yaw_deg: pitch_yaw.unwrap_or(active.pitch_yaw_deg),
+            rms_px: rms.unwrap_or(active.rms_px),
+            percentile_px: p95.unwrap_or(active.percentile_px),
+            max_px: max.unwrap_or(active.max_px),
+        };
+        workflow.reevaluate_last_with_thresholds(&thresholds)
+            .map_err(|e| format!("重新判定失败: {}", e))
+    } else {
+        Err("工作流未启动".to_string())
+    }
+}
+
+/// 获取当前姿态/合像判定阈值
+/// 
+/// 合像检测运行中返回检测系统实际使用的阈值，否则返回配置中的阈值（下次启动生效）
+#[tauri::command]
+pub async fn get_alignment_thresholds(
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<AcceptanceThresholds, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    if let Some(ref workflow) = workflow_state.workflow {
+        if let Ok(thresholds) = workflow.get_acceptance_thresholds() {
+            return Ok(thresholds);
+        }
+    }
+    Ok(config_manager.lock().unwrap().alignment_config.acceptance_thresholds())
+}
+
+/// 设置姿态/合像判定阈值（左右眼共用）
+/// 
+/// 未指定的阈值沿用配置中的当前值；运行中立即生效，persist 为 true (默认) 时写入配置文件。
+/// 写入后配置不再使用 legacy 阈值常量
+#[tauri::command]
+pub async fn set_alignment_thresholds(
+    rms: Option<f64>,
+    p95: Option<f64>,
+    max: Option<f64>,
+    roll: Option<f64>,
+    pitch_yaw: Option<f64>,
+    persist: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<AcceptanceThresholds, String> {
+    let active = config_manager.lock().unwrap().alignment_config.acceptance_thresholds();
+    let thresholds = AcceptanceThresholds {
+        roll_deg: roll.unwrap_or(active.roll_deg),
+        pitch_yaw_deg: pitch_yaw.unwrap_or(active.pitch_yaw_deg),
+        rms_px: rms.unwrap_or(active.rms_px),
+        percentile_px: p95.unwrap_or(active.percentile_px),
+        max_px: max.unwrap_or(active.max_px),
+    };
+    thresholds.validate()?;
     
-    let placeholder = format!("data:image/jpeg;base64,/9j/4AAQSkZJRgABAQEAYABgAAD//thumbnail_{}x{}", thumbnail_size, thumbnail_size);
-    Ok(placeholder)
+    apply_alignment_setting(&state, &config_manager, persist,
+        |workflow| workflow.set_acceptance_thresholds(thresholds).map_err(|e| format!("设置判定阈值失败: {}", e)),
+        // 已在上方校验，写入配置不会失败
+        |config| { let _ = config.set_acceptance_thresholds(&thresholds); })?;
+    Ok(thresholds)
 }
 
-/// 将检测结果转换为前端显示格式
-pub fn convert_detection_result_to_display(result: &DetectionResult) -> AlignmentResultDisplay {
-    match result {
-        DetectionResult::LeftEyePose { roll, pitch, yaw, pass, message } => {
-            AlignmentResultDisplay {
-                left_eye: EyeDeviationDisplay {
-                    eye_name: "左眼".to_string(),
-                    pose_status: message.clone(),
-                    pose_pass: *pass,
-                    roll_adjustment: format!("Roll: {:.3}°", -roll),
-                    pitch_adjustment: format!("Pitch: {:.3}°", -pitch),
-                    yaw_adjustment: format!("Yaw: {:.3}°", -yaw),
-                    centering_status: None,
-                    centering_pass: None,
-                    centering_adjustment: None,
-                },
-                right_eye: EyeDeviationDisplay {
-                    eye_name: "右眼".to_string(),
-                    pose_status: "等待左眼检测完成".to_string(),
-                    pose_pass: false,
-                    roll_adjustment: "待检测".to_string(),
-                    pitch_adjustment: "待检测".to_string(),
-                    yaw_adjustment: "待检测".to_string(),
-                    centering_status: None,
-                    centering_pass: None,
-                    centering_adjustment: None,
-                },
-                alignment_status: None,
-                alignment_pass: None,
-                adjustment_hint: None,
-                rms_error: None,
-                processing_time_ms: 0,
-            }
-        },
-        DetectionResult::RightEyePose { roll, pitch, yaw, pass, message } => {
-            AlignmentResultDisplay {
-                left_eye: EyeDeviationDisplay {
-                    eye_name: "左眼".to_string(),
-                    pose_status: "检测完成".to_string(),
-                    pose_pass: true,
-                    roll_adjustment: "已通过".to_string(),
-                    pitch_adjustment: "已通过".to_string(),
-                    yaw_adjustment: "已通过".to_string(),
-                    centering_status: Some("已通过".to_string()),
-                    centering_pass: Some(true),
-                    centering_adjustment: Some("无需调整".to_string()),
-                },
-                right_eye: EyeDeviationDisplay {
-                    eye_name: "右眼".to_string(),
-                    pose_status: message.clone(),
-                    pose_pass: *pass,
-                    roll_adjustment: format!("Roll: {:.3}°", -roll),
-                    pitch_adjustment: format!("Pitch: {:.3}°", -pitch),
-                    yaw_adjustment: format!("Yaw: {:.3}°", -yaw),
-                    centering_status: None,
-                    centering_pass: None,
-                    centering_adjustment: None,
-                },
-                alignment_status: None,
-                alignment_pass: None,
-                adjustment_hint: None,
-                rms_error: None,
-                processing_time_ms: 0,
-            }
+/// 设置左眼居中判定的期望关键点位置（按设备分辨率/光机配置）
+/// 
+/// 期望位置须位于当前采集分辨率内；运行中立即生效，persist 为 true (默认) 时写入配置文件
+#[tauri::command]
+pub async fn set_centering_targets(
+    targets: CenteringTargets,
+    persist: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<CenteringTargets, String> {
+    let (width, height) = config_manager.lock().unwrap().camera_config.active_resolution();
+    targets.validate(opencv::core::Size::new(width, height))?;
+    
+    apply_alignment_setting(&state, &config_manager, persist,
+        |workflow| workflow.set_centering_targets(targets).map_err(|e| format!("设置居中期望位置失败: {}", e)),
+        |config| config.centering_targets = targets)?;
+    Ok(targets)
+}
+
+/// 获取检测结果与检测报告各数值字段的单位与显示名称（按结果阶段标签 / 报告类型分组）
+/// 
+/// alignment-result 事件及 AlignmentReport / FastCheckReport 的 units 只含单位；前端按分组 + key 查此表显示中英文名称与单位符号
+#[tauri::command]
+pub async fn get_result_metric_descriptors() -> Result<std::collections::BTreeMap<MetricSource, Vec<MetricDescriptor>>, String> {
+    Ok(MetricSource::ALL.iter().map(|&source| (source, source_metrics(source))).collect())
+}
+
+/// 获取最近一次合像判定的单点误差直方图
+/// 
+/// 误差集中在低位桶为整体偏移，出现远离主体的长尾为个别角点局部偏差；
+/// bin_width 为桶宽 (像素)，不指定时按最大误差均分为10个桶
+#[tauri::command]
+pub async fn get_alignment_error_histogram(
+    bin_width: Option<f64>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<ErrorHistogram, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        workflow.get_alignment_error_histogram(bin_width)
+            .map_err(|e| format!("获取误差直方图失败: {}", e))
+    } else {
+        Err("工作流未启动".to_string())
+    }
+}
+
+/// 设置采集线程目标帧率
+#[tauri::command]
+pub async fn set_acquisition_target_fps(
+    fps: f64,
+    persist: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<AcquisitionFpsInfo, String> {
+    if !(fps > 0.0 && fps <= crate::config::MAX_ACQUISITION_TARGET_FPS) {
+        return Err(format!("采集帧率必须在0-{}fps范围内: {}", crate::config::MAX_ACQUISITION_TARGET_FPS, fps));
+    }
+
+    let achieved_fps = apply_alignment_setting(&state, &config_manager, persist,
+        |workflow| {
+            workflow.set_target_fps(fps).map_err(|e| format!("设置采集帧率失败: {}", e))?;
+            Ok(workflow.get_achieved_fps())
         },
-        DetectionResult::DualEyeAlignment { mean_dx, mean_dy, rms, p95: _, max_err: _, pass, adjustment_hint } => {
-            AlignmentResultDisplay {
-                left_eye: EyeDeviationDisplay {
-                    eye_name: "左眼".to_string(),
-                    pose_status: "检测完成".to_string(),
-                    pose_pass: true,
-                    roll_adjustment: "已通过".to_string(),
-                    pitch_adjustment: "已通过".to_string(),
-                    yaw_adjustment: "已通过".to_string(),
-                    centering_status: Some("已通过".to_string()),
-                    centering_pass: Some(true),
-                    centering_adjustment: Some("无需调整".to_string()),
-                },
-                right_eye: EyeDeviationDisplay {
-                    eye_name: "右眼".to_string(),
-                    pose_status: "检测完成".to_string(),
-                    pose_pass: true,
-                    roll_adjustment: "已通过".to_string(),
-                    pitch_adjustment: "已通过".to_string(),
-                    yaw_adjustment: "已通过".to_string(),
-                    centering_status: None,
-                    centering_pass: None,
-                    centering_adjustment: None,
-                },
-                alignment_status: Some(if *pass { "✓ 合像检测通过".to_string() } else { "❌ 合像精度不足".to_string() }),
-                alignment_pass: Some(*pass),
-                adjustment_hint: Some(adjustment_hint.clone()),
-                rms_error: Some(*rms),
-                processing_time_ms: 0,
+        |config| config.acquisition_target_fps = fps)?
+        .unwrap_or(0.0);
+
+    Ok(AcquisitionFpsInfo {
+        target_fps: fps,
+        achieved_fps,
+    })
+}
+
+/// 获取采集目标帧率与实际帧率（用于判断相机是否跟得上）
+#[tauri::command]
+pub async fn get_acquisition_fps(
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<AcquisitionFpsInfo, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        Ok(AcquisitionFpsInfo {
+            target_fps: workflow.get_target_fps(),
+            achieved_fps: workflow.get_achieved_fps(),
+        })
+    } else {
+        Ok(AcquisitionFpsInfo {
+            target_fps: config_manager.lock().unwrap().alignment_config.acquisition_target_fps,
+            achieved_fps: 0.0,
+        })
+    }
+}
+
+/// 获取最新帧指定区域的原始分辨率图像（用于局部放大检查圆点眩光/污渍）
+/// 
+/// 仅返回所选区域的Base64 PNG，避免传输整幅2448×2048图像
+#[tauri::command]
+pub async fn get_full_resolution_region(
+    camera_side: String,
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<String, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if !workflow_state.is_active {
+        return Err("相机未启动".to_string());
+    }
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        workflow.get_full_resolution_region(&camera_side, x, y, w, h)
+            .map_err(|e| format!("获取原始分辨率区域失败: {}", e))
+    } else {
+        Err("工作流未初始化".to_string())
+    }
+}
+
+/// 获取最近一次检测中圆点检测器的二值图（Base64 PNG）
+/// 
+/// 检测失败时查看圆点是否在二值化后保留，无需加调试打印重新编译
+#[tauri::command]
+pub async fn get_detection_binary_mask(
+    camera_side: String,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<String, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        workflow.get_last_binary_mask(&camera_side)
+            .map_err(|e| format!("获取检测二值图失败: {}", e))
+    } else {
+        Err("工作流未初始化".to_string())
+    }
+}
+
+/// 获取最近一次检测中指定眼的筛选阶段诊断（各阶段计数、被拒候选及摘要）
+#[tauri::command]
+pub async fn get_detection_diagnostics(
+    camera_side: String,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<DetectionDiagnostics, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        workflow.get_last_detection_diagnostics(&camera_side)
+            .map_err(|e| format!("获取检测诊断失败: {}", e))
+    } else {
+        Err("工作流未初始化".to_string())
+    }
+}
+
+/// 获取最新帧校正后的红/青立体图（Base64 PNG）
+/// 
+/// 左图为红、右图为青，未对齐处出现彩色边缘，可肉眼快速判断合像偏差；
+/// save 为 true 时同时保存到采集目录 anaglyph/ 下
+#[tauri::command]
+pub async fn get_anaglyph_image(
+    save: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<String, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if !workflow_state.is_active {
+        return Err("相机未启动".to_string());
+    }
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        workflow.render_anaglyph(save.unwrap_or(false))
+            .map_err(|e| format!("生成红/青立体图失败: {}", e))
+    } else {
+        Err("工作流未初始化".to_string())
+    }
+}
+
+/// 快速完整检测：最新一帧经流水线并行处理，一次返回双眼姿态与合像结果
+/// 
+/// compare_sequential 为 true 时对同一帧再做一次顺序检测，返回两者耗时对比
+/// 
+/// 状态锁内只快照最新帧与检测设置，等待流水线结果（最长5秒）时不持有状态锁
+#[tauri::command]
+pub async fn fast_full_check(
+    compare_sequential: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<FastCheckReport, String> {
+    let job = {
+        let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+        
+        if !workflow_state.is_active {
+            return Err("相机未启动".to_string());
+        }
+        
+        let workflow = workflow_state.workflow.as_ref().ok_or("工作流未初始化")?;
+        workflow.fast_check_job()
+            .map_err(|e| format!("快速完整检测失败: {}", e))?
+    };
+    
+    job.run(compare_sequential.unwrap_or(false))
+        .map_err(|e| format!("快速完整检测失败: {}", e))
+}
+
+/// 合成圆阵检测（无需相机/标定文件，供前端开发）
+/// 
+/// 按参数生成左右眼合成图像并走完整检测流程，返回图像与检测报告；
+/// 未传参数时使用默认姿态（正对、无偏移、轻微噪声）
+#[tauri::command]
+pub async fn simulate_synthetic_grid_detection(
+    params: Option<SyntheticGridParams>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<SyntheticDetectionReport, String> {
+    let pattern_size = config_manager.lock().unwrap().alignment_config.pattern_size;
+    simulate_synthetic_detection(&params.unwrap_or_default(), pattern_size)
+        .map_err(|e| format!("合成检测失败: {}", e))
+}
+
+/// 设置预览传输方式
+/// 
+/// mode: "base64" (默认，兼容) 或 "raw" (原始像素缓冲 + preview:// 协议)
+#[tauri::command]
+pub async fn set_preview_transport(
+    mode: String,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<String, String> {
+    let transport = match mode.as_str() {
+        "base64" => PreviewTransport::Base64,
+        "raw" => PreviewTransport::RawBuffer,
+        other => return Err(format!("不支持的预览传输方式: {}", other)),
+    };
+
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        workflow.set_preview_transport(transport);
+        Ok(format!("预览传输方式已设置为: {}", mode))
+    } else {
+        Err("工作流未启动".to_string())
+    }
+}
+
+/// 设置合像debug图像绘制样式
+/// 
+/// preset: "default" / "color_blind" / "print" / "overlay"；提供 config 时以 config 为准
+#[tauri::command]
+pub async fn set_debug_render_config(
+    preset: Option<String>,
+    config: Option<DebugRenderConfig>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<DebugRenderConfig, String> {
+    let config = match (config, preset) {
+        (Some(config), _) => config,
+        (None, Some(name)) => DebugRenderConfig::preset(&name)
+            .ok_or_else(|| format!("未知的debug样式预设: {}", name))?,
+        (None, None) => return Err("需要提供 preset 或 config".to_string()),
+    };
+
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        workflow.set_debug_render_config(config);
+        Ok(workflow.get_debug_render_config())
+    } else {
+        Err("工作流未启动".to_string())
+    }
+}
+
+/// 设置实时调试叠加流（二值图 + 圆点标记，事件 alignment-debug-overlay）
+/// 
+/// enabled: 是否启用；interval_ms: 最短发送间隔 (默认500)；scale: 缩放系数 (默认0.25)
+#[tauri::command]
+pub async fn set_debug_overlay_stream(
+    enabled: bool,
+    interval_ms: Option<u64>,
+    scale: Option<f64>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<DebugOverlayConfig, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        let defaults = DebugOverlayConfig::default();
+        let config = DebugOverlayConfig {
+            enabled,
+            interval_ms: interval_ms.unwrap_or(defaults.interval_ms),
+            scale: scale.unwrap_or(defaults.scale),
+        };
+        workflow.set_debug_overlay_config(config).map_err(|e| e.to_string())?;
+        Ok(workflow.get_debug_overlay_config())
+    } else {
+        Err("工作流未启动".to_string())
+    }
+}
+
+/// 设置部分网格补全
+/// 
+/// enabled: 是否启用；max_missing: 最多插值点数 (默认2)；
+/// include_in_pose: 插值点是否参与姿态/合像计算 (默认false)
+#[tauri::command]
+pub async fn set_partial_grid_completion(
+    enabled: bool,
+    max_missing: Option<usize>,
+    include_in_pose: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<String, String> {
+    let max_missing = max_missing.unwrap_or(2);
+    if max_missing > 4 {
+        return Err(format!("最多插值点数过大: {} (上限4)", max_missing));
+    }
+
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        workflow.set_partial_grid_completion(enabled, max_missing, include_in_pose.unwrap_or(false))
+            .map_err(|e| format!("设置部分网格补全失败: {}", e))?;
+        Ok(format!("部分网格补全已{}", if enabled { "启用" } else { "关闭" }))
+    } else {
+        Err("工作流未启动".to_string())
+    }
+}
+
+/// 设置姿态解算是否排除未细化圆点
+/// 
+/// 亚像素细化失败的圆点仅为连通域质心，启用后在细化点不少于一半时不参与 solvePnP
+#[tauri::command]
+pub async fn set_exclude_unrefined_points(
+    exclude: bool,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<String, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        workflow.set_exclude_unrefined_points(exclude)
+            .map_err(|e| format!("设置未细化点排除失败: {}", e))?;
+        Ok(format!("姿态解算排除未细化点已{}", if exclude { "启用" } else { "关闭" }))
+    } else {
+        Err("工作流未启动".to_string())
+    }
+}
+
+/// 设置黏连圆点检查
+/// 
+/// area_ratio: 连通域面积超过本帧中位面积的该倍数视为黏连 (默认1.7)；
+/// split: 是否尝试拆分黏连连通域 (默认true，false 时直接丢弃并提示降低亮度)
+#[tauri::command]
+pub async fn set_merged_blob_filter(
+    area_ratio: Option<f64>,
+    split: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<String, String> {
+    let area_ratio = area_ratio.unwrap_or(1.7);
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        workflow.set_merged_blob_filter(area_ratio, split.unwrap_or(true))
+            .map_err(|e| format!("设置黏连圆点检查失败: {}", e))?;
+        Ok(format!("黏连圆点面积比例已设置为 {:.2}", area_ratio))
+    } else {
+        Err("工作流未启动".to_string())
+    }
+}
+
+/// 设置圆点检测前预处理
+/// 
+/// preprocessing: {"mode":"None"} / {"mode":"Clahe","clip_limit":..,"tile_grid":..} / {"mode":"Gamma","gamma":..}，
+/// 低对比度投影时无需调整曝光即可提高检出率
+#[tauri::command]
+pub async fn set_detection_preprocessing(
+    preprocessing: DetectionPreprocessing,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<String, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        workflow.set_detection_preprocessing(preprocessing)
+            .map_err(|e| format!("设置检测预处理失败: {}", e))?;
+        Ok(format!("检测预处理已设置为 {:?}", preprocessing))
+    } else {
+        Err("工作流未启动".to_string())
+    }
+}
+
+/// 热更新圆点检测参数
+/// 
+/// 更新运行中检测系统内的连通域检测器（面积窗口、连通性、固定阈值、黏连/补全、预处理），
+/// 下一帧生效，无需重启工作流；返回生效后的参数，get_alignment_status 中同步回显。
+/// persist 为 true 时写入配置文件下次启动沿用（默认 false，现场调参不落盘）
+#[tauri::command]
+pub async fn set_detector_config(
+    config: DetectorConfig,
+    persist: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<DetectorConfig, String> {
+    config.validate()?;
+    
+    let persist = persist.unwrap_or(false);
+    if persist {
+        let mut manager = config_manager.lock().unwrap();
+        manager.alignment_config.circle_detector = config;
+        manager.save_to_default_dir()?;
+    }
+    
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        workflow.set_detector_config(&config)
+            .map_err(|e| format!("设置检测参数失败: {}", e))?;
+        workflow.get_detector_config().ok_or_else(|| "合像检测系统未初始化".to_string())
+    } else if persist {
+        // 已写入配置，下次启动工作流时生效
+        Ok(config)
+    } else {
+        Err("工作流未启动".to_string())
+    }
+}
+
+/// 实机检测性能测试（新工位验收）
+/// 
+/// 用当前相机与光路连续采集 iterations 帧，计时完整的 检测→姿态→合像 流程，
+/// 返回平均/P95/最大耗时与10fps判定。需在相机启动后、预览模式下调用
+#[tauri::command]
+pub async fn run_live_benchmark(
+    iterations: Option<usize>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<BenchmarkSummary, String> {
+    let session = {
+        let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+        
+        if !workflow_state.is_active {
+            return Err("相机未启动".to_string());
+        }
+        workflow_state.workflow.as_ref().ok_or("工作流未初始化")?.live_session()
+    };
+    
+    // 逐帧计时期间不占用工作流状态锁，状态查询/停止命令可随时执行
+    session.run_live_benchmark(iterations.unwrap_or(20))
+        .map_err(|e| format!("实机性能测试失败: {}", e))
+}
+
+/// 重复性测试（测量噪声验收）
+/// 
+/// 静止工装上连续触发 k 次检测（每次新帧），返回 Δx/Δy/RMS 的均值与标准差。
+/// 与 run_live_benchmark 不同，关注的是结果离散程度而非耗时；建议在预览模式下调用
+#[tauri::command]
+pub async fn measure_repeatability(
+    k: Option<usize>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<RepeatabilityReport, String> {
+    let session = {
+        let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+        
+        if !workflow_state.is_active {
+            return Err("相机未启动".to_string());
+        }
+        workflow_state.workflow.as_ref().ok_or("工作流未初始化")?.live_session()
+    };
+    
+    // k 次取帧等待期间不占用工作流状态锁
+    session.measure_repeatability(k.unwrap_or(30))
+        .map_err(|e| format!("重复性测试失败: {}", e))
+}
+
+/// 设置上报姿态角/调整量的坐标约定（按工位千分尺方向）
+#[tauri::command]
+pub async fn set_pose_convention(
+    convention: PoseConvention,
+    persist: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<String, String> {
+    convention.validate()?;
+    
+    apply_alignment_setting(&state, &config_manager, persist,
+        |workflow| workflow.set_pose_convention(convention).map_err(|e| format!("设置姿态坐标约定失败: {}", e)),
+        |config| config.pose_convention = convention)?;
+    
+    Ok("姿态坐标约定已更新".to_string())
+}
+
+/// 设置姿态多帧平均帧数（1为单帧）
+/// 
+/// 对最近N帧的 solvePnP 解做旋转平均后上报，结果附带离散程度。
+#[tauri::command]
+pub async fn set_pose_averaging_frames(
+    frames: usize,
+    persist: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<String, String> {
+    if frames == 0 || frames > MAX_POSE_AVERAGING_FRAMES {
+        return Err(format!("姿态平均帧数必须在1-{}范围内", MAX_POSE_AVERAGING_FRAMES));
+    }
+    
+    apply_alignment_setting(&state, &config_manager, persist,
+        |workflow| workflow.set_pose_averaging_frames(frames).map_err(|e| format!("设置姿态平均帧数失败: {}", e)),
+        |config| config.pose_averaging_frames = frames)?;
+    
+    Ok(format!("姿态多帧平均已设为 {} 帧", frames))
+}
+
+/// 设置工作距离合理范围 (mm)
+/// 
+/// 姿态结果上报 |tvec| 作为工作距离，超出范围时告警（通常为世界坐标尺度/单位错误）。
+#[tauri::command]
+pub async fn set_standoff_range(
+    range: StandoffRange,
+    persist: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<String, String> {
+    range.validate()?;
+    
+    apply_alignment_setting(&state, &config_manager, persist,
+        |workflow| workflow.set_standoff_range(range).map_err(|e| format!("设置工作距离范围失败: {}", e)),
+        |config| config.standoff_range = range)?;
+    
+    Ok(format!("工作距离合理范围已设为 [{:.0}, {:.0}] mm", range.min_mm, range.max_mm))
+}
+
+/// 设置双眼虚像距离合格范围 (mm)
+/// 
+/// 会聚检测由左右眼对应圆点视差经 Q 矩阵三角化得到虚像距离，超出范围判定不通过。
+#[tauri::command]
+pub async fn set_convergence_range(
+    range: ConvergenceRange,
+    persist: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<String, String> {
+    range.validate()?;
+    
+    apply_alignment_setting(&state, &config_manager, persist,
+        |workflow| workflow.set_convergence_range(range).map_err(|e| format!("设置虚像距离范围失败: {}", e)),
+        |config| config.convergence_range = range)?;
+    
+    Ok(format!("虚像距离合格范围已设为 [{:.0}, {:.0}] mm", range.min_mm, range.max_mm))
+}
+
+/// 对最新一帧做双眼会聚检测（平均视差、视差离散度、三角化虚像距离）
+#[tauri::command]
+pub async fn check_convergence(
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<ConvergenceCheck, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if !workflow_state.is_active {
+        return Err("相机未启动".to_string());
+    }
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        workflow.check_convergence()
+            .map_err(|e| format!("会聚检测失败: {}", e))
+    } else {
+        Err("工作流未初始化".to_string())
+    }
+}
+
+/// 设置姿态解重投影RMS上限 (像素)
+/// 
+/// solvePnP 后用解出的位姿重投影世界坐标，RMS 超出上限时姿态判定不通过（圆点对应错误）。
+#[tauri::command]
+pub async fn set_pose_reprojection_threshold(
+    max_px: f64,
+    persist: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<String, String> {
+    if !(max_px > 0.0 && max_px.is_finite()) {
+        return Err(format!("重投影RMS上限必须为正数: {}", max_px));
+    }
+    
+    apply_alignment_setting(&state, &config_manager, persist,
+        |workflow| workflow.set_pose_reprojection_max_px(max_px).map_err(|e| format!("设置重投影RMS上限失败: {}", e)),
+        |config| config.pose_reprojection_max_px = max_px)?;
+    
+    Ok(format!("姿态重投影RMS上限已设为 {:.3} px", max_px))
+}
+
+/// 设置 solvePnP 求解方法 ("ippe" / "iterative" / "sqpnp")
+/// 
+/// 所选方法失败或 rvec/tvec 非有限值时自动退回 ITERATIVE，姿态结果 method_used 为实际使用的方法。
+#[tauri::command]
+pub async fn set_pnp_method(
+    method: PnpMethod,
+    persist: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<String, String> {
+    apply_alignment_setting(&state, &config_manager, persist,
+        |workflow| workflow.set_pnp_method(method).map_err(|e| format!("设置solvePnP求解方法失败: {}", e)),
+        |config| config.pnp_method = method)?;
+    
+    Ok(format!("solvePnP 求解方法已设为 {:?}", method))
+}
+
+/// 设置放大倍率不一致判定上限
+/// 
+/// 合像时比较左右网格跨度（外接矩形对角线），右/左跨度比偏离1超过上限（如0.02即2%）
+/// 判定为放大倍率不一致，合像结果 magnification.mismatch 为 true 且不通过。
+#[tauri::command]
+pub async fn set_magnification_mismatch_threshold(
+    max_deviation: f64,
+    persist: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<String, String> {
+    if !(max_deviation > 0.0 && max_deviation < 1.0) {
+        return Err(format!("放大倍率偏差上限必须在(0, 1)范围内: {}", max_deviation));
+    }
+    
+    apply_alignment_setting(&state, &config_manager, persist,
+        |workflow| workflow.set_magnification_mismatch_max(max_deviation).map_err(|e| format!("设置放大倍率偏差上限失败: {}", e)),
+        |config| config.magnification_mismatch_max = max_deviation)?;
+    
+    Ok(format!("放大倍率偏差上限已设为 {:.2}%", max_deviation * 100.0))
+}
+
+/// 设置合像稳健统计（MAD 离群点剔除）
+/// 
+/// 启用后单点误差超过 中位数 + k·MAD 的点判为离群点，合像结果另报剔除后的
+/// robust_rms / robust_p95 与 outlier_indices；use_for_pass 为 true 时判定改用剔除后的统计。
+#[tauri::command]
+pub async fn set_robust_statistics(
+    enabled: bool,
+    mad_k: Option<f64>,
+    use_for_pass: Option<bool>,
+    persist: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<RobustStatsConfig, String> {
+    let current = config_manager.lock().unwrap().alignment_config.robust_statistics;
+    let config = RobustStatsConfig {
+        enabled,
+        mad_k: mad_k.unwrap_or(current.mad_k),
+        use_for_pass: use_for_pass.unwrap_or(current.use_for_pass) && enabled,
+    };
+    config.validate()?;
+    
+    apply_alignment_setting(&state, &config_manager, persist,
+        |workflow| workflow.set_robust_stats(config).map_err(|e| format!("设置稳健统计失败: {}", e)),
+        |alignment_config| alignment_config.robust_statistics = config)?;
+    
+    Ok(config)
+}
+
+/// 设置预览缓存帧最长沿用时间
+/// 
+/// 采集短暂无新帧时 get_camera_preview 返回上一帧（stale = true，age_ms 为缓存时长），
+/// 超过该时间仍无帧才视为无可用帧。0 表示不沿用。
+#[tauri::command]
+pub async fn set_preview_stale_timeout(
+    timeout_ms: u64,
+    persist: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<String, String> {
+    if timeout_ms > crate::config::MAX_PREVIEW_STALE_TIMEOUT_MS {
+        return Err(format!("预览缓存帧沿用时间不能超过{} ms: {}", crate::config::MAX_PREVIEW_STALE_TIMEOUT_MS, timeout_ms));
+    }
+    
+    apply_alignment_setting(&state, &config_manager, persist,
+        |workflow| {
+            workflow.set_preview_stale_timeout(timeout_ms);
+            Ok(())
+        },
+        |config| config.preview_stale_timeout_ms = timeout_ms)?;
+    
+    Ok(format!("预览缓存帧最长沿用 {} ms", timeout_ms))
+}
+
+/// 设置检测抽帧间隔
+/// 
+/// 检测线程每 every_n 个新采集帧处理1帧（1 = 每帧处理），CPU负载高时增大以免积压；
+/// 预览不受影响。实际处理帧率见 get_performance_stats 的 detection.processed_fps。
+#[tauri::command]
+pub async fn set_detection_decimation(
+    every_n: u32,
+    persist: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<String, String> {
+    if every_n == 0 || every_n > MAX_DETECTION_DECIMATION {
+        return Err(format!("检测抽帧间隔必须在1-{}范围内: {}", MAX_DETECTION_DECIMATION, every_n));
+    }
+    
+    apply_alignment_setting(&state, &config_manager, persist,
+        |workflow| workflow.set_detection_decimation(every_n).map_err(|e| format!("设置检测抽帧间隔失败: {}", e)),
+        |config| config.detection_decimation = every_n)?;
+    
+    Ok(format!("检测抽帧间隔已设为每 {} 帧处理1帧", every_n))
+}
+
+/// 设置实时姿态显示卡尔曼滤波
+/// 
+/// 开启后每次单眼检测额外发送 alignment-pose-filtered 事件（原始值与滤波值），
+/// alignment-result 中的姿态及判定仍使用原始值。未指定的噪声参数沿用当前配置；
+#[tauri::command]
+pub async fn set_pose_kalman_filter(
+    enabled: bool,
+    process_noise: Option<f64>,
+    measurement_noise: Option<f64>,
+    persist: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<PoseKalmanConfig, String> {
+    let current = config_manager.lock().unwrap().alignment_config.pose_kalman;
+    let config = PoseKalmanConfig {
+        enabled,
+        process_noise: process_noise.unwrap_or(current.process_noise),
+        measurement_noise: measurement_noise.unwrap_or(current.measurement_noise),
+    };
+    config.validate()?;
+    
+    apply_alignment_setting(&state, &config_manager, persist,
+        |workflow| workflow.set_pose_kalman_config(config).map_err(|e| format!("设置姿态显示滤波失败: {}", e)),
+        |alignment_config| alignment_config.pose_kalman = config)?;
+    
+    Ok(config)
+}
+
+/// 设置工位千分尺换算参数（各轴每圈调整量与方向）
+/// 
+/// persist 为 true (默认) 时写入配置文件，下次启动沿用
+#[tauri::command]
+pub async fn set_micrometer_calibration(
+    calibration: MicrometerCalibration,
+    persist: Option<bool>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<String, String> {
+    calibration.validate()?;
+    
+    let mut manager = config_manager.lock().unwrap();
+    manager.alignment_config.micrometer_calibration = calibration;
+    if persist.unwrap_or(true) {
+        manager.save_to_default_dir()?;
+    }
+    
+    Ok("千分尺换算参数已更新".to_string())
+}
+
+/// 获取最新一帧的千分尺调整指令（螺杆、方向、圈数）
+#[tauri::command]
+pub async fn get_micrometer_turns(
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<ScrewTurnReport, String> {
+    let calibration = config_manager.lock().unwrap().alignment_config.micrometer_calibration.clone();
+    
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if !workflow_state.is_active {
+        return Err("相机未启动".to_string());
+    }
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        workflow.compute_screw_turns(&calibration)
+            .map_err(|e| format!("计算千分尺调整指令失败: {}", e))
+    } else {
+        Err("工作流未初始化".to_string())
+    }
+}
+
+/// 获取完整检测报告（双眼姿态、左眼居中、合像、调整向量及总判定）
+/// 
+/// 由处理线程各阶段最近一次结果组装，不额外检测
+#[tauri::command]
+pub async fn get_alignment_report(
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<AlignmentReport, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if !workflow_state.is_active {
+        return Err("相机未启动".to_string());
+    }
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        workflow.compute_alignment_report()
+            .map_err(|e| format!("生成检测报告失败: {}", e))
+    } else {
+        Err("工作流未初始化".to_string())
+    }
+}
+
+/// 运行中开启/关闭合像结果日志（生产追溯）
+/// 
+/// path 扩展名 .csv / .jsonl 决定格式，未指定时写入采集目录下 alignment_results.csv；
+/// rotate_daily 为 true 时按日期分文件。返回当前日志文件路径（关闭时为 None）
+#[tauri::command]
+pub async fn set_result_logging(
+    enabled: bool,
+    path: Option<String>,
+    rotate_daily: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<Option<String>, String> {
+    let mut workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    let workflow = workflow_state.workflow.as_mut().ok_or("工作流未初始化")?;
+    
+    if enabled {
+        let path = path.map(std::path::PathBuf::from)
+            .unwrap_or_else(|| crate::paths::captures_dir().join("alignment_results.csv"));
+        workflow.enable_result_logging_with_rotation(path, rotate_daily.unwrap_or(false))
+            .map_err(|e| format!("开启结果日志失败: {}", e))?;
+    } else {
+        workflow.disable_result_logging();
+    }
+    Ok(workflow.result_log_path().map(|path| path.to_string_lossy().to_string()))
+}
+
+/// 设置当前被测产品序列号，本台产品结束时的合像结果日志记录带此序列号
+/// 
+/// 更换序列号时上一台产品未写入的结果先按原序列号写入
+#[tauri::command]
+pub async fn set_unit_serial_number(
+    serial: String,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<String, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    let workflow = workflow_state.workflow.as_ref().ok_or("工作流未初始化")?;
+    workflow.set_unit_serial(&serial)
+        .map_err(|e| format!("写入上一台产品结果日志失败: {}", e))?;
+    Ok(format!("产品序列号已设置: {}", serial.trim()))
+}
+
+/// 结束当前产品：写入一条合像结果日志并清空序列号（每台产品只记录一次）
+/// 
+/// 返回写入的记录；本台产品尚无合像结果时返回 None
+#[tauri::command]
+pub async fn finish_unit_measurement(
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<Option<AlignmentLogRecord>, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    let workflow = workflow_state.workflow.as_ref().ok_or("工作流未初始化")?;
+    workflow.finish_unit()
+        .map_err(|e| format!("写入合像结果日志失败: {}", e))
+}
+
+/// 将当前测量保存为黄金基准（写入配置目录，覆盖旧基准）
+#[tauri::command]
+pub async fn save_golden_reference_from_current(
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<AlignmentMeasurement, String> {
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if !workflow_state.is_active {
+        return Err("相机未启动".to_string());
+    }
+    
+    let workflow = workflow_state.workflow.as_ref().ok_or("工作流未初始化")?;
+    let measurement = workflow.measure_current_alignment()
+        .map_err(|e| format!("测量失败: {}", e))?;
+    let path = crate::paths::configs_path(GOLDEN_REFERENCE_FILE);
+    save_golden_reference(&path, &measurement)
+        .map_err(|e| format!("保存黄金基准失败: {}", e))?;
+    println!("🏅 已保存黄金基准: {} (RMS={:.3})", path, measurement.rms);
+    Ok(measurement)
+}
+
+/// 测量当前产品并与黄金基准逐项比较（当前 - 基准）
+#[tauri::command]
+pub async fn compare_with_golden_reference(
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<GoldenComparison, String> {
+    let reference = load_golden_reference(crate::paths::configs_path(GOLDEN_REFERENCE_FILE))
+        .map_err(|e| format!("读取黄金基准失败（请先保存基准）: {}", e))?;
+    
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if !workflow_state.is_active {
+        return Err("相机未启动".to_string());
+    }
+    
+    let workflow = workflow_state.workflow.as_ref().ok_or("工作流未初始化")?;
+    let current = workflow.measure_current_alignment()
+        .map_err(|e| format!("测量失败: {}", e))?;
+    let deltas = current.compare_to(&reference);
+    Ok(GoldenComparison { reference, current, deltas })
+}
+
+/// 导出最新一帧的完整测量存档 (zip)：原始图、校正图、检测叠加图、报告及生效配置
+/// 
+/// path 为目录时在其中生成 measurement_<时间戳>.zip
+#[tauri::command]
+pub async fn export_measurement_archive(
+    path: String,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<MeasurementArchiveSummary, String> {
+    let mut archive_path = std::path::PathBuf::from(&path);
+    if archive_path.is_dir() {
+        archive_path.push(format!("measurement_{}.zip", chrono::Local::now().format("%Y%m%d_%H%M%S")));
+    }
+    let active_config = config_manager.lock().unwrap().to_yaml_string()?;
+    
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if !workflow_state.is_active {
+        return Err("相机未启动".to_string());
+    }
+    
+    let workflow = workflow_state.workflow.as_ref().ok_or("工作流未初始化")?;
+    workflow.export_measurement_archive(&archive_path, &active_config)
+        .map_err(|e| format!("导出测量存档失败: {}", e))
+}
+
+/// 设置检测失败时降低曝光重试
+#[tauri::command]
+pub async fn set_detection_retry_config(
+    config: DetectionRetryConfig,
+    persist: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<String, String> {
+    config.validate()?;
+    
+    apply_alignment_setting(&state, &config_manager, persist,
+        |workflow| workflow.set_detection_retry_config(config).map_err(|e| format!("设置检测失败重试失败: {}", e)),
+        |alignment_config| alignment_config.detection_retry = config)?;
+    
+    Ok("检测失败重试配置已更新".to_string())
+}
+
+/// 设置检测排除区域（屏蔽工装固定反光点）
+#[tauri::command]
+pub async fn set_detection_exclusion_regions(
+    regions: Vec<ExclusionRegion>,
+    persist: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<String, String> {
+    for region in &regions {
+        region.validate()?;
+    }
+    
+    apply_alignment_setting(&state, &config_manager, persist,
+        |workflow| workflow.set_detection_exclusion_regions(regions.clone()).map_err(|e| format!("设置检测排除区域失败: {}", e)),
+        |config| config.detection_exclusion_regions = regions.clone())?;
+    
+    Ok(format!("检测排除区域已更新: {} 个", regions.len()))
+}
+
+/// 自动曝光扫描
+/// 
+/// 在 [min_us, max_us] 内均匀取 steps 个曝光值，每个曝光下采集一帧并按
+/// 圆点数量/对比度/过曝比例评分，返回各曝光评分与最佳曝光。
+/// apply 为 true (默认) 时应用最佳曝光并写入配置文件（之后启动合像/标定时下发），否则恢复原曝光
+#[tauri::command]
+pub async fn auto_exposure_scan(
+    min_us: f64,
+    max_us: f64,
+    steps: u32,
+    apply: Option<bool>,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<ExposureScanResult, String> {
+    let session = {
+        let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+        workflow_state.workflow.as_ref().ok_or("工作流未启动")?.live_session()
+    };
+    // 逐档切换曝光、等待新帧期间不占用工作流状态锁
+    let result = session.auto_exposure_scan(min_us, max_us, steps, apply.unwrap_or(true))
+        .map_err(|e| format!("自动曝光扫描失败: {}", e))?;
+
+    if result.applied {
+        let mut manager = config_manager.lock().unwrap();
+        manager.camera_config.exposure_time = result.best_exposure_us;
+        manager.save_to_default_dir()?;
+    }
+
+    Ok(result)
+}
+
+/// 设置无投影（全黑帧）判定阈值
+/// 
+/// 均值亮度与最大亮度 (0-255) 同时不高于阈值时判定为无投影，返回 NoProjection 结果
+#[tauri::command]
+pub async fn set_blank_frame_config(
+    config: BlankFrameConfig,
+    state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<String, String> {
+    if config.max_mean_intensity < 0.0 || config.max_peak_intensity < 0.0 {
+        return Err("亮度阈值不能为负数".to_string());
+    }
+
+    let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+    
+    if let Some(ref workflow) = workflow_state.workflow {
+        workflow.set_blank_frame_config(config)
+            .map_err(|e| format!("设置无投影检查失败: {}", e))?;
+        Ok("无投影检查阈值已更新".to_string())
+    } else {
+        Err("工作流未启动".to_string())
+    }
+}
+
+// ==================== 辅助函数 ====================
+
+/// 合像参数设置命令的公共流程：工作流运行中先立即应用，成功后再更新配置；
+/// persist 为 true (默认) 时写入配置文件，下次启动沿用
+/// 
+/// 工作流拒绝该设置时配置与配置文件均保持不变；工作流未启动时只更新配置，返回 None
+fn apply_alignment_setting<T>(
+    state: &Mutex<AlignmentWorkflowState>,
+    config_manager: &Mutex<ConfigManager>,
+    persist: Option<bool>,
+    apply: impl FnOnce(&AlignmentWorkflow) -> Result<T, String>,
+    update: impl FnOnce(&mut AlignmentConfig),
+) -> Result<Option<T>, String> {
+    let applied = {
+        let workflow_state = state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+        workflow_state.workflow.as_ref().map(apply).transpose()?
+    };
+    
+    let mut manager = config_manager.lock().unwrap();
+    update(&mut manager.alignment_config);
+    if persist.unwrap_or(true) {
+        manager.save_to_default_dir()?;
+    }
+    Ok(applied)
+}
+
+/// 将原始图像数据转换为Base64缩略图
+fn create_thumbnail_base64(image_data: &[u8], width: u32, height: u32, thumbnail_size: u32) -> Result<String, String> {
+    // 这里应该实现图像缩放和Base64编码
+    // 为了简化，暂时返回占位符
+    // TODO: 实现真实的图像处理逻辑
+    
+    let placeholder = format!("data:image/jpeg;base64,/9j/4AAQSkZJRgABAQEAYABgAAD//thumbnail_{}x{}", thumbnail_size, thumbnail_size);
+    Ok(placeholder)
+}
+
+/// 将检测结果转换为前端显示格式
+pub fn convert_detection_result_to_display(result: &DetectionResult) -> AlignmentResultDisplay {
+    match result {
+        DetectionResult::LeftEyePose { roll, pitch, yaw, pass, message, timings, .. } => {
+            AlignmentResultDisplay {
+                left_eye: EyeDeviationDisplay {
+                    eye_name: "左眼".to_string(),
+                    pose_status: message.clone(),
+                    pose_pass: *pass,
+                    roll_adjustment: format!("Roll: {:.3}°", -roll),
+                    pitch_adjustment: format!("Pitch: {:.3}°", -pitch),
+                    yaw_adjustment: format!("Yaw: {:.3}°", -yaw),
+                    centering_status: None,
+                    centering_pass: None,
+                    centering_adjustment: None,
+                },
+                right_eye: EyeDeviationDisplay {
+                    eye_name: "右眼".to_string(),
+                    pose_status: "等待左眼检测完成".to_string(),
+                    pose_pass: false,
+                    roll_adjustment: "待检测".to_string(),
+                    pitch_adjustment: "待检测".to_string(),
+                    yaw_adjustment: "待检测".to_string(),
+                    centering_status: None,
+                    centering_pass: None,
+                    centering_adjustment: None,
+                },
+                alignment_status: None,
+                alignment_pass: None,
+                adjustment_hint: None,
+                rms_error: None,
+                processing_time_ms: timings.total_ms() as u64,
+            }
+        },
+        DetectionResult::RightEyePose { roll, pitch, yaw, pass, message, timings, .. } => {
+            AlignmentResultDisplay {
+                left_eye: EyeDeviationDisplay {
+                    eye_name: "左眼".to_string(),
+                    pose_status: "检测完成".to_string(),
+                    pose_pass: true,
+                    roll_adjustment: "已通过".to_string(),
+                    pitch_adjustment: "已通过".to_string(),
+                    yaw_adjustment: "已通过".to_string(),
+                    centering_status: Some("已通过".to_string()),
+                    centering_pass: Some(true),
+                    centering_adjustment: Some("无需调整".to_string()),
+                },
+                right_eye: EyeDeviationDisplay {
+                    eye_name: "右眼".to_string(),
+                    pose_status: message.clone(),
+                    pose_pass: *pass,
+                    roll_adjustment: format!("Roll: {:.3}°", -roll),
+                    pitch_adjustment: format!("Pitch: {:.3}°", -pitch),
+                    yaw_adjustment: format!("Yaw: {:.3}°", -yaw),
+                    centering_status: None,
+                    centering_pass: None,
+                    centering_adjustment: None,
+                },
+                alignment_status: None,
+                alignment_pass: None,
+                adjustment_hint: None,
+                rms_error: None,
+                processing_time_ms: timings.total_ms() as u64,
+            }
+        },
+        DetectionResult::DualEyeAlignment { mean_dx, mean_dy, rms, p95: _, max_err: _, pass, adjustment_hint, timings, magnification, .. } => {
+            AlignmentResultDisplay {
+                left_eye: EyeDeviationDisplay {
+                    eye_name: "左眼".to_string(),
+                    pose_status: "检测完成".to_string(),
+                    pose_pass: true,
+                    roll_adjustment: "已通过".to_string(),
+                    pitch_adjustment: "已通过".to_string(),
+                    yaw_adjustment: "已通过".to_string(),
+                    centering_status: Some("已通过".to_string()),
+                    centering_pass: Some(true),
+                    centering_adjustment: Some("无需调整".to_string()),
+                },
+                right_eye: EyeDeviationDisplay {
+                    eye_name: "右眼".to_string(),
+                    pose_status: "检测完成".to_string(),
+                    pose_pass: true,
+                    roll_adjustment: "已通过".to_string(),
+                    pitch_adjustment: "已通过".to_string(),
+                    yaw_adjustment: "已通过".to_string(),
+                    centering_status: None,
+                    centering_pass: None,
+                    centering_adjustment: None,
+                },
+                alignment_status: Some(if *pass {
+                    "✓ 合像检测通过".to_string()
+                } else if magnification.mismatch {
+                    "❌ 放大倍率不一致".to_string()
+                } else {
+                    "❌ 合像精度不足".to_string()
+                }),
+                alignment_pass: Some(*pass),
+                adjustment_hint: Some(adjustment_hint.clone()),
+                rms_error: Some(*rms),
+                processing_time_ms: timings.total_ms() as u64,
             }
         },
+        DetectionResult::NoProjection { left_blank, right_blank, message, .. } => {
+            let eye_status = |blank: bool| if blank {
+                "⚫ 无投影信号".to_string()
+            } else {
+                "投影正常".to_string()
+            };
+            AlignmentResultDisplay {
+                left_eye: EyeDeviationDisplay {
+                    eye_name: "左眼".to_string(),
+                    pose_status: eye_status(*left_blank),
+                    pose_pass: false,
+                    roll_adjustment: "--".to_string(),
+                    pitch_adjustment: "--".to_string(),
+                    yaw_adjustment: "--".to_string(),
+                    centering_status: None,
+                    centering_pass: None,
+                    centering_adjustment: None,
+                },
+                right_eye: EyeDeviationDisplay {
+                    eye_name: "右眼".to_string(),
+                    pose_status: eye_status(*right_blank),
+                    pose_pass: false,
+                    roll_adjustment: "--".to_string(),
+                    pitch_adjustment: "--".to_string(),
+                    yaw_adjustment: "--".to_string(),
+                    centering_status: None,
+                    centering_pass: None,
+                    centering_adjustment: None,
+                },
+                alignment_status: Some(message.clone()),
+                alignment_pass: None,
+                adjustment_hint: Some("未检测到投影图像 — 请检查光机电源与遮光片，非合像问题".to_string()),
+                rms_error: None,
+                processing_time_ms: 0,
+            }
+        }
         DetectionResult::Error { message } => {
             AlignmentResultDisplay {
                 left_eye: EyeDeviationDisplay {
@@ -10,6 +10,7 @@
 //! 3. `get_captured_images()` - 获取已采集图像列表
 //! 4. `delete_captured_image(pair_id)` - 删除指定图像对
 //! 5. `run_calibration_process()` - 执行标定算法
+//!    （或 `start_calibration_job()` 后台执行，`cancel_calibration_job()` 取消）
 //! 6. `get_calibration_status()` - 获取标定状态
 //! 7. `get_preview_frame()` - 获取实时预览帧
 //! 
@@ -26,48 +27,108 @@
 //! @version 2.1 - 架构优化版本
 //! @date 2025-01-15
 
-use tauri::State;
-use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, State};
+use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
 use crate::modules::calibration_workflow::{
     CalibrationWorkflow, 
     CalibrationStatus, 
     CalibrationResult, 
+    CalibrationRunControl,
+    CalibrationProgress,
+    CalibrationJobOutcome,
+    CalibrationStage,
+    CALIBRATION_CANCELLED,
     ImagePair,
-    PreviewFrame
+    PreviewFrame,
+    IncrementalCalibProgress,
+    BoardRoiSuggestion,
+    DEFAULT_BOARD_ROI_PADDING,
 };
+use crate::config::ConfigManager;
+use crate::modules::calibration_circles::GridDetectionBudget;
+use crate::modules::param_io::{RectifyCoverageReport, check_rectify_coverage};
+use crate::modules::alignment::ModuleConsistencyReport;
+use crate::modules::alignment_workflow::{check_module_consistency, param_file_path, WarmupProgress};
+use crate::commands::alignment_commands::{AlignmentWorkflowState, active_param_dir};
 
 /// 标定工作流程管理器状态
 pub type CalibrationWorkflowState = Arc<Mutex<Option<CalibrationWorkflow>>>;
 
+/// 正在运行的后台标定任务（取消标志独立于工作流锁，计算期间也可取消）
+struct RunningCalibrationJob {
+    job_id: u64,
+    cancel: Arc<AtomicBool>,
+}
+
+/// 后台标定任务登记：同一时间最多一个任务
+#[derive(Default)]
+pub struct CalibrationJobs {
+    running: Option<RunningCalibrationJob>,
+    last_job_id: u64,
+}
+
+/// 后台标定任务状态
+pub type CalibrationJobState = Arc<Mutex<CalibrationJobs>>;
+
 /// 开始标定会话
 /// 
-/// 启动相机并开始标定图像采集会话
+/// 启动相机并开始标定图像采集会话；预热期间释放工作流锁，
+/// `get_calibration_status` 可查询到 WarmingUp，进度通过 calibration-warmup 事件推送
 /// 
 /// # 返回值
 /// - `Ok(session_id)`: 成功启动，返回会话ID
 /// - `Err(String)`: 启动失败的错误信息
 #[tauri::command]
 pub async fn start_calibration_session(
-    state: State<'_, CalibrationWorkflowState>
+    app: AppHandle,
+    state: State<'_, CalibrationWorkflowState>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
 ) -> Result<String, String> {
     println!("🎬 Tauri命令: start_calibration_session");
     
-    let mut workflow_guard = state.lock()
-        .map_err(|e| format!("获取工作流程状态失败: {}", e))?;
+    let (swap_eyes, camera_warmup, frame_resolution, exposure_us) = {
+        let manager = config_manager.lock().unwrap();
+        (manager.camera_config.swap_eyes, manager.camera_config.warmup, manager.camera_config.frame_resolution(),
+         manager.camera_config.configured_exposure_us())
+    };
     
-    // 如果没有实例，创建新实例
-    if workflow_guard.is_none() {
-        let workflow = CalibrationWorkflow::new()?;
-        *workflow_guard = Some(workflow);
-    }
+    let warmup = {
+        let mut workflow_guard = state.lock()
+            .map_err(|e| format!("获取工作流程状态失败: {}", e))?;
+        
+        // 如果没有实例，创建新实例
+        if workflow_guard.is_none() {
+            let workflow = CalibrationWorkflow::new()?;
+            *workflow_guard = Some(workflow);
+        }
+        
+        // 启动标定会话（左右眼分配与合像检测保持一致）
+        let workflow = workflow_guard.as_mut().ok_or("无法创建标定工作流程")?;
+        workflow.set_swap_eyes(swap_eyes);
+        workflow.set_camera_warmup_config(camera_warmup)?;
+        if let Some(exposure_us) = exposure_us {
+            workflow.set_exposure_time(exposure_us)?;
+        }
+        workflow.set_frame_resolution(frame_resolution)?;
+        workflow.begin_session()?
+    };
     
-    // 启动标定会话
-    if let Some(workflow) = workflow_guard.as_mut() {
-        workflow.start_calibration()?;
-        Ok("calibration_session_started".to_string())
-    } else {
-        Err("无法创建标定工作流程".to_string())
-    }
+    // 丢弃预热帧时不占用工作流锁
+    let result = warmup.run(&mut |discarded, total| {
+        let _ = app.emit("calibration-warmup", WarmupProgress { discarded, total, done: false, error: None });
+    });
+    let _ = app.emit("calibration-warmup", WarmupProgress {
+        discarded: *result.as_ref().unwrap_or(&0),
+        total: *result.as_ref().unwrap_or(&0),
+        done: true,
+        error: result.as_ref().err().cloned(),
+    });
+    
+    let mut workflow_guard = state.lock()
+        .map_err(|e| format!("获取工作流程状态失败: {}", e))?;
+    let workflow = workflow_guard.as_mut().ok_or("标定工作流程已释放")?;
+    workflow.finish_session_start(warmup, result)?;
+    Ok("calibration_session_started".to_string())
 }
 
 /// 保存当前帧为标定图像
@@ -152,20 +213,132 @@ pub async fn delete_captured_image(
 /// - `Err(String)`: 标定失败的错误信息
 #[tauri::command]
 pub async fn run_calibration_process(
-    state: State<'_, CalibrationWorkflowState>
+    state: State<'_, CalibrationWorkflowState>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
 ) -> Result<CalibrationResult, String> {
     println!("🚀 Tauri命令: run_calibration_process");
     
+    let (left_serial, right_serial) = config_manager.lock().unwrap().camera_config.get_camera_serials();
+    
     let mut workflow_guard = state.lock()
         .map_err(|e| format!("获取工作流程状态失败: {}", e))?;
     
     if let Some(workflow) = workflow_guard.as_mut() {
+        // 标定参数与相机绑定，记录序列号以便后续校验
+        workflow.set_camera_serials(left_serial, right_serial);
         workflow.run_calibration()
     } else {
         Err("标定会话未启动".to_string())
     }
 }
 
+/// 后台执行标定算法
+/// 
+/// 立即返回任务ID，计算在工作线程中进行：每个阶段开始时发送 `calibration-progress`
+/// (CalibrationProgress)，结束后发送 `calibration-finished` (CalibrationJobOutcome)。
+/// 工作流锁只在开始与结束时短暂持有，计算期间其他标定命令照常响应（状态为 Calibrating）；
+/// 可用 `cancel_calibration_job` 在阶段之间取消
+/// 
+/// # 返回值
+/// - `Ok(job_id)`: 任务已启动
+/// - `Err(String)`: 会话未启动、状态不允许或已有标定任务在运行
+#[tauri::command]
+pub async fn start_calibration_job(
+    app: AppHandle,
+    state: State<'_, CalibrationWorkflowState>,
+    jobs: State<'_, CalibrationJobState>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<u64, String> {
+    println!("🚀 Tauri命令: start_calibration_job");
+    
+    let (left_serial, right_serial) = config_manager.lock().unwrap().camera_config.get_camera_serials();
+    
+    let mut jobs_guard = jobs.lock()
+        .map_err(|e| format!("获取标定任务状态失败: {}", e))?;
+    if let Some(running) = jobs_guard.running.as_ref() {
+        return Err(format!("标定任务 #{} 正在运行", running.job_id));
+    }
+    
+    // 锁内只取输入快照并进入 Calibrating，计算时不占用工作流锁
+    let run = {
+        let mut workflow_guard = state.lock()
+            .map_err(|e| format!("获取工作流程状态失败: {}", e))?;
+        let workflow = workflow_guard.as_mut().ok_or("标定会话未启动")?;
+        workflow.set_camera_serials(left_serial, right_serial);
+        workflow.begin_calibration()?
+    };
+    
+    jobs_guard.last_job_id += 1;
+    let job_id = jobs_guard.last_job_id;
+    let cancel = Arc::new(AtomicBool::new(false));
+    jobs_guard.running = Some(RunningCalibrationJob { job_id, cancel: cancel.clone() });
+    drop(jobs_guard);
+    
+    let state = state.inner().clone();
+    let jobs = jobs.inner().clone();
+    std::thread::spawn(move || {
+        let progress_app = app.clone();
+        let control = CalibrationRunControl::new(cancel.clone(), move |stage| {
+            println!("📶 标定任务 #{} 阶段 {}/{}: {}", job_id, stage.step(), CalibrationStage::ALL.len(), stage.label());
+            if let Err(e) = progress_app.emit("calibration-progress", CalibrationProgress::new(job_id, stage)) {
+                println!("⚠️ 推送标定进度事件失败: {}", e);
+            }
+        });
+        
+        let result = run.run(&control);
+        
+        // 重新加锁只为写回状态（计算期间会话已停止时忽略）
+        match state.lock() {
+            Ok(mut workflow_guard) => {
+                if let Some(workflow) = workflow_guard.as_mut() {
+                    workflow.finish_calibration(&result, control.is_cancelled());
+                }
+            }
+            Err(e) => println!("⚠️ 写回标定状态失败: {}", e),
+        }
+        
+        let cancelled = cancel.load(Ordering::SeqCst) && matches!(&result, Err(e) if e == CALIBRATION_CANCELLED);
+        let outcome = match result {
+            Ok(result) => CalibrationJobOutcome { job_id, cancelled, result: Some(result), error: None },
+            Err(e) => CalibrationJobOutcome { job_id, cancelled, result: None, error: Some(e) },
+        };
+        if let Ok(mut jobs) = jobs.lock() {
+            jobs.running = None;
+        }
+        println!("🏁 标定任务 #{} 结束: {}", job_id,
+                 if outcome.cancelled { "已取消" } else if outcome.result.is_some() { "完成" } else { "失败" });
+        if let Err(e) = app.emit("calibration-finished", &outcome) {
+            println!("⚠️ 推送标定结果事件失败: {}", e);
+        }
+    });
+    
+    Ok(job_id)
+}
+
+/// 取消后台标定任务
+/// 
+/// 当前阶段完成后停止（已进入保存阶段时不再取消），标定状态恢复为 ReadyToCalibrate
+/// 
+/// # 返回值
+/// - `Ok(true)`: 已请求取消
+/// - `Ok(false)`: 没有运行中的任务或 job_id 不匹配
+#[tauri::command]
+pub async fn cancel_calibration_job(
+    job_id: Option<u64>,
+    jobs: State<'_, CalibrationJobState>,
+) -> Result<bool, String> {
+    println!("⏹️ Tauri命令: cancel_calibration_job({:?})", job_id);
+    
+    let jobs = jobs.lock().map_err(|e| format!("获取标定任务状态失败: {}", e))?;
+    match jobs.running.as_ref() {
+        Some(running) if job_id.map_or(true, |id| id == running.job_id) => {
+            running.cancel.store(true, Ordering::SeqCst);
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
 /// 获取当前标定状态
 /// 
 /// 返回标定工作流程的当前状态
@@ -253,18 +426,32 @@ pub async fn reset_calibration_workflow(
 /// - `Err(String)`: 获取失败的错误信息
 #[tauri::command]
 pub async fn get_calibration_config(
-    _state: State<'_, CalibrationWorkflowState>
+    state: State<'_, CalibrationWorkflowState>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
 ) -> Result<String, String> {
     println!("⚙️ Tauri命令: get_calibration_config");
     
-    // 返回默认配置信息
+    // 图像数量要求取自当前工作流程，尚未创建时使用默认配置
+    let (target_image_count, min_valid_pairs) = {
+        let workflow_guard = state.lock()
+            .map_err(|e| format!("获取工作流程状态失败: {}", e))?;
+        let config = workflow_guard.as_ref()
+            .map(|workflow| workflow.calibration_config().clone())
+            .unwrap_or_default();
+        (config.target_image_count, config.min_valid_pairs)
+    };
+    
+    // 图像分辨率取自相机配置（与帧解析使用的分辨率一致）
+    let resolution = config_manager.lock().unwrap().camera_config.frame_resolution();
+    
     let config_info = serde_json::json!({
         "circle_diameter": 15.0,
         "center_distance": 25.0,
         "pattern_size": {"width": 10, "height": 4},
         "error_threshold": 2.0,
-        "target_image_count": 10,
-        "image_resolution": {"width": 2448, "height": 2048}
+        "target_image_count": target_image_count,
+        "min_valid_pairs": min_valid_pairs,
+        "image_resolution": {"width": resolution.width, "height": resolution.height}
     });
     
     Ok(config_info.to_string())
@@ -283,6 +470,7 @@ pub async fn get_calibration_config(
 /// 
 /// # 参数
 /// - `should_save`: 是否同时保存当前帧为标定图像
+/// - `annotate`: 是否在预览缩略图上叠加检测到的圆点网格（默认否）
 /// 
 /// # 返回值
 /// - `Ok(PreviewFrame)`: 包含左右相机Base64图像的预览帧
@@ -290,10 +478,14 @@ pub async fn get_calibration_config(
 #[tauri::command]
 pub async fn get_preview_frame(
     should_save: Option<bool>,
-    state: State<'_, CalibrationWorkflowState>
+    annotate: Option<bool>,
+    app: AppHandle,
+    state: State<'_, CalibrationWorkflowState>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
 ) -> Result<PreviewFrame, String> {
     let should_save = should_save.unwrap_or(false);
-    println!("🎥 Tauri命令: get_preview_frame(should_save={})", should_save);
+    let annotate = annotate.unwrap_or(false);
+    println!("🎥 Tauri命令: get_preview_frame(should_save={}, annotate={})", should_save, annotate);
     
     // 修复Send问题：分离锁的获取和异步调用
     let frame_result = {
@@ -306,7 +498,8 @@ pub async fn get_preview_frame(
             None => {
                 // 创建临时工作流实例用于预览
                 println!("💡 创建临时工作流实例用于预览");
-                let temp_workflow = CalibrationWorkflow::new()?;
+                let mut temp_workflow = CalibrationWorkflow::new()?;
+                temp_workflow.set_frame_resolution(config_manager.lock().unwrap().camera_config.frame_resolution())?;
                 *workflow_guard = Some(temp_workflow);
                 workflow_guard.as_mut().unwrap()
             }
@@ -321,7 +514,16 @@ pub async fn get_preview_frame(
         }
         
         // 同步获取预览帧（传入should_save参数）
-        workflow.get_preview_frame_sync(should_save)
+        let frame = workflow.get_preview_frame_sync(should_save, annotate);
+        
+        // 保存后推送增量标定的运行RMS
+        if let Some(progress) = workflow.take_pending_incremental_progress() {
+            if let Err(e) = app.emit("calibration-running-rms", &progress) {
+                println!("⚠️ 推送运行RMS事件失败: {}", e);
+            }
+        }
+        
+        frame
     };
     
     // 处理结果
@@ -363,4 +565,308 @@ pub async fn get_latest_captured_image(
     } else {
         Ok(None)
     }
-} 
\ No newline at end of file
+} 
+
+/// 获取增量标定的运行RMS历史
+/// 
+/// 每接受一张有效标定板记录一次，用于前端绘制RMS收敛曲线
+/// 
+/// # 返回值
+/// - `Ok(Vec<IncrementalCalibProgress>)`: 按采集顺序的进度记录（无会话时为空）
+#[tauri::command]
+pub async fn get_incremental_calibration_history(
+    state: State<'_, CalibrationWorkflowState>
+) -> Result<Vec<IncrementalCalibProgress>, String> {
+    println!("📈 Tauri命令: get_incremental_calibration_history");
+    
+    let workflow_guard = state.lock()
+        .map_err(|e| format!("获取工作流程状态失败: {}", e))?;
+    
+    Ok(workflow_guard.as_ref()
+        .map(|workflow| workflow.get_incremental_history())
+        .unwrap_or_default())
+}
+
+/// 设置重复位姿判定策略
+/// 
+/// 采集时将新标定板与已采集标定板的位姿（质心/尺度/方向/倾斜）比较，
+/// 相似度达到阈值时提示移动标定板；`reject=true` 时直接丢弃该图像对
+/// 
+/// # 参数
+/// - `similarity_threshold`: 相似度阈值 (0-1，默认0.8，越大越宽松)
+/// - `reject`: 是否丢弃重复位姿（默认false，仅提示操作员）
+#[tauri::command]
+pub async fn set_duplicate_pose_policy(
+    similarity_threshold: f64,
+    reject: Option<bool>,
+    state: State<'_, CalibrationWorkflowState>
+) -> Result<(), String> {
+    println!("⚙️ Tauri命令: set_duplicate_pose_policy({:.2})", similarity_threshold);
+    
+    if !(0.0..=1.0).contains(&similarity_threshold) {
+        return Err(format!("相似度阈值超出范围 [0, 1]: {}", similarity_threshold));
+    }
+    
+    let mut workflow_guard = state.lock()
+        .map_err(|e| format!("获取工作流程状态失败: {}", e))?;
+    
+    match workflow_guard.as_mut() {
+        Some(workflow) => {
+            workflow.set_duplicate_pose_policy(similarity_threshold, reject.unwrap_or(false));
+            Ok(())
+        }
+        None => Err("标定会话未启动".to_string()),
+    }
+} 
+
+/// 设置目标图像数量与开始标定所需的最少有效图像对数量
+/// 
+/// 可在开始标定会话前调用（无实例时先创建工作流程），`start_calibration_session` 保留该配置；
+/// 设置成功后发送 `calibration-image-counts` 事件，前端据此刷新目标数量
+#[tauri::command]
+pub async fn set_calibration_image_counts(
+    target_image_count: u32,
+    min_valid_pairs: usize,
+    app: AppHandle,
+    state: State<'_, CalibrationWorkflowState>
+) -> Result<(), String> {
+    println!("⚙️ Tauri命令: set_calibration_image_counts({}, {})", target_image_count, min_valid_pairs);
+    
+    let mut workflow_guard = state.lock()
+        .map_err(|e| format!("获取工作流程状态失败: {}", e))?;
+    
+    // 如果没有实例，创建新实例
+    if workflow_guard.is_none() {
+        let workflow = CalibrationWorkflow::new()?;
+        *workflow_guard = Some(workflow);
+    }
+    
+    match workflow_guard.as_mut() {
+        Some(workflow) => workflow.set_image_count_requirements(target_image_count, min_valid_pairs)?,
+        None => return Err("标定工作流程未初始化".to_string()),
+    }
+    
+    let counts = serde_json::json!({
+        "target_image_count": target_image_count,
+        "min_valid_pairs": min_valid_pairs,
+    });
+    if let Err(e) = app.emit("calibration-image-counts", counts) {
+        println!("⚠️ 发送图像数量配置事件失败: {}", e);
+    }
+    Ok(())
+}
+
+/// 设置最多保留的标定图像对数量
+/// 
+/// 达到上限后优先淘汰最早的未检测到标定板的图像对，全部有效时拒绝继续采集
+#[tauri::command]
+pub async fn set_max_retained_calibration_pairs(
+    max_pairs: usize,
+    state: State<'_, CalibrationWorkflowState>
+) -> Result<(), String> {
+    println!("⚙️ Tauri命令: set_max_retained_calibration_pairs({})", max_pairs);
+    
+    let mut workflow_guard = state.lock()
+        .map_err(|e| format!("获取工作流程状态失败: {}", e))?;
+    
+    match workflow_guard.as_mut() {
+        Some(workflow) => workflow.set_max_retained_pairs(max_pairs),
+        None => Err("标定会话未启动".to_string()),
+    }
+}
+
+/// 设置是否保存检测圆心旁路文件
+/// 
+/// 启用后每个有效图像对都会在图像目录下生成 `calib_corners_XX.json`，
+/// 标定时若所有图像都有旁路文件则直接复用，跳过圆心检测
+#[tauri::command]
+pub async fn set_corner_sidecar_saving(
+    enabled: bool,
+    state: State<'_, CalibrationWorkflowState>
+) -> Result<(), String> {
+    println!("⚙️ Tauri命令: set_corner_sidecar_saving({})", enabled);
+    
+    let mut workflow_guard = state.lock()
+        .map_err(|e| format!("获取工作流程状态失败: {}", e))?;
+    
+    match workflow_guard.as_mut() {
+        Some(workflow) => {
+            workflow.set_corner_sidecar_saving(enabled);
+            Ok(())
+        }
+        None => Err("标定会话未启动".to_string()),
+    }
+}
+
+/// 设置圆点网格检测预算
+/// 
+/// 按原有放宽顺序最多尝试 max_attempts 次 (1-4)，总耗时超过 time_budget_ms (0 为不限时)
+/// 后放弃剩余尝试，避免无标定板的帧长时间阻塞预览
+#[tauri::command]
+pub async fn set_grid_detection_budget(
+    max_attempts: usize,
+    time_budget_ms: u64,
+    state: State<'_, CalibrationWorkflowState>
+) -> Result<(), String> {
+    println!("⚙️ Tauri命令: set_grid_detection_budget({}, {})", max_attempts, time_budget_ms);
+    
+    let mut workflow_guard = state.lock()
+        .map_err(|e| format!("获取工作流程状态失败: {}", e))?;
+    
+    match workflow_guard.as_mut() {
+        Some(workflow) => workflow.set_detection_budget(GridDetectionBudget { max_attempts, time_budget_ms }),
+        None => Err("标定会话未启动".to_string()),
+    }
+}
+
+/// 从圆心旁路文件目录重新标定（离线，不做圆心检测）
+/// 
+/// # 参数
+/// - `directory`: 旁路文件所在目录（默认数据目录下的 captures）
+#[tauri::command]
+pub async fn recalibrate_from_corner_sidecars(
+    directory: Option<String>,
+    state: State<'_, CalibrationWorkflowState>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<CalibrationResult, String> {
+    let directory = directory.unwrap_or_else(|| crate::paths::captures_dir().to_string_lossy().to_string());
+    println!("📄 Tauri命令: recalibrate_from_corner_sidecars({})", directory);
+    
+    let (left_serial, right_serial) = config_manager.lock().unwrap().camera_config.get_camera_serials();
+    
+    let mut workflow_guard = state.lock()
+        .map_err(|e| format!("获取工作流程状态失败: {}", e))?;
+    
+    match workflow_guard.as_mut() {
+        Some(workflow) => {
+            workflow.set_camera_serials(left_serial, right_serial);
+            workflow.run_calibration_from_sidecars(&directory)
+        }
+        None => Err("标定会话未启动".to_string()),
+    }
+}
+
+/// 导出标定点对应关系 (世界点, 左图点, 右图点)，供外部光束法平差使用
+/// 
+/// 数据来自本次标定会话采集时缓存的检测结果，需在停止会话前导出
+/// 
+/// # 参数
+/// - `output_path`: 输出JSON路径（默认会话保存目录下的 calib_correspondences.json）
+/// 
+/// # 返回
+/// 输出文件路径
+#[tauri::command]
+pub async fn export_point_correspondences(
+    output_path: Option<String>,
+    state: State<'_, CalibrationWorkflowState>,
+) -> Result<String, String> {
+    println!("📤 Tauri命令: export_point_correspondences");
+    
+    let workflow_guard = state.lock()
+        .map_err(|e| format!("获取工作流程状态失败: {}", e))?;
+    
+    match workflow_guard.as_ref() {
+        Some(workflow) => workflow.export_point_correspondences(output_path.as_deref())
+            .map(|(path, _)| path),
+        None => Err("标定会话未启动".to_string()),
+    }
+}
+
+/// 导出 ROS camera_info YAML（左右相机各一份），供基于ROS的下游系统使用
+/// 
+/// 读取标定参数目录中当前生效的相机参数与立体校正参数，无需启动标定会话
+/// 
+/// # 参数
+/// - `output_dir`: 输出目录（默认标定参数目录）
+/// - `image_width` / `image_height`: 标定分辨率（默认相机配置的当前分辨率）
+/// 
+/// # 返回
+/// [左相机文件路径, 右相机文件路径]
+#[tauri::command]
+pub async fn export_ros_camera_info(
+    output_dir: Option<String>,
+    image_width: Option<i32>,
+    image_height: Option<i32>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+    alignment_state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<Vec<String>, String> {
+    let param_dir = active_param_dir(&alignment_state)?;
+    let (width, height) = config_manager.lock().unwrap().camera_config.active_resolution();
+    let image_size = (image_width.unwrap_or(width), image_height.unwrap_or(height));
+    let output_dir = output_dir.unwrap_or_else(|| param_dir.to_string_lossy().to_string());
+    println!("📤 Tauri命令: export_ros_camera_info({}, {}x{})", output_dir, image_size.0, image_size.1);
+    
+    let (left_path, right_path) = crate::modules::param_io::export_ros_camera_info(
+        &param_dir, &output_dir, image_size)
+        .map_err(|e| format!("导出ROS camera_info失败: {}", e))?;
+    println!("✓ ROS camera_info 已导出: {}, {}", left_path, right_path);
+    Ok(vec![left_path, right_path])
+}
+
+/// 校正有效区域覆盖率：左右图有效区域及其交集占整幅图像的比例
+/// 
+/// 有效区域过小说明标定质量或校正 alpha 有问题。
+/// min_fraction 默认取系统配置 rectify_min_coverage
+#[tauri::command]
+pub async fn get_rectify_roi_coverage(
+    min_fraction: Option<f64>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+    alignment_state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<RectifyCoverageReport, String> {
+    let min_fraction = min_fraction.unwrap_or(config_manager.lock().unwrap().system_config.rectify_min_coverage);
+    if !(min_fraction > 0.0 && min_fraction <= 1.0) {
+        return Err(format!("覆盖率下限应在 (0, 1] 内: {}", min_fraction));
+    }
+    
+    let param_dir = active_param_dir(&alignment_state)?;
+    let report = check_rectify_coverage(param_file_path(&param_dir, "rectify_params.yaml"), min_fraction)
+        .map_err(|e| format!("读取校正有效区域失败: {}", e))?;
+    println!("📐 {}", report.message);
+    Ok(report)
+}
+
+/// 标定与合像模块一致性自检
+/// 
+/// 比较两模块生成的世界坐标，以及对同一组固定圆心的排序结果，返回全部不一致项。
+/// 标定侧使用当前标定会话的配置（未启动会话时用默认配置），合像侧使用配置的标定板规格，不需要相机
+#[tauri::command]
+pub async fn check_calibration_alignment_consistency(
+    state: State<'_, CalibrationWorkflowState>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<ModuleConsistencyReport, String> {
+    println!("🧪 Tauri命令: check_calibration_alignment_consistency");
+    
+    let calibration_config = state.lock()
+        .map_err(|e| format!("获取工作流程状态失败: {}", e))?
+        .as_ref()
+        .map(|workflow| workflow.calibration_config().clone())
+        .unwrap_or_default();
+    
+    let pattern_size = config_manager.lock().unwrap().alignment_config.pattern_size;
+    check_module_consistency(&calibration_config, pattern_size)
+        .map_err(|e| format!("模块一致性自检失败: {}", e))
+}
+
+/// 根据当前帧检测到的标定板建议相机ROI
+/// 
+/// 返回的 `roi` 覆盖左右两侧标定板，可直接传给 `apply_roi_config`，
+/// 提高每个圆点的有效分辨率并减少画面取景时间
+/// 
+/// # 参数
+/// - `padding_ratio`: 相对标定板包围框的外扩比例（默认0.15）
+#[tauri::command]
+pub async fn suggest_board_roi(
+    padding_ratio: Option<f64>,
+    state: State<'_, CalibrationWorkflowState>,
+) -> Result<BoardRoiSuggestion, String> {
+    let padding_ratio = padding_ratio.unwrap_or(DEFAULT_BOARD_ROI_PADDING);
+    println!("📐 Tauri命令: suggest_board_roi(padding_ratio={})", padding_ratio);
+    
+    let workflow_guard = state.lock()
+        .map_err(|e| format!("获取工作流程状态失败: {}", e))?;
+    
+    match workflow_guard.as_ref() {
+        Some(workflow) => workflow.suggest_board_roi(padding_ratio),
+        None => Err("标定会话未启动".to_string()),
+    }
+}
@@ -1,6 +1,12 @@
 use tauri::State;
 use std::sync::{Arc, Mutex};
 use crate::config::{ConfigManager, SystemConfig, CameraConfig, AlignmentConfig, CompatibilityManager, ConfigPreset};
+use crate::commands::alignment_commands::{AlignmentWorkflowState, active_param_dir};
+use crate::modules::alignment_workflow::{RECTIFY_MAPS_FILE, param_file_path};
+use crate::modules::param_io::{CameraSerialCheck, check_calibration_dir_serials, check_rectify_maps_file};
+use crate::paths::{DebugImageRetention, DebugCleanupReport};
+use crate::camera_ffi::CameraDevice;
+use crate::camera_manager::CameraAssignment;
 
 /// 系统参数配置命令
 #[tauri::command]
@@ -21,11 +27,48 @@ pub async fn set_system_config(
     // 验证配置有效性
     config.validate()?;
     
+    crate::paths::set_debug_image_retention(config.debug_image_retention);
     manager.system_config = config;
     println!("✓ 系统配置已更新");
     Ok(())
 }
 
+/// 设置调试图像保留策略 (0 表示该项不限制)
+/// 
+/// 立即生效并对现有调试目录执行一次清理；persist 为 true (默认) 时写入配置文件
+#[tauri::command]
+pub async fn set_debug_image_retention(
+    policy: DebugImageRetention,
+    persist: Option<bool>,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<String, String> {
+    {
+        let mut manager = config_manager.lock().unwrap();
+        manager.system_config.debug_image_retention = policy;
+        if persist.unwrap_or(true) {
+            manager.save_to_default_dir()?;
+        }
+    }
+    
+    crate::paths::set_debug_image_retention(policy);
+    for name in crate::paths::DEBUG_IMAGE_DIR_NAMES {
+        crate::paths::apply_debug_retention(&crate::paths::debug_images_dir(name));
+    }
+    
+    Ok(format!("调试图像保留策略已更新: 最多{}个, {}天, {} MB",
+               policy.max_files, policy.max_age_days, policy.max_total_mb))
+}
+
+/// 立即删除全部调试图像
+#[tauri::command]
+pub async fn purge_debug_images() -> Result<DebugCleanupReport, String> {
+    let report = crate::paths::purge_debug_images()
+        .map_err(|e| format!("清理调试图像失败: {}", e))?;
+    println!("🧹 已清空调试图像: {} 个文件，释放 {:.1} MB",
+             report.removed_files, report.freed_bytes as f64 / 1024.0 / 1024.0);
+    Ok(report)
+}
+
 /// 相机参数配置命令 - 统一管理左右两个相机
 #[tauri::command]
 pub async fn get_camera_config(
@@ -50,12 +93,52 @@ pub async fn set_camera_config(
     manager.apply_camera_config(1, &config)?;  // 右相机
     
     // 保存配置到内存
+    crate::camera_manager::set_camera_assignment(Some(config.camera_assignment()));
     manager.camera_config = config;
     
     println!("✓ 相机配置已更新 (左右相机统一配置)");
     Ok(())
 }
 
+/// 枚举已连接的相机 (索引, 序列号, 名称)，供界面选择左右相机
+#[tauri::command]
+pub async fn list_cameras() -> Result<Vec<CameraDevice>, String> {
+    let devices = crate::camera_manager::list_cameras().map_err(|e| e.to_string())?;
+    println!("📷 检测到 {} 台相机", devices.len());
+    for device in &devices {
+        println!("   - [{}] {} ({})", device.index, device.serial, device.name);
+    }
+    Ok(devices)
+}
+
+/// 按序列号指定左右相机并写入配置
+/// 
+/// 下次初始化相机时生效（已在运行的相机需重新启动工作流）
+#[tauri::command]
+pub async fn assign_cameras(
+    left_serial: String,
+    right_serial: String,
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+) -> Result<String, String> {
+    let assignment = CameraAssignment {
+        left_serial: left_serial.trim().to_string(),
+        right_serial: right_serial.trim().to_string(),
+    };
+    assignment.validate()?;
+    
+    {
+        let mut manager = config_manager.lock().unwrap();
+        manager.camera_config.left_camera_serial = assignment.left_serial.clone();
+        manager.camera_config.right_camera_serial = assignment.right_serial.clone();
+        manager.save_to_default_dir()?;
+    }
+    
+    crate::camera_manager::set_camera_assignment(Some(assignment.clone()));
+    println!("✓ 左右相机已指定: 左 {}, 右 {}", assignment.left_serial, assignment.right_serial);
+    Ok(format!("左右相机已指定: 左 {}, 右 {}（下次初始化相机时生效）",
+               assignment.left_serial, assignment.right_serial))
+}
+
 /// 获取单个相机的序列号 - 兼容旧接口
 #[tauri::command]
 pub async fn get_camera_serial(
@@ -72,6 +155,44 @@ pub async fn get_camera_serial(
     }
 }
 
+/// 自检：当前相机序列号是否与生效标定记录的序列号一致，重映射矩阵尺寸是否与当前分辨率一致
+/// 
+/// 更换相机或切换分辨率后旧标定参数会产生错误的校正结果，此命令在不匹配时返回警告
+#[tauri::command]
+pub async fn verify_camera_calibration_match(
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+    alignment_state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<CameraSerialCheck, String> {
+    let param_dir = active_param_dir(&alignment_state)?;
+    let resolution = config_manager.lock().unwrap().camera_config.active_resolution();
+    let left_serial = get_camera_serial(config_manager.clone(), "left".to_string()).await?;
+    let right_serial = get_camera_serial(config_manager, "right".to_string()).await?;
+    
+    let mut check = check_calibration_dir_serials(&param_dir, &left_serial, &right_serial);
+    match &check.warning {
+        Some(warning) => println!("⚠️ 相机/标定校验: {}", warning),
+        None => println!("✓ 相机序列号与当前标定一致"),
+    }
+    
+    match check_rectify_maps_file(param_file_path(&param_dir, RECTIFY_MAPS_FILE), resolution) {
+        Ok(maps_check) => {
+            match &maps_check.error {
+                Some(error) => {
+                    println!("⚠️ 重映射矩阵校验: {}", error);
+                    check.warning = Some(match check.warning.take() {
+                        Some(warning) => format!("{}；{}", warning, error),
+                        None => error.clone(),
+                    });
+                }
+                None => println!("✓ 重映射矩阵尺寸与当前分辨率一致"),
+            }
+            check.rectify_maps = Some(maps_check);
+        }
+        Err(e) => println!("⚠️ 无法读取重映射矩阵，跳过尺寸校验: {}", e),
+    }
+    Ok(check)
+}
+
 /// 合像参数配置命令
 #[tauri::command]
 pub async fn get_alignment_config(
@@ -125,10 +246,13 @@ pub async fn load_config_from_file(
     
     // 替换当前配置管理器的内容
     let mut manager = config_manager.lock().unwrap();
+    crate::paths::set_debug_image_retention(loaded_manager.system_config.debug_image_retention);
+    crate::camera_manager::set_camera_assignment(Some(loaded_manager.camera_config.camera_assignment()));
     manager.system_config = loaded_manager.system_config;
     manager.camera_config = loaded_manager.camera_config;
     manager.alignment_config = loaded_manager.alignment_config;
     manager.config_root_dir = loaded_manager.config_root_dir;
+    manager.active_preset = loaded_manager.active_preset;
     
     // ⚠️ 谨慎应用加载的配置到硬件
     if !manager.preserve_existing_implementations {
@@ -229,7 +353,8 @@ pub async fn apply_roi_config(
         roi_config.offset_x, roi_config.offset_y, roi_config.width, roi_config.height);
     println!("   需要实现: camera_set_roi_ffi(cam_index, roi_config)");
     
-    Ok(())
+    // 未下发到硬件前不能报告成功，否则帧解析会按ROI尺寸错位
+    Err("暂不支持相机ROI：camera_set_roi_ffi 尚未实现".to_string())
 }
 
 /// 配置预设管理命令
@@ -327,15 +452,63 @@ pub async fn reset_to_default_config(
     
     // 重置为默认配置
     let default_manager = ConfigManager::new();
+    crate::paths::set_debug_image_retention(default_manager.system_config.debug_image_retention);
+    crate::camera_manager::set_camera_assignment(Some(default_manager.camera_config.camera_assignment()));
     manager.system_config = default_manager.system_config;
     manager.camera_config = default_manager.camera_config;
     manager.alignment_config = default_manager.alignment_config;
     manager.preserve_existing_implementations = true;  // 强制保护现有实现
+    manager.active_preset = None;
     
     println!("✓ 已重置为默认配置 (保护现有实现)");
     Ok(())
 }
 
+/// 将合像阈值恢复为出厂规格 (来自内置 "spec" 预设)
+/// 
+/// 仅重置姿态阈值与合像阈值，其余配置保持不变
+#[tauri::command]
+pub async fn reset_alignment_thresholds_to_spec(
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+    compatibility_manager: State<'_, Arc<Mutex<CompatibilityManager>>>,
+) -> Result<AlignmentConfig, String> {
+    let compat_manager = compatibility_manager.lock().unwrap();
+    let mut manager = config_manager.lock().unwrap();
+    
+    let spec = compat_manager.get_preset(crate::config::SPEC_PRESET_NAME)
+        .ok_or_else(|| format!("内置规格预设不存在: {}", crate::config::SPEC_PRESET_NAME))?;
+    
+    manager.alignment_config.pose_thresholds = spec.alignment.pose_thresholds.clone();
+    manager.alignment_config.alignment_thresholds = spec.alignment.alignment_thresholds.clone();
+    
+    println!("✓ 合像阈值已恢复为出厂规格");
+    Ok(manager.alignment_config.clone())
+}
+
+/// 导出当前生效配置快照（单个JSON，供操作员复制附加到售后工单）
+/// 
+/// 包含完整配置、生效预设、解析后的文件路径，以及合像工作流运行中的
+/// 实际生效参数（采集帧率、分位数、无投影阈值、检测器参数、代码内阈值常量等）
+#[tauri::command]
+pub async fn dump_effective_config(
+    config_manager: State<'_, Arc<Mutex<ConfigManager>>>,
+    alignment_state: State<'_, Arc<Mutex<AlignmentWorkflowState>>>,
+) -> Result<String, String> {
+    let mut snapshot = config_manager.lock().unwrap().effective_config_snapshot();
+    
+    let runtime = {
+        let workflow_state = alignment_state.lock().map_err(|e| format!("状态锁定失败: {}", e))?;
+        match workflow_state.workflow {
+            Some(ref workflow) => workflow.runtime_config_snapshot(),
+            None => serde_json::Value::Null,  // 合像工作流未启动
+        }
+    };
+    snapshot["runtime"] = serde_json::json!({ "alignment_workflow": runtime });
+    
+    serde_json::to_string_pretty(&snapshot)
+        .map_err(|e| format!("导出配置快照失败: {}", e))
+}
+
 /// 配置导出/导入命令 (预留接口)
 #[tauri::command]
 pub async fn export_config_to_json(
@@ -350,6 +523,7 @@ pub async fn export_config_to_json(
         version: "1.0".to_string(),
         created_at: manager.system_config.created_at.clone(),
         last_modified: chrono::Utc::now().to_rfc3339(),
+        active_preset: manager.active_preset.clone(),
     };
     
     serde_json::to_string_pretty(&config_data)
@@ -368,6 +542,7 @@ pub async fn import_config_from_json(
     manager.system_config = config_data.system;
     manager.camera_config = config_data.camera;
     manager.alignment_config = config_data.alignment;
+    manager.active_preset = config_data.active_preset;
     
     // 验证导入的配置
     manager.validate_all()?;
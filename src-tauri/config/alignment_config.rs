@@ -1,4 +1,9 @@
 use serde::{Deserialize, Serialize};
+use crate::modules::alignment::{PoseConvention, DEFAULT_PATTERN_SIZE, StandoffRange, ConvergenceRange, MicrometerCalibration, AcceptanceThresholds, CenteringTargets, RobustStatsConfig, PnpMethod, MAX_POSE_AVERAGING_FRAMES, DEFAULT_POSE_REPROJECTION_MAX_PX, DEFAULT_MAGNIFICATION_MISMATCH_MAX};
+use crate::modules::alignment_workflow::{DetectionRetryConfig, DEFAULT_DETECTION_DECIMATION, MAX_DETECTION_DECIMATION, DEFAULT_PREVIEW_STALE_TIMEOUT_MS};
+use crate::modules::alignment_circles_detection::{DetectorConfig, ExclusionRegion};
+use crate::modules::plc_modbus::PlcModbusConfig;
+use crate::modules::pose_filter::PoseKalmanConfig;
 
 /// 合像参数配置 - 保护现有alignment.rs实现
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,20 +15,134 @@ pub struct AlignmentConfig {
     /// 合像检测用SimpleBlobDetector参数 - 保留现有实现
     pub alignment_blob_detector: AlignmentBlobDetectorConfig,
     
-    /// 姿态检测阈值设置 - 当前在alignment.rs中写死
+    /// 姿态检测阈值设置 - 启动合像检测时应用到检测系统
     pub pose_thresholds: PoseThresholds,
     
-    /// 合像判定阈值 - 当前在alignment.rs中写死  
+    /// 合像判定阈值 - 启动合像检测时应用到检测系统
     pub alignment_thresholds: AlignmentThresholds,
     
     /// ROI区域设置 - 基于性能优化结果
     pub roi_config: AlignmentRoiConfig,
     
+    /// 采集线程目标帧率 (fps) - 原alignment_workflow.rs中写死为10fps
+    #[serde(default = "default_acquisition_target_fps")]
+    pub acquisition_target_fps: f64,
+    
+    /// 上报姿态角/调整量的坐标约定 - 按工位千分尺方向配置
+    #[serde(default)]
+    pub pose_convention: PoseConvention,
+    
+    /// 姿态多帧平均帧数 - 默认1，即单帧 solvePnP
+    #[serde(default = "default_pose_averaging_frames")]
+    pub pose_averaging_frames: usize,
+    
+    /// 工作距离合理范围 (mm) - 超出时告警，提示世界坐标尺度/单位错误
+    #[serde(default)]
+    pub standoff_range: StandoffRange,
+    
+    /// 双眼虚像距离合格范围 (mm) - 会聚检测判定
+    #[serde(default)]
+    pub convergence_range: ConvergenceRange,
+    
+    /// 姿态解重投影RMS上限 (像素) - 超出时姿态判定不通过（点对应错误）
+    #[serde(default = "default_pose_reprojection_max_px")]
+    pub pose_reprojection_max_px: f64,
+    
+    /// solvePnP 求解方法 (ippe/iterative/sqpnp) - 默认IPPE，失败或解非有限值时自动退回ITERATIVE
+    #[serde(default)]
+    pub pnp_method: PnpMethod,
+    
+    /// 放大倍率不一致判定上限 - 右/左网格跨度比偏离1的比例，默认0.02 (2%)
+    #[serde(default = "default_magnification_mismatch_max")]
+    pub magnification_mismatch_max: f64,
+    
+    /// 合像稳健统计 (MAD 离群点剔除) - 另报剔除后的RMS/分位误差，可选用于判定，默认关闭
+    #[serde(default)]
+    pub robust_statistics: RobustStatsConfig,
+    
+    /// 检测抽帧间隔 - 每N个新帧检测1帧，默认2 (10fps采集下约5fps检测)
+    #[serde(default = "default_detection_decimation")]
+    pub detection_decimation: u32,
+    
+    /// 预览缓存帧最长沿用时间 (毫秒) - 采集短暂无帧时返回上一帧，超时后才报错，默认2000
+    #[serde(default = "default_preview_stale_timeout_ms")]
+    pub preview_stale_timeout_ms: u64,
+    
+    /// 实时姿态显示卡尔曼滤波 - 仅影响显示，判定仍用原始值，默认关闭
+    #[serde(default)]
+    pub pose_kalman: PoseKalmanConfig,
+    
+    /// 千分尺换算 (每圈调整量/方向) - 按工位夹具配置，默认未配置
+    #[serde(default)]
+    pub micrometer_calibration: MicrometerCalibration,
+    
+    /// 检测失败时降低曝光重试 - 默认关闭
+    #[serde(default)]
+    pub detection_retry: DetectionRetryConfig,
+    
+    /// 检测排除区域 (校正后图像坐标) - 屏蔽工装固定反光点，默认无
+    #[serde(default)]
+    pub detection_exclusion_regions: Vec<ExclusionRegion>,
+    
+    /// 连通域圆点检测参数 (面积窗口/连通性/阈值等) - 启动时应用到检测系统
+    #[serde(default)]
+    pub circle_detector: DetectorConfig,
+    
+    /// 合像结果输出到 Modbus/TCP 保持寄存器 (PLC对接) - 默认关闭
+    #[serde(default)]
+    pub plc_modbus: PlcModbusConfig,
+    
+    /// 左眼居中判定的期望关键点位置 (像素) - 保持默认值时按采集分辨率缩放 (见 centering_targets_for)，更换光机时修改
+    #[serde(default)]
+    pub centering_targets: CenteringTargets,
+    
+    /// 合像标定板规格 (每列点数, 列数) - 默认 4×10 (40点)，更换标定板 (如 4×11) 时修改
+    #[serde(default = "default_pattern_size")]
+    pub pattern_size: (i32, i32),
+    
     /// 兼容性设置
     pub use_legacy_alignment_params: bool,  // 是否使用alignment.rs中的原有参数
     pub legacy_params_location: String,     // 记录原参数位置
 }
 
+/// 采集线程目标帧率上限 (fps)
+pub const MAX_ACQUISITION_TARGET_FPS: f64 = 30.0;
+
+fn default_acquisition_target_fps() -> f64 {
+    10.0
+}
+
+fn default_pose_averaging_frames() -> usize {
+    1
+}
+
+fn default_pose_reprojection_max_px() -> f64 {
+    DEFAULT_POSE_REPROJECTION_MAX_PX
+}
+
+fn default_magnification_mismatch_max() -> f64 {
+    DEFAULT_MAGNIFICATION_MISMATCH_MAX
+}
+
+fn default_detection_decimation() -> u32 {
+    DEFAULT_DETECTION_DECIMATION
+}
+
+fn default_preview_stale_timeout_ms() -> u64 {
+    DEFAULT_PREVIEW_STALE_TIMEOUT_MS
+}
+
+fn default_pattern_size() -> (i32, i32) {
+    DEFAULT_PATTERN_SIZE
+}
+
+/// 预览缓存帧沿用时间上限 (毫秒)
+pub const MAX_PREVIEW_STALE_TIMEOUT_MS: u64 = 60_000;
+
+fn default_error_percentile() -> f64 {
+    95.0
+}
+
 /// 合像检测用SimpleBlobDetector配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlignmentBlobDetectorConfig {
@@ -52,7 +171,7 @@ pub struct AlignmentBlobDetectorConfig {
 /// 姿态检测阈值
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoseThresholds {
-    /// ⚠️ 重要：当前在alignment.rs中写死，保留原有实现
+    /// ⚠️ 重要：为 true 时使用alignment.rs中的默认常量，保留原有实现
     pub use_legacy_pose_thresholds: bool,  // 是否使用原有阈值
     
     /// 左眼基准姿态判定阈值 - 基于alignment.rs:17-18的常量
@@ -74,14 +193,18 @@ pub struct PoseThresholds {
 /// 合像判定阈值
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlignmentThresholds {
-    /// ⚠️ 重要：当前在alignment.rs中写死，保留原有实现
+    /// ⚠️ 重要：为 true 时使用alignment.rs中的默认常量，保留原有实现
     pub use_legacy_alignment_thresholds: bool,  // 是否使用原有阈值
     
     /// 双光机合像判定阈值 - 基于alignment.rs:19-21的常量
     pub max_rms_error: f64,            // 最大RMS误差 (像素) - 当前RMS_TH: 100.0
-    pub max_p95_error: f64,            // 最大P95误差 (像素) - 当前P95_TH: 100.0
+    pub max_p95_error: f64,            // 最大P95误差 (像素) - 当前P95_TH: 100.0，作用于error_percentile分位
     pub max_max_error: f64,            // 最大最大误差 (像素) - 当前MAX_TH: 200.0
     
+    /// 分位误差所用分位数 (默认95，即P95；客户规格可为P90/P99)
+    #[serde(default = "default_error_percentile")]
+    pub error_percentile: f64,
+    
     /// 调整提示阈值 - 用于指导调整方向
     pub adjustment_hint_threshold: f64, // 调整提示阈值 (像素)
     pub mean_dx_threshold: f64,        // X方向均值阈值
@@ -167,6 +290,7 @@ impl Default for AlignmentConfig {
                 max_rms_error: 100.0,        // RMS_TH
                 max_p95_error: 100.0,        // P95_TH
                 max_max_error: 200.0,        // MAX_TH
+                error_percentile: default_error_percentile(),
                 
                 adjustment_hint_threshold: 1.0,
                 mean_dx_threshold: 0.5,
@@ -194,6 +318,63 @@ impl Default for AlignmentConfig {
                 roi_optimization_notes: "右相机ROI可减少50%搜索范围，提升检测性能".to_string(),
             },
             
+            // 采集帧率 - 与原写死的100ms间隔一致
+            acquisition_target_fps: default_acquisition_target_fps(),
+            
+            // 姿态坐标约定 - 默认与原输出一致
+            pose_convention: PoseConvention::default(),
+            
+            // 姿态多帧平均 - 默认单帧，与原行为一致
+            pose_averaging_frames: default_pose_averaging_frames(),
+            
+            // 工作距离合理范围 - 仅告警，不参与判定
+            standoff_range: StandoffRange::default(),
+            
+            // 双眼虚像距离合格范围 - 会聚检测
+            convergence_range: ConvergenceRange::default(),
+            
+            // 姿态重投影校验上限
+            pose_reprojection_max_px: default_pose_reprojection_max_px(),
+            
+            // solvePnP 求解方法 - 默认IPPE，与原写死方法一致
+            pnp_method: PnpMethod::default(),
+            
+            // 放大倍率不一致判定
+            magnification_mismatch_max: default_magnification_mismatch_max(),
+            
+            // 稳健统计 - 默认关闭，判定与原行为一致
+            robust_statistics: RobustStatsConfig::default(),
+            
+            // 检测抽帧 - 默认每2帧处理1帧，与原200ms节流相当
+            detection_decimation: default_detection_decimation(),
+            
+            // 预览缓存帧沿用时间
+            preview_stale_timeout_ms: default_preview_stale_timeout_ms(),
+            
+            // 姿态显示滤波 - 默认关闭，与原行为一致
+            pose_kalman: PoseKalmanConfig::default(),
+            
+            // 千分尺换算 - 默认未配置任何螺杆
+            micrometer_calibration: MicrometerCalibration::default(),
+            
+            // 检测失败重试 - 默认关闭，与原行为一致
+            detection_retry: DetectionRetryConfig::default(),
+            
+            // 检测排除区域 - 默认无，与原行为一致
+            detection_exclusion_regions: Vec::new(),
+            
+            // 连通域检测参数 - 默认同 ConnectedComponentsDetector::new()
+            circle_detector: DetectorConfig::default(),
+            
+            // PLC输出 - 默认关闭
+            plc_modbus: PlcModbusConfig::default(),
+            
+            // 居中期望位置 - 默认与原写死常量一致
+            centering_targets: CenteringTargets::default(),
+            
+            // 标定板规格 - 默认4×10
+            pattern_size: default_pattern_size(),
+            
             // 兼容性设置
             use_legacy_alignment_params: true,  // 默认使用原有参数
             legacy_params_location: "src-tauri/src/modules/alignment.rs".to_string(),
@@ -201,6 +382,40 @@ impl Default for AlignmentConfig {
     }
 }
 
+impl PoseThresholds {
+    /// 出厂规格姿态阈值 (放宽前的原始指标: roll 0.05°, pitch/yaw 0.10°)
+    pub fn spec() -> Self {
+        Self {
+            use_legacy_pose_thresholds: false,  // 规格值需实际生效，不使用放宽的legacy常量
+            left_eye_max_roll: 0.05,
+            left_eye_max_pitch: 0.10,
+            left_eye_max_yaw: 0.10,
+            left_eye_max_translation: 10.0,
+            right_eye_max_roll: 0.05,
+            right_eye_max_pitch: 0.10,
+            right_eye_max_yaw: 0.10,
+            legacy_thresholds_location: "src-tauri/src/modules/alignment.rs:17-21".to_string(),
+        }
+    }
+}
+
+impl AlignmentThresholds {
+    /// 出厂规格合像阈值 (放宽前的原始指标: RMS 0.10px, P95 0.20px, Max 0.30px)
+    pub fn spec() -> Self {
+        Self {
+            use_legacy_alignment_thresholds: false,  // 规格值需实际生效，不使用放宽的legacy常量
+            max_rms_error: 0.10,
+            max_p95_error: 0.20,
+            max_max_error: 0.30,
+            error_percentile: default_error_percentile(),
+            adjustment_hint_threshold: 1.0,
+            mean_dx_threshold: 0.5,
+            mean_dy_threshold: 0.5,
+            legacy_thresholds_location: "src-tauri/src/modules/alignment.rs:19-21".to_string(),
+        }
+    }
+}
+
 impl AlignmentConfig {
     /// 创建新的合像配置实例
     pub fn new() -> Self {
@@ -222,6 +437,80 @@ impl AlignmentConfig {
            self.alignment_thresholds.max_max_error <= 0.0 {
             return Err("合像阈值必须为正数".to_string());
         }
+        self.acceptance_thresholds().validate()?;
+        
+        // 验证分位数
+        let pct = self.alignment_thresholds.error_percentile;
+        if !(pct > 0.0 && pct <= 100.0) {
+            return Err(format!("分位数必须在(0, 100]范围内: {}", pct));
+        }
+        
+        // 验证采集帧率
+        if self.acquisition_target_fps <= 0.0 || self.acquisition_target_fps > MAX_ACQUISITION_TARGET_FPS {
+            return Err(format!("采集帧率必须在0-{}fps范围内", MAX_ACQUISITION_TARGET_FPS));
+        }
+        
+        // 验证姿态坐标约定
+        self.pose_convention.validate()?;
+        
+        // 验证姿态平均帧数
+        if self.pose_averaging_frames == 0 || self.pose_averaging_frames > MAX_POSE_AVERAGING_FRAMES {
+            return Err(format!("姿态平均帧数必须在1-{}范围内", MAX_POSE_AVERAGING_FRAMES));
+        }
+        
+        // 验证工作距离范围
+        self.standoff_range.validate()?;
+        
+        // 验证虚像距离范围
+        self.convergence_range.validate()?;
+        
+        // 验证姿态重投影RMS上限
+        if !(self.pose_reprojection_max_px > 0.0 && self.pose_reprojection_max_px.is_finite()) {
+            return Err(format!("重投影RMS上限必须为正数: {}", self.pose_reprojection_max_px));
+        }
+        
+        // 验证放大倍率偏差上限
+        if !(self.magnification_mismatch_max > 0.0 && self.magnification_mismatch_max < 1.0) {
+            return Err(format!("放大倍率偏差上限必须在(0, 1)范围内: {}", self.magnification_mismatch_max));
+        }
+        
+        // 验证稳健统计参数
+        self.robust_statistics.validate()?;
+        
+        // 验证检测抽帧间隔
+        if self.detection_decimation == 0 || self.detection_decimation > MAX_DETECTION_DECIMATION {
+            return Err(format!("检测抽帧间隔必须在1-{}范围内: {}", MAX_DETECTION_DECIMATION, self.detection_decimation));
+        }
+        
+        // 验证预览缓存帧沿用时间
+        if self.preview_stale_timeout_ms > MAX_PREVIEW_STALE_TIMEOUT_MS {
+            return Err(format!("预览缓存帧沿用时间不能超过{} ms: {}", MAX_PREVIEW_STALE_TIMEOUT_MS, self.preview_stale_timeout_ms));
+        }
+        
+        // 验证姿态显示滤波参数
+        self.pose_kalman.validate()?;
+        
+        // 验证千分尺换算参数
+        self.micrometer_calibration.validate()?;
+        
+        // 验证检测失败重试参数
+        self.detection_retry.validate()?;
+        
+        // 验证检测排除区域
+        for region in &self.detection_exclusion_regions {
+            region.validate()?;
+        }
+        
+        // 验证连通域检测参数
+        self.circle_detector.validate()?;
+        
+        // 验证PLC输出配置
+        self.plc_modbus.validate()?;
+        
+        // 验证标定板规格
+        if self.pattern_size.0 < 2 || self.pattern_size.1 < 2 {
+            return Err(format!("合像标定板规格无效: {}×{}", self.pattern_size.0, self.pattern_size.1));
+        }
         
         // 验证ROI参数
         if self.roi_config.right_roi_enabled {
@@ -234,6 +523,22 @@ impl AlignmentConfig {
         Ok(())
     }
     
+    /// 按采集分辨率 (宽, 高) 解析居中期望位置：未自定义时使用按分辨率缩放的默认值
+    pub fn centering_targets_for(&self, resolution: (i32, i32)) -> CenteringTargets {
+        if self.centering_targets == CenteringTargets::default() {
+            CenteringTargets::default_for(resolution)
+        } else {
+            self.centering_targets
+        }
+    }
+    
+    /// 校验与采集分辨率相关的配置（居中期望位置须落在图像范围内）
+    pub fn validate_for_resolution(&self, resolution: (i32, i32)) -> Result<(), String> {
+        let size = opencv::core::Size::new(resolution.0, resolution.1);
+        self.centering_targets_for(resolution).validate(size)
+            .map_err(|e| format!("居中期望位置与采集分辨率不符: {}", e))
+    }
+    
     /// 获取当前有效的姿态阈值 (优先使用legacy实现)
     pub fn get_effective_pose_thresholds(&self) -> &PoseThresholds {
         // 总是返回当前配置，但实际使用时检查use_legacy_pose_thresholds标志
@@ -246,12 +551,82 @@ impl AlignmentConfig {
         &self.alignment_thresholds
     }
     
+    /// 检测系统实际使用的判定阈值
+    /// 
+    /// use_legacy_*_thresholds 为 true 时沿用 alignment.rs 中的默认常量；检测系统左右眼共用一组
+    /// 姿态阈值，配置中左右眼或俯仰/偏航不同时取较严值
+    pub fn acceptance_thresholds(&self) -> AcceptanceThresholds {
+        let mut thresholds = AcceptanceThresholds::default();
+        
+        let pose = &self.pose_thresholds;
+        if !pose.use_legacy_pose_thresholds {
+            thresholds.roll_deg = pose.left_eye_max_roll.min(pose.right_eye_max_roll);
+            thresholds.pitch_yaw_deg = [pose.left_eye_max_pitch, pose.left_eye_max_yaw, pose.right_eye_max_pitch, pose.right_eye_max_yaw]
+                .into_iter()
+                .fold(f64::INFINITY, f64::min);
+        }
+        
+        let align = &self.alignment_thresholds;
+        if !align.use_legacy_alignment_thresholds {
+            thresholds.rms_px = align.max_rms_error;
+            thresholds.percentile_px = align.max_p95_error;
+            thresholds.max_px = align.max_max_error;
+        }
+        thresholds
+    }
+    
+    /// 写入判定阈值（左右眼相同），并关闭 legacy 常量使其生效
+    pub fn set_acceptance_thresholds(&mut self, thresholds: &AcceptanceThresholds) -> Result<(), String> {
+        thresholds.validate()?;
+        
+        let pose = &mut self.pose_thresholds;
+        pose.use_legacy_pose_thresholds = false;
+        pose.left_eye_max_roll = thresholds.roll_deg;
+        pose.left_eye_max_pitch = thresholds.pitch_yaw_deg;
+        pose.left_eye_max_yaw = thresholds.pitch_yaw_deg;
+        pose.right_eye_max_roll = thresholds.roll_deg;
+        pose.right_eye_max_pitch = thresholds.pitch_yaw_deg;
+        pose.right_eye_max_yaw = thresholds.pitch_yaw_deg;
+        
+        let align = &mut self.alignment_thresholds;
+        align.use_legacy_alignment_thresholds = false;
+        align.max_rms_error = thresholds.rms_px;
+        align.max_p95_error = thresholds.percentile_px;
+        align.max_max_error = thresholds.max_px;
+        Ok(())
+    }
+    
     /// 获取当前有效的SimpleBlobDetector参数 (优先使用legacy实现)
     pub fn get_effective_blob_detector_params(&self) -> &AlignmentBlobDetectorConfig {
         // 总是返回当前配置，但实际使用时检查use_legacy_alignment_params标志
         &self.alignment_blob_detector
     }
     
+    /// 检查当前阈值与出厂规格的差异，返回差异描述（为空表示与规格一致）
+    pub fn threshold_deviations_from_spec(&self) -> Vec<String> {
+        let spec_pose = PoseThresholds::spec();
+        let spec_align = AlignmentThresholds::spec();
+        let pose = &self.pose_thresholds;
+        let align = &self.alignment_thresholds;
+        
+        let checks = [
+            ("左眼roll(°)", pose.left_eye_max_roll, spec_pose.left_eye_max_roll),
+            ("左眼pitch(°)", pose.left_eye_max_pitch, spec_pose.left_eye_max_pitch),
+            ("左眼yaw(°)", pose.left_eye_max_yaw, spec_pose.left_eye_max_yaw),
+            ("右眼roll(°)", pose.right_eye_max_roll, spec_pose.right_eye_max_roll),
+            ("右眼pitch(°)", pose.right_eye_max_pitch, spec_pose.right_eye_max_pitch),
+            ("右眼yaw(°)", pose.right_eye_max_yaw, spec_pose.right_eye_max_yaw),
+            ("RMS(px)", align.max_rms_error, spec_align.max_rms_error),
+            ("P95(px)", align.max_p95_error, spec_align.max_p95_error),
+            ("Max(px)", align.max_max_error, spec_align.max_max_error),
+        ];
+        
+        checks.iter()
+            .filter(|(_, actual, spec)| (actual - spec).abs() > 1e-9)
+            .map(|(name, actual, spec)| format!("{}: 当前 {} ≠ 规格 {}", name, actual, spec))
+            .collect()
+    }
+    
     /// 检查是否应该绕过现有的alignment.rs实现
     pub fn should_bypass_legacy_alignment(&self) -> bool {
         !self.use_legacy_alignment_params
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use crate::camera_manager::{FrameRecoveryConfig, CameraAssignment, CameraWarmupConfig, FrameResolution};
 
 /// 相机配置 - 统一配置左右两个相机，保护现有camera_init.c实现
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +30,18 @@ pub struct CameraConfig {
     pub left_camera_serial: String,           // 左相机序列号
     pub right_camera_serial: String,          // 右相机序列号
     
+    /// 单帧采集超时与断连自动重连 - 原为camera_api.h中写死的TIMEOUT_MS
+    #[serde(default)]
+    pub frame_recovery: FrameRecoveryConfig,
+    
+    /// 启动预热 - 相机启动后丢弃曝光未稳定的前几帧，默认丢弃3帧
+    #[serde(default)]
+    pub warmup: CameraWarmupConfig,
+    
+    /// 左右眼互换 - 物理左光机接在相机1时开启，无需改接线
+    #[serde(default)]
+    pub swap_eyes: bool,
+    
     /// 兼容性设置
     pub use_legacy_camera_init: bool,         // 是否使用camera_init.c中的现有设置
     pub legacy_init_location: String,         // 记录原实现位置
@@ -127,6 +140,15 @@ impl Default for CameraConfig {
             left_camera_serial: "DA5158733".to_string(),   // 从camera_api.h读取
             right_camera_serial: "DA5158736".to_string(),  // 从camera_api.h读取
             
+            // 采集超时/重连 - 超时与原TIMEOUT_MS一致
+            frame_recovery: FrameRecoveryConfig::default(),
+            
+            // 启动预热 - 默认丢弃少量帧
+            warmup: CameraWarmupConfig::default(),
+            
+            // 左右眼分配 - 默认相机0为左眼
+            swap_eyes: false,
+            
             // 兼容性设置
             use_legacy_camera_init: true,          // 默认使用现有camera_init.c实现
             legacy_init_location: "src-tauri/camera_sdk/src/camera_init.c:196-218".to_string(),
@@ -165,22 +187,22 @@ impl CameraConfig {
             return Err("增益不能为负数".to_string());
         }
         
+        // 验证采集超时/重连参数
+        self.frame_recovery.validate()?;
+        
+        // 验证启动预热参数
+        self.warmup.validate()?;
+        
         // 验证相机序列号
         if self.left_camera_serial.is_empty() || self.right_camera_serial.is_empty() {
             return Err("左右相机序列号不能为空".to_string());
         }
+        self.camera_assignment().validate()?;
         
-        // 验证ROI参数
+        // 验证ROI参数：ROI尚未下发到相机硬件（camera_set_roi_ffi 未实现），
+        // 启用后相机仍输出全幅图像，按ROI尺寸解析帧会错位，因此暂不允许启用
         if self.roi.enabled {
-            if self.roi.offset_x < 0 || self.roi.offset_y < 0 ||
-               self.roi.width <= 0 || self.roi.height <= 0 {
-                return Err("ROI参数无效".to_string());
-            }
-            
-            if (self.roi.offset_x + self.roi.width) > self.width as i32 ||
-               (self.roi.offset_y + self.roi.height) > self.height as i32 {
-                return Err("ROI区域超出图像范围".to_string());
-            }
+            return Err("暂不支持启用相机ROI：ROI尚未下发到相机硬件，请将 roi.enabled 设为 false".to_string());
         }
         
         Ok(())
@@ -192,6 +214,17 @@ impl CameraConfig {
         &self.calibration_blob_detector
     }
     
+    /// 相机输出帧分辨率：启用ROI时为ROI宽高，否则为传感器图像宽高
+    pub fn frame_resolution(&self) -> FrameResolution {
+        let (width, height) = self.active_resolution();
+        FrameResolution::new(width.max(0) as u32, height.max(0) as u32)
+    }
+    
+    /// 设定的曝光时间（微秒），0 表示沿用 camera_init.c 的默认曝光
+    pub fn configured_exposure_us(&self) -> Option<f32> {
+        (self.exposure_time > 0.0).then_some(self.exposure_time as f32)
+    }
+    
     /// 检查是否应该绕过现有的camera_init.c实现
     pub fn should_bypass_legacy_init(&self) -> bool {
         !self.use_legacy_camera_init
@@ -201,4 +234,21 @@ impl CameraConfig {
     pub fn get_camera_serials(&self) -> (String, String) {
         (self.left_camera_serial.clone(), self.right_camera_serial.clone())
     }
+    
+    /// 按配置序列号确定的左右相机分配（始终生效，初始化时据此选择并校验左右相机）
+    pub fn camera_assignment(&self) -> CameraAssignment {
+        CameraAssignment {
+            left_serial: self.left_camera_serial.clone(),
+            right_serial: self.right_camera_serial.clone(),
+        }
+    }
+    
+    /// 当前生效的采集分辨率 (宽, 高)，启用硬件ROI时为ROI尺寸
+    pub fn active_resolution(&self) -> (i32, i32) {
+        if self.roi.enabled {
+            (self.roi.width, self.roi.height)
+        } else {
+            (self.width as i32, self.height as i32)
+        }
+    }
 } 
\ No newline at end of file
@@ -17,6 +17,9 @@ pub struct ConfigPreset {
     pub preset_type: String,  // "builtin" or "user"
 }
 
+/// 出厂规格预设名称 - 用于恢复生产阈值
+pub const SPEC_PRESET_NAME: &str = "spec";
+
 /// 兼容性管理器 - 处理配置预设和现有代码兼容性
 pub struct CompatibilityManager {
     presets: HashMap<String, ConfigPreset>,
@@ -76,6 +79,8 @@ impl CompatibilityManager {
                     auto_detect_serials: false,
                     legacy_serial_location: "src-tauri/camera_sdk/include/camera_api.h:29-30".to_string(),
                 },
+                debug_image_retention: crate::paths::DebugImageRetention::default(),
+                rectify_min_coverage: crate::modules::param_io::DEFAULT_RECTIFY_MIN_COVERAGE,
                 version: "1.0".to_string(),
                 created_at: "2025-01-15T00:00:00Z".to_string(),
             },
@@ -118,6 +123,9 @@ impl CompatibilityManager {
                 },
                 left_camera_serial: "DA5158733".to_string(),
                 right_camera_serial: "DA5158736".to_string(),
+                frame_recovery: Default::default(),
+                warmup: Default::default(),
+                swap_eyes: false,
                 use_legacy_camera_init: true,        // 强制使用legacy
                 legacy_init_location: "src-tauri/camera_sdk/src/camera_init.c:196-218".to_string(),
             },
@@ -157,6 +165,7 @@ impl CompatibilityManager {
                     max_rms_error: 100.0,
                     max_p95_error: 100.0,
                     max_max_error: 200.0,
+                    error_percentile: 95.0,
                     adjustment_hint_threshold: 1.0,
                     mean_dx_threshold: 0.5,
                     mean_dy_threshold: 0.5,
@@ -175,6 +184,24 @@ impl CompatibilityManager {
                     left_roi_height: 2048,
                     roi_optimization_notes: "生产环境：启用右相机ROI以提升50%性能".to_string(),
                 },
+                acquisition_target_fps: 10.0,
+                pose_convention: Default::default(),
+                pose_averaging_frames: 1,
+                standoff_range: Default::default(),
+                convergence_range: Default::default(),
+                pose_reprojection_max_px: crate::modules::alignment::DEFAULT_POSE_REPROJECTION_MAX_PX,
+                pnp_method: Default::default(),
+                magnification_mismatch_max: crate::modules::alignment::DEFAULT_MAGNIFICATION_MISMATCH_MAX,
+                robust_statistics: Default::default(),
+                detection_decimation: crate::modules::alignment_workflow::DEFAULT_DETECTION_DECIMATION,
+                preview_stale_timeout_ms: crate::modules::alignment_workflow::DEFAULT_PREVIEW_STALE_TIMEOUT_MS,
+                pose_kalman: Default::default(),
+                micrometer_calibration: Default::default(),
+                detection_retry: Default::default(),
+                detection_exclusion_regions: Vec::new(),
+                circle_detector: Default::default(),
+                plc_modbus: Default::default(),
+                centering_targets: Default::default(),
                 use_legacy_alignment_params: true,   // 强制使用legacy
                 legacy_params_location: "src-tauri/src/modules/alignment.rs".to_string(),
             },
@@ -209,6 +236,7 @@ impl CompatibilityManager {
                     max_rms_error: 150.0,             // 更宽松的合像要求
                     max_p95_error: 200.0,
                     max_max_error: 300.0,
+                    error_percentile: 95.0,
                     adjustment_hint_threshold: 2.0,
                     mean_dx_threshold: 1.0,
                     mean_dy_threshold: 1.0,
@@ -227,6 +255,7 @@ impl CompatibilityManager {
                     left_roi_height: 2048,
                     roi_optimization_notes: "调试环境：禁用ROI以便观察完整图像".to_string(),
                 },
+                acquisition_target_fps: 5.0,        // 低帧率便于调试
                 ..production_preset.alignment.clone()
             },
             created_at: "2025-01-15T00:00:00Z".to_string(),
@@ -256,9 +285,26 @@ impl CompatibilityManager {
             preset_type: "builtin".to_string(),
         };
         
+        // 出厂规格预设 - 生产配置 + 原始规格阈值（防止放宽的测试阈值流入生产）
+        let spec_preset = ConfigPreset {
+            name: "出厂规格".to_string(),
+            description: "生产环境配置 + 出厂规格阈值 (roll 0.05°, pitch/yaw 0.10°, RMS 0.10px)".to_string(),
+            system: production_preset.system.clone(),
+            camera: production_preset.camera.clone(),
+            alignment: AlignmentConfig {
+                pose_thresholds: crate::config::PoseThresholds::spec(),
+                alignment_thresholds: crate::config::AlignmentThresholds::spec(),
+                ..production_preset.alignment.clone()
+            },
+            created_at: "2025-01-15T00:00:00Z".to_string(),
+            version: "1.0".to_string(),
+            preset_type: "builtin".to_string(),
+        };
+        
         self.presets.insert("production".to_string(), production_preset);
         self.presets.insert("debug".to_string(), debug_preset);
         self.presets.insert("advanced".to_string(), advanced_preset);
+        self.presets.insert(SPEC_PRESET_NAME.to_string(), spec_preset);
         
         println!("✓ 加载了 {} 个内置配置预设", self.presets.len());
     }
@@ -371,10 +417,11 @@ impl CompatibilityManager {
         
         // 根据预设类型设置保护模式
         manager.preserve_existing_implementations = match preset_name {
-            "production" | "debug" => true,   // 内置预设强制保护
+            "production" | "debug" | SPEC_PRESET_NAME => true,   // 内置预设强制保护
             "advanced" => false,              // 高级预设允许部分修改
             _ => true,                        // 用户预设默认保护
         };
+        manager.active_preset = Some(preset_name.to_string());
         
         println!("✓ 已应用预设 '{}' 到配置管理器", preset_name);
         Ok(())
@@ -19,6 +19,9 @@ pub struct ConfigManager {
     
     /// 配置文件根目录
     pub config_root_dir: String,
+    
+    /// 当前生效的预设名称 (None 表示默认配置或手动修改)
+    pub active_preset: Option<String>,
 }
 
 /// 完整的配置数据结构 - 用于序列化保存
@@ -30,6 +33,25 @@ pub struct ConfigData {
     pub version: String,
     pub created_at: String,
     pub last_modified: String,
+    #[serde(default)]
+    pub active_preset: Option<String>,
+}
+
+/// 路径解析信息：原始路径、相对当前工作目录解析后的绝对路径、是否存在
+pub fn resolve_path_info(path: &str) -> serde_json::Value {
+    let raw = Path::new(path);
+    let resolved = if raw.is_absolute() {
+        raw.to_path_buf()
+    } else {
+        std::env::current_dir()
+            .map(|cwd| cwd.join(raw))
+            .unwrap_or_else(|_| raw.to_path_buf())
+    };
+    serde_json::json!({
+        "path": path,
+        "resolved": resolved.to_string_lossy(),
+        "exists": resolved.exists(),
+    })
 }
 
 impl ConfigManager {
@@ -43,7 +65,8 @@ impl ConfigManager {
             alignment_config: AlignmentConfig::new(),
             system_config,
             preserve_existing_implementations: true,  // 默认保护现有代码
-            config_root_dir: "configs".to_string(),
+            config_root_dir: crate::paths::configs_dir().to_string_lossy().to_string(),
+            active_preset: None,
         }
     }
     
@@ -80,11 +103,12 @@ impl ConfigManager {
             alignment_config: config_data.alignment,
             preserve_existing_implementations: true,  // 始终保护现有实现
             config_root_dir: config_dir,
+            active_preset: config_data.active_preset,
         })
     }
     
-    /// 保存配置到文件
-    pub fn save_to_file<P: AsRef<Path>>(&self, file_path: P) -> Result<(), String> {
+    /// 当前配置序列化为YAML（与配置文件格式一致）
+    pub fn to_yaml_string(&self) -> Result<String, String> {
         let config_data = ConfigData {
             system: self.system_config.clone(),
             camera: self.camera_config.clone(),
@@ -92,10 +116,16 @@ impl ConfigManager {
             version: "1.0".to_string(),
             created_at: self.system_config.created_at.clone(),
             last_modified: chrono::Utc::now().to_rfc3339(),
+            active_preset: self.active_preset.clone(),
         };
         
-        let content = serde_yaml::to_string(&config_data)
-            .map_err(|e| format!("序列化配置失败: {}", e))?;
+        serde_yaml::to_string(&config_data)
+            .map_err(|e| format!("序列化配置失败: {}", e))
+    }
+    
+    /// 保存配置到文件
+    pub fn save_to_file<P: AsRef<Path>>(&self, file_path: P) -> Result<(), String> {
+        let content = self.to_yaml_string()?;
             
         // 确保目录存在
         if let Some(parent) = file_path.as_ref().parent() {
@@ -129,6 +159,18 @@ impl ConfigManager {
         // 验证合像配置
         self.alignment_config.validate()
             .map_err(|e| format!("合像配置验证失败: {}", e))?;
+        self.alignment_config.validate_for_resolution(self.camera_config.active_resolution())
+            .map_err(|e| format!("合像配置验证失败: {}", e))?;
+        
+        // 检查阈值是否偏离出厂规格（仅告警，不阻断）
+        let deviations = self.alignment_config.threshold_deviations_from_spec();
+        if !deviations.is_empty() {
+            println!("⚠️⚠️⚠️ 警告: 当前合像阈值与出厂规格不一致，禁止以此配置出货！");
+            for deviation in &deviations {
+                println!("   ⚠️ {}", deviation);
+            }
+            println!("   请调用 reset_alignment_thresholds_to_spec 恢复规格阈值");
+        }
         
         Ok(())
     }
@@ -229,6 +271,15 @@ impl ConfigManager {
             align_th.max_rms_error, align_th.max_p95_error, align_th.max_max_error));
         report.push_str(&format!("  - 使用legacy参数: {}\n", 
             self.alignment_config.use_legacy_alignment_params));
+        let deviations = self.alignment_config.threshold_deviations_from_spec();
+        if deviations.is_empty() {
+            report.push_str("  - 阈值规格检查: ✓ 与出厂规格一致\n");
+        } else {
+            report.push_str("  - 阈值规格检查: ⚠️ 偏离出厂规格，禁止出货!\n");
+            for deviation in &deviations {
+                report.push_str(&format!("      {}\n", deviation));
+            }
+        }
         
         // 兼容性报告
         report.push_str("\n🛡️ 兼容性保护:\n");
@@ -239,6 +290,51 @@ impl ConfigManager {
         report
     }
     
+    /// 生成当前生效配置快照 (JSON)，用于附加到售后工单
+    /// 
+    /// 包含完整配置、生效预设、legacy解析后的有效参数、阈值规格偏差与解析后的文件路径；
+    /// 运行时覆盖（合像工作流）由调用方合并到 "runtime" 字段
+    pub fn effective_config_snapshot(&self) -> serde_json::Value {
+        let (circle_diameter, center_distance, pattern_size) = self.get_effective_pattern_params();
+        let (left_serial, right_serial) = self.get_effective_camera_serials();
+        let paths = &self.system_config.file_paths;
+        
+        serde_json::json!({
+            "generated_at": chrono::Utc::now().to_rfc3339(),
+            "app_version": env!("CARGO_PKG_VERSION"),
+            "active_preset": self.active_preset,
+            "preserve_existing_implementations": self.preserve_existing_implementations,
+            "use_legacy_implementations": self.should_use_legacy_implementations(),
+            "effective": {
+                "pattern": {
+                    "circle_diameter_mm": circle_diameter,
+                    "center_distance_mm": center_distance,
+                    "pattern_size": [pattern_size.width, pattern_size.height],
+                },
+                "camera_serials": [left_serial, right_serial],
+                "threshold_deviations_from_spec": self.alignment_config.threshold_deviations_from_spec(),
+            },
+            "paths": {
+                "config_root_dir": resolve_path_info(&self.config_root_dir),
+                "system_config_file": resolve_path_info(&Path::new(&self.config_root_dir).join("system_config.yaml").to_string_lossy()),
+                "camera_config_dir": resolve_path_info(&paths.camera_config_dir),
+                "calibration_images_dir": resolve_path_info(&paths.calibration_images_dir),
+                "calibration_params_dir": resolve_path_info(&paths.calibration_params_dir),
+                "rectify_maps_path": resolve_path_info(&paths.rectify_maps_path),
+                "alignment_config_dir": resolve_path_info(&paths.alignment_config_dir),
+                "left_camera_params_path": resolve_path_info(&paths.left_camera_params_path),
+                "right_camera_params_path": resolve_path_info(&paths.right_camera_params_path),
+                "stereo_params_path": resolve_path_info(&paths.stereo_params_path),
+                "rectify_params_path": resolve_path_info(&paths.rectify_params_path),
+            },
+            "config": {
+                "system": self.system_config,
+                "camera": self.camera_config,
+                "alignment": self.alignment_config,
+            },
+        })
+    }
+    
     /// 列出配置目录中的所有配置文件
     pub fn list_config_files(&self) -> Result<Vec<String>, String> {
         let config_dir = Path::new(&self.config_root_dir);
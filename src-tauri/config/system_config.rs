@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use crate::paths::DebugImageRetention;
+use crate::modules::param_io::DEFAULT_RECTIFY_MIN_COVERAGE;
 
 /// 系统配置 - 标定板layout、文件路径、相机序列号等核心设置
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,11 +14,23 @@ pub struct SystemConfig {
     /// 相机序列号配置
     pub camera_serials: CameraSerialConfig,
     
+    /// 调试图像保留策略 - 防止调试输出占满磁盘
+    #[serde(default)]
+    pub debug_image_retention: DebugImageRetention,
+    
+    /// 校正有效区域覆盖率合格下限 (0-1) - 低于该比例提示标定质量问题
+    #[serde(default = "default_rectify_min_coverage")]
+    pub rectify_min_coverage: f64,
+    
     /// 配置版本和元信息
     pub version: String,
     pub created_at: String,
 }
 
+fn default_rectify_min_coverage() -> f64 {
+    DEFAULT_RECTIFY_MIN_COVERAGE
+}
+
 /// 标定板layout配置 - 谨慎处理世界坐标问题
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PatternLayoutConfig {
@@ -106,6 +120,8 @@ impl Default for SystemConfig {
                 auto_detect_serials: false,  // 当前使用固定序列号
                 legacy_serial_location: "src-tauri/camera_sdk/include/camera_api.h:29-30".to_string(),
             },
+            debug_image_retention: DebugImageRetention::default(),
+            rectify_min_coverage: default_rectify_min_coverage(),
             version: "1.0".to_string(),
             created_at: chrono::Utc::now().to_rfc3339(),
         }
@@ -135,6 +151,11 @@ impl SystemConfig {
             return Err("相机序列号不能为空".to_string());
         }
         
+        // 验证校正覆盖率下限
+        if !(self.rectify_min_coverage > 0.0 && self.rectify_min_coverage <= 1.0) {
+            return Err(format!("校正覆盖率下限应在 (0, 1] 内: {}", self.rectify_min_coverage));
+        }
+        
         Ok(())
     }
     
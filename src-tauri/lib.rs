@@ -2,6 +2,7 @@
 pub mod camera_ffi;
 pub mod camera_manager;
 pub mod config;
+pub mod paths;
 pub mod commands {
     pub mod config_commands;
     pub mod calibration_commands;
@@ -20,6 +21,11 @@ pub mod modules {
     pub mod calibration_workflow;
     pub mod simple_config;  // 添加simple_config模块
     pub mod alignment_circles_detection;  // 🆕 连通域圆点检测核心算法模块
+    pub mod benchmark;  // 检测性能统计汇总（离线/实机benchmark共用）
+    pub mod plc_modbus;  // 合像结果输出到 Modbus/TCP 寄存器（PLC对接，服务端需 modbus 特性）
+    pub mod pose_filter;  // 实时姿态显示用卡尔曼滤波
+    pub mod result_metrics;  // 检测结果指标的单位与显示名称
+    pub mod result_log;  // 合像结果追溯日志（CSV/JSONL）
 }
 
 //pub use config::simple_config;
@@ -31,7 +37,7 @@ use crate::camera_manager::CameraManager;
 // 导出新的SimpleCameraManager供测试使用
 pub use crate::camera_manager::{SimpleCameraManager, CameraError};
 // 导出连通域圆点检测核心算法模块
-pub use crate::modules::alignment_circles_detection::{ConnectedComponentsDetector, RefineTag};
+pub use crate::modules::alignment_circles_detection::{ConnectedComponentsDetector, DetectionDiagnostics, RefineTag};
 use crate::camera_ffi::CameraHandle;
 use crate::modules::alignment_workflow::{AlignmentWorkflow, WorkflowCommand, DetectionStage};
 use crate::config::{ConfigManager, CompatibilityManager};
@@ -52,6 +58,10 @@ mod tests {
     mod calibration_test_new;
     mod calibration_circles_test;
     mod alignment_test;
+    mod alignment_workflow_test;
+    mod camera_manager_test;
+    mod fixtures;          // 合成样例帧夹具，不依赖 src/tests/data
+    mod file_frame_source; // 回放已保存帧对的 CameraBackend，工作流集成测试无需相机
 }
 
 
@@ -204,10 +214,38 @@ pub fn run() {
     tauri::Builder::default()
         // 外部打开 URL 等功能，可选
         .plugin(tauri_plugin_opener::init())
+        // 原始像素预览协议：preview://localhost/left | right -> 灰度缩略图原始字节
+        .register_uri_scheme_protocol("preview", |_ctx, request| {
+            let name = request.uri().path().trim_start_matches('/');
+            match crate::modules::alignment_workflow::read_raw_preview_buffer(name) {
+                Some(bytes) => tauri::http::Response::builder()
+                    .header("Content-Type", "application/octet-stream")
+                    .header("Access-Control-Allow-Origin", "*")
+                    .body(bytes)
+                    .unwrap(),
+                None => tauri::http::Response::builder()
+                    .status(404)
+                    .body(Vec::new())
+                    .unwrap(),
+            }
+        })
         // 在 setup 阶段用 AppHandle 初始化 CameraManager 并注入到 State
         .setup(|app| {
             let handle = app.handle();
             
+            // 数据目录使用 app-data，并一次性迁移工作目录下的旧数据（见 paths.rs）
+            match app.path().app_data_dir() {
+                Ok(dir) => {
+                    crate::paths::init_data_root(dir);
+                    if let Ok(cwd) = std::env::current_dir() {
+                        if let Err(e) = crate::paths::migrate_legacy_data(&cwd, &crate::paths::data_root()) {
+                            eprintln!("⚠️ 旧数据迁移失败: {}", e);
+                        }
+                    }
+                }
+                Err(e) => eprintln!("⚠️ 获取 app-data 目录失败，使用默认数据目录: {}", e),
+            }
+            
             // 初始化CameraManager (保持向后兼容)
             let manager = CameraManager::new(handle.clone())
                 .expect("failed to initialize CameraManager");
@@ -237,11 +275,13 @@ pub fn run() {
             
             // 初始化配置管理器
             let config_manager = ConfigManager::new();
+            crate::paths::set_debug_image_retention(config_manager.system_config.debug_image_retention);
+            crate::camera_manager::set_camera_assignment(Some(config_manager.camera_config.camera_assignment()));
             println!("✓ ConfigManager 创建成功");
             app.manage(Arc::new(Mutex::new(config_manager)));
             
             // 初始化兼容性管理器
-            let compatibility_manager = CompatibilityManager::new("configs");
+            let compatibility_manager = CompatibilityManager::new(&crate::paths::configs_dir().to_string_lossy());
             println!("✓ CompatibilityManager 创建成功");
             app.manage(Arc::new(Mutex::new(compatibility_manager)));
             
@@ -249,6 +289,7 @@ pub fn run() {
             let calibration_workflow_state: Arc<Mutex<Option<crate::modules::calibration_workflow::CalibrationWorkflow>>> = Arc::new(Mutex::new(None));
             println!("✓ CalibrationWorkflowState 创建成功");
             app.manage(calibration_workflow_state);
+            app.manage(calibration_commands::CalibrationJobState::default());
             
             // 初始化合像检测状态管理器
             let alignment_state = Arc::new(Mutex::new(alignment_commands::AlignmentWorkflowState::new()));
@@ -282,30 +323,103 @@ pub fn run() {
             calibration_commands::get_captured_images,
             calibration_commands::delete_captured_image,
             calibration_commands::run_calibration_process,
+            calibration_commands::start_calibration_job,
+            calibration_commands::cancel_calibration_job,
             calibration_commands::get_calibration_status,
             calibration_commands::stop_calibration_session,
             calibration_commands::reset_calibration_workflow,
             calibration_commands::get_calibration_config,
             calibration_commands::get_preview_frame,
             calibration_commands::get_latest_captured_image,
+            calibration_commands::get_incremental_calibration_history,
+            calibration_commands::set_duplicate_pose_policy,
+            calibration_commands::set_corner_sidecar_saving,
+            calibration_commands::set_grid_detection_budget,
+            calibration_commands::set_calibration_image_counts,
+            calibration_commands::set_max_retained_calibration_pairs,
+            calibration_commands::recalibrate_from_corner_sidecars,
+            calibration_commands::export_point_correspondences,
+            calibration_commands::export_ros_camera_info,
+            calibration_commands::get_rectify_roi_coverage,
+            calibration_commands::check_calibration_alignment_consistency,
+            calibration_commands::suggest_board_roi,
             
             // 合像检测命令
             alignment_commands::start_alignment_camera,
             alignment_commands::stop_alignment_camera,
             alignment_commands::get_alignment_status,
+            alignment_commands::get_init_state,
+            alignment_commands::validate_rectify_maps,
             alignment_commands::get_camera_preview,
             alignment_commands::get_alignment_deviation,
             alignment_commands::trigger_alignment_detection,
+            alignment_commands::run_automated_cycle,
             alignment_commands::reset_to_preview,
             alignment_commands::save_debug_images,
             alignment_commands::get_alignment_performance,
+            alignment_commands::get_alignment_latency,
+            alignment_commands::get_alignment_error_histogram,
+            alignment_commands::reevaluate_last_with_thresholds,
+            alignment_commands::get_alignment_thresholds,
+            alignment_commands::set_alignment_thresholds,
+            alignment_commands::set_centering_targets,
+            alignment_commands::get_result_metric_descriptors,
+            alignment_commands::set_preview_transport,
+            alignment_commands::get_full_resolution_region,
+            alignment_commands::get_detection_binary_mask,
+            alignment_commands::get_detection_diagnostics,
+            alignment_commands::get_anaglyph_image,
+            alignment_commands::fast_full_check,
+            alignment_commands::simulate_synthetic_grid_detection,
+            alignment_commands::set_debug_render_config,
+            alignment_commands::set_debug_overlay_stream,
+            alignment_commands::set_partial_grid_completion,
+            alignment_commands::set_exclude_unrefined_points,
+            alignment_commands::set_merged_blob_filter,
+            alignment_commands::set_detection_preprocessing,
+            alignment_commands::set_detector_config,
+            alignment_commands::auto_exposure_scan,
+            alignment_commands::set_pose_convention,
+            alignment_commands::set_pose_averaging_frames,
+            alignment_commands::set_standoff_range,
+            alignment_commands::set_convergence_range,
+            alignment_commands::check_convergence,
+            alignment_commands::set_pose_reprojection_threshold,
+            alignment_commands::set_pnp_method,
+            alignment_commands::set_magnification_mismatch_threshold,
+            alignment_commands::set_robust_statistics,
+            alignment_commands::set_detection_decimation,
+            alignment_commands::set_preview_stale_timeout,
+            alignment_commands::set_pose_kalman_filter,
+            alignment_commands::set_micrometer_calibration,
+            alignment_commands::get_micrometer_turns,
+            alignment_commands::get_alignment_report,
+            alignment_commands::set_result_logging,
+            alignment_commands::set_unit_serial_number,
+            alignment_commands::finish_unit_measurement,
+            alignment_commands::set_detection_retry_config,
+            alignment_commands::set_detection_exclusion_regions,
+            alignment_commands::measure_repeatability,
+            alignment_commands::set_detection_paused,
+            alignment_commands::save_golden_reference_from_current,
+            alignment_commands::compare_with_golden_reference,
+            alignment_commands::export_measurement_archive,
+            alignment_commands::run_live_benchmark,
+            alignment_commands::set_acquisition_target_fps,
+            alignment_commands::get_acquisition_fps,
+            alignment_commands::set_blank_frame_config,
             
             // 配置管理命令
             config_commands::get_system_config,
             config_commands::set_system_config,
+            config_commands::set_debug_image_retention,
+            config_commands::purge_debug_images,
             config_commands::get_camera_config,
             config_commands::set_camera_config,
+            config_commands::list_cameras,
+            config_commands::assign_cameras,
             config_commands::get_camera_serial,
+            config_commands::verify_camera_calibration_match,
             config_commands::get_alignment_config,
             config_commands::set_alignment_config,
             config_commands::save_config_to_file,
@@ -328,8 +442,10 @@ pub fn run() {
             config_commands::generate_compatibility_report,
             config_commands::load_current_hardware_config,
             config_commands::reset_to_default_config,
+            config_commands::reset_alignment_thresholds_to_spec,
             config_commands::export_config_to_json,
             config_commands::import_config_from_json,
+            config_commands::dump_effective_config,
             
             // 简单配置管理命令（新增）
             config_commands::read_config_file,
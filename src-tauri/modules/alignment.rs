@@ -10,25 +10,59 @@ use opencv::{
     types, 
     features2d::{SimpleBlobDetector, SimpleBlobDetector_Params},
 };
-use crate::modules::{param_io::*, rectification::Rectifier, calibration_circles::Calibrator};
+use crate::modules::{param_io::*, rectification::{Rectifier, RemapInterpolation}, calibration_circles::Calibrator};
 // 🆕 导入新的连通域圆点检测模块
-use crate::modules::alignment_circles_detection::ConnectedComponentsDetector;
+use crate::modules::alignment_circles_detection::{ConnectedComponentsDetector, DetectionDiagnostics, DetectorConfig, ExclusionRegion, GridOrderStrategy, RefineTag, swap_adjacent_columns};
+use crate::modules::result_metrics::{source_units, MetricSource, MetricUnit};
 use std::time::Instant; // 添加性能监控
+use std::path::Path;
+use std::collections::{BTreeMap, VecDeque};
+use serde::{Serialize, Deserialize};
 
 // ---------- 常量定义 ----------
-// 🔧 临时放宽容差以专注性能优化测试
+// 🔧 临时放宽容差以专注性能优化测试（默认判定阈值；部署时由 AlignmentConfig 覆盖，见 set_thresholds）
 const ROLL_TH: f64 = 5.0;        // 旋转角度阈值 (度) - 临时放宽 0.05
 const PITCH_YAW_TH: f64 = 10.0;  // 俯仰/偏航角度阈值 (度) - 临时放宽 0.10
 const RMS_TH: f64 = 100.0;         // RMS误差阈值 (像素) - 临时放宽 0.10
 const P95_TH: f64 = 100.0;        // P95误差阈值 (像素) - 临时放宽 0.20
 const MAX_TH: f64 = 200.0;        // 最大误差阈值 (像素) - 临时放宽 0.30
 
+/// 分位误差默认分位数 (P95)，P95_TH 作用于所配置分位数的误差
+pub const DEFAULT_ERROR_PERCENTILE: f64 = 95.0;
+
+/// 姿态解重投影RMS默认上限 (像素)，超出视为点对应错误，姿态判定不通过
+pub const DEFAULT_POSE_REPROJECTION_MAX_PX: f64 = 2.0;
+
+/// 左右网格跨度比 (右/左) 偏离1的默认上限，超出判定为放大倍率不一致
+pub const DEFAULT_MAGNIFICATION_MISMATCH_MAX: f64 = 0.02;
+
+/// 稳健统计默认离群倍数 k：单点误差超过 中位数 + k·MAD 判为离群点
+pub const DEFAULT_ROBUST_MAD_K: f64 = 3.5;
+
+/// MAD 下限 (像素)，各点误差几乎一致时避免把亚像素差异判为离群
+const ROBUST_MAD_FLOOR_PX: f64 = 0.05;
+
 // 🎯 居中检测阈值常量
 const CENTERING_TOLERANCE_PX: f32 = 50.0;  // 居中容差阈值 (像素)
 
-// 🎯 期望的居中位置 (基于2448×2048分辨率)
+// 🎯 期望的居中位置默认值 (基于 CENTERING_REFERENCE_SIZE，其他分辨率按比例缩放，光机变化通过 CenteringTargets 配置)
 const EXPECTED_TOP_RIGHT: (f32, f32) = (1735.0, 545.0);  // 序号0点期望位置
-const EXPECTED_BOTTOM_LEFT: (f32, f32) = (1215.0, 970.0); // 序号39点期望位置
+const EXPECTED_BOTTOM_LEFT: (f32, f32) = (1215.0, 970.0); // 末点(序号 pattern_size.area()-1)期望位置
+
+/// 默认居中期望位置对应的参考分辨率 (宽, 高)
+pub const CENTERING_REFERENCE_SIZE: (i32, i32) = (2448, 2048);
+
+/// 默认标定板规格 (每列点数, 列数) = 4×10，共40点
+pub const DEFAULT_PATTERN_SIZE: (i32, i32) = (4, 10);
+/// 默认圆心对角间距 (mm)
+pub const DEFAULT_CENTER_DISTANCE_MM: f32 = 25.0;
+
+/// 居中判定关键点序号 (右上角点, 左下角点)
+/// 
+/// 序号0位于最右列顶部；末点 (每列点数×列数-1) 位于最左列底部，4×10时为39
+pub fn centering_key_indices(pattern_size: Size) -> (usize, usize) {
+    (0, (pattern_size.area() as usize).saturating_sub(1))
+}
 
 /// 光机合像检测系统
 pub struct AlignmentSystem {
@@ -43,6 +77,8 @@ pub struct AlignmentSystem {
     // 重映射矩阵（懒加载）
     left_maps: Option<(Mat, Mat)>,
     right_maps: Option<(Mat, Mat)>,
+    // 重映射矩阵是否由相机参数重新计算（降级模式）
+    maps_regenerated: bool,
     
     // 工具组件
     rectifier: Rectifier,
@@ -52,32 +88,1277 @@ pub struct AlignmentSystem {
     
     // 图像尺寸
     image_size: Size,
+    
+    // 最近一次检测的分阶段耗时
+    last_timings: StageTimings,
+    
+    // 合像debug图像绘制样式
+    debug_render: DebugRenderConfig,
+    // 最近一次重映射后的左右图像（仅当debug图像以校正图为底图时保留）
+    last_rectified: Option<(Mat, Mat)>,
+    // 最近一次检测中左右眼圆点检测器的二值图（检测失败时查看圆点是否在二值化后保留）
+    last_binary_masks: (Option<Mat>, Option<Mat>),
+    // 最近一次检测中左右眼的筛选阶段诊断（检测失败时摘要附在错误信息中）
+    last_diagnostics: (Option<DetectionDiagnostics>, Option<DetectionDiagnostics>),
+    
+    // 实时调试叠加：开启时保留左右眼最近一次的校正图与检测到的圆点（默认关闭，避免额外拷贝）
+    mask_overlay_enabled: bool,
+    last_found_blobs: Vec<Point2f>,
+    last_overlay_sources: (Option<MaskOverlaySource>, Option<MaskOverlaySource>),
+    
+    // 最近一次检测中部分网格补全插值的点（左右眼合并）
+    interpolated_points: Vec<Point2f>,
+    // 插值点是否参与姿态/合像计算（默认排除）
+    include_interpolated_points: bool,
+    
+    // 最近一次检测输出的圆点及逐点细化来源标记（左右眼各一组，标记随排序与圆点同序）
+    corner_refine_tags: Vec<(Vector<Point2f>, Vec<RefineTag>)>,
+    // 细化点足够时姿态解算是否排除未细化点（默认不排除）
+    exclude_unrefined_points: bool,
+    
+    // 无投影（全黑帧）判定阈值
+    blank_frame_config: BlankFrameConfig,
+    
+    // 上报姿态角/调整量的坐标约定
+    pose_convention: PoseConvention,
+    
+    // solvePnP 世界坐标原点（默认第一个点）
+    object_origin: ObjectOrigin,
+    
+    // 姿态多帧平均帧数（默认1，即单帧）与左右眼最近的 solvePnP 解
+    pose_averaging_frames: usize,
+    pose_history: std::sync::Mutex<[VecDeque<PoseSample>; 2]>,
+    
+    // 工作距离合理范围，超出时告警（提示世界坐标尺度/单位错误）
+    standoff_range: StandoffRange,
+    
+    // 双眼虚像距离合格范围（会聚检测）
+    convergence_range: ConvergenceRange,
+    
+    // 分位误差所用分位数（默认95，即P95）
+    error_percentile: f64,
+    
+    // 姿态解重投影RMS上限 (像素)
+    pose_reprojection_max_px: f64,
+    
+    // solvePnP 求解方法（失败时退回 ITERATIVE）
+    pnp_method: PnpMethod,
+    
+    // 左右网格跨度比偏离1的上限（放大倍率不一致判定）
+    magnification_mismatch_max: f64,
+    
+    // 合像稳健统计（MAD 离群点剔除）
+    robust_stats: RobustStatsConfig,
+    
+    // 姿态/合像判定阈值（默认为本文件常量）
+    thresholds: AcceptanceThresholds,
+    
+    // 左眼居中判定的期望关键点位置
+    centering_targets: CenteringTargets,
+    
+    // 最近一次合像判定的残差与左右眼最近一次姿态判定，供按新阈值重新判定
+    last_alignment: std::sync::Mutex<Option<LastAlignmentResiduals>>,
+    last_poses: std::sync::Mutex<[Option<LastPoseEvaluation>; 2]>,
+}
+
+/// 分阶段耗时统计 (毫秒)
+/// 
+/// remap/detect/sort 由 detect_circles_grid 实测填充（左右眼累加），
+/// pose/alignment 由调用方在执行姿态/合像判定时填充
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StageTimings {
+    pub remap_ms: f64,      // 重映射矩阵加载 + 图像重映射
+    pub detect_ms: f64,     // 连通域圆点检测
+    pub sort_ms: f64,       // 圆点排序
+    pub pose_ms: f64,       // 姿态解算 (solvePnP)
+    pub alignment_ms: f64,  // 双眼合像分析
+}
+
+impl StageTimings {
+    /// 各阶段耗时总和
+    pub fn total_ms(&self) -> f64 {
+        self.remap_ms + self.detect_ms + self.sort_ms + self.pose_ms + self.alignment_ms
+    }
+}
+
+/// 合像debug图像底图
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DebugBackground {
+    White,
+    Black,
+    RectifiedLeft,   // 左眼重映射图像
+    RectifiedRight,  // 右眼重映射图像
+}
+
+impl DebugBackground {
+    /// 是否需要保留重映射后的图像
+    pub fn needs_rectified(&self) -> bool {
+        matches!(self, DebugBackground::RectifiedLeft | DebugBackground::RectifiedRight)
+    }
+}
+
+/// 合像debug图像绘制样式
+/// 
+/// 颜色均为 BGR 顺序 (0-255)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebugRenderConfig {
+    pub left_color: [f64; 3],     // 左眼圆点颜色
+    pub right_color: [f64; 3],    // 右眼圆点颜色
+    pub line_color: [f64; 3],     // 左右连线颜色
+    pub text_color: [f64; 3],     // 序号文字颜色
+    pub marker_radius: i32,       // 圆点半径 (像素)
+    pub line_thickness: i32,      // 连线粗细 (像素)
+    pub draw_indices: bool,       // 是否绘制序号
+    pub index_stride: usize,      // 序号间隔 (1=全部绘制，首末点始终绘制)
+    pub font_scale: f64,          // 序号字号
+    pub background: DebugBackground,
+    #[serde(default = "default_true")]
+    pub color_lines_by_error: bool,  // 连线按单点误差着色 (绿→红)，否则使用 line_color
+    #[serde(default)]
+    pub error_color_max_px: f64,     // 着色满红对应的误差 (像素)，<=0 时取本帧最大误差
+    #[serde(default)]
+    pub min_line_error_px: f64,      // 仅绘制误差不低于该值的连线 (0=全部绘制)
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for DebugRenderConfig {
+    /// 与原硬编码样式一致：白底、蓝/红点、绿线、黑字
+    fn default() -> Self {
+        Self {
+            left_color: [255.0, 0.0, 0.0],
+            right_color: [0.0, 0.0, 255.0],
+            line_color: [0.0, 255.0, 0.0],
+            text_color: [0.0, 0.0, 0.0],
+            marker_radius: 3,
+            line_thickness: 1,
+            draw_indices: true,
+            index_stride: 1,
+            font_scale: 0.4,
+            background: DebugBackground::White,
+            color_lines_by_error: true,
+            error_color_max_px: 0.0,
+            min_line_error_px: 0.0,
+        }
+    }
+}
+
+impl DebugRenderConfig {
+    /// 色盲友好配色 (Okabe-Ito 蓝/橙，黑线)
+    pub fn color_blind_safe() -> Self {
+        Self {
+            left_color: [178.0, 114.0, 0.0],   // 蓝 #0072B2
+            right_color: [0.0, 159.0, 230.0],  // 橙 #E69F00
+            line_color: [0.0, 0.0, 0.0],
+            text_color: [0.0, 0.0, 0.0],
+            ..Self::default()
+        }
+    }
+    
+    /// 打印用样式：大圆点、粗线、大字号，隔点标注避免重叠
+    pub fn print() -> Self {
+        Self {
+            marker_radius: 8,
+            line_thickness: 3,
+            index_stride: 4,
+            font_scale: 1.2,
+            ..Self::color_blind_safe()
+        }
+    }
+    
+    /// 操作员辅助：以左眼校正图为底图
+    pub fn operator_overlay() -> Self {
+        Self {
+            left_color: [255.0, 128.0, 0.0],
+            right_color: [0.0, 0.0, 255.0],
+            line_color: [0.0, 255.0, 255.0],
+            text_color: [0.0, 255.0, 255.0],
+            marker_radius: 6,
+            line_thickness: 2,
+            index_stride: 4,
+            font_scale: 1.0,
+            background: DebugBackground::RectifiedLeft,
+            ..Self::default()
+        }
+    }
+    
+    /// 按名称获取预设: default / color_blind / print / overlay
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            "color_blind" => Some(Self::color_blind_safe()),
+            "print" => Some(Self::print()),
+            "overlay" => Some(Self::operator_overlay()),
+            _ => None,
+        }
+    }
+    
+    fn scalar(color: &[f64; 3]) -> Scalar {
+        Scalar::new(color[0], color[1], color[2], 0.0)
+    }
+    
+    /// 误差着色：0 → 绿，error_max → 红 (BGR，中间经过黄色)
+    fn error_color(error: f64, error_max: f64) -> Scalar {
+        let t = if error_max > 0.0 { (error / error_max).clamp(0.0, 1.0) } else { 0.0 };
+        let (green, red) = if t < 0.5 { (255.0, 510.0 * t) } else { (510.0 * (1.0 - t), 255.0) };
+        Scalar::new(0.0, green, red, 0.0)
+    }
+}
+
+/// 无投影（全黑帧）判定阈值
+/// 
+/// 均值亮度与最大亮度同时不高于阈值时判定为无投影信号
+/// （光机未上电/遮光片未打开），检测前直接返回，不再尝试圆点检测
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlankFrameConfig {
+    pub enabled: bool,             // 是否启用无投影检查
+    pub max_mean_intensity: f64,   // 均值亮度上限 (0-255)
+    pub max_peak_intensity: f64,   // 最大亮度上限 (0-255)
+}
+
+impl Default for BlankFrameConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_mean_intensity: 10.0,
+            max_peak_intensity: 50.0,
+        }
+    }
+}
+
+impl BlankFrameConfig {
+    /// 按阈值判定是否为无投影帧
+    pub fn is_blank(&self, intensity: &FrameIntensity) -> bool {
+        self.enabled
+            && intensity.mean <= self.max_mean_intensity
+            && intensity.max <= self.max_peak_intensity
+    }
+}
+
+/// 姿态角分量
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PoseAxis {
+    Roll,   // 绕光轴 (Z) 旋转
+    Pitch,  // 俯仰，绕水平轴 (X) 旋转
+    Yaw,    // 偏航，绕竖直轴 (Y) 旋转
+}
+
+/// 单个上报角度的来源分量与符号
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AxisMapping {
+    pub source: PoseAxis,  // 取自哪个原始角度
+    pub invert: bool,      // 是否取反
+}
+
+/// 上报姿态角与调整量的坐标约定（按工位机械结构配置）
+/// 
+/// 原始 roll/pitch/yaw 按映射重排、变号后上报，调整建议 = -上报角度，
+/// 因此 "顺时针/逆时针" 与工位千分尺实际旋向一致。
+/// 居中/合像的像素调整量按 invert_x / invert_y 变号。
+/// 姿态通过判定始终基于原始角度，不受约定影响。
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PoseConvention {
+    pub roll: AxisMapping,
+    pub pitch: AxisMapping,
+    pub yaw: AxisMapping,
+    pub invert_x: bool,    // X方向像素调整量取反
+    pub invert_y: bool,    // Y方向像素调整量取反
+}
+
+impl Default for PoseConvention {
+    fn default() -> Self {
+        Self {
+            roll: AxisMapping { source: PoseAxis::Roll, invert: false },
+            pitch: AxisMapping { source: PoseAxis::Pitch, invert: false },
+            yaw: AxisMapping { source: PoseAxis::Yaw, invert: false },
+            invert_x: false,
+            invert_y: false,
+        }
+    }
+}
+
+impl PoseConvention {
+    /// 三个上报角度必须各取一个不同的原始分量
+    pub fn validate(&self) -> Result<(), String> {
+        let sources = [self.roll.source, self.pitch.source, self.yaw.source];
+        for axis in [PoseAxis::Roll, PoseAxis::Pitch, PoseAxis::Yaw] {
+            if !sources.contains(&axis) {
+                return Err(format!("姿态轴映射无效: 原始分量 {:?} 未被使用 (映射: {:?})", axis, sources));
+            }
+        }
+        Ok(())
+    }
+    
+    /// 原始 (roll, pitch, yaw) → 上报 (roll, pitch, yaw)
+    pub fn apply(&self, roll: f64, pitch: f64, yaw: f64) -> (f64, f64, f64) {
+        let map = |m: &AxisMapping| {
+            let value = match m.source {
+                PoseAxis::Roll => roll,
+                PoseAxis::Pitch => pitch,
+                PoseAxis::Yaw => yaw,
+            };
+            if m.invert { -value } else { value }
+        };
+        (map(&self.roll), map(&self.pitch), map(&self.yaw))
+    }
+    
+    /// X/Y方向像素调整量符号
+    pub fn pixel_signs(&self) -> (f64, f64) {
+        (if self.invert_x { -1.0 } else { 1.0 }, if self.invert_y { -1.0 } else { 1.0 })
+    }
+    
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// solvePnP 世界坐标原点选择
+/// 
+/// 原点只平移物体坐标系，不改变旋转：roll/pitch/yaw 均由旋转矩阵计算，与原点无关；
+/// 原点只影响 tvec（原点在相机系下的位置），即工作距离 standoff。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ObjectOrigin {
+    #[default]
+    FirstPoint,          // 第一个点为原点（原有行为）
+    Centroid,            // 所有点的质心为原点
+    CustomIndex(usize),  // 指定序号的点为原点
+}
+
+/// 世界坐标平移到所选原点 (z 置 0)
+pub fn build_object_points(world_points: &Vector<Point3f>, origin: ObjectOrigin) -> Result<Vector<Point3f>, opencv::Error> {
+    let n = world_points.len();
+    if n == 0 {
+        return Err(opencv::Error::new(opencv::core::StsBadArg, "世界坐标为空".to_string()));
+    }
+    
+    let (offset_x, offset_y) = match origin {
+        ObjectOrigin::FirstPoint => {
+            let p = world_points.get(0)?;
+            (p.x, p.y)
+        }
+        ObjectOrigin::Centroid => {
+            let (sx, sy) = world_points.iter().fold((0.0f64, 0.0f64), |(sx, sy), p| (sx + p.x as f64, sy + p.y as f64));
+            ((sx / n as f64) as f32, (sy / n as f64) as f32)
+        }
+        ObjectOrigin::CustomIndex(index) => {
+            if index >= n {
+                return Err(opencv::Error::new(opencv::core::StsOutOfRange,
+                    format!("原点序号 {} 超出范围 (共{}个点)", index, n)));
+            }
+            let p = world_points.get(index)?;
+            (p.x, p.y)
+        }
+    };
+    
+    Ok(world_points.iter()
+        .map(|p| Point3f::new(p.x - offset_x, p.y - offset_y, 0.0))
+        .collect())
+}
+
+/// solvePnP 求解方法
+/// 
+/// IPPE 专用于平面标定板（原有行为），大倾角时可能退化；ITERATIVE (LM迭代) 最稳健；SQPNP 为全局最优解法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PnpMethod {
+    #[default]
+    Ippe,
+    Iterative,
+    Sqpnp,
+}
+
+impl PnpMethod {
+    /// 对应的 OpenCV SOLVEPNP_* 标志
+    pub fn flag(&self) -> i32 {
+        match self {
+            PnpMethod::Ippe => calib3d::SOLVEPNP_IPPE,
+            PnpMethod::Iterative => calib3d::SOLVEPNP_ITERATIVE,
+            PnpMethod::Sqpnp => calib3d::SOLVEPNP_SQPNP,
+        }
+    }
+}
+
+/// solvePnP 单次求解结果
+pub type PnpSolution = ([f64; 3], [f64; 3]);
+
+/// 按所选方法求解，失败或 rvec/tvec 非有限值时退回 ITERATIVE；返回实际产生结果的方法
+/// 
+/// solve(method) 返回 Ok(None) 表示该方法未给出解
+pub fn solve_pnp_with_fallback<F>(method: PnpMethod, mut solve: F) -> Result<(PnpSolution, PnpMethod), opencv::Error>
+where
+    F: FnMut(PnpMethod) -> Result<Option<PnpSolution>, opencv::Error>,
+{
+    let is_finite = |(rvec, tvec): &PnpSolution| rvec.iter().chain(tvec.iter()).all(|v| v.is_finite());
+    let failure = match solve(method) {
+        Ok(Some(solution)) if is_finite(&solution) => return Ok((solution, method)),
+        Ok(Some(_)) => "解非有限值".to_string(),
+        Ok(None) => "未求得解".to_string(),
+        Err(e) if method != PnpMethod::Iterative => e.to_string(),
+        Err(e) => return Err(e),
+    };
+    if method == PnpMethod::Iterative {
+        return Err(opencv::Error::new(opencv::core::StsNoConv, format!("solvePnP (ITERATIVE) {}", failure)));
+    }
+    
+    println!("⚠️ solvePnP ({:?}) {}，退回 ITERATIVE", method, failure);
+    match solve(PnpMethod::Iterative)? {
+        Some(solution) if is_finite(&solution) => Ok((solution, PnpMethod::Iterative)),
+        _ => Err(opencv::Error::new(opencv::core::StsNoConv,
+            format!("solvePnP ({:?}) {}，ITERATIVE 退回后仍无有效解", method, failure))),
+    }
+}
+
+/// 将校正后的左右图合成红/青立体图：左图→红通道，右图→绿/蓝通道
+/// 
+/// 左右圆点重合处呈灰白色，未对齐处出现红/青色边缘，肉眼即可判断合像偏差方向
+pub fn compose_anaglyph(left_rect: &Mat, right_rect: &Mat) -> Result<Mat, opencv::Error> {
+    if left_rect.size()? != right_rect.size()? {
+        return Err(opencv::Error::new(opencv::core::StsUnmatchedSizes,
+            format!("左右图尺寸不一致: {:?} vs {:?}", left_rect.size()?, right_rect.size()?)));
+    }
+    let to_gray = |image: &Mat| -> Result<Mat, opencv::Error> {
+        let mut gray = Mat::default();
+        if image.channels() == 1 {
+            image.copy_to(&mut gray)?;
+        } else {
+            imgproc::cvt_color(image, &mut gray, imgproc::COLOR_BGR2GRAY, 0, AlgorithmHint::ALGO_HINT_DEFAULT)?;
+        }
+        Ok(gray)
+    };
+    let left = to_gray(left_rect)?;
+    let right = to_gray(right_rect)?;
+    
+    // OpenCV 通道顺序 BGR
+    let channels = Vector::<Mat>::from_iter([right.clone(), right, left]);
+    let mut anaglyph = Mat::default();
+    opencv::core::merge(&channels, &mut anaglyph)?;
+    Ok(anaglyph)
+}
+
+/// 调试叠加图的原始数据：校正图 + 本次检测找到的圆点（排序前，含未凑满40个的情况）
+struct MaskOverlaySource {
+    rectified: Mat,
+    blobs: Vec<Point2f>,
+}
+
+/// 合成检测调试叠加图：校正图为底，二值图前景着绿色，检测到的圆点画红圈，按 scale 缩放
+/// 
+/// 调参时实时查看阈值/面积窗口/排除区域对二值化与圆点检测的影响；二值图尺寸与底图不一致时不着色
+pub fn compose_mask_overlay(rectified: &Mat, mask: Option<&Mat>, blobs: &[Point2f], scale: f64) -> Result<Mat, opencv::Error> {
+    let mut base = Mat::default();
+    if rectified.channels() == 1 {
+        imgproc::cvt_color(rectified, &mut base, imgproc::COLOR_GRAY2BGR, 0, AlgorithmHint::ALGO_HINT_DEFAULT)?;
+    } else {
+        rectified.copy_to(&mut base)?;
+    }
+    
+    let mut overlay = base.clone();
+    if let Some(mask) = mask.filter(|mask| mask.size().ok() == base.size().ok()) {
+        let mut tinted = base.clone();
+        tinted.set_to(&Scalar::new(0.0, 255.0, 0.0, 0.0), mask)?;
+        opencv::core::add_weighted(&base, 0.6, &tinted, 0.4, 0.0, &mut overlay, -1)?;
+    }
+    for blob in blobs {
+        let center = Point::new(blob.x.round() as i32, blob.y.round() as i32);
+        imgproc::circle(&mut overlay, center, 20, Scalar::new(0.0, 0.0, 255.0, 0.0), 4, imgproc::LINE_AA, 0)?;
+    }
+    
+    if scale > 0.0 && scale < 1.0 {
+        let mut resized = Mat::default();
+        imgproc::resize(&overlay, &mut resized, Size::default(), scale, scale, imgproc::INTER_AREA)?;
+        return Ok(resized);
+    }
+    Ok(overlay)
+}
+
+/// 合成圆阵图像参数（无硬件时前端开发/演示用的确定性数据源）
+/// 
+/// 圆阵以质心为原点，按 Rz(roll)·Ry(tilt_y)·Rx(tilt_x) 旋转后置于 distance_mm 处，
+/// 质心投影到图像中心 + shift；右眼在左眼基础上再平移 right_dx/dy 像素、旋转 right_roll。
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyntheticGridParams {
+    pub distance_mm: f64,     // 圆阵质心到相机的距离 (mm)
+    pub roll_deg: f64,        // 绕光轴旋转 (度)
+    pub tilt_x_deg: f64,      // 圆阵平面绕X轴倾斜 (度)
+    pub tilt_y_deg: f64,      // 圆阵平面绕Y轴倾斜 (度)
+    pub shift_x_px: f64,      // 质心相对图像中心的偏移 (像素)
+    pub shift_y_px: f64,
+    pub right_dx_px: f64,     // 右眼相对左眼的偏移 (像素)
+    pub right_dy_px: f64,
+    pub right_roll_deg: f64,  // 右眼相对左眼的额外旋转 (度)
+    pub noise_std: f64,       // 高斯噪声标准差 (灰度级)，0为无噪声
+    pub seed: u64,            // 噪声随机种子，相同参数生成相同图像
+}
+
+impl Default for SyntheticGridParams {
+    fn default() -> Self {
+        Self {
+            distance_mm: 530.0, // f=3000px 时圆心水平间距约100px
+            roll_deg: 0.0,
+            tilt_x_deg: 0.0,
+            tilt_y_deg: 0.0,
+            shift_x_px: 0.0,
+            shift_y_px: 0.0,
+            right_dx_px: 0.0,
+            right_dy_px: 0.0,
+            right_roll_deg: 0.0,
+            noise_std: 2.0,
+            seed: 1,
+        }
+    }
+}
+
+impl SyntheticGridParams {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.distance_mm > 0.0) {
+            return Err(format!("合成距离必须大于0: {}", self.distance_mm));
+        }
+        if self.tilt_x_deg.abs() >= 60.0 || self.tilt_y_deg.abs() >= 60.0 {
+            return Err(format!("倾斜角必须在±60°以内: ({}, {})", self.tilt_x_deg, self.tilt_y_deg));
+        }
+        if !(self.noise_std >= 0.0) {
+            return Err(format!("噪声标准差不能为负: {}", self.noise_std));
+        }
+        Ok(())
+    }
+}
+
+/// 合成圆点灰度 (背景/圆点)
+const SYNTHETIC_BACKGROUND: f64 = 30.0;
+const SYNTHETIC_DOT: f64 = 230.0;
+/// 每个圆点边界的采样点数
+const SYNTHETIC_CIRCLE_SEGMENTS: usize = 48;
+
+/// 按合成姿态投影世界坐标并绘制圆阵 (CV_8UC1)
+/// 
+/// 每个圆点按真实直径取边界点投影后填充，倾斜时自然呈椭圆；
+/// 噪声由 seed 决定的伪随机序列生成，同一参数的输出逐像素一致
+pub fn render_synthetic_grid(
+    world_points: &Vector<Point3f>,
+    diameter_mm: f64,
+    camera_matrix: &Mat,
+    image_size: Size,
+    params: &SyntheticGridParams,
+    is_right: bool,
+) -> Result<Mat, Box<dyn std::error::Error>> {
+    params.validate()?;
+    let object_points = build_object_points(world_points, ObjectOrigin::Centroid)?;
+    
+    let (roll, dx, dy) = if is_right {
+        (params.roll_deg + params.right_roll_deg,
+         params.shift_x_px + params.right_dx_px,
+         params.shift_y_px + params.right_dy_px)
+    } else {
+        (params.roll_deg, params.shift_x_px, params.shift_y_px)
+    };
+    
+    // R = Rz(roll)·Ry(tilt_y)·Rx(tilt_x)，与 pose_angles 的 ZYX 约定一致（tilt_y → yaw, tilt_x → pitch）
+    let (sz, cz) = roll.to_radians().sin_cos();
+    let (sy, cy) = params.tilt_y_deg.to_radians().sin_cos();
+    let (sx, cx) = params.tilt_x_deg.to_radians().sin_cos();
+    let rotation = vec2d_to_mat_f64(&[
+        vec![cz * cy, cz * sy * sx - sz * cx, cz * sy * cx + sz * sx],
+        vec![sz * cy, sz * sy * sx + cz * cx, sz * sy * cx - cz * sx],
+        vec![-sy, cy * sx, cy * cx],
+    ])?;
+    let mut rvec = Mat::default();
+    calib3d::rodrigues(&rotation, &mut rvec, &mut Mat::default())?;
+    
+    // 质心投影到 (cx + dx, cy + dy)
+    let fx = *camera_matrix.at_2d::<f64>(0, 0)?;
+    let fy = *camera_matrix.at_2d::<f64>(1, 1)?;
+    let z = params.distance_mm;
+    let tvec = vec_to_mat_f64(&[dx * z / fx, dy * z / fy, z])?;
+    
+    // 圆点边界采样点
+    let radius = (diameter_mm / 2.0) as f32;
+    let boundary: Vector<Point3f> = object_points.iter()
+        .flat_map(|c| (0..SYNTHETIC_CIRCLE_SEGMENTS).map(move |k| {
+            let theta = k as f32 / SYNTHETIC_CIRCLE_SEGMENTS as f32 * std::f32::consts::TAU;
+            Point3f::new(c.x + radius * theta.cos(), c.y + radius * theta.sin(), 0.0)
+        }))
+        .collect();
+    let mut projected = Vector::<Point2f>::new();
+    let no_distortion = Mat::zeros(5, 1, CV_64F)?.to_mat()?;
+    calib3d::project_points(&boundary, &rvec, &tvec, camera_matrix, &no_distortion,
+                            &mut projected, &mut Mat::default(), 0.0)?;
+    
+    // 4位亚像素精度绘制
+    let mut image = Mat::new_rows_cols_with_default(
+        image_size.height, image_size.width, opencv::core::CV_8UC1, Scalar::all(SYNTHETIC_BACKGROUND))?;
+    let projected = projected.to_vec();
+    for circle in projected.chunks(SYNTHETIC_CIRCLE_SEGMENTS) {
+        let polygon: Vector<Point> = circle.iter()
+            .map(|p| Point::new((p.x * 16.0).round() as i32, (p.y * 16.0).round() as i32))
+            .collect();
+        imgproc::fill_convex_poly(&mut image, &polygon, Scalar::all(SYNTHETIC_DOT), imgproc::LINE_AA, 4)?;
+    }
+    
+    if params.noise_std > 0.0 {
+        add_gaussian_noise(&mut image, params.noise_std, params.seed.wrapping_add(is_right as u64))?;
+    }
+    Ok(image)
+}
+
+/// 叠加确定性高斯噪声 (xorshift64* + Box-Muller)
+fn add_gaussian_noise(image: &mut Mat, std_dev: f64, seed: u64) -> Result<(), opencv::Error> {
+    let mut state = seed ^ 0x9E37_79B9_7F4A_7C15;
+    let mut next_uniform = || {
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        ((state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    };
+    for pixel in image.data_bytes_mut()?.iter_mut() {
+        let (u1, u2) = (next_uniform(), next_uniform());
+        let noise = (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos() * std_dev;
+        *pixel = (*pixel as f64 + noise).round().clamp(0.0, 255.0) as u8;
+    }
+    Ok(())
+}
+
+/// 网格几何校验：仿射拟合残差上限（相对最近邻圆心距）
+const GRID_RESIDUAL_MAX_RATIO: f64 = 0.2;
+
+/// 世界坐标 → 图像坐标的仿射拟合结果（用于校验排序后的圆点与期望 4×10 布局一致）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridFit {
+    pub residual_ratio: f64,  // 拟合残差RMS / 最近邻圆心距（像素）
+    pub determinant: f64,     // 仿射线性部分行列式，<0 表示镜像（图像被转置/翻转）
+}
+
+impl GridFit {
+    /// 点序与布局一致（错误的点序在仿射下无法拟合，残差约为半个圆心距以上）
+    pub fn is_consistent(&self) -> bool {
+        self.residual_ratio <= GRID_RESIDUAL_MAX_RATIO
+    }
+    
+    pub fn is_mirrored(&self) -> bool {
+        self.determinant < 0.0
+    }
+}
+
+/// 3x3 线性方程组 (克拉默法则)
+fn solve_3x3(m: &[[f64; 3]; 3], b: &[f64; 3]) -> Option<[f64; 3]> {
+    let det3 = |m: &[[f64; 3]; 3]| {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    };
+    let d = det3(m);
+    if d.abs() < 1e-12 {
+        return None;
+    }
+    let mut out = [0.0; 3];
+    for k in 0..3 {
+        let mut mk = *m;
+        for r in 0..3 {
+            mk[r][k] = b[r];
+        }
+        out[k] = det3(&mk) / d;
+    }
+    Some(out)
+}
+
+/// 最小二乘拟合 世界坐标(x, y) → 图像坐标 的仿射变换，评估点序与布局的一致性
+pub fn fit_grid_affine(world_points: &Vector<Point3f>, image_points: &Vector<Point2f>) -> Option<GridFit> {
+    if world_points.len() != image_points.len() || world_points.len() < 3 {
+        return None;
+    }
+    let pairs: Vec<(Point3f, Point2f)> = world_points.iter().zip(image_points.iter()).collect();
+    
+    let mut m = [[0.0f64; 3]; 3];
+    let (mut bu, mut bv) = ([0.0f64; 3], [0.0f64; 3]);
+    for (w, p) in &pairs {
+        let a = [w.x as f64, w.y as f64, 1.0];
+        for r in 0..3 {
+            for c in 0..3 {
+                m[r][c] += a[r] * a[c];
+            }
+            bu[r] += a[r] * p.x as f64;
+            bv[r] += a[r] * p.y as f64;
+        }
+    }
+    let u = solve_3x3(&m, &bu)?;
+    let v = solve_3x3(&m, &bv)?;
+    let determinant = u[0] * v[1] - u[1] * v[0];
+    
+    let sq_sum: f64 = pairs.iter().map(|(w, p)| {
+        let du = u[0] * w.x as f64 + u[1] * w.y as f64 + u[2] - p.x as f64;
+        let dv = v[0] * w.x as f64 + v[1] * w.y as f64 + v[2] - p.y as f64;
+        du * du + dv * dv
+    }).sum();
+    let rms = (sq_sum / pairs.len() as f64).sqrt();
+    
+    // 最近邻圆心距（世界坐标）换算为像素
+    let min_world_dist = pairs.iter().enumerate()
+        .flat_map(|(i, (a, _))| pairs[i + 1..].iter().map(move |(b, _)| ((a.x - b.x) as f64).hypot((a.y - b.y) as f64)))
+        .filter(|d| *d > 1e-6)
+        .fold(f64::INFINITY, f64::min);
+    let spacing_px = min_world_dist * determinant.abs().sqrt();
+    if !spacing_px.is_finite() || spacing_px < 1e-6 {
+        return None;
+    }
+    
+    Some(GridFit { residual_ratio: rms / spacing_px, determinant })
+}
+
+/// 姿态多帧平均帧数上限
+pub const MAX_POSE_AVERAGING_FRAMES: usize = 30;
+
+/// 单帧 solvePnP 解（旋转向量 + 平移向量）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoseSample {
+    pub rvec: [f64; 3],
+    pub tvec: [f64; 3],
+}
+
+/// 多帧平均姿态的离散程度
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct PoseSpread {
+    pub samples: usize,   // 参与平均的帧数
+    pub spread_deg: f64,  // roll/pitch/yaw 中最大的标准差 (度)，单帧时为0
+}
+
+/// 多帧平均后的原始姿态角 (度，未应用 PoseConvention)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AveragedPose {
+    pub roll: f64,
+    pub pitch: f64,
+    pub yaw: f64,
+    pub spread: PoseSpread,
+    pub standoff_mm: f64,  // 平均平移向量的模长 (mm)
+}
+
+/// 光机工作距离合理范围 (mm)
+/// 
+/// solvePnP 世界坐标由 center_distance (mm) 生成，tvec 单位即为 mm；
+/// 工作距离 |tvec| 明显超出工装范围通常意味着世界坐标尺度/单位错误
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StandoffRange {
+    pub min_mm: f64,
+    pub max_mm: f64,
+}
+
+impl Default for StandoffRange {
+    fn default() -> Self {
+        Self { min_mm: 200.0, max_mm: 1500.0 }
+    }
+}
+
+impl StandoffRange {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.min_mm > 0.0 && self.min_mm < self.max_mm && self.max_mm.is_finite()) {
+            return Err(format!("工作距离范围无效: [{}, {}] mm", self.min_mm, self.max_mm));
+        }
+        Ok(())
+    }
+    
+    pub fn contains(&self, standoff_mm: f64) -> bool {
+        standoff_mm >= self.min_mm && standoff_mm <= self.max_mm
+    }
+}
+
+/// 单光机工作距离上报
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct StandoffCheck {
+    pub standoff_mm: f64,  // |tvec| (mm)
+    pub plausible: bool,   // 是否在 StandoffRange 内
+}
+
+/// 双眼虚像距离合格范围 (mm)，按光机设计虚像距离配置
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ConvergenceRange {
+    pub min_mm: f64,
+    pub max_mm: f64,
+}
+
+impl Default for ConvergenceRange {
+    fn default() -> Self {
+        Self { min_mm: 1000.0, max_mm: 10000.0 }
+    }
+}
+
+impl ConvergenceRange {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.min_mm > 0.0 && self.min_mm < self.max_mm && self.max_mm.is_finite()) {
+            return Err(format!("虚像距离范围无效: [{}, {}] mm", self.min_mm, self.max_mm));
+        }
+        Ok(())
+    }
+}
+
+/// 双眼会聚（虚像距离）检测结果
+/// 
+/// 合像RMS只反映左右相对偏移；视差经立体标定换算为绝对深度，可发现会聚角（虚像距离）不良
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ConvergenceCheck {
+    pub mean_disparity_px: f64,    // 平均水平视差 xL - xR (像素，校正后)
+    pub disparity_std_px: f64,     // 各点视差标准差 (像素)
+    pub virtual_image_distance_mm: Option<f64>,  // 平均视差对应的虚像深度 Z，视差 ≤ 0（无穷远/发散）时为None
+    pub range: ConvergenceRange,
+    pub pass: bool,
+}
+
+impl ConvergenceCheck {
+    pub fn message(&self) -> String {
+        match self.virtual_image_distance_mm {
+            Some(distance) if self.pass => format!("✓ 虚像距离 {:.0} mm (视差 {:.2} px)", distance, self.mean_disparity_px),
+            Some(distance) => format!("❌ 虚像距离 {:.0} mm 超出范围 [{:.0}, {:.0}] mm (视差 {:.2} px)",
+                                      distance, self.range.min_mm, self.range.max_mm, self.mean_disparity_px),
+            None => format!("❌ 双眼视差 {:.2} px ≤ 0，光机不会聚（发散或会聚于无穷远）", self.mean_disparity_px),
+        }
+    }
+}
+
+/// 旋转向量 → 单位四元数 (w, x, y, z)
+fn rvec_to_quaternion(rvec: &[f64; 3]) -> [f64; 4] {
+    let theta = (rvec[0] * rvec[0] + rvec[1] * rvec[1] + rvec[2] * rvec[2]).sqrt();
+    if theta < 1e-12 {
+        return [1.0, 0.0, 0.0, 0.0];
+    }
+    let s = (theta / 2.0).sin() / theta;
+    [(theta / 2.0).cos(), rvec[0] * s, rvec[1] * s, rvec[2] * s]
+}
+
+/// 单位四元数 → 旋转矩阵
+fn quaternion_to_matrix(q: &[f64; 4]) -> [[f64; 3]; 3] {
+    let [w, x, y, z] = *q;
+    [
+        [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z), 2.0 * (x * z + w * y)],
+        [2.0 * (x * y + w * z), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x)],
+        [2.0 * (x * z - w * y), 2.0 * (y * z + w * x), 1.0 - 2.0 * (x * x + y * y)],
+    ]
+}
+
+/// 由旋转矩阵计算原始 (roll, pitch, yaw)，ZYX 欧拉角约定：R = Rz(roll)·Ry(yaw)·Rx(pitch)
+/// 
+/// roll 绕光轴 (Z)，yaw 绕竖直轴 (Y)，pitch 绕水平轴 (X)；标定板正对相机时 R = I，三者均为0。
+/// 平移不参与计算，标定板在视野中的位置不影响姿态角
+fn pose_angles(rot: &[[f64; 3]; 3]) -> (f64, f64, f64) {
+    let roll = f64::atan2(rot[1][0], rot[0][0]).to_degrees();
+    let yaw = (-rot[2][0]).clamp(-1.0, 1.0).asin().to_degrees();
+    let pitch = f64::atan2(rot[2][1], rot[2][2]).to_degrees();
+    (roll, pitch, yaw)
+}
+
+/// 角度差归一化到 (-180, 180]
+fn wrap_angle_deg(diff: f64) -> f64 {
+    let wrapped = (diff + 180.0).rem_euclid(360.0) - 180.0;
+    if wrapped == -180.0 { 180.0 } else { wrapped }
+}
+
+/// 多帧姿态平均
+/// 
+/// 旋转按四元数平均（符号对齐到第一帧后求和再归一化，即旋转的弦距离均值），
+/// 避免直接平均欧拉角在 ±180° 附近出错；平移向量取算术平均。
+/// 离散程度为各帧 roll/pitch/yaw 相对平均值的标准差中的最大值。
+pub fn average_pose_samples(samples: &[PoseSample]) -> Option<AveragedPose> {
+    let first = samples.first()?;
+    let reference = rvec_to_quaternion(&first.rvec);
+    
+    let mut q_sum = [0.0f64; 4];
+    let mut t_sum = [0.0f64; 3];
+    for sample in samples {
+        let q = rvec_to_quaternion(&sample.rvec);
+        let dot: f64 = (0..4).map(|i| q[i] * reference[i]).sum();
+        let sign = if dot < 0.0 { -1.0 } else { 1.0 };
+        for i in 0..4 {
+            q_sum[i] += sign * q[i];
+        }
+        for i in 0..3 {
+            t_sum[i] += sample.tvec[i];
+        }
+    }
+    
+    let norm = q_sum.iter().map(|v| v * v).sum::<f64>().sqrt();
+    if norm < 1e-12 {
+        return None;
+    }
+    let n = samples.len() as f64;
+    let q_mean = q_sum.map(|v| v / norm);
+    let t_mean = t_sum.map(|v| v / n);
+    let standoff_mm = t_mean.iter().map(|v| v * v).sum::<f64>().sqrt();
+    let (roll, pitch, yaw) = pose_angles(&quaternion_to_matrix(&q_mean));
+    
+    let mut sq = [0.0f64; 3];
+    for sample in samples {
+        let rot = quaternion_to_matrix(&rvec_to_quaternion(&sample.rvec));
+        let (r, p, y) = pose_angles(&rot);
+        sq[0] += wrap_angle_deg(r - roll).powi(2);
+        sq[1] += wrap_angle_deg(p - pitch).powi(2);
+        sq[2] += (y - yaw).powi(2);
+    }
+    let spread_deg = sq.iter().map(|s| (s / n).sqrt()).fold(0.0, f64::max);
+    
+    Some(AveragedPose {
+        roll,
+        pitch,
+        yaw,
+        spread: PoseSpread { samples: samples.len(), spread_deg },
+        standoff_mm,
+    })
+}
+
+/// 单帧亮度统计 (灰度 0-255)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct FrameIntensity {
+    pub mean: f64,
+    pub max: f64,
+}
+
+impl FrameIntensity {
+    /// 计算图像的均值/最大亮度（多通道图像按第一通道统计）
+    pub fn measure(image: &Mat) -> Result<Self, opencv::Error> {
+        let mean = opencv::core::mean(image, &opencv::core::no_array())?[0];
+        let mut max = 0.0;
+        opencv::core::min_max_loc(image, None, Some(&mut max), None, None, &opencv::core::no_array())?;
+        Ok(Self { mean, max })
+    }
 }
 
 /// 单光机姿态检测结果
 #[derive(Debug)]
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct SingleEyePoseResult {
     pub roll: f64,   // 旋转角 (度)
     pub pitch: f64,  // 俯仰角 (度)
     pub yaw: f64,    // 偏航角 (度)
     pub pass: bool,  // 是否通过
+    pub spread: PoseSpread,  // 多帧平均的帧数与离散程度
+    pub standoff: StandoffCheck,  // 工作距离 (mm) 及是否合理
+    pub reprojection_rms: f64,    // 本帧 solvePnP 解的重投影RMS (像素)
+    pub reject_reason: Option<String>,  // 姿态角之外的不通过原因（如重投影校验失败）
+    #[serde(default)]
+    pub method_used: PnpMethod,   // 本帧实际产生姿态解的 solvePnP 方法（含退回）
+}
+
+impl SingleEyePoseResult {
+    /// 判定说明，如 "✓ 左眼姿态检测通过" / "❌ 左眼姿态超出容差 - roll=..."
+    pub fn message(&self, eye: &str) -> String {
+        match (&self.reject_reason, self.pass) {
+            (Some(reason), _) => format!("❌ {}姿态解不可信 - {}", eye, reason),
+            (None, true) => format!("✓ {}姿态检测通过", eye),
+            (None, false) => format!("❌ {}姿态超出容差 - roll={:.3}°, pitch={:.3}°, yaw={:.3}°",
+                                     eye, self.roll, self.pitch, self.yaw),
+        }
+    }
 }
 
 /// 双光机合像检测结果
 #[derive(Debug)]
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct DualEyeAlignmentResult {
     pub mean_dx: f64,  // x方向平均偏差 (像素)
     pub mean_dy: f64,  // y方向平均偏差 (像素)
     pub rms: f64,      // RMS误差 (像素)
-    pub p95: f64,      // P95误差 (像素)
+    pub p95: f64,      // 分位误差 (像素)，分位数见 percentile (默认P95)
     pub max_err: f64,  // 最大误差 (像素)
     pub pass: bool,    // 是否通过
+    pub percentile: f64, // p95 字段实际使用的分位数
+    pub magnification: MagnificationCheck, // 左右网格放大倍率比较
+    #[serde(default)]
+    pub robust_rms: Option<f64>,   // 剔除离群点后的RMS (像素)，未启用稳健统计时为None
+    #[serde(default)]
+    pub robust_p95: Option<f64>,   // 剔除离群点后的分位误差 (像素)
+    #[serde(default)]
+    pub outlier_indices: Vec<usize>, // MAD 离群点序号
+}
+
+impl DualEyeAlignmentResult {
+    /// 分位误差标签，如 "P95" / "P99" / "P97.5"
+    pub fn percentile_label(&self) -> String {
+        percentile_label(self.percentile)
+    }
+    
+    /// 右眼调整提示（左右眼按逻辑分配，见相机配置 swap_eyes）
+    /// 
+    /// 放大倍率不一致时平移无法消除残差，返回倍率提示
+    pub fn adjustment_hint(&self) -> String {
+        if self.magnification.mismatch {
+            return self.magnification.message();
+        }
+        format!(
+            "调整提示: Δx={:.3}px {}, Δy={:.3}px {}",
+            self.mean_dx,
+            if self.mean_dx > 0.0 { "(右眼向左调)" } else { "(右眼向右调)" },
+            self.mean_dy,
+            if self.mean_dy < 0.0 { "(右眼向上调)" } else { "(右眼向下调)" }
+        )
+    }
+}
+
+/// 标定与合像模块一致性自检结果
+/// 
+/// 两个模块各自生成世界坐标、各自排序圆点；任一处不一致都会让姿态解产生系统偏差而不报错
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleConsistencyReport {
+    pub world_points_match: bool,
+    pub world_point_count: (usize, usize),     // (标定, 合像)
+    pub world_point_max_diff_mm: f64,          // 相对点0的坐标最大差 (原点约定不同不计入)
+    pub calibration_grid_order: GridOrderStrategy,
+    pub ordering_match: bool,
+    pub ordering_mismatched_indices: Vec<usize>, // 两种排序结果不同的序号
+    pub calibration_order_errors: usize,       // 标定排序与世界坐标点序不符的点数
+    pub alignment_order_errors: usize,         // 合像排序与世界坐标点序不符的点数
+    pub consistent: bool,
+    pub divergences: Vec<String>,              // 不一致项说明
+}
+
+/// 一致性自检的坐标容差 (mm / 像素)
+const CONSISTENCY_TOLERANCE: f64 = 1e-3;
+
+/// 左右眼网格放大倍率比较
+/// 
+/// 两台光机缩放不同时，两侧都能检测到完整网格，但合像残差随到中心距离增大，
+/// 单看 RMS 无法区分倍率缺陷与位置偏差。以网格跨度（各圆点到质心距离的均方根，与网格旋转无关）比较左右尺度。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct MagnificationCheck {
+    pub left_span_px: f64,   // 左眼网格跨度 (像素)
+    pub right_span_px: f64,  // 右眼网格跨度 (像素)
+    pub ratio: f64,          // 右/左 跨度比
+    pub max_deviation: f64,  // |ratio - 1| 上限
+    pub mismatch: bool,      // 是否判定为放大倍率不一致
+}
+
+impl MagnificationCheck {
+    /// 比较左右网格跨度，任一侧无法计算跨度时返回 None
+    pub fn compare(corners_left: &Vector<Point2f>, corners_right: &Vector<Point2f>, max_deviation: f64) -> Option<Self> {
+        let left_span_px = grid_span_px(corners_left)?;
+        let right_span_px = grid_span_px(corners_right)?;
+        let ratio = right_span_px / left_span_px;
+        Some(Self {
+            left_span_px,
+            right_span_px,
+            ratio,
+            max_deviation,
+            mismatch: (ratio - 1.0).abs() > max_deviation,
+        })
+    }
+    
+    pub fn message(&self) -> String {
+        if self.mismatch {
+            format!("❌ 放大倍率不一致: 右/左网格跨度比 {:.4} (偏差 {:.2}%, 上限 {:.2}%)，请检查光机缩放/焦距，平移调整无法消除",
+                    self.ratio, (self.ratio - 1.0) * 100.0, self.max_deviation * 100.0)
+        } else {
+            format!("✓ 放大倍率一致: 右/左网格跨度比 {:.4}", self.ratio)
+        }
+    }
+}
+
+/// 圆点网格检测失败信息：附筛选阶段摘要与两轮合并诊断，供操作员判断过暗/过曝/遮挡
+fn grid_failure_message(eye: &str, merge_diagnostic: Option<String>, diagnostics: &DetectionDiagnostics) -> String {
+    let summary = if diagnostics.final_count == diagnostics.expected {
+        format!("圆点数正确 ({} 个)，网格排序或校验未通过", diagnostics.final_count)
+    } else {
+        diagnostics.summary()
+    };
+    match merge_diagnostic {
+        Some(diagnostic) => format!("{}圆点网格检测失败: {}; {}", eye, summary, diagnostic),
+        None => format!("{}圆点网格检测失败: {}", eye, summary),
+    }
+}
+
+/// 网格跨度：各圆点到质心距离的均方根 (像素)，点数不足或退化时返回 None
+/// 
+/// 只依赖点间距离，网格旋转（roll）时不变，与放大倍率成正比
+pub fn grid_span_px(corners: &Vector<Point2f>) -> Option<f64> {
+    if corners.len() < 2 {
+        return None;
+    }
+    let n = corners.len() as f64;
+    let (cx, cy) = corners.iter().fold((0.0, 0.0), |(sx, sy), p| (sx + p.x as f64, sy + p.y as f64));
+    let (cx, cy) = (cx / n, cy / n);
+    let mean_sq = corners.iter()
+        .map(|p| (p.x as f64 - cx).powi(2) + (p.y as f64 - cy).powi(2))
+        .sum::<f64>() / n;
+    let span = mean_sq.sqrt();
+    (span > 0.0 && span.is_finite()).then_some(span)
+}
+
+/// 姿态/合像判定阈值（实时判定使用 AlignmentSystem 当前阈值；重新判定时可临时指定）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AcceptanceThresholds {
+    pub roll_deg: f64,
+    pub pitch_yaw_deg: f64,
+    pub rms_px: f64,
+    pub percentile_px: f64,  // 作用于所配置分位数的误差 (默认P95)
+    pub max_px: f64,
+}
+
+impl Default for AcceptanceThresholds {
+    /// 默认阈值常量（未配置时使用）
+    fn default() -> Self {
+        Self {
+            roll_deg: ROLL_TH,
+            pitch_yaw_deg: PITCH_YAW_TH,
+            rms_px: RMS_TH,
+            percentile_px: P95_TH,
+            max_px: MAX_TH,
+        }
+    }
+}
+
+impl AcceptanceThresholds {
+    pub fn validate(&self) -> Result<(), String> {
+        let values = [
+            ("roll", self.roll_deg),
+            ("pitch/yaw", self.pitch_yaw_deg),
+            ("RMS", self.rms_px),
+            ("分位误差", self.percentile_px),
+            ("最大误差", self.max_px),
+        ];
+        for (name, value) in values {
+            if !(value > 0.0 && value.is_finite()) {
+                return Err(format!("{}阈值必须为正数: {}", name, value));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 合像稳健统计配置：按 MAD 剔除离群点（如单个误关联点）后另算 RMS/分位误差
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RobustStatsConfig {
+    pub enabled: bool,       // 计算稳健统计，默认关闭
+    pub mad_k: f64,          // 离群倍数 k，默认3.5
+    pub use_for_pass: bool,  // 判定改用剔除离群点后的 RMS/分位/最大误差，默认仍用原始统计
+}
+
+impl Default for RobustStatsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mad_k: DEFAULT_ROBUST_MAD_K,
+            use_for_pass: false,
+        }
+    }
+}
+
+impl RobustStatsConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.mad_k > 0.0 && self.mad_k.is_finite()) {
+            return Err(format!("离群倍数k必须为正数: {}", self.mad_k));
+        }
+        if self.use_for_pass && !self.enabled {
+            return Err("按稳健统计判定须先启用稳健统计".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// 最近一次单眼姿态判定（原始约定角度，与阈值比较的值）
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LastPoseEvaluation {
+    roll: f64,
+    pitch: f64,
+    yaw: f64,
+    reprojection_ok: bool,
+}
+
+impl LastPoseEvaluation {
+    fn passes(&self, thresholds: &AcceptanceThresholds) -> bool {
+        self.reprojection_ok
+            && self.roll.abs() <= thresholds.roll_deg
+            && self.pitch.abs() <= thresholds.pitch_yaw_deg
+            && self.yaw.abs() <= thresholds.pitch_yaw_deg
+    }
+}
+
+/// 最近一次合像判定的残差（按点序号，未参与统计的点为None）与倍率比较
+#[derive(Debug, Clone, Default)]
+struct LastAlignmentResiduals {
+    point_errors: Vec<Option<f64>>,
+    magnification: MagnificationCheck,
+}
+
+/// 按新阈值重新判定的合像结果
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ReevaluatedAlignment {
+    pub rms: f64,
+    pub p95: f64,            // 所配置分位数的误差
+    pub max_err: f64,
+    pub rms_pass: bool,
+    pub p95_pass: bool,
+    pub max_pass: bool,
+    pub magnification_mismatch: bool,  // 倍率不一致与阈值无关，仍判定不通过
+    pub pass: bool,
+}
+
+/// 最近结果按新阈值重新判定（未缓存的项为 None，不参与总判定）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReevaluationVerdict {
+    pub thresholds: AcceptanceThresholds,
+    pub left_pose_pass: Option<bool>,
+    pub right_pose_pass: Option<bool>,
+    pub alignment: Option<ReevaluatedAlignment>,
+    pub pass: bool,
+}
+
+/// 分位数标签，如 95.0 → "P95"，97.5 → "P97.5"
+pub fn percentile_label(pct: f64) -> String {
+    if pct.fract() == 0.0 {
+        format!("P{:.0}", pct)
+    } else {
+        format!("P{}", pct)
+    }
+}
+
+/// 左眼居中判定的期望关键点位置 (像素)
+/// 
+/// 默认值对应 2448×2048 传感器与当前光机布局；更换分辨率或光机时按设备配置
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CenteringTargets {
+    pub expected_top_right: (f32, f32),    // 序号0点期望位置
+    pub expected_bottom_left: (f32, f32),  // 末点(序号 pattern_size.area()-1)期望位置
+}
+
+impl Default for CenteringTargets {
+    fn default() -> Self {
+        Self {
+            expected_top_right: EXPECTED_TOP_RIGHT,
+            expected_bottom_left: EXPECTED_BOTTOM_LEFT,
+        }
+    }
+}
+
+impl CenteringTargets {
+    /// 期望位置须位于图像范围内，否则偏移量无意义
+    pub fn validate(&self, image_size: Size) -> Result<(), String> {
+        let points = [("右上角点(序号0)", self.expected_top_right), ("左下角点(末点)", self.expected_bottom_left)];
+        for (name, (x, y)) in points {
+            let inside = x >= 0.0 && y >= 0.0 && x < image_size.width as f32 && y < image_size.height as f32;
+            if !inside {
+                return Err(format!("{}期望位置 ({}, {}) 超出图像范围 {}×{}",
+                                   name, x, y, image_size.width, image_size.height));
+            }
+        }
+        Ok(())
+    }
+    
+    /// 按分辨率等比缩放期望位置（如全分辨率 → 1/2分辨率）
+    pub fn rescaled(&self, from: Size, to: Size) -> Self {
+        let (sx, sy) = (to.width as f32 / from.width as f32, to.height as f32 / from.height as f32);
+        let scale = |(x, y): (f32, f32)| (x * sx, y * sy);
+        Self {
+            expected_top_right: scale(self.expected_top_right),
+            expected_bottom_left: scale(self.expected_bottom_left),
+        }
+    }
+    
+    /// 按实际采集分辨率 (宽, 高) 缩放的默认期望位置，ROI/1/2分辨率下仍落在图像范围内
+    pub fn default_for(resolution: (i32, i32)) -> Self {
+        let (rw, rh) = CENTERING_REFERENCE_SIZE;
+        Self::default().rescaled(Size::new(rw, rh), Size::new(resolution.0, resolution.1))
+    }
 }
 
 /// 居中检测结果
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CenteringResult {
     pub is_centered: bool,              // 是否居中
     pub top_right_offset_x: f32,        // 右上角点X偏移 (像素)
@@ -101,7 +1382,7 @@ pub struct KeyPointValidation {
 }
 
 /// 操作调整向量 - 提供机械调整的原始数据
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdjustmentVectors {
     pub left_eye_adjustment: EyeAdjustment,   // 左眼调整建议
     pub right_eye_adjustment: EyeAdjustment,  // 右眼调整建议
@@ -110,7 +1391,7 @@ pub struct AdjustmentVectors {
 }
 
 /// 单眼调整建议
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EyeAdjustment {
     pub roll_adjustment: f64,    // 旋转调整 (度)
     pub pitch_adjustment: f64,   // 俯仰调整 (度) 
@@ -121,7 +1402,7 @@ pub struct EyeAdjustment {
 }
 
 /// 合像调整建议
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlignmentAdjustment {
     pub delta_x: f64,           // X方向像素偏差
     pub delta_y: f64,           // Y方向像素偏差
@@ -129,8 +1410,122 @@ pub struct AlignmentAdjustment {
     pub adjustment_priority: String, // 调整优先级描述
 }
 
+/// 单个千分尺螺杆的换算参数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MicrometerAxis {
+    pub screw: String,            // 螺杆名称（工位上的标注）
+    pub units_per_turn: f64,      // 每圈对应的调整量 (度或像素)
+    pub clockwise_positive: bool, // 顺时针旋转使调整量增加
+}
+
+/// 工位千分尺换算配置：调整向量各轴 → 螺杆圈数，未配置螺杆的轴不输出
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MicrometerCalibration {
+    pub left_roll: Option<MicrometerAxis>,
+    pub left_pitch: Option<MicrometerAxis>,
+    pub left_yaw: Option<MicrometerAxis>,
+    pub left_centering_x: Option<MicrometerAxis>,
+    pub left_centering_y: Option<MicrometerAxis>,
+    pub right_roll: Option<MicrometerAxis>,
+    pub right_pitch: Option<MicrometerAxis>,
+    pub right_yaw: Option<MicrometerAxis>,
+    pub alignment_x: Option<MicrometerAxis>,
+    pub alignment_y: Option<MicrometerAxis>,
+}
+
+/// 螺杆旋转方向（面对螺杆头）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TurnDirection {
+    Clockwise,
+    CounterClockwise,
+}
+
+/// 单个螺杆的旋转指令
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScrewTurn {
+    pub axis: String,             // 调整轴，如 "left_roll"
+    pub screw: String,            // 螺杆名称
+    pub adjustment: f64,          // 换算前的调整量
+    pub unit: String,             // "deg" 或 "px"
+    pub turns: f64,               // 圈数 (≥0)
+    pub direction: TurnDirection,
+    pub instruction: String,      // 如 "L1 顺时针 1.50 圈"
+}
+
+impl MicrometerCalibration {
+    /// 已配置的轴: (轴名, 单位, 螺杆)
+    fn configured_axes(&self) -> Vec<(&'static str, &'static str, &MicrometerAxis)> {
+        [
+            ("left_roll", "deg", &self.left_roll),
+            ("left_pitch", "deg", &self.left_pitch),
+            ("left_yaw", "deg", &self.left_yaw),
+            ("left_centering_x", "px", &self.left_centering_x),
+            ("left_centering_y", "px", &self.left_centering_y),
+            ("right_roll", "deg", &self.right_roll),
+            ("right_pitch", "deg", &self.right_pitch),
+            ("right_yaw", "deg", &self.right_yaw),
+            ("alignment_x", "px", &self.alignment_x),
+            ("alignment_y", "px", &self.alignment_y),
+        ].into_iter()
+            .filter_map(|(axis, unit, screw)| screw.as_ref().map(|s| (axis, unit, s)))
+            .collect()
+    }
+    
+    pub fn validate(&self) -> Result<(), String> {
+        for (axis, _, screw) in self.configured_axes() {
+            if !(screw.units_per_turn.is_finite() && screw.units_per_turn > 0.0) {
+                return Err(format!("{} 每圈调整量必须为正数: {}", axis, screw.units_per_turn));
+            }
+        }
+        Ok(())
+    }
+    
+    /// 调整向量换算为各螺杆的圈数与方向
+    pub fn to_screw_turns(&self, adjustments: &AdjustmentVectors) -> Vec<ScrewTurn> {
+        let left = &adjustments.left_eye_adjustment;
+        let right = &adjustments.right_eye_adjustment;
+        let alignment = &adjustments.alignment_adjustment;
+        let value = |axis: &str| match axis {
+            "left_roll" => left.roll_adjustment,
+            "left_pitch" => left.pitch_adjustment,
+            "left_yaw" => left.yaw_adjustment,
+            "left_centering_x" => left.centering_x as f64,
+            "left_centering_y" => left.centering_y as f64,
+            "right_roll" => right.roll_adjustment,
+            "right_pitch" => right.pitch_adjustment,
+            "right_yaw" => right.yaw_adjustment,
+            "alignment_x" => alignment.delta_x,
+            _ => alignment.delta_y,
+        };
+        
+        self.configured_axes().into_iter().map(|(axis, unit, screw)| {
+            let adjustment = value(axis);
+            let turns = adjustment.abs() / screw.units_per_turn;
+            let direction = if (adjustment >= 0.0) == screw.clockwise_positive {
+                TurnDirection::Clockwise
+            } else {
+                TurnDirection::CounterClockwise
+            };
+            let direction_label = match direction {
+                TurnDirection::Clockwise => "顺时针",
+                TurnDirection::CounterClockwise => "逆时针",
+            };
+            ScrewTurn {
+                axis: axis.to_string(),
+                screw: screw.screw.clone(),
+                adjustment,
+                unit: unit.to_string(),
+                turns,
+                direction,
+                instruction: format!("{} {} {:.2} 圈", screw.screw, direction_label, turns),
+            }
+        }).collect()
+    }
+}
+
 /// 调整优先级枚举
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AdjustmentPriority {
     LeftEyePose,      // 优先调整左眼姿态
     LeftEyeCentering, // 优先调整左眼居中
@@ -139,8 +1534,50 @@ pub enum AdjustmentPriority {
     Complete,         // 调整完成
 }
 
+/// 单次完整检测报告：双眼姿态、左眼居中、双眼合像与调整向量合为一个对象
+/// 
+/// 前端每次检测获取一个结构化对象，无需拼接各阶段的 DetectionResult 事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlignmentReport {
+    pub timestamp: String,                  // 生成时间 (本地时间，毫秒)
+    pub pass: bool,                         // 姿态、居中、合像全部通过
+    pub left_pose: SingleEyePoseResult,
+    pub right_pose: SingleEyePoseResult,
+    pub left_centering: CenteringResult,
+    pub alignment: DualEyeAlignmentResult,
+    pub adjustments: AdjustmentVectors,
+    #[serde(default)]
+    pub units: BTreeMap<String, MetricUnit>, // 字段路径 → 单位（如 "left_pose.roll" → degree）
+}
+
+/// AlignmentSystem 的全部检测/判定设置（不含标定参数与运行时状态）
+/// 
+/// 用于按同一设置创建其他实例（如快速完整检测的流水线），设置变化时据此重建
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AlignmentSettings {
+    pub pattern_size: (i32, i32),           // 标定板规格 (每列点数, 列数)，只能在创建时指定
+    pub center_distance_mm: f32,            // 圆心对角间距，只能在创建时指定
+    pub detector: DetectorConfig,
+    pub exclusion_regions: Vec<ExclusionRegion>,
+    pub centering_targets: CenteringTargets,
+    pub thresholds: AcceptanceThresholds,
+    pub blank_frame: BlankFrameConfig,
+    pub pose_convention: PoseConvention,
+    pub object_origin: ObjectOrigin,
+    pub pose_averaging_frames: usize,
+    pub standoff_range: StandoffRange,
+    pub convergence_range: ConvergenceRange,
+    pub error_percentile: f64,
+    pub pose_reprojection_max_px: f64,
+    pub pnp_method: PnpMethod,
+    pub magnification_mismatch_max: f64,
+    pub robust_stats: RobustStatsConfig,
+    pub include_interpolated_points: bool,
+    pub exclude_unrefined_points: bool,
+}
+
 impl AlignmentSystem {
-    /// 创建光机合像检测系统
+    /// 创建光机合像检测系统（默认4×10标定板，圆心对角间距25mm）
     pub fn new(
         image_size: Size,
         left_camera_params_path: &str,
@@ -148,6 +1585,36 @@ impl AlignmentSystem {
         stereo_params_path: &str,
         rectify_params_path: &str,
     ) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_pattern(
+            image_size,
+            left_camera_params_path,
+            right_camera_params_path,
+            stereo_params_path,
+            rectify_params_path,
+            Size::new(DEFAULT_PATTERN_SIZE.0, DEFAULT_PATTERN_SIZE.1),
+            DEFAULT_CENTER_DISTANCE_MM,
+        )
+    }
+    
+    /// 按指定标定板规格创建光机合像检测系统
+    /// 
+    /// - `pattern_size`: (每列点数, 列数)，如 4×10 (40点)、4×11 (44点)
+    /// - `center_distance_mm`: 圆心对角间距 (mm)
+    /// 
+    /// 期望圆点数、世界坐标、排序分列与居中关键点序号均由 pattern_size 决定
+    pub fn with_pattern(
+        image_size: Size,
+        left_camera_params_path: &str,
+        right_camera_params_path: &str,
+        stereo_params_path: &str,
+        rectify_params_path: &str,
+        pattern_size: Size,
+        center_distance_mm: f32,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        if !(center_distance_mm > 0.0 && center_distance_mm.is_finite()) {
+            return Err(format!("圆心间距必须为正数: {}", center_distance_mm).into());
+        }
+        
         // 加载轻量参数
         println!("加载标定参数...");
         let left_camera = load_camera_params(left_camera_params_path)?;
@@ -166,13 +1633,14 @@ impl AlignmentSystem {
         let calibrator = Calibrator::new(
             image_size,
             15.0,    // 圆点直径 (mm)
-            25.0,   // 圆心距离 (mm)
-            Size::new(4, 10), // pattern_size
+            center_distance_mm,   // 圆心距离 (mm)
+            pattern_size,
             1.0,    // 重投影误差阈值
         )?;
         
         // 🆕 创建连通域圆点检测器
-        let circle_detector = ConnectedComponentsDetector::new();
+        let mut circle_detector = ConnectedComponentsDetector::new();
+        circle_detector.set_pattern_size(pattern_size)?;
         
         println!("标定参数加载完成");
         
@@ -185,10 +1653,39 @@ impl AlignmentSystem {
             rectify_params: rectify,
             left_maps: None,
             right_maps: None,
+            maps_regenerated: false,
             rectifier,
             calibrator,
             circle_detector, // 🆕 添加新字段
             image_size,
+            last_timings: StageTimings::default(),
+            debug_render: DebugRenderConfig::default(),
+            last_rectified: None,
+            last_binary_masks: (None, None),
+            last_diagnostics: (None, None),
+            mask_overlay_enabled: false,
+            last_found_blobs: Vec::new(),
+            last_overlay_sources: (None, None),
+            interpolated_points: Vec::new(),
+            include_interpolated_points: false,
+            corner_refine_tags: Vec::new(),
+            exclude_unrefined_points: false,
+            blank_frame_config: BlankFrameConfig::default(),
+            pose_convention: PoseConvention::default(),
+            object_origin: ObjectOrigin::default(),
+            pose_averaging_frames: 1,
+            pose_history: std::sync::Mutex::new([VecDeque::new(), VecDeque::new()]),
+            standoff_range: StandoffRange::default(),
+            convergence_range: ConvergenceRange::default(),
+            error_percentile: DEFAULT_ERROR_PERCENTILE,
+            pose_reprojection_max_px: DEFAULT_POSE_REPROJECTION_MAX_PX,
+            pnp_method: PnpMethod::default(),
+            magnification_mismatch_max: DEFAULT_MAGNIFICATION_MISMATCH_MAX,
+            robust_stats: RobustStatsConfig::default(),
+            thresholds: AcceptanceThresholds::default(),
+            centering_targets: CenteringTargets::default(),
+            last_alignment: std::sync::Mutex::new(None),
+            last_poses: std::sync::Mutex::new([None, None]),
         })
     }
     
@@ -260,7 +1757,19 @@ impl AlignmentSystem {
     }
     
     /// 确保重映射矩阵已加载
+    /// 
+    /// 重映射矩阵文件缺失时（如标定结果导出不完整），降级为根据已加载的
+    /// 相机参数 + 校正参数重新计算，并缓存在内存中
     pub fn ensure_maps_loaded(&mut self, rectify_maps_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if self.left_maps.is_none() && !Path::new(rectify_maps_path).exists() {
+            println!("⚠️ 重映射矩阵文件不存在: {}", rectify_maps_path);
+            println!("⚠️ 降级模式: 根据相机参数与校正参数重新计算重映射矩阵...");
+            let start = Instant::now();
+            self.regenerate_rectify_maps()?;
+            self.maps_regenerated = true;
+            println!("⚠️ 重映射矩阵已重新生成 (耗时 {:.1} ms)，建议重新导出完整标定结果", start.elapsed().as_millis());
+        }
+        
         if self.left_maps.is_none() {
             println!("首次使用，加载重映射矩阵...");
             let maps = load_rectify_maps(rectify_maps_path)?;
@@ -274,31 +1783,71 @@ impl AlignmentSystem {
                 vec2d_to_mat_f32(&maps.right_map2)?
             ));
             println!("重映射矩阵加载完成");
+            
+            // 标定分辨率与当前分辨率不一致时拒绝使用，避免 remap 越界或输出错误图像
+            if let Some(check) = self.check_rectify_maps_size() {
+                if let Some(error) = check.error {
+                    self.left_maps = None;
+                    self.right_maps = None;
+                    println!("❌ {}", error);
+                    return Err(error.into());
+                }
+            }
         }
         Ok(())
     }
     
-    /// 生成简化的世界坐标点（第一个点为原点）
+    /// 已加载重映射矩阵的尺寸与 image_size 比对（未加载时返回 None）
+    pub fn check_rectify_maps_size(&self) -> Option<RectifyMapsSizeCheck> {
+        let (left, right) = match (&self.left_maps, &self.right_maps) {
+            (Some(left), Some(right)) => (&left.0, &right.0),
+            _ => return None,
+        };
+        Some(check_rectify_maps_size(
+            (left.cols(), left.rows()),
+            (right.cols(), right.rows()),
+            (self.image_size.width, self.image_size.height),
+        ))
+    }
+    
+    /// 根据相机内参/畸变与校正参数 (R1/P1, R2/P2) 重新计算重映射矩阵
+    fn regenerate_rectify_maps(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let r1 = vec2d_to_mat_f64(&self.rectify_params.r1)?;
+        let p1 = vec2d_to_mat_f64(&self.rectify_params.p1)?;
+        let r2 = vec2d_to_mat_f64(&self.rectify_params.r2)?;
+        let p2 = vec2d_to_mat_f64(&self.rectify_params.p2)?;
+        
+        let left_maps = self.calibrator.compute_undistort_maps(
+            &self.left_camera_matrix, &self.left_dist_coeffs, &r1, &p1)?;
+        let right_maps = self.calibrator.compute_undistort_maps(
+            &self.right_camera_matrix, &self.right_dist_coeffs, &r2, &p2)?;
+        
+        self.left_maps = Some(left_maps);
+        self.right_maps = Some(right_maps);
+        Ok(())
+    }
+    
+    /// 重映射矩阵是否为降级模式下重新计算的
+    pub fn maps_regenerated(&self) -> bool {
+        self.maps_regenerated
+    }
+    
+    /// 标定板规格 (每列点数, 列数)
+    pub fn pattern_size(&self) -> Size {
+        self.calibrator.get_pattern_size()
+    }
+    
+    /// 每只眼期望检测到的圆点数 (pattern_size.area())
+    pub fn expected_points(&self) -> usize {
+        self.pattern_size().area() as usize
+    }
+    
+    /// 生成简化的世界坐标点（原点由 object_origin 决定，默认第一个点）
     fn generate_simplified_object_points(&self) -> Result<Vector<Point3f>, opencv::Error> {
         let world_points = self.calibrator.generate_world_points_from_list()?;
-        let mut simplified_points = Vector::<Point3f>::new();
-        
-        // 获取第一个点作为原点偏移
-        let first_point = world_points.get(0)?;
-        let offset_x = first_point.x;
-        let offset_y = first_point.y;
-        
-        // 所有点减去第一个点的坐标，使第一个点为原点
-        for i in 0..world_points.len() {
-            let point = world_points.get(i)?;
-            simplified_points.push(Point3f::new(
-                point.x - offset_x,
-                point.y - offset_y,
-                0.0
-            ));
-        }
+        let simplified_points = build_object_points(&world_points, self.object_origin)?;
         
-        println!("生成简化世界坐标，共{}个点，第一个点为原点", simplified_points.len());
+        println!("生成简化世界坐标，共{}个点，原点: {:?}", simplified_points.len(), self.object_origin);
         Ok(simplified_points)
     }
     
@@ -309,7 +1858,47 @@ impl AlignmentSystem {
         right_image: &Mat,
         rectify_maps_path: &str,
     ) -> Result<(Vector<Point2f>, Vector<Point2f>), Box<dyn std::error::Error>> {
+        let (left, right) = self.detect_circles_grid_eyes(left_image, right_image, rectify_maps_path)?;
+        let corners_left = left?;
+        let corners_right = right?;
+        
+        println!("✓ 左眼检测到{}个圆点", corners_left.len());
+        println!("✓ 右眼检测到{}个圆点", corners_right.len());
+        
+        Ok((corners_left, corners_right))
+    }
+    
+    /// 双眼检测的降级版本：单眼未找到完整网格时该眼返回 Err(失败原因) 而不中断
+    /// 
+    /// 调用方可继续用已找到的左眼做姿态/居中反馈，并把未找到一侧的诊断信息转给用户；
+    /// 仅重映射等整体失败时外层返回 Err
+    pub fn detect_circles_grid_partial(
+        &mut self,
+        left_image: &Mat,
+        right_image: &Mat,
+        rectify_maps_path: &str,
+    ) -> Result<(Result<Vector<Point2f>, String>, Result<Vector<Point2f>, String>), Box<dyn std::error::Error>> {
+        let (left, right) = self.detect_circles_grid_eyes(left_image, right_image, rectify_maps_path)?;
+        for (eye, result) in [("左眼", &left), ("右眼", &right)] {
+            match result {
+                Ok(corners) => println!("✓ {}检测到{}个圆点", eye, corners.len()),
+                Err(e) => println!("⚠️ {}", e),
+            }
+        }
+        Ok((left, right))
+    }
+    
+    /// 双眼重映射 + 圆点检测；外层 Err 为整体失败，内层 Err 为该眼网格检测失败原因
+    fn detect_circles_grid_eyes(
+        &mut self,
+        left_image: &Mat,
+        right_image: &Mat,
+        rectify_maps_path: &str,
+    ) -> Result<(Result<Vector<Point2f>, String>, Result<Vector<Point2f>, String>), Box<dyn std::error::Error>> {
         let detection_start = Instant::now();
+        self.last_timings = StageTimings::default();
+        self.interpolated_points.clear();
+        self.corner_refine_tags.clear();
         
         // Debug: 打印输入图像信息
         println!("输入图像信息:");
@@ -329,16 +1918,18 @@ impl AlignmentSystem {
         // 应用重映射
         println!("应用图像重映射...");
         let remap_process_start = Instant::now();
-        let left_rect = self.rectifier.remap_image_adaptive(left_image, left_map1, left_map2)?;
-        let right_rect = self.rectifier.remap_image_adaptive(right_image, right_map1, right_map2)?;
+        // 检测路径需要亚像素精度，固定使用线性插值
+        let left_rect = self.rectifier.remap_image_adaptive(left_image, left_map1, left_map2, RemapInterpolation::Linear)?;
+        let right_rect = self.rectifier.remap_image_adaptive(right_image, right_map1, right_map2, RemapInterpolation::Linear)?;
         let remap_process_time = remap_process_start.elapsed();
         println!("⏱️  图像重映射处理耗时: {:.1} ms", remap_process_time.as_millis());
+        self.last_timings.remap_ms = (remap_load_time + remap_process_time).as_secs_f64() * 1000.0;
         
         // 🚀 ROI区域优化 - 基于先验知识限制检测区域
         let roi_detection_start = Instant::now();
         
         // 检测圆点 - 使用优化的ROI方法
-        let pattern_size = Size::new(4, 10);
+        let pattern_size = self.pattern_size();
         let mut corners_left = Vector::<Point2f>::new();
         let mut corners_right = Vector::<Point2f>::new();
         
@@ -353,6 +1944,10 @@ impl AlignmentSystem {
             &mut corners_left,
             &detector
         )?;
+        let left_merge_diagnostic = self.circle_detector.last_merge_diagnostic();
+        let left_diagnostics = self.circle_detector.last_diagnostics().clone();
+        self.last_binary_masks.0 = self.circle_detector.take_last_binary_mask();
+        let left_blobs = std::mem::take(&mut self.last_found_blobs);
         
         println!("🔍 使用全图检测右眼圆点...");
         let right_found = self.detect_circles_full_image(
@@ -361,24 +1956,108 @@ impl AlignmentSystem {
             &mut corners_right,
             &detector
         )?;
+        let right_merge_diagnostic = self.circle_detector.last_merge_diagnostic();
+        let right_diagnostics = self.circle_detector.last_diagnostics().clone();
+        self.last_binary_masks.1 = self.circle_detector.take_last_binary_mask();
+        let right_blobs = std::mem::take(&mut self.last_found_blobs);
         
         let roi_detection_time = roi_detection_start.elapsed();
         println!("⏱️  ROI圆心检测耗时: {:.1} ms", roi_detection_time.as_millis());
         
-        if !left_found {
-            return Err("左眼圆点网格检测失败".into());
-        }
-        if !right_found {
-            return Err("右眼圆点网格检测失败".into());
+        if self.mask_overlay_enabled {
+            self.last_overlay_sources = (
+                Some(MaskOverlaySource { rectified: left_rect.clone(), blobs: left_blobs }),
+                Some(MaskOverlaySource { rectified: right_rect.clone(), blobs: right_blobs }),
+            );
         }
         
-        println!("✓ 左眼检测到{}个圆点", corners_left.len());
-        println!("✓ 右眼检测到{}个圆点", corners_right.len());
+        // 仅在debug底图需要时保留重映射图像（直接转移所有权，无拷贝）
+        self.last_rectified = if self.debug_render.background.needs_rectified() {
+            Some((left_rect, right_rect))
+        } else {
+            None
+        };
+        
+        let eye_result = |found: bool, corners: Vector<Point2f>, merge_diagnostic: Option<String>, diagnostics: &DetectionDiagnostics, eye: &str| {
+            if found {
+                return Ok(corners);
+            }
+            Err(grid_failure_message(eye, merge_diagnostic, diagnostics))
+        };
+        let left = eye_result(left_found, corners_left, left_merge_diagnostic, &left_diagnostics, "左眼");
+        let right = eye_result(right_found, corners_right, right_merge_diagnostic, &right_diagnostics, "右眼");
+        self.last_diagnostics = (Some(left_diagnostics), Some(right_diagnostics));
         
         let total_detection_time = detection_start.elapsed();
         println!("⏱️  总检测耗时: {:.1} ms", total_detection_time.as_millis());
         
-        Ok((corners_left, corners_right))
+        Ok((left, right))
+    }
+    
+    /// 仅检测左眼圆点网格（左眼姿态检测阶段使用，右眼光机关闭时不受影响）
+    pub fn detect_left_circles_only(
+        &mut self,
+        left_image: &Mat,
+        rectify_maps_path: &str,
+    ) -> Result<Vector<Point2f>, Box<dyn std::error::Error>> {
+        self.detect_single_eye_circles(left_image, true, rectify_maps_path)
+    }
+    
+    /// 仅检测右眼圆点网格（右眼姿态检测阶段使用，左眼光机关闭时不受影响）
+    pub fn detect_right_circles_only(
+        &mut self,
+        right_image: &Mat,
+        rectify_maps_path: &str,
+    ) -> Result<Vector<Point2f>, Box<dyn std::error::Error>> {
+        self.detect_single_eye_circles(right_image, false, rectify_maps_path)
+    }
+    
+    /// 单眼重映射 + 圆点检测，耗时约为 detect_circles_grid 的一半
+    fn detect_single_eye_circles(
+        &mut self,
+        image: &Mat,
+        is_left: bool,
+        rectify_maps_path: &str,
+    ) -> Result<Vector<Point2f>, Box<dyn std::error::Error>> {
+        let detection_start = Instant::now();
+        let eye = if is_left { "左眼" } else { "右眼" };
+        self.last_timings = StageTimings::default();
+        self.interpolated_points.clear();
+        self.corner_refine_tags.clear();
+        // 单眼结果不用于合像debug图像
+        self.last_rectified = None;
+        
+        let remap_start = Instant::now();
+        self.ensure_maps_loaded(rectify_maps_path)?;
+        let (map1, map2) = if is_left {
+            self.left_maps.as_ref().unwrap()
+        } else {
+            self.right_maps.as_ref().unwrap()
+        };
+        let rectified = self.rectifier.remap_image_adaptive(image, map1, map2, RemapInterpolation::Linear)?;
+        self.last_timings.remap_ms = remap_start.elapsed().as_secs_f64() * 1000.0;
+        
+        println!("🔍 仅检测{}圆点...", eye);
+        let pattern_size = self.pattern_size();
+        let mut corners = Vector::<Point2f>::new();
+        let detector = SimpleBlobDetector::create(SimpleBlobDetector_Params::default()?)?.into(); // 保持接口兼容，但实际不使用
+        let found = self.detect_circles_full_image(&rectified, pattern_size, &mut corners, &detector)?;
+        let mask = self.circle_detector.take_last_binary_mask();
+        self.last_binary_masks = if is_left { (mask, None) } else { (None, mask) };
+        let diagnostics = self.circle_detector.last_diagnostics().clone();
+        if self.mask_overlay_enabled {
+            let source = Some(MaskOverlaySource { rectified, blobs: std::mem::take(&mut self.last_found_blobs) });
+            self.last_overlay_sources = if is_left { (source, None) } else { (None, source) };
+        }
+        
+        let message = (!found).then(|| grid_failure_message(eye, self.circle_detector.last_merge_diagnostic(), &diagnostics));
+        self.last_diagnostics = if is_left { (Some(diagnostics), None) } else { (None, Some(diagnostics)) };
+        if let Some(message) = message {
+            return Err(message.into());
+        }
+        
+        println!("✓ {}检测到{}个圆点, 耗时 {:.1} ms", eye, corners.len(), detection_start.elapsed().as_millis());
+        Ok(corners)
     }
     
     // 🔧 【已替换】创建优化的SimpleBlobDetector - 针对2448×2048图像和25mm圆心距离
@@ -672,21 +2351,26 @@ impl AlignmentSystem {
         println!("🔍 执行连通域圆心检测 (图像: {}×{}, 通道: {}, 类型: {})", 
                 image.cols(), image.rows(), image.channels(), image.typ());
         
-        // 验证pattern_size是否为期望的4×10
-        if pattern_size.width != 4 || pattern_size.height != 10 {
-            println!("⚠️ 警告: pattern_size不是4×10，当前为{}×{}", pattern_size.width, pattern_size.height);
+        // 验证pattern_size与系统配置的标定板规格一致
+        if pattern_size != self.pattern_size() {
+            println!("⚠️ 警告: pattern_size {}×{} 与标定板规格 {}×{} 不一致，按标定板规格检测",
+                     pattern_size.width, pattern_size.height, self.pattern_size().width, self.pattern_size().height);
         }
+        let expected_points = self.expected_points();
         
         // 使用连通域检测器进行圆点检测
         let detection_start = std::time::Instant::now();
-        let detected_centers = self.circle_detector.detect_circles(image)
+        let (detected_centers, refine_tags) = self.circle_detector.detect_circles_tagged(image)
             .map_err(|e| opencv::Error::new(opencv::core::StsError, &format!("连通域检测失败: {}", e)))?;
         
         let detection_time = detection_start.elapsed();
         println!("⏱️  连通域检测耗时: {:.1} ms", detection_time.as_millis());
+        self.last_timings.detect_ms += detection_time.as_secs_f64() * 1000.0;
+        self.interpolated_points.extend_from_slice(self.circle_detector.last_interpolated_points());
+        self.last_found_blobs = detected_centers.to_vec();
         
         // 检查检测结果
-        if detected_centers.len() == 40 {
+        if detected_centers.len() == expected_points {
             println!("✓ 连通域检测成功: {}个圆点", detected_centers.len());
             
             // 进行排序
@@ -697,6 +2381,10 @@ impl AlignmentSystem {
             
             let sort_time = sort_start.elapsed();
             println!("⏱️  圆点排序耗时: {:.1} ms", sort_time.as_millis());
+            self.last_timings.sort_ms += sort_time.as_secs_f64() * 1000.0;
+            
+            // 校验点序与标定板布局一致，避免转置/镜像的点序映射到错误的世界坐标
+            self.verify_grid_orientation(&mut sorted_centers)?;
             
             // 将结果复制到输出参数
             corners.clear();
@@ -704,93 +2392,89 @@ impl AlignmentSystem {
                 corners.push(sorted_centers.get(i).map_err(|e| opencv::Error::new(opencv::core::StsError, &format!("获取圆点失败: {}", e)))?);
             }
             
+            // 排序与方向校正只重排圆点、不改坐标：按检测序号把细化标记重排为与输出点序一致
+            let sorted_tags: Vec<RefineTag> = corners.iter()
+                .map(|p| detected_centers.iter().position(|q| q == p).map_or(RefineTag::Fallback, |i| refine_tags[i]))
+                .collect();
+            // 只保留最近的左右眼两组（流水线路径不在每帧开始时清空）
+            if self.corner_refine_tags.len() >= 2 {
+                self.corner_refine_tags.remove(0);
+            }
+            self.corner_refine_tags.push((corners.clone(), sorted_tags));
+            
             println!("✅ 连通域检测+排序完成: {}个圆点", corners.len());
             Ok(true)
         } else {
-            println!("❌ 连通域检测失败: 期望40个圆点，实际检测到{}个", detected_centers.len());
+            println!("❌ 连通域检测失败: 期望{}个圆点，实际检测到{}个", expected_points, detected_centers.len());
             Ok(false)
         }
     }
     
-    // 【已替换】重新排序 asymmetric circles 以匹配世界坐标
-    // 🆕 现在使用ConnectedComponentsDetector.sort_asymmetric_grid()替代
-    // 原实现保留用于参考和回滚
-    /*
-    /// 重新排序 asymmetric circles 以匹配世界坐标
+    /// 排序结果几何一致性校验
     /// 
-    /// OpenCV的find_circles_grid可能返回不同的列顺序，
-    /// 这个函数确保输出顺序与generate_world_points_from_list一致
-    /// （参考自calibration_circles.rs的实现）
-    fn reorder_asymmetric_circles(&self, centers: &Vector<Point2f>) -> Result<Vector<Point2f>, opencv::Error> {
-        if centers.len() != 40 {
-            return Ok(centers.clone());
-        }
-        
-        // 检查序号0和序号4的x坐标
-        let point_0 = centers.get(0)?;
-        let point_4 = centers.get(4)?;
-        
-        // 如果序号0的x坐标小于序号4，说明列顺序错了
-        // 正确情况：序号0应该在最右边（第9列），x坐标应该更大
-        if point_0.x < point_4.x {
-            println!("   检测到列顺序错误（点0.x={:.0} < 点4.x={:.0}），执行奇偶列交换...", 
-                    point_0.x, point_4.x);
-            
-            // 创建新的排序数组
-            let mut reordered = Vector::<Point2f>::new();
-            
-            // 交换相邻的奇偶列
-            // 原顺序: 0-3, 4-7, 8-11, 12-15, 16-19, 20-23, 24-27, 28-31, 32-35, 36-39
-            // 新顺序: 4-7, 0-3, 12-15, 8-11, 20-23, 16-19, 28-31, 24-27, 36-39, 32-35
-            
-            // 交换第1对列（0-3 和 4-7）
-            for i in 4..8 {
-                reordered.push(centers.get(i)?);
-            }
-            for i in 0..4 {
-                reordered.push(centers.get(i)?);
-            }
-            
-            // 交换第2对列（8-11 和 12-15）
-            for i in 12..16 {
-                reordered.push(centers.get(i)?);
-            }
-            for i in 8..12 {
-                reordered.push(centers.get(i)?);
-            }
-            
-            // 交换第3对列（16-19 和 20-23）
-            for i in 20..24 {
-                reordered.push(centers.get(i)?);
-            }
-            for i in 16..20 {
-                reordered.push(centers.get(i)?);
-            }
-            
-            // 交换第4对列（24-27 和 28-31）
-            for i in 28..32 {
-                reordered.push(centers.get(i)?);
-            }
-            for i in 24..28 {
-                reordered.push(centers.get(i)?);
-            }
-            
-            // 交换第5对列（32-35 和 36-39）
-            for i in 36..40 {
-                reordered.push(centers.get(i)?);
-            }
-            for i in 32..36 {
-                reordered.push(centers.get(i)?);
+    /// 网格在图像中旋转约90°时 PCA 排序会把行列分错，点序看似完整但对应错误的世界坐标。
+    /// 依次尝试将圆点旋转 0/90/180/270° (及镜像) 后重新排序，取第一个与布局一致且非镜像的点序；
+    /// 仅镜像点序一致（图像被转置或翻转）时报错，不向下游传递。
+    fn verify_grid_orientation(&self, corners: &mut Vector<Point2f>) -> Result<(), opencv::Error> {
+        let world_points = self.calibrator.generate_world_points_from_list()?;
+        let original: Vec<Point2f> = corners.to_vec();
+        let n = original.len() as f32;
+        let (cx, cy) = original.iter().fold((0.0f32, 0.0f32), |(sx, sy), p| (sx + p.x / n, sy + p.y / n));
+        
+        let mut mirrored_found = false;
+        for mirror in [false, true] {
+            for quarter_turns in 0..4 {
+                let candidate = if !mirror && quarter_turns == 0 {
+                    corners.clone()
+                } else {
+                    // 变换后重新排序，再按位置映射回原始坐标
+                    let transformed: Vec<Point2f> = original.iter().map(|p| {
+                        let (mut x, mut y) = (p.x - cx, p.y - cy);
+                        if mirror {
+                            x = -x;
+                        }
+                        for _ in 0..quarter_turns {
+                            (x, y) = (-y, x);
+                        }
+                        Point2f::new(x, y)
+                    }).collect();
+                    let mut sorted = Vector::<Point2f>::from_iter(transformed.iter().copied());
+                    self.circle_detector.sort_asymmetric_grid(&mut sorted)?;
+                    sorted.iter()
+                        .filter_map(|p| transformed.iter().position(|q| *q == p).map(|i| original[i]))
+                        .collect::<Vector<Point2f>>()
+                };
+                if candidate.len() != original.len() {
+                    continue;
+                }
+                
+                let fit = match fit_grid_affine(&world_points, &candidate) {
+                    Some(fit) if fit.is_consistent() => fit,
+                    _ => continue,
+                };
+                if fit.is_mirrored() {
+                    mirrored_found = true;
+                    continue;
+                }
+                if mirror || quarter_turns != 0 {
+                    println!("⚠️ 圆点排序与{}×{}布局不一致，已按旋转{}°{}重新排序纠正 (残差 {:.3})",
+                             self.pattern_size().width, self.pattern_size().height, quarter_turns * 90, if mirror { "+镜像" } else { "" }, fit.residual_ratio);
+                    *corners = candidate;
+                }
+                return Ok(());
             }
-            
-            Ok(reordered)
-        } else {
-            // 顺序正确，直接返回
-            println!("   列顺序正确（点0.x={:.0} >= 点4.x={:.0}）", point_0.x, point_4.x);
-            Ok(centers.clone())
         }
+        
+        let message = if mirrored_found {
+            "圆点网格为镜像/转置排列，点会映射到错误的世界坐标 - 请检查图像是否被转置或相机 ReverseX/ReverseY 设置"
+        } else {
+            "圆点排序与标定板布局不一致 (网格几何校验失败)"
+        };
+        println!("❌ {}", message);
+        Err(opencv::Error::new(opencv::core::StsError, message))
     }
-    */
+    
+    // 圆点排序统一由 alignment_circles_detection::order_asymmetric_grid 实现（标定共用）
     
     /// 【已弃用】ROI区域圆心检测 - 保留用于向后兼容
     /// 
@@ -809,7 +2493,7 @@ impl AlignmentSystem {
         self.detect_circles_full_image(image, pattern_size, corners, detector)
     }
     
-    /// 3.4.2 单光机姿态判定（通用版本 - 支持左右眼）
+    /// 3.4.2 单光机姿态判定（通用版本 - 支持左右眼，单帧，不参与多帧平均）
     pub fn check_single_eye_pose(
         &self,
         corners: &Vector<Point2f>,
@@ -818,83 +2502,226 @@ impl AlignmentSystem {
     ) -> Result<SingleEyePoseResult, Box<dyn std::error::Error>> {
         println!("=== 单光机姿态检测 ===");
         
+        let (sample, reprojection_rms, method_used) = self.solve_pose_sample(corners, camera_matrix, dist_coeffs)?;
+        let averaged = average_pose_samples(&[sample]).ok_or("姿态解无效")?;
+        Ok(self.evaluate_pose(&averaged, reprojection_rms, method_used))
+    }
+    
+    /// 单光机姿态判定，按 pose_averaging_frames 对该眼最近N帧的解取平均
+    fn check_eye_pose_averaged(
+        &self,
+        eye: usize,
+        corners: &Vector<Point2f>,
+        camera_matrix: &Mat,
+        dist_coeffs: &Mat,
+    ) -> Result<SingleEyePoseResult, Box<dyn std::error::Error>> {
+        let (sample, reprojection_rms, method_used) = self.solve_pose_sample(corners, camera_matrix, dist_coeffs)?;
+        // 重投影校验失败的解不进入多帧平均，单帧上报
+        if reprojection_rms > self.pose_reprojection_max_px {
+            let single = average_pose_samples(&[sample]).ok_or("姿态解无效")?;
+            self.record_last_pose(eye, &single, reprojection_rms);
+            return Ok(self.evaluate_pose(&single, reprojection_rms, method_used));
+        }
+        let window: Vec<PoseSample> = {
+            let mut history = self.pose_history.lock().map_err(|_| "姿态历史锁中毒")?;
+            let queue = &mut history[eye];
+            queue.push_back(sample);
+            while queue.len() > self.pose_averaging_frames {
+                queue.pop_front();
+            }
+            queue.iter().copied().collect()
+        };
+        let averaged = average_pose_samples(&window).ok_or("姿态平均失败")?;
+        self.record_last_pose(eye, &averaged, reprojection_rms);
+        Ok(self.evaluate_pose(&averaged, reprojection_rms, method_used))
+    }
+    
+    /// 记录该眼最近一次参与判定的姿态（原始约定），供按新阈值重新判定
+    fn record_last_pose(&self, eye: usize, pose: &AveragedPose, reprojection_rms: f64) {
+        if let Ok(mut last) = self.last_poses.lock() {
+            last[eye] = Some(LastPoseEvaluation {
+                roll: pose.roll,
+                pitch: pose.pitch,
+                yaw: pose.yaw,
+                reprojection_ok: reprojection_rms <= self.pose_reprojection_max_px,
+            });
+        }
+    }
+    
+    /// solvePnP 求单帧姿态解，并返回该解的重投影RMS (像素) 与实际使用的求解方法
+    fn solve_pose_sample(
+        &self,
+        corners: &Vector<Point2f>,
+        camera_matrix: &Mat,
+        dist_coeffs: &Mat,
+    ) -> Result<(PoseSample, f64, PnpMethod), Box<dyn std::error::Error>> {
         // 生成简化世界坐标
-        let object_points = self.generate_simplified_object_points()?;
+        let all_object_points = self.generate_simplified_object_points()?;
         
-        // 使用solvePnP计算姿态
-        let mut rvec = Mat::default();
-        let mut tvec = Mat::default();
-        
-        calib3d::solve_pnp(
-            &object_points,
-            corners,
-            camera_matrix,
-            dist_coeffs,
-            &mut rvec,
-            &mut tvec,
-            false,
-            calib3d::SOLVEPNP_IPPE,
-        )?;
+        // 细化点足够时可排除未细化的点（按与圆点同序的细化标记，须在其他剔除改变点序之前）
+        let (object_points, corners) = self.exclude_unrefined(&all_object_points, corners)?;
+        // 默认排除部分网格补全的插值点
+        let (object_points, corners) = self.exclude_interpolated(&object_points, &corners)?;
+        let corners = &corners;
         
-        // 转换旋转向量为旋转矩阵
-        let mut rot_matrix = Mat::default();
-        calib3d::rodrigues(&rvec, &mut rot_matrix, &mut Mat::default())?;
+        // 使用solvePnP计算姿态（所选方法失败或解非有限值时退回 ITERATIVE）
+        let ((rvec_values, tvec_values), method_used) = solve_pnp_with_fallback(self.pnp_method, |method| {
+            let mut rvec = Mat::default();
+            let mut tvec = Mat::default();
+            let solved = calib3d::solve_pnp(
+                &object_points,
+                corners,
+                camera_matrix,
+                dist_coeffs,
+                &mut rvec,
+                &mut tvec,
+                false,
+                method.flag(),
+            )?;
+            if !solved || rvec.empty() || tvec.empty() {
+                return Ok(None);
+            }
+            let rvec = [*rvec.at_2d::<f64>(0, 0)?, *rvec.at_2d::<f64>(1, 0)?, *rvec.at_2d::<f64>(2, 0)?];
+            let tvec = [*tvec.at_2d::<f64>(0, 0)?, *tvec.at_2d::<f64>(1, 0)?, *tvec.at_2d::<f64>(2, 0)?];
+            Ok(Some((rvec, tvec)))
+        })?;
+        let rvec = Mat::from_slice(&rvec_values)?.try_clone()?;
+        let tvec = Mat::from_slice(&tvec_values)?.try_clone()?;
         
-        // 计算欧拉角
-        let roll = f64::atan2(
-            *rot_matrix.at_2d::<f64>(1, 0)?,
-            *rot_matrix.at_2d::<f64>(0, 0)?
-        ) * 180.0 / std::f64::consts::PI;
+        // 用解出的位姿重投影世界坐标：点对应错误时 IPPE 仍会给出确定的解，但重投影误差明显偏大
+        let mut projected = Vector::<Point2f>::new();
+        calib3d::project_points(
+            &object_points, &rvec, &tvec, camera_matrix, dist_coeffs,
+            &mut projected, &mut Mat::default(), 0.0,
+        )?;
+        let sum_sq: f64 = projected.iter().zip(corners.iter())
+            .map(|(p, o)| ((p.x - o.x) as f64).powi(2) + ((p.y - o.y) as f64).powi(2))
+            .sum();
+        let reprojection_rms = (sum_sq / corners.len().max(1) as f64).sqrt();
         
-        let pitch = f64::atan(
-            *tvec.at_2d::<f64>(1, 0)? / *tvec.at_2d::<f64>(2, 0)?
-        ) * 180.0 / std::f64::consts::PI;
+        let sample = PoseSample { rvec: rvec_values, tvec: tvec_values };
+        Ok((sample, reprojection_rms, method_used))
+    }
+    
+    /// 按阈值判定（原始约定）并按工位约定上报
+    fn evaluate_pose(&self, averaged: &AveragedPose, reprojection_rms: f64, method_used: PnpMethod) -> SingleEyePoseResult {
+        let AveragedPose { roll, pitch, yaw, spread, standoff_mm } = *averaged;
         
-        let yaw = f64::atan(
-            *tvec.at_2d::<f64>(0, 0)? / *tvec.at_2d::<f64>(2, 0)?
-        ) * 180.0 / std::f64::consts::PI;
+        // 重投影校验：解不可信时不论姿态角大小均判定不通过
+        let reject_reason = if reprojection_rms > self.pose_reprojection_max_px {
+            Some(format!("重投影RMS {:.3} px 超过上限 {:.3} px，圆点对应可能错误",
+                         reprojection_rms, self.pose_reprojection_max_px))
+        } else {
+            None
+        };
         
         // 判断是否在阈值范围内
-        let pass = roll.abs() <= ROLL_TH && 
-                   pitch.abs() <= PITCH_YAW_TH && 
-                   yaw.abs() <= PITCH_YAW_TH;
+        let pass = reject_reason.is_none() &&
+                   roll.abs() <= self.thresholds.roll_deg && 
+                   pitch.abs() <= self.thresholds.pitch_yaw_deg && 
+                   yaw.abs() <= self.thresholds.pitch_yaw_deg;
         
         println!("roll={:.3}°, pitch={:.3}°, yaw={:.3}°", roll, pitch, yaw);
-        println!("阈值: |roll| ≤ {:.2}°, |pitch|,|yaw| ≤ {:.2}°", ROLL_TH, PITCH_YAW_TH);
+        if spread.samples > 1 {
+            println!("{}帧平均, 离散度 ±{:.3}°", spread.samples, spread.spread_deg);
+        }
+        println!("阈值: |roll| ≤ {:.2}°, |pitch|,|yaw| ≤ {:.2}°", self.thresholds.roll_deg, self.thresholds.pitch_yaw_deg);
+        println!("重投影RMS: {:.3} px (上限 {:.3} px), solvePnP: {:?}", reprojection_rms, self.pose_reprojection_max_px, method_used);
+        
+        let standoff = StandoffCheck {
+            standoff_mm,
+            plausible: self.standoff_range.contains(standoff_mm),
+        };
+        println!("工作距离: {:.1} mm", standoff_mm);
+        if !standoff.plausible {
+            println!("⚠️ 工作距离 {:.1} mm 超出合理范围 [{:.0}, {:.0}] mm - 请检查世界坐标尺度/单位 (center_distance 应为 mm)",
+                     standoff_mm, self.standoff_range.min_mm, self.standoff_range.max_mm);
+        }
         
-        if pass {
+        if let Some(reason) = &reject_reason {
+            println!("❌ 姿态解不可信 - {}", reason);
+        } else if pass {
             println!("✓ 姿态检测通过");
         } else {
             println!("❌ 姿态超出容差 - 请先机械调平");
         }
         
-        Ok(SingleEyePoseResult {
+        // 按工位坐标约定上报
+        let (roll, pitch, yaw) = self.pose_convention.apply(roll, pitch, yaw);
+        if !self.pose_convention.is_identity() {
+            println!("工位约定: roll={:.3}°, pitch={:.3}°, yaw={:.3}°", roll, pitch, yaw);
+        }
+        
+        SingleEyePoseResult {
             roll,
             pitch,
             yaw,
             pass,
-        })
+            spread,
+            standoff,
+            reprojection_rms,
+            reject_reason,
+            method_used,
+        }
     }
     
     /// 3.4.3 双光机合像判定（纯合像分析，不包含姿态检测）
+    /// 
+    /// 结果的逐点残差记为最近一次合像，供重新判定与误差直方图使用
     pub fn check_dual_eye_alignment(
         &self,
         corners_left: &Vector<Point2f>,
         corners_right: &Vector<Point2f>,
         save_debug_image: bool,
     ) -> Result<DualEyeAlignmentResult, Box<dyn std::error::Error>> {
+        let (result, residuals) = self.evaluate_dual_eye_alignment(corners_left, corners_right, save_debug_image)?;
+        if let Ok(mut last) = self.last_alignment.lock() {
+            *last = Some(residuals);
+        }
+        Ok(result)
+    }
+    
+    /// 单帧合像判定（不更新最近一次合像记录），供会聚检测、测量存档等旁路测量
+    pub fn measure_dual_eye_alignment(
+        &self,
+        corners_left: &Vector<Point2f>,
+        corners_right: &Vector<Point2f>,
+    ) -> Result<DualEyeAlignmentResult, Box<dyn std::error::Error>> {
+        self.evaluate_dual_eye_alignment(corners_left, corners_right, false).map(|(result, _)| result)
+    }
+    
+    /// 合像判定计算，返回结果及逐点残差（是否记录由调用方决定）
+    fn evaluate_dual_eye_alignment(
+        &self,
+        corners_left: &Vector<Point2f>,
+        corners_right: &Vector<Point2f>,
+        save_debug_image: bool,
+    ) -> Result<(DualEyeAlignmentResult, LastAlignmentResiduals), Box<dyn std::error::Error>> {
         println!("=== 双光机合像判定 ===");
         
-        if corners_left.len() != corners_right.len() {
-            return Err("左右眼检测到的圆点数量不一致".into());
+        let expected_points = self.expected_points();
+        if corners_left.len() != expected_points || corners_right.len() != expected_points {
+            return Err(format!("圆点数量不正确: 期望每眼{}个，左眼{}个，右眼{}个",
+                               expected_points, corners_left.len(), corners_right.len()).into());
         }
         
         // 计算残差向量 Δx = xR - xL, Δy = yR - yL
         let mut dx_values = Vec::new();
         let mut dy_values = Vec::new();
         let mut errors = Vec::new();
+        let mut error_indices = Vec::new();
+        // 按序号记录的单点误差（排除的点为None），供debug图像着色
+        let mut point_errors: Vec<Option<f64>> = vec![None; corners_left.len()];
+        
+        let left_mask = self.interpolated_mask(corners_left);
+        let right_mask = self.interpolated_mask(corners_right);
         
         for i in 0..corners_left.len() {
+            // 默认排除任一眼为插值的点
+            if !self.include_interpolated_points && (left_mask[i] || right_mask[i]) {
+                continue;
+            }
+            
             let left_point = corners_left.get(i)?;
             let right_point = corners_right.get(i)?;
             
@@ -905,17 +2732,33 @@ impl AlignmentSystem {
             dx_values.push(dx);
             dy_values.push(dy);
             errors.push(error);
+            error_indices.push(i);
+            point_errors[i] = Some(error);
         }
         
-        // 计算统计量
-        let mean_dx = mean(&dx_values);
-        let mean_dy = mean(&dy_values);
-        let rms = rms(&errors);
-        let p95 = percentile(&errors, 95.0);
+        // 计算统计量（全部点被排除时无法判定）
+        if errors.is_empty() {
+            return Err("没有可用于合像判定的匹配点".into());
+        }
+        let mean_dx = mean(&dx_values).ok_or("Δx 无有效值")?;
+        let mean_dy = mean(&dy_values).ok_or("Δy 无有效值")?;
+        let rms = rms(&errors).ok_or("残差无有效值")?;
+        let p95 = percentile(&errors, self.error_percentile).ok_or("残差无有效值")?;
         let max_err = errors.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let label = percentile_label(self.error_percentile);
+        let robust = robust_error_stats(&errors, &self.robust_stats, self.error_percentile);
         
-        // 判断是否通过
-        let pass = rms <= RMS_TH && p95 <= P95_TH && max_err <= MAX_TH;
+        // 放大倍率比较（使用全部点，含插值点：跨度只取决于网格外框）
+        let magnification = MagnificationCheck::compare(corners_left, corners_right, self.magnification_mismatch_max)
+            .ok_or("网格跨度无效，无法比较放大倍率")?;
+        
+        // 判断是否通过（分位误差阈值作用于所配置分位数的误差；倍率不一致直接不通过）
+        let thresholds = &self.thresholds;
+        let (judged_rms, judged_p95, judged_max) = match &robust {
+            Some(stats) if self.robust_stats.use_for_pass => (stats.rms, stats.p95, stats.max_err),
+            _ => (rms, p95, max_err),
+        };
+        let pass = judged_rms <= thresholds.rms_px && judged_p95 <= thresholds.percentile_px && judged_max <= thresholds.max_px && !magnification.mismatch;
         
         // 输出结果
         println!("方向提示:");
@@ -923,34 +2766,115 @@ impl AlignmentSystem {
         println!("  Δy_mean = {:.3} px {}", mean_dy, if mean_dy < 0.0 { "(右眼向上调)" } else { "(右眼向下调)" });
         
         println!("统计误差:");
-        println!("  RMS = {:.3} px (阈值: {:.2})", rms, RMS_TH);
-        println!("  P95 = {:.3} px (阈值: {:.2})", p95, P95_TH);
-        println!("  Max = {:.3} px (阈值: {:.2})", max_err, MAX_TH);
+        println!("  RMS = {:.3} px (阈值: {:.2})", rms, thresholds.rms_px);
+        println!("  {} = {:.3} px (阈值: {:.2})", label, p95, thresholds.percentile_px);
+        println!("  Max = {:.3} px (阈值: {:.2})", max_err, thresholds.max_px);
+        if let Some(stats) = &robust {
+            println!("  稳健统计{}: RMS = {:.3} px, {} = {:.3} px, Max = {:.3} px, 离群点 {} 个",
+                     if self.robust_stats.use_for_pass { "（用于判定）" } else { "" },
+                     stats.rms, label, stats.p95, stats.max_err, stats.outliers.len());
+        }
+        println!("{}", magnification.message());
         
         println!("判定结果: {}", if pass { "✓ PASS" } else { "❌ FAIL" });
         
         // 生成debug图像
         if save_debug_image {
-            self.generate_alignment_debug_image(corners_left, corners_right, &dx_values, &dy_values)?;
+            self.generate_alignment_debug_image(corners_left, corners_right, &point_errors)?;
         }
         
-        Ok(DualEyeAlignmentResult {
+        let residuals = LastAlignmentResiduals { point_errors, magnification };
+        
+        Ok((DualEyeAlignmentResult {
             mean_dx,
             mean_dy,
             rms,
             p95,
             max_err,
             pass,
-        })
+            percentile: self.error_percentile,
+            magnification,
+            robust_rms: robust.as_ref().map(|stats| stats.rms),
+            robust_p95: robust.as_ref().map(|stats| stats.p95),
+            outlier_indices: robust.map(|stats| stats.outliers.iter().map(|&i| error_indices[i]).collect()).unwrap_or_default(),
+        }, residuals))
+    }
+    
+    /// 双眼会聚检测：校正后对应点的水平视差经 Q 矩阵三角化为深度，得到虚像距离
+    /// 
+    /// Q 取自校正参数；旧参数文件未保存 Q（全零）时由 P1/P2 与立体标定平移 T 构造
+    pub fn check_convergence(
+        &self,
+        corners_left: &Vector<Point2f>,
+        corners_right: &Vector<Point2f>,
+    ) -> Result<ConvergenceCheck, Box<dyn std::error::Error>> {
+        println!("=== 双眼会聚（虚像距离）检测 ===");
+        
+        if corners_left.len() != corners_right.len() || corners_left.is_empty() {
+            return Err(format!("左右圆点数量不匹配: 左眼{}个，右眼{}个", corners_left.len(), corners_right.len()).into());
+        }
+        
+        let disparities: Vec<f64> = corners_left.iter().zip(corners_right.iter())
+            .map(|(l, r)| (l.x - r.x) as f64)
+            .collect();
+        let mean_disparity_px = mean(&disparities).ok_or("视差无有效值")?;
+        let disparity_std_px = rms(&disparities.iter().map(|d| d - mean_disparity_px).collect::<Vec<_>>()).unwrap_or(0.0);
+        
+        // 平均视差在左眼圆阵中心处三角化
+        let (sum_x, sum_y) = corners_left.iter().fold((0.0, 0.0), |(x, y), p| (x + p.x as f64, y + p.y as f64));
+        let n = corners_left.len() as f64;
+        let q = self.disparity_to_depth_matrix()?;
+        let virtual_image_distance_mm = if mean_disparity_px > 0.0 {
+            let v = [sum_x / n, sum_y / n, mean_disparity_px, 1.0];
+            let [_, _, z, w] = q.map(|row| (0..4).map(|i| row[i] * v[i]).sum::<f64>());
+            let distance = z / w;
+            (distance.is_finite() && distance > 0.0).then_some(distance)
+        } else {
+            None
+        };
+        
+        let range = self.convergence_range;
+        let pass = virtual_image_distance_mm.map_or(false, |d| d >= range.min_mm && d <= range.max_mm);
+        let result = ConvergenceCheck { mean_disparity_px, disparity_std_px, virtual_image_distance_mm, range, pass };
+        println!("视差: {:.3} ± {:.3} px", mean_disparity_px, disparity_std_px);
+        println!("{}", result.message());
+        Ok(result)
+    }
+    
+    /// 视差→深度矩阵 Q (4×4)
+    fn disparity_to_depth_matrix(&self) -> Result<[[f64; 4]; 4], String> {
+        let q = &self.rectify_params.q;
+        if q.len() == 4 && q.iter().all(|row| row.len() == 4) && q.iter().flatten().any(|&v| v != 0.0) {
+            return Ok(std::array::from_fn(|i| std::array::from_fn(|j| q[i][j])));
+        }
+        
+        // Q = [[1,0,0,-cx], [0,1,0,-cy], [0,0,0,f], [0,0,-1/Tx,(cx-cx')/Tx]]
+        let (p1, p2) = (&self.rectify_params.p1, &self.rectify_params.p2);
+        let (f, cx, cy, cx_right) = (p1[0][0], p1[0][2], p1[1][2], p2[0][2]);
+        let tx = if p2[0][3] != 0.0 {
+            p2[0][3] / p2[0][0]
+        } else {
+            self.stereo_params.t.first().copied().unwrap_or(0.0)
+        };
+        if tx == 0.0 || f == 0.0 {
+            return Err("校正参数缺少 Q 矩阵且基线为0，无法换算深度".to_string());
+        }
+        Ok([
+            [1.0, 0.0, 0.0, -cx],
+            [0.0, 1.0, 0.0, -cy],
+            [0.0, 0.0, 0.0, f],
+            [0.0, 0.0, -1.0 / tx, (cx - cx_right) / tx],
+        ])
     }
     
     /// 🎯 检查左眼图像是否居中
     /// 
     /// 基于asymmetric circles grid的关键点位置判断图像是否居中。
-    /// 使用右上角点(序号0)和左下角点(序号39)作为参考点。
+    /// 使用右上角点(序号0)和左下角点(末点，序号 pattern_size.area()-1)作为参考点，期望位置为当前配置的 CenteringTargets。
+    /// 关键点序号由标定板规格决定（见 centering_key_indices）。
     /// 
     /// # 参数
-    /// - `corners`: 检测到的40个圆心坐标 (10×4网格)
+    /// - `corners`: 检测到的圆心坐标 (pattern_size.area() 个，4×10时为40个)
     /// - `tolerance_px`: 居中容差阈值 (像素)，如果为None则使用默认值
     /// 
     /// # 返回
@@ -959,24 +2883,40 @@ impl AlignmentSystem {
         &self,
         corners: &Vector<Point2f>,
         tolerance_px: Option<f32>,
+    ) -> Result<CenteringResult, Box<dyn std::error::Error>> {
+        self.check_left_eye_centering_with_targets(corners, &self.centering_targets, tolerance_px)
+    }
+    
+    /// 🎯 按指定期望位置检查左眼图像是否居中（按设备传入期望位置）
+    /// 
+    /// 期望位置超出 image_size 时返回错误，不输出无意义的偏移量
+    pub fn check_left_eye_centering_with_targets(
+        &self,
+        corners: &Vector<Point2f>,
+        targets: &CenteringTargets,
+        tolerance_px: Option<f32>,
     ) -> Result<CenteringResult, Box<dyn std::error::Error>> {
         println!("=== 左眼图像居中检测 ===");
         
+        targets.validate(self.image_size)?;
+        
         // 验证圆点数量
-        if corners.len() != 40 {
-            return Err(format!("圆点数量不正确: 期望40个，实际{}个", corners.len()).into());
+        let expected_points = self.expected_points();
+        if corners.len() != expected_points {
+            return Err(format!("圆点数量不正确: 期望{}个，实际{}个", expected_points, corners.len()).into());
         }
         
         let tolerance = tolerance_px.unwrap_or(CENTERING_TOLERANCE_PX);
         
         // 获取关键点坐标
-        // 根据asymmetric circles grid的排列，序号0在右上角，序号39在左下角
-        let actual_top_right = corners.get(0)?;      // 序号0: 右上角
-        let actual_bottom_left = corners.get(39)?;   // 序号39: 左下角
+        // 根据asymmetric circles grid的排列，序号0在右上角，末点在左下角
+        let (top_right_index, bottom_left_index) = centering_key_indices(self.pattern_size());
+        let actual_top_right = corners.get(top_right_index)?;      // 序号0: 右上角
+        let actual_bottom_left = corners.get(bottom_left_index)?;  // 末点: 左下角
         
         // 期望位置
-        let expected_top_right = Point2f::new(EXPECTED_TOP_RIGHT.0, EXPECTED_TOP_RIGHT.1);
-        let expected_bottom_left = Point2f::new(EXPECTED_BOTTOM_LEFT.0, EXPECTED_BOTTOM_LEFT.1);
+        let expected_top_right = Point2f::new(targets.expected_top_right.0, targets.expected_top_right.1);
+        let expected_bottom_left = Point2f::new(targets.expected_bottom_left.0, targets.expected_bottom_left.1);
         
         // 计算偏移量
         let top_right_offset_x = actual_top_right.x - expected_top_right.x;
@@ -999,14 +2939,14 @@ impl AlignmentSystem {
         
         // 输出检测结果
         println!("关键点位置分析:");
-        println!("  右上角点(序号0):");
+        println!("  右上角点(序号{}):", top_right_index);
         println!("    期望位置: ({:.1}, {:.1})", expected_top_right.x, expected_top_right.y);
         println!("    实际位置: ({:.1}, {:.1})", actual_top_right.x, actual_top_right.y);
         println!("    偏移量: ({:.1}, {:.1}) px", top_right_offset_x, top_right_offset_y);
         println!("    偏移距离: {:.1} px (容差: {:.1} px) {}", 
                 top_right_distance, tolerance, if top_right_ok { "✓" } else { "❌" });
         
-        println!("  左下角点(序号39):");
+        println!("  左下角点(序号{}):", bottom_left_index);
         println!("    期望位置: ({:.1}, {:.1})", expected_bottom_left.x, expected_bottom_left.y);
         println!("    实际位置: ({:.1}, {:.1})", actual_bottom_left.x, actual_bottom_left.y);
         println!("    偏移量: ({:.1}, {:.1}) px", bottom_left_offset_x, bottom_left_offset_y);
@@ -1107,6 +3047,28 @@ impl AlignmentSystem {
         }
     }
     
+    /// 由已完成的各阶段检测结果组装完整检测报告（调整向量按当前阈值计算）
+    pub fn build_report(
+        &self,
+        left_pose: &SingleEyePoseResult,
+        left_centering: &CenteringResult,
+        right_pose: &SingleEyePoseResult,
+        alignment: &DualEyeAlignmentResult,
+    ) -> AlignmentReport {
+        let adjustments = self.calculate_adjustment_vectors(
+            Some(left_pose), Some(left_centering), Some(right_pose), Some(alignment));
+        AlignmentReport {
+            timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            pass: left_pose.pass && left_centering.is_centered && right_pose.pass && alignment.pass,
+            left_pose: left_pose.clone(),
+            right_pose: right_pose.clone(),
+            left_centering: left_centering.clone(),
+            alignment: alignment.clone(),
+            adjustments,
+            units: source_units(MetricSource::AlignmentReport),
+        }
+    }
+    
     /// 计算单眼调整建议
     fn calculate_eye_adjustment(
         &self,
@@ -1138,8 +3100,9 @@ impl AlignmentSystem {
         
         // 处理居中调整（仅左眼）
         if let Some(centering_result) = centering {
-            adjustment.centering_x = -centering_result.top_right_offset_x; // 反向调整
-            adjustment.centering_y = -centering_result.top_right_offset_y;
+            let (sx, sy) = self.pose_convention.pixel_signs();
+            adjustment.centering_x = -centering_result.top_right_offset_x * sx as f32; // 反向调整
+            adjustment.centering_y = -centering_result.top_right_offset_y * sy as f32;
             adjustment.needs_adjustment = adjustment.needs_adjustment || !centering_result.is_centered;
             
             println!("{}居中调整建议:", eye_name);
@@ -1158,27 +3121,32 @@ impl AlignmentSystem {
         alignment: Option<&DualEyeAlignmentResult>,
     ) -> AlignmentAdjustment {
         if let Some(alignment_result) = alignment {
-            let priority_desc = if alignment_result.rms > RMS_TH {
-                "RMS误差过大，优先调整整体对准"
-            } else if alignment_result.p95 > P95_TH {
-                "P95误差过大，优先调整局部对准"
-            } else if alignment_result.max_err > MAX_TH {
-                "最大误差过大，优先调整极值点"
+            let thresholds = &self.thresholds;
+            let priority_desc = if alignment_result.rms > thresholds.rms_px {
+                "RMS误差过大，优先调整整体对准".to_string()
+            } else if alignment_result.p95 > thresholds.percentile_px {
+                format!("{}误差过大，优先调整局部对准", alignment_result.percentile_label())
+            } else if alignment_result.max_err > thresholds.max_px {
+                "最大误差过大，优先调整极值点".to_string()
             } else {
-                "合像精度良好"
+                "合像精度良好".to_string()
             };
             
+            let (sx, sy) = self.pose_convention.pixel_signs();
+            let delta_x = -alignment_result.mean_dx * sx; // 反向调整
+            let delta_y = -alignment_result.mean_dy * sy;
+            
             println!("合像调整建议:");
-            println!("  X方向调整: {:.3}px (右眼相对左眼)", -alignment_result.mean_dx);
-            println!("  Y方向调整: {:.3}px (右眼相对左眼)", -alignment_result.mean_dy);
+            println!("  X方向调整: {:.3}px (右眼相对左眼)", delta_x);
+            println!("  Y方向调整: {:.3}px (右眼相对左眼)", delta_y);
             println!("  RMS误差: {:.3}px", alignment_result.rms);
             println!("  调整优先级: {}", priority_desc);
             
             AlignmentAdjustment {
-                delta_x: -alignment_result.mean_dx, // 反向调整
-                delta_y: -alignment_result.mean_dy,
+                delta_x,
+                delta_y,
                 rms_error: alignment_result.rms,
-                adjustment_priority: priority_desc.to_string(),
+                adjustment_priority: priority_desc,
             }
         } else {
             AlignmentAdjustment {
@@ -1200,12 +3168,13 @@ impl AlignmentSystem {
         centering: Option<&CenteringResult>,
     ) -> AdjustmentPriority {
         // 优先级逻辑：姿态 -> 居中 -> 合像
+        let thresholds = &self.thresholds;
         
         // 1. 检查左眼姿态
         if left_pose_adj.needs_adjustment && 
-           (left_pose_adj.roll_adjustment.abs() > ROLL_TH || 
-            left_pose_adj.pitch_adjustment.abs() > PITCH_YAW_TH ||
-            left_pose_adj.yaw_adjustment.abs() > PITCH_YAW_TH) {
+           (left_pose_adj.roll_adjustment.abs() > thresholds.roll_deg || 
+            left_pose_adj.pitch_adjustment.abs() > thresholds.pitch_yaw_deg ||
+            left_pose_adj.yaw_adjustment.abs() > thresholds.pitch_yaw_deg) {
             return AdjustmentPriority::LeftEyePose;
         }
         
@@ -1218,14 +3187,14 @@ impl AlignmentSystem {
         
         // 3. 检查右眼姿态
         if right_pose_adj.needs_adjustment &&
-           (right_pose_adj.roll_adjustment.abs() > ROLL_TH || 
-            right_pose_adj.pitch_adjustment.abs() > PITCH_YAW_TH ||
-            right_pose_adj.yaw_adjustment.abs() > PITCH_YAW_TH) {
+           (right_pose_adj.roll_adjustment.abs() > thresholds.roll_deg || 
+            right_pose_adj.pitch_adjustment.abs() > thresholds.pitch_yaw_deg ||
+            right_pose_adj.yaw_adjustment.abs() > thresholds.pitch_yaw_deg) {
             return AdjustmentPriority::RightEyePose;
         }
         
         // 4. 检查双眼合像
-        if alignment_adj.rms_error > RMS_TH {
+        if alignment_adj.rms_error > thresholds.rms_px {
             return AdjustmentPriority::DualEyeAlignment;
         }
         
@@ -1233,103 +3202,255 @@ impl AlignmentSystem {
         AdjustmentPriority::Complete
     }
     
-    /// 生成带标注的debug图像
+    /// 生成带标注的debug图像（样式由 DebugRenderConfig 控制）
+    /// 
+    /// point_errors: 按序号的单点合像误差，None 表示未参与统计（如插值点）
     fn generate_alignment_debug_image(
         &self,
         corners_left: &Vector<Point2f>,
         corners_right: &Vector<Point2f>,
-        dx_values: &[f64],
-        dy_values: &[f64],
+        point_errors: &[Option<f64>],
     ) -> Result<(), opencv::Error> {
         println!("生成合像检测debug图像...");
+        let style = &self.debug_render;
         
-        // 创建debug图像 (白色背景)
-                  let mut debug_img = Mat::new_rows_cols_with_default(
-            self.image_size.height,
-            self.image_size.width,
-              CV_8UC3, // 使用8位3通道RGB格式
-              Scalar::new(255.0, 255.0, 255.0, 0.0),
-        )?;
+        let mut debug_img = self.create_debug_background(style.background)?;
+        
+        let left_color = DebugRenderConfig::scalar(&style.left_color);
+        let right_color = DebugRenderConfig::scalar(&style.right_color);
+        let line_color = DebugRenderConfig::scalar(&style.line_color);
+        let text_color = DebugRenderConfig::scalar(&style.text_color);
+        let stride = style.index_stride.max(1);
+        let last_index = corners_left.len().saturating_sub(1);
+        
+        // 着色上限：未配置时取本帧最大误差
+        let error_max = if style.error_color_max_px > 0.0 {
+            style.error_color_max_px
+        } else {
+            point_errors.iter().flatten().cloned().fold(0.0, f64::max)
+        };
+        let mut skipped_lines = 0;
         
         // 绘制左右眼圆点和连线
         for i in 0..corners_left.len() {
             let left_point = corners_left.get(i)?;
             let right_point = corners_right.get(i)?;
             
-            // 使用Point2f保持浮点精度
-            let left_pt = Point2f::new(left_point.x, left_point.y);
-            let right_pt = Point2f::new(right_point.x, right_point.y);
+            // 仅在绘制时转换为整型
+            let left_pt = Point::new(left_point.x.round() as i32, left_point.y.round() as i32);
+            let right_pt = Point::new(right_point.x.round() as i32, right_point.y.round() as i32);
             
-            // 绘制左眼圆点 (蓝色)
-            imgproc::circle(
-                &mut debug_img,
-                Point::new(left_pt.x as i32, left_pt.y as i32), // 仅在绘制时转换为整型
-                3,
-                Scalar::new(255.0, 0.0, 0.0, 0.0), // 蓝色
-                -1,
-                imgproc::LINE_8,
-                0,
-            )?;
+            // 先画连线，避免遮挡圆点；低于阈值的连线不绘制以突出最差点
+            let point_error = point_errors.get(i).copied().flatten();
+            let draw_line = point_error.map_or(true, |e| e >= style.min_line_error_px);
+            if draw_line {
+                let color = match point_error {
+                    Some(e) if style.color_lines_by_error => DebugRenderConfig::error_color(e, error_max),
+                    _ => line_color,
+                };
+                imgproc::line(
+                    &mut debug_img,
+                    left_pt,
+                    right_pt,
+                    color,
+                    style.line_thickness,
+                    imgproc::LINE_AA,
+                    0,
+                )?;
+            } else {
+                skipped_lines += 1;
+            }
             
-            // 绘制右眼圆点 (红色)
-            imgproc::circle(
-                &mut debug_img,
-                Point::new(right_pt.x as i32, right_pt.y as i32), // 仅在绘制时转换为整型
-                3,
-                Scalar::new(0.0, 0.0, 255.0, 0.0), // 红色
-                -1,
-                imgproc::LINE_8,
-                0,
-            )?;
+            // 左眼圆点
+            imgproc::circle(&mut debug_img, left_pt, style.marker_radius, left_color, -1, imgproc::LINE_AA, 0)?;
             
-            // 绘制连线 (绿色)
-            imgproc::line(
-                &mut debug_img,
-                Point::new(left_pt.x as i32, left_pt.y as i32), // 仅在绘制时转换为整型
-                Point::new(right_pt.x as i32, right_pt.y as i32), // 仅在绘制时转换为整型
-                Scalar::new(0.0, 255.0, 0.0, 0.0), // 绿色
-                1,
-                imgproc::LINE_8,
-                0,
-            )?;
+            // 右眼圆点
+            imgproc::circle(&mut debug_img, right_pt, style.marker_radius, right_color, -1, imgproc::LINE_AA, 0)?;
             
-            // 添加序号标注
-            let text = format!("{}", i);
-            imgproc::put_text(
-                &mut debug_img,
-                &text,
-                Point::new((left_pt.x - 10.0) as i32, (left_pt.y - 10.0) as i32), // 仅在绘制时转换为整型
-                imgproc::FONT_HERSHEY_SIMPLEX,
-                0.4,
-                Scalar::new(0.0, 0.0, 0.0, 0.0), // 黑色
-                1,
-                imgproc::LINE_8,
-                false,
-            )?;
+            // 序号标注（按间隔绘制，首末点始终绘制）
+            if style.draw_indices && (i % stride == 0 || i == last_index) {
+                let offset = style.marker_radius + 4;
+                imgproc::put_text(
+                    &mut debug_img,
+                    &format!("{}", i),
+                    Point::new(left_pt.x - offset - 6, left_pt.y - offset),
+                    imgproc::FONT_HERSHEY_SIMPLEX,
+                    style.font_scale,
+                    text_color,
+                    1.max(style.line_thickness - 1),
+                    imgproc::LINE_AA,
+                    false,
+                )?;
+            }
         }
         
         // 保存debug图像
-        imgcodecs::imwrite("alignment_debug.png", &debug_img, &Vector::<i32>::new())?;
-        println!("已保存合像检测debug图像: alignment_debug.png");
+        let debug_path = crate::paths::captures_path("alignment_debug.png");
+        let _ = std::fs::create_dir_all(crate::paths::captures_dir());
+        imgcodecs::imwrite(&debug_path, &debug_img, &Vector::<i32>::new())?;
+        println!("已保存合像检测debug图像: {} (底图: {:?})", debug_path, style.background);
+        if style.color_lines_by_error {
+            println!("   连线着色: 绿(0 px) → 红({:.3} px)", error_max);
+        }
+        if skipped_lines > 0 {
+            println!("   已隐藏 {} 条误差低于 {:.3} px 的连线", skipped_lines, style.min_line_error_px);
+        }
         
         Ok(())
     }
+    
+    /// 创建debug图像底图（8位3通道）
+    fn create_debug_background(&self, background: DebugBackground) -> Result<Mat, opencv::Error> {
+        let rectified = match (background, &self.last_rectified) {
+            (DebugBackground::RectifiedLeft, Some((left, _))) => Some(left),
+            (DebugBackground::RectifiedRight, Some((_, right))) => Some(right),
+            _ => None,
+        };
+        
+        if let Some(rect) = rectified {
+            let mut bgr = Mat::default();
+            if rect.channels() == 1 {
+                imgproc::cvt_color(rect, &mut bgr, imgproc::COLOR_GRAY2BGR, 0, AlgorithmHint::ALGO_HINT_DEFAULT)?;
+            } else {
+                rect.copy_to(&mut bgr)?;
+            }
+            return Ok(bgr);
+        }
+        
+        if background.needs_rectified() {
+            println!("⚠️ 无可用的重映射图像，debug底图回退为白色");
+        }
+        
+        let value = if background == DebugBackground::Black { 0.0 } else { 255.0 };
+        Mat::new_rows_cols_with_default(
+            self.image_size.height,
+            self.image_size.width,
+            CV_8UC3,
+            Scalar::new(value, value, value, 0.0),
+        )
+    }
 }
 
 // ---------- 辅助函数 ----------
-pub fn mean(values: &[f64]) -> f64 {
-    values.iter().sum::<f64>() / values.len() as f64
+// 统计函数忽略 NaN；输入为空（或全为 NaN）时返回 None，由调用方决定如何处理
+
+fn valid_values(values: &[f64]) -> Vec<f64> {
+    values.iter().copied().filter(|v| !v.is_nan()).collect()
+}
+
+pub fn mean(values: &[f64]) -> Option<f64> {
+    let values = valid_values(values);
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<f64>() / values.len() as f64)
+}
+
+pub fn rms(values: &[f64]) -> Option<f64> {
+    let values = valid_values(values);
+    if values.is_empty() {
+        return None;
+    }
+    Some((values.iter().map(|v| v * v).sum::<f64>() / values.len() as f64).sqrt())
+}
+
+pub fn percentile(data: &[f64], pct: f64) -> Option<f64> {
+    let mut sorted = valid_values(data);
+    if sorted.is_empty() {
+        return None;
+    }
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let index = ((pct.clamp(0.0, 100.0) / 100.0) * (sorted.len() as f64 - 1.0)).round() as usize;
+    Some(sorted[index.min(sorted.len() - 1)])
+}
+
+/// MAD 离群点：值超过 median + k·MAD 的下标（只判误差偏大一侧），MAD 低于 mad_floor 时按 mad_floor 计
+pub fn mad_outliers(values: &[f64], k: f64, mad_floor: f64) -> Vec<usize> {
+    let median = match percentile(values, 50.0) {
+        Some(median) => median,
+        None => return Vec::new(),
+    };
+    let deviations: Vec<f64> = values.iter().map(|v| (v - median).abs()).collect();
+    let mad = percentile(&deviations, 50.0).unwrap_or(0.0).max(mad_floor);
+    values.iter().enumerate()
+        .filter(|(_, &v)| v - median > k * mad)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// 剔除离群点后的误差统计；outliers 为 errors 中的下标
+struct RobustErrorStats {
+    rms: f64,
+    p95: f64,
+    max_err: f64,
+    outliers: Vec<usize>,
+}
+
+/// 未启用稳健统计或无有效误差时返回 None
+fn robust_error_stats(errors: &[f64], config: &RobustStatsConfig, pct: f64) -> Option<RobustErrorStats> {
+    if !config.enabled {
+        return None;
+    }
+    let outliers = mad_outliers(errors, config.mad_k, ROBUST_MAD_FLOOR_PX);
+    let inliers: Vec<f64> = errors.iter().enumerate()
+        .filter(|(i, _)| !outliers.contains(i))
+        .map(|(_, &e)| e)
+        .collect();
+    Some(RobustErrorStats {
+        rms: rms(&inliers)?,
+        p95: percentile(&inliers, pct)?,
+        max_err: inliers.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+        outliers,
+    })
+}
+
+/// 误差直方图桶数上限（桶宽过小时拒绝，避免生成大量空桶）
+pub const MAX_HISTOGRAM_BINS: usize = 200;
+
+/// 未指定桶宽时的桶数（按最大误差均分）
+pub const DEFAULT_HISTOGRAM_BINS: usize = 10;
+
+/// 直方图单个桶 [lower, upper)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HistogramBin {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: usize,
 }
 
-pub fn rms(values: &[f64]) -> f64 {
-    (values.iter().map(|v| v * v).sum::<f64>() / values.len() as f64).sqrt()
+/// 单点合像误差直方图：集中在单个桶内为整体偏移，长尾为局部（个别角点）偏差
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorHistogram {
+    pub bin_width: f64,     // 桶宽 (像素)
+    pub bins: Vec<HistogramBin>, // 从0开始，覆盖到最大误差所在桶
+    pub total: usize,       // 参与统计的点数
+    pub max_err: f64,
 }
 
-pub fn percentile(data: &[f64], pct: f64) -> f64 {
-    let mut sorted = data.to_vec();
-    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
-    let index = ((pct / 100.0) * (sorted.len() as f64 - 1.0)).round() as usize;
-    sorted[index.min(sorted.len() - 1)]
+/// 按固定桶宽统计误差分布（忽略 NaN），输入为空时返回 None
+pub fn error_histogram(errors: &[f64], bin_width: f64) -> Result<Option<ErrorHistogram>, String> {
+    if !(bin_width > 0.0 && bin_width.is_finite()) {
+        return Err(format!("直方图桶宽必须为正数: {}", bin_width));
+    }
+    let values = valid_values(errors);
+    let max_err = match values.iter().cloned().reduce(f64::max) {
+        Some(max_err) => max_err,
+        None => return Ok(None),
+    };
+    let bin_count = (max_err.max(0.0) / bin_width).floor() as usize + 1;
+    if bin_count > MAX_HISTOGRAM_BINS {
+        return Err(format!("桶宽 {} 过小：最大误差 {:.3} 需要 {} 个桶（上限 {}）",
+                           bin_width, max_err, bin_count, MAX_HISTOGRAM_BINS));
+    }
+    
+    let mut bins: Vec<HistogramBin> = (0..bin_count)
+        .map(|i| HistogramBin { lower: i as f64 * bin_width, upper: (i + 1) as f64 * bin_width, count: 0 })
+        .collect();
+    for value in &values {
+        let index = ((value.max(0.0) / bin_width).floor() as usize).min(bin_count - 1);
+        bins[index].count += 1;
+    }
+    Ok(Some(ErrorHistogram { bin_width, bins, total: values.len(), max_err }))
 }
 
 /// 为流水线处理添加的访问方法
@@ -1344,6 +3465,508 @@ impl AlignmentSystem {
         }
     }
     
+    /// 获取最近一次检测中指定眼的筛选阶段诊断（各阶段计数、被拒候选）
+    pub fn get_last_detection_diagnostics(&self, is_left: bool) -> Option<&DetectionDiagnostics> {
+        if is_left {
+            self.last_diagnostics.0.as_ref()
+        } else {
+            self.last_diagnostics.1.as_ref()
+        }
+    }
+    
+    /// 获取最近一次检测中指定眼的圆点二值图（校正后坐标系，255=前景）
+    pub fn get_last_binary_mask(&self, is_left: bool) -> Option<&Mat> {
+        if is_left {
+            self.last_binary_masks.0.as_ref()
+        } else {
+            self.last_binary_masks.1.as_ref()
+        }
+    }
+    
+    /// 开启/关闭实时调试叠加数据的保留（关闭时释放已保留的校正图）
+    pub fn set_mask_overlay_enabled(&mut self, enabled: bool) {
+        self.mask_overlay_enabled = enabled;
+        if !enabled {
+            self.last_overlay_sources = (None, None);
+        }
+    }
+    
+    /// 最近一次检测中指定眼的调试叠加图（见 compose_mask_overlay），该眼未检测过时返回 None
+    pub fn render_mask_overlay(&self, is_left: bool, scale: f64) -> Result<Option<Mat>, opencv::Error> {
+        let source = if is_left { &self.last_overlay_sources.0 } else { &self.last_overlay_sources.1 };
+        match source {
+            Some(source) => compose_mask_overlay(&source.rectified, self.get_last_binary_mask(is_left), &source.blobs, scale).map(Some),
+            None => Ok(None),
+        }
+    }
+    
+    /// 重映射左右原始图像并合成红/青立体图（人工目视检查合像用，见 compose_anaglyph）
+    /// 
+    /// 重映射矩阵与 detect_circles_grid 一样由调用方按标定参数目录给出
+    pub fn render_anaglyph(&mut self, left_image: &Mat, right_image: &Mat, rectify_maps_path: &str) -> Result<Mat, Box<dyn std::error::Error>> {
+        self.ensure_maps_loaded(rectify_maps_path)?;
+        let (left_map1, left_map2) = self.left_maps.as_ref().ok_or("重映射矩阵未加载")?;
+        let (right_map1, right_map2) = self.right_maps.as_ref().ok_or("重映射矩阵未加载")?;
+        
+        // 仅供人工查看，与预览一样使用最近邻插值
+        let left_rect = self.rectifier.remap_image_adaptive(left_image, left_map1, left_map2, RemapInterpolation::Nearest)?;
+        let right_rect = self.rectifier.remap_image_adaptive(right_image, right_map1, right_map2, RemapInterpolation::Nearest)?;
+        Ok(compose_anaglyph(&left_rect, &right_rect)?)
+    }
+    
+    /// 获取最近一次 detect_circles_grid 的分阶段耗时
+    pub fn get_last_stage_timings(&self) -> StageTimings {
+        self.last_timings.clone()
+    }
+    
+    /// 最近一次 check_dual_eye_alignment 的单点误差（按点序号，未参与统计的点为None）
+    pub fn last_point_errors(&self) -> Vec<Option<f64>> {
+        self.last_alignment.lock().ok()
+            .and_then(|last| last.as_ref().map(|residuals| residuals.point_errors.clone()))
+            .unwrap_or_default()
+    }
+    
+    /// 最近一次姿态/合像结果按新阈值重新判定（不改变实时判定所用阈值，无需重新测量）
+    pub fn reevaluate_last(&self, thresholds: &AcceptanceThresholds) -> Result<ReevaluationVerdict, String> {
+        thresholds.validate()?;
+        
+        let poses = *self.last_poses.lock().map_err(|_| "姿态缓存锁中毒")?;
+        let [left_pose_pass, right_pose_pass] = poses.map(|pose| pose.map(|pose| pose.passes(thresholds)));
+        
+        let alignment = match self.last_alignment.lock().map_err(|_| "合像缓存锁中毒")?.as_ref() {
+            Some(residuals) => {
+                let errors: Vec<f64> = residuals.point_errors.iter().flatten().copied().collect();
+                let (rms, p95, max_err) = match robust_error_stats(&errors, &self.robust_stats, self.error_percentile) {
+                    Some(stats) if self.robust_stats.use_for_pass => (stats.rms, stats.p95, stats.max_err),
+                    _ => (
+                        rms(&errors).ok_or("残差无有效值")?,
+                        percentile(&errors, self.error_percentile).ok_or("残差无有效值")?,
+                        errors.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+                    ),
+                };
+                let (rms_pass, p95_pass, max_pass) = (
+                    rms <= thresholds.rms_px,
+                    p95 <= thresholds.percentile_px,
+                    max_err <= thresholds.max_px,
+                );
+                let magnification_mismatch = residuals.magnification.mismatch;
+                Some(ReevaluatedAlignment {
+                    rms, p95, max_err, rms_pass, p95_pass, max_pass, magnification_mismatch,
+                    pass: rms_pass && p95_pass && max_pass && !magnification_mismatch,
+                })
+            }
+            None => None,
+        };
+        
+        let verdicts: Vec<bool> = [left_pose_pass, right_pose_pass, alignment.map(|a| a.pass)]
+            .into_iter().flatten().collect();
+        if verdicts.is_empty() {
+            return Err("尚无姿态或合像判定结果".to_string());
+        }
+        Ok(ReevaluationVerdict {
+            thresholds: *thresholds,
+            left_pose_pass,
+            right_pose_pass,
+            alignment,
+            pass: verdicts.iter().all(|&pass| pass),
+        })
+    }
+    
+    /// 最近一次合像判定的单点误差直方图；未指定桶宽时按最大误差均分为 DEFAULT_HISTOGRAM_BINS 个桶
+    pub fn last_error_histogram(&self, bin_width: Option<f64>) -> Result<ErrorHistogram, String> {
+        let errors: Vec<f64> = self.last_point_errors().into_iter().flatten().collect();
+        let bin_width = bin_width.unwrap_or_else(|| {
+            let max_err = errors.iter().cloned().fold(0.0, f64::max);
+            // 略大于 max/N，使最大误差落在第N个桶内
+            (max_err / DEFAULT_HISTOGRAM_BINS as f64 * 1.0001).max(1e-3)
+        });
+        error_histogram(&errors, bin_width)?.ok_or_else(|| "尚无合像判定结果".to_string())
+    }
+    
+    /// 设置插值点是否参与姿态/合像计算（默认 false）
+    pub fn set_include_interpolated_points(&mut self, include: bool) {
+        self.include_interpolated_points = include;
+    }
+    
+    /// 设置细化点足够时姿态解算是否排除未细化（仅连通域质心）的点（默认 false）
+    pub fn set_exclude_unrefined_points(&mut self, exclude: bool) {
+        self.exclude_unrefined_points = exclude;
+    }
+    
+    pub fn get_exclude_unrefined_points(&self) -> bool {
+        self.exclude_unrefined_points
+    }
+    
+    /// 设置分位误差所用分位数 (0, 100]，默认95
+    pub fn set_error_percentile(&mut self, pct: f64) -> Result<(), String> {
+        if !(pct > 0.0 && pct <= 100.0) {
+            return Err(format!("分位数必须在(0, 100]范围内: {}", pct));
+        }
+        self.error_percentile = pct;
+        Ok(())
+    }
+    
+    /// 获取分位误差所用分位数
+    pub fn get_error_percentile(&self) -> f64 {
+        self.error_percentile
+    }
+    
+    /// 设置姿态/合像判定阈值，对后续检测生效
+    pub fn set_thresholds(&mut self, thresholds: AcceptanceThresholds) -> Result<(), String> {
+        thresholds.validate()?;
+        self.thresholds = thresholds;
+        Ok(())
+    }
+    
+    /// 获取当前姿态/合像判定阈值
+    pub fn get_thresholds(&self) -> AcceptanceThresholds {
+        self.thresholds
+    }
+    
+    /// 设置左眼居中判定的期望关键点位置（须位于图像范围内）
+    pub fn set_centering_targets(&mut self, targets: CenteringTargets) -> Result<(), String> {
+        targets.validate(self.image_size)?;
+        self.centering_targets = targets;
+        Ok(())
+    }
+    
+    /// 获取左眼居中判定的期望关键点位置
+    pub fn get_centering_targets(&self) -> CenteringTargets {
+        self.centering_targets
+    }
+    
+    /// 最近一次检测中插值补齐的点（左右眼合并，未补全时为空）
+    pub fn get_interpolated_points(&self) -> &[Point2f] {
+        &self.interpolated_points
+    }
+    
+    /// 最近一次检测中未经亚像素细化的点（左右眼合并，含插值点）
+    pub fn get_unrefined_points(&self) -> Vec<Point2f> {
+        self.corner_refine_tags.iter()
+            .flat_map(|(corners, tags)| corners.iter().zip(tags.iter()).filter(|(_, tag)| !tag.is_refined()).map(|(p, _)| p))
+            .collect()
+    }
+    
+    /// corners 为最近一次检测的输出时，返回与其逐点对应的细化来源标记
+    pub fn get_corner_refine_tags(&self, corners: &Vector<Point2f>) -> Option<&[RefineTag]> {
+        self.corner_refine_tags.iter()
+            .find(|(detected, _)| detected.len() == corners.len() && detected.iter().zip(corners.iter()).all(|(p, q)| p == q))
+            .map(|(_, tags)| tags.as_slice())
+    }
+    
+    /// 标记 corners 中哪些点为最近一次检测的插值点（坐标精确匹配）
+    fn interpolated_mask(&self, corners: &Vector<Point2f>) -> Vec<bool> {
+        corners.iter().map(|p| {
+            self.interpolated_points.iter().any(|q| (p.x - q.x).abs() < 1e-3 && (p.y - q.y).abs() < 1e-3)
+        }).collect()
+    }
+    
+    /// 按配置剔除插值点，返回对应的 (世界坐标, 图像坐标)
+    fn exclude_interpolated(
+        &self,
+        object_points: &Vector<Point3f>,
+        corners: &Vector<Point2f>,
+    ) -> Result<(Vector<Point3f>, Vector<Point2f>), opencv::Error> {
+        if self.include_interpolated_points || self.interpolated_points.is_empty() {
+            return Ok((object_points.clone(), corners.clone()));
+        }
+        
+        let mask = self.interpolated_mask(corners);
+        let mut obj = Vector::<Point3f>::new();
+        let mut img = Vector::<Point2f>::new();
+        for (i, interpolated) in mask.iter().enumerate() {
+            if !interpolated {
+                obj.push(object_points.get(i)?);
+                img.push(corners.get(i)?);
+            }
+        }
+        if mask.iter().any(|m| *m) {
+            println!("🧩 姿态解算排除 {} 个插值点", mask.iter().filter(|m| **m).count());
+        }
+        Ok((obj, img))
+    }
+    
+    /// 按配置剔除未细化点（质心回退与插值点）；剔除后剩余点少于总数一半时保留全部，避免少量点解算不稳定
+    /// 
+    /// corners 须为最近一次检测输出的点序，否则无对应标记，不做剔除
+    pub(crate) fn exclude_unrefined(
+        &self,
+        object_points: &Vector<Point3f>,
+        corners: &Vector<Point2f>,
+    ) -> Result<(Vector<Point3f>, Vector<Point2f>), opencv::Error> {
+        let tags = match self.get_corner_refine_tags(corners) {
+            Some(tags) if self.exclude_unrefined_points => tags,
+            _ => return Ok((object_points.clone(), corners.clone())),
+        };
+        
+        let mask: Vec<bool> = tags.iter().map(|tag| !tag.is_refined()).collect();
+        let excluded = mask.iter().filter(|m| **m).count();
+        if excluded == 0 {
+            return Ok((object_points.clone(), corners.clone()));
+        }
+        if (corners.len() - excluded) * 2 < corners.len() {
+            println!("⚠️ 未细化点 {} 个过多，姿态解算保留全部点", excluded);
+            return Ok((object_points.clone(), corners.clone()));
+        }
+        
+        let mut obj = Vector::<Point3f>::new();
+        let mut img = Vector::<Point2f>::new();
+        for (i, unrefined) in mask.iter().enumerate() {
+            if !unrefined {
+                obj.push(object_points.get(i)?);
+                img.push(corners.get(i)?);
+            }
+        }
+        println!("🎯 姿态解算排除 {} 个未细化点", excluded);
+        Ok((obj, img))
+    }
+    
+    /// 设置合像debug图像绘制样式
+    pub fn set_debug_render_config(&mut self, config: DebugRenderConfig) {
+        if !config.background.needs_rectified() {
+            self.last_rectified = None;
+        }
+        self.debug_render = config;
+    }
+    
+    /// 获取合像debug图像绘制样式
+    pub fn get_debug_render_config(&self) -> DebugRenderConfig {
+        self.debug_render.clone()
+    }
+    
+    /// 设置无投影（全黑帧）判定阈值
+    pub fn set_blank_frame_config(&mut self, config: BlankFrameConfig) {
+        self.blank_frame_config = config;
+    }
+    
+    /// 获取无投影（全黑帧）判定阈值
+    pub fn get_blank_frame_config(&self) -> BlankFrameConfig {
+        self.blank_frame_config.clone()
+    }
+    
+    /// 设置上报姿态角/调整量的坐标约定
+    pub fn set_pose_convention(&mut self, convention: PoseConvention) -> Result<(), String> {
+        convention.validate()?;
+        self.pose_convention = convention;
+        Ok(())
+    }
+    
+    pub fn get_pose_convention(&self) -> PoseConvention {
+        self.pose_convention
+    }
+    
+    /// 设置 solvePnP 世界坐标原点（只影响 tvec 与工作距离，见 ObjectOrigin）
+    pub fn set_object_origin(&mut self, origin: ObjectOrigin) -> Result<(), String> {
+        let world_points = self.calibrator.generate_world_points_from_list()
+            .map_err(|e| format!("生成世界坐标失败: {}", e))?;
+        build_object_points(&world_points, origin)
+            .map_err(|e| format!("原点设置无效: {}", e))?;
+        self.object_origin = origin;
+        Ok(())
+    }
+    
+    pub fn get_object_origin(&self) -> ObjectOrigin {
+        self.object_origin
+    }
+    
+    /// 设置姿态多帧平均帧数（1为单帧），同时清空已累积的姿态解
+    pub fn set_pose_averaging_frames(&mut self, frames: usize) -> Result<(), String> {
+        if frames == 0 || frames > MAX_POSE_AVERAGING_FRAMES {
+            return Err(format!("姿态平均帧数必须在1-{}范围内: {}", MAX_POSE_AVERAGING_FRAMES, frames));
+        }
+        self.pose_averaging_frames = frames;
+        self.reset_pose_history();
+        Ok(())
+    }
+    
+    pub fn get_pose_averaging_frames(&self) -> usize {
+        self.pose_averaging_frames
+    }
+    
+    /// 设置工作距离合理范围 (mm)
+    pub fn set_standoff_range(&mut self, range: StandoffRange) -> Result<(), String> {
+        range.validate()?;
+        self.standoff_range = range;
+        Ok(())
+    }
+    
+    pub fn get_standoff_range(&self) -> StandoffRange {
+        self.standoff_range
+    }
+    
+    /// 设置双眼虚像距离合格范围 (mm)
+    pub fn set_convergence_range(&mut self, range: ConvergenceRange) -> Result<(), String> {
+        range.validate()?;
+        self.convergence_range = range;
+        Ok(())
+    }
+    
+    pub fn get_convergence_range(&self) -> ConvergenceRange {
+        self.convergence_range
+    }
+    
+    /// 设置姿态解重投影RMS上限 (像素)
+    pub fn set_pose_reprojection_max_px(&mut self, max_px: f64) -> Result<(), String> {
+        if !(max_px > 0.0 && max_px.is_finite()) {
+            return Err(format!("重投影RMS上限必须为正数: {}", max_px));
+        }
+        self.pose_reprojection_max_px = max_px;
+        Ok(())
+    }
+    
+    pub fn get_pose_reprojection_max_px(&self) -> f64 {
+        self.pose_reprojection_max_px
+    }
+    
+    /// 设置 solvePnP 求解方法（失败或解非有限值时自动退回 ITERATIVE）
+    pub fn set_pnp_method(&mut self, method: PnpMethod) {
+        self.pnp_method = method;
+    }
+    
+    pub fn get_pnp_method(&self) -> PnpMethod {
+        self.pnp_method
+    }
+    
+    /// 设置放大倍率不一致判定上限（右/左网格跨度比偏离1的比例，如0.02即2%）
+    pub fn set_magnification_mismatch_max(&mut self, max_deviation: f64) -> Result<(), String> {
+        if !(max_deviation > 0.0 && max_deviation < 1.0) {
+            return Err(format!("放大倍率偏差上限必须在(0, 1)范围内: {}", max_deviation));
+        }
+        self.magnification_mismatch_max = max_deviation;
+        Ok(())
+    }
+    
+    pub fn get_magnification_mismatch_max(&self) -> f64 {
+        self.magnification_mismatch_max
+    }
+    
+    /// 设置合像稳健统计（MAD 离群点剔除）
+    pub fn set_robust_stats(&mut self, config: RobustStatsConfig) -> Result<(), String> {
+        config.validate()?;
+        self.robust_stats = config;
+        Ok(())
+    }
+    
+    pub fn get_robust_stats(&self) -> RobustStatsConfig {
+        self.robust_stats
+    }
+    
+    /// 当前全部检测/判定设置
+    pub fn settings(&self) -> AlignmentSettings {
+        let pattern_size = self.pattern_size();
+        AlignmentSettings {
+            pattern_size: (pattern_size.width, pattern_size.height),
+            center_distance_mm: self.calibrator.get_center_distance(),
+            detector: self.circle_detector.config(),
+            exclusion_regions: self.circle_detector.exclusion_regions().to_vec(),
+            centering_targets: self.centering_targets,
+            thresholds: self.thresholds,
+            blank_frame: self.blank_frame_config.clone(),
+            pose_convention: self.pose_convention,
+            object_origin: self.object_origin,
+            pose_averaging_frames: self.pose_averaging_frames,
+            standoff_range: self.standoff_range,
+            convergence_range: self.convergence_range,
+            error_percentile: self.error_percentile,
+            pose_reprojection_max_px: self.pose_reprojection_max_px,
+            pnp_method: self.pnp_method,
+            magnification_mismatch_max: self.magnification_mismatch_max,
+            robust_stats: self.robust_stats,
+            include_interpolated_points: self.include_interpolated_points,
+            exclude_unrefined_points: self.exclude_unrefined_points,
+        }
+    }
+    
+    /// 应用全部检测/判定设置（标定板规格与圆心间距须与创建时一致）
+    pub fn apply_settings(&mut self, settings: &AlignmentSettings) -> Result<(), String> {
+        let (width, height) = settings.pattern_size;
+        if Size::new(width, height) != self.pattern_size()
+            || settings.center_distance_mm != self.calibrator.get_center_distance() {
+            return Err(format!("标定板规格 {}×{} / 圆心间距 {} mm 与当前检测系统不一致，需按新规格重新创建",
+                               width, height, settings.center_distance_mm));
+        }
+        self.circle_detector.apply_config(&settings.detector)?;
+        self.circle_detector.set_exclusion_regions(settings.exclusion_regions.clone())?;
+        self.set_centering_targets(settings.centering_targets)?;
+        self.set_thresholds(settings.thresholds)?;
+        self.set_blank_frame_config(settings.blank_frame.clone());
+        self.set_pose_convention(settings.pose_convention)?;
+        self.set_object_origin(settings.object_origin)?;
+        self.set_pose_averaging_frames(settings.pose_averaging_frames)?;
+        self.set_standoff_range(settings.standoff_range)?;
+        self.set_convergence_range(settings.convergence_range)?;
+        self.set_error_percentile(settings.error_percentile)?;
+        self.set_pose_reprojection_max_px(settings.pose_reprojection_max_px)?;
+        self.set_pnp_method(settings.pnp_method);
+        self.set_magnification_mismatch_max(settings.magnification_mismatch_max)?;
+        self.set_robust_stats(settings.robust_stats)?;
+        self.set_include_interpolated_points(settings.include_interpolated_points);
+        self.set_exclude_unrefined_points(settings.exclude_unrefined_points);
+        Ok(())
+    }
+    
+    /// 清空左右眼已累积的姿态解（切换检测阶段/被测件时调用，避免混入上一件的姿态）
+    pub fn reset_pose_history(&self) {
+        if let Ok(mut history) = self.pose_history.lock() {
+            history.iter_mut().for_each(|queue| queue.clear());
+        }
+    }
+    
+    /// 检测前检查左右原始帧亮度
+    /// 
+    /// 返回 (左眼亮度, 右眼亮度, 左眼无投影, 右眼无投影)；未启用时不计算亮度
+    pub fn check_projection_signal(
+        &self,
+        left_image: &Mat,
+        right_image: &Mat,
+    ) -> Result<(FrameIntensity, FrameIntensity, bool, bool), opencv::Error> {
+        if !self.blank_frame_config.enabled {
+            return Ok((FrameIntensity::default(), FrameIntensity::default(), false, false));
+        }
+        let left = FrameIntensity::measure(left_image)?;
+        let right = FrameIntensity::measure(right_image)?;
+        Ok((
+            left,
+            right,
+            self.blank_frame_config.is_blank(&left),
+            self.blank_frame_config.is_blank(&right),
+        ))
+    }
+    
+    /// 当前实际生效的判定阈值与检测参数（含运行时覆盖，用于导出配置快照）
+    pub fn runtime_settings(&self) -> serde_json::Value {
+        serde_json::json!({
+            "active_thresholds": {
+                "roll_deg": self.thresholds.roll_deg,
+                "pitch_yaw_deg": self.thresholds.pitch_yaw_deg,
+                "rms_px": self.thresholds.rms_px,
+                "percentile_px": self.thresholds.percentile_px,
+                "max_px": self.thresholds.max_px,
+                "centering_tolerance_px": CENTERING_TOLERANCE_PX,
+                "expected_top_right": [self.centering_targets.expected_top_right.0, self.centering_targets.expected_top_right.1],
+                "expected_bottom_left": [self.centering_targets.expected_bottom_left.0, self.centering_targets.expected_bottom_left.1],
+            },
+            "error_percentile": self.error_percentile,
+            "error_percentile_label": percentile_label(self.error_percentile),
+            "blank_frame": self.blank_frame_config,
+            "pose_convention": self.pose_convention,
+            "object_origin": self.object_origin,
+            "pose_averaging_frames": self.pose_averaging_frames,
+            "standoff_range_mm": self.standoff_range,
+            "convergence_range_mm": self.convergence_range,
+            "pose_reprojection_max_px": self.pose_reprojection_max_px,
+            "pnp_method": self.pnp_method,
+            "robust_stats": self.robust_stats,
+            "include_interpolated_points": self.include_interpolated_points,
+            "exclude_unrefined_points": self.exclude_unrefined_points,
+            "debug_render": self.debug_render,
+            "rectify_maps_loaded": self.left_maps.is_some(),
+            "rectify_maps_regenerated": self.maps_regenerated,
+            "image_size": [self.image_size.width, self.image_size.height],
+            "circle_detector": self.circle_detector.params_snapshot(),
+        })
+    }
+    
     /// 获取 rectifier 的只读访问
     pub fn get_rectifier(&self) -> &Rectifier {
         &self.rectifier
@@ -1359,13 +3982,109 @@ impl AlignmentSystem {
         (&self.right_camera_matrix, &self.right_dist_coeffs)
     }
     
+    /// 标定与合像模块一致性自检
+    /// 
+    /// 1. 世界坐标：标定器 generate_world_points_from_list 与合像 solvePnP 所用世界坐标
+    ///    （扣除原点后）逐点比较，间距/点序不同即报告；
+    /// 2. 排序：由标定器世界坐标投影出固定的圆心（列顺序颠倒，模拟 findCirclesGrid 输出），
+    ///    分别经标定器 reorder_asymmetric_circles 与合像检测器 sort_asymmetric_grid 排序后逐点比较，
+    ///    并各自与世界坐标点序对照。
+    pub fn check_calibration_consistency(&self, calibrator: &mut Calibrator) -> Result<ModuleConsistencyReport, Box<dyn std::error::Error>> {
+        let mut divergences = Vec::new();
+        
+        // 1. 世界坐标（相对点0比较）
+        let calibration_points = calibrator.generate_world_points_from_list()?;
+        let alignment_points = self.generate_simplified_object_points()?;
+        let relative = |points: &Vector<Point3f>| -> Vec<(f64, f64, f64)> {
+            let origin = points.get(0).unwrap_or_default();
+            points.iter().map(|p| ((p.x - origin.x) as f64, (p.y - origin.y) as f64, (p.z - origin.z) as f64)).collect()
+        };
+        let (calibration_relative, alignment_relative) = (relative(&calibration_points), relative(&alignment_points));
+        let world_point_max_diff_mm = calibration_relative.iter().zip(&alignment_relative)
+            .map(|(a, b)| (a.0 - b.0).abs().max((a.1 - b.1).abs()).max((a.2 - b.2).abs()))
+            .fold(0.0, f64::max);
+        let world_point_count = (calibration_points.len(), alignment_points.len());
+        if world_point_count.0 != world_point_count.1 {
+            divergences.push(format!("世界坐标点数不同: 标定 {} / 合像 {}", world_point_count.0, world_point_count.1));
+        }
+        if world_point_max_diff_mm > CONSISTENCY_TOLERANCE {
+            divergences.push(format!("世界坐标不一致: 最大差 {:.4} mm (检查圆心距/点序)", world_point_max_diff_mm));
+        }
+        
+        // 2. 排序：世界坐标按 4 px/mm 投影并旋转2°，序号0在右上角
+        let (scale, angle) = (4.0_f32, 2.0_f32.to_radians());
+        let expected: Vector<Point2f> = calibration_points.iter()
+            .map(|p| Point2f::new(
+                600.0 + scale * (p.x * angle.cos() - p.y * angle.sin()),
+                400.0 + scale * (p.x * angle.sin() + p.y * angle.cos()),
+            ))
+            .collect();
+        let input = swap_adjacent_columns(&expected)?;
+        let calibration_sorted = calibrator.reorder_asymmetric_circles(&input)?;
+        let mut alignment_sorted = input.clone();
+        self.circle_detector.sort_asymmetric_grid(&mut alignment_sorted)?;
+        
+        let differs = |a: Point2f, b: Point2f| ((a.x - b.x) as f64).hypot((a.y - b.y) as f64) > CONSISTENCY_TOLERANCE;
+        let mismatched = |sorted: &Vector<Point2f>, reference: &Vector<Point2f>| -> Vec<usize> {
+            (0..sorted.len().max(reference.len()))
+                .filter(|&i| match (sorted.get(i), reference.get(i)) {
+                    (Ok(a), Ok(b)) => differs(a, b),
+                    _ => true,
+                })
+                .collect()
+        };
+        let ordering_mismatched_indices = mismatched(&calibration_sorted, &alignment_sorted);
+        let calibration_order_errors = mismatched(&calibration_sorted, &expected).len();
+        let alignment_order_errors = mismatched(&alignment_sorted, &expected).len();
+        if !ordering_mismatched_indices.is_empty() {
+            divergences.push(format!("标定与合像排序结果不同: {} 个点 (序号 {:?})",
+                                     ordering_mismatched_indices.len(), ordering_mismatched_indices));
+        }
+        if calibration_order_errors > 0 {
+            divergences.push(format!("标定排序与世界坐标点序不符: {} 个点", calibration_order_errors));
+        }
+        if alignment_order_errors > 0 {
+            divergences.push(format!("合像排序与世界坐标点序不符: {} 个点", alignment_order_errors));
+        }
+        
+        let consistent = divergences.is_empty();
+        if consistent {
+            println!("✅ 标定/合像模块一致性自检通过");
+        } else {
+            for divergence in &divergences {
+                println!("❌ {}", divergence);
+            }
+        }
+        Ok(ModuleConsistencyReport {
+            world_points_match: world_point_count.0 == world_point_count.1 && world_point_max_diff_mm <= CONSISTENCY_TOLERANCE,
+            world_point_count,
+            world_point_max_diff_mm,
+            calibration_grid_order: calibrator.get_grid_order_strategy(),
+            ordering_match: ordering_mismatched_indices.is_empty(),
+            ordering_mismatched_indices,
+            calibration_order_errors,
+            alignment_order_errors,
+            consistent,
+            divergences,
+        })
+    }
+    
+    /// 用本系统的圆阵世界坐标与左右相机内参生成合成图像对 (左, 右)
+    pub fn render_synthetic_pair(&self, params: &SyntheticGridParams) -> Result<(Mat, Mat), Box<dyn std::error::Error>> {
+        let world_points = self.calibrator.generate_world_points_from_list()?;
+        let diameter = self.calibrator.get_diameter() as f64;
+        let left = render_synthetic_grid(&world_points, diameter, &self.left_camera_matrix, self.image_size, params, false)?;
+        let right = render_synthetic_grid(&world_points, diameter, &self.right_camera_matrix, self.image_size, params, true)?;
+        Ok((left, right))
+    }
+    
     /// 【向后兼容】检查左眼姿态（使用内置左相机参数）
     pub fn check_left_eye_pose(
         &self,
         corners_left: &Vector<Point2f>,
     ) -> Result<SingleEyePoseResult, Box<dyn std::error::Error>> {
         println!("🔄 使用向后兼容的左眼姿态检测");
-        self.check_single_eye_pose(corners_left, &self.left_camera_matrix, &self.left_dist_coeffs)
+        self.check_eye_pose_averaged(0, corners_left, &self.left_camera_matrix, &self.left_dist_coeffs)
     }
     
     /// 【向后兼容】检查右眼姿态（使用内置右相机参数）
@@ -1374,6 +4093,22 @@ impl AlignmentSystem {
         corners_right: &Vector<Point2f>,
     ) -> Result<SingleEyePoseResult, Box<dyn std::error::Error>> {
         println!("🔄 使用向后兼容的右眼姿态检测");
+        self.check_eye_pose_averaged(1, corners_right, &self.right_camera_matrix, &self.right_dist_coeffs)
+    }
+    
+    /// 左眼单帧姿态（不进入多帧平均、不更新重新判定记录），供黄金样比对、测量存档等旁路测量
+    pub fn measure_left_eye_pose(
+        &self,
+        corners_left: &Vector<Point2f>,
+    ) -> Result<SingleEyePoseResult, Box<dyn std::error::Error>> {
+        self.check_single_eye_pose(corners_left, &self.left_camera_matrix, &self.left_dist_coeffs)
+    }
+    
+    /// 右眼单帧姿态（不进入多帧平均、不更新重新判定记录），供黄金样比对、测量存档等旁路测量
+    pub fn measure_right_eye_pose(
+        &self,
+        corners_right: &Vector<Point2f>,
+    ) -> Result<SingleEyePoseResult, Box<dyn std::error::Error>> {
         self.check_single_eye_pose(corners_right, &self.right_camera_matrix, &self.right_dist_coeffs)
     }
 }
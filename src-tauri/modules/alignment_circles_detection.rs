@@ -3,14 +3,22 @@
 
 use std::path::Path;
 use std::time::Instant;
-use opencv::{core, imgcodecs, imgproc, prelude::*};
+use opencv::{calib3d, core, imgcodecs, imgproc, prelude::*};
 
-/// 🎨 V3: 圆心细化来源标记（用于debug可视化）
-#[derive(Copy, Clone)]
+/// 🎨 V3: 圆心细化来源标记（用于debug可视化与姿态解算降权）
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum RefineTag { 
     Hi,       // 高置信（DT-only）
     Lo,       // 低置信（径向采样+轻量圆拟合）
-    Fallback  // 回退到原坐标
+    Fallback, // 回退到原坐标
+    Interpolated // 部分网格补全插值（未实际检测到）
+}
+
+impl RefineTag {
+    /// 是否经过亚像素细化（Hi/Lo）；Fallback 仅为连通域质心，Interpolated 未实际检测
+    pub fn is_refined(&self) -> bool {
+        matches!(self, RefineTag::Hi | RefineTag::Lo)
+    }
 }
 
 
@@ -85,6 +93,277 @@ impl Precomputed {
     }
 }
 
+/// 检测前的图像预处理（作用于校正后的灰度图），用于低对比度投影
+#[derive(Debug, Clone, Copy, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "mode")]
+pub enum DetectionPreprocessing {
+    /// 不处理（默认）
+    #[default]
+    None,
+    /// 限制对比度自适应直方图均衡：clip_limit 越大增强越强，tile_grid 为每边分块数
+    Clahe { clip_limit: f64, tile_grid: i32 },
+    /// 伽马校正：out = 255·(in/255)^gamma，gamma < 1 提亮暗部
+    Gamma { gamma: f64 },
+}
+
+impl DetectionPreprocessing {
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            Self::None => Ok(()),
+            Self::Clahe { clip_limit, tile_grid } => {
+                if !(clip_limit > 0.0) || !(1..=64).contains(&tile_grid) {
+                    return Err(format!("CLAHE参数无效: clip_limit={}, tile_grid={} (需 clip_limit>0, tile_grid 1-64)", clip_limit, tile_grid));
+                }
+                Ok(())
+            }
+            Self::Gamma { gamma } => {
+                if !(gamma > 0.0 && gamma.is_finite()) {
+                    return Err(format!("伽马值必须为正数: {}", gamma));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// 执行预处理，None 时返回 Ok(None)（直接使用原图）
+    pub fn apply(&self, image: &core::Mat) -> Result<Option<core::Mat>, opencv::Error> {
+        let mut output = core::Mat::default();
+        match *self {
+            Self::None => return Ok(None),
+            Self::Clahe { clip_limit, tile_grid } => {
+                let mut clahe = imgproc::create_clahe(clip_limit, core::Size::new(tile_grid, tile_grid))?;
+                clahe.apply(image, &mut output)?;
+            }
+            Self::Gamma { gamma } => {
+                let table: Vec<u8> = (0..256)
+                    .map(|v| (255.0 * (v as f64 / 255.0).powf(gamma)).round().clamp(0.0, 255.0) as u8)
+                    .collect();
+                let lut = core::Mat::from_slice(&table)?;
+                core::lut(image, &lut, &mut output)?;
+            }
+        }
+        Ok(Some(output))
+    }
+}
+
+/// 圆点极性：投影图案为暗背景亮圆点，打印标定板为白底黑圆点
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DotPolarity {
+    /// 暗背景亮圆点（投影，默认）
+    #[default]
+    BrightOnDark,
+    /// 亮背景暗圆点（打印标定板）：连通域分析前反相
+    DarkOnBright,
+}
+
+impl DotPolarity {
+    /// 转换为亮圆点图像，BrightOnDark 时返回 Ok(None)（直接使用原图）
+    pub fn normalize(&self, image: &core::Mat) -> Result<Option<core::Mat>, opencv::Error> {
+        match self {
+            Self::BrightOnDark => Ok(None),
+            Self::DarkOnBright => {
+                let mut inverted = core::Mat::default();
+                core::bitwise_not(image, &mut inverted, &core::no_array())?;
+                Ok(Some(inverted))
+            }
+        }
+    }
+}
+
+/// 局部自适应阈值参数（与全局阈值同时满足才为前景）
+/// 
+/// 光面模组镜面反光使相邻圆点间隙也超过全局阈值而黏连；间隙低于局部均值，自适应阈值可将其断开
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AdaptiveThresholdParams {
+    pub block_size: i32,  // 局部均值窗口 (奇数像素，≥3)，宜接近圆点直径
+    pub offset: f64,      // 前景须高于局部均值的灰度
+}
+
+impl AdaptiveThresholdParams {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.block_size < 3 || self.block_size % 2 == 0 {
+            return Err(format!("自适应阈值窗口必须为≥3的奇数: {}", self.block_size));
+        }
+        if !(self.offset.is_finite() && self.offset >= 0.0) {
+            return Err(format!("自适应阈值偏移不能为负: {}", self.offset));
+        }
+        Ok(())
+    }
+}
+
+/// 背景平坦化均值滤波核尺寸须为≥3的奇数
+fn validate_flatten_kernel(kernel_size: i32) -> Result<(), String> {
+    if kernel_size < 3 || kernel_size % 2 == 0 {
+        return Err(format!("背景平坦化核尺寸必须为≥3的奇数: {}", kernel_size));
+    }
+    Ok(())
+}
+
+/// 可热更新的检测参数（运行中调整，下一帧生效）
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DetectorConfig {
+    pub min_area: f64,                  // 第一轮面积窗口下限 (像素²)
+    pub max_area: f64,                  // 第一轮面积窗口上限 (像素²)
+    pub connectivity: i32,              // 连通性 4 / 8，默认4连通减少黏连
+    pub fixed_threshold: Option<f64>,   // 固定高阈值（背景平坦化后灰度），None 为 Triangle+25 自动
+    pub merged_area_ratio: f64,         // 黏连判定面积比例
+    pub split_merged_blobs: bool,       // 是否拆分黏连连通域
+    pub partial_grid_completion: bool,  // 部分网格补全
+    pub max_interpolated_points: usize, // 最多插值点数
+    pub preprocessing: DetectionPreprocessing,
+    pub polarity: DotPolarity,          // 圆点极性，默认暗背景亮圆点
+    pub flatten_kernel_size: Option<i32>, // 背景平坦化核尺寸 (奇数像素)，None 按期望直径自动 (≈377)
+    pub adaptive_threshold: Option<AdaptiveThresholdParams>, // 局部自适应阈值，None 仅用全局阈值
+}
+
+impl Default for DetectorConfig {
+    fn default() -> Self {
+        Self {
+            min_area: 1600.0,
+            max_area: 14000.0,
+            connectivity: 4,
+            fixed_threshold: None,
+            merged_area_ratio: 1.7,
+            split_merged_blobs: true,
+            partial_grid_completion: false,
+            max_interpolated_points: 2,
+            preprocessing: DetectionPreprocessing::None,
+            polarity: DotPolarity::BrightOnDark,
+            flatten_kernel_size: None,
+            adaptive_threshold: None,
+        }
+    }
+}
+
+impl DetectorConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.min_area > 0.0 && self.min_area < self.max_area) {
+            return Err(format!("面积窗口无效: [{}, {}]", self.min_area, self.max_area));
+        }
+        validate_connectivity(self.connectivity)?;
+        if let Some(threshold) = self.fixed_threshold {
+            if !(0.0..=255.0).contains(&threshold) {
+                return Err(format!("固定阈值必须在0-255范围内: {}", threshold));
+            }
+        }
+        if !(self.merged_area_ratio > 1.0) {
+            return Err(format!("黏连面积比例必须大于1.0，当前: {}", self.merged_area_ratio));
+        }
+        if self.max_interpolated_points > 4 {
+            return Err(format!("最多插值点数过大: {} (上限4)", self.max_interpolated_points));
+        }
+        if let Some(kernel_size) = self.flatten_kernel_size {
+            validate_flatten_kernel(kernel_size)?;
+        }
+        if let Some(adaptive) = &self.adaptive_threshold {
+            adaptive.validate()?;
+        }
+        self.preprocessing.validate()
+    }
+}
+
+/// 检测诊断：各筛选阶段的计数与被拒候选的外接矩形（现场排查过暗/过曝/遮挡）
+/// 
+/// 阶段计数取最后一轮阈值（启用低阈值兜底时为低阈值轮）；final_count 为两轮合并、补全后的最终圆点数
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct DetectionDiagnostics {
+    pub expected: usize,            // 期望圆点数
+    pub threshold: f64,             // 最后一轮二值化阈值
+    pub low_threshold_used: bool,   // 是否启用了低阈值兜底
+    pub raw_components: usize,      // 阈值后连通域总数（面积过滤前）
+    pub area_passed: usize,         // 通过面积窗口
+    pub shape_passed: usize,        // 通过长宽比/填充比筛选
+    pub merged_blobs: usize,        // 面积窗口内疑似黏连的连通域
+    pub split_centers: usize,       // 黏连拆分/ROI分裂补回的圆心数
+    pub excluded: usize,            // 落入排除区域丢弃
+    pub interpolated: usize,        // 部分网格补全插值点数
+    pub final_count: usize,         // 最终输出圆点数
+    #[serde(serialize_with = "serialize_rects")]
+    pub rejected: Vec<core::Rect>,  // 被面积/形状筛选拒绝的候选（面积 ≥ 下限/4，忽略细小噪点），序列化为 [x, y, 宽, 高]
+}
+
+fn serialize_rects<S: serde::Serializer>(rects: &[core::Rect], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(rects.iter().map(|rect| [rect.x, rect.y, rect.width, rect.height]))
+}
+
+impl DetectionDiagnostics {
+    /// 简要判断失败原因（供操作员参考）
+    pub fn summary(&self) -> String {
+        if self.final_count == self.expected {
+            format!("检测正常: {} 个圆点", self.final_count)
+        } else if self.raw_components < self.expected {
+            format!("连通域仅 {} 个 (期望{}) — 图像过暗或标定板被遮挡", self.raw_components, self.expected)
+        } else if self.area_passed < self.expected && self.merged_blobs + self.split_centers > 0 {
+            format!("面积过滤后 {} 个，存在黏连 — 图像过亮", self.area_passed)
+        } else {
+            format!("期望{}个圆点，实际检测到{}个 (面积过滤 {}，形状筛选 {}，被拒 {} 个候选)",
+                    self.expected, self.final_count, self.area_passed, self.shape_passed, self.rejected.len())
+        }
+    }
+}
+
+/// 连通域分析只支持 4 / 8 连通
+fn validate_connectivity(connectivity: i32) -> Result<(), String> {
+    if connectivity != 4 && connectivity != 8 {
+        return Err(format!("连通性必须为4或8，当前: {}", connectivity));
+    }
+    Ok(())
+}
+
+/// 检测排除区域（校正后图像坐标，左右图共用）
+/// 
+/// 用于屏蔽工装上固定的反光点（如安装螺钉），质心落入区域的连通域在网格匹配前丢弃
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "shape")]
+pub enum ExclusionRegion {
+    /// 轴对齐矩形 (左上角 + 宽高，像素)
+    Rect { x: f32, y: f32, width: f32, height: f32 },
+    /// 多边形顶点 [[x, y], ...]，至少3个点
+    Polygon { points: Vec<[f32; 2]> },
+}
+
+impl ExclusionRegion {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Rect { x, y, width, height } => {
+                if ![*x, *y, *width, *height].iter().all(|v| v.is_finite()) || !(*width > 0.0 && *height > 0.0) {
+                    return Err(format!("排除矩形无效: x={}, y={}, w={}, h={}", x, y, width, height));
+                }
+                Ok(())
+            }
+            Self::Polygon { points } => {
+                if points.len() < 3 || !points.iter().flatten().all(|v| v.is_finite()) {
+                    return Err(format!("排除多边形至少需要3个有效顶点，当前: {}", points.len()));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// 点是否落在区域内（多边形使用射线法，边界上的点不保证归属）
+    pub fn contains(&self, p: core::Point2f) -> bool {
+        match self {
+            Self::Rect { x, y, width, height } => {
+                p.x >= *x && p.x <= x + width && p.y >= *y && p.y <= y + height
+            }
+            Self::Polygon { points } => {
+                let mut inside = false;
+                let mut j = points.len() - 1;
+                for i in 0..points.len() {
+                    let ([xi, yi], [xj, yj]) = (points[i], points[j]);
+                    if (yi > p.y) != (yj > p.y) && p.x < (xj - xi) * (p.y - yi) / (yj - yi) + xi {
+                        inside = !inside;
+                    }
+                    j = i;
+                }
+                inside
+            }
+        }
+    }
+}
+
 /// 连通域圆点检测器
 pub struct ConnectedComponentsDetector {
     // 阈值参数
@@ -99,9 +378,12 @@ pub struct ConnectedComponentsDetector {
     // 图像信息
     image_size: core::Size,
     expected_diameter_range: (f32, f32), // (67, 90)
+    pattern_size: core::Size,            // 标定板规格 (每列点数, 列数)，默认4×10
     
     // 是否已初始化Triangle阈值
     triangle_initialized: bool,
+    // 固定高阈值（代替Triangle自动阈值）
+    fixed_threshold: Option<f64>,
     
     // 🆕 新增优化参数
     connectivity: i32,           // 连通性：4连通
@@ -114,6 +396,36 @@ pub struct ConnectedComponentsDetector {
     // 🎨 V3: Debug可视化相关字段
     last_refine_tags: Option<Vec<RefineTag>>,
     last_original_centers: Option<core::Vector<core::Point2f>>,
+    
+    // 🆕 部分网格补全（遮挡/灰尘导致缺1-2个点时插值补齐，默认关闭）
+    partial_grid_completion: bool,
+    max_interpolated_points: usize,
+    last_interpolated_points: Vec<core::Point2f>,
+    
+    // 🆕 黏连圆点检查：强反光下相邻两点合并成一个大连通域，仍可能通过面积窗口
+    merged_area_ratio: f64,      // 面积超过 本帧中位面积×该比例 视为黏连（两点≈2.0）
+    split_merged_blobs: bool,    // true: 尝试距离变换拆分；false: 直接丢弃并给出诊断
+    last_merged_blobs: usize,    // 最近一次检测中黏连的连通域数
+    last_unresolved_merged_blobs: usize, // 其中未能拆分（被丢弃）的数量
+    
+    // 🆕 最近一次检测最后一轮的二值图及其阈值（检测失败时查看圆点是否在二值化后保留）
+    last_binary_mask: Option<(f64, core::Mat)>,
+    
+    // 🆕 最近一次检测的分阶段诊断
+    last_diagnostics: DetectionDiagnostics,
+    
+    // 🆕 检测前预处理（CLAHE/伽马，默认不处理）
+    preprocessing: DetectionPreprocessing,
+    
+    // 🆕 圆点极性（打印标定板为亮背景暗圆点，检测前反相）
+    polarity: DotPolarity,
+    
+    // 🆕 排除区域：固定反光点等已知误检来源（默认无）
+    exclusion_regions: Vec<ExclusionRegion>,
+    
+    // 🆕 背景平坦化核尺寸（None 按期望直径自动）与局部自适应阈值（默认关闭）
+    flatten_kernel_size: Option<i32>,
+    adaptive_threshold: Option<AdaptiveThresholdParams>,
 }
 
 impl ConnectedComponentsDetector {
@@ -132,7 +444,9 @@ impl ConnectedComponentsDetector {
             max_area: 14000.0,
             image_size: core::Size::new(2448, 2048),
             expected_diameter_range: (67.0, 90.0),
+            pattern_size: core::Size::new(4, 10),
             triangle_initialized: false,
+            fixed_threshold: None,
             // 🆕 新增优化参数
             connectivity: 4,                                    // 4连通减少黏连
             roi_split_threshold: 1.6 * max_expected_area,      // ≈ 10179
@@ -144,15 +458,63 @@ impl ConnectedComponentsDetector {
             // 🎨 V3: Debug可视化字段初始化
             last_refine_tags: None,
             last_original_centers: None,
+            
+            partial_grid_completion: false,
+            max_interpolated_points: 2,
+            last_interpolated_points: Vec::new(),
+            
+            merged_area_ratio: 1.7,
+            split_merged_blobs: true,
+            last_merged_blobs: 0,
+            last_unresolved_merged_blobs: 0,
+            
+            last_binary_mask: None,
+            
+            last_diagnostics: DetectionDiagnostics::default(),
+            
+            preprocessing: DetectionPreprocessing::None,
+            
+            polarity: DotPolarity::BrightOnDark,
+            
+            exclusion_regions: Vec::new(),
+            
+            flatten_kernel_size: None,
+            adaptive_threshold: None,
         }
     }
     
+    /// 按完整检测参数创建检测器（工装调参：平坦化核、自适应阈值等），未包含的参数同 new()
+    pub fn with_config(config: &DetectorConfig) -> Result<Self, String> {
+        let mut detector = Self::new();
+        detector.apply_config(config)?;
+        Ok(detector)
+    }
+    
+    /// 按指定面积窗口 (像素²) 与连通性 (4/8) 创建检测器，其余参数同 new()
+    /// 
+    /// 放大倍率或投影亮度不同的工位圆点面积不同，默认窗口 [1600, 14000] 不一定适用
+    pub fn with_params(min_area: f64, max_area: f64, connectivity: i32) -> Result<Self, String> {
+        let mut detector = Self::new();
+        detector.set_area_range(min_area, max_area)?;
+        detector.set_connectivity(connectivity)?;
+        Ok(detector)
+    }
+    
     /// 初始化Triangle阈值 (仅在首次调用时执行)
     fn initialize_triangle_threshold(&mut self, image: &core::Mat) -> Result<(), opencv::Error> {
         if self.triangle_initialized {
             return Ok(());
         }
         
+        if let Some(threshold) = self.fixed_threshold {
+            self.triangle_threshold = threshold - 25.0;
+            self.high_threshold = threshold;
+            self.low_threshold = (threshold - 60.0).max(10.0);
+            println!("🔧 使用固定阈值: 高 {:.1}, 低 {:.1}", self.high_threshold, self.low_threshold);
+            self.triangle_initialized = true;
+            return Ok(());
+        }
+        
         println!("🔧 初始化Triangle阈值...");
         let mut temp = core::Mat::default();
         
@@ -181,17 +543,31 @@ impl ConnectedComponentsDetector {
     pub fn detect_circles(&mut self, image: &core::Mat) -> Result<core::Vector<core::Point2f>, opencv::Error> {
         let detection_start = Instant::now();
         
+        // 暗圆点先反相为亮圆点，之后的阈值/细化按亮圆点处理
+        let normalized = self.polarity.normalize(image)?;
+        let image = normalized.as_ref().unwrap_or(image);
+        
+        // 可选预处理（低对比度投影），之后的阈值/细化均基于预处理后的图像
+        let preprocessed = self.preprocessing.apply(image)?;
+        let image = preprocessed.as_ref().unwrap_or(image);
+        
         // 初始化阈值 (仅首次)
         self.initialize_triangle_threshold(image)?;
+        self.last_merged_blobs = 0;
+        self.last_unresolved_merged_blobs = 0;
+        self.last_binary_mask = None;
+        self.last_diagnostics = DetectionDiagnostics::default();
         
         // 主路径：高阈值检测
         let mut centers = self.detect_with_threshold(image, self.high_threshold)?;
         println!("🔍 高阈值检测到 {} 个圆点", centers.len());
         
         // 兜底路径：如果检测数量不足，使用低阈值补充
-        if centers.len() < 40 {
+        let expected = self.expected_points();
+        if centers.len() < expected {
             println!("⚠️ 检测数量不足，启用低阈值兜底检测...");
             let low_centers = self.detect_with_threshold(image, self.low_threshold)?;
+            self.last_diagnostics.low_threshold_used = true;
             println!("🔍 低阈值检测到 {} 个圆点", low_centers.len());
             
             // 合并去重 (简单距离去重)
@@ -199,12 +575,37 @@ impl ConnectedComponentsDetector {
             println!("🔗 合并去重后: {} 个圆点", centers.len());
         }
         
+        if let Some(diagnostic) = self.last_merge_diagnostic() {
+            println!("⚠️ {}", diagnostic);
+        }
+        
+        // 🆕 部分网格补全（可选）：缺失点数不超过上限且布局一致时插值补齐
+        self.last_interpolated_points.clear();
+        let mut interpolated_mask: Option<Vec<bool>> = None;
+        if self.partial_grid_completion && centers.len() < expected {
+            if let Some((completed, mask)) = self.complete_partial_grid(&centers)? {
+                println!("🧩 部分网格补全: 插值 {} 个缺失点", mask.iter().filter(|m| **m).count());
+                centers = completed;
+                interpolated_mask = Some(mask);
+            }
+        }
+        
         // 🆕 V3: 边界约束自适应圆心细化 (解决向阵列中心偏移问题，可回滚到背景平坦化版本)
-        let (refine_tags, original_centers) = if centers.len() == 40 {
+        let (refine_tags, original_centers) = if centers.len() == expected {
             println!("🔧 启动边界约束自适应圆心细化...");
             let refine_start = Instant::now();
             let original_centers = centers.clone(); // 🎨 保存原始坐标
-            let (refined_centers, tags) = self.refine_centers_adaptive_v3(image, centers)?;
+            let (mut refined_centers, mut tags) = self.refine_centers_adaptive_v3(image, centers)?;
+            
+            // 插值点不参与细化（被遮挡区域无可靠边缘），恢复为插值坐标
+            if let Some(mask) = &interpolated_mask {
+                for (i, _) in mask.iter().enumerate().filter(|(_, m)| **m) {
+                    let p = original_centers.get(i)?;
+                    refined_centers.set(i, p)?;
+                    tags[i] = RefineTag::Interpolated;
+                    self.last_interpolated_points.push(p);
+                }
+            }
             centers = refined_centers;
             let refine_time = refine_start.elapsed();
             println!("   ✅ 边界约束细化完成，耗时: {:.1} ms", refine_time.as_millis());
@@ -220,20 +621,53 @@ impl ConnectedComponentsDetector {
         let detection_time = detection_start.elapsed();
         println!("⏱️  连通域检测总耗时: {:.1} ms", detection_time.as_millis());
         
+        self.last_diagnostics.expected = expected;
+        self.last_diagnostics.interpolated = self.last_interpolated_points.len();
+        self.last_diagnostics.final_count = centers.len();
+        
         Ok(centers)
     }
     
+    /// 圆点检测并返回分阶段诊断（原始连通域数、各筛选阶段保留数、被拒候选外接矩形）
+    pub fn detect_circles_with_diagnostics(&mut self, image: &core::Mat) -> Result<(core::Vector<core::Point2f>, DetectionDiagnostics), opencv::Error> {
+        let centers = self.detect_circles(image)?;
+        let diagnostics = self.last_diagnostics.clone();
+        if diagnostics.final_count != diagnostics.expected {
+            println!("🩺 {}", diagnostics.summary());
+        }
+        Ok((centers, diagnostics))
+    }
+    
+    /// 圆点检测并返回与圆心一一对应的细化来源标记
+    /// 
+    /// 圆点数不等于期望数时不做细化，全部标记为 Fallback（连通域质心）
+    pub fn detect_circles_tagged(&mut self, image: &core::Mat) -> Result<(core::Vector<core::Point2f>, Vec<RefineTag>), opencv::Error> {
+        let centers = self.detect_circles(image)?;
+        let tags = match &self.last_refine_tags {
+            Some(tags) if tags.len() == centers.len() => tags.clone(),
+            _ => vec![RefineTag::Fallback; centers.len()],
+        };
+        Ok((centers, tags))
+    }
+    
+    /// 最近一次 detect_circles 的细化来源标记（未细化时为 None）
+    pub fn last_refine_tags(&self) -> Option<&[RefineTag]> {
+        self.last_refine_tags.as_deref()
+    }
+    
+    /// 最近一次 detect_circles 的分阶段诊断
+    pub fn last_diagnostics(&self) -> &DetectionDiagnostics {
+        &self.last_diagnostics
+    }
+    
     /// 使用指定阈值进行连通域检测 - 新增背景平坦化预处理
-    fn detect_with_threshold(&self, image: &core::Mat, threshold: f64) -> Result<core::Vector<core::Point2f>, opencv::Error> {
+    fn detect_with_threshold(&mut self, image: &core::Mat, threshold: f64) -> Result<core::Vector<core::Point2f>, opencv::Error> {
         println!("   🔍 阈值检测: {:.1}", threshold);
         
         // 🆕 背景平坦化预处理 (极轻量，<2ms)
-        let d_nom = (self.expected_diameter_range.0 + self.expected_diameter_range.1) / 2.0; // ≈ 78.5
-        let sigma = d_nom * 0.8; // ≈ 62.8
-        
         // 高斯模糊提取背景 - 使用blur简化实现
         let mut bg = core::Mat::default();
-        let kernel_size = ((sigma * 3.0) as i32 * 2 + 1).max(3); // 3σ规则
+        let kernel_size = self.flatten_kernel_size();
         let ksize = core::Size::new(kernel_size, kernel_size);
         imgproc::blur(image, &mut bg, ksize, core::Point::new(-1, -1), core::BORDER_DEFAULT)?;
         
@@ -246,12 +680,22 @@ impl ConnectedComponentsDetector {
         imgproc::threshold(&flat, &mut flat_truncated, 255.0, 255.0, imgproc::THRESH_TRUNC)?;
         let flat = flat_truncated; // 重新绑定
         
-        println!("     🔧 背景平坦化完成 (sigma={:.1})", sigma);
+        println!("     🔧 背景平坦化完成 (核尺寸={})", kernel_size);
         
         // 二值化
         let mut binary = core::Mat::default();
         imgproc::threshold(&flat, &mut binary, threshold, 255.0, imgproc::THRESH_BINARY)?;
         
+        // 🆕 局部自适应阈值：须同时高于局部均值+偏移，断开反光黏连
+        if let Some(adaptive) = self.adaptive_threshold {
+            let mut local = core::Mat::default();
+            imgproc::adaptive_threshold(&flat, &mut local, 255.0, imgproc::ADAPTIVE_THRESH_MEAN_C,
+                                        imgproc::THRESH_BINARY, adaptive.block_size, -adaptive.offset)?;
+            let mut combined = core::Mat::default();
+            core::bitwise_and(&binary, &local, &mut combined, &core::no_array())?;
+            binary = combined;
+        }
+        
         // 连通域分析
         let mut labels = core::Mat::default();
         let mut stats = core::Mat::default();
@@ -282,21 +726,43 @@ impl ConnectedComponentsDetector {
         println!("   📊 最大5个连通域面积: {:?}", 
                 &all_areas[..std::cmp::min(5, all_areas.len())]);
         
+        // 🆕 面积窗口内连通域的中位面积，作为本帧单个圆点面积的参考（用于黏连判定）
+        let mut window_areas: Vec<f64> = all_areas.iter().copied()
+            .filter(|a| *a >= self.min_area && *a <= self.max_area)
+            .collect();
+        window_areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let merged_area_limit = if window_areas.len() >= 5 {
+            Some(window_areas[window_areas.len() / 2] * self.merged_area_ratio)
+        } else {
+            None // 样本太少，中位数不可靠
+        };
+        
         // 面积过滤和形状筛选
         let mut centers = core::Vector::<core::Point2f>::new();
         let mut area_filtered_count = 0;
         let mut shape_filtered_count = 0;
         let mut roi_split_candidates = Vec::new();
+        let mut merged_candidates = Vec::new();
+        let mut rejected = Vec::new();
         
         for i in 1..num_labels { // 跳过背景(标签0)
             let area = *stats.at_2d::<i32>(i, imgproc::CC_STAT_AREA)?;
             let width = *stats.at_2d::<i32>(i, imgproc::CC_STAT_WIDTH)?;
             let height = *stats.at_2d::<i32>(i, imgproc::CC_STAT_HEIGHT)?;
+            let bounding_box = || -> Result<core::Rect, opencv::Error> {
+                Ok(core::Rect::new(*stats.at_2d::<i32>(i, imgproc::CC_STAT_LEFT)?, *stats.at_2d::<i32>(i, imgproc::CC_STAT_TOP)?, width, height))
+            };
             
             // 🔧 第一轮：宽松面积过滤
             if area as f64 >= self.min_area && area as f64 <= self.max_area {
                 area_filtered_count += 1;
                 
+                // 🆕 黏连检查：约两个圆点面积的连通域不参与形状筛选，单独处理
+                if merged_area_limit.map_or(false, |limit| area as f64 > limit) {
+                    merged_candidates.push(i);
+                    continue;
+                }
+                
                 // 🔧 形状筛选：长宽比和填充比
                 let aspect_ratio = width as f64 / height as f64;
                 let fill_ratio = area as f64 / (width as f64 * height as f64);
@@ -314,14 +780,45 @@ impl ConnectedComponentsDetector {
                 } else {
                     println!("   ⚠️ 形状筛选丢弃: 面积={}, 长宽比={:.2}, 填充比={:.2}", 
                             area, aspect_ratio, fill_ratio);
+                    rejected.push(bounding_box()?);
                 }
             } else if area as f64 > self.roi_split_threshold {
                 // 🔧 ROI分裂候选：面积过大的连通域
                 roi_split_candidates.push((i, area));
+            } else if area as f64 >= self.min_area / 4.0 {
+                rejected.push(bounding_box()?);
             }
         }
+        let mut split_count = 0;
         
         println!("   📊 形状筛选: {} → {} 个", area_filtered_count, shape_filtered_count);
+        if !merged_candidates.is_empty() {
+            println!("   ⚠️ 疑似黏连圆点: {} 个连通域 (面积 > {:.0})",
+                    merged_candidates.len(), merged_area_limit.unwrap_or(0.0));
+            let mut unresolved = 0;
+            for &label_id in &merged_candidates {
+                let split = if self.split_merged_blobs {
+                    self.split_merged_component(&labels, &stats, label_id)?
+                } else {
+                    Vec::new()
+                };
+                
+                if split.len() >= 2 {
+                    println!("     ✂️ 黏连连通域 #{} 拆分为 {} 个圆心", label_id, split.len());
+                    split_count += split.len();
+                    for center in split {
+                        centers.push(center);
+                    }
+                } else {
+                    // 未拆分的黏连连通域直接丢弃，避免错误圆心污染网格
+                    unresolved += 1;
+                }
+            }
+            
+            // 高/低阈值两轮会看到同一处黏连，取较大值而非累加
+            self.last_merged_blobs = self.last_merged_blobs.max(merged_candidates.len());
+            self.last_unresolved_merged_blobs = self.last_unresolved_merged_blobs.max(unresolved);
+        }
         if !roi_split_candidates.is_empty() {
             println!("   📊 ROI分裂候选: {} 个大连通域 (面积 > {:.0})", 
                     roi_split_candidates.len(), self.roi_split_threshold);
@@ -336,12 +833,25 @@ impl ConnectedComponentsDetector {
                         roi_split_candidates.len(), split_centers.len());
                 
                 // 将分裂得到的圆心添加到结果中
+                split_count += split_centers.len();
                 for center in split_centers {
                     centers.push(center);
                 }
             }
         }
         
+        // 🆕 排除区域：丢弃质心落入已知反光区的连通域
+        let before_exclusion = centers.len();
+        if !self.exclusion_regions.is_empty() {
+            let before = centers.len();
+            centers = centers.iter()
+                .filter(|c| !self.exclusion_regions.iter().any(|region| region.contains(*c)))
+                .collect();
+            if centers.len() < before {
+                println!("   🚫 排除区域丢弃: {} 个连通域", before - centers.len());
+            }
+        }
+        
         // 🔍 计数2: 面积过滤后的个数
         println!("   📊 [计数2] 面积过滤后: {} 个 ({:.0}-{:.0} px²)", 
                 area_filtered_count, self.min_area, self.max_area);
@@ -370,6 +880,20 @@ impl ConnectedComponentsDetector {
             println!("   ⚠️ 诊断: 检测结果偏少，需要综合调优");
         }
         
+        // 低阈值兜底轮会覆盖高阈值轮，保留的是最终决定检测结果的二值图
+        self.last_binary_mask = Some((threshold, binary));
+        self.last_diagnostics = DetectionDiagnostics {
+            threshold,
+            raw_components: total_components.max(0) as usize,
+            area_passed: area_filtered_count,
+            shape_passed: shape_filtered_count,
+            merged_blobs: merged_candidates.len(),
+            split_centers: split_count,
+            excluded: before_exclusion - centers.len(),
+            rejected,
+            ..Default::default()
+        };
+        
         Ok(centers)
     }
     
@@ -456,6 +980,62 @@ impl ConnectedComponentsDetector {
         Ok(split_centers)
     }
     
+    /// 拆分两三个圆点黏连成的连通域：距离变换后只保留靠近峰值的内核，
+    /// 黏连处的窄桥距离值低，被阈值切断，各内核质心即圆心近似
+    fn split_merged_component(
+        &self,
+        labels: &core::Mat,
+        stats: &core::Mat,
+        label_id: i32,
+    ) -> Result<Vec<core::Point2f>, opencv::Error> {
+        let x = *stats.at_2d::<i32>(label_id, imgproc::CC_STAT_LEFT)?;
+        let y = *stats.at_2d::<i32>(label_id, imgproc::CC_STAT_TOP)?;
+        let w = *stats.at_2d::<i32>(label_id, imgproc::CC_STAT_WIDTH)?;
+        let h = *stats.at_2d::<i32>(label_id, imgproc::CC_STAT_HEIGHT)?;
+        
+        // 外扩2px，保证ROI边缘为背景，距离变换不受图像边界影响
+        let pad = 2;
+        let x0 = (x - pad).max(0);
+        let y0 = (y - pad).max(0);
+        let x1 = (x + w + pad).min(labels.cols());
+        let y1 = (y + h + pad).min(labels.rows());
+        let roi_labels = core::Mat::roi(labels, core::Rect::new(x0, y0, x1 - x0, y1 - y0))?;
+        
+        let mut roi_mask = core::Mat::default();
+        core::compare(&roi_labels, &core::Scalar::all(label_id as f64), &mut roi_mask, core::CMP_EQ)?;
+        
+        let mut dist = core::Mat::default();
+        imgproc::distance_transform(&roi_mask, &mut dist, imgproc::DIST_L2, 3, core::CV_32F)?;
+        let mut max_val = 0.0;
+        core::min_max_loc(&dist, None, Some(&mut max_val), None, None, &core::Mat::default())?;
+        if max_val < 5.0 {
+            return Ok(Vec::new());
+        }
+        
+        let mut cores = core::Mat::default();
+        imgproc::threshold(&dist, &mut cores, 0.7 * max_val, 255.0, imgproc::THRESH_BINARY)?;
+        let mut cores_u8 = core::Mat::default();
+        cores.convert_to(&mut cores_u8, core::CV_8U, 1.0, 0.0)?;
+        
+        let mut core_labels = core::Mat::default();
+        let mut core_stats = core::Mat::default();
+        let mut core_centroids = core::Mat::default();
+        let num_cores = imgproc::connected_components_with_stats(
+            &cores_u8, &mut core_labels, &mut core_stats, &mut core_centroids, 8, core::CV_32S
+        )?;
+        
+        let mut centers = Vec::new();
+        for i in 1..num_cores {
+            if *core_stats.at_2d::<i32>(i, imgproc::CC_STAT_AREA)? < 5 {
+                continue; // 噪声峰
+            }
+            let cx = *core_centroids.at_2d::<f64>(i, 0)? as f32 + x0 as f32;
+            let cy = *core_centroids.at_2d::<f64>(i, 1)? as f32 + y0 as f32;
+            centers.push(core::Point2f::new(cx, cy));
+        }
+        Ok(centers)
+    }
+    
     /// 距离变换 + 局部极大值分裂
     fn distance_transform_split(
         &self,
@@ -1147,6 +1727,7 @@ impl ConnectedComponentsDetector {
             RefineTag::Hi => (core::Scalar::new(50.0, 220.0, 50.0, 0.0), "H"),      // 绿色 - 高置信
             RefineTag::Lo => (core::Scalar::new(40.0, 180.0, 255.0, 0.0), "L"),     // 橙色 - 低置信
             RefineTag::Fallback => (core::Scalar::new(30.0, 30.0, 255.0, 0.0), "F"), // 红色 - 回退
+            RefineTag::Interpolated => (core::Scalar::new(255.0, 0.0, 255.0, 0.0), "I"), // 品红 - 插值
         };
         
         let scaled_pt = core::Point::new(
@@ -1176,150 +1757,421 @@ impl ConnectedComponentsDetector {
         Ok(())
     }
     
-    /// Asymmetric Grid排序 - 基于PCA+投影+量化的稳定排序算法
-    /// 参考calibration_circles.rs中generate_world_points_from_list的坐标模式
+    /// Asymmetric Grid排序 - 统一使用 [`order_asymmetric_grid`] 的 PCA 投影策略
+    /// （与标定 Calibrator 的点序一致，见模块末尾的排序策略说明）
     pub fn sort_asymmetric_grid(&self, centers: &mut core::Vector<core::Point2f>) -> Result<(), opencv::Error> {
-        if centers.len() != 40 {
-            println!("⚠️ 圆点数量不是40个，跳过排序 (当前: {}个)", centers.len());
+        if centers.len() != self.expected_points() {
+            println!("⚠️ 圆点数量不是{}个，跳过排序 (当前: {}个)", self.expected_points(), centers.len());
             return Ok(());
         }
 
         println!("🔧 开始PCA+投影+量化 asymmetric grid排序...");
-
-        // 使用新的排序算法
-        let sorted_centers = self.sort_asymmetric_grid_new(centers)?;
-        *centers = sorted_centers;
-        
+        *centers = order_asymmetric_grid(centers, GridOrderStrategy::PcaProjection, self.pattern_size)?;
         println!("   ✅ Asymmetric grid排序完成");
         Ok(())
     }
 
-    /// 返回按线性顺序(0..39)排序后的圆心 - 优化版：按投影排序+均分
-    fn sort_asymmetric_grid_new(&self, centers: &core::Vector<core::Point2f>) -> Result<core::Vector<core::Point2f>, opencv::Error> {
-        if centers.len() != 40 {
-            println!("⚠️ sort_asymmetric_grid 需要40个点，当前={}", centers.len());
-            return Ok(centers.clone());
+    /// 设置标定板规格 (每列点数, 列数)，决定期望圆点数、排序分列与部分网格补全
+    pub fn set_pattern_size(&mut self, pattern_size: core::Size) -> Result<(), String> {
+        if pattern_size.width < 2 || pattern_size.height < 2 {
+            return Err(format!("标定板规格无效: {}×{}", pattern_size.width, pattern_size.height));
         }
+        self.pattern_size = pattern_size;
+        Ok(())
+    }
 
-        // 1) PCA估计 "右向/下向" 单位向量
-        let (axis_right, axis_down) = self.estimate_axes_pca(centers)?;
+    pub fn pattern_size(&self) -> core::Size {
+        self.pattern_size
+    }
 
-        #[derive(Clone)]
-        struct Node { 
-            x: f64,           // 沿"右向轴"的投影
-            y: f64,           // 沿"下向轴"的投影
-            pt: core::Point2f, 
-            raw_idx: usize 
+    /// 设置第一轮面积窗口 (像素²)，下一次 detect_circles 生效
+    pub fn set_area_range(&mut self, min_area: f64, max_area: f64) -> Result<(), String> {
+        if !(min_area > 0.0 && min_area < max_area) {
+            return Err(format!("面积窗口无效: [{}, {}]", min_area, max_area));
         }
+        self.min_area = min_area;
+        self.max_area = max_area;
+        println!("📐 面积窗口: [{:.0}, {:.0}] px²", min_area, max_area);
+        Ok(())
+    }
 
-        // 2) 投影并收集
-        let mut nodes: Vec<Node> = (0..40).map(|i| {
-            let p = centers.get(i).unwrap();
-            let px = p.x as f64; 
-            let py = p.y as f64;
-            Node {
-                x: px*axis_right.0 + py*axis_right.1,   // 沿"右向轴"的投影
-                y: px*axis_down.0  + py*axis_down.1,    // 沿"下向轴"的投影
-                pt: p, 
-                raw_idx: i
-            }
-        }).collect();
+    /// 第一轮面积窗口 (min_area, max_area)
+    pub fn area_range(&self) -> (f64, f64) {
+        (self.min_area, self.max_area)
+    }
 
-        // 3) 按 x′ 从右到左排序后，均分成10列
-        nodes.sort_by(|a, b| b.x.partial_cmp(&a.x).unwrap_or(std::cmp::Ordering::Equal));
+    /// 设置连通性 (4 或 8)
+    pub fn set_connectivity(&mut self, connectivity: i32) -> Result<(), String> {
+        validate_connectivity(connectivity)?;
+        self.connectivity = connectivity;
+        println!("🔗 连通性: {}连通", connectivity);
+        Ok(())
+    }
 
-        let mut out = core::Vector::<core::Point2f>::new();
-        out.reserve(40);
+    pub fn connectivity(&self) -> i32 {
+        self.connectivity
+    }
 
-        // 可选：做个简单的列间隙检查，便于定位异常
-        for c in 0..9 {
-            let right_end = nodes[c*4 + 3].x;        // 该列最"靠左"的点（列内x′最小）
-            let next_begin = nodes[(c+1)*4].x;       // 下一列最"靠右"的点（列内x′最大）
-            if right_end < next_begin {
-                // 如果出现 right_end < next_begin，说明列边界有交叠
-                // 正常轻微交叠也没关系，因为我们强制按4个一列切分
-                println!("   📊 列{}与列{}有轻微交叠 ({:.1} < {:.1})", c, c+1, right_end, next_begin);
-            }
+    /// 设置背景平坦化核尺寸（None 恢复按期望直径自动计算）
+    pub fn set_flatten_kernel_size(&mut self, kernel_size: Option<i32>) -> Result<(), String> {
+        if let Some(kernel_size) = kernel_size {
+            validate_flatten_kernel(kernel_size)?;
         }
+        self.flatten_kernel_size = kernel_size;
+        println!("🧹 背景平坦化核尺寸: {}", self.flatten_kernel_size());
+        Ok(())
+    }
 
-        // 4) 列内按 y′ 从上到下排序，然后按 c*4+j 的顺序推入
-        for c in 0..10 {
-            let start = c*4;
-            let end = start + 4;
-            let mut col: Vec<Node> = nodes[start..end].to_vec();
+    /// 生效的背景平坦化核尺寸；自动时按 3σ 规则，σ = 0.8 × 期望直径均值 (≈62.8) → 377
+    pub fn flatten_kernel_size(&self) -> i32 {
+        self.flatten_kernel_size.unwrap_or_else(|| {
+            let d_nom = (self.expected_diameter_range.0 + self.expected_diameter_range.1) / 2.0; // ≈ 78.5
+            let sigma = d_nom * 0.8;
+            ((sigma * 3.0) as i32 * 2 + 1).max(3)
+        })
+    }
 
-            // 列内按 y′ 从上到下排序
-            col.sort_by(|a, b| a.y.partial_cmp(&b.y).unwrap_or(std::cmp::Ordering::Equal));
+    /// 设置局部自适应阈值（None 关闭，仅用全局阈值）
+    pub fn set_adaptive_threshold(&mut self, adaptive: Option<AdaptiveThresholdParams>) -> Result<(), String> {
+        if let Some(adaptive) = &adaptive {
+            adaptive.validate()?;
+        }
+        self.adaptive_threshold = adaptive;
+        println!("🎚️ 局部自适应阈值: {:?}", adaptive);
+        Ok(())
+    }
 
-            // 按顺序推入结果
-            for j in 0..4 {
-                out.push(col[j].pt);
-            }
+    pub fn adaptive_threshold(&self) -> Option<AdaptiveThresholdParams> {
+        self.adaptive_threshold
+    }
+
+    /// 期望圆点数 (pattern_size.area())
+    pub fn expected_points(&self) -> usize {
+        self.pattern_size.area() as usize
+    }
+
+    /// 启用/关闭部分网格补全
+    /// 
+    /// `max_missing`: 最多允许插值的缺失点数（建议1-2）
+    pub fn set_partial_grid_completion(&mut self, enabled: bool, max_missing: usize) {
+        self.partial_grid_completion = enabled;
+        self.max_interpolated_points = max_missing;
+        println!("🧩 部分网格补全: {} (最多插值 {} 个点)", if enabled { "启用" } else { "关闭" }, max_missing);
+    }
+
+    /// 设置黏连圆点检查
+    /// 
+    /// - `area_ratio`: 连通域面积超过本帧中位面积的该倍数即视为黏连（两点约2.0，需 > 1.0）
+    /// - `split`: true 尝试拆分黏连连通域；false 直接丢弃并提示降低亮度
+    pub fn set_merged_blob_filter(&mut self, area_ratio: f64, split: bool) -> Result<(), String> {
+        if !(area_ratio > 1.0) {
+            return Err(format!("黏连面积比例必须大于1.0，当前: {}", area_ratio));
         }
+        self.merged_area_ratio = area_ratio;
+        self.split_merged_blobs = split;
+        println!("🔗 黏连圆点检查: 面积比例 {:.2}, {}", area_ratio, if split { "尝试拆分" } else { "直接丢弃" });
+        Ok(())
+    }
 
-        println!("   ✅ 按投影排序+均分完成：10列×4点");
-        Ok(out)
+    /// 设置检测前预处理，重新计算Triangle阈值（预处理改变灰度分布）
+    pub fn set_preprocessing(&mut self, preprocessing: DetectionPreprocessing) -> Result<(), String> {
+        preprocessing.validate()?;
+        self.preprocessing = preprocessing;
+        self.triangle_initialized = false;
+        println!("🎛️ 检测预处理: {:?}", preprocessing);
+        Ok(())
     }
 
-    /// 通过 PCA 估计"右、下"单位向量（结合±45°约束设定符号）
-    fn estimate_axes_pca(&self, centers: &core::Vector<core::Point2f>) -> Result<((f64,f64),(f64,f64)), opencv::Error> {
-        // 计算均值与 2x2 协方差
-        let mut mx = 0.0; let mut my = 0.0;
-        for i in 0..centers.len() { let p = centers.get(i)?; mx += p.x as f64; my += p.y as f64; }
-        mx /= centers.len() as f64; my /= centers.len() as f64;
+    pub fn get_preprocessing(&self) -> DetectionPreprocessing {
+        self.preprocessing
+    }
 
-        let mut sxx=0.0; let mut syy=0.0; let mut sxy=0.0;
-        for i in 0..centers.len() {
-            let p = centers.get(i)?; let dx = p.x as f64 - mx; let dy = p.y as f64 - my;
-            sxx += dx*dx; syy += dy*dy; sxy += dx*dy;
-        }
-        sxx /= centers.len() as f64; syy /= centers.len() as f64; sxy /= centers.len() as f64;
-
-        // OpenCV 求特征向量（行向量形式）
-        let mut cov = core::Mat::zeros(2, 2, core::CV_64F)?.to_mat()?;
-        *cov.at_2d_mut::<f64>(0,0)? = sxx;
-        *cov.at_2d_mut::<f64>(0,1)? = sxy;
-        *cov.at_2d_mut::<f64>(1,0)? = sxy;
-        *cov.at_2d_mut::<f64>(1,1)? = syy;
-
-        let mut eigvals = core::Mat::default();
-        let mut eigvecs = core::Mat::default();
-        core::eigen(&cov, &mut eigvals, &mut eigvecs)?;
-
-        // 最大特征向量 -> 主轴1；第二个 -> 主轴2
-        let e0 = (
-            *eigvecs.at_2d::<f64>(0,0)?,
-            *eigvecs.at_2d::<f64>(0,1)?,
-        );
-        let e1 = (
-            *eigvecs.at_2d::<f64>(1,0)?,
-            *eigvecs.at_2d::<f64>(1,1)?,
-        );
+    /// 设置圆点极性，重新计算Triangle阈值（反相改变灰度分布）
+    pub fn set_polarity(&mut self, polarity: DotPolarity) {
+        self.polarity = polarity;
+        self.triangle_initialized = false;
+        println!("🌓 圆点极性: {:?}", polarity);
+    }
+
+    pub fn get_polarity(&self) -> DotPolarity {
+        self.polarity
+    }
+
+    /// 设置检测排除区域（空列表表示不排除），下一次 detect_circles 生效
+    pub fn set_exclusion_regions(&mut self, regions: Vec<ExclusionRegion>) -> Result<(), String> {
+        for region in &regions {
+            region.validate()?;
+        }
+        println!("🚫 检测排除区域: {} 个", regions.len());
+        self.exclusion_regions = regions;
+        Ok(())
+    }
+
+    pub fn exclusion_regions(&self) -> &[ExclusionRegion] {
+        &self.exclusion_regions
+    }
+
+    /// 热更新检测参数，下一次 detect_circles 生效（阈值重新初始化）
+    pub fn apply_config(&mut self, config: &DetectorConfig) -> Result<(), String> {
+        config.validate()?;
+        self.min_area = config.min_area;
+        self.max_area = config.max_area;
+        self.connectivity = config.connectivity;
+        self.fixed_threshold = config.fixed_threshold;
+        self.merged_area_ratio = config.merged_area_ratio;
+        self.split_merged_blobs = config.split_merged_blobs;
+        self.partial_grid_completion = config.partial_grid_completion;
+        self.max_interpolated_points = config.max_interpolated_points;
+        self.preprocessing = config.preprocessing;
+        self.polarity = config.polarity;
+        self.flatten_kernel_size = config.flatten_kernel_size;
+        self.adaptive_threshold = config.adaptive_threshold;
+        self.triangle_initialized = false;
+        println!("🎛️ 检测参数已更新: {:?}", config);
+        Ok(())
+    }
+
+    /// 当前生效的可热更新检测参数
+    pub fn config(&self) -> DetectorConfig {
+        DetectorConfig {
+            min_area: self.min_area,
+            max_area: self.max_area,
+            connectivity: self.connectivity,
+            fixed_threshold: self.fixed_threshold,
+            merged_area_ratio: self.merged_area_ratio,
+            split_merged_blobs: self.split_merged_blobs,
+            partial_grid_completion: self.partial_grid_completion,
+            max_interpolated_points: self.max_interpolated_points,
+            preprocessing: self.preprocessing,
+            polarity: self.polarity,
+            flatten_kernel_size: self.flatten_kernel_size,
+            adaptive_threshold: self.adaptive_threshold,
+        }
+    }
+
+    /// 最近一次检测中有黏连连通域未能拆分时返回诊断信息
+    pub fn last_merge_diagnostic(&self) -> Option<String> {
+        if self.last_unresolved_merged_blobs > 0 {
+            Some(format!("圆点黏连 — 请降低亮度 ({} 处黏连未能拆分)", self.last_unresolved_merged_blobs))
+        } else {
+            None
+        }
+    }
+
+    /// 最近一次检测中发现的黏连连通域数（含已成功拆分的）
+    pub fn last_merged_blob_count(&self) -> usize {
+        self.last_merged_blobs
+    }
+
+    /// 最近一次 detect_circles 最后一轮的二值图（255=前景）
+    pub fn last_binary_mask(&self) -> Option<&core::Mat> {
+        self.last_binary_mask.as_ref().map(|(_, mask)| mask)
+    }
 
-        // 选择"下"轴为更接近(0,1)的那条；"右"轴为另一条
-        let dot0_down = e0.1.abs(); // 与 y 轴的投影
-        let dot1_down = e1.1.abs();
-        let mut axis_down = if dot0_down >= dot1_down { e0 } else { e1 };
-        let mut axis_right = if dot0_down >= dot1_down { e1 } else { e0 };
+    /// 最近一次二值图所用阈值（背景平坦化后的灰度）
+    pub fn last_binary_threshold(&self) -> Option<f64> {
+        self.last_binary_mask.as_ref().map(|(threshold, _)| *threshold)
+    }
+
+    /// 取出最近一次二值图（不拷贝，取出后检测器不再持有）
+    pub fn take_last_binary_mask(&mut self) -> Option<core::Mat> {
+        self.last_binary_mask.take().map(|(_, mask)| mask)
+    }
+
+    /// 保存最近一次二值图，无可用二值图时返回 false
+    pub fn save_last_binary_mask(&self, path: &str) -> Result<bool, opencv::Error> {
+        match self.last_binary_mask() {
+            Some(mask) => imgcodecs::imwrite(path, mask, &core::Vector::<i32>::new()),
+            None => Ok(false),
+        }
+    }
 
-        // 设定符号：向下(dot>0)、向右(dot>0)
-        if axis_down.1 < 0.0 { axis_down = (-axis_down.0, -axis_down.1); }
-        if axis_right.0 < 0.0 { axis_right = (-axis_right.0, -axis_right.1); }
+    /// 当前生效的检测参数（用于导出配置快照）
+    pub fn params_snapshot(&self) -> serde_json::Value {
+        serde_json::json!({
+            "triangle_threshold": self.triangle_threshold,
+            "high_threshold": self.high_threshold,
+            "low_threshold": self.low_threshold,
+            "triangle_initialized": self.triangle_initialized,
+            "fixed_threshold": self.fixed_threshold,
+            "min_area": self.min_area,
+            "max_area": self.max_area,
+            "expected_diameter_range": [self.expected_diameter_range.0, self.expected_diameter_range.1],
+            "connectivity": self.connectivity,
+            "roi_split_threshold": self.roi_split_threshold,
+            "aspect_ratio_range": [self.aspect_ratio_min, self.aspect_ratio_max],
+            "fill_ratio_range": [self.fill_ratio_min, self.fill_ratio_max],
+            "partial_grid_completion": self.partial_grid_completion,
+            "max_interpolated_points": self.max_interpolated_points,
+            "merged_area_ratio": self.merged_area_ratio,
+            "split_merged_blobs": self.split_merged_blobs,
+            "preprocessing": self.preprocessing,
+            "exclusion_regions": self.exclusion_regions,
+            "flatten_kernel_size": self.flatten_kernel_size(),
+            "adaptive_threshold": self.adaptive_threshold,
+        })
+    }
 
-        // 归一化并正交（数值稳健）
-        let axis_down = Self::norm(axis_down);
-        let mut axis_right = Self::norm(axis_right);
-        // 强制正交
-        let dot = axis_right.0*axis_down.0 + axis_right.1*axis_down.1;
-        axis_right = Self::norm((axis_right.0 - dot*axis_down.0, axis_right.1 - dot*axis_down.1));
+    /// 最近一次检测中插值补齐的点坐标（未补全时为空）
+    pub fn last_interpolated_points(&self) -> &[core::Point2f] {
+        &self.last_interpolated_points
+    }
 
-        Ok((axis_right, axis_down))
+    /// 序号 i 对应的网格坐标 (u, v)，与 generate_world_points_from_list 一致
+    fn grid_coord(&self, i: usize) -> (f32, f32) {
+        let per_column = self.pattern_size.width as usize;
+        let c = i / per_column;
+        let j = i % per_column;
+        ((self.pattern_size.height as usize - 1 - c) as f32, (2 * j + c % 2) as f32)
     }
 
-    /// 向量归一化
-    fn norm(v: (f64, f64)) -> (f64, f64) {
-        let n = (v.0*v.0 + v.1*v.1).sqrt().max(1e-12);
-        (v.0/n, v.1/n)
+    /// 🧩 部分网格补全：按 asymmetric grid 几何插值缺失点
+    /// 
+    /// 1. PCA投影后按最大的(列数-1)个x′间隙切分各列（列0在最右，与排序算法一致）
+    /// 2. 用完整列拟合单应性 (网格坐标→图像)，将不完整列的点分配到最近的网格位置
+    /// 3. 用全部检测点重新拟合单应性，残差一致才接受，缺失位置由单应性预测
+    /// 
+    /// 返回按序号排序的完整点集及插值标记；布局不一致时返回 None
+    pub fn complete_partial_grid(
+        &self,
+        centers: &core::Vector<core::Point2f>,
+    ) -> Result<Option<(core::Vector<core::Point2f>, Vec<bool>)>, opencv::Error> {
+        let n = centers.len();
+        let per_column = self.pattern_size.width as usize;
+        let column_count = self.pattern_size.height as usize;
+        let expected = self.expected_points();
+        if n >= expected || n + self.max_interpolated_points < expected || n < column_count {
+            return Ok(None);
+        }
+
+        // 1) PCA投影并从右到左排序
+        let (axis_right, axis_down) = estimate_axes_pca(centers)?;
+        let mut nodes: Vec<(f64, f64, core::Point2f)> = centers.iter().map(|p| {
+            let (px, py) = (p.x as f64, p.y as f64);
+            (px*axis_right.0 + py*axis_right.1, px*axis_down.0 + py*axis_down.1, p)
+        }).collect();
+        nodes.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        // 最大的(列数-1)个间隙为列边界，且须明显大于列内间隙
+        let cut_count = column_count - 1;
+        let mut gaps: Vec<(f64, usize)> = (0..n - 1).map(|i| (nodes[i].0 - nodes[i + 1].0, i)).collect();
+        gaps.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        if gaps[cut_count - 1].0 < 3.0 * gaps[cut_count].0 {
+            println!("   ⚠️ 部分网格补全: 列间隙不明显 ({:.1} vs {:.1})，放弃", gaps[cut_count - 1].0, gaps[cut_count].0);
+            return Ok(None);
+        }
+        let mut cuts: Vec<usize> = gaps[..cut_count].iter().map(|g| g.1).collect();
+        cuts.sort();
+
+        let mut columns: Vec<Vec<(f64, f64, core::Point2f)>> = Vec::with_capacity(column_count);
+        let mut start = 0;
+        for cut in cuts.into_iter().chain(std::iter::once(n - 1)) {
+            let mut col = nodes[start..=cut].to_vec();
+            col.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+            columns.push(col);
+            start = cut + 1;
+        }
+        if columns.iter().any(|col| col.len() > per_column) {
+            println!("   ⚠️ 部分网格补全: 列内点数超过{}，放弃", per_column);
+            return Ok(None);
+        }
+
+        // 2) 完整列直接分配，拟合初始单应性
+        let mut slots: Vec<Option<core::Point2f>> = vec![None; expected];
+        let mut grid_pts = core::Vector::<core::Point2f>::new();
+        let mut img_pts = core::Vector::<core::Point2f>::new();
+        for (c, col) in columns.iter().enumerate().filter(|(_, col)| col.len() == per_column) {
+            for (j, node) in col.iter().enumerate() {
+                let (u, v) = self.grid_coord(c * per_column + j);
+                slots[c * per_column + j] = Some(node.2);
+                grid_pts.push(core::Point2f::new(u, v));
+                img_pts.push(node.2);
+            }
+        }
+        let h0 = calib3d::find_homography(&grid_pts, &img_pts, &mut core::Mat::default(), 0, 3.0)?;
+        if h0.empty() {
+            return Ok(None);
+        }
+
+        // 网格单位长度 (像素)：用于分配/一致性容差
+        let probe = core::Vector::<core::Point2f>::from_iter([
+            core::Point2f::new(0.0, 0.0), core::Point2f::new(1.0, 0.0), core::Point2f::new(0.0, 1.0),
+        ]);
+        let mut probe_img = core::Vector::<core::Point2f>::new();
+        core::perspective_transform(&probe, &mut probe_img, &h0)?;
+        let dist = |a: core::Point2f, b: core::Point2f| ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt();
+        let unit = (dist(probe_img.get(0)?, probe_img.get(1)?) + dist(probe_img.get(0)?, probe_img.get(2)?)) / 2.0;
+
+        // 不完整列：点分配到最近的预测网格位置
+        for (c, col) in columns.iter().enumerate().filter(|(_, col)| col.len() < per_column) {
+            let column_grid = core::Vector::<core::Point2f>::from_iter(
+                (0..per_column).map(|j| { let (u, v) = self.grid_coord(c * per_column + j); core::Point2f::new(u, v) }));
+            let mut predicted = core::Vector::<core::Point2f>::new();
+            core::perspective_transform(&column_grid, &mut predicted, &h0)?;
+
+            for node in col {
+                let (j, d) = (0..per_column)
+                    .map(|j| (j, dist(node.2, predicted.get(j).unwrap())))
+                    .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+                    .unwrap();
+                if d > 0.5 * unit || slots[c * per_column + j].is_some() {
+                    println!("   ⚠️ 部分网格补全: 列{}的点无法唯一匹配网格位置，放弃", c);
+                    return Ok(None);
+                }
+                slots[c * per_column + j] = Some(node.2);
+            }
+        }
+
+        // 3) 全部检测点重新拟合并检查一致性
+        let mut grid_pts = core::Vector::<core::Point2f>::new();
+        let mut img_pts = core::Vector::<core::Point2f>::new();
+        for (i, slot) in slots.iter().enumerate() {
+            if let Some(p) = slot {
+                let (u, v) = self.grid_coord(i);
+                grid_pts.push(core::Point2f::new(u, v));
+                img_pts.push(*p);
+            }
+        }
+        let h = calib3d::find_homography(&grid_pts, &img_pts, &mut core::Mat::default(), 0, 3.0)?;
+        if h.empty() {
+            return Ok(None);
+        }
+        let mut reproj = core::Vector::<core::Point2f>::new();
+        core::perspective_transform(&grid_pts, &mut reproj, &h)?;
+        let mut max_residual = 0.0f32;
+        for k in 0..img_pts.len() {
+            max_residual = max_residual.max(dist(img_pts.get(k)?, reproj.get(k)?));
+        }
+        if max_residual > 0.25 * unit {
+            println!("   ⚠️ 部分网格补全: 布局不一致 (最大残差 {:.1}px > {:.1}px)，放弃", max_residual, 0.25 * unit);
+            return Ok(None);
+        }
+
+        // 预测缺失点
+        let mut completed = core::Vector::<core::Point2f>::with_capacity(expected);
+        let mut mask = vec![false; expected];
+        for (i, slot) in slots.iter().enumerate() {
+            let p = match slot {
+                Some(p) => *p,
+                None => {
+                    let (u, v) = self.grid_coord(i);
+                    let mut out = core::Vector::<core::Point2f>::new();
+                    core::perspective_transform(&core::Vector::<core::Point2f>::from_iter([core::Point2f::new(u, v)]), &mut out, &h)?;
+                    let p = out.get(0)?;
+                    if p.x < 0.0 || p.y < 0.0 || p.x >= self.image_size.width as f32 || p.y >= self.image_size.height as f32 {
+                        println!("   ⚠️ 部分网格补全: 插值点{}超出图像范围，放弃", i);
+                        return Ok(None);
+                    }
+                    mask[i] = true;
+                    println!("   🧩 插值点{}: ({:.1}, {:.1})", i, p.x, p.y);
+                    p
+                }
+            };
+            completed.push(p);
+        }
+
+        println!("   ✅ 部分网格补全完成 (最大残差 {:.2}px, 网格单位 {:.1}px)", max_residual, unit);
+        Ok(Some((completed, mask)))
     }
 
 
@@ -1535,4 +2387,240 @@ impl ConnectedComponentsDetector {
         )?;
         Ok(())
     }
-}
\ No newline at end of file
+}
+/// 快速可检测性评分（自动曝光扫描用，不做完整检测）
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct DetectabilityScore {
+    pub blob_count: usize,      // 面积合理的亮斑数
+    pub contrast: f64,          // (圆点均值 - 背景均值) / 255
+    pub saturated_ratio: f64,   // 圆点内过曝(≥250)像素比例
+    pub score: f64,             // 综合评分 0-1，越大越易检测
+}
+
+/// 计算单张图像的可检测性评分
+///
+/// 1/4 降采样 + Otsu 二值化 + 连通域计数，评分 = 计数接近标定板圆点数 (pattern_size.area()) 的程度 × 对比度 × (1 - 过曝比例)，
+/// 单张 5MP 图像耗时约数毫秒
+pub fn quick_detectability_score(image: &core::Mat, pattern_size: core::Size) -> Result<DetectabilityScore, opencv::Error> {
+    const SCALE: f64 = 0.25;
+    let expected_blobs = pattern_size.area() as f64;
+
+    let mut small = core::Mat::default();
+    imgproc::resize(image, &mut small, core::Size::new(0, 0), SCALE, SCALE, imgproc::INTER_AREA)?;
+
+    let mut binary = core::Mat::default();
+    imgproc::threshold(&small, &mut binary, 0.0, 255.0, imgproc::THRESH_BINARY | imgproc::THRESH_OTSU)?;
+
+    // 期望圆点直径 67-90px，降采样后面积放宽到 0.4×最小 ~ 2×最大
+    let dot_area = |d: f64| std::f64::consts::PI * (d * SCALE / 2.0).powi(2);
+    let (min_area, max_area) = (0.4 * dot_area(67.0), 2.0 * dot_area(90.0));
+
+    let mut labels = core::Mat::default();
+    let mut stats = core::Mat::default();
+    let mut centroids = core::Mat::default();
+    let num_labels = imgproc::connected_components_with_stats(
+        &binary, &mut labels, &mut stats, &mut centroids, 8, core::CV_32S
+    )?;
+    let mut blob_count = 0;
+    for i in 1..num_labels {
+        let area = *stats.at_2d::<i32>(i, imgproc::CC_STAT_AREA)? as f64;
+        if area >= min_area && area <= max_area {
+            blob_count += 1;
+        }
+    }
+
+    let mut background_mask = core::Mat::default();
+    core::bitwise_not(&binary, &mut background_mask, &core::Mat::default())?;
+    let foreground_mean = core::mean(&small, &binary)?[0];
+    let background_mean = core::mean(&small, &background_mask)?[0];
+    let contrast = ((foreground_mean - background_mean) / 255.0).max(0.0);
+
+    let mut saturated = core::Mat::default();
+    imgproc::threshold(&small, &mut saturated, 249.0, 255.0, imgproc::THRESH_BINARY)?;
+    let mut saturated_fg = core::Mat::default();
+    core::bitwise_and(&saturated, &binary, &mut saturated_fg, &core::Mat::default())?;
+    let foreground_pixels = core::count_non_zero(&binary)?;
+    let saturated_ratio = if foreground_pixels > 0 {
+        core::count_non_zero(&saturated_fg)? as f64 / foreground_pixels as f64
+    } else {
+        0.0
+    };
+
+    let count_score = (1.0 - (blob_count as f64 - expected_blobs).abs() / expected_blobs).max(0.0);
+    let score = count_score * contrast * (1.0 - saturated_ratio);
+
+    Ok(DetectabilityScore { blob_count, contrast, saturated_ratio, score })
+}
+
+// ==================== 圆点排序（标定与合像检测共用） ====================
+//
+// 点序约定与 Calibrator::generate_world_points_from_list 一致：
+// 序号0在右上角，默认共10列×4点；列从右到左 (c = 0..9)，列内从上到下 (j = 0..3)，序号 = c*4 + j。
+// 其他规格 pattern_size = (每列点数 W, 列数 H) 同理：序号 = c*W + j，末点 (W*H-1) 位于最左列底部。
+// 标定 (calibration_circles.rs) 与合像检测 (alignment.rs) 均通过 order_asymmetric_grid 排序，
+// 保证同一张图像得到相同的点序，从而对应相同的世界坐标。
+
+/// 非对称圆点网格排序策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum GridOrderStrategy {
+    /// PCA 估计"右/下"主轴，按右向投影从右到左均分10列，列内按下向投影从上到下（默认）
+    /// 不依赖输入顺序，适用于连通域检测的无序圆心与 findCirclesGrid 输出
+    #[default]
+    PcaProjection,
+    /// 旧版标定策略：输入须为 findCirclesGrid 的列优先顺序，点0.x < 点4.x 时交换相邻奇偶列
+    ColumnSwap,
+}
+
+/// 按所选策略将 pattern_size = (每列点数, 列数) 规格的圆心排成与世界坐标一致的点序，
+/// 数量不等于 pattern_size.area() 时原样返回
+pub fn order_asymmetric_grid(
+    centers: &core::Vector<core::Point2f>,
+    strategy: GridOrderStrategy,
+    pattern_size: core::Size,
+) -> Result<core::Vector<core::Point2f>, opencv::Error> {
+    let expected = pattern_size.area() as usize;
+    if centers.len() != expected {
+        println!("⚠️ 圆点排序需要{}个点，当前={}", expected, centers.len());
+        return Ok(centers.clone());
+    }
+    let per_column = pattern_size.width as usize;
+    match strategy {
+        GridOrderStrategy::PcaProjection => order_by_pca_projection(centers, per_column),
+        GridOrderStrategy::ColumnSwap => {
+            if centers.get(0)?.x < centers.get(per_column)?.x {
+                swap_adjacent_column_groups(centers, per_column)
+            } else {
+                Ok(centers.clone())
+            }
+        }
+    }
+}
+
+/// 交换相邻的奇偶列（0-3 ↔ 4-7, 8-11 ↔ 12-15, ...），修正 findCirclesGrid 的列顺序颠倒
+pub fn swap_adjacent_columns(centers: &core::Vector<core::Point2f>) -> Result<core::Vector<core::Point2f>, opencv::Error> {
+    swap_adjacent_column_groups(centers, 4)
+}
+
+/// 按每列 per_column 个点交换相邻的奇偶列
+fn swap_adjacent_column_groups(centers: &core::Vector<core::Point2f>, per_column: usize) -> Result<core::Vector<core::Point2f>, opencv::Error> {
+    let mut reordered = core::Vector::<core::Point2f>::new();
+    for pair in 0..centers.len() / (2 * per_column) {
+        let base = pair * 2 * per_column;
+        for i in (base + per_column..base + 2 * per_column).chain(base..base + per_column) {
+            reordered.push(centers.get(i)?);
+        }
+    }
+    Ok(reordered)
+}
+
+/// PCA+投影+量化：按"右向轴"投影从右到左排序后每 per_column 个点切为一列，列内按"下向轴"投影从上到下
+fn order_by_pca_projection(centers: &core::Vector<core::Point2f>, per_column: usize) -> Result<core::Vector<core::Point2f>, opencv::Error> {
+    // 1) PCA估计 "右向/下向" 单位向量
+    let (axis_right, axis_down) = estimate_axes_pca(centers)?;
+
+    #[derive(Clone)]
+    struct Node { 
+        x: f64,           // 沿"右向轴"的投影
+        y: f64,           // 沿"下向轴"的投影
+        pt: core::Point2f, 
+    }
+
+    // 2) 投影并收集
+    let mut nodes: Vec<Node> = centers.iter().map(|p| {
+        let px = p.x as f64; 
+        let py = p.y as f64;
+        Node {
+            x: px*axis_right.0 + py*axis_right.1,
+            y: px*axis_down.0  + py*axis_down.1,
+            pt: p, 
+        }
+    }).collect();
+
+    // 3) 按 x′ 从右到左排序后，均分成各列
+    nodes.sort_by(|a, b| b.x.partial_cmp(&a.x).unwrap_or(std::cmp::Ordering::Equal));
+    let column_count = nodes.len() / per_column;
+
+    // 可选：做个简单的列间隙检查，便于定位异常
+    for c in 0..column_count.saturating_sub(1) {
+        let right_end = nodes[c*per_column + per_column - 1].x;  // 该列最"靠左"的点（列内x′最小）
+        let next_begin = nodes[(c+1)*per_column].x;              // 下一列最"靠右"的点（列内x′最大）
+        if right_end < next_begin {
+            // 正常轻微交叠也没关系，因为我们强制按每列点数切分
+            println!("   📊 列{}与列{}有轻微交叠 ({:.1} < {:.1})", c, c+1, right_end, next_begin);
+        }
+    }
+
+    // 4) 列内按 y′ 从上到下排序，然后按 c*per_column+j 的顺序推入
+    let mut out = core::Vector::<core::Point2f>::new();
+    out.reserve(nodes.len());
+    for column in nodes.chunks_mut(per_column) {
+        column.sort_by(|a, b| a.y.partial_cmp(&b.y).unwrap_or(std::cmp::Ordering::Equal));
+        for node in column.iter() {
+            out.push(node.pt);
+        }
+    }
+
+    println!("   ✅ 按投影排序+均分完成：{}列×{}点", column_count, per_column);
+    Ok(out)
+}
+
+/// 通过 PCA 估计"右、下"单位向量（结合±45°约束设定符号）
+fn estimate_axes_pca(centers: &core::Vector<core::Point2f>) -> Result<((f64,f64),(f64,f64)), opencv::Error> {
+    // 计算均值与 2x2 协方差
+    let mut mx = 0.0; let mut my = 0.0;
+    for i in 0..centers.len() { let p = centers.get(i)?; mx += p.x as f64; my += p.y as f64; }
+    mx /= centers.len() as f64; my /= centers.len() as f64;
+
+    let mut sxx=0.0; let mut syy=0.0; let mut sxy=0.0;
+    for i in 0..centers.len() {
+        let p = centers.get(i)?; let dx = p.x as f64 - mx; let dy = p.y as f64 - my;
+        sxx += dx*dx; syy += dy*dy; sxy += dx*dy;
+    }
+    sxx /= centers.len() as f64; syy /= centers.len() as f64; sxy /= centers.len() as f64;
+
+    // OpenCV 求特征向量（行向量形式）
+    let mut cov = core::Mat::zeros(2, 2, core::CV_64F)?.to_mat()?;
+    *cov.at_2d_mut::<f64>(0,0)? = sxx;
+    *cov.at_2d_mut::<f64>(0,1)? = sxy;
+    *cov.at_2d_mut::<f64>(1,0)? = sxy;
+    *cov.at_2d_mut::<f64>(1,1)? = syy;
+
+    let mut eigvals = core::Mat::default();
+    let mut eigvecs = core::Mat::default();
+    core::eigen(&cov, &mut eigvals, &mut eigvecs)?;
+
+    // 最大特征向量 -> 主轴1；第二个 -> 主轴2
+    let e0 = (
+        *eigvecs.at_2d::<f64>(0,0)?,
+        *eigvecs.at_2d::<f64>(0,1)?,
+    );
+    let e1 = (
+        *eigvecs.at_2d::<f64>(1,0)?,
+        *eigvecs.at_2d::<f64>(1,1)?,
+    );
+
+    // 选择"下"轴为更接近(0,1)的那条；"右"轴为另一条
+    let dot0_down = e0.1.abs(); // 与 y 轴的投影
+    let dot1_down = e1.1.abs();
+    let mut axis_down = if dot0_down >= dot1_down { e0 } else { e1 };
+    let mut axis_right = if dot0_down >= dot1_down { e1 } else { e0 };
+
+    // 设定符号：向下(dot>0)、向右(dot>0)
+    if axis_down.1 < 0.0 { axis_down = (-axis_down.0, -axis_down.1); }
+    if axis_right.0 < 0.0 { axis_right = (-axis_right.0, -axis_right.1); }
+
+    // 归一化并正交（数值稳健）
+    let axis_down = norm(axis_down);
+    let mut axis_right = norm(axis_right);
+    // 强制正交
+    let dot = axis_right.0*axis_down.0 + axis_right.1*axis_down.1;
+    axis_right = norm((axis_right.0 - dot*axis_down.0, axis_right.1 - dot*axis_down.1));
+
+    Ok((axis_right, axis_down))
+}
+
+/// 向量归一化
+fn norm(v: (f64, f64)) -> (f64, f64) {
+    let n = (v.0*v.0 + v.1*v.1).sqrt().max(1e-12);
+    (v.0/n, v.1/n)
+}
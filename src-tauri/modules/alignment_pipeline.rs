@@ -26,6 +26,8 @@
 // 
 // - **流水线专用**: 此模块仅处理连续帧的并行处理
 // - **单帧检测**: 前端触发的单帧检测应使用 `alignment_workflow.rs`
+// - **双眼并行**: Thread A/B 内左右眼同时处理（右眼在作用域线程中执行），
+//   工作流的 "快速完整检测" 复用本流水线处理单帧
 // - **空值处理**: Thread C 的条件检测已完美支持前端空值显示需求
 
 use std::sync::{Arc, Mutex, mpsc};
@@ -33,7 +35,8 @@ use std::thread;
 use std::time::{Duration, Instant};
 use std::collections::VecDeque;
 use opencv::{core::Mat, prelude::*};
-use crate::modules::alignment::{AlignmentSystem, SingleEyePoseResult, DualEyeAlignmentResult};
+use crate::modules::alignment::{AlignmentSystem, AlignmentSettings, SingleEyePoseResult, DualEyeAlignmentResult, DEFAULT_PATTERN_SIZE, DEFAULT_CENTER_DISTANCE_MM};
+use crate::modules::rectification::RemapInterpolation;
 
 /// 流水线任务数据
 #[derive(Clone)]
@@ -75,10 +78,10 @@ pub struct AlignmentResult {
 
 /// 🚀 流水线并行处理系统
 pub struct AlignmentPipeline {
-    // 各阶段通信通道 - 使用 SyncSender
-    remap_sender: mpsc::SyncSender<PipelineFrame>,
-    detection_sender: mpsc::SyncSender<RemappedFrame>,
-    analysis_sender: mpsc::SyncSender<DetectionResult>,
+    // 各阶段通信通道 - 使用 SyncSender（Option 以便关闭时按顺序释放）
+    remap_sender: Option<mpsc::SyncSender<PipelineFrame>>,
+    detection_sender: Option<mpsc::SyncSender<RemappedFrame>>,
+    analysis_sender: Option<mpsc::SyncSender<DetectionResult>>,
     result_receiver: mpsc::Receiver<AlignmentResult>,
     
     // 线程句柄
@@ -91,6 +94,9 @@ pub struct AlignmentPipeline {
     performance_stats: Arc<Mutex<PipelineStats>>,
 }
 
+/// 关闭时等待单个线程退出的超时时间
+const SHUTDOWN_JOIN_TIMEOUT: Duration = Duration::from_millis(2000);
+
 /// 流水线性能统计
 #[derive(Debug, Clone)]
 pub struct PipelineStats {
@@ -103,7 +109,7 @@ pub struct PipelineStats {
 }
 
 impl AlignmentPipeline {
-    /// 创建新的流水线实例
+    /// 创建新的流水线实例（默认4×10标定板）
     pub fn new(
         image_size: opencv::core::Size,
         left_camera_params_path: &str,
@@ -112,6 +118,84 @@ impl AlignmentPipeline {
         rectify_params_path: &str,
         rectify_maps_path: &str,
     ) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_configure(
+            image_size,
+            left_camera_params_path,
+            right_camera_params_path,
+            stereo_params_path,
+            rectify_params_path,
+            rectify_maps_path,
+            opencv::core::Size::new(DEFAULT_PATTERN_SIZE.0, DEFAULT_PATTERN_SIZE.1),
+            |_| Ok(()),
+        )
+    }
+    
+    /// 创建流水线实例，各线程的 AlignmentSystem 按 pattern_size 创建，再由 configure 配置
+    /// （如与工作流一致的姿态坐标约定、世界坐标原点、分位数）
+    pub fn with_configure<F>(
+        image_size: opencv::core::Size,
+        left_camera_params_path: &str,
+        right_camera_params_path: &str,
+        stereo_params_path: &str,
+        rectify_params_path: &str,
+        rectify_maps_path: &str,
+        pattern_size: opencv::core::Size,
+        configure: F,
+    ) -> Result<Self, Box<dyn std::error::Error>>
+    where
+        F: Fn(&mut AlignmentSystem) -> Result<(), String>,
+    {
+        let create_system = |image_size| -> Result<AlignmentSystem, Box<dyn std::error::Error>> {
+            let mut system = AlignmentSystem::with_pattern(
+                image_size,
+                left_camera_params_path,
+                right_camera_params_path,
+                stereo_params_path,
+                rectify_params_path,
+                pattern_size,
+                DEFAULT_CENTER_DISTANCE_MM,
+            )?;
+            configure(&mut system)?;
+            Ok(system)
+        };
+        Self::build(image_size, rectify_maps_path, create_system)
+    }
+    
+    /// 按工作流 AlignmentSystem 的全部设置（标定板规格、检测参数、居中目标、判定阈值等）创建流水线
+    pub fn with_settings(
+        image_size: opencv::core::Size,
+        left_camera_params_path: &str,
+        right_camera_params_path: &str,
+        stereo_params_path: &str,
+        rectify_params_path: &str,
+        rectify_maps_path: &str,
+        settings: &AlignmentSettings,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let create_system = |image_size| -> Result<AlignmentSystem, Box<dyn std::error::Error>> {
+            let mut system = AlignmentSystem::with_pattern(
+                image_size,
+                left_camera_params_path,
+                right_camera_params_path,
+                stereo_params_path,
+                rectify_params_path,
+                opencv::core::Size::new(settings.pattern_size.0, settings.pattern_size.1),
+                settings.center_distance_mm,
+            )?;
+            system.apply_settings(settings)?;
+            Ok(system)
+        };
+        Self::build(image_size, rectify_maps_path, create_system)
+    }
+    
+    /// 创建流水线线程，各线程的 AlignmentSystem 由 create_system 创建
+    fn build<F>(
+        image_size: opencv::core::Size,
+        rectify_maps_path: &str,
+        create_system: F,
+    ) -> Result<Self, Box<dyn std::error::Error>>
+    where
+        F: Fn(opencv::core::Size) -> Result<AlignmentSystem, Box<dyn std::error::Error>>,
+    {
         println!("🚀 初始化流水线并行处理系统...");
         
         // 🚀 生产环境优化缓冲区配置 - 充分利用16GB内存
@@ -153,15 +237,10 @@ impl AlignmentPipeline {
             let detection_tx = detection_tx.clone();
             let stats = Arc::clone(&performance_stats);
             // 为Thread A创建轻量级实例（不重复预加载）
-            let mut alignment_system = AlignmentSystem::new(
-                image_size,
-                left_camera_params_path,
-                right_camera_params_path,
-                stereo_params_path,
-                rectify_params_path,
-            )?;
+            let mut alignment_system = create_system(image_size)?;
             // 手动触发预加载，但不重复初始化
             alignment_system.ensure_maps_loaded(rectify_maps_path)?;
+            let rectify_maps_path = rectify_maps_path.to_string();
             
             thread::spawn(move || {
                 println!("🔧 Thread A: 重映射线程启动");
@@ -169,7 +248,7 @@ impl AlignmentPipeline {
                 while let Ok(frame) = remap_rx.recv() {
                     let remap_start = Instant::now();
                     
-                    match alignment_system.remap_images_only(&frame.left_image, &frame.right_image) {
+                    match alignment_system.remap_images_only(&frame.left_image, &frame.right_image, &rectify_maps_path) {
                         Ok((left_rect, right_rect)) => {
                             let remap_time = remap_start.elapsed();
                             
@@ -204,14 +283,9 @@ impl AlignmentPipeline {
         let detection_handle = {
             let analysis_tx = analysis_tx.clone();
             let stats = Arc::clone(&performance_stats);
-            // Thread B只需要基础系统，不需要重映射矩阵
-            let mut alignment_system = AlignmentSystem::new(
-                image_size,
-                left_camera_params_path,
-                right_camera_params_path,
-                stereo_params_path,
-                rectify_params_path,
-            )?;
+            // Thread B只需要基础系统，不需要重映射矩阵；左右眼各一个实例以便并行检测
+            let mut alignment_system = create_system(image_size)?;
+            let mut right_system = create_system(image_size)?;
             
             thread::spawn(move || {
                 println!("🔍 Thread B: 圆心检测线程启动");
@@ -219,7 +293,7 @@ impl AlignmentPipeline {
                 while let Ok(frame) = detection_rx.recv() {
                     let detection_start = Instant::now();
                     
-                    match alignment_system.detect_circles_only(&frame.left_rectified, &frame.right_rectified) {
+                    match alignment_system.detect_circles_parallel(&mut right_system, &frame.left_rectified, &frame.right_rectified) {
                         Ok((left_corners, right_corners)) => {
                             let detection_time = detection_start.elapsed();
                             
@@ -255,13 +329,7 @@ impl AlignmentPipeline {
             let result_tx = result_tx.clone();
             let stats = Arc::clone(&performance_stats);
             // Thread C只需要基础系统，不需要重映射矩阵
-            let mut alignment_system = AlignmentSystem::new(
-                image_size,
-                left_camera_params_path,
-                right_camera_params_path,
-                stereo_params_path,
-                rectify_params_path,
-            )?;
+            let mut alignment_system = create_system(image_size)?;
             
             thread::spawn(move || {
                 println!("🎯 Thread C: 姿态分析线程启动");
@@ -291,6 +359,11 @@ impl AlignmentPipeline {
                                 pitch: 0.0,
                                 yaw: 0.0,
                                 pass: false,
+                                spread: Default::default(),
+                                standoff: Default::default(),
+                                reprojection_rms: 0.0,
+                                reject_reason: Some(e.to_string()),
+                                method_used: Default::default(),
                             }
                         }
                     };
@@ -314,6 +387,11 @@ impl AlignmentPipeline {
                                 pitch: 0.0,
                                 yaw: 0.0,
                                 pass: false,
+                                spread: Default::default(),
+                                standoff: Default::default(),
+                                reprojection_rms: 0.0,
+                                reject_reason: Some(e.to_string()),
+                                method_used: Default::default(),
                             }
                         }
                     };
@@ -378,9 +456,9 @@ impl AlignmentPipeline {
         println!("✅ 流水线并行处理系统初始化完成");
         
         Ok(Self {
-            remap_sender: remap_tx,
-            detection_sender: detection_tx,
-            analysis_sender: analysis_tx,
+            remap_sender: Some(remap_tx),
+            detection_sender: Some(detection_tx),
+            analysis_sender: Some(analysis_tx),
             result_receiver: result_rx,
             remap_handle: Some(remap_handle),
             detection_handle: Some(detection_handle),
@@ -401,8 +479,10 @@ impl AlignmentPipeline {
             right_image,
         };
         
+        let remap_sender = self.remap_sender.as_ref().ok_or("流水线已关闭")?;
+        
         // 🔍 缓冲区健康检查 - 保护长期运行
-        match self.remap_sender.try_send(frame) {
+        match remap_sender.try_send(frame) {
             Ok(_) => {
                 // 发送成功，流水线健康
                 Ok(())
@@ -425,6 +505,11 @@ impl AlignmentPipeline {
         }
     }
     
+    /// 最近一次提交的帧序号（被丢弃的帧也计数）
+    pub fn last_frame_id(&self) -> u64 {
+        self.frame_counter
+    }
+    
     /// 🎯 获取处理结果（非阻塞）
     pub fn try_get_result(&self) -> Option<AlignmentResult> {
         self.result_receiver.try_recv().ok()
@@ -465,36 +550,77 @@ impl AlignmentPipeline {
     }
     
     /// 🛑 关闭流水线系统
+    /// 
+    /// 关闭顺序：按流水线方向释放发送端 (A → B → C)，使各线程的 recv() 依次返回错误退出；
+    /// 再按同样顺序等待线程结束，每个线程最多等待 SHUTDOWN_JOIN_TIMEOUT。
+    /// 等待期间持续清空结果通道，避免 Thread C 阻塞在已满的结果缓冲上。
+    /// 可重复调用，Drop 时自动调用。
     pub fn shutdown(&mut self) {
+        if self.remap_handle.is_none() && self.detection_handle.is_none() && self.analysis_handle.is_none() {
+            return;
+        }
+        
         println!("🛑 关闭流水线处理系统...");
         
-        // 主动关闭所有发送端，让接收线程退出
-        // 这些 sender 会在 drop 时自动关闭，触发线程退出
+        // 1. 释放发送端，触发上游到下游的级联退出
+        self.remap_sender.take();
+        self.detection_sender.take();
+        self.analysis_sender.take();
+        println!("📤 通道已关闭，等待线程退出...");
         
-        // 设置较短的等待时间，避免无限等待
-        use std::time::Duration;
-        let timeout = Duration::from_millis(1000); // 1秒超时
+        // 2. 按流水线顺序等待线程结束
+        let handles = [
+            ("Thread A (重映射)", self.remap_handle.take()),
+            ("Thread B (圆心检测)", self.detection_handle.take()),
+            ("Thread C (姿态分析)", self.analysis_handle.take()),
+        ];
         
-        // 快速退出策略 - 不等待线程，让系统自然清理
-        println!("⏳ 释放线程资源...");
+        let mut all_exited = true;
+        for (name, handle) in handles {
+            if let Some(handle) = handle {
+                all_exited &= self.join_with_timeout(name, handle, SHUTDOWN_JOIN_TIMEOUT);
+            }
+        }
         
-        // 取出线程句柄但不等待，让它们自然结束
-        let _remap_handle = self.remap_handle.take();
-        let _detection_handle = self.detection_handle.take();
-        let _analysis_handle = self.analysis_handle.take();
+        if all_exited {
+            println!("✅ 流水线处理系统已关闭");
+        } else {
+            println!("⚠️ 流水线处理系统已关闭，但部分线程未在超时内退出");
+        }
+    }
+    
+    /// 等待线程结束（带超时），返回线程是否已退出
+    fn join_with_timeout(&self, name: &str, handle: thread::JoinHandle<()>, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
         
-        // 注意：线程会在通道关闭时自然退出，不需要强制等待
-        println!("📤 通道已关闭，线程将自然退出");
+        while !handle.is_finished() {
+            if Instant::now() >= deadline {
+                println!("⚠️ {} 未在 {} ms 内退出，放弃等待", name, timeout.as_millis());
+                return false;
+            }
+            // 清空结果通道，防止 Thread C 阻塞在 send 上
+            while self.result_receiver.try_recv().is_ok() {}
+            thread::sleep(Duration::from_millis(10));
+        }
         
-        println!("✅ 流水线处理系统已关闭");
+        match handle.join() {
+            Ok(_) => {
+                println!("✓ {} 已退出", name);
+                true
+            }
+            Err(e) => {
+                println!("⚠️ {} 退出异常: {:?}", name, e);
+                true
+            }
+        }
     }
 }
 
 impl Drop for AlignmentPipeline {
     fn drop(&mut self) {
-        // 避免在Drop中调用可能阻塞的shutdown
-        // 用户应该显式调用shutdown()
+        // 确保线程在 AlignmentSystem 等资源释放前退出
         println!("🔄 AlignmentPipeline正在释放资源...");
+        self.shutdown();
     }
 }
 
@@ -505,22 +631,67 @@ impl AlignmentSystem {
         &mut self,
         left_image: &Mat,
         right_image: &Mat,
+        rectify_maps_path: &str,
     ) -> Result<(Mat, Mat), Box<dyn std::error::Error>> {
         // 确保重映射矩阵已加载
-        // 🔧 修正重映射矩阵路径 - 使用yaml_last_param_file目录
-        self.ensure_maps_loaded("yaml_last_param_file/rectify_maps.yaml")?;
+        self.ensure_maps_loaded(rectify_maps_path)?;
         
         // 使用公有的访问方法获取重映射矩阵
         if let Some((left_map1, left_map2, right_map1, right_map2)) = self.get_rectify_maps() {
             let rectifier = self.get_rectifier();
-            let left_rect = rectifier.remap_image_adaptive(left_image, left_map1, left_map2)?;
-            let right_rect = rectifier.remap_image_adaptive(right_image, right_map1, right_map2)?;
-            Ok((left_rect, right_rect))
+            // Thread A 的输出直接进入圆心检测，使用线性插值；右眼在作用域线程中同时重映射
+            let (left_rect, right_rect) = thread::scope(|s| {
+                let right = s.spawn(|| rectifier.remap_image_adaptive(right_image, right_map1, right_map2, RemapInterpolation::Linear));
+                let left = rectifier.remap_image_adaptive(left_image, left_map1, left_map2, RemapInterpolation::Linear);
+                (left, right.join())
+            });
+            let right_rect = right_rect.map_err(|_| "右眼重映射线程异常退出")?;
+            Ok((left_rect?, right_rect?))
         } else {
             Err("重映射矩阵未加载".into())
         }
     }
     
+    /// 单眼圆心检测（连通域检测器），返回 (是否找到完整网格, 圆心)
+    fn detect_eye_circles(&mut self, rectified: &Mat) -> Result<(bool, opencv::core::Vector<opencv::core::Point2f>), opencv::Error> {
+        use opencv::features2d::{SimpleBlobDetector, SimpleBlobDetector_Params};
+        let pattern_size = self.pattern_size();
+        let detector = SimpleBlobDetector::create(SimpleBlobDetector_Params::default()?)?.into(); // 保持接口兼容，但实际不使用
+        let mut corners = opencv::core::Vector::<opencv::core::Point2f>::new();
+        let found = self.detect_circles_full_image(rectified, pattern_size, &mut corners, &detector)?;
+        Ok((found, corners))
+    }
+    
+    /// 左右眼并行圆心检测（用于Thread B）
+    /// 
+    /// 左眼在当前线程使用 self 检测，右眼在作用域线程中使用 right_system 检测
+    pub fn detect_circles_parallel(
+        &mut self,
+        right_system: &mut AlignmentSystem,
+        left_rectified: &Mat,
+        right_rectified: &Mat,
+    ) -> Result<(opencv::core::Vector<opencv::core::Point2f>, opencv::core::Vector<opencv::core::Point2f>), Box<dyn std::error::Error>> {
+        let (left, right) = thread::scope(|s| {
+            let right = s.spawn(|| right_system.detect_eye_circles(right_rectified));
+            let left = self.detect_eye_circles(left_rectified);
+            (left, right.join())
+        });
+        let (left_found, corners_left) = left?;
+        let (right_found, corners_right) = right.map_err(|_| "右眼检测线程异常退出")??;
+        
+        if !left_found {
+            return Err("左眼圆点网格检测失败".into());
+        }
+        if !right_found {
+            return Err("右眼圆点网格检测失败".into());
+        }
+        
+        println!("✅ Thread B: 并行圆心检测完成 - 左眼{}个点，右眼{}个点", 
+                corners_left.len(), corners_right.len());
+        
+        Ok((corners_left, corners_right))
+    }
+    
     /// 仅执行圆心检测（用于Thread B）
     /// 🆕 已更新使用ConnectedComponentsDetector替代SimpleBlobDetector
     pub fn detect_circles_only(
@@ -528,7 +699,7 @@ impl AlignmentSystem {
         left_rectified: &Mat,
         right_rectified: &Mat,
     ) -> Result<(opencv::core::Vector<opencv::core::Point2f>, opencv::core::Vector<opencv::core::Point2f>), Box<dyn std::error::Error>> {
-        let pattern_size = opencv::core::Size::new(4, 10);
+        let pattern_size = self.pattern_size();
         let mut corners_left = opencv::core::Vector::<opencv::core::Point2f>::new();
         let mut corners_right = opencv::core::Vector::<opencv::core::Point2f>::new();
         
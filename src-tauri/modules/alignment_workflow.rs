@@ -1,19 +1,36 @@
 // alignment_workflow.rs - 光机合像检测工作流程
 // 双线程架构：采集线程 + 处理线程
 // 支持实时预览和阶段化合像检测
-
-use std::sync::{Arc, Mutex, mpsc, atomic::{AtomicBool, Ordering}};
+//
+// 测量存档 (write_measurement_archive) 以 zip 格式写出，依赖 zip crate 的 SimpleFileOptions（1.0 起提供）
+// 与 Deflated 压缩（deflate 特性）；src-tauri/Cargo.toml 需声明：
+//
+//   [dependencies]
+//   zip = { version = "2", default-features = false, features = ["deflate"] }
+
+use std::sync::{Arc, Mutex, mpsc, atomic::{AtomicBool, AtomicU64, Ordering}};
 use std::thread;
 use std::time::{Duration, Instant};
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use opencv::{core, imgcodecs, imgproc, prelude::*};
 use tauri::{AppHandle, Emitter};
 use serde::{Serialize, Deserialize};
 
-use crate::camera_manager::{SimpleCameraManager, CameraError};
+use crate::camera_manager::{SimpleCameraManager, CameraError, FrameRecoveryConfig, CameraWarmupConfig, FrameResolution};
+use crate::paths;
+use crate::commands::alignment_commands::CameraPreviewData;
 use crate::modules::{
-    alignment::{AlignmentSystem, SingleEyePoseResult, DualEyeAlignmentResult, CenteringResult, AdjustmentVectors},
+    alignment::{compose_mask_overlay, AlignmentSystem, AlignmentSettings, SingleEyePoseResult, DualEyeAlignmentResult, CenteringResult, AdjustmentVectors, StageTimings, DebugRenderConfig, BlankFrameConfig, PoseConvention, PoseSpread, StandoffCheck, StandoffRange, ConvergenceRange, ConvergenceCheck, SyntheticGridParams, MicrometerCalibration, ScrewTurn, MagnificationCheck, ModuleConsistencyReport, ErrorHistogram, AcceptanceThresholds, ReevaluationVerdict, CenteringTargets, AlignmentReport, RobustStatsConfig, PnpMethod, DEFAULT_PATTERN_SIZE, DEFAULT_CENTER_DISTANCE_MM},
     param_io::*,
+    rectification::RemapInterpolation,
+    alignment_circles_detection::{quick_detectability_score, DetectabilityScore, DetectionDiagnostics, DetectionPreprocessing, DetectorConfig, ExclusionRegion},
+    alignment_pipeline::AlignmentPipeline,
+    benchmark::BenchmarkSummary,
+    plc_modbus::{PlcModbusConfig, PlcRegisterBank},
+    result_log::{AlignmentLogRecord, ResultLogger},
+    pose_filter::{PoseKalmanConfig, PoseKalmanFilter, PoseAngles},
+    result_metrics::{source_metrics, source_units, MetricDescriptor, MetricSource, MetricUnit},
+    calibration_workflow::CalibrationConfig,
 };
 
 // ==================== 数据结构定义 ====================
@@ -32,14 +49,128 @@ pub enum DetectionStage {
     Error { message: String }, // 错误状态
 }
 
+/// 工作流生命周期状态
+/// 
+/// NotCreated: 尚未创建工作流实例（仅命令层可判断）
+/// Created: 已创建（相机已初始化），检测参数未加载
+/// ParamsLoaded: 标定参数已加载，采集/处理线程未运行
+/// WarmingUp: 相机已启动，正在丢弃启动后的预热帧
+/// Running: 采集/处理线程运行中，可执行检测
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum WorkflowInitState {
+    NotCreated,
+    Created,
+    ParamsLoaded,
+    WarmingUp,
+    Running,
+}
+
+/// 相机启动预热进度（alignment-warmup / calibration-warmup 事件）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WarmupProgress {
+    pub discarded: u32,  // 已取出的预热帧数
+    pub total: u32,
+    pub done: bool,
+    pub error: Option<String>,  // 预热失败原因（done 为 true 时有效）
+}
+
 /// 帧数据结构 (原始数据版本)
 #[derive(Clone)]
 pub struct FrameData {
     pub left_image: Vec<u8>,
     pub right_image: Vec<u8>,
+    pub resolution: FrameResolution,  // 采集时配置的分辨率，解析原始数据用
     pub timestamp: Instant,
 }
 
+/// 自动曝光扫描单步结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExposureScanPoint {
+    pub exposure_us: f64,
+    pub left: DetectabilityScore,
+    pub right: DetectabilityScore,
+    pub score: f64,             // 左右评分取较小值
+}
+
+/// 自动曝光扫描结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExposureScanResult {
+    pub points: Vec<ExposureScanPoint>,
+    pub best_exposure_us: f64,
+    pub best_score: f64,
+    pub original_exposure_us: f64,
+    pub applied: bool,          // true: 已应用最佳曝光; false: 已恢复原曝光
+}
+
+/// 自动化工位单眼姿态结论
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CyclePoseVerdict {
+    pub roll: f64,
+    pub pitch: f64,
+    pub yaw: f64,
+    pub pass: bool,
+}
+
+/// 自动化工位左眼居中结论
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleCenteringVerdict {
+    pub pass: bool,
+    pub max_offset_px: f32,
+    pub tolerance_px: f32,
+    pub top_right_offset: (f32, f32),
+    pub bottom_left_offset: (f32, f32),
+}
+
+/// 自动化工位合像结论
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleAlignmentVerdict {
+    pub mean_dx: f64,
+    pub mean_dy: f64,
+    pub rms: f64,
+    pub p95: f64,
+    pub max_err: f64,
+    pub percentile: f64,
+    pub pass: bool,
+}
+
+/// 自动化工位单次完整检测结论
+/// 
+/// 与实时检测不同，姿态不通过时不会提前结束：左眼姿态/居中、右眼姿态、合像全部计算后给出总判定，
+/// failed_stage 为按调整顺序第一个未通过的项目 (no_projection / detection / left_pose /
+/// left_centering / right_pose / alignment)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutomatedCycleVerdict {
+    pub pass: bool,
+    pub failed_stage: Option<String>,
+    pub message: String,
+    pub left_pose: Option<CyclePoseVerdict>,
+    pub left_centering: Option<CycleCenteringVerdict>,
+    pub right_pose: Option<CyclePoseVerdict>,
+    pub alignment: Option<CycleAlignmentVerdict>,
+    pub adjustment_priority: Option<String>,  // 调整优先级 (AdjustmentPriority)
+    pub timings: StageTimings,
+    pub cycle_ms: f64,                        // 取帧到出结论总耗时
+    pub timestamp: String,
+}
+
+impl AutomatedCycleVerdict {
+    fn failed(stage: &str, message: String, cycle_start: Instant) -> Self {
+        Self {
+            pass: false,
+            failed_stage: Some(stage.to_string()),
+            message,
+            left_pose: None,
+            left_centering: None,
+            right_pose: None,
+            alignment: None,
+            adjustment_priority: None,
+            timings: StageTimings::default(),
+            cycle_ms: cycle_start.elapsed().as_secs_f64() * 1000.0,
+            timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+        }
+    }
+}
+
 /// 检测结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "stage")]
@@ -50,6 +181,14 @@ pub enum DetectionResult {
         yaw: f64,
         pass: bool,
         message: String,
+        #[serde(default)]
+        spread: PoseSpread,      // 多帧平均的帧数与离散程度
+        #[serde(default)]
+        standoff: StandoffCheck, // 工作距离 (mm)
+        #[serde(default)]
+        reprojection_rms: f64,   // 姿态解重投影RMS (像素)
+        #[serde(default)]
+        timings: StageTimings,
     },
     RightEyePose {
         roll: f64,
@@ -57,6 +196,14 @@ pub enum DetectionResult {
         yaw: f64,
         pass: bool,
         message: String,
+        #[serde(default)]
+        spread: PoseSpread,
+        #[serde(default)]
+        standoff: StandoffCheck,
+        #[serde(default)]
+        reprojection_rms: f64,
+        #[serde(default)]
+        timings: StageTimings,
     },
     DualEyeAlignment {
         mean_dx: f64,
@@ -66,248 +213,1815 @@ pub enum DetectionResult {
         max_err: f64,
         pass: bool,
         adjustment_hint: String,
+        #[serde(default)]
+        timings: StageTimings,
+        #[serde(default = "default_error_percentile")]
+        percentile: f64,         // p95 实际对应的分位数
+        #[serde(default)]
+        percentile_label: String, // 如 "P95" / "P99"，供前端标注
+        #[serde(default)]
+        magnification: MagnificationCheck, // 左右网格放大倍率比较，mismatch 时区别于位置偏差
+        #[serde(default)]
+        robust_rms: Option<f64>,  // 剔除离群点后的RMS，未启用稳健统计时为None
+        #[serde(default)]
+        robust_p95: Option<f64>,  // 剔除离群点后的分位误差
+        #[serde(default)]
+        outlier_indices: Vec<usize>, // MAD 离群点序号
+    },
+    /// 无投影信号（全黑帧），区别于“圆点网格未找到”的失调问题
+    NoProjection {
+        left_blank: bool,
+        right_blank: bool,
+        left_mean: f64,
+        left_max: f64,
+        right_mean: f64,
+        right_max: f64,
+        message: String,
     },
     Error {
         message: String,
     },
 }
 
-/// 环形缓冲区（优化版）
-pub struct RingBuffer<T> {
-    buffer: VecDeque<T>,
-    capacity: usize,
-    total_pushed: u64,
-    dropped_count: u64,
+fn default_error_percentile() -> f64 {
+    crate::modules::alignment::DEFAULT_ERROR_PERCENTILE
 }
 
-impl<T> RingBuffer<T> {
-    pub fn new(capacity: usize) -> Self {
-        Self {
-            buffer: VecDeque::with_capacity(capacity),
-            capacity,
-            total_pushed: 0,
-            dropped_count: 0,
+impl DetectionResult {
+    /// 序列化标签（与 #[serde(tag = "stage")] 一致）
+    pub fn stage_name(&self) -> &'static str {
+        match self {
+            DetectionResult::LeftEyePose { .. } => "LeftEyePose",
+            DetectionResult::RightEyePose { .. } => "RightEyePose",
+            DetectionResult::DualEyeAlignment { .. } => "DualEyeAlignment",
+            DetectionResult::NoProjection { .. } => "NoProjection",
+            DetectionResult::Error { .. } => "Error",
         }
     }
 
-    pub fn push(&mut self, item: T) {
-        self.total_pushed += 1;
-        
-        if self.buffer.len() >= self.capacity {
-            self.buffer.pop_front();
-            self.dropped_count += 1;
+    /// 本结果对应的指标描述表
+    pub fn metric_source(&self) -> MetricSource {
+        match self {
+            DetectionResult::LeftEyePose { .. } => MetricSource::LeftEyePose,
+            DetectionResult::RightEyePose { .. } => MetricSource::RightEyePose,
+            DetectionResult::DualEyeAlignment { .. } => MetricSource::DualEyeAlignment,
+            DetectionResult::NoProjection { .. } => MetricSource::NoProjection,
+            DetectionResult::Error { .. } => MetricSource::Error,
         }
-        self.buffer.push_back(item);
     }
 
-    pub fn latest(&self) -> Option<&T> {
-        self.buffer.back()
+    /// 本结果各数值字段的单位与显示名称
+    pub fn metric_descriptors(&self) -> Vec<MetricDescriptor> {
+        source_metrics(self.metric_source())
     }
+}
 
-    pub fn len(&self) -> usize {
-        self.buffer.len()
-    }
+/// alignment-result 事件负载：结果字段保持不变，另附 units（字段路径 → 单位），
+/// 使度/像素/毫米在传输格式中显式可见；显示名称见 get_result_metric_descriptors
+#[derive(Debug, Clone, Serialize)]
+pub struct LabeledDetectionResult<'a> {
+    #[serde(flatten)]
+    pub result: &'a DetectionResult,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub units: BTreeMap<String, MetricUnit>,
+}
 
-    /// 获取性能统计
-    pub fn get_stats(&self) -> (u64, u64, f64) {
-        let drop_rate = if self.total_pushed > 0 {
-            (self.dropped_count as f64 / self.total_pushed as f64) * 100.0
-        } else {
-            0.0
-        };
-        (self.total_pushed, self.dropped_count, drop_rate)
+impl<'a> LabeledDetectionResult<'a> {
+    pub fn new(result: &'a DetectionResult) -> Self {
+        Self { result, units: source_units(result.metric_source()) }
     }
 }
 
-// ==================== 主工作流程系统 ====================
-
-pub struct AlignmentWorkflow {
-    // 基础组件 (简化版)
-    camera_manager: Arc<Mutex<SimpleCameraManager>>,
-    alignment_system: Arc<Mutex<Option<AlignmentSystem>>>,
-    app_handle: AppHandle,
-
-    // 线程控制
-    running: Arc<AtomicBool>,
-    acquisition_thread: Option<thread::JoinHandle<()>>,
-    processing_thread: Option<thread::JoinHandle<()>>,
+/// 快速完整检测等待流水线结果的超时时间
+const FAST_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
 
-    // 数据通信
-    frame_buffer: Arc<Mutex<RingBuffer<FrameData>>>,
-    stage: Arc<Mutex<DetectionStage>>,
-    
-    // 通道通信
-    command_sender: Option<mpsc::Sender<WorkflowCommand>>,
+/// 快速完整检测结果（单帧经流水线，双眼并行重映射/检测）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FastCheckReport {
+    pub left_pose: DetectionResult,          // LeftEyePose
+    pub right_pose: DetectionResult,         // RightEyePose
+    pub alignment: Option<DetectionResult>,  // DualEyeAlignment，任一眼姿态未通过时为空
+    pub pipeline_ms: f64,                    // 流水线提交到出结果的耗时
+    pub sequential_ms: Option<f64>,          // 同一帧顺序检测 (detect_single_frame) 耗时
+    pub speedup: Option<f64>,                // sequential_ms / pipeline_ms
+    #[serde(default)]
+    pub units: BTreeMap<String, MetricUnit>, // 字段路径 → 单位（嵌套结果以 "left_pose.roll" 等路径表示）
 }
 
-/// 工作流程命令
-#[derive(Debug)]
-pub enum WorkflowCommand {
-    StartPreview,
-    StartDetection,
-    NextStage,
-    Reset,
-    Stop,
+impl FastCheckReport {
+    pub fn new(left_pose: DetectionResult, right_pose: DetectionResult, alignment: Option<DetectionResult>, pipeline_ms: f64, sequential_ms: Option<f64>) -> Self {
+        Self {
+            left_pose,
+            right_pose,
+            alignment,
+            pipeline_ms,
+            sequential_ms,
+            speedup: sequential_ms.map(|ms| ms / pipeline_ms.max(1e-6)),
+            units: source_units(MetricSource::FastCheckReport),
+        }
+    }
 }
 
-impl AlignmentWorkflow {
-    /// 创建合像检测工作流程 (SimpleCameraManager版本)
-    pub fn new(
-        app_handle: AppHandle,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
-        println!("初始化合像检测工作流程 (SimpleCameraManager版本)...");
-
-        // 创建SimpleCameraManager
-        let camera_manager = Arc::new(Mutex::new(SimpleCameraManager::new()?));
-        let frame_buffer = Arc::new(Mutex::new(RingBuffer::new(5))); // 保持最近5帧
-        let stage = Arc::new(Mutex::new(DetectionStage::Idle));
-
-        Ok(Self {
-            camera_manager,
-            alignment_system: Arc::new(Mutex::new(None)),
-            app_handle,
-            running: Arc::new(AtomicBool::new(false)),
-            acquisition_thread: None,
-            processing_thread: None,
-            frame_buffer,
-            stage,
-            command_sender: None,
-        })
-    }
+/// 快速完整检测流水线及其创建时的检测设置与图像尺寸（设置变化后重建）
+type FastCheckPipelineSlot = Arc<Mutex<Option<(AlignmentSettings, core::Size, AlignmentPipeline)>>>;
+
+/// 快速完整检测任务：在工作流状态锁内快照最新帧与检测设置，释放状态锁后再执行
+/// 
+/// 等待流水线结果最长 FAST_CHECK_TIMEOUT，期间不阻塞其他命令
+pub struct FastCheckJob {
+    left_mat: Mat,
+    right_mat: Mat,
+    settings: AlignmentSettings,
+    image_size: core::Size,
+    param_paths: [String; 4],   // 左/右相机参数、双目参数、校正参数
+    rectify_maps_path: String,
+    pipeline: FastCheckPipelineSlot,
+    alignment_system: Arc<Mutex<Option<AlignmentSystem>>>,
+}
 
-    /// 初始化合像检测系统（加载参数）
-    pub fn initialize_alignment_system(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        println!("=== 初始化合像检测系统 ===");
+impl FastCheckJob {
+    /// 执行快速完整检测（流水线首次使用或设置变化时按快照设置重建）
+    /// 
+    /// compare_sequential 为 true 时对同一帧再执行一次顺序检测，上报两者耗时以衡量加速效果
+    pub fn run(self, compare_sequential: bool) -> Result<FastCheckReport, Box<dyn std::error::Error>> {
+        let mut pipeline_slot = self.pipeline.lock().unwrap();
+        let stale = pipeline_slot.as_ref()
+            .map_or(true, |(settings, image_size, _)| *settings != self.settings || *image_size != self.image_size);
+        if stale {
+            println!("🚀 创建快速完整检测流水线...");
+            *pipeline_slot = None; // 先关闭旧流水线
+            let [left_params, right_params, stereo_params, rectify_params] = &self.param_paths;
+            let pipeline = AlignmentPipeline::with_settings(
+                self.image_size,
+                left_params,
+                right_params,
+                stereo_params,
+                rectify_params,
+                &self.rectify_maps_path,
+                &self.settings,
+            )?;
+            *pipeline_slot = Some((self.settings.clone(), self.image_size, pipeline));
+        }
+        let (_, _, pipeline) = pipeline_slot.as_mut().unwrap();
         
-        // 更新状态
-        *self.stage.lock().unwrap() = DetectionStage::Loading;
-        self.emit_stage_update()?;
-
-        // 加载标定参数
-        let image_size = core::Size::new(2448, 2048);
+        // 丢弃之前超时遗留的结果
+        while pipeline.try_get_result().is_some() {}
+        
+        let start = Instant::now();
+        pipeline.process_frame(self.left_mat.clone(), self.right_mat.clone())?;
+        let frame_id = pipeline.last_frame_id();
+        let result = loop {
+            let remaining = FAST_CHECK_TIMEOUT.checked_sub(start.elapsed()).unwrap_or_default();
+            match pipeline.get_result_timeout(remaining) {
+                Some(result) if result.frame_id == frame_id => break result,
+                Some(_) => continue,
+                None => return Err(format!("流水线未在 {} ms 内返回结果（圆点检测失败或超时）",
+                                           FAST_CHECK_TIMEOUT.as_millis()).into()),
+            }
+        };
+        let pipeline_ms = start.elapsed().as_secs_f64() * 1000.0;
+        drop(pipeline_slot);
         
-        // 🔧 修正参数文件路径 - 使用yaml_last_param_file目录
-        // 旧路径 (注释掉):
-        // "left_camera_params.yaml",
-        // "right_camera_params.yaml", 
-        // "stereo_params.yaml",
-        // "rectify_params.yaml",
+        let alignment = result.alignment_result.as_ref().map(|a| alignment_to_detection_result(a, StageTimings::default()));
         
-        let alignment_sys = AlignmentSystem::new(
-            image_size,
-            "yaml_last_param_file/left_camera_params.yaml",
-            "yaml_last_param_file/right_camera_params.yaml", 
-            "yaml_last_param_file/stereo_params.yaml",
-            "yaml_last_param_file/rectify_params.yaml",
-        )?;
-
-        *self.alignment_system.lock().unwrap() = Some(alignment_sys);
+        let sequential_ms = if compare_sequential {
+            let mut alignment_sys = self.alignment_system.lock().unwrap();
+            let sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+            let sequential_start = Instant::now();
+            if let Err(e) = AlignmentWorkflow::detect_frame_with(sys, &self.rectify_maps_path, self.left_mat, self.right_mat, false) {
+                println!("⚠️ 顺序检测对比失败: {}", e);
+            }
+            Some(sequential_start.elapsed().as_secs_f64() * 1000.0)
+        } else {
+            None
+        };
+        let report = FastCheckReport::new(
+            pose_to_detection_result(true, &result.left_pose_result),
+            pose_to_detection_result(false, &result.right_pose_result),
+            alignment,
+            pipeline_ms,
+            sequential_ms,
+        );
         
-        println!("✓ 合像检测系统初始化完成");
-        *self.stage.lock().unwrap() = DetectionStage::Idle;
-        self.emit_stage_update()?;
+        println!("⚡ 快速完整检测: 流水线 {:.1} ms", pipeline_ms);
+        if let (Some(ms), Some(ratio)) = (report.sequential_ms, report.speedup) {
+            println!("   顺序检测 {:.1} ms, 加速 {:.2}×", ms, ratio);
+        }
         
-        Ok(())
+        Ok(report)
+    }
+}
+
+/// 单项指标的重复性统计（样本标准差，n-1）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetricRepeatability {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl MetricRepeatability {
+    /// 由样本计算统计量，忽略 NaN，无有效样本时返回 None（单个样本标准差为0）
+    pub fn from_samples(values: &[f64]) -> Option<Self> {
+        let values: Vec<f64> = values.iter().copied().filter(|v| !v.is_nan()).collect();
+        let mean = crate::modules::alignment::mean(&values)?;
+        let std_dev = if values.len() > 1 {
+            (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64).sqrt()
+        } else {
+            0.0
+        };
+        Some(Self {
+            mean,
+            std_dev,
+            min: values.iter().cloned().fold(f64::INFINITY, f64::min),
+            max: values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+        })
     }
+}
 
-    /// 启动工作流程（双线程模式 - SimpleCameraManager版本）
-    pub fn start_workflow(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        println!("🚀 启动合像检测工作流程...");
-        
-        if self.running.load(Ordering::SeqCst) {
-            return Err("工作流程已经在运行中".into());
+/// 重复性测试结果：静止工装上连续检测 K 次的合像指标离散程度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepeatabilityReport {
+    pub requested: usize,                    // 请求次数 K
+    pub successful: usize,                   // 得到合像结果的次数（仅这些计入统计）
+    pub mean_dx: Option<MetricRepeatability>,
+    pub mean_dy: Option<MetricRepeatability>,
+    pub rms: Option<MetricRepeatability>,
+    pub failures: Vec<String>,               // 未得到合像结果的原因（第N次: 原因）
+}
+
+/// 单眼姿态结果 → 检测结果
+fn pose_to_detection_result(is_left: bool, pose: &SingleEyePoseResult) -> DetectionResult {
+    let message = pose.message(if is_left { "左眼" } else { "右眼" });
+    if is_left {
+        DetectionResult::LeftEyePose {
+            roll: pose.roll,
+            pitch: pose.pitch,
+            yaw: pose.yaw,
+            pass: pose.pass,
+            message,
+            spread: pose.spread,
+            standoff: pose.standoff,
+            reprojection_rms: pose.reprojection_rms,
+            timings: StageTimings::default(),
         }
-        
-        // [配置系统 - 已注释] 设置相机为合像检测模式
-        // unsafe {
-        //     crate::camera_ffi::set_camera_mode(2); // 2 = alignment mode
-        // }
-        // println!("📷 已设置相机为合像检测模式");
-        
-        // 创建SimpleCameraManager并启动
-        {
-            let mut cam = self.camera_manager.lock()
-                .map_err(|e| format!("获取相机管理器失败: {}", e))?;
-            cam.start()
-                .map_err(|e| format!("启动相机失败: {:?}", e))?;
+    } else {
+        DetectionResult::RightEyePose {
+            roll: pose.roll,
+            pitch: pose.pitch,
+            yaw: pose.yaw,
+            pass: pose.pass,
+            message,
+            spread: pose.spread,
+            standoff: pose.standoff,
+            reprojection_rms: pose.reprojection_rms,
+            timings: StageTimings::default(),
         }
+    }
+}
 
-        // 初始化系统（如果还没有）
-        if self.alignment_system.lock().unwrap().is_none() {
-            self.initialize_alignment_system()?;
+/// 双眼合像结果 → 检测结果（含稳健统计，供日志/PLC/前端使用）
+fn alignment_to_detection_result(alignment: &DualEyeAlignmentResult, timings: StageTimings) -> DetectionResult {
+    DetectionResult::DualEyeAlignment {
+        mean_dx: alignment.mean_dx,
+        mean_dy: alignment.mean_dy,
+        rms: alignment.rms,
+        p95: alignment.p95,
+        max_err: alignment.max_err,
+        pass: alignment.pass,
+        adjustment_hint: alignment.adjustment_hint(),
+        timings,
+        percentile: alignment.percentile,
+        percentile_label: alignment.percentile_label(),
+        magnification: alignment.magnification,
+        robust_rms: alignment.robust_rms,
+        robust_p95: alignment.robust_p95,
+        outlier_indices: alignment.outlier_indices.clone(),
+    }
+}
+
+/// 检测结果 → 双眼合像结果（非合像阶段返回 None）
+fn detection_result_to_alignment(result: &DetectionResult) -> Option<DualEyeAlignmentResult> {
+    match result {
+        DetectionResult::DualEyeAlignment { mean_dx, mean_dy, rms, p95, max_err, pass, percentile, magnification, robust_rms, robust_p95, outlier_indices, .. } => {
+            Some(DualEyeAlignmentResult {
+                mean_dx: *mean_dx,
+                mean_dy: *mean_dy,
+                rms: *rms,
+                p95: *p95,
+                max_err: *max_err,
+                pass: *pass,
+                percentile: *percentile,
+                magnification: *magnification,
+                robust_rms: *robust_rms,
+                robust_p95: *robust_p95,
+                outlier_indices: outlier_indices.clone(),
+            })
         }
+        _ => None,
+    }
+}
 
-        // 优化OpenCV性能配置
-        self.configure_opencv_performance()?;
+/// 千分尺调整指令（最新一帧的调整向量换算为螺杆圈数）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScrewTurnReport {
+    pub priority: String,       // 调整优先级
+    pub turns: Vec<ScrewTurn>,  // 已配置螺杆的旋转指令
+}
 
-        self.running.store(true, Ordering::SeqCst);
-        
-        // 创建命令通道
-        let (cmd_tx, cmd_rx) = mpsc::channel();
-        self.command_sender = Some(cmd_tx);
+/// 黄金基准文件名（配置目录下）
+pub const GOLDEN_REFERENCE_FILE: &str = "golden_reference.json";
 
-        // 启动采集线程
-        self.start_acquisition_thread()?;
-        
-        // 启动处理线程
-        self.start_processing_thread(cmd_rx)?;
+/// 重映射矩阵文件名（标定参数目录下）
+pub const RECTIFY_MAPS_FILE: &str = "rectify_maps.yaml";
 
-        // 启动预览模式
-        self.send_command(WorkflowCommand::StartPreview)?;
+/// 标定参数目录下的文件路径；工作流的参数/重映射矩阵路径均经此构造
+pub fn param_file_path(param_dir: &std::path::Path, file_name: &str) -> String {
+    param_dir.join(file_name).to_string_lossy().to_string()
+}
 
-        println!("✓ 工作流程启动完成");
-        Ok(())
-    }
+/// 单次完整测量（双眼姿态 + 合像），用作黄金基准或与基准比较
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlignmentMeasurement {
+    pub left_roll: f64,    // 度
+    pub left_pitch: f64,
+    pub left_yaw: f64,
+    pub right_roll: f64,
+    pub right_pitch: f64,
+    pub right_yaw: f64,
+    pub mean_dx: f64,      // 像素
+    pub mean_dy: f64,
+    pub rms: f64,
+    pub p95: f64,
+    pub max_err: f64,
+    pub pass: bool,
+    pub captured_at: String,
+}
 
-    /// 配置OpenCV性能优化
-    fn configure_opencv_performance(&self) -> Result<(), Box<dyn std::error::Error>> {
-        // 设置OpenCV线程数为CPU核心数的一半，避免过度并行
-        let cpu_cores = num_cpus::get();
-        let opencv_threads = (cpu_cores / 2).max(1).min(4); // 限制在1-4之间
-        
-        #[cfg(feature = "opencv")]
-        {
-            opencv::core::set_num_threads(opencv_threads as i32)
-                .map_err(|e| format!("设置OpenCV线程数失败: {}", e))?;
-            println!("🔧 OpenCV线程数设置为: {} (CPU核心: {})", opencv_threads, cpu_cores);
+impl AlignmentMeasurement {
+    pub fn from_results(left: &SingleEyePoseResult, right: &SingleEyePoseResult, alignment: &DualEyeAlignmentResult) -> Self {
+        Self {
+            left_roll: left.roll,
+            left_pitch: left.pitch,
+            left_yaw: left.yaw,
+            right_roll: right.roll,
+            right_pitch: right.pitch,
+            right_yaw: right.yaw,
+            mean_dx: alignment.mean_dx,
+            mean_dy: alignment.mean_dy,
+            rms: alignment.rms,
+            p95: alignment.p95,
+            max_err: alignment.max_err,
+            pass: left.pass && right.pass && alignment.pass,
+            captured_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
         }
+    }
 
-        // 启用OpenCV优化
-        #[cfg(feature = "opencv")]
-        {
-            opencv::core::set_use_optimized(true)
-                .map_err(|e| format!("启用OpenCV优化失败: {}", e))?;
-            println!("🚀 OpenCV优化已启用");
-        }
+    fn metrics(&self) -> [(&'static str, f64); 11] {
+        [
+            ("left_roll", self.left_roll),
+            ("left_pitch", self.left_pitch),
+            ("left_yaw", self.left_yaw),
+            ("right_roll", self.right_roll),
+            ("right_pitch", self.right_pitch),
+            ("right_yaw", self.right_yaw),
+            ("mean_dx", self.mean_dx),
+            ("mean_dy", self.mean_dy),
+            ("rms", self.rms),
+            ("p95", self.p95),
+            ("max_err", self.max_err),
+        ]
+    }
 
-        Ok(())
+    /// 逐项计算与基准的差值 (当前 - 基准)
+    pub fn compare_to(&self, reference: &AlignmentMeasurement) -> Vec<MetricDelta> {
+        self.metrics().iter().zip(reference.metrics().iter())
+            .map(|(&(metric, current), &(_, reference))| MetricDelta {
+                metric: metric.to_string(),
+                reference,
+                current,
+                delta: current - reference,
+            })
+            .collect()
     }
+}
 
-    /// 启动采集线程 (SimpleCameraManager版本)
-    fn start_acquisition_thread(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        let camera_manager = Arc::clone(&self.camera_manager);
-        let frame_buffer = Arc::clone(&self.frame_buffer);
-        let running = Arc::clone(&self.running);
+/// 单项指标与基准的差值
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricDelta {
+    pub metric: String,
+    pub reference: f64,
+    pub current: f64,
+    pub delta: f64,
+}
 
-        let handle = thread::spawn(move || {
-            println!("📷 采集线程启动 (SimpleCameraManager版本)");
-            
-            // 相机已经在 start_workflow() 中启动，这里不需要重复启动
-            // 移除重复的启动代码：
-            // if let Err(e) = camera_manager.lock().unwrap().start() {
-            //     eprintln!("相机启动失败: {:?}", e);
-            //     return;
-            // }
+/// 与黄金基准的比较结果（漂移监控/SPC）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoldenComparison {
+    pub reference: AlignmentMeasurement,
+    pub current: AlignmentMeasurement,
+    pub deltas: Vec<MetricDelta>,
+}
 
-            let mut frame_count = 0u64;
-            let mut last_stats_time = Instant::now();
+pub fn save_golden_reference<P: AsRef<std::path::Path>>(path: P, reference: &AlignmentMeasurement) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(parent) = path.as_ref().parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(reference)?)?;
+    Ok(())
+}
 
-            // 10fps = 100ms间隔
-            let frame_interval = Duration::from_millis(100);
-            let mut last_capture_time = Instant::now();
+pub fn load_golden_reference<P: AsRef<std::path::Path>>(path: P) -> Result<AlignmentMeasurement, Box<dyn std::error::Error>> {
+    let json = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&json)?)
+}
 
-            while running.load(Ordering::SeqCst) {
-                let now = Instant::now();
+/// 测量存档内的报告（report.json）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeasurementArchiveReport {
+    pub captured_at: String,
+    pub measurement: Option<AlignmentMeasurement>, // 检测失败时为空
+    pub messages: Vec<String>,                     // 左/右眼姿态与合像判定说明
+    pub error: Option<String>,                     // 检测失败原因
+    pub runtime: serde_json::Value,                // 运行时配置快照
+}
+
+/// 测量存档导出结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeasurementArchiveSummary {
+    pub path: String,
+    pub files: Vec<String>,
+    pub report: MeasurementArchiveReport,
+}
+
+/// 将 (文件名, 内容) 写入 zip 存档（Deflated 压缩，依赖声明见文件头）
+pub fn write_measurement_archive<P: AsRef<std::path::Path>>(path: P, entries: &[(String, Vec<u8>)]) -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::Write;
+    
+    if let Some(parent) = path.as_ref().parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(path)?);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for (name, data) in entries {
+        zip.start_file(name.as_str(), options)?;
+        zip.write_all(data)?;
+    }
+    zip.finish()?;
+    Ok(())
+}
+
+/// 合成检测所用理想针孔相机的焦距 (像素)
+const SYNTHETIC_FOCAL_PX: f64 = 3000.0;
+/// 合成检测所用理想双目基线 (mm)
+const SYNTHETIC_BASELINE_MM: f64 = 60.0;
+/// 合成检测返回图像的宽度 (缩小以减少传输数据量)
+const SYNTHETIC_PREVIEW_WIDTH: i32 = 1224;
+
+/// 合成圆阵检测结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyntheticDetectionReport {
+    pub params: SyntheticGridParams,
+    pub left_image: String,                  // 合成左图 (PNG data URL，缩小至 SYNTHETIC_PREVIEW_WIDTH)
+    pub right_image: String,                 // 合成右图
+    pub left_pose: Option<DetectionResult>,  // LeftEyePose，圆点检测失败时为空
+    pub right_pose: Option<DetectionResult>, // RightEyePose
+    pub alignment: Option<DetectionResult>,  // DualEyeAlignment（姿态未通过也计算）
+    pub error: Option<String>,               // 圆点检测失败原因，图像仍返回
+    pub elapsed_ms: f64,
+}
+
+/// 在 dir 下写入理想针孔双目参数 (无畸变, R=I, P=[K|0])，重映射即恒等映射
+/// 
+/// 测试夹具 (tests/fixtures.rs) 也用它生成不依赖外部数据的标定参数
+pub(crate) fn write_synthetic_camera_params(dir: &std::path::Path, image_size: core::Size) -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::create_dir_all(dir)?;
+    let (cx, cy) = (image_size.width as f64 / 2.0, image_size.height as f64 / 2.0);
+    let k = vec![vec![SYNTHETIC_FOCAL_PX, 0.0, cx], vec![0.0, SYNTHETIC_FOCAL_PX, cy], vec![0.0, 0.0, 1.0]];
+    let p = vec![vec![SYNTHETIC_FOCAL_PX, 0.0, cx, 0.0], vec![0.0, SYNTHETIC_FOCAL_PX, cy, 0.0], vec![0.0, 0.0, 1.0, 0.0]];
+    let identity = vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 1.0]];
+    let camera = CameraParams { camera_matrix: k, dist_coeffs: vec![0.0; 5] };
+    
+    save_camera_params(dir.join("left_camera_params.yaml"), &camera)?;
+    save_camera_params(dir.join("right_camera_params.yaml"), &camera)?;
+    save_stereo_params(dir.join("stereo_params.yaml"), &StereoParams {
+        r: identity.clone(),
+        t: vec![-SYNTHETIC_BASELINE_MM, 0.0, 0.0],
+    })?;
+    save_rectify_params(dir.join("rectify_params.yaml"), &RectifyParams {
+        r1: identity.clone(),
+        r2: identity,
+        p1: p.clone(),
+        p2: p,
+        q: vec![vec![0.0; 4]; 4],
+        valid_roi: None,
+    })?;
+    Ok(())
+}
+
+/// 缩小后编码为 PNG data URL
+fn synthetic_image_to_base64(image: &core::Mat) -> Result<String, Box<dyn std::error::Error>> {
+    use base64::{Engine as _, engine::general_purpose};
+    
+    let height = image.rows() * SYNTHETIC_PREVIEW_WIDTH / image.cols().max(1);
+    let mut resized = core::Mat::default();
+    imgproc::resize(image, &mut resized, core::Size::new(SYNTHETIC_PREVIEW_WIDTH, height), 0.0, 0.0, imgproc::INTER_AREA)?;
+    let mut buffer = core::Vector::<u8>::new();
+    imgcodecs::imencode(".png", &resized, &mut buffer, &core::Vector::new())?;
+    Ok(format!("data:image/png;base64,{}", general_purpose::STANDARD.encode(buffer.as_slice())))
+}
+
+/// 理想针孔参数（写入 captures/synthetic_camera）、指定标定板规格的合像检测系统，返回 (系统, 参数目录)
+fn synthetic_alignment_system(pattern_size: (i32, i32)) -> Result<(AlignmentSystem, std::path::PathBuf), Box<dyn std::error::Error>> {
+    let image_size = core::Size::new(2448, 2048);
+    let dir = std::path::PathBuf::from(paths::captures_path("synthetic_camera"));
+    write_synthetic_camera_params(&dir, image_size)?;
+    let path = |name: &str| dir.join(name).to_string_lossy().to_string();
+    
+    let system = AlignmentSystem::with_pattern(
+        image_size,
+        &path("left_camera_params.yaml"),
+        &path("right_camera_params.yaml"),
+        &path("stereo_params.yaml"),
+        &path("rectify_params.yaml"),
+        core::Size::new(pattern_size.0, pattern_size.1),
+        DEFAULT_CENTER_DISTANCE_MM,
+    )?;
+    Ok((system, dir))
+}
+
+/// 标定与合像模块一致性自检（世界坐标与圆点排序），不需要相机
+/// 
+/// 世界坐标与排序只取决于圆阵布局，合像侧使用理想参数、配置标定板规格 (pattern_size) 的检测系统即可
+pub fn check_module_consistency(calibration_config: &CalibrationConfig, pattern_size: (i32, i32)) -> Result<ModuleConsistencyReport, Box<dyn std::error::Error>> {
+    let (system, _) = synthetic_alignment_system(pattern_size)?;
+    let mut calibrator = calibration_config.create_calibrator(core::Size::new(2448, 2048))?;
+    system.check_calibration_consistency(&mut calibrator)
+}
+
+/// 无硬件合成检测：按参数生成左右圆阵图像，走完整检测流程（重映射→圆点检测→双眼姿态→合像）
+/// 
+/// 使用理想针孔相机（参数写入 captures/synthetic_camera，不触碰实际标定文件），
+/// 结果只由参数决定，供前端在没有相机和样例图像时开发调试。
+/// 各阶段不因前一阶段未通过而中止，圆点检测失败时返回图像与 error。
+pub fn simulate_synthetic_detection(params: &SyntheticGridParams, pattern_size: (i32, i32)) -> Result<SyntheticDetectionReport, Box<dyn std::error::Error>> {
+    params.validate()?;
+    let start = Instant::now();
+    let (mut system, dir) = synthetic_alignment_system(pattern_size)?;
+    let path = |name: &str| dir.join(name).to_string_lossy().to_string();
+    let (left, right) = system.render_synthetic_pair(params)?;
+    
+    let mut report = SyntheticDetectionReport {
+        params: *params,
+        left_image: synthetic_image_to_base64(&left)?,
+        right_image: synthetic_image_to_base64(&right)?,
+        left_pose: None,
+        right_pose: None,
+        alignment: None,
+        error: None,
+        elapsed_ms: 0.0,
+    };
+    
+    // 不写重映射矩阵文件，由理想参数降级计算（恒等映射）
+    match system.detect_circles_grid(&left, &right, &path("rectify_maps.yaml")) {
+        Ok((left_corners, right_corners)) => {
+            let left_pose = system.check_left_eye_pose(&left_corners)?;
+            let right_pose = system.check_right_eye_pose(&right_corners)?;
+            let alignment = system.check_dual_eye_alignment(&left_corners, &right_corners, false)?;
+            report.left_pose = Some(pose_to_detection_result(true, &left_pose));
+            report.right_pose = Some(pose_to_detection_result(false, &right_pose));
+            report.alignment = Some(alignment_to_detection_result(&alignment, StageTimings::default()));
+        }
+        Err(e) => {
+            println!("❌ 合成图像圆点检测失败: {}", e);
+            report.error = Some(format!("圆点检测失败: {}", e));
+        }
+    }
+    
+    report.elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+    println!("🧪 合成检测完成: {:.1} ms", report.elapsed_ms);
+    Ok(report)
+}
+
+/// 检测失败时降低曝光重试（反光件瞬时眩光导致单帧失败时使用）
+/// 
+/// 启用后检测失败会将曝光降低 exposure_step_us，等待新曝光下的帧重试一次，
+/// 无论成败均恢复原曝光；无投影 (NoProjection) 不触发重试
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct DetectionRetryConfig {
+    pub enabled: bool,
+    pub exposure_step_us: f64,      // 每次重试降低的曝光时间 (μs)
+    pub min_exposure_us: f64,       // 降低后的曝光下限 (μs)
+}
+
+impl DetectionRetryConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.exposure_step_us > 0.0) || self.min_exposure_us < 0.0 {
+            return Err(format!("无效的重试曝光参数: 步长 {} μs, 下限 {} μs",
+                               self.exposure_step_us, self.min_exposure_us));
+        }
+        Ok(())
+    }
+}
+
+impl Default for DetectionRetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            exposure_step_us: 5000.0,
+            min_exposure_us: 1000.0,
+        }
+    }
+}
+
+/// 实时调试叠加流配置
+/// 
+/// 启用后检测阶段每隔 interval_ms 发送一次 `alignment-debug-overlay` 事件：
+/// 校正图 + 二值图前景(绿) + 检测到的圆点(红圈)，缩放后编码为 JPEG。
+/// 开启期间检测系统额外保留校正图拷贝，生产环境应保持关闭
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DebugOverlayConfig {
+    pub enabled: bool,
+    pub interval_ms: u64,   // 最短发送间隔 (ms)，默认500（2fps）
+    pub scale: f64,         // 缩放系数 (0, 1]，默认0.25
+}
+
+impl DebugOverlayConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.interval_ms < 100 {
+            return Err(format!("调试叠加发送间隔不能小于100ms: {}", self.interval_ms));
+        }
+        if !(self.scale > 0.0 && self.scale <= 1.0) {
+            return Err(format!("调试叠加缩放系数应在 (0, 1] 内: {}", self.scale));
+        }
+        Ok(())
+    }
+}
+
+impl Default for DebugOverlayConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_ms: 500,
+            scale: 0.25,
+        }
+    }
+}
+
+/// 调试叠加事件内容（未检测的一眼为 None）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebugOverlayFrame {
+    pub stage: DetectionStage,
+    pub detected: bool,                 // 本帧检测是否成功
+    pub left_image: Option<String>,     // data:image/jpeg;base64,...
+    pub right_image: Option<String>,
+}
+
+/// 姿态显示滤波事件内容（alignment-pose-filtered），判定仍以 raw 为准
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilteredPoseFrame {
+    pub stage: DetectionStage,
+    pub raw: PoseAngles,
+    pub filtered: PoseAngles,
+}
+
+/// 调试叠加流状态（处理线程内按间隔限流）
+#[derive(Default)]
+struct DebugOverlayState {
+    config: DebugOverlayConfig,
+    last_sent: Option<Instant>,
+}
+
+/// 预览帧传输方式
+/// - Base64: 缩略图编码为PNG+Base64，经JSON事件传输（兼容模式，默认）
+/// - RawBuffer: 缩略图灰度原始像素写入临时目录，前端通过 `preview://` 协议读取，
+///   事件中只携带文件名/尺寸/序号，避免PNG编码与JSON往返开销
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum PreviewTransport {
+    Base64,
+    RawBuffer,
+}
+
+impl Default for PreviewTransport {
+    fn default() -> Self {
+        PreviewTransport::Base64
+    }
+}
+
+/// 原始像素预览通知事件（alignment-preview-raw）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RawPreviewNotice {
+    pub left_uri: String,      // 例如 preview://localhost/left
+    pub right_uri: String,     // 例如 preview://localhost/right
+    pub width: i32,            // 缩略图宽度
+    pub height: i32,           // 缩略图高度
+    pub format: String,        // 固定为 "gray8"
+    pub sequence: u64,         // 帧序号，前端用于丢弃旧帧
+}
+
+/// 目标帧率 → 采集帧间隔 (微秒)
+fn fps_to_interval_us(fps: f64) -> u64 {
+    (1_000_000.0 / fps).round() as u64
+}
+
+/// 采集帧间隔 (微秒) → 目标帧率
+fn interval_us_to_fps(interval_us: u64) -> f64 {
+    1_000_000.0 / interval_us.max(1) as f64
+}
+
+/// 原始像素预览缓冲区目录（供 `preview://` 协议读取）
+pub fn raw_preview_dir() -> std::path::PathBuf {
+    std::env::temp_dir().join("cosonic_alignment_preview")
+}
+
+/// 原始预览帧序号
+static RAW_PREVIEW_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// 采集线程默认目标帧率 (10fps = 100ms间隔)
+const DEFAULT_ACQUISITION_FPS: f64 = 10.0;
+
+/// 端到端延迟统计窗口大小（最近N个结果）
+const LATENCY_WINDOW: usize = 200;
+
+/// 端到端延迟分布 (毫秒)
+/// 
+/// total = 采集打时间戳 → 结果发送；queue = 采集 → 处理线程取帧
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LatencyStats {
+    pub count: usize,
+    pub last_ms: f64,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    pub mean_queue_ms: f64,
+}
+
+/// 滚动窗口延迟统计
+pub struct LatencyTracker {
+    total_ms: VecDeque<f64>,
+    queue_ms: VecDeque<f64>,
+    capacity: usize,
+}
+
+impl LatencyTracker {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            total_ms: VecDeque::with_capacity(capacity),
+            queue_ms: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// 记录一次结果发送的延迟
+    pub fn record(&mut self, total: Duration, queue: Duration) {
+        if self.total_ms.len() >= self.capacity {
+            self.total_ms.pop_front();
+            self.queue_ms.pop_front();
+        }
+        self.total_ms.push_back(total.as_secs_f64() * 1000.0);
+        self.queue_ms.push_back(queue.as_secs_f64() * 1000.0);
+    }
+
+    pub fn clear(&mut self) {
+        self.total_ms.clear();
+        self.queue_ms.clear();
+    }
+
+    /// 当前窗口内的延迟分布
+    pub fn summary(&self) -> LatencyStats {
+        if self.total_ms.is_empty() {
+            return LatencyStats::default();
+        }
+        let values: Vec<f64> = self.total_ms.iter().copied().collect();
+        let queue: Vec<f64> = self.queue_ms.iter().copied().collect();
+        LatencyStats {
+            count: values.len(),
+            last_ms: *values.last().unwrap(),
+            mean_ms: crate::modules::alignment::mean(&values).unwrap_or_default(),
+            p50_ms: crate::modules::alignment::percentile(&values, 50.0).unwrap_or_default(),
+            p95_ms: crate::modules::alignment::percentile(&values, 95.0).unwrap_or_default(),
+            p99_ms: crate::modules::alignment::percentile(&values, 99.0).unwrap_or_default(),
+            max_ms: values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            mean_queue_ms: crate::modules::alignment::mean(&queue).unwrap_or_default(),
+        }
+    }
+}
+
+/// 检测线程默认抽帧间隔：每2个新帧处理1帧（10fps采集下约5fps，与原200ms节流一致）
+pub const DEFAULT_DETECTION_DECIMATION: u32 = 2;
+
+/// 检测抽帧间隔上限
+pub const MAX_DETECTION_DECIMATION: u32 = 30;
+
+/// 实际处理帧率统计窗口 (秒)
+const DECIMATION_FPS_WINDOW: Duration = Duration::from_secs(2);
+
+/// 检测抽帧统计
+/// 
+/// skipped = 按抽帧间隔主动跳过的帧；overrun = 处理耗时超出间隔导致额外错过的帧
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct DecimationStats {
+    pub every_n: u32,
+    pub processed: u64,
+    pub skipped: u64,
+    pub overrun: u64,
+    pub processed_fps: f64,
+}
+
+/// 检测线程抽帧：按采集帧序号每N帧处理1帧，同一帧不重复处理
+pub struct FrameDecimator {
+    every_n: u32,
+    last_processed: Option<u64>,
+    last_seen: Option<u64>,
+    processed: u64,
+    skipped: u64,
+    overrun: u64,
+    processed_at: VecDeque<Instant>,
+}
+
+impl Default for FrameDecimator {
+    fn default() -> Self {
+        Self::new(DEFAULT_DETECTION_DECIMATION)
+    }
+}
+
+impl FrameDecimator {
+    pub fn new(every_n: u32) -> Self {
+        Self {
+            every_n: every_n.max(1),
+            last_processed: None,
+            last_seen: None,
+            processed: 0,
+            skipped: 0,
+            overrun: 0,
+            processed_at: VecDeque::new(),
+        }
+    }
+
+    pub fn every_n(&self) -> u32 {
+        self.every_n
+    }
+
+    /// 设置抽帧间隔 (1 = 处理每个新帧)，统计清零
+    pub fn set_every_n(&mut self, every_n: u32) -> Result<(), String> {
+        if every_n == 0 || every_n > MAX_DETECTION_DECIMATION {
+            return Err(format!("抽帧间隔必须在1-{}范围内: {}", MAX_DETECTION_DECIMATION, every_n));
+        }
+        *self = Self::new(every_n);
+        Ok(())
+    }
+
+    /// 阶段切换后重新计数：下一个新帧立即处理
+    pub fn restart(&mut self) {
+        self.last_processed = None;
+        self.last_seen = None;
+    }
+
+    /// 输入最新帧序号（采集累计帧数），返回本帧是否需要检测
+    pub fn should_process(&mut self, seq: u64, now: Instant) -> bool {
+        if self.last_seen == Some(seq) {
+            return false;
+        }
+        self.last_seen = Some(seq);
+
+        let every_n = self.every_n as u64;
+        if let Some(last) = self.last_processed {
+            let since_processed = seq.saturating_sub(last);
+            if since_processed < every_n {
+                return false;
+            }
+            // 两次处理之间的帧：间隔内的N-1帧为主动跳过，超出部分为处理过慢错过的帧
+            self.skipped += every_n - 1;
+            self.overrun += since_processed - every_n;
+        }
+        self.last_processed = Some(seq);
+        self.processed += 1;
+
+        self.processed_at.push_back(now);
+        while let Some(&oldest) = self.processed_at.front() {
+            if now.duration_since(oldest) > DECIMATION_FPS_WINDOW {
+                self.processed_at.pop_front();
+            } else {
+                break;
+            }
+        }
+        true
+    }
+
+    /// 抽帧统计；processed_fps 为最近窗口内实际处理帧率
+    pub fn stats(&self) -> DecimationStats {
+        let processed_fps = match (self.processed_at.front(), self.processed_at.back()) {
+            (Some(first), Some(last)) if self.processed_at.len() > 1 => {
+                let span = last.duration_since(*first).as_secs_f64();
+                if span > 0.0 { (self.processed_at.len() - 1) as f64 / span } else { 0.0 }
+            }
+            _ => 0.0,
+        };
+        DecimationStats {
+            every_n: self.every_n,
+            processed: self.processed,
+            skipped: self.skipped,
+            overrun: self.overrun,
+            processed_fps,
+        }
+    }
+}
+
+/// 预览缓存帧默认最长沿用时间 (毫秒)，超过后才报告无可用帧
+pub const DEFAULT_PREVIEW_STALE_TIMEOUT_MS: u64 = 2000;
+
+/// 最近一次成功编码的预览帧：采集短暂无帧时沿用，避免界面闪烁报错
+pub struct PreviewCache {
+    last: Option<(CameraPreviewData, Instant)>,
+    stale_timeout: Duration,
+}
+
+impl Default for PreviewCache {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(DEFAULT_PREVIEW_STALE_TIMEOUT_MS))
+    }
+}
+
+impl PreviewCache {
+    pub fn new(stale_timeout: Duration) -> Self {
+        Self { last: None, stale_timeout }
+    }
+
+    pub fn stale_timeout(&self) -> Duration {
+        self.stale_timeout
+    }
+
+    pub fn set_stale_timeout(&mut self, stale_timeout: Duration) {
+        self.stale_timeout = stale_timeout;
+    }
+
+    /// 记录成功编码的预览帧
+    pub fn store(&mut self, preview: &CameraPreviewData, now: Instant) {
+        self.last = Some((preview.clone(), now));
+    }
+
+    /// 缓冲区无帧时返回缓存帧（标记 stale 及缓存时长），无缓存或超过沿用时间时报错
+    pub fn fallback(&self, now: Instant) -> Result<CameraPreviewData, String> {
+        let (preview, stored_at) = self.last.as_ref().ok_or("没有可用的帧数据")?;
+        let age = now.saturating_duration_since(*stored_at);
+        if age > self.stale_timeout {
+            return Err(format!("没有可用的帧数据（最近一帧已过去 {} ms）", age.as_millis()));
+        }
+        Ok(CameraPreviewData { stale: true, age_ms: age.as_millis() as u64, ..preview.clone() })
+    }
+
+    pub fn clear(&mut self) {
+        self.last = None;
+    }
+}
+
+/// 环形缓冲区满时的覆盖策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OverflowPolicy {
+    /// 丢弃最旧帧，保留最新帧（默认，适合实时预览/检测）
+    #[default]
+    DropOldest,
+    /// 丢弃新到达帧，保留已缓存的连续帧（适合多帧平均，避免混入不连续帧）
+    DropNewest,
+}
+
+/// 环形缓冲区（优化版）
+pub struct RingBuffer<T> {
+    buffer: VecDeque<T>,
+    capacity: usize,
+    policy: OverflowPolicy,
+    total_pushed: u64,
+    dropped_count: u64,
+}
+
+impl<T> RingBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self::with_policy(capacity, OverflowPolicy::default())
+    }
+
+    /// 指定覆盖策略创建缓冲区
+    pub fn with_policy(capacity: usize, policy: OverflowPolicy) -> Self {
+        Self {
+            buffer: VecDeque::with_capacity(capacity),
+            capacity,
+            policy,
+            total_pushed: 0,
+            dropped_count: 0,
+        }
+    }
+
+    /// 推入一帧，缓冲区满时按覆盖策略丢弃一帧（最旧帧或本帧）
+    pub fn push(&mut self, item: T) {
+        self.total_pushed += 1;
+        
+        if self.buffer.len() >= self.capacity {
+            self.dropped_count += 1;
+            match self.policy {
+                OverflowPolicy::DropOldest => {
+                    self.buffer.pop_front();
+                }
+                OverflowPolicy::DropNewest => return,
+            }
+        }
+        self.buffer.push_back(item);
+    }
+
+    /// 最近缓存的一帧（DropNewest 策略下缓冲区满后不再更新）
+    pub fn latest(&self) -> Option<&T> {
+        self.buffer.back()
+    }
+
+    /// 按时间顺序（旧 → 新）遍历缓存帧
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.buffer.iter()
+    }
+
+    /// 清空缓存帧（统计保留），DropNewest 策略下消费完一组连续帧后调用
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// 累计推入帧数（即最新一帧的序号，从1开始）
+    pub fn total_pushed(&self) -> u64 {
+        self.total_pushed
+    }
+
+    /// 获取性能统计
+    pub fn get_stats(&self) -> (u64, u64, f64) {
+        let drop_rate = if self.total_pushed > 0 {
+            (self.dropped_count as f64 / self.total_pushed as f64) * 100.0
+        } else {
+            0.0
+        };
+        (self.total_pushed, self.dropped_count, drop_rate)
+    }
+}
+
+/// 处理线程最近一次各阶段的检测结果，检测报告由此组装而不重新检测
+/// 
+/// 开始检测/复位时清空，避免上一台产品的结果混入报告
+#[derive(Debug, Clone, Default)]
+pub struct LatestStageResults {
+    pub left_pose: Option<SingleEyePoseResult>,
+    pub left_centering: Option<CenteringResult>,
+    pub right_pose: Option<SingleEyePoseResult>,
+    pub alignment: Option<DualEyeAlignmentResult>,
+}
+
+// ==================== 主工作流程系统 ====================
+
+/// 工作流事件发送端：无 AppHandle（无界面的集成测试）时丢弃事件
+#[derive(Clone)]
+struct WorkflowEmitter(Option<AppHandle>);
+
+impl WorkflowEmitter {
+    fn emit<S: Serialize + Clone>(&self, event: &str, payload: S) -> tauri::Result<()> {
+        match &self.0 {
+            Some(app_handle) => app_handle.emit(event, payload),
+            None => Ok(()),
+        }
+    }
+}
+
+pub struct AlignmentWorkflow {
+    // 基础组件 (简化版)
+    camera_manager: Arc<Mutex<SimpleCameraManager>>,
+    alignment_system: Arc<Mutex<Option<AlignmentSystem>>>,
+    app_handle: WorkflowEmitter,
+
+    // 线程控制
+    running: Arc<AtomicBool>,
+    acquisition_thread: Option<thread::JoinHandle<()>>,
+    processing_thread: Option<thread::JoinHandle<()>>,
+
+    // 数据通信
+    frame_buffer: Arc<Mutex<RingBuffer<FrameData>>>,
+    stage: Arc<Mutex<DetectionStage>>,
+    
+    // 通道通信
+    command_sender: Option<mpsc::Sender<WorkflowCommand>>,
+
+    // 预览传输方式
+    preview_transport: Arc<Mutex<PreviewTransport>>,
+    preview_cache: Mutex<PreviewCache>,
+
+    // 端到端延迟统计（采集 → 结果发送）
+    latency_tracker: Arc<Mutex<LatencyTracker>>,
+
+    // debug图像绘制样式（系统初始化前设置的样式在初始化时生效）
+    debug_render_config: Arc<Mutex<DebugRenderConfig>>,
+
+    // 采集帧间隔 (微秒)，采集线程每轮读取，支持运行时调整
+    frame_interval_us: Arc<AtomicU64>,
+
+    // 实际采集帧率 (f64::to_bits)，采集线程每秒更新
+    achieved_fps: Arc<AtomicU64>,
+
+    // 检测失败时降低曝光重试（默认关闭）
+    retry_config: Arc<Mutex<DetectionRetryConfig>>,
+
+    // 暂停检测：采集与预览照常，处理线程跳过检测（两次测量之间节省CPU）
+    detection_paused: Arc<AtomicBool>,
+
+    // 实时调试叠加流（默认关闭）
+    debug_overlay: Arc<Mutex<DebugOverlayState>>,
+
+    // 实时姿态显示卡尔曼滤波（默认关闭）
+    pose_filter: Arc<Mutex<PoseKalmanFilter>>,
+    frame_decimator: Arc<Mutex<FrameDecimator>>,
+    warming_up: Arc<AtomicBool>,
+
+    // 处理线程最近一次各阶段检测结果（检测报告由此组装）
+    latest_results: Arc<Mutex<LatestStageResults>>,
+
+    // PLC输出寄存器（合像阶段每次检测后更新）及 Modbus/TCP 服务端
+    plc_registers: Arc<PlcRegisterBank>,
+    #[cfg(feature = "modbus")]
+    plc_server: Option<tauri::async_runtime::JoinHandle<()>>,
+
+    // 标定参数与重映射矩阵所在目录（默认 paths::params_dir()）
+    param_dir: std::path::PathBuf,
+
+    // 合像结果追溯日志（默认关闭）及当前被测产品的序列号与待写入结果
+    result_logger: Arc<Mutex<Option<ResultLogger>>>,
+    unit_log: Arc<Mutex<UnitLogState>>,
+
+    // 快速完整检测用流水线（首次使用时创建）及创建时的检测参数快照
+    fast_check_pipeline: FastCheckPipelineSlot,
+
+    // 原始帧分辨率（相机配置的图像/ROI尺寸），采集帧按此解析并校验长度
+    frame_resolution: FrameResolution,
+
+    // 合像标定板规格（配置 pattern_size），检测系统按此创建
+    pattern_size: core::Size,
+}
+
+/// 当前被测产品的日志状态：序列号与尚未写入日志的最新合像结果
+#[derive(Debug, Default)]
+struct UnitLogState {
+    serial: String,
+    pending: Option<DualEyeAlignmentResult>,
+}
+
+/// 结果日志写入目标：处理线程只更新待写入结果，每台产品在结束时写入一条
+#[derive(Clone)]
+struct ResultLogTarget {
+    logger: Arc<Mutex<Option<ResultLogger>>>,
+    unit: Arc<Mutex<UnitLogState>>,
+}
+
+impl ResultLogTarget {
+    /// 记录本台产品最新的合像结果（不写日志）
+    fn update_pending(&self, alignment: DualEyeAlignmentResult) {
+        self.unit.lock().unwrap().pending = Some(alignment);
+    }
+
+    /// 结束当前产品：日志开启时写入一条记录，随后清空序列号与待写入结果
+    /// 
+    /// 没有待写入结果时不写入，返回 None
+    fn finish_unit(&self) -> Result<Option<AlignmentLogRecord>, String> {
+        let mut unit = self.unit.lock().unwrap();
+        let alignment = match unit.pending.take() {
+            Some(alignment) => alignment,
+            None => return Ok(None),
+        };
+        let record = AlignmentLogRecord::new(&unit.serial, &alignment);
+        unit.serial.clear();
+        drop(unit);
+        
+        if let Some(logger) = self.logger.lock().unwrap().as_ref() {
+            logger.append(&record)?;
+            println!("📝 产品 {} 合像结果已记录: {}", 
+                     if record.serial_number.is_empty() { "(未设置序列号)" } else { &record.serial_number },
+                     if record.pass { "通过" } else { "未通过" });
+        }
+        Ok(Some(record))
+    }
+}
+
+/// 检测失败重试所需的相机访问与配置（处理线程内使用）
+struct DetectionRetryContext<'a> {
+    camera_manager: &'a Arc<Mutex<SimpleCameraManager>>,
+    frame_interval_us: &'a Arc<AtomicU64>,
+    config: DetectionRetryConfig,
+}
+
+/// 实机长时操作（自动曝光扫描/实机性能测试/自动化工位检测）使用的共享句柄
+/// 
+/// 由 `AlignmentWorkflow::live_session` 在工作流状态锁内创建，命令释放状态锁后在其上执行，
+/// 期间状态查询、停止等命令不被阻塞；每步只短暂锁定相机或检测系统
+#[derive(Clone)]
+pub struct LiveSession {
+    camera_manager: Arc<Mutex<SimpleCameraManager>>,
+    alignment_system: Arc<Mutex<Option<AlignmentSystem>>>,
+    frame_buffer: Arc<Mutex<RingBuffer<FrameData>>>,
+    running: Arc<AtomicBool>,
+    frame_interval_us: Arc<AtomicU64>,
+    rectify_maps_path: String,
+}
+
+impl LiveSession {
+    fn ensure_running(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if !self.running.load(Ordering::SeqCst) {
+            return Err("相机采集未运行".into());
+        }
+        Ok(())
+    }
+
+    /// 等待采集线程送出时间戳不早于 after 的帧（工作流已停止时立即返回错误）
+    fn wait_for_frame_after(&self, after: Instant, timeout: Duration) -> Result<FrameData, String> {
+        if !self.running.load(Ordering::SeqCst) {
+            return Err("相机采集已停止".to_string());
+        }
+        AlignmentWorkflow::wait_for_fresh_frame(&self.frame_buffer, after, timeout)
+    }
+
+    /// 自动曝光扫描：在 [min_us, max_us] 内均匀取 steps 个曝光值，逐一评分可检测性
+    /// 
+    /// 需在采集运行中调用。apply=true 时应用最佳曝光，否则恢复原曝光
+    pub fn auto_exposure_scan(&self, min_us: f64, max_us: f64, steps: u32, apply: bool) -> Result<ExposureScanResult, Box<dyn std::error::Error>> {
+        if !(min_us > 0.0 && max_us >= min_us) {
+            return Err(format!("无效的曝光范围: {}-{} μs", min_us, max_us).into());
+        }
+        if !(2..=50).contains(&steps) {
+            return Err(format!("扫描步数应在 2-50 之间: {}", steps).into());
+        }
+        self.ensure_running()?;
+
+        let pattern_size = self.alignment_system.lock().unwrap().as_ref()
+            .map(|sys| sys.pattern_size())
+            .ok_or("合像检测系统未初始化")?;
+        let original_exposure_us = self.camera_manager.lock().unwrap().get_exposure_time()? as f64;
+        println!("🔆 自动曝光扫描: {:.0}-{:.0} μs, {} 步 (当前 {:.0} μs)", min_us, max_us, steps, original_exposure_us);
+
+        let mut points = Vec::with_capacity(steps as usize);
+        let scan_result = (|| -> Result<(), Box<dyn std::error::Error>> {
+            for i in 0..steps {
+                let exposure_us = min_us + (max_us - min_us) * i as f64 / (steps - 1) as f64;
+                let frame = self.capture_frame_with_exposure(exposure_us)?;
+                let left = quick_detectability_score(&AlignmentWorkflow::raw_data_to_mat(&frame.left_image, frame.resolution)?, pattern_size)?;
+                let right = quick_detectability_score(&AlignmentWorkflow::raw_data_to_mat(&frame.right_image, frame.resolution)?, pattern_size)?;
+                let score = left.score.min(right.score);
+                println!("   {:>8.0} μs: 左 {} 点/{:.3}, 右 {} 点/{:.3}, 评分 {:.3}",
+                         exposure_us, left.blob_count, left.score, right.blob_count, right.score, score);
+                points.push(ExposureScanPoint { exposure_us, left, right, score });
+            }
+            Ok(())
+        })();
+
+        let best = points.iter()
+            .max_by(|a, b| a.score.partial_cmp(&b.score).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|p| (p.exposure_us, p.score));
+        let (best_exposure_us, best_score) = best.unwrap_or((original_exposure_us, 0.0));
+        // 扫描失败或所有曝光均无法检测时恢复原曝光
+        let applied = apply && scan_result.is_ok() && best_score > 0.0;
+        let final_exposure = if applied { best_exposure_us } else { original_exposure_us };
+        self.camera_manager.lock().unwrap().set_exposure_time(final_exposure as f32)?;
+        scan_result?;
+
+        println!("✅ 最佳曝光 {:.0} μs (评分 {:.3}), {}", best_exposure_us, best_score,
+                 if applied { "已应用" } else { "已恢复原曝光" });
+        Ok(ExposureScanResult { points, best_exposure_us, best_score, original_exposure_us, applied })
+    }
+
+    /// 设置曝光并等待采集线程送出新曝光下的帧
+    fn capture_frame_with_exposure(&self, exposure_us: f64) -> Result<FrameData, Box<dyn std::error::Error>> {
+        self.camera_manager.lock().unwrap().set_exposure_time(exposure_us as f32)?;
+        // 跳过曝光切换时正在传输的帧：曝光时间 + 两个帧间隔
+        let frame_interval = Duration::from_micros(self.frame_interval_us.load(Ordering::Relaxed));
+        let settle_until = Instant::now() + Duration::from_micros(exposure_us as u64) + frame_interval * 2;
+        self.wait_for_frame_after(settle_until, Duration::from_secs(2))
+            .map_err(|e| format!("曝光 {:.0} μs 下{}", exposure_us, e).into())
+    }
+
+    /// 实机检测性能测试：连续取 iterations 个新帧，计时完整的 检测→姿态→合像 流程
+    /// 
+    /// 建议在预览模式下运行，检测模式下后台处理线程会争用检测系统导致耗时偏高
+    pub fn run_live_benchmark(&self, iterations: usize) -> Result<BenchmarkSummary, Box<dyn std::error::Error>> {
+        if !(1..=500).contains(&iterations) {
+            return Err(format!("测试次数应在 1-500 之间: {}", iterations).into());
+        }
+        self.ensure_running()?;
+
+        println!("⏱️ 实机检测性能测试: {} 次", iterations);
+        let mut times = Vec::with_capacity(iterations);
+        let mut stages = Vec::with_capacity(iterations);
+        let mut last_timestamp: Option<Instant> = None;
+
+        for i in 1..=iterations {
+            // 每次使用新帧，避免重复检测同一帧
+            let after = last_timestamp.map(|t| t + Duration::from_micros(1)).unwrap_or_else(Instant::now);
+            let frame = self.wait_for_frame_after(after, Duration::from_secs(2))?;
+            last_timestamp = Some(frame.timestamp);
+
+            let mut alignment_sys = self.alignment_system.lock().unwrap();
+            let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+
+            let start = Instant::now();
+            let result = (|| -> Result<StageTimings, Box<dyn std::error::Error>> {
+                let left_image = AlignmentWorkflow::raw_data_to_mat(&frame.left_image, frame.resolution)?;
+                let right_image = AlignmentWorkflow::raw_data_to_mat(&frame.right_image, frame.resolution)?;
+                let (corners_left, corners_right) = alignment_sys.detect_circles_grid(
+                    &left_image,
+                    &right_image,
+                    &self.rectify_maps_path,
+                )?;
+                let mut timings = alignment_sys.get_last_stage_timings();
+
+                // 旁路测量：不改变实时检测的姿态平均与最近一次结果
+                let pose_start = Instant::now();
+                alignment_sys.measure_left_eye_pose(&corners_left)?;
+                alignment_sys.measure_right_eye_pose(&corners_right)?;
+                timings.pose_ms = pose_start.elapsed().as_secs_f64() * 1000.0;
+
+                let alignment_start = Instant::now();
+                alignment_sys.measure_dual_eye_alignment(&corners_left, &corners_right)?;
+                timings.alignment_ms = alignment_start.elapsed().as_secs_f64() * 1000.0;
+                Ok(timings)
+            })();
+            let elapsed = start.elapsed();
+
+            match result {
+                Ok(timings) => {
+                    println!("   第{}次: {:.1} ms", i, elapsed.as_secs_f64() * 1000.0);
+                    times.push(elapsed);
+                    stages.push(timings);
+                }
+                Err(e) => println!("   第{}次: 失败 - {}", i, e),
+            }
+        }
+
+        let summary = BenchmarkSummary::from_samples(iterations, &times, &stages);
+        summary.print_report("实机检测性能测试报告");
+        Ok(summary)
+    }
+
+    /// 重复性测试：静止工装上取 k 个新帧逐一执行单帧完整检测，
+    /// 统计 Δx/Δy/RMS 的均值与标准差（测量噪声，用于验收/量具R&R）
+    pub fn measure_repeatability(&self, k: usize) -> Result<RepeatabilityReport, Box<dyn std::error::Error>> {
+        if !(2..=500).contains(&k) {
+            return Err(format!("重复次数应在 2-500 之间: {}", k).into());
+        }
+        self.ensure_running()?;
+
+        println!("🔁 重复性测试: {} 次", k);
+        let (mut dx, mut dy, mut rms) = (Vec::with_capacity(k), Vec::with_capacity(k), Vec::with_capacity(k));
+        let mut failures = Vec::new();
+        let mut last_timestamp: Option<Instant> = None;
+
+        for i in 1..=k {
+            // 每次使用新帧，测量的是帧间噪声而非同一帧的重复计算
+            let after = last_timestamp.map(|t| t + Duration::from_micros(1)).unwrap_or_else(Instant::now);
+            let frame = self.wait_for_frame_after(after, Duration::from_secs(2))?;
+            last_timestamp = Some(frame.timestamp);
+
+            let left_image = AlignmentWorkflow::raw_data_to_mat(&frame.left_image, frame.resolution)?;
+            let right_image = AlignmentWorkflow::raw_data_to_mat(&frame.right_image, frame.resolution)?;
+            // 每帧只短暂锁定检测系统，帧间等待不占用
+            let result = {
+                let mut alignment_sys = self.alignment_system.lock().unwrap();
+                let sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+                AlignmentWorkflow::detect_frame_with(sys, &self.rectify_maps_path, left_image, right_image, false)
+            };
+            match result {
+                Ok(DetectionResult::DualEyeAlignment { mean_dx, mean_dy, rms: frame_rms, .. }) => {
+                    println!("   第{}次: Δx={:.3}, Δy={:.3}, RMS={:.3}", i, mean_dx, mean_dy, frame_rms);
+                    dx.push(mean_dx);
+                    dy.push(mean_dy);
+                    rms.push(frame_rms);
+                }
+                Ok(DetectionResult::LeftEyePose { message, .. })
+                | Ok(DetectionResult::RightEyePose { message, .. })
+                | Ok(DetectionResult::NoProjection { message, .. })
+                | Ok(DetectionResult::Error { message }) => failures.push(format!("第{}次: {}", i, message)),
+                Err(e) => failures.push(format!("第{}次: {}", i, e)),
+            }
+        }
+
+        let report = RepeatabilityReport {
+            requested: k,
+            successful: rms.len(),
+            mean_dx: MetricRepeatability::from_samples(&dx),
+            mean_dy: MetricRepeatability::from_samples(&dy),
+            rms: MetricRepeatability::from_samples(&rms),
+            failures,
+        };
+        println!("📋 重复性测试: 成功 {}/{}", report.successful, report.requested);
+        for (name, stats) in [("Δx", &report.mean_dx), ("Δy", &report.mean_dy), ("RMS", &report.rms)] {
+            if let Some(s) = stats {
+                println!("   {}: 均值 {:.4}, 标准差 {:.4}, 范围 [{:.4}, {:.4}]", name, s.mean, s.std_dev, s.min, s.max);
+            }
+        }
+        Ok(report)
+    }
+
+    /// 自动化工位单次检测：取一帧新图，同步完成全部检测并返回总判定
+    /// 
+    /// 不经过预览/事件通道；无投影、圆点检测失败等视为不通过而非错误，
+    /// 仅相机未运行、取帧超时等无法给出结论的情况返回 Err
+    pub fn run_automated_cycle(&self) -> Result<AutomatedCycleVerdict, Box<dyn std::error::Error>> {
+        self.ensure_running()?;
+
+        let cycle_start = Instant::now();
+        let frame = self.wait_for_frame_after(cycle_start, Duration::from_secs(2))?;
+
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        let left_image = AlignmentWorkflow::raw_data_to_mat(&frame.left_image, frame.resolution)?;
+        let right_image = AlignmentWorkflow::raw_data_to_mat(&frame.right_image, frame.resolution)?;
+
+        if let Some(DetectionResult::NoProjection { message, .. }) =
+            AlignmentWorkflow::check_no_projection(sys, &left_image, &right_image, true, true)? {
+            return Ok(AutomatedCycleVerdict::failed("no_projection", message, cycle_start));
+        }
+
+        let (left_corners, right_corners) = match sys.detect_circles_grid(&left_image, &right_image, &self.rectify_maps_path) {
+            Ok(corners) => corners,
+            Err(e) => return Ok(AutomatedCycleVerdict::failed("detection", format!("圆点检测失败: {}", e), cycle_start)),
+        };
+        let mut timings = sys.get_last_stage_timings();
+
+        // 每次自动化检测独立单帧判定：不与之前的被测件做姿态平均，也不清空实时检测的平均状态
+        let pose_start = Instant::now();
+        let left_pose = sys.measure_left_eye_pose(&left_corners)?;
+        let left_centering = sys.check_left_eye_centering(&left_corners, None)?;
+        let right_pose = sys.measure_right_eye_pose(&right_corners)?;
+        timings.pose_ms = pose_start.elapsed().as_secs_f64() * 1000.0;
+
+        let alignment_start = Instant::now();
+        let alignment = sys.measure_dual_eye_alignment(&left_corners, &right_corners)?;
+        timings.alignment_ms = alignment_start.elapsed().as_secs_f64() * 1000.0;
+
+        let adjustments = sys.calculate_adjustment_vectors(
+            Some(&left_pose), Some(&left_centering), Some(&right_pose), Some(&alignment));
+
+        let failed_stage = [
+            ("left_pose", left_pose.pass),
+            ("left_centering", left_centering.is_centered),
+            ("right_pose", right_pose.pass),
+            ("alignment", alignment.pass),
+        ].iter().find(|(_, pass)| !pass).map(|(stage, _)| stage.to_string());
+        let pass = failed_stage.is_none();
+        let message = match &failed_stage {
+            None => format!("✓ 检测通过 - RMS={:.3}px, {}={:.3}px", alignment.rms, alignment.percentile_label(), alignment.p95),
+            Some(stage) => format!("❌ 未通过: {} (调整优先级: {:?})", stage, adjustments.priority),
+        };
+        println!("🤖 自动化检测: {}", message);
+
+        Ok(AutomatedCycleVerdict {
+            pass,
+            failed_stage,
+            message,
+            left_pose: Some(CyclePoseVerdict {
+                roll: left_pose.roll, pitch: left_pose.pitch, yaw: left_pose.yaw, pass: left_pose.pass,
+            }),
+            left_centering: Some(CycleCenteringVerdict {
+                pass: left_centering.is_centered,
+                max_offset_px: left_centering.max_offset_distance,
+                tolerance_px: left_centering.tolerance_px,
+                top_right_offset: (left_centering.top_right_offset_x, left_centering.top_right_offset_y),
+                bottom_left_offset: (left_centering.bottom_left_offset_x, left_centering.bottom_left_offset_y),
+            }),
+            right_pose: Some(CyclePoseVerdict {
+                roll: right_pose.roll, pitch: right_pose.pitch, yaw: right_pose.yaw, pass: right_pose.pass,
+            }),
+            alignment: Some(CycleAlignmentVerdict {
+                mean_dx: alignment.mean_dx,
+                mean_dy: alignment.mean_dy,
+                rms: alignment.rms,
+                p95: alignment.p95,
+                max_err: alignment.max_err,
+                percentile: alignment.percentile,
+                pass: alignment.pass,
+            }),
+            adjustment_priority: Some(format!("{:?}", adjustments.priority)),
+            timings,
+            cycle_ms: cycle_start.elapsed().as_secs_f64() * 1000.0,
+            timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+        })
+    }
+}
+
+/// 工作流程命令
+#[derive(Debug)]
+pub enum WorkflowCommand {
+    StartPreview,
+    StartDetection,
+    NextStage,
+    Reset,
+    Stop,
+}
+
+impl AlignmentWorkflow {
+    /// 创建合像检测工作流程 (SimpleCameraManager版本)
+    pub fn new(
+        app_handle: AppHandle,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        println!("初始化合像检测工作流程 (SimpleCameraManager版本)...");
+
+        // 创建SimpleCameraManager
+        let camera_manager = SimpleCameraManager::new()?;
+        Ok(Self::with_camera(Some(app_handle), camera_manager))
+    }
+
+    /// 创建合像检测工作流程，标定参数从指定目录加载（而非默认数据目录）
+    pub fn new_with_param_dir(
+        app_handle: AppHandle,
+        param_dir: impl Into<std::path::PathBuf>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut workflow = Self::new(app_handle)?;
+        workflow.set_param_dir(param_dir);
+        Ok(workflow)
+    }
+
+    /// 使用指定相机管理器创建工作流程
+    /// 
+    /// 集成测试中传入 `SimpleCameraManager::with_backend` 回放已保存帧对，app_handle 为 None 时不发送事件
+    pub fn with_camera(app_handle: Option<AppHandle>, camera_manager: SimpleCameraManager) -> Self {
+        let camera_manager = Arc::new(Mutex::new(camera_manager));
+        let frame_buffer = Arc::new(Mutex::new(RingBuffer::new(5))); // 保持最近5帧
+        let stage = Arc::new(Mutex::new(DetectionStage::Idle));
+
+        Self {
+            camera_manager,
+            alignment_system: Arc::new(Mutex::new(None)),
+            app_handle: WorkflowEmitter(app_handle),
+            running: Arc::new(AtomicBool::new(false)),
+            acquisition_thread: None,
+            processing_thread: None,
+            frame_buffer,
+            stage,
+            command_sender: None,
+            preview_transport: Arc::new(Mutex::new(PreviewTransport::default())),
+            preview_cache: Mutex::new(PreviewCache::default()),
+            latency_tracker: Arc::new(Mutex::new(LatencyTracker::new(LATENCY_WINDOW))),
+            debug_render_config: Arc::new(Mutex::new(DebugRenderConfig::default())),
+            frame_interval_us: Arc::new(AtomicU64::new(fps_to_interval_us(DEFAULT_ACQUISITION_FPS))),
+            achieved_fps: Arc::new(AtomicU64::new(0f64.to_bits())),
+            retry_config: Arc::new(Mutex::new(DetectionRetryConfig::default())),
+            detection_paused: Arc::new(AtomicBool::new(false)),
+            debug_overlay: Arc::new(Mutex::new(DebugOverlayState::default())),
+            pose_filter: Arc::new(Mutex::new(PoseKalmanFilter::default())),
+            frame_decimator: Arc::new(Mutex::new(FrameDecimator::default())),
+            warming_up: Arc::new(AtomicBool::new(false)),
+            latest_results: Arc::new(Mutex::new(LatestStageResults::default())),
+            plc_registers: Arc::new(PlcRegisterBank::new(Default::default())),
+            #[cfg(feature = "modbus")]
+            plc_server: None,
+            param_dir: paths::params_dir(),
+            result_logger: Arc::new(Mutex::new(None)),
+            unit_log: Arc::new(Mutex::new(UnitLogState::default())),
+            fast_check_pipeline: Arc::new(Mutex::new(None)),
+            frame_resolution: FrameResolution::default(),
+            pattern_size: core::Size::new(DEFAULT_PATTERN_SIZE.0, DEFAULT_PATTERN_SIZE.1),
+        }
+    }
+
+    /// 设置原始帧分辨率（相机配置的图像/ROI尺寸），须在启动工作流前设置
+    pub fn set_frame_resolution(&mut self, resolution: FrameResolution) -> Result<(), String> {
+        if resolution.width == 0 || resolution.height == 0 {
+            return Err(format!("帧分辨率无效: {}×{}", resolution.width, resolution.height));
+        }
+        if self.running.load(Ordering::SeqCst) {
+            return Err("工作流运行中，不能更改帧分辨率".to_string());
+        }
+        self.frame_resolution = resolution;
+        println!("📐 合像帧分辨率: {}×{}", resolution.width, resolution.height);
+        Ok(())
+    }
+
+    pub fn frame_resolution(&self) -> FrameResolution {
+        self.frame_resolution
+    }
+
+    /// 设置合像标定板规格 (每列点数, 列数)，须在初始化检测系统前设置
+    pub fn set_pattern_size(&mut self, pattern_size: (i32, i32)) -> Result<(), String> {
+        if pattern_size.0 < 2 || pattern_size.1 < 2 {
+            return Err(format!("标定板规格无效: {}×{}", pattern_size.0, pattern_size.1));
+        }
+        if self.alignment_system.lock().unwrap().is_some() {
+            return Err("检测系统已初始化，不能更改标定板规格".to_string());
+        }
+        self.pattern_size = core::Size::new(pattern_size.0, pattern_size.1);
+        println!("🎯 合像标定板规格: {}×{}", pattern_size.0, pattern_size.1);
+        Ok(())
+    }
+
+    pub fn pattern_size(&self) -> (i32, i32) {
+        (self.pattern_size.width, self.pattern_size.height)
+    }
+
+    /// 设置标定参数目录，下次 initialize_alignment_system 起生效
+    pub fn set_param_dir(&mut self, param_dir: impl Into<std::path::PathBuf>) {
+        self.param_dir = param_dir.into();
+        println!("📁 标定参数目录: {}", self.param_dir.display());
+    }
+
+    pub fn param_dir(&self) -> &std::path::Path {
+        &self.param_dir
+    }
+
+    /// 标定参数目录下的文件路径
+    pub fn param_path(&self, file_name: &str) -> String {
+        param_file_path(&self.param_dir, file_name)
+    }
+
+    /// 重映射矩阵路径（所有 detect_circles_grid 调用使用）
+    pub fn rectify_maps_path(&self) -> String {
+        self.param_path(RECTIFY_MAPS_FILE)
+    }
+
+    /// 创建实机长时操作句柄，供命令释放工作流状态锁后执行
+    pub fn live_session(&self) -> LiveSession {
+        LiveSession {
+            camera_manager: self.camera_manager.clone(),
+            alignment_system: self.alignment_system.clone(),
+            frame_buffer: self.frame_buffer.clone(),
+            running: self.running.clone(),
+            frame_interval_us: self.frame_interval_us.clone(),
+            rectify_maps_path: self.rectify_maps_path(),
+        }
+    }
+
+    /// 初始化合像检测系统（加载参数）
+    pub fn initialize_alignment_system(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        println!("=== 初始化合像检测系统 ===");
+        
+        // 更新状态
+        *self.stage.lock().unwrap() = DetectionStage::Loading;
+        self.emit_stage_update()?;
+
+        // 加载标定参数（图像尺寸与采集帧分辨率一致）
+        let image_size = core::Size::new(self.frame_resolution.width as i32, self.frame_resolution.height as i32);
+        
+        // 参数文件从 param_dir 解析（默认数据目录，见 paths.rs），标定板规格按配置
+        let mut alignment_sys = AlignmentSystem::with_pattern(
+            image_size,
+            &self.param_path("left_camera_params.yaml"),
+            &self.param_path("right_camera_params.yaml"),
+            &self.param_path("stereo_params.yaml"),
+            &self.param_path("rectify_params.yaml"),
+            self.pattern_size,
+            DEFAULT_CENTER_DISTANCE_MM,
+        )?;
+
+        alignment_sys.set_debug_render_config(self.debug_render_config.lock().unwrap().clone());
+        alignment_sys.set_mask_overlay_enabled(self.debug_overlay.lock().unwrap().config.enabled);
+        *self.alignment_system.lock().unwrap() = Some(alignment_sys);
+        
+        println!("✓ 合像检测系统初始化完成");
+        *self.stage.lock().unwrap() = DetectionStage::Idle;
+        self.emit_stage_update()?;
+        
+        Ok(())
+    }
+
+    /// 启动工作流程（双线程模式 - SimpleCameraManager版本）
+    pub fn start_workflow(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        println!("🚀 启动合像检测工作流程...");
+        
+        if self.running.load(Ordering::SeqCst) {
+            return Err("工作流程已经在运行中".into());
+        }
+        
+        // [配置系统 - 已注释] 设置相机为合像检测模式
+        // unsafe {
+        //     crate::camera_ffi::set_camera_mode(2); // 2 = alignment mode
+        // }
+        // println!("📷 已设置相机为合像检测模式");
+        
+        // 创建SimpleCameraManager并启动
+        {
+            let mut cam = self.camera_manager.lock()
+                .map_err(|e| format!("获取相机管理器失败: {}", e))?;
+            cam.start()
+                .map_err(|e| format!("启动相机失败: {:?}", e))?;
+        }
+
+        // 初始化系统（如果还没有）
+        if self.alignment_system.lock().unwrap().is_none() {
+            self.initialize_alignment_system()?;
+        }
+
+        // 优化OpenCV性能配置
+        self.configure_opencv_performance()?;
+
+        // 丢弃上次会话残留的帧与预览，避免重启后界面显示旧画面
+        self.frame_buffer.lock().unwrap().clear();
+        self.preview_cache.lock().unwrap().clear();
+        
+        self.running.store(true, Ordering::SeqCst);
+        
+        // 创建命令通道
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        self.command_sender = Some(cmd_tx);
+
+        // 启动采集线程
+        self.start_acquisition_thread()?;
+        
+        // 启动处理线程
+        self.start_processing_thread(cmd_rx)?;
+
+        // 启动预览模式
+        self.send_command(WorkflowCommand::StartPreview)?;
+
+        println!("✓ 工作流程启动完成");
+        Ok(())
+    }
+
+    /// 配置OpenCV性能优化
+    fn configure_opencv_performance(&self) -> Result<(), Box<dyn std::error::Error>> {
+        // 设置OpenCV线程数为CPU核心数的一半，避免过度并行
+        let cpu_cores = num_cpus::get();
+        let opencv_threads = (cpu_cores / 2).max(1).min(4); // 限制在1-4之间
+        
+        #[cfg(feature = "opencv")]
+        {
+            opencv::core::set_num_threads(opencv_threads as i32)
+                .map_err(|e| format!("设置OpenCV线程数失败: {}", e))?;
+            println!("🔧 OpenCV线程数设置为: {} (CPU核心: {})", opencv_threads, cpu_cores);
+        }
+
+        // 启用OpenCV优化
+        #[cfg(feature = "opencv")]
+        {
+            opencv::core::set_use_optimized(true)
+                .map_err(|e| format!("启用OpenCV优化失败: {}", e))?;
+            println!("🚀 OpenCV优化已启用");
+        }
+
+        Ok(())
+    }
+
+    /// 启动采集线程 (SimpleCameraManager版本)
+    fn start_acquisition_thread(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let camera_manager = Arc::clone(&self.camera_manager);
+        let frame_buffer = Arc::clone(&self.frame_buffer);
+        let running = Arc::clone(&self.running);
+        let frame_interval_us = Arc::clone(&self.frame_interval_us);
+        let achieved_fps = Arc::clone(&self.achieved_fps);
+        let resolution = self.frame_resolution;
+        let warming_up = Arc::clone(&self.warming_up);
+        let app_handle = self.app_handle.clone();
+        
+        // 丢弃启动后曝光未稳定的帧：由采集线程执行，预热完成后才推帧进入预览/检测；
+        // start_workflow 不等待预热，命令层释放状态锁后即可查询到 WarmingUp
+        warming_up.store(true, Ordering::SeqCst);
+
+        let handle = thread::spawn(move || {
+            println!("📷 采集线程启动 (SimpleCameraManager版本)");
+            
+            // 相机预热
+            let warmup = camera_manager.lock().unwrap().warm_up(&mut |discarded, total| {
+                let _ = app_handle.emit("alignment-warmup", WarmupProgress { discarded, total, done: false, error: None });
+            });
+            warming_up.store(false, Ordering::SeqCst);
+            match warmup {
+                Ok(discarded) => {
+                    let _ = app_handle.emit("alignment-warmup", WarmupProgress { discarded, total: discarded, done: true, error: None });
+                }
+                Err(e) => {
+                    let error = format!("相机预热失败: {:?}", e);
+                    eprintln!("❌ {}", error);
+                    let _ = app_handle.emit("alignment-warmup", WarmupProgress { discarded: 0, total: 0, done: true, error: Some(error) });
+                    // 预热失败不进入采集，停止工作流线程
+                    running.store(false, Ordering::SeqCst);
+                }
+            }
+            
+            // 相机已经在 start_workflow() 中启动，这里不需要重复启动
+            // 移除重复的启动代码：
+            // if let Err(e) = camera_manager.lock().unwrap().start() {
+            //     eprintln!("相机启动失败: {:?}", e);
+            //     return;
+            // }
+
+            let mut frame_count = 0u64;
+            let mut last_stats_time = Instant::now();
+
+            let mut last_capture_time = Instant::now();
+
+            // 实际帧率统计窗口
+            let mut fps_window_start = Instant::now();
+            let mut fps_window_frames = 0u64;
+
+            while running.load(Ordering::SeqCst) {
+                let now = Instant::now();
+                // 每轮读取目标帧间隔，支持运行时调整
+                let frame_interval = Duration::from_micros(frame_interval_us.load(Ordering::Relaxed));
                 
                 // 控制帧率
                 if now.duration_since(last_capture_time) >= frame_interval {
@@ -316,14 +2030,24 @@ impl AlignmentWorkflow {
                             let frame = FrameData {
                                 left_image: left_data,
                                 right_image: right_data,
+                                resolution,
                                 timestamp: now,
                             };
 
                             // 推入环形缓冲区
                             frame_buffer.lock().unwrap().push(frame);
                             frame_count += 1;
+                            fps_window_frames += 1;
                             last_capture_time = now;
                         }
+                        Err(CameraError::Reconnecting { attempt, .. }) => {
+                            // 相机链路中断后已自动重连，线程保持运行，等待新帧
+                            println!("🔌 相机重连中 (第{}次)，采集线程继续等待", attempt);
+                            if !running.load(Ordering::SeqCst) {
+                                break;
+                            }
+                            thread::sleep(Duration::from_millis(200));
+                        }
                         Err(e) => {
                             eprintln!("采集帧失败: {:?}", e);
                             // 检查是否需要停止
@@ -335,10 +2059,21 @@ impl AlignmentWorkflow {
                     }
                 }
 
+                // 实际帧率（每秒更新一次）
+                let fps_window = now.duration_since(fps_window_start);
+                if fps_window >= Duration::from_secs(1) {
+                    let fps = fps_window_frames as f64 / fps_window.as_secs_f64();
+                    achieved_fps.store(fps.to_bits(), Ordering::Relaxed);
+                    fps_window_start = now;
+                    fps_window_frames = 0;
+                }
+
                 // 统计信息（每5秒输出一次）
                 if now.duration_since(last_stats_time) >= Duration::from_secs(5) {
-                    println!("📊 采集统计: {}帧, 缓冲区: {}帧", 
-                             frame_count, frame_buffer.lock().unwrap().len());
+                    println!("📊 采集统计: {}帧, 缓冲区: {}帧, 实际帧率: {:.1}fps (目标 {:.1}fps)", 
+                             frame_count, frame_buffer.lock().unwrap().len(),
+                             f64::from_bits(achieved_fps.load(Ordering::Relaxed)),
+                             interval_us_to_fps(frame_interval_us.load(Ordering::Relaxed)));
                     last_stats_time = now;
                 }
 
@@ -369,6 +2104,19 @@ impl AlignmentWorkflow {
         let alignment_system = Arc::clone(&self.alignment_system);
         let running = Arc::clone(&self.running);
         let app_handle = self.app_handle.clone();
+        let preview_transport = Arc::clone(&self.preview_transport);
+        let latency_tracker = Arc::clone(&self.latency_tracker);
+        let camera_manager = Arc::clone(&self.camera_manager);
+        let frame_interval_us = Arc::clone(&self.frame_interval_us);
+        let retry_config = Arc::clone(&self.retry_config);
+        let detection_paused = Arc::clone(&self.detection_paused);
+        let plc_registers = Arc::clone(&self.plc_registers);
+        let rectify_maps_path = self.rectify_maps_path();
+        let result_log = self.result_log_target();
+        let debug_overlay = Arc::clone(&self.debug_overlay);
+        let pose_filter = Arc::clone(&self.pose_filter);
+        let frame_decimator = Arc::clone(&self.frame_decimator);
+        let latest_results = Arc::clone(&self.latest_results);
 
         let handle = thread::spawn(move || {
             println!("🔄 处理线程启动");
@@ -376,12 +2124,19 @@ impl AlignmentWorkflow {
             while running.load(Ordering::SeqCst) {
                 // 处理命令
                 if let Ok(cmd) = cmd_rx.try_recv() {
+                    // 阶段切换后不沿用之前累积的姿态解
+                    if let Some(sys) = alignment_system.lock().unwrap().as_ref() {
+                        sys.reset_pose_history();
+                    }
+                    pose_filter.lock().unwrap().reset();
+                    frame_decimator.lock().unwrap().restart();
                     match cmd {
                         WorkflowCommand::StartPreview => {
                             *stage.lock().unwrap() = DetectionStage::Preview;
                             let _ = app_handle.emit("alignment-stage", DetectionStage::Preview);
                         }
                         WorkflowCommand::StartDetection => {
+                            *latest_results.lock().unwrap() = LatestStageResults::default();
                             *stage.lock().unwrap() = DetectionStage::LeftEyePoseCheck;
                             let _ = app_handle.emit("alignment-stage", DetectionStage::LeftEyePoseCheck);
                         }
@@ -390,6 +2145,7 @@ impl AlignmentWorkflow {
                             Self::handle_stage_transition(&stage, &app_handle);
                         }
                         WorkflowCommand::Reset => {
+                            *latest_results.lock().unwrap() = LatestStageResults::default();
                             *stage.lock().unwrap() = DetectionStage::Preview;
                             let _ = app_handle.emit("alignment-stage", DetectionStage::Preview);
                         }
@@ -405,7 +2161,13 @@ impl AlignmentWorkflow {
                 match current_stage {
                     DetectionStage::Preview => {
                         // 预览模式：定期发送预览图像
-                        Self::handle_preview_mode(&frame_buffer, &app_handle);
+                        Self::handle_preview_mode(&frame_buffer, &app_handle, &preview_transport);
+                    }
+                    DetectionStage::LeftEyePoseCheck |
+                    DetectionStage::RightEyePoseCheck |
+                    DetectionStage::DualEyeAlignment if detection_paused.load(Ordering::SeqCst) => {
+                        // 暂停检测：保持当前阶段，仅发送预览
+                        Self::handle_preview_mode(&frame_buffer, &app_handle, &preview_transport);
                     }
                     DetectionStage::LeftEyePoseCheck |
                     DetectionStage::RightEyePoseCheck |
@@ -415,7 +2177,20 @@ impl AlignmentWorkflow {
                             &frame_buffer,
                             &alignment_system,
                             &current_stage,
+                            &rectify_maps_path,
                             &app_handle,
+                            &latency_tracker,
+                            &DetectionRetryContext {
+                                camera_manager: &camera_manager,
+                                frame_interval_us: &frame_interval_us,
+                                config: *retry_config.lock().unwrap(),
+                            },
+                            &plc_registers,
+                            &result_log,
+                            &debug_overlay,
+                            &pose_filter,
+                            &frame_decimator,
+                            &latest_results,
                         );
                     }
                     _ => {}
@@ -434,21 +2209,43 @@ impl AlignmentWorkflow {
     /// 处理预览模式 (原始数据版本)
     fn handle_preview_mode(
         frame_buffer: &Arc<Mutex<RingBuffer<FrameData>>>,
-        app_handle: &AppHandle,
+        app_handle: &WorkflowEmitter,
+        preview_transport: &Arc<Mutex<PreviewTransport>>,
     ) {
-        if let Some(frame) = frame_buffer.lock().unwrap().latest() {
-            // 每200ms发送一次预览图像（5fps预览）
-            // 注意：这里发送原始数据，前端需要相应处理
-            let preview_data = serde_json::json!({
-                "left_preview_size": frame.left_image.len(),
-                "right_preview_size": frame.right_image.len(),
-                "timestamp": frame.timestamp.elapsed().as_millis(),
-                "width": 2448,
-                "height": 2048,
-                "format": "grayscale"
-            });
-            
-            let _ = app_handle.emit("alignment-preview", preview_data);
+        let frame = {
+            let buffer = frame_buffer.lock().unwrap();
+            buffer.latest().cloned()
+        };
+
+        if let Some(frame) = frame {
+            let transport = *preview_transport.lock().unwrap();
+            match transport {
+                PreviewTransport::RawBuffer => {
+                    // 原始像素模式：写入缓冲文件，仅发送轻量通知
+                    match write_raw_preview_buffers(&frame) {
+                        Ok(notice) => {
+                            let _ = app_handle.emit("alignment-preview-raw", notice);
+                        }
+                        Err(e) => {
+                            eprintln!("写入原始预览缓冲失败: {}", e);
+                        }
+                    }
+                }
+                PreviewTransport::Base64 => {
+                    // 每200ms发送一次预览图像（5fps预览）
+                    // 注意：这里发送原始数据，前端需要相应处理
+                    let preview_data = serde_json::json!({
+                        "left_preview_size": frame.left_image.len(),
+                        "right_preview_size": frame.right_image.len(),
+                        "timestamp": frame.timestamp.elapsed().as_millis(),
+                        "width": frame.resolution.width,
+                        "height": frame.resolution.height,
+                        "format": "grayscale"
+                    });
+                    
+                    let _ = app_handle.emit("alignment-preview", preview_data);
+                }
+            }
         }
         
         thread::sleep(Duration::from_millis(200));
@@ -459,95 +2256,269 @@ impl AlignmentWorkflow {
         frame_buffer: &Arc<Mutex<RingBuffer<FrameData>>>,
         alignment_system: &Arc<Mutex<Option<AlignmentSystem>>>,
         stage: &DetectionStage,
-        app_handle: &AppHandle,
+        rectify_maps_path: &str,
+        app_handle: &WorkflowEmitter,
+        latency_tracker: &Arc<Mutex<LatencyTracker>>,
+        retry: &DetectionRetryContext,
+        plc_registers: &PlcRegisterBank,
+        result_log: &ResultLogTarget,
+        debug_overlay: &Mutex<DebugOverlayState>,
+        pose_filter: &Mutex<PoseKalmanFilter>,
+        frame_decimator: &Mutex<FrameDecimator>,
+        latest_results: &Mutex<LatestStageResults>,
     ) {
         let start_time = Instant::now();
         
-        let frame = {
+        let (frame, seq) = {
             let buffer = frame_buffer.lock().unwrap();
-            buffer.latest().cloned()
+            (buffer.latest().cloned(), buffer.total_pushed())
         };
+        
+        // 按抽帧间隔处理新帧，避免负载高时处理线程积压
+        if frame.is_none() || !frame_decimator.lock().unwrap().should_process(seq, start_time) {
+            return;
+        }
 
-        if let Some(frame_data) = frame {
+        if let Some(mut frame_data) = frame {
+            let first_outcome = match alignment_system.lock().unwrap().as_mut() {
+                Some(sys) => Self::process_detection_frame(sys, &frame_data, stage, rectify_maps_path, latest_results),
+                None => return,
+            };
+            let mut outcome = first_outcome;
+            // 重试等待新曝光帧期间不持有检测系统锁
+            if let Err(e) = &outcome {
+                if retry.config.enabled {
+                    println!("🔁 检测失败 ({}), 降低曝光重试...", e);
+                    match Self::retry_with_lower_exposure(alignment_system, frame_buffer, stage, rectify_maps_path, retry, latest_results) {
+                        Ok((retry_frame, retry_outcome)) => {
+                            frame_data = retry_frame;
+                            outcome = retry_outcome;
+                        }
+                        Err(retry_err) => println!("⚠️ 降低曝光重试未执行: {}", retry_err),
+                    }
+                }
+            }
             let mut alignment_sys = alignment_system.lock().unwrap();
             if let Some(ref mut sys) = *alignment_sys {
-                match Self::process_detection_frame(sys, &frame_data, stage) {
+                Self::emit_debug_overlay(sys, stage, app_handle, debug_overlay, outcome.is_ok());
+                match outcome {
                     Ok(result) => {
                         let processing_time = start_time.elapsed();
                         println!("🔍 检测处理耗时: {:.1}ms", processing_time.as_millis());
                         
-                        let _ = app_handle.emit("alignment-result", result);
+                        if *stage == DetectionStage::DualEyeAlignment {
+                            Self::publish_plc_registers(plc_registers, Some(&result));
+                            if let Some(alignment) = latest_results.lock().unwrap().alignment.clone() {
+                                result_log.update_pending(alignment);
+                            }
+                        }
+                        Self::emit_filtered_pose(&result, stage, app_handle, pose_filter);
+                        let _ = app_handle.emit("alignment-result", LabeledDetectionResult::new(&result));
+                        
+                        // 端到端延迟：帧采集时间戳 → 结果发送
+                        let total_latency = frame_data.timestamp.elapsed();
+                        let queue_latency = start_time.saturating_duration_since(frame_data.timestamp);
+                        latency_tracker.lock().unwrap().record(total_latency, queue_latency);
+                        println!("⏱️  端到端延迟: {:.1}ms (排队 {:.1}ms)", 
+                                 total_latency.as_secs_f64() * 1000.0, queue_latency.as_secs_f64() * 1000.0);
                     }
                     Err(e) => {
+                        if *stage == DetectionStage::DualEyeAlignment {
+                            Self::publish_plc_registers(plc_registers, None);
+                        }
                         let error_result = DetectionResult::Error {
                             message: format!("检测处理失败: {}", e),
                         };
-                        let _ = app_handle.emit("alignment-result", error_result);
+                        let _ = app_handle.emit("alignment-result", LabeledDetectionResult::new(&error_result));
                     }
                 }
             }
         }
+    }
+
+    /// 姿态显示滤波：开启时对单眼姿态结果滤波并发送，原始结果不变
+    fn emit_filtered_pose(
+        result: &DetectionResult,
+        stage: &DetectionStage,
+        app_handle: &WorkflowEmitter,
+        pose_filter: &Mutex<PoseKalmanFilter>,
+    ) {
+        let (eye, raw) = match result {
+            DetectionResult::LeftEyePose { roll, pitch, yaw, .. } => (0, PoseAngles { roll: *roll, pitch: *pitch, yaw: *yaw }),
+            DetectionResult::RightEyePose { roll, pitch, yaw, .. } => (1, PoseAngles { roll: *roll, pitch: *pitch, yaw: *yaw }),
+            _ => return,
+        };
+        let mut filter = pose_filter.lock().unwrap();
+        if !filter.config().enabled {
+            return;
+        }
+        let filtered = filter.update(eye, raw);
+        let _ = app_handle.emit("alignment-pose-filtered", FilteredPoseFrame { stage: stage.clone(), raw, filtered });
+    }
+
+    /// 调试叠加流：未开启或距上次发送不足间隔时直接返回，否则发送左右眼叠加图
+    fn emit_debug_overlay(
+        sys: &AlignmentSystem,
+        stage: &DetectionStage,
+        app_handle: &WorkflowEmitter,
+        debug_overlay: &Mutex<DebugOverlayState>,
+        detected: bool,
+    ) {
+        use base64::{Engine as _, engine::general_purpose};
+        
+        let config = {
+            let mut state = debug_overlay.lock().unwrap();
+            let interval = Duration::from_millis(state.config.interval_ms);
+            if !state.config.enabled || state.last_sent.map_or(false, |sent| sent.elapsed() < interval) {
+                return;
+            }
+            state.last_sent = Some(Instant::now());
+            state.config
+        };
+        
+        let encode = |is_left: bool| -> Option<String> {
+            let image = match sys.render_mask_overlay(is_left, config.scale) {
+                Ok(image) => image?,
+                Err(e) => {
+                    eprintln!("⚠️ 调试叠加图生成失败: {}", e);
+                    return None;
+                }
+            };
+            let mut buffer = core::Vector::<u8>::new();
+            let params = core::Vector::<i32>::from_slice(&[imgcodecs::IMWRITE_JPEG_QUALITY, 80]);
+            imgcodecs::imencode(".jpg", &image, &mut buffer, &params).ok()?;
+            Some(format!("data:image/jpeg;base64,{}", general_purpose::STANDARD.encode(buffer.as_slice())))
+        };
+        let frame = DebugOverlayFrame {
+            stage: stage.clone(),
+            detected,
+            left_image: encode(true),
+            right_image: encode(false),
+        };
+        let _ = app_handle.emit("alignment-debug-overlay", frame);
+    }
+
+    /// 合像阶段的检测结果写入PLC寄存器，非合像结果（无投影/检测失败）清除通过位
+    fn publish_plc_registers(plc_registers: &PlcRegisterBank, result: Option<&DetectionResult>) {
+        match result.and_then(detection_result_to_alignment) {
+            Some(alignment) => plc_registers.publish_alignment(&alignment),
+            None => plc_registers.invalidate(),
+        }
+    }
+
+    /// 降低曝光一档，等待新曝光下的帧重试一次检测，结束后恢复原曝光
+    /// 
+    /// 外层 Err 表示重试本身无法执行（读写曝光失败/等待新帧超时/检测系统已释放）；
+    /// 只在检测新帧时锁定检测系统，等待期间状态查询等命令不被阻塞
+    fn retry_with_lower_exposure(
+        alignment_system: &Mutex<Option<AlignmentSystem>>,
+        frame_buffer: &Arc<Mutex<RingBuffer<FrameData>>>,
+        stage: &DetectionStage,
+        rectify_maps_path: &str,
+        retry: &DetectionRetryContext,
+        latest_results: &Mutex<LatestStageResults>,
+    ) -> Result<(FrameData, Result<DetectionResult, Box<dyn std::error::Error>>), Box<dyn std::error::Error>> {
+        let original_us = retry.camera_manager.lock().unwrap().get_exposure_time()? as f64;
+        let lowered_us = (original_us - retry.config.exposure_step_us).max(retry.config.min_exposure_us);
+        if lowered_us >= original_us {
+            return Err(format!("曝光已达下限 {:.0} μs", retry.config.min_exposure_us).into());
+        }
 
-        // 检测模式下降低处理频率，避免CPU过载
-        thread::sleep(Duration::from_millis(200));
+        retry.camera_manager.lock().unwrap().set_exposure_time(lowered_us as f32)?;
+        let frame_interval = Duration::from_micros(retry.frame_interval_us.load(Ordering::Relaxed));
+        let settle_until = Instant::now() + Duration::from_micros(lowered_us as u64) + frame_interval * 2;
+        let frame = Self::wait_for_fresh_frame(frame_buffer, settle_until, Duration::from_secs(2));
+        let restored = retry.camera_manager.lock().unwrap().set_exposure_time(original_us as f32);
+
+        let frame = frame?;
+        let mut alignment_sys = alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        let outcome = Self::process_detection_frame(alignment_sys, &frame, stage, rectify_maps_path, latest_results);
+        match &outcome {
+            Ok(_) => println!("✅ 曝光 {:.0} → {:.0} μs 重试检测成功", original_us, lowered_us),
+            Err(e) => println!("❌ 曝光 {:.0} μs 重试仍失败: {}", lowered_us, e),
+        }
+        if let Err(e) = restored {
+            eprintln!("⚠️ 恢复曝光 {:.0} μs 失败: {}", original_us, e);
+        }
+        Ok((frame, outcome))
     }
 
-    /// 处理检测帧（优化版）
+    /// 处理检测帧（优化版），成功时同时更新 latest_results 中对应阶段的结果
     fn process_detection_frame(
         alignment_sys: &mut AlignmentSystem,
         frame_data: &FrameData,
         stage: &DetectionStage,
+        rectify_maps_path: &str,
+        latest_results: &Mutex<LatestStageResults>,
     ) -> Result<DetectionResult, Box<dyn std::error::Error>> {
         // 将原始数据转换为OpenCV Mat
-        let left_image = Self::raw_data_to_mat(&frame_data.left_image, 2448, 2048)?;
-        let right_image = Self::raw_data_to_mat(&frame_data.right_image, 2448, 2048)?;
+        let left_image = Self::raw_data_to_mat(&frame_data.left_image, frame_data.resolution)?;
+        let right_image = Self::raw_data_to_mat(&frame_data.right_image, frame_data.resolution)?;
+
+        // 无投影信号时直接返回，不进行圆点检测（单眼阶段只检查当前眼）
+        let check_left = !matches!(stage, DetectionStage::RightEyePoseCheck);
+        let check_right = !matches!(stage, DetectionStage::LeftEyePoseCheck);
+        if let Some(result) = Self::check_no_projection(alignment_sys, &left_image, &right_image, check_left, check_right)? {
+            return Ok(result);
+        }
 
         // 根据检测阶段优化处理策略
         match stage {
             DetectionStage::LeftEyePoseCheck => {
-                // 只检测左眼圆心，提高效率
-                let (corners_left, _) = alignment_sys.detect_circles_grid(
+                // 只检测左眼圆心，右眼光机关闭时不影响
+                let corners_left = alignment_sys.detect_left_circles_only(
                     &left_image,
-                    &right_image, // 仍需传入，但内部可以优化只处理左眼
-                    "yaml_last_param_file/rectify_maps.yaml", // 🔧 修正路径
+                    rectify_maps_path,
                 )?;
                 
                 // 使用向后兼容的左眼姿态检测方法
+                let pose_start = Instant::now();
                 let result = alignment_sys.check_left_eye_pose(&corners_left)?;
+                let mut timings = alignment_sys.get_last_stage_timings();
+                timings.pose_ms = pose_start.elapsed().as_secs_f64() * 1000.0;
+                // 居中检查为纯计算，随左眼阶段一并缓存（期望位置无效时不缓存）
+                let centering = alignment_sys.check_left_eye_centering(&corners_left, None).ok();
+                {
+                    let mut latest = latest_results.lock().unwrap();
+                    latest.left_pose = Some(result.clone());
+                    latest.left_centering = centering;
+                }
                 Ok(DetectionResult::LeftEyePose {
                     roll: result.roll,
                     pitch: result.pitch,
                     yaw: result.yaw,
                     pass: result.pass,
-                    message: if result.pass {
-                        "✓ 左眼姿态检测通过".to_string()
-                    } else {
-                        format!("❌ 左眼姿态超出容差 - roll={:.3}°, pitch={:.3}°, yaw={:.3}°", 
-                               result.roll, result.pitch, result.yaw)
-                    },
+                    message: result.message("左眼"),
+                    spread: result.spread,
+                    standoff: result.standoff,
+                    reprojection_rms: result.reprojection_rms,
+                    timings,
                 })
             }
             DetectionStage::RightEyePoseCheck => {
-                // 只检测右眼圆心
-                let (_, corners_right) = alignment_sys.detect_circles_grid(
-                    &left_image,
+                // 只检测右眼圆心，左眼光机关闭时不影响
+                let corners_right = alignment_sys.detect_right_circles_only(
                     &right_image,
-                    "yaml_last_param_file/rectify_maps.yaml", // 🔧 修正路径
+                    rectify_maps_path,
                 )?;
                 
                 // 使用向后兼容的右眼姿态检测方法
+                let pose_start = Instant::now();
                 let result = alignment_sys.check_right_eye_pose(&corners_right)?;
+                let mut timings = alignment_sys.get_last_stage_timings();
+                timings.pose_ms = pose_start.elapsed().as_secs_f64() * 1000.0;
+                latest_results.lock().unwrap().right_pose = Some(result.clone());
                 Ok(DetectionResult::RightEyePose {
                     roll: result.roll,
                     pitch: result.pitch,
                     yaw: result.yaw,
                     pass: result.pass,
-                    message: if result.pass {
-                        "✓ 右眼姿态检测通过".to_string()
-                    } else {
-                        format!("❌ 右眼姿态超出容差 - roll={:.3}°, pitch={:.3}°, yaw={:.3}°", 
-                               result.roll, result.pitch, result.yaw)
-                    },
+                    message: result.message("右眼"),
+                    spread: result.spread,
+                    standoff: result.standoff,
+                    reprojection_rms: result.reprojection_rms,
+                    timings,
                 })
             }
             DetectionStage::DualEyeAlignment => {
@@ -555,147 +2526,640 @@ impl AlignmentWorkflow {
                 let (corners_left, corners_right) = alignment_sys.detect_circles_grid(
                     &left_image,
                     &right_image,
-                    "yaml_last_param_file/rectify_maps.yaml", // 🔧 修正路径
+                    rectify_maps_path,
                 )?;
                 
+                let alignment_start = Instant::now();
                 let result = alignment_sys.check_dual_eye_alignment(&corners_left, &corners_right, true)?;
-                let adjustment_hint = format!(
-                    "调整提示: Δx={:.3}px {}, Δy={:.3}px {}",
-                    result.mean_dx,
-                    if result.mean_dx > 0.0 { "(右眼向左调)" } else { "(右眼向右调)" },
-                    result.mean_dy,
-                    if result.mean_dy < 0.0 { "(右眼向上调)" } else { "(右眼向下调)" }
-                );
-
-                Ok(DetectionResult::DualEyeAlignment {
-                    mean_dx: result.mean_dx,
-                    mean_dy: result.mean_dy,
-                    rms: result.rms,
-                    p95: result.p95,
-                    max_err: result.max_err,
-                    pass: result.pass,
-                    adjustment_hint,
-                })
+                let mut timings = alignment_sys.get_last_stage_timings();
+                timings.alignment_ms = alignment_start.elapsed().as_secs_f64() * 1000.0;
+                let detection = alignment_to_detection_result(&result, timings);
+                latest_results.lock().unwrap().alignment = Some(result);
+
+                Ok(detection)
             }
             _ => Err("不支持的检测阶段".into()),
         }
     }
 
-    /// 将原始数据转换为OpenCV Mat
-    fn raw_data_to_mat(data: &[u8], width: i32, height: i32) -> Result<core::Mat, opencv::Error> {
+    /// 检测前的无投影检查，需要检测的眼为全黑帧时返回 NoProjection 结果
+    /// 
+    /// 单眼姿态阶段只检查当前眼，另一眼光机关闭不视为异常
+    fn check_no_projection(
+        alignment_sys: &AlignmentSystem,
+        left_image: &core::Mat,
+        right_image: &core::Mat,
+        check_left: bool,
+        check_right: bool,
+    ) -> Result<Option<DetectionResult>, Box<dyn std::error::Error>> {
+        let (left, right, left_blank, right_blank) = alignment_sys.check_projection_signal(left_image, right_image)?;
+        let left_blank = left_blank && check_left;
+        let right_blank = right_blank && check_right;
+        if !left_blank && !right_blank {
+            return Ok(None);
+        }
+
+        let eyes = match (left_blank, right_blank) {
+            (true, true) => "左右眼",
+            (true, false) => "左眼",
+            _ => "右眼",
+        };
+        let message = format!(
+            "⚫ {}未检测到投影图像 — 请检查光机电源/遮光片 (左: 均值{:.1} 最大{:.0}, 右: 均值{:.1} 最大{:.0})",
+            eyes, left.mean, left.max, right.mean, right.max
+        );
+        println!("{}", message);
+
+        Ok(Some(DetectionResult::NoProjection {
+            left_blank,
+            right_blank,
+            left_mean: left.mean,
+            left_max: left.max,
+            right_mean: right.mean,
+            right_max: right.max,
+            message,
+        }))
+    }
+
+    /// 将原始数据按帧分辨率转换为OpenCV Mat，数据长度与分辨率不符时报错
+    pub fn raw_data_to_mat(data: &[u8], resolution: FrameResolution) -> Result<core::Mat, opencv::Error> {
+        resolution.check_frame(data)
+            .map_err(|message| opencv::Error::new(opencv::core::StsError, message))?;
+        
         // 创建空的Mat
         let mut mat = core::Mat::new_rows_cols_with_default(
-            height,
-            width,
+            resolution.height as i32,
+            resolution.width as i32,
             core::CV_8UC1,
             core::Scalar::default(),
         )?;
         
         // 将数据拷贝到Mat中
         let mat_data = mat.data_mut();
-        let expected_size = (width * height) as usize;
-        
-        if data.len() >= expected_size {
-            unsafe {
-                std::ptr::copy_nonoverlapping(
-                    data.as_ptr(),
-                    mat_data,
-                    expected_size,
-                );
-            }
-        } else {
-            return Err(opencv::Error::new(
-                opencv::core::StsError, 
-                format!("数据长度不足: 需要{}字节，实际{}字节", expected_size, data.len())
-            ));
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                data.as_ptr(),
+                mat_data,
+                data.len(),
+            );
         }
         
         Ok(mat)
     }
 
-    /// 处理阶段转换
-    fn handle_stage_transition(
-        stage: &Arc<Mutex<DetectionStage>>,
-        app_handle: &AppHandle,
-    ) {
-        let mut current_stage = stage.lock().unwrap();
-        let next_stage = match *current_stage {
-            DetectionStage::LeftEyePoseCheck => DetectionStage::RightEyePoseCheck,
-            DetectionStage::RightEyePoseCheck => DetectionStage::DualEyeAlignment,
-            DetectionStage::DualEyeAlignment => DetectionStage::Completed,
-            _ => return,
-        };
-
-        *current_stage = next_stage.clone();
-        let _ = app_handle.emit("alignment-stage", next_stage);
+    /// 处理阶段转换
+    fn handle_stage_transition(
+        stage: &Arc<Mutex<DetectionStage>>,
+        app_handle: &WorkflowEmitter,
+    ) {
+        let mut current_stage = stage.lock().unwrap();
+        let next_stage = match *current_stage {
+            DetectionStage::LeftEyePoseCheck => DetectionStage::RightEyePoseCheck,
+            DetectionStage::RightEyePoseCheck => DetectionStage::DualEyeAlignment,
+            DetectionStage::DualEyeAlignment => DetectionStage::Completed,
+            _ => return,
+        };
+
+        *current_stage = next_stage.clone();
+        let _ = app_handle.emit("alignment-stage", next_stage);
+    }
+
+    // ==================== 公共接口方法 ====================
+
+    /// 发送命令
+    pub fn send_command(&self, cmd: WorkflowCommand) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(ref sender) = self.command_sender {
+            sender.send(cmd)?;
+        }
+        Ok(())
+    }
+
+    /// 开始检测
+    pub fn start_detection(&self) -> Result<(), Box<dyn std::error::Error>> {
+        match self.init_state() {
+            WorkflowInitState::Running => self.send_command(WorkflowCommand::StartDetection),
+            state => Err(format!("工作流未运行，无法开始检测 (当前状态: {:?})", state).into()),
+        }
+    }
+
+    /// 下一阶段
+    pub fn next_stage(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.send_command(WorkflowCommand::NextStage)
+    }
+
+    /// 重置到预览模式
+    pub fn reset_to_preview(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.send_command(WorkflowCommand::Reset)
+    }
+
+    /// 停止工作流程
+    pub fn stop_workflow(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if !self.running.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        println!("=== 停止合像检测工作流程 ===");
+        
+        // 立即设置停止标志
+        self.running.store(false, Ordering::SeqCst);
+        
+        // 发送停止命令
+        if let Some(ref sender) = self.command_sender {
+            let _ = sender.send(WorkflowCommand::Stop);
+        }
+        
+        // 强制停止相机（如果线程没有及时响应）
+        if let Ok(camera_manager) = self.camera_manager.lock() {
+            let _ = camera_manager.stop();
+            println!("🛑 强制停止相机");
+        }
+        
+        // 等待线程结束（设置超时）
+        if let Some(handle) = self.acquisition_thread.take() {
+            println!("⏳ 等待采集线程结束...");
+            match handle.join() {
+                Ok(_) => println!("✓ 采集线程已结束"),
+                Err(e) => println!("⚠️ 采集线程结束异常: {:?}", e),
+            }
+        }
+        
+        if let Some(handle) = self.processing_thread.take() {
+            println!("⏳ 等待处理线程结束...");
+            match handle.join() {
+                Ok(_) => println!("✓ 处理线程已结束"),
+                Err(e) => println!("⚠️ 处理线程结束异常: {:?}", e),
+            }
+        }
+
+        // 处理线程已结束：当前产品最后的合像结果不会再更新，写入日志避免停止时丢失
+        match self.result_log_target().finish_unit() {
+            Ok(Some(_)) => println!("📝 停止前已写入当前产品的合像结果"),
+            Ok(None) => {}
+            Err(e) => println!("⚠️ 停止时写入合像结果日志失败: {}", e),
+        }
+        
+        self.achieved_fps.store(0f64.to_bits(), Ordering::Relaxed);
+        // 停止后不再提供旧帧/旧预览（沿用缓存只用于运行中短暂无帧）
+        self.frame_buffer.lock().unwrap().clear();
+        self.preview_cache.lock().unwrap().clear();
+        
+        // 停止PLC输出，寄存器清零避免PLC读到过期结果
+        #[cfg(feature = "modbus")]
+        if let Some(server) = self.plc_server.take() {
+            server.abort();
+            println!("🔌 Modbus/TCP 服务已停止");
+        }
+        self.plc_registers.invalidate();
+        println!("✓ 工作流程已停止");
+        Ok(())
+    }
+
+    /// 按配置启用PLC输出（寄存器映射立即生效；Modbus/TCP 服务端需 modbus 特性）
+    pub fn start_plc_modbus(&mut self, config: &PlcModbusConfig) -> Result<(), Box<dyn std::error::Error>> {
+        config.validate()?;
+        self.plc_registers.set_map(config.registers)?;
+        if !config.enabled {
+            return Ok(());
+        }
+        #[cfg(feature = "modbus")]
+        {
+            if let Some(server) = self.plc_server.take() {
+                server.abort();
+            }
+            self.plc_server = Some(crate::modules::plc_modbus::server::spawn(&config.bind_address, Arc::clone(&self.plc_registers))?);
+        }
+        #[cfg(not(feature = "modbus"))]
+        println!("⚠️ 配置启用了 Modbus/TCP 输出，但当前构建未启用 modbus 特性，仅更新寄存器缓存");
+        Ok(())
+    }
+
+    /// 开启合像结果日志：每台产品结束时追加一条合像结果到 path（扩展名 .csv / .jsonl 决定格式）
+    pub fn enable_result_logging(&mut self, path: std::path::PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+        self.enable_result_logging_with_rotation(path, false)
+    }
+
+    /// 开启合像结果日志，rotate_daily 时按日期分文件
+    pub fn enable_result_logging_with_rotation(&mut self, path: std::path::PathBuf, rotate_daily: bool) -> Result<(), Box<dyn std::error::Error>> {
+        let logger = ResultLogger::new(path, rotate_daily)?;
+        println!("📝 合像结果日志已开启: {} ({:?})", logger.current_path().display(), logger.format());
+        *self.result_logger.lock().unwrap() = Some(logger);
+        Ok(())
+    }
+
+    /// 关闭合像结果日志
+    pub fn disable_result_logging(&mut self) {
+        if self.result_logger.lock().unwrap().take().is_some() {
+            println!("📝 合像结果日志已关闭");
+        }
+    }
+
+    /// 当前写入的日志文件（未开启时为 None）
+    pub fn result_log_path(&self) -> Option<std::path::PathBuf> {
+        self.result_logger.lock().unwrap().as_ref().map(|logger| logger.current_path())
+    }
+
+    /// 设置当前被测产品序列号
+    /// 
+    /// 序列号变化且上一台产品仍有未写入的合像结果时，先按上一台的序列号写入；
+    /// 停止工作流程时同样写入当前产品的最后结果
+    pub fn set_unit_serial(&self, serial: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let serial = serial.trim();
+        let target = self.result_log_target();
+        let changed = {
+            let unit = target.unit.lock().unwrap();
+            unit.serial != serial && unit.pending.is_some()
+        };
+        if changed {
+            target.finish_unit()?;
+        }
+        target.unit.lock().unwrap().serial = serial.to_string();
+        Ok(())
+    }
+
+    /// 结束当前产品：写入一条合像结果日志（日志开启时）并清空序列号
+    /// 
+    /// 返回写入的记录；本台产品尚无合像结果时返回 None
+    pub fn finish_unit(&self) -> Result<Option<AlignmentLogRecord>, Box<dyn std::error::Error>> {
+        Ok(self.result_log_target().finish_unit()?)
+    }
+
+    /// 当前被测产品序列号（未设置或已结束时为空）
+    pub fn unit_serial(&self) -> String {
+        self.unit_log.lock().unwrap().serial.clone()
+    }
+
+    fn result_log_target(&self) -> ResultLogTarget {
+        ResultLogTarget {
+            logger: Arc::clone(&self.result_logger),
+            unit: Arc::clone(&self.unit_log),
+        }
+    }
+
+    /// 设置预览传输方式
+    pub fn set_preview_transport(&self, transport: PreviewTransport) {
+        *self.preview_transport.lock().unwrap() = transport;
+        println!("🖼️ 预览传输方式切换为: {:?}", transport);
+    }
+
+    /// 获取预览传输方式
+    pub fn get_preview_transport(&self) -> PreviewTransport {
+        *self.preview_transport.lock().unwrap()
+    }
+
+    /// 设置采集目标帧率（运行中立即生效，下一轮采集循环读取）
+    pub fn set_target_fps(&self, fps: f64) -> Result<(), Box<dyn std::error::Error>> {
+        if !(fps > 0.0 && fps <= crate::config::MAX_ACQUISITION_TARGET_FPS) {
+            return Err(format!("采集帧率必须在0-{}fps范围内: {}", crate::config::MAX_ACQUISITION_TARGET_FPS, fps).into());
+        }
+        self.frame_interval_us.store(fps_to_interval_us(fps), Ordering::Relaxed);
+        println!("⏱️ 采集目标帧率设置为: {:.1}fps", fps);
+        Ok(())
+    }
+
+    /// 获取采集目标帧率
+    pub fn get_target_fps(&self) -> f64 {
+        interval_us_to_fps(self.frame_interval_us.load(Ordering::Relaxed))
+    }
+
+    /// 获取实际采集帧率（最近1秒窗口，未运行时为0）
+    pub fn get_achieved_fps(&self) -> f64 {
+        f64::from_bits(self.achieved_fps.load(Ordering::Relaxed))
+    }
+
+    /// 设置debug图像绘制样式
+    pub fn set_debug_render_config(&self, config: DebugRenderConfig) {
+        println!("🎨 debug图像样式更新: 底图 {:?}, 圆点半径 {}, 序号 {}",
+                 config.background, config.marker_radius, config.draw_indices);
+        if let Some(ref mut alignment_sys) = *self.alignment_system.lock().unwrap() {
+            alignment_sys.set_debug_render_config(config.clone());
+        }
+        *self.debug_render_config.lock().unwrap() = config;
+    }
+
+    /// 设置部分网格补全（遮挡1-2个圆点时插值补齐，默认关闭）
+    /// 
+    /// include_in_pose: 插值点是否参与姿态/合像计算（默认排除）
+    pub fn set_partial_grid_completion(&self, enabled: bool, max_missing: usize, include_in_pose: bool) -> Result<(), Box<dyn std::error::Error>> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        alignment_sys.get_circle_detector_mut().set_partial_grid_completion(enabled, max_missing);
+        alignment_sys.set_include_interpolated_points(include_in_pose);
+        Ok(())
+    }
+
+    /// 设置细化点足够时姿态解算是否排除未细化（仅连通域质心）的圆点
+    pub fn set_exclude_unrefined_points(&self, exclude: bool) -> Result<(), Box<dyn std::error::Error>> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        alignment_sys.set_exclude_unrefined_points(exclude);
+        println!("🎯 姿态解算排除未细化点: {}", if exclude { "启用" } else { "关闭" });
+        Ok(())
+    }
+
+    /// 设置黏连圆点检查（面积比例阈值，是否尝试拆分）
+    pub fn set_merged_blob_filter(&self, area_ratio: f64, split: bool) -> Result<(), Box<dyn std::error::Error>> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        alignment_sys.get_circle_detector_mut().set_merged_blob_filter(area_ratio, split)?;
+        Ok(())
+    }
+
+    /// 设置圆点检测前预处理（CLAHE/伽马）
+    pub fn set_detection_preprocessing(&self, preprocessing: DetectionPreprocessing) -> Result<(), Box<dyn std::error::Error>> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        alignment_sys.get_circle_detector_mut().set_preprocessing(preprocessing)?;
+        Ok(())
+    }
+
+    /// 热更新圆点检测参数（面积窗口/阈值/预处理等），下一帧生效
+    pub fn set_detector_config(&self, config: &DetectorConfig) -> Result<(), Box<dyn std::error::Error>> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        alignment_sys.get_circle_detector_mut().apply_config(config)?;
+        Ok(())
+    }
+
+    /// 设置检测排除区域（质心落入区域的连通域丢弃），下一帧生效
+    pub fn set_detection_exclusion_regions(&self, regions: Vec<ExclusionRegion>) -> Result<(), Box<dyn std::error::Error>> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        alignment_sys.get_circle_detector_mut().set_exclusion_regions(regions)?;
+        Ok(())
+    }
+
+    /// 当前生效的圆点检测参数（检测系统未初始化时为 None）
+    pub fn get_detector_config(&self) -> Option<DetectorConfig> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        alignment_sys.as_mut().map(|sys| sys.get_circle_detector_mut().config())
+    }
+
+    /// 设置无投影（全黑帧）判定阈值
+    pub fn set_blank_frame_config(&self, config: BlankFrameConfig) -> Result<(), Box<dyn std::error::Error>> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        println!("⚫ 无投影检查: 启用 {}, 均值≤{:.1}, 最大≤{:.1}",
+                 config.enabled, config.max_mean_intensity, config.max_peak_intensity);
+        alignment_sys.set_blank_frame_config(config);
+        Ok(())
+    }
+
+    /// 设置相机单帧采集超时与自动重连
+    pub fn set_frame_recovery_config(&self, config: FrameRecoveryConfig) -> Result<(), Box<dyn std::error::Error>> {
+        self.camera_manager.lock().unwrap().set_frame_recovery_config(config)?;
+        Ok(())
+    }
+
+    /// 设置实时调试叠加流（检测系统未初始化时，在初始化后生效）
+    pub fn set_debug_overlay_config(&self, config: DebugOverlayConfig) -> Result<(), Box<dyn std::error::Error>> {
+        config.validate()?;
+        if let Some(sys) = self.alignment_system.lock().unwrap().as_mut() {
+            sys.set_mask_overlay_enabled(config.enabled);
+        }
+        println!("🩻 调试叠加流: 启用 {}, 间隔 {} ms, 缩放 {:.2}", config.enabled, config.interval_ms, config.scale);
+        *self.debug_overlay.lock().unwrap() = DebugOverlayState { config, last_sent: None };
+        Ok(())
+    }
+
+    pub fn get_debug_overlay_config(&self) -> DebugOverlayConfig {
+        self.debug_overlay.lock().unwrap().config
+    }
+
+    /// 设置实时姿态显示卡尔曼滤波（与姿态多帧平均相互独立，仅影响 alignment-pose-filtered 事件）
+    pub fn set_pose_kalman_config(&self, config: PoseKalmanConfig) -> Result<(), Box<dyn std::error::Error>> {
+        self.pose_filter.lock().unwrap().set_config(config)?;
+        println!("📉 姿态显示滤波: 启用 {}, Q {:.4}, R {:.4}", config.enabled, config.process_noise, config.measurement_noise);
+        Ok(())
+    }
+
+    pub fn get_pose_kalman_config(&self) -> PoseKalmanConfig {
+        self.pose_filter.lock().unwrap().config()
+    }
+
+    /// 设置检测抽帧间隔：每N个新帧检测1帧 (1 = 每帧检测)，预览不受影响
+    pub fn set_detection_decimation(&self, every_n: u32) -> Result<(), Box<dyn std::error::Error>> {
+        self.frame_decimator.lock().unwrap().set_every_n(every_n)?;
+        println!("⏭️ 检测抽帧: 每 {} 帧处理1帧", every_n);
+        Ok(())
+    }
+
+    pub fn get_detection_decimation(&self) -> u32 {
+        self.frame_decimator.lock().unwrap().every_n()
+    }
+
+    /// 检测抽帧统计（实际处理帧率、跳过/错过帧数）
+    pub fn get_decimation_stats(&self) -> DecimationStats {
+        self.frame_decimator.lock().unwrap().stats()
+    }
+
+    /// 暂停/恢复检测（采集与预览不受影响，阶段保持不变）
+    pub fn set_detection_paused(&self, paused: bool) {
+        if self.detection_paused.swap(paused, Ordering::SeqCst) == paused {
+            return;
+        }
+        // 恢复时不沿用暂停前累积的姿态解（期间工装可能已更换）
+        if !paused {
+            if let Some(sys) = self.alignment_system.lock().unwrap().as_ref() {
+                sys.reset_pose_history();
+            }
+        }
+        println!("{}", if paused { "⏸️ 检测已暂停（预览继续）" } else { "▶️ 检测已恢复" });
+        let _ = self.app_handle.emit("alignment-detection-paused", paused);
+    }
+
+    pub fn is_detection_paused(&self) -> bool {
+        self.detection_paused.load(Ordering::SeqCst)
+    }
+
+    /// 设置左右眼互换（相机1为逻辑左眼），调整提示与debug图像颜色随逻辑左右眼
+    pub fn set_swap_eyes(&self, swap: bool) {
+        self.camera_manager.lock().unwrap().set_swap_eyes(swap);
+    }
+
+    /// 设置相机启动预热（start_workflow 时生效）
+    pub fn set_camera_warmup_config(&self, config: CameraWarmupConfig) -> Result<(), Box<dyn std::error::Error>> {
+        self.camera_manager.lock().unwrap().set_warmup_config(config)?;
+        Ok(())
+    }
+
+    /// 设置相机曝光时间（微秒），运行中立即生效，否则在 start_workflow 时下发
+    pub fn set_exposure_time(&self, exposure_us: f32) -> Result<(), Box<dyn std::error::Error>> {
+        self.camera_manager.lock().unwrap().set_exposure_time(exposure_us)?;
+        Ok(())
+    }
+
+    /// 设置检测失败时降低曝光重试
+    pub fn set_detection_retry_config(&self, config: DetectionRetryConfig) -> Result<(), Box<dyn std::error::Error>> {
+        config.validate()?;
+        println!("🔁 检测失败重试: 启用 {}, 曝光步长 {:.0} μs, 下限 {:.0} μs",
+                 config.enabled, config.exposure_step_us, config.min_exposure_us);
+        *self.retry_config.lock().unwrap() = config;
+        Ok(())
+    }
+
+    pub fn get_detection_retry_config(&self) -> DetectionRetryConfig {
+        *self.retry_config.lock().unwrap()
+    }
+
+    /// 设置上报姿态角/调整量的坐标约定
+    pub fn set_pose_convention(&self, convention: PoseConvention) -> Result<(), Box<dyn std::error::Error>> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        alignment_sys.set_pose_convention(convention)?;
+        println!("🧭 姿态坐标约定: {:?}", convention);
+        Ok(())
+    }
+
+    /// 设置姿态多帧平均帧数（1为单帧，即原有行为）
+    pub fn set_pose_averaging_frames(&self, frames: usize) -> Result<(), Box<dyn std::error::Error>> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        alignment_sys.set_pose_averaging_frames(frames)?;
+        println!("📊 姿态多帧平均: {} 帧", frames);
+        Ok(())
+    }
+
+    /// 设置工作距离合理范围 (mm)
+    pub fn set_standoff_range(&self, range: StandoffRange) -> Result<(), Box<dyn std::error::Error>> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        alignment_sys.set_standoff_range(range)?;
+        println!("📏 工作距离合理范围: [{:.0}, {:.0}] mm", range.min_mm, range.max_mm);
+        Ok(())
+    }
+
+    /// 设置双眼虚像距离合格范围 (mm)
+    pub fn set_convergence_range(&self, range: ConvergenceRange) -> Result<(), Box<dyn std::error::Error>> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        alignment_sys.set_convergence_range(range)?;
+        println!("📏 虚像距离合格范围: [{:.0}, {:.0}] mm", range.min_mm, range.max_mm);
+        Ok(())
+    }
+
+    /// 设置姿态解重投影RMS上限 (像素)
+    pub fn set_pose_reprojection_max_px(&self, max_px: f64) -> Result<(), Box<dyn std::error::Error>> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        alignment_sys.set_pose_reprojection_max_px(max_px)?;
+        println!("🎯 姿态重投影RMS上限: {:.3} px", max_px);
+        Ok(())
+    }
+
+    /// 设置 solvePnP 求解方法（失败时自动退回 ITERATIVE）
+    pub fn set_pnp_method(&self, method: PnpMethod) -> Result<(), Box<dyn std::error::Error>> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        alignment_sys.set_pnp_method(method);
+        println!("🎯 solvePnP 求解方法: {:?}", method);
+        Ok(())
+    }
+
+    /// 设置放大倍率不一致判定上限（右/左网格跨度比偏离1的比例）
+    pub fn set_magnification_mismatch_max(&self, max_deviation: f64) -> Result<(), Box<dyn std::error::Error>> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        alignment_sys.set_magnification_mismatch_max(max_deviation)?;
+        println!("🔍 放大倍率偏差上限: {:.2}%", max_deviation * 100.0);
+        Ok(())
+    }
+
+    /// 设置合像稳健统计（MAD 离群点剔除），对后续检测生效
+    pub fn set_robust_stats(&self, config: RobustStatsConfig) -> Result<(), Box<dyn std::error::Error>> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        alignment_sys.set_robust_stats(config)?;
+        println!("📊 稳健统计: {} (k={:.2}, 判定使用{})",
+                 if config.enabled { "启用" } else { "关闭" }, config.mad_k,
+                 if config.use_for_pass { "稳健统计" } else { "原始统计" });
+        Ok(())
     }
 
-    // ==================== 公共接口方法 ====================
+    /// 设置姿态/合像判定阈值，对后续检测生效
+    pub fn set_acceptance_thresholds(&self, thresholds: AcceptanceThresholds) -> Result<(), Box<dyn std::error::Error>> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        alignment_sys.set_thresholds(thresholds)?;
+        println!("🎯 判定阈值: roll ≤ {}°, pitch/yaw ≤ {}°, RMS ≤ {} px, 分位 ≤ {} px, Max ≤ {} px",
+                 thresholds.roll_deg, thresholds.pitch_yaw_deg, thresholds.rms_px, thresholds.percentile_px, thresholds.max_px);
+        Ok(())
+    }
 
-    /// 发送命令
-    pub fn send_command(&self, cmd: WorkflowCommand) -> Result<(), Box<dyn std::error::Error>> {
-        if let Some(ref sender) = self.command_sender {
-            sender.send(cmd)?;
-        }
+    /// 设置左眼居中判定的期望关键点位置（须位于图像范围内）
+    pub fn set_centering_targets(&self, targets: CenteringTargets) -> Result<(), Box<dyn std::error::Error>> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        alignment_sys.set_centering_targets(targets)?;
+        println!("🎯 居中期望位置: 右上角 ({:.1}, {:.1}), 左下角 ({:.1}, {:.1})",
+                 targets.expected_top_right.0, targets.expected_top_right.1,
+                 targets.expected_bottom_left.0, targets.expected_bottom_left.1);
         Ok(())
     }
 
-    /// 开始检测
-    pub fn start_detection(&self) -> Result<(), Box<dyn std::error::Error>> {
-        self.send_command(WorkflowCommand::StartDetection)
+    /// 当前生效的姿态/合像判定阈值
+    pub fn get_acceptance_thresholds(&self) -> Result<AcceptanceThresholds, Box<dyn std::error::Error>> {
+        let alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_ref().ok_or("合像检测系统未初始化")?;
+        Ok(alignment_sys.get_thresholds())
     }
 
-    /// 下一阶段
-    pub fn next_stage(&self) -> Result<(), Box<dyn std::error::Error>> {
-        self.send_command(WorkflowCommand::NextStage)
+    /// 最近一次姿态/合像结果按新阈值重新判定（实时判定阈值不变）
+    pub fn reevaluate_last_with_thresholds(&self, thresholds: &AcceptanceThresholds) -> Result<ReevaluationVerdict, Box<dyn std::error::Error>> {
+        let alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_ref().ok_or("合像检测系统未初始化")?;
+        Ok(alignment_sys.reevaluate_last(thresholds)?)
     }
 
-    /// 重置到预览模式
-    pub fn reset_to_preview(&self) -> Result<(), Box<dyn std::error::Error>> {
-        self.send_command(WorkflowCommand::Reset)
+    /// 最近一次合像判定的单点误差直方图（bin_width 为 None 时自动均分）
+    pub fn get_alignment_error_histogram(&self, bin_width: Option<f64>) -> Result<ErrorHistogram, Box<dyn std::error::Error>> {
+        let alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_ref().ok_or("合像检测系统未初始化")?;
+        Ok(alignment_sys.last_error_histogram(bin_width)?)
     }
 
-    /// 停止工作流程
-    pub fn stop_workflow(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        if !self.running.load(Ordering::SeqCst) {
-            return Ok(());
-        }
+    /// 设置合像分位误差所用分位数（默认95）
+    pub fn set_error_percentile(&self, pct: f64) -> Result<(), Box<dyn std::error::Error>> {
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        alignment_sys.set_error_percentile(pct)?;
+        println!("📐 合像分位误差: {}", crate::modules::alignment::percentile_label(pct));
+        Ok(())
+    }
 
-        println!("=== 停止合像检测工作流程 ===");
-        
-        // 立即设置停止标志
-        self.running.store(false, Ordering::SeqCst);
-        
-        // 发送停止命令
-        if let Some(ref sender) = self.command_sender {
-            let _ = sender.send(WorkflowCommand::Stop);
-        }
-        
-        // 强制停止相机（如果线程没有及时响应）
-        if let Ok(camera_manager) = self.camera_manager.lock() {
-            let _ = camera_manager.stop();
-            println!("🛑 强制停止相机");
-        }
-        
-        // 等待线程结束（设置超时）
-        if let Some(handle) = self.acquisition_thread.take() {
-            println!("⏳ 等待采集线程结束...");
-            match handle.join() {
-                Ok(_) => println!("✓ 采集线程已结束"),
-                Err(e) => println!("⚠️ 采集线程结束异常: {:?}", e),
+    fn wait_for_fresh_frame(
+        frame_buffer: &Arc<Mutex<RingBuffer<FrameData>>>,
+        after: Instant,
+        timeout: Duration,
+    ) -> Result<FrameData, String> {
+        let deadline = after.max(Instant::now()) + timeout;
+        loop {
+            let frame = frame_buffer.lock().unwrap().latest().cloned();
+            if let Some(frame) = frame.filter(|f| f.timestamp >= after) {
+                return Ok(frame);
             }
-        }
-        
-        if let Some(handle) = self.processing_thread.take() {
-            println!("⏳ 等待处理线程结束...");
-            match handle.join() {
-                Ok(_) => println!("✓ 处理线程已结束"),
-                Err(e) => println!("⚠️ 处理线程结束异常: {:?}", e),
+            if Instant::now() > deadline {
+                return Err("等待新帧超时".to_string());
             }
+            thread::sleep(Duration::from_millis(10));
         }
+    }
 
-        println!("✓ 工作流程已停止");
-        Ok(())
+    /// 获取debug图像绘制样式
+    pub fn get_debug_render_config(&self) -> DebugRenderConfig {
+        self.debug_render_config.lock().unwrap().clone()
+    }
+
+    /// 生命周期状态（由检测系统是否加载、线程是否运行推导）
+    pub fn init_state(&self) -> WorkflowInitState {
+        if self.warming_up.load(Ordering::SeqCst) {
+            WorkflowInitState::WarmingUp
+        } else if self.running.load(Ordering::SeqCst) {
+            WorkflowInitState::Running
+        } else if self.alignment_system.lock().unwrap().is_some() {
+            WorkflowInitState::ParamsLoaded
+        } else {
+            WorkflowInitState::Created
+        }
     }
 
     /// 获取当前状态
@@ -710,8 +3174,20 @@ impl AlignmentWorkflow {
         Ok(())
     }
 
+    /// 设置预览缓存帧最长沿用时间（采集短暂无帧时返回上一帧，超时后才报错）
+    pub fn set_preview_stale_timeout(&self, timeout_ms: u64) {
+        self.preview_cache.lock().unwrap().set_stale_timeout(Duration::from_millis(timeout_ms));
+        println!("🖼️ 预览缓存帧最长沿用: {} ms", timeout_ms);
+    }
+
+    pub fn get_preview_stale_timeout(&self) -> u64 {
+        self.preview_cache.lock().unwrap().stale_timeout().as_millis() as u64
+    }
+
     /// 获取当前预览帧（Base64格式）
-    pub fn get_current_preview_frame(&self) -> Result<crate::commands::alignment_commands::CameraPreviewData, Box<dyn std::error::Error>> {
+    /// 
+    /// 缓冲区暂无帧时返回最近一次成功编码的预览（stale = true），超过沿用时间才返回错误
+    pub fn get_current_preview_frame(&self) -> Result<CameraPreviewData, Box<dyn std::error::Error>> {
         use base64::{Engine as _, engine::general_purpose};
         
         // 从缓冲区获取最新帧
@@ -733,20 +3209,342 @@ impl AlignmentWorkflow {
             // // ===== DEBUG END: 可在正式版本中删除 =====
             
             // 将原始数据转换为Base64图像
-            let left_base64 = raw_data_to_base64_image(&frame.left_image, 2448, 2048)?;
-            let right_base64 = raw_data_to_base64_image(&frame.right_image, 2448, 2048)?;
+            let left_base64 = raw_data_to_base64_image(&frame.left_image, frame.resolution)?;
+            let right_base64 = raw_data_to_base64_image(&frame.right_image, frame.resolution)?;
             
-            Ok(crate::commands::alignment_commands::CameraPreviewData {
+            let preview = CameraPreviewData {
                 left_image_base64: left_base64,
                 right_image_base64: right_base64,
                 timestamp: frame.timestamp.elapsed().as_millis() as u64,
-                width: 2448,
-                height: 2048,
-                fps: 10.0,
-            })
+                width: frame.resolution.width as i32,
+                height: frame.resolution.height as i32,
+                fps: self.get_achieved_fps() as f32,
+                stale: false,
+                age_ms: 0,
+            };
+            self.preview_cache.lock().unwrap().store(&preview, Instant::now());
+            Ok(preview)
         } else {
-            Err("没有可用的帧数据".into())
+            Ok(self.preview_cache.lock().unwrap().fallback(Instant::now())?)
+        }
+    }
+
+    /// 获取最新帧指定区域的原始分辨率图像（Base64 PNG，用于局部放大检查）
+    /// 
+    /// # 参数
+    /// - `camera_side`: "left" 或 "right"
+    /// - `x`, `y`, `w`, `h`: 区域（原始2448×2048像素坐标），超出图像部分会被裁掉
+    pub fn get_full_resolution_region(
+        &self,
+        camera_side: &str,
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        use base64::{Engine as _, engine::general_purpose};
+        
+        let frame_data = {
+            let buffer = self.frame_buffer.lock().unwrap();
+            buffer.latest().cloned()
+        };
+        let frame = frame_data.ok_or("没有可用的帧数据")?;
+        
+        let raw = match camera_side {
+            "left" => &frame.left_image,
+            "right" => &frame.right_image,
+            _ => return Err("无效的相机侧别，应为 'left' 或 'right'".into()),
+        };
+        let mat = Self::raw_data_to_mat(raw, frame.resolution)?;
+        
+        // 裁剪到图像范围内
+        let x0 = x.clamp(0, mat.cols());
+        let y0 = y.clamp(0, mat.rows());
+        let x1 = (x.saturating_add(w)).clamp(0, mat.cols());
+        let y1 = (y.saturating_add(h)).clamp(0, mat.rows());
+        if x1 <= x0 || y1 <= y0 {
+            return Err(format!("区域无效或完全超出图像: ({}, {}, {}, {})", x, y, w, h).into());
+        }
+        let roi = core::Rect::new(x0, y0, x1 - x0, y1 - y0);
+        let region = core::Mat::roi(&mat, roi)?.try_clone()?;
+        
+        let mut buffer = core::Vector::<u8>::new();
+        imgcodecs::imencode(".png", &region, &mut buffer, &core::Vector::new())?;
+        
+        println!("🔍 原始分辨率区域: {} ({}, {}) {}×{}", camera_side, x0, y0, roi.width, roi.height);
+        Ok(format!("data:image/png;base64,{}", general_purpose::STANDARD.encode(buffer.as_slice())))
+    }
+
+    /// 获取最近一次检测中圆点检测器的二值图（Base64 PNG，校正后坐标系）
+    /// 
+    /// 检测失败时用于查看圆点是否在二值化后保留、是否黏连
+    pub fn get_last_binary_mask(&self, camera_side: &str) -> Result<String, Box<dyn std::error::Error>> {
+        use base64::{Engine as _, engine::general_purpose};
+        
+        let is_left = match camera_side {
+            "left" => true,
+            "right" => false,
+            _ => return Err("无效的相机侧别，应为 'left' 或 'right'".into()),
+        };
+        
+        let alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_ref().ok_or("合像检测系统未初始化")?;
+        let mask = alignment_sys.get_last_binary_mask(is_left)
+            .ok_or("暂无该眼的检测二值图，请先执行一次检测")?;
+        
+        let mut buffer = core::Vector::<u8>::new();
+        imgcodecs::imencode(".png", mask, &mut buffer, &core::Vector::new())?;
+        Ok(format!("data:image/png;base64,{}", general_purpose::STANDARD.encode(buffer.as_slice())))
+    }
+
+    /// 获取最近一次检测中指定眼的筛选阶段诊断（配合二值图排查过暗/过曝/遮挡）
+    pub fn get_last_detection_diagnostics(&self, camera_side: &str) -> Result<DetectionDiagnostics, Box<dyn std::error::Error>> {
+        let is_left = match camera_side {
+            "left" => true,
+            "right" => false,
+            _ => return Err("无效的相机侧别，应为 'left' 或 'right'".into()),
+        };
+        
+        let alignment_sys = self.alignment_system.lock().unwrap();
+        let alignment_sys = alignment_sys.as_ref().ok_or("合像检测系统未初始化")?;
+        let diagnostics = alignment_sys.get_last_detection_diagnostics(is_left)
+            .ok_or("暂无该眼的检测诊断，请先执行一次检测")?;
+        Ok(diagnostics.clone())
+    }
+
+    /// 最新帧的红/青立体图（Base64 PNG），save 为 true 时同时保存到采集目录
+    pub fn render_anaglyph(&self, save: bool) -> Result<String, Box<dyn std::error::Error>> {
+        use base64::{Engine as _, engine::general_purpose};
+        
+        let frame_data = {
+            let buffer = self.frame_buffer.lock().unwrap();
+            buffer.latest().cloned()
+        };
+        let frame = frame_data.ok_or("没有可用的帧数据")?;
+        let left_mat = Self::raw_data_to_mat(&frame.left_image, frame.resolution)?;
+        let right_mat = Self::raw_data_to_mat(&frame.right_image, frame.resolution)?;
+        
+        let anaglyph = {
+            let mut alignment_sys = self.alignment_system.lock().unwrap();
+            let alignment_sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+            alignment_sys.render_anaglyph(&left_mat, &right_mat, &self.rectify_maps_path())?
+        };
+        
+        if save {
+            let dir = paths::debug_images_dir("anaglyph");
+            std::fs::create_dir_all(&dir)?;
+            let path = format!("{}/anaglyph_{}.png", dir, chrono::Local::now().format("%Y%m%d_%H%M%S"));
+            imgcodecs::imwrite(&path, &anaglyph, &core::Vector::new())?;
+            println!("✅ 已保存红/青立体图: {}", path);
+            paths::apply_debug_retention(&dir);
+        }
+        
+        let mut buffer = core::Vector::<u8>::new();
+        imgcodecs::imencode(".png", &anaglyph, &mut buffer, &core::Vector::new())?;
+        Ok(format!("data:image/png;base64,{}", general_purpose::STANDARD.encode(buffer.as_slice())))
+    }
+
+    /// 快速完整检测：最新一帧送入流水线，左右眼并行重映射/检测后一次返回双眼姿态与合像结果
+    /// 
+    /// 等同于 `fast_check_job()?.run(compare_sequential)`；命令层应先取任务、释放状态锁再执行
+    pub fn fast_full_check(&self, compare_sequential: bool) -> Result<FastCheckReport, Box<dyn std::error::Error>> {
+        self.fast_check_job()?.run(compare_sequential)
+    }
+    
+    /// 快照快速完整检测所需的最新帧与检测系统全部设置（标定板规格、检测参数、居中目标、判定阈值等）
+    pub fn fast_check_job(&self) -> Result<FastCheckJob, Box<dyn std::error::Error>> {
+        let frame = self.frame_buffer.lock().unwrap().latest().cloned().ok_or("没有可用的帧数据")?;
+        let left_mat = Self::raw_data_to_mat(&frame.left_image, frame.resolution)?;
+        let right_mat = Self::raw_data_to_mat(&frame.right_image, frame.resolution)?;
+        
+        let settings = {
+            let alignment_sys = self.alignment_system.lock().unwrap();
+            alignment_sys.as_ref().ok_or("合像检测系统未初始化")?.settings()
+        };
+        
+        Ok(FastCheckJob {
+            left_mat,
+            right_mat,
+            settings,
+            image_size: core::Size::new(self.frame_resolution.width as i32, self.frame_resolution.height as i32),
+            param_paths: [
+                self.param_path("left_camera_params.yaml"),
+                self.param_path("right_camera_params.yaml"),
+                self.param_path("stereo_params.yaml"),
+                self.param_path("rectify_params.yaml"),
+            ],
+            rectify_maps_path: self.rectify_maps_path(),
+            pipeline: Arc::clone(&self.fast_check_pipeline),
+            alignment_system: Arc::clone(&self.alignment_system),
+        })
+    }
+
+    /// 对最新一帧完整检测并把调整向量换算为千分尺圈数
+    pub fn compute_screw_turns(&self, calibration: &MicrometerCalibration) -> Result<ScrewTurnReport, Box<dyn std::error::Error>> {
+        let frame = self.frame_buffer.lock().unwrap().latest().cloned().ok_or("没有可用的帧数据")?;
+        let left_image = Self::raw_data_to_mat(&frame.left_image, frame.resolution)?;
+        let right_image = Self::raw_data_to_mat(&frame.right_image, frame.resolution)?;
+        
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        let (left_corners, right_corners) = sys.detect_circles_grid(&left_image, &right_image, &self.rectify_maps_path())?;
+        let left_pose = sys.check_left_eye_pose(&left_corners)?;
+        let left_centering = sys.check_left_eye_centering(&left_corners, None)?;
+        let right_pose = sys.check_right_eye_pose(&right_corners)?;
+        let alignment = sys.check_dual_eye_alignment(&left_corners, &right_corners, false)?;
+        let adjustments = sys.calculate_adjustment_vectors(
+            Some(&left_pose), Some(&left_centering), Some(&right_pose), Some(&alignment));
+        
+        let turns = calibration.to_screw_turns(&adjustments);
+        println!("🔩 千分尺调整指令 ({:?}):", adjustments.priority);
+        for turn in &turns {
+            println!("   {}: {}", turn.axis, turn.instruction);
         }
+        Ok(ScrewTurnReport {
+            priority: format!("{:?}", adjustments.priority),
+            turns,
+        })
+    }
+
+    /// 处理线程最近一次各阶段检测结果
+    pub fn latest_stage_results(&self) -> LatestStageResults {
+        self.latest_results.lock().unwrap().clone()
+    }
+
+    /// 由处理线程已缓存的各阶段结果组装姿态/居中/合像/调整向量合一的检测报告
+    /// 
+    /// 不重新检测（不影响多帧姿态平均与复判缓存）；任一阶段尚无结果时报错
+    pub fn compute_alignment_report(&self) -> Result<AlignmentReport, Box<dyn std::error::Error>> {
+        let latest = self.latest_stage_results();
+        let left_pose = latest.left_pose.ok_or("尚未完成左眼姿态检测")?;
+        let left_centering = latest.left_centering.ok_or("尚未完成左眼居中检测")?;
+        let right_pose = latest.right_pose.ok_or("尚未完成右眼姿态检测")?;
+        let alignment = latest.alignment.ok_or("尚未完成双眼合像检测")?;
+        
+        let alignment_sys = self.alignment_system.lock().unwrap();
+        let sys = alignment_sys.as_ref().ok_or("合像检测系统未初始化")?;
+        let report = sys.build_report(&left_pose, &left_centering, &right_pose, &alignment);
+        println!("📋 检测报告: {} (调整优先级: {:?})", if report.pass { "通过" } else { "未通过" }, report.adjustments.priority);
+        Ok(report)
+    }
+
+    /// 最新一帧完整测量（双眼姿态 + 合像），不论是否通过均返回数值
+    pub fn measure_current_alignment(&self) -> Result<AlignmentMeasurement, Box<dyn std::error::Error>> {
+        let frame = self.frame_buffer.lock().unwrap().latest().cloned().ok_or("没有可用的帧数据")?;
+        let left_image = Self::raw_data_to_mat(&frame.left_image, frame.resolution)?;
+        let right_image = Self::raw_data_to_mat(&frame.right_image, frame.resolution)?;
+        
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        let (left_corners, right_corners) = sys.detect_circles_grid(&left_image, &right_image, &self.rectify_maps_path())?;
+        // 单帧姿态：旁路测量不影响实时检测的多帧平均
+        let left_pose = sys.measure_left_eye_pose(&left_corners)?;
+        let right_pose = sys.measure_right_eye_pose(&right_corners)?;
+        let alignment = sys.measure_dual_eye_alignment(&left_corners, &right_corners)?;
+        Ok(AlignmentMeasurement::from_results(&left_pose, &right_pose, &alignment))
+    }
+
+    /// 最新一帧的双眼会聚检测（视差三角化虚像距离）
+    pub fn check_convergence(&self) -> Result<ConvergenceCheck, Box<dyn std::error::Error>> {
+        let frame = self.frame_buffer.lock().unwrap().latest().cloned().ok_or("没有可用的帧数据")?;
+        let left_image = Self::raw_data_to_mat(&frame.left_image, frame.resolution)?;
+        let right_image = Self::raw_data_to_mat(&frame.right_image, frame.resolution)?;
+        
+        let mut alignment_sys = self.alignment_system.lock().unwrap();
+        let sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+        let (left_corners, right_corners) = sys.detect_circles_grid(&left_image, &right_image, &self.rectify_maps_path())?;
+        let check = sys.check_convergence(&left_corners, &right_corners)?;
+        println!("🔭 {}", check.message());
+        Ok(check)
+    }
+
+    /// 导出最新一帧的完整测量存档（追溯/RMA）
+    /// 
+    /// zip 内含原始图、校正图、检测叠加图（二值掩码+圆点）、report.json 及生效配置 config.yaml。
+    /// 检测失败时仍导出图像，失败原因写入报告，便于复现不良品。
+    pub fn export_measurement_archive(&self, path: &std::path::Path, active_config_yaml: &str) -> Result<MeasurementArchiveSummary, Box<dyn std::error::Error>> {
+        let frame = self.frame_buffer.lock().unwrap().latest().cloned().ok_or("没有可用的帧数据")?;
+        let left_image = Self::raw_data_to_mat(&frame.left_image, frame.resolution)?;
+        let right_image = Self::raw_data_to_mat(&frame.right_image, frame.resolution)?;
+        let encode_png = |mat: &Mat| -> Result<Vec<u8>, opencv::Error> {
+            let mut buffer = core::Vector::<u8>::new();
+            imgcodecs::imencode(".png", mat, &mut buffer, &core::Vector::new())?;
+            Ok(buffer.to_vec())
+        };
+        
+        let mut entries = vec![
+            ("raw_left.png".to_string(), encode_png(&left_image)?),
+            ("raw_right.png".to_string(), encode_png(&right_image)?),
+        ];
+        
+        let runtime = self.runtime_config_snapshot();
+        let (measurement, messages, error) = {
+            let mut alignment_sys = self.alignment_system.lock().unwrap();
+            let sys = alignment_sys.as_mut().ok_or("合像检测系统未初始化")?;
+            
+            // 校正图（与检测一致的插值方式）
+            let rectified = match sys.get_rectify_maps() {
+                Some((left_map1, left_map2, right_map1, right_map2)) => {
+                    let rectifier = sys.get_rectifier();
+                    Some((
+                        rectifier.remap_image_adaptive(&left_image, left_map1, left_map2, RemapInterpolation::Linear)?,
+                        rectifier.remap_image_adaptive(&right_image, right_map1, right_map2, RemapInterpolation::Linear)?,
+                    ))
+                }
+                None => None,
+            };
+            
+            let mut detect = || -> Result<_, Box<dyn std::error::Error>> {
+                let (left_corners, right_corners) = sys.detect_circles_grid(&left_image, &right_image, &self.rectify_maps_path())?;
+                // 单帧姿态：存档不影响实时检测的多帧平均
+                let left_pose = sys.measure_left_eye_pose(&left_corners)?;
+                let right_pose = sys.measure_right_eye_pose(&right_corners)?;
+                let alignment = sys.measure_dual_eye_alignment(&left_corners, &right_corners)?;
+                Ok((left_corners.to_vec(), right_corners.to_vec(), left_pose, right_pose, alignment))
+            };
+            let (blobs, measurement, messages, error) = match detect() {
+                Ok((left_corners, right_corners, left_pose, right_pose, alignment)) => {
+                    let messages = vec![
+                        left_pose.message("左眼"),
+                        right_pose.message("右眼"),
+                        format!("合像: RMS {:.3} px, {} {:.3} px, 最大 {:.3} px, {}",
+                                alignment.rms, alignment.percentile_label(), alignment.p95, alignment.max_err,
+                                if alignment.pass { "通过" } else { "不通过" }),
+                    ];
+                    let measurement = AlignmentMeasurement::from_results(&left_pose, &right_pose, &alignment);
+                    ((left_corners, right_corners), Some(measurement), messages, None)
+                }
+                Err(e) => ((Vec::new(), Vec::new()), None, Vec::new(), Some(e.to_string())),
+            };
+            
+            if let Some((left_rect, right_rect)) = rectified {
+                let left_overlay = compose_mask_overlay(&left_rect, sys.get_last_binary_mask(true), &blobs.0, 1.0)?;
+                let right_overlay = compose_mask_overlay(&right_rect, sys.get_last_binary_mask(false), &blobs.1, 1.0)?;
+                entries.push(("rectified_left.png".to_string(), encode_png(&left_rect)?));
+                entries.push(("rectified_right.png".to_string(), encode_png(&right_rect)?));
+                entries.push(("overlay_left.png".to_string(), encode_png(&left_overlay)?));
+                entries.push(("overlay_right.png".to_string(), encode_png(&right_overlay)?));
+            }
+            (measurement, messages, error)
+        };
+        
+        let report = MeasurementArchiveReport {
+            captured_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            measurement,
+            messages,
+            error,
+            runtime,
+        };
+        entries.push(("report.json".to_string(), serde_json::to_vec_pretty(&report)?));
+        entries.push(("config.yaml".to_string(), active_config_yaml.as_bytes().to_vec()));
+        
+        write_measurement_archive(path, &entries)?;
+        println!("🗜️ 测量存档已导出: {} ({} 个文件)", path.display(), entries.len());
+        Ok(MeasurementArchiveSummary {
+            path: path.display().to_string(),
+            files: entries.into_iter().map(|(name, _)| name).collect(),
+            report,
+        })
     }
 
     /// 获取当前检测结果
@@ -761,8 +3559,8 @@ impl AlignmentWorkflow {
             let mut alignment_sys = self.alignment_system.lock().unwrap();
             if let Some(ref mut sys) = *alignment_sys {
                 // 执行完整的检测流程
-                let left_image = Self::raw_data_to_mat(&frame.left_image, 2448, 2048)?;
-                let right_image = Self::raw_data_to_mat(&frame.right_image, 2448, 2048)?;
+                let left_image = Self::raw_data_to_mat(&frame.left_image, frame.resolution)?;
+                let right_image = Self::raw_data_to_mat(&frame.right_image, frame.resolution)?;
                 
                 // 使用单帧检测方法
                 self.detect_single_frame_internal(sys, left_image, right_image)
@@ -781,58 +3579,83 @@ impl AlignmentWorkflow {
         left_image: opencv::core::Mat,
         right_image: opencv::core::Mat,
     ) -> Result<DetectionResult, Box<dyn std::error::Error>> {
-        // 1. 执行圆心检测
-        let (left_corners, right_corners) = alignment_sys.detect_circles_grid(
+        Self::detect_frame_with(alignment_sys, &self.rectify_maps_path(), left_image, right_image, false)
+    }
+    
+    /// 单帧完整检测：无投影 → 圆心 → 双眼姿态 → 合像（不依赖工作流实例，供快速完整检测等在状态锁外调用）
+    /// 
+    /// 单眼未检测到网格时失败原因原样返回或附在左眼结果消息中
+    fn detect_frame_with(
+        alignment_sys: &mut crate::modules::alignment::AlignmentSystem,
+        rectify_maps_path: &str,
+        left_image: opencv::core::Mat,
+        right_image: opencv::core::Mat,
+        save_debug_image: bool,
+    ) -> Result<DetectionResult, Box<dyn std::error::Error>> {
+        // 0. 无投影检查
+        if let Some(result) = Self::check_no_projection(alignment_sys, &left_image, &right_image, true, true)? {
+            return Ok(result);
+        }
+        
+        // 1. 执行圆心检测（右眼未检测到时降级为仅左眼反馈）
+        let (left_corners, right_corners) = alignment_sys.detect_circles_grid_partial(
             &left_image,
             &right_image,
-            "yaml_last_param_file/rectify_maps.yaml", // 🔧 修正路径
+            rectify_maps_path,
         )?;
+        let left_corners = left_corners?;
+        
+        let mut timings = alignment_sys.get_last_stage_timings();
         
         // 2. 左眼姿态检测
+        let pose_start = Instant::now();
         let left_pose = alignment_sys.check_left_eye_pose(&left_corners)?;
-        if !left_pose.pass {
-            return Ok(DetectionResult::LeftEyePose {
-                roll: left_pose.roll,
-                pitch: left_pose.pitch,
-                yaw: left_pose.yaw,
-                pass: false,
-                message: format!("❌ 左眼姿态超出容差 - roll={:.3}°, pitch={:.3}°, yaw={:.3}°", 
-                               left_pose.roll, left_pose.pitch, left_pose.yaw),
-            });
-        }
+        timings.pose_ms = pose_start.elapsed().as_secs_f64() * 1000.0;
+        let right_corners = match right_corners {
+            Ok(corners) if left_pose.pass => corners,
+            right_corners => {
+                let mut message = left_pose.message("左眼");
+                if let Err(reason) = right_corners {
+                    message.push_str(&format!("（{}，仅左眼结果）", reason));
+                }
+                return Ok(DetectionResult::LeftEyePose {
+                    roll: left_pose.roll,
+                    pitch: left_pose.pitch,
+                    yaw: left_pose.yaw,
+                    pass: left_pose.pass,
+                    message,
+                    spread: left_pose.spread,
+                    standoff: left_pose.standoff,
+                    reprojection_rms: left_pose.reprojection_rms,
+                    timings,
+                });
+            }
+        };
         
         // 3. 右眼姿态检测
+        let pose_start = Instant::now();
         let right_pose = alignment_sys.check_right_eye_pose(&right_corners)?;
+        timings.pose_ms += pose_start.elapsed().as_secs_f64() * 1000.0;
         if !right_pose.pass {
             return Ok(DetectionResult::RightEyePose {
                 roll: right_pose.roll,
                 pitch: right_pose.pitch,
                 yaw: right_pose.yaw,
                 pass: false,
-                message: format!("❌ 右眼姿态超出容差 - roll={:.3}°, pitch={:.3}°, yaw={:.3}°", 
-                               right_pose.roll, right_pose.pitch, right_pose.yaw),
+                message: right_pose.message("右眼"),
+                spread: right_pose.spread,
+                standoff: right_pose.standoff,
+                reprojection_rms: right_pose.reprojection_rms,
+                timings,
             });
         }
         
         // 4. 双眼合像检测
-        let alignment_result = alignment_sys.check_dual_eye_alignment(&left_corners, &right_corners, false)?;
-        let adjustment_hint = format!(
-            "调整提示: Δx={:.3}px {}, Δy={:.3}px {}",
-            alignment_result.mean_dx,
-            if alignment_result.mean_dx > 0.0 { "(右眼向左调)" } else { "(右眼向右调)" },
-            alignment_result.mean_dy,
-            if alignment_result.mean_dy < 0.0 { "(右眼向上调)" } else { "(右眼向下调)" }
-        );
+        let alignment_start = Instant::now();
+        let alignment_result = alignment_sys.check_dual_eye_alignment(&left_corners, &right_corners, save_debug_image)?;
+        timings.alignment_ms = alignment_start.elapsed().as_secs_f64() * 1000.0;
         
-        Ok(DetectionResult::DualEyeAlignment {
-            mean_dx: alignment_result.mean_dx,
-            mean_dy: alignment_result.mean_dy,
-            rms: alignment_result.rms,
-            p95: alignment_result.p95,
-            max_err: alignment_result.max_err,
-            pass: alignment_result.pass,
-            adjustment_hint,
-        })
+        Ok(alignment_to_detection_result(&alignment_result, timings))
     }
 
     /// 获取系统性能统计
@@ -841,6 +3664,10 @@ impl AlignmentWorkflow {
             let buffer = self.frame_buffer.lock().unwrap();
             buffer.get_stats()
         };
+        let maps_regenerated = self.alignment_system.lock().unwrap()
+            .as_ref()
+            .map(|sys| sys.maps_regenerated())
+            .unwrap_or(false);
 
         let stats = serde_json::json!({
             "buffer": {
@@ -854,14 +3681,68 @@ impl AlignmentWorkflow {
                 "cpu_cores": num_cpus::get(),
                 "opencv_threads": 2, // 已在configure_opencv_performance中设置
                 "thread_count": 2,   // 采集线程 + 处理线程
-                "running": self.running.load(Ordering::SeqCst)
+                "running": self.running.load(Ordering::SeqCst),
+                "rectify_maps_regenerated": maps_regenerated // 降级模式：重映射矩阵由参数重新计算
+            },
+            "acquisition": {
+                "target_fps": self.get_target_fps(),
+                "achieved_fps": self.get_achieved_fps()
             },
+            "detection": self.get_decimation_stats(),
+            "latency": self.get_latency_stats(),
             "stage": self.get_current_stage()
         });
 
         Ok(stats)
     }
 
+    /// 已加载重映射矩阵的尺寸校验（检测系统未初始化或矩阵未加载时返回 None）
+    pub fn check_rectify_maps_size(&self) -> Option<RectifyMapsSizeCheck> {
+        self.alignment_system.lock().unwrap()
+            .as_ref()
+            .and_then(|sys| sys.check_rectify_maps_size())
+    }
+
+    /// 运行时配置快照（采集/预览设置 + 合像系统实际生效参数）
+    pub fn runtime_config_snapshot(&self) -> serde_json::Value {
+        let alignment_system = self.alignment_system.lock().unwrap()
+            .as_ref()
+            .map(|sys| sys.runtime_settings());
+
+        serde_json::json!({
+            "running": self.running.load(Ordering::SeqCst),
+            "init_state": self.init_state(),
+            "stage": self.get_current_stage(),
+            "preview_transport": self.get_preview_transport(),
+            "acquisition": {
+                "target_fps": self.get_target_fps(),
+                "achieved_fps": self.get_achieved_fps()
+            },
+            "frame_buffer_capacity": 5,
+            "detection_retry": self.get_detection_retry_config(),
+            "frame_recovery": self.camera_manager.lock().unwrap().get_frame_recovery_config(),
+            "swap_eyes": self.camera_manager.lock().unwrap().is_eyes_swapped(),
+            "param_files": [
+                "left_camera_params.yaml",
+                "right_camera_params.yaml",
+                "stereo_params.yaml",
+                "rectify_params.yaml",
+                "rectify_maps.yaml"
+            ].iter().map(|name| crate::config::resolve_path_info(&self.param_path(name))).collect::<Vec<_>>(),
+            "alignment_system": alignment_system
+        })
+    }
+
+    /// 获取端到端延迟分布（最近 LATENCY_WINDOW 个检测结果）
+    pub fn get_latency_stats(&self) -> LatencyStats {
+        self.latency_tracker.lock().unwrap().summary()
+    }
+
+    /// 清空端到端延迟统计
+    pub fn reset_latency_stats(&self) {
+        self.latency_tracker.lock().unwrap().clear();
+    }
+
     /// 手动保存调试图像（公开接口）
     pub fn save_debug_images_manual(&self) -> Result<(), Box<dyn std::error::Error>> {
         let frame_data = {
@@ -885,8 +3766,8 @@ impl AlignmentWorkflow {
         println!("📸 保存调试图像...");
         
         // 转换为Mat格式
-        let left_mat = Self::raw_data_to_mat(&frame.left_image, 2448, 2048)?;
-        let right_mat = Self::raw_data_to_mat(&frame.right_image, 2448, 2048)?;
+        let left_mat = Self::raw_data_to_mat(&frame.left_image, frame.resolution)?;
+        let right_mat = Self::raw_data_to_mat(&frame.right_image, frame.resolution)?;
         
         // 生成时间戳文件名
         let timestamp = SystemTime::now()
@@ -895,8 +3776,8 @@ impl AlignmentWorkflow {
             .as_secs();
         
         // 确保调试目录存在
-        let debug_dir = "src-tauri/captures/alignment_workflow_debug";
-        std::fs::create_dir_all(debug_dir)?;
+        let debug_dir = paths::debug_images_dir("alignment_workflow_debug");
+        std::fs::create_dir_all(&debug_dir)?;
         
         let left_path = format!("{}/debug_left_{}.png", debug_dir, timestamp);
         let right_path = format!("{}/debug_right_{}.png", debug_dir, timestamp);
@@ -917,8 +3798,9 @@ impl AlignmentWorkflow {
                     let (left_map1, left_map2, right_map1, right_map2) = sys.get_rectify_maps().unwrap();
                     let rectifier = sys.get_rectifier();
                     
-                    let left_rect = rectifier.remap_image_adaptive(&left_mat, left_map1, left_map2)?;
-                    let right_rect = rectifier.remap_image_adaptive(&right_mat, right_map1, right_map2)?;
+                    // 调试快照仅供人工查看，与预览一样使用最近邻插值
+                    let left_rect = rectifier.remap_image_adaptive(&left_mat, left_map1, left_map2, RemapInterpolation::Nearest)?;
+                    let right_rect = rectifier.remap_image_adaptive(&right_mat, right_map1, right_map2, RemapInterpolation::Nearest)?;
                     
                     let left_rect_path = format!("{}/debug_left_rectified_{}.png", debug_dir, timestamp);
                     let right_rect_path = format!("{}/debug_right_rectified_{}.png", debug_dir, timestamp);
@@ -930,6 +3812,7 @@ impl AlignmentWorkflow {
             }
         }
         
+        paths::apply_debug_retention(&debug_dir);
         Ok(())
     }
     // ===== DEBUG END: 可在正式版本中删除 =====
@@ -977,62 +3860,12 @@ impl AlignmentWorkflow {
         }
         
         let sys = alignment_sys.as_mut().unwrap();
-        
-        // 1. 执行圆心检测
-        let (left_corners, right_corners) = sys.detect_circles_grid(
-            &left_image,
-            &right_image,
-            "yaml_last_param_file/rectify_maps.yaml", // 🔧 修正路径
-        )?;
-        
-        // 2. 左眼姿态检测
-        let left_pose = sys.check_left_eye_pose(&left_corners)?;
-        if !left_pose.pass {
-            return Ok(DetectionResult::LeftEyePose {
-                roll: left_pose.roll,
-                pitch: left_pose.pitch,
-                yaw: left_pose.yaw,
-                pass: false,
-                message: format!("❌ 左眼姿态超出容差 - roll={:.3}°, pitch={:.3}°, yaw={:.3}°", 
-                               left_pose.roll, left_pose.pitch, left_pose.yaw),
-            });
-        }
-        
-        // 3. 右眼姿态检测
-        let right_pose = sys.check_right_eye_pose(&right_corners)?;
-        if !right_pose.pass {
-            return Ok(DetectionResult::RightEyePose {
-                roll: right_pose.roll,
-                pitch: right_pose.pitch,
-                yaw: right_pose.yaw,
-                pass: false,
-                message: format!("❌ 右眼姿态超出容差 - roll={:.3}°, pitch={:.3}°, yaw={:.3}°", 
-                               right_pose.roll, right_pose.pitch, right_pose.yaw),
-            });
-        }
-        
-        // 4. 双眼合像检测
-        let alignment_result = sys.check_dual_eye_alignment(&left_corners, &right_corners, true)?;
-        let adjustment_hint = format!(
-            "调整提示: Δx={:.3}px {}, Δy={:.3}px {}",
-            alignment_result.mean_dx,
-            if alignment_result.mean_dx > 0.0 { "(右眼向左调)" } else { "(右眼向右调)" },
-            alignment_result.mean_dy,
-            if alignment_result.mean_dy < 0.0 { "(右眼向上调)" } else { "(右眼向下调)" }
-        );
+        let result = Self::detect_frame_with(sys, &self.rectify_maps_path(), left_image, right_image, true);
         
         let processing_time = start_time.elapsed();
         println!("✓ 工作流单帧检测完成，总耗时: {:.1} ms", processing_time.as_millis());
         
-        Ok(DetectionResult::DualEyeAlignment {
-            mean_dx: alignment_result.mean_dx,
-            mean_dy: alignment_result.mean_dy,
-            rms: alignment_result.rms,
-            p95: alignment_result.p95,
-            max_err: alignment_result.max_err,
-            pass: alignment_result.pass,
-            adjustment_hint,
-        })
+        result
     }
     
     /// 🎯 仅执行圆心检测 - 用于快速验证图像质量
@@ -1047,9 +3880,7 @@ impl AlignmentWorkflow {
         }
         
         let sys = alignment_sys.as_mut().unwrap();
-        // 🔧 修正重映射矩阵路径 - 使用yaml_last_param_file目录
-        // 旧路径: "rectify_maps.yaml"
-        sys.detect_circles_grid(&left_image, &right_image, "yaml_last_param_file/rectify_maps.yaml")
+        sys.detect_circles_grid(&left_image, &right_image, &self.rectify_maps_path())
     }
 }
 
@@ -1061,13 +3892,69 @@ impl Drop for AlignmentWorkflow {
 
 // ==================== 辅助函数 ====================
 
+/// 将原始图像数据缩放为灰度缩略图（宽400），返回 (像素, 宽, 高)
+fn raw_data_to_gray_thumbnail(raw_data: &[u8], resolution: FrameResolution) -> Result<(Vec<u8>, i32, i32), Box<dyn std::error::Error>> {
+    let mat = AlignmentWorkflow::raw_data_to_mat(raw_data, resolution)?;
+    let (width, height) = (resolution.width as i32, resolution.height as i32);
+
+    let thumbnail_width = 400;
+    let thumbnail_height = (height as f32 * thumbnail_width as f32 / width as f32) as i32;
+
+    let mut resized_mat = core::Mat::default();
+    imgproc::resize(
+        &mat,
+        &mut resized_mat,
+        core::Size::new(thumbnail_width, thumbnail_height),
+        0.0,
+        0.0,
+        imgproc::INTER_AREA,
+    )?;
+
+    Ok((resized_mat.data_bytes()?.to_vec(), thumbnail_width, thumbnail_height))
+}
+
+/// 将左右缩略图写入原始像素缓冲文件，返回前端通知
+/// 先写临时文件再rename，保证前端不会读到半帧
+fn write_raw_preview_buffers(frame: &FrameData) -> Result<RawPreviewNotice, Box<dyn std::error::Error>> {
+    let dir = raw_preview_dir();
+    std::fs::create_dir_all(&dir)?;
+
+    let (left_pixels, thumb_w, thumb_h) = raw_data_to_gray_thumbnail(&frame.left_image, frame.resolution)?;
+    let (right_pixels, _, _) = raw_data_to_gray_thumbnail(&frame.right_image, frame.resolution)?;
+
+    for (name, pixels) in [("left", &left_pixels), ("right", &right_pixels)] {
+        let tmp_path = dir.join(format!("{}.gray.tmp", name));
+        std::fs::write(&tmp_path, pixels)?;
+        std::fs::rename(&tmp_path, dir.join(format!("{}.gray", name)))?;
+    }
+
+    Ok(RawPreviewNotice {
+        left_uri: "preview://localhost/left".to_string(),
+        right_uri: "preview://localhost/right".to_string(),
+        width: thumb_w,
+        height: thumb_h,
+        format: "gray8".to_string(),
+        sequence: RAW_PREVIEW_SEQ.fetch_add(1, Ordering::SeqCst),
+    })
+}
+
+/// 读取原始像素预览缓冲（`preview://` 协议处理函数使用）
+/// name 仅允许 "left" / "right"
+pub fn read_raw_preview_buffer(name: &str) -> Option<Vec<u8>> {
+    match name {
+        "left" | "right" => std::fs::read(raw_preview_dir().join(format!("{}.gray", name))).ok(),
+        _ => None,
+    }
+}
+
 /// 将原始图像数据转换为Base64格式的PNG图像
-fn raw_data_to_base64_image(raw_data: &[u8], width: i32, height: i32) -> Result<String, Box<dyn std::error::Error>> {
+fn raw_data_to_base64_image(raw_data: &[u8], resolution: FrameResolution) -> Result<String, Box<dyn std::error::Error>> {
     use base64::{Engine as _, engine::general_purpose};
     use opencv::{core, imgcodecs, prelude::*};
     
     // 将原始数据转换为OpenCV Mat
-    let mat = AlignmentWorkflow::raw_data_to_mat(raw_data, width, height)?;
+    let mat = AlignmentWorkflow::raw_data_to_mat(raw_data, resolution)?;
+    let (width, height) = (resolution.width as i32, resolution.height as i32);
     
     // 创建缩略图 (缩放到400x300以减少传输数据量)
     let thumbnail_width = 400;
@@ -0,0 +1,67 @@
+// benchmark.rs - 检测性能统计汇总
+// detection_benchmark (离线图像) 与 run_live_benchmark (实际相机) 共用的统计与10fps判定
+
+use std::time::Duration;
+use serde::{Serialize, Deserialize};
+use crate::modules::alignment::{StageTimings, mean, percentile};
+
+/// 10fps 实时检测的单帧耗时上限 (ms)
+pub const TARGET_FRAME_TIME_10FPS_MS: f64 = 100.0;
+
+/// 检测耗时统计汇总
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BenchmarkSummary {
+    pub iterations: usize,          // 总次数
+    pub successful: usize,          // 成功次数（仅成功的计入耗时统计）
+    pub mean_ms: f64,
+    pub p95_ms: f64,
+    pub max_ms: f64,
+    pub min_ms: f64,
+    pub target_ms: f64,             // 10fps 阈值
+    pub pass_10fps: bool,           // 平均与P95耗时均不超过阈值
+    pub stage_means: StageTimings,  // 各阶段平均耗时
+}
+
+impl BenchmarkSummary {
+    /// 由成功检测的耗时与分阶段耗时计算汇总
+    pub fn from_samples(iterations: usize, times: &[Duration], stages: &[StageTimings]) -> Self {
+        let values: Vec<f64> = times.iter().map(|t| t.as_secs_f64() * 1000.0).collect();
+        if values.is_empty() {
+            return Self { iterations, target_ms: TARGET_FRAME_TIME_10FPS_MS, ..Self::default() };
+        }
+
+        let stage_mean = |f: fn(&StageTimings) -> f64| mean(&stages.iter().map(f).collect::<Vec<_>>()).unwrap_or_default();
+        let mean_ms = mean(&values).unwrap_or_default();
+        let p95_ms = percentile(&values, 95.0).unwrap_or_default();
+        Self {
+            iterations,
+            successful: values.len(),
+            mean_ms,
+            p95_ms,
+            max_ms: values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            min_ms: values.iter().cloned().fold(f64::INFINITY, f64::min),
+            target_ms: TARGET_FRAME_TIME_10FPS_MS,
+            pass_10fps: mean_ms <= TARGET_FRAME_TIME_10FPS_MS && p95_ms <= TARGET_FRAME_TIME_10FPS_MS,
+            stage_means: StageTimings {
+                remap_ms: stage_mean(|s| s.remap_ms),
+                detect_ms: stage_mean(|s| s.detect_ms),
+                sort_ms: stage_mean(|s| s.sort_ms),
+                pose_ms: stage_mean(|s| s.pose_ms),
+                alignment_ms: stage_mean(|s| s.alignment_ms),
+            },
+        }
+    }
+
+    /// 打印汇总报告
+    pub fn print_report(&self, title: &str) {
+        println!("\n📋 {}", title);
+        println!("   成功率: {}/{}", self.successful, self.iterations);
+        println!("   平均耗时: {:.1} ms, P95: {:.1} ms, 最大: {:.1} ms, 最小: {:.1} ms",
+                 self.mean_ms, self.p95_ms, self.max_ms, self.min_ms);
+        println!("   阶段平均: 重映射 {:.1} ms, 检测 {:.1} ms, 排序 {:.1} ms, 姿态 {:.1} ms, 合像 {:.1} ms",
+                 self.stage_means.remap_ms, self.stage_means.detect_ms, self.stage_means.sort_ms,
+                 self.stage_means.pose_ms, self.stage_means.alignment_ms);
+        println!("   10fps兼容性 (≤{:.0} ms): {}", self.target_ms,
+                 if self.pass_10fps { "✓ PASS" } else { "❌ FAIL" });
+    }
+}
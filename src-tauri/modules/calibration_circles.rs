@@ -8,7 +8,10 @@ use opencv::{
     imgcodecs, imgproc::{self, COLOR_BGR2GRAY}, 
     prelude::*
 };
+use std::time::{Duration, Instant};
+use serde::{Serialize, Deserialize};
 use crate::modules::param_io::*;
+use crate::modules::alignment_circles_detection::{GridOrderStrategy, order_asymmetric_grid, swap_adjacent_columns};
 
 /// 相机类型枚举
 #[derive(Debug, Clone, Copy)]
@@ -26,6 +29,48 @@ impl CameraType {
     }
 }
 
+/// 读取图像文件并转换为标定检测所需格式 (8位 3通道 BGR)
+/// 
+/// BMP/PNG/JPG 统一处理，灰度/带Alpha/16位图像均会被归一化，
+/// 避免 IMREAD_COLOR 与 IMREAD_GRAYSCALE 混用导致的检测失败
+pub fn load_image_for_detection(path: &str) -> Result<Mat, opencv::Error> {
+    let image = imgcodecs::imread(path, imgcodecs::IMREAD_UNCHANGED)?;
+    if image.empty() {
+        return Err(opencv::Error::new(
+            opencv::core::StsError,
+            format!("无法读取图像: {}", path),
+        ));
+    }
+    to_detection_format(&image)
+}
+
+/// 将任意 Mat 转换为标定检测所需格式 (8位 3通道 BGR)
+pub fn to_detection_format(image: &Mat) -> Result<Mat, opencv::Error> {
+    // 16位图像缩放到8位
+    let image_8u = if image.depth() == opencv::core::CV_16U {
+        let mut converted = Mat::default();
+        image.convert_to(&mut converted, opencv::core::CV_8U, 1.0 / 256.0, 0.0)?;
+        converted
+    } else {
+        image.clone()
+    };
+    
+    let code = match image_8u.channels() {
+        1 => imgproc::COLOR_GRAY2BGR,
+        4 => imgproc::COLOR_BGRA2BGR,
+        _ => return Ok(image_8u),
+    };
+    let mut bgr = Mat::default();
+    imgproc::cvt_color(&image_8u, &mut bgr, code, 0, AlgorithmHint::ALGO_HINT_DEFAULT)?;
+    Ok(bgr)
+}
+
+/// 默认参与标定所需的最少有效图像数
+pub const DEFAULT_MIN_VALID_IMAGES: usize = 8;
+
+/// 最少有效图像数的下限（单目标定至少需要3个视图）
+pub const MIN_VALID_IMAGES_FLOOR: usize = 3;
+
 pub struct Calibrator {
     image_size: Size,                 // Size::new(width pixel i32, height pixel i32) image pixel size
     diameter: f32,                    // 圆点实际直径(mm)
@@ -34,6 +79,84 @@ pub struct Calibrator {
     //detector: opencv::core::Ptr<SimpleBlobDetector>,     // 圆点detector
     detector: opencv::core::Ptr<opencv::features2d::Feature2D>, // 圆点detector
     error_threshold: f64,             // 重投影误差阈值
+    mono_flag_combos: Vec<CalibFlagCombo>, // 单目标定候选标志组合（取RMS最小者）
+    grid_order_strategy: GridOrderStrategy, // 圆点排序策略（默认与合像检测一致）
+    column_swap_margin_px: f32,       // 奇偶列交换判定的滞回余量(px)，仅 ColumnSwap 策略使用
+    last_column_swap: Option<bool>,   // 上一帧的列交换判定（余量内沿用，避免逐帧跳变）
+    detection_budget: GridDetectionBudget, // 圆点网格检测的尝试次数/总耗时上限
+    min_valid_images: usize,          // 参与标定所需的最少有效图像数
+}
+
+/// 圆点网格检测的逐级尝试总数（基本 / +CLUSTERING / 交换行列 / 交换行列+CLUSTERING）
+pub const GRID_DETECTION_ATTEMPTS: usize = 4;
+
+/// 圆点网格检测预算
+/// 
+/// 单次尝试无法中断，耗时在每次尝试开始前检查；time_budget_ms 为 0 表示不限时
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GridDetectionBudget {
+    pub max_attempts: usize,    // 最多尝试次数 (1-4)，按原有放宽顺序截断
+    pub time_budget_ms: u64,    // 总耗时上限 (ms)
+}
+
+impl Default for GridDetectionBudget {
+    fn default() -> Self {
+        Self { max_attempts: GRID_DETECTION_ATTEMPTS, time_budget_ms: 1500 }
+    }
+}
+
+impl GridDetectionBudget {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_attempts == 0 || self.max_attempts > GRID_DETECTION_ATTEMPTS {
+            return Err(format!("检测尝试次数必须在1-{}范围内: {}", GRID_DETECTION_ATTEMPTS, self.max_attempts));
+        }
+        Ok(())
+    }
+
+    /// 已用时间是否超出预算
+    pub fn is_exhausted(&self, elapsed: Duration) -> bool {
+        self.time_budget_ms > 0 && elapsed >= Duration::from_millis(self.time_budget_ms)
+    }
+}
+
+/// 奇偶列交换判定的默认滞回余量(px)
+pub const DEFAULT_COLUMN_SWAP_MARGIN_PX: f32 = 5.0;
+
+/// 单目标定标志组合
+#[derive(Debug, Clone)]
+pub struct CalibFlagCombo {
+    pub name: String,   // 组合名称（用于日志和结果报告）
+    pub flags: i32,     // calibrate_camera 标志位
+}
+
+impl CalibFlagCombo {
+    pub fn new(name: &str, flags: i32) -> Self {
+        Self { name: name.to_string(), flags }
+    }
+
+    /// 默认A/B组合：固定主点 vs 自由主点
+    pub fn default_ab_list() -> Vec<Self> {
+        vec![
+            Self::new("A-固定主点", calib3d::CALIB_FIX_K3 | calib3d::CALIB_FIX_PRINCIPAL_POINT | calib3d::CALIB_USE_INTRINSIC_GUESS),
+            Self::new("B-自由主点", calib3d::CALIB_FIX_K3 | calib3d::CALIB_USE_INTRINSIC_GUESS),
+        ]
+    }
+
+    /// 扩展组合：A/B + 放开K3 + 有理畸变模型（适用于广角/难收敛数据集）
+    pub fn extended_list() -> Vec<Self> {
+        let mut list = Self::default_ab_list();
+        list.push(Self::new("C-自由主点+K3", calib3d::CALIB_USE_INTRINSIC_GUESS));
+        list.push(Self::new("D-有理模型", calib3d::CALIB_RATIONAL_MODEL | calib3d::CALIB_USE_INTRINSIC_GUESS));
+        list
+    }
+}
+
+/// 多标志组合单目标定结果
+pub struct MonoFlagSearchResult {
+    pub result: MonoCalibResult,        // 最优组合的标定结果（已按误差阈值判定）
+    pub winner: String,                 // 最优组合名称
+    pub rms_by_combo: Vec<(String, f64)>, // 各组合RMS误差
 }
 
 impl Calibrator {
@@ -97,6 +220,12 @@ impl Calibrator {
             pattern_size,
             detector,
             error_threshold,
+            mono_flag_combos: CalibFlagCombo::default_ab_list(),
+            grid_order_strategy: GridOrderStrategy::default(),
+            column_swap_margin_px: DEFAULT_COLUMN_SWAP_MARGIN_PX,
+            last_column_swap: None,
+            detection_budget: GridDetectionBudget::default(),
+            min_valid_images: DEFAULT_MIN_VALID_IMAGES,
         })
     }
 
@@ -148,36 +277,27 @@ impl Calibrator {
         Ok(world_points)
     }
 
-    /// 根据固定的坐标清单生成世界坐标点
-    /// 坐标清单中x = diagonal_spacing / √2，其中diagonal_spacing = 25mm
+    /// 按点序约定生成世界坐标点（序号0在右上角）
+    /// 
+    /// pattern_size = (每列点数, 列数)，默认4×10；序号 i 位于列 c = i / 每列点数（从右到左）、
+    /// 列内第 j = i % 每列点数 个点（从上到下），坐标 ((列数-1-c)·x, (2j + c%2)·x)，
+    /// 其中x = diagonal_spacing / √2（diagonal_spacing = 25mm 时 x ≈ 17.68mm）。
+    /// 4×10 时与原固定坐标清单 (9,0),(9,2),(9,4),(9,6),(8,1),...,(0,7) 完全一致
     pub fn generate_world_points_from_list(&self) -> Result<Vector<Point3f>, opencv::Error> {
         let x = self.center_distance / (2.0_f32.sqrt()); // x ≈ 17.68mm
-        let mut world_points = Vector::<Point3f>::new();
-
-        // 按照坐标清单顺序生成点（序号0在右上角）
-        let coordinates = [
-            (9.0, 0.0), (9.0, 2.0), (9.0, 4.0), (9.0, 6.0), // 0-3
-            (8.0, 1.0), (8.0, 3.0), (8.0, 5.0), (8.0, 7.0), // 4-7
-            (7.0, 0.0), (7.0, 2.0), (7.0, 4.0), (7.0, 6.0), // 8-11
-            (6.0, 1.0), (6.0, 3.0), (6.0, 5.0), (6.0, 7.0), // 12-15
-            (5.0, 0.0), (5.0, 2.0), (5.0, 4.0), (5.0, 6.0), // 16-19
-            (4.0, 1.0), (4.0, 3.0), (4.0, 5.0), (4.0, 7.0), // 20-23
-            (3.0, 0.0), (3.0, 2.0), (3.0, 4.0), (3.0, 6.0), // 24-27
-            (2.0, 1.0), (2.0, 3.0), (2.0, 5.0), (2.0, 7.0), // 28-31
-            (1.0, 0.0), (1.0, 2.0), (1.0, 4.0), (1.0, 6.0), // 32-35
-            (0.0, 1.0), (0.0, 3.0), (0.0, 5.0), (0.0, 7.0), // 36-39
-        ];
+        let per_column = self.pattern_size.width.max(0) as usize;
+        let columns = self.pattern_size.height.max(0) as usize;
+        let mut world_points = Vector::<Point3f>::with_capacity(per_column * columns);
 
-        println!("=== 根据固定坐标清单生成世界坐标 ===");
+        println!("=== 根据点序约定生成世界坐标 ({}×{}) ===", per_column, columns);
         println!("diagonal spacing = {:.2}mm, 基础单位 x = {:.2}mm", self.center_distance, x);
 
-        for (i, &(col, row)) in coordinates.iter().enumerate() {
-            let world_x = col * x;
-            let world_y = row * x;
-            let world_z = 0.0;
-            world_points.push(Point3f::new(world_x, world_y, world_z));
-            
-            //println!("世界坐标点{}: ({:.1}, {:.1}, {:.1}) mm", i, world_x, world_y, world_z);
+        for i in 0..per_column * columns {
+            let c = i / per_column;
+            let j = i % per_column;
+            let world_x = (columns - 1 - c) as f32 * x;
+            let world_y = (2 * j + c % 2) as f32 * x;
+            world_points.push(Point3f::new(world_x, world_y, 0.0));
         }
 
         println!("总共生成了 {} 个世界坐标点", world_points.len());
@@ -243,19 +363,66 @@ impl Calibrator {
         println!("尝试检测 asymmetric circles grid，模式尺寸: {}x{} (cols x rows)", 
                  self.pattern_size.width, self.pattern_size.height);
 
-        // 第一次尝试：使用基本参数 + 自定义detector（必须提供detector）
-        println!("第一次尝试：ASYMMETRIC_GRID + 自定义detector...");
-        let result = calib3d::find_circles_grid(
-            image, 
-            self.pattern_size, 
-            &mut centers, 
-            CALIB_CB_ASYMMETRIC_GRID, 
-            Some(&self.detector),  // 必须提供detector
-            calib3d::CirclesGridFinderParameters::default()?
-        )?;
+        // 逐级放宽的检测尝试：基本 → +CLUSTERING → 交换行列 → 交换行列+CLUSTERING，
+        // 按检测预算限制尝试次数与总耗时，无望的帧尽快放弃（避免采集预览卡顿）
+        let swapped_size = Size::new(self.pattern_size.height, self.pattern_size.width);
+        let attempts = [
+            ("基本 ASYMMETRIC_GRID", self.pattern_size, CALIB_CB_ASYMMETRIC_GRID),
+            ("ASYMMETRIC_GRID + CLUSTERING", self.pattern_size, CALIB_CB_ASYMMETRIC_GRID | CALIB_CB_CLUSTERING),
+            ("交换行列尺寸", swapped_size, CALIB_CB_ASYMMETRIC_GRID),
+            ("交换尺寸 + CLUSTERING", swapped_size, CALIB_CB_ASYMMETRIC_GRID | CALIB_CB_CLUSTERING),
+        ];
+        let expected_points = (self.pattern_size.width * self.pattern_size.height) as usize;
+        let budget = self.detection_budget;
+        let start = Instant::now();
+        let mut attempts_made = 0;
+        let mut found = false;
+        for (index, &(name, size, flags)) in attempts.iter().take(budget.max_attempts).enumerate() {
+            if index > 0 {
+                if budget.is_exhausted(start.elapsed()) {
+                    println!("⏱️ 检测预算 {} ms 已用完，放弃剩余尝试", budget.time_budget_ms);
+                    break;
+                }
+                if index == 1 {
+                    // 后续尝试使用同一detector，斑点数量不足时不可能成功
+                    let mut keypoints = Vector::new();
+                    self.detector.detect(image, &mut keypoints, &Mat::default())?;
+                    if keypoints.len() < expected_points {
+                        println!("⏱️ 仅检测到 {} 个斑点 (需要 {})，放弃剩余尝试", keypoints.len(), expected_points);
+                        break;
+                    }
+                }
+            }
+            println!("第{}次尝试：{}...", index + 1, name);
+            attempts_made += 1;
+            if calib3d::find_circles_grid(
+                image, 
+                size, 
+                &mut centers, 
+                flags, 
+                Some(&self.detector),  // 必须提供detector
+                calib3d::CirclesGridFinderParameters::default()?
+            )? {
+                println!("✓ 成功！使用{}: {}x{}", name, size.width, size.height);
+                found = true;
+                break;
+            }
+        }
+
+        if !found {
+            return Err(opencv::Error::new(
+                opencv::core::StsError,
+                format!("检测预算内未找到圆点网格 (尝试 {}/{} 次, 耗时 {:.0} ms)。预期圆点数: {}, 请检查：\n\
+                       1. 图像中是否有清晰的圆点\n\
+                       2. 圆点数量是否为{}列x{}行\n\
+                       3. 是否为asymmetric grid布局（偶数列偏移）", 
+                       attempts_made, budget.max_attempts, start.elapsed().as_secs_f64() * 1000.0,
+                       expected_points, self.pattern_size.width, self.pattern_size.height)
+            ));
+        }
 
         // 如果检测成功且需要debug，绘制检测到的圆心
-        if result && draw_debug_image {
+        if draw_debug_image {
             let mut debug_image = image.clone();
             
             // 🔍 新增：输出前10个点的详细信息用于诊断
@@ -313,31 +480,8 @@ impl Calibrator {
             println!("=========================\n");
             
             // 绘制检测到的所有圆心
+            draw_grid_points(&mut debug_image, &centers, GridOverlayStyle::DEBUG)?;
             for (i, center) in centers.iter().enumerate() {
-                // 绘制圆心
-                imgproc::circle(
-                    &mut debug_image,
-                    opencv::core::Point::new(center.x as i32, center.y as i32),
-                    5,  // 半径
-                    opencv::core::Scalar::new(0.0, 0.0, 255.0, 0.0),  // 红色
-                    2,  // 线宽
-                    imgproc::LINE_8,
-                    0
-                )?;
-                
-                // 添加序号和坐标
-                let text = format!("{}:({:.0},{:.0})", i, center.x, center.y);
-                imgproc::put_text(
-                    &mut debug_image,
-                    &text,
-                    opencv::core::Point::new(center.x as i32 + 10, center.y as i32 + 10),
-                    imgproc::FONT_HERSHEY_SIMPLEX,
-                    0.4,  // 稍微减小字体避免重叠
-                    opencv::core::Scalar::new(0.0, 255.0, 0.0, 0.0),  // 绿色
-                    1,    // 线宽
-                    imgproc::LINE_8,
-                    false
-                )?;
                 println!("序号{}: 坐标({:.0},{:.0})", i, center.x, center.y);
             }
             // 生成带时间戳和图像信息的文件名
@@ -347,68 +491,14 @@ impl Calibrator {
                 .as_secs();
             let expected_points = (self.pattern_size.width * self.pattern_size.height) as usize;
             let success_flag = if centers.len() == expected_points { "SUCCESS" } else { "FAILED" };
-            let debug_filename = format!("debug_{}_{}_detected{}_expected{}.png", 
-                                       timestamp, success_flag, centers.len(), expected_points);
+            let debug_dir = crate::paths::debug_images_dir("circles_debug");
+            let _ = std::fs::create_dir_all(&debug_dir);
+            let debug_filename = format!("{}/debug_{}_{}_detected{}_expected{}.png", 
+                                       debug_dir, timestamp, success_flag, centers.len(), expected_points);
             
             imgcodecs::imwrite(&debug_filename, &debug_image, &Vector::new())?;
             println!("🔍 已保存圆心检测结果图像：{} (检测到{}个圆心)", debug_filename, centers.len());
-        }
-
-        if !result {
-            println!("第一次失败，第二次尝试：添加CLUSTERING...");
-            let result2 = calib3d::find_circles_grid(
-                image, 
-                self.pattern_size, 
-                &mut centers, 
-                CALIB_CB_ASYMMETRIC_GRID | CALIB_CB_CLUSTERING, 
-                Some(&self.detector),
-                calib3d::CirclesGridFinderParameters::default()?
-            )?;
-            
-            if !result2 {
-                println!("第二次失败，第三次尝试：交换行列尺寸...");
-                let swapped_size = Size::new(self.pattern_size.height, self.pattern_size.width);
-                let result3 = calib3d::find_circles_grid(
-                    image, 
-                    swapped_size, 
-                    &mut centers, 
-                    CALIB_CB_ASYMMETRIC_GRID, 
-                    Some(&self.detector),
-                    calib3d::CirclesGridFinderParameters::default()?
-                )?;
-                
-                if !result3 {
-                    println!("第三次失败，第四次尝试：交换尺寸 + CLUSTERING...");
-                    let result4 = calib3d::find_circles_grid(
-                        image, 
-                        swapped_size, 
-                        &mut centers, 
-                        CALIB_CB_ASYMMETRIC_GRID | CALIB_CB_CLUSTERING, 
-                        Some(&self.detector),
-                        calib3d::CirclesGridFinderParameters::default()?
-                    )?;
-                    
-                    if !result4 {
-                        return Err(opencv::Error::new(
-                            opencv::core::StsError,
-                            format!("所有尝试都失败了。预期圆点数: {}, 请检查：\n\
-                                   1. 图像中是否有清晰的圆点\n\
-                                   2. 圆点数量是否为{}列x{}行\n\
-                                   3. 是否为asymmetric grid布局（偶数列偏移）", 
-                                   self.pattern_size.width * self.pattern_size.height,
-                                   self.pattern_size.width, self.pattern_size.height)
-                        ));
-                    } else {
-                        println!("✓ 成功！使用交换尺寸 + CLUSTERING: {}x{}", swapped_size.width, swapped_size.height);
-                    }
-                } else {
-                    println!("✓ 成功！使用交换后的尺寸: {}x{}", swapped_size.width, swapped_size.height);
-                }
-            } else {
-                println!("✓ 成功！使用 ASYMMETRIC_GRID + CLUSTERING");
-            }
-        } else {
-            println!("✓ 成功！使用基本 ASYMMETRIC_GRID");
+            crate::paths::apply_debug_retention(&debug_dir);
         }
 
         println!("检测到的圆心数量: {}", centers.len());
@@ -440,76 +530,119 @@ impl Calibrator {
     
     /// 重新排序 asymmetric circles 以匹配世界坐标
     /// 
-    /// OpenCV的find_circles_grid可能返回不同的列顺序，
-    /// 这个函数确保输出顺序与generate_world_points_from_list一致
-    fn reorder_asymmetric_circles(&self, centers: &Vector<Point2f>) -> Result<Vector<Point2f>, opencv::Error> {
-        if centers.len() != 40 {
+    /// OpenCV的find_circles_grid可能返回不同的列顺序，统一经
+    /// [`order_asymmetric_grid`] 排序，保证与合像检测的点序及 generate_world_points_from_list 一致。
+    /// ColumnSwap 策略下奇偶列交换判定带滞回（见 column_swap_decision，仅适用于4×10旧版流程）
+    pub fn reorder_asymmetric_circles(&mut self, centers: &Vector<Point2f>) -> Result<Vector<Point2f>, opencv::Error> {
+        if centers.len() != self.pattern_size.area() as usize {
             return Ok(centers.clone());
         }
         
-        // 检查序号0和序号4的x坐标
-        let point_0 = centers.get(0)?;
-        let point_4 = centers.get(4)?;
-        
-        // 如果序号0的x坐标小于序号4，说明列顺序错了
-        // 正确情况：序号0应该在最右边，x坐标应该更大
-        if point_0.x < point_4.x {
-            println!("   检测到列顺序错误（点0.x={:.0} < 点4.x={:.0}），执行奇偶列交换...", 
-                    point_0.x, point_4.x);
-            
-            // 创建新的排序数组
-            let mut reordered = Vector::<Point2f>::new();
-            
-            // 交换相邻的奇偶列
-            // 原顺序: 0-3, 4-7, 8-11, 12-15, 16-19, 20-23, 24-27, 28-31, 32-35, 36-39
-            // 新顺序: 4-7, 0-3, 12-15, 8-11, 20-23, 16-19, 28-31, 24-27, 36-39, 32-35
-            
-            // 交换第1对列（0-3 和 4-7）
-            for i in 4..8 {
-                reordered.push(centers.get(i)?);
-            }
-            for i in 0..4 {
-                reordered.push(centers.get(i)?);
-            }
-            
-            // 交换第2对列（8-11 和 12-15）
-            for i in 12..16 {
-                reordered.push(centers.get(i)?);
-            }
-            for i in 8..12 {
-                reordered.push(centers.get(i)?);
-            }
-            
-            // 交换第3对列（16-19 和 20-23）
-            for i in 20..24 {
-                reordered.push(centers.get(i)?);
-            }
-            for i in 16..20 {
-                reordered.push(centers.get(i)?);
-            }
-            
-            // 交换第4对列（24-27 和 28-31）
-            for i in 28..32 {
-                reordered.push(centers.get(i)?);
-            }
-            for i in 24..28 {
-                reordered.push(centers.get(i)?);
-            }
-            
-            // 交换第5对列（32-35 和 36-39）
-            for i in 36..40 {
-                reordered.push(centers.get(i)?);
-            }
-            for i in 32..36 {
-                reordered.push(centers.get(i)?);
+        match self.grid_order_strategy {
+            GridOrderStrategy::PcaProjection => order_asymmetric_grid(centers, GridOrderStrategy::PcaProjection, self.pattern_size),
+            GridOrderStrategy::ColumnSwap => {
+                let point_0 = centers.get(0)?;
+                let point_4 = centers.get(4)?;
+                if self.column_swap_decision(point_0.x, point_4.x) {
+                    println!("   检测到列顺序错误（点0.x={:.0} < 点4.x={:.0}），执行奇偶列交换...", 
+                            point_0.x, point_4.x);
+                    swap_adjacent_columns(centers)
+                } else {
+                    println!("   列顺序正确（点0.x={:.0} >= 点4.x={:.0}）", point_0.x, point_4.x);
+                    Ok(centers.clone())
+                }
             }
-            
-            Ok(reordered)
+        }
+    }
+
+    /// 设置圆点排序策略
+    pub fn set_grid_order_strategy(&mut self, strategy: GridOrderStrategy) {
+        self.grid_order_strategy = strategy;
+        self.reset_column_swap_state();
+    }
+
+    pub fn get_grid_order_strategy(&self) -> GridOrderStrategy {
+        self.grid_order_strategy
+    }
+
+    /// 圆点实际直径 (mm)
+    pub fn get_diameter(&self) -> f32 {
+        self.diameter
+    }
+
+    /// 标定板规格 (每列点数, 列数)
+    pub fn get_pattern_size(&self) -> Size {
+        self.pattern_size
+    }
+
+    /// 圆心对角间距 (mm)
+    pub fn get_center_distance(&self) -> f32 {
+        self.center_distance
+    }
+
+    /// 标定图像尺寸
+    pub fn get_image_size(&self) -> Size {
+        self.image_size
+    }
+
+    /// 奇偶列交换判定（带滞回）
+    /// 
+    /// |点4.x - 点0.x| 超过余量时按大小关系判定并记住结果；
+    /// 在余量内（近似对称的取景）沿用上一帧判定，首帧无历史时退化为直接比较
+    pub fn column_swap_decision(&mut self, point_0_x: f32, point_4_x: f32) -> bool {
+        let diff = point_4_x - point_0_x;
+        let swap = if diff.abs() > self.column_swap_margin_px {
+            diff > 0.0
         } else {
-            // 顺序正确，直接返回
-            println!("   列顺序正确（点0.x={:.0} >= 点4.x={:.0}）", point_0.x, point_4.x);
-            Ok(centers.clone())
+            match self.last_column_swap {
+                Some(previous) => {
+                    println!("   点0/点4 x差 {:.1}px 在余量 {:.1}px 内，沿用上一帧判定 (交换: {})",
+                            diff, self.column_swap_margin_px, previous);
+                    previous
+                }
+                None => diff > 0.0,
+            }
+        };
+        self.last_column_swap = Some(swap);
+        swap
+    }
+
+    /// 设置圆点网格检测预算
+    pub fn set_detection_budget(&mut self, budget: GridDetectionBudget) -> Result<(), String> {
+        budget.validate()?;
+        self.detection_budget = budget;
+        Ok(())
+    }
+
+    pub fn get_detection_budget(&self) -> GridDetectionBudget {
+        self.detection_budget
+    }
+
+    /// 设置参与标定所需的最少有效图像数（检测后不足时报错，异常值剔除后至少保留该数量）
+    pub fn set_min_valid_images(&mut self, min_valid: usize) -> Result<(), String> {
+        if min_valid < MIN_VALID_IMAGES_FLOOR {
+            return Err(format!("最少有效图像数不能小于{}: {}", MIN_VALID_IMAGES_FLOOR, min_valid));
         }
+        self.min_valid_images = min_valid;
+        Ok(())
+    }
+
+    pub fn get_min_valid_images(&self) -> usize {
+        self.min_valid_images
+    }
+
+    /// 设置奇偶列交换判定的滞回余量(px)，0 表示直接比较
+    pub fn set_column_swap_margin(&mut self, margin_px: f32) -> Result<(), String> {
+        if !(margin_px >= 0.0) {
+            return Err(format!("列交换余量不能为负: {}", margin_px));
+        }
+        self.column_swap_margin_px = margin_px;
+        Ok(())
+    }
+
+    /// 清除上一帧的列交换判定（切换相机/标定板后调用）
+    pub fn reset_column_swap_state(&mut self) {
+        self.last_column_swap = None;
     }
 
     // 生成对应的 obj/img 点
@@ -535,11 +668,13 @@ impl Calibrator {
         //     let file_path = format!("{}\\{}", image_folder, file_name);
 
             // 读取图像
-            let img = imgcodecs::imread(&file_path, imgcodecs::IMREAD_COLOR)?;
-            if img.empty() {
-                println!("Unable to read {}, skipping.", file_path);
-                continue;
-            }
+            let img = match load_image_for_detection(&file_path) {
+                Ok(img) => img,
+                Err(_) => {
+                    println!("Unable to read {}, skipping.", file_path);
+                    continue;
+                }
+            };
 
             match self.find_asymmetric_circles_grid_points(&img, true) {
                 Ok(centers) => {
@@ -688,18 +823,15 @@ impl Calibrator {
         println!("  总体RMS误差: {:.4}", error);
         
         // 🔧 优化版本 - 分析per-view误差，找出异常图像
-        if !per_view_errors.empty() {
+        let per_view_errors = per_view_errors_to_vec(&per_view_errors)?;
+        if !per_view_errors.is_empty() {
             println!("  每组图像的误差:");
-            for i in 0..per_view_errors.rows() {
-                unsafe {
-                    let left_err = *per_view_errors.at_2d::<f64>(i, 0)?;
-                    let right_err = *per_view_errors.at_2d::<f64>(i, 1)?;
-                    println!("    图像对{}: 左={:.3}, 右={:.3}", i, left_err, right_err);
-                    
-                    // 如果某对图像误差特别大，给出警告
-                    if left_err > error * 2.0 || right_err > error * 2.0 {
-                        println!("    ⚠️ 图像对{}误差异常大，建议检查或剔除", i);
-                    }
+            for (i, &(left_err, right_err)) in per_view_errors.iter().enumerate() {
+                println!("    图像对{}: 左={:.3}, 右={:.3}", i, left_err, right_err);
+                
+                // 如果某对图像误差特别大，给出警告
+                if left_err > error * 2.0 || right_err > error * 2.0 {
+                    println!("    ⚠️ 图像对{}误差异常大，建议检查或剔除", i);
                 }
             }
         }
@@ -708,7 +840,7 @@ impl Calibrator {
         if error > self.error_threshold {
             Ok(StereoCalibResult::NeedRecalibration(error))
         } else {
-            Ok(StereoCalibResult::Success { r, t, error })
+            Ok(StereoCalibResult::Success { r, t, error, per_view_errors })
         }
     }
 
@@ -764,22 +896,17 @@ impl Calibrator {
         )?;
         
         // 分析误差，找出需要剔除的图像对
-        let mut errors_with_indices: Vec<(usize, f64)> = Vec::new();
-        for i in 0..per_view_errors.rows() {
-            unsafe {
-                let left_err = *per_view_errors.at_2d::<f64>(i, 0)?;
-                let right_err = *per_view_errors.at_2d::<f64>(i, 1)?;
-                let avg_err = (left_err + right_err) / 2.0;
-                errors_with_indices.push((i as usize, avg_err));
-            }
-        }
+        let first_view_errors = per_view_errors_to_vec(&per_view_errors)?;
+        let mut errors_with_indices: Vec<(usize, f64)> = first_view_errors.iter().enumerate()
+            .map(|(i, &(left_err, right_err))| (i, (left_err + right_err) / 2.0))
+            .collect();
         
         // 按误差排序
         errors_with_indices.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
         
         // 计算需要剔除的数量
         let num_to_reject = ((errors_with_indices.len() as f64) * rejection_ratio) as usize;
-        let num_to_reject = num_to_reject.max(1).min(errors_with_indices.len() - 8); // 至少保留8组
+        let num_to_reject = num_to_reject.max(1).min(errors_with_indices.len().saturating_sub(self.min_valid_images)); // 至少保留 min_valid_images 组
         
         println!("  剔除误差最大的 {} 组图像对（共 {} 组）", num_to_reject, errors_with_indices.len());
         
@@ -808,81 +935,161 @@ impl Calibrator {
         println!("  使用 {} 组图像对重新标定", filtered_obj_points.len());
         
         // 使用过滤后的数据重新标定
-        self.calibrate_stereo(
+        let result = self.calibrate_stereo(
             &filtered_obj_points,
             &filtered_left_points,
             &filtered_right_points,
             left_camera,
             right_camera
-        )
+        )?;
+        
+        // 逐视图误差按输入顺序还原：保留的图像对取重新标定的误差，被剔除的取第一次标定的误差（供界面提示重拍）
+        Ok(match result {
+            StereoCalibResult::Success { r, t, error, per_view_errors: kept_errors } => {
+                let mut kept_errors = kept_errors.into_iter();
+                let per_view_errors = first_view_errors.iter().enumerate()
+                    .map(|(i, &first)| if indices_to_keep.contains(&i) { kept_errors.next().unwrap_or(first) } else { first })
+                    .collect();
+                StereoCalibResult::Success { r, t, error, per_view_errors }
+            }
+            need_recalibration => need_recalibration,
+        })
+    }
+
+    /// 按给定内参逐视图计算重投影RMS误差 (像素)
+    /// 
+    /// 每个视图用 solvePnP 求外参后重投影，用于标定报告中定位质量差的图像
+    pub fn compute_per_view_errors(
+        &self,
+        obj_points: &Vector<Vector<Point3f>>,
+        img_points: &Vector<Vector<Point2f>>,
+        camera: &MonoCamera,
+    ) -> Result<Vec<f64>, opencv::Error> {
+        let mut errors = Vec::with_capacity(img_points.len());
+        for (objects, observed) in obj_points.iter().zip(img_points.iter()) {
+            let mut rvec = Mat::default();
+            let mut tvec = Mat::default();
+            calib3d::solve_pnp(
+                &objects, &observed, &camera.camera_matrix, &camera.dist_coeffs,
+                &mut rvec, &mut tvec, false, calib3d::SOLVEPNP_ITERATIVE,
+            )?;
+            let mut projected = Vector::<Point2f>::new();
+            calib3d::project_points(
+                &objects, &rvec, &tvec, &camera.camera_matrix, &camera.dist_coeffs,
+                &mut projected, &mut Mat::default(), 0.0,
+            )?;
+            let sum_sq: f64 = projected.iter().zip(observed.iter())
+                .map(|(p, o)| ((p.x - o.x) as f64).powi(2) + ((p.y - o.y) as f64).powi(2))
+                .sum();
+            errors.push((sum_sq / observed.len().max(1) as f64).sqrt());
+        }
+        Ok(errors)
+    }
+
+    /// 设置单目标定候选标志组合（默认为固定主点/自由主点A/B两组）
+    pub fn set_mono_flag_combos(&mut self, combos: Vec<CalibFlagCombo>) {
+        self.mono_flag_combos = combos;
+    }
+
+    /// 获取单目标定候选标志组合
+    pub fn get_mono_flag_combos(&self) -> &[CalibFlagCombo] {
+        &self.mono_flag_combos
     }
 
     /// 🔧 优化方案2: A/B对比测试主点固定策略
     /// 
-    /// 对比固定主点和自由主点两种策略，选择误差更小的方案
+    /// 依次尝试 mono_flag_combos 中的标志组合，选择误差最小的方案
+    /// (默认列表即原有的固定主点 vs 自由主点)
     pub fn calibrate_mono_with_ab_test(
         &self,
         obj_points: &Vector<Vector<Point3f>>,
         img_points: &Vector<Vector<Point2f>>,
     ) -> Result<MonoCalibResult, opencv::Error> {
-        println!("🔧 执行A/B测试：固定主点 vs 自由主点...");
-        
-        // 方案A：固定主点
-        let mut camera_matrix_a = Mat::zeros(3, 3, opencv::core::CV_64F)?.to_mat()?;
-        let focal_estimate = self.image_size.width as f64 * 1.2;
-        unsafe {
-            *camera_matrix_a.at_mut::<f64>(0)? = focal_estimate;
-            *camera_matrix_a.at_mut::<f64>(4)? = focal_estimate;
-            *camera_matrix_a.at_mut::<f64>(2)? = self.image_size.width as f64 / 2.0;
-            *camera_matrix_a.at_mut::<f64>(5)? = self.image_size.height as f64 / 2.0;
-            *camera_matrix_a.at_mut::<f64>(8)? = 1.0;
+        Ok(self.calibrate_mono_with_flag_search(obj_points, img_points)?.result)
+    }
+
+    /// 多标志组合单目标定，返回最优结果及各组合RMS
+    pub fn calibrate_mono_with_flag_search(
+        &self,
+        obj_points: &Vector<Vector<Point3f>>,
+        img_points: &Vector<Vector<Point2f>>,
+    ) -> Result<MonoFlagSearchResult, opencv::Error> {
+        if self.mono_flag_combos.is_empty() {
+            return Err(opencv::Error::new(opencv::core::StsBadArg, "标定标志组合列表为空".to_string()));
         }
+
+        println!("🔧 执行多组合标定测试（共{}组）...", self.mono_flag_combos.len());
         
-        let mut dist_coeffs_a = Mat::zeros(5, 1, opencv::core::CV_64F)?.to_mat()?;
-        let mut rvecs_a = Vector::<Mat>::new();
-        let mut tvecs_a = Vector::<Mat>::new();
+        let mut rms_by_combo = Vec::new();
+        let mut best: Option<(usize, f64, Mat, Mat)> = None;
         
-        let error_a = calib3d::calibrate_camera(
-            obj_points,
-            img_points,
-            self.image_size,
-            &mut camera_matrix_a,
-            &mut dist_coeffs_a,
-            &mut rvecs_a,
-            &mut tvecs_a,
-            calib3d::CALIB_FIX_K3 | calib3d::CALIB_FIX_PRINCIPAL_POINT | calib3d::CALIB_USE_INTRINSIC_GUESS,
-            TermCriteria::new(
-                opencv::core::TermCriteria_COUNT + opencv::core::TermCriteria_EPS,
-                100,
-                1e-8
-            )?,
-        )?;
+        for (i, combo) in self.mono_flag_combos.iter().enumerate() {
+            let (error, camera_matrix, dist_coeffs) = self.run_mono_calibration(obj_points, img_points, combo.flags)?;
+            println!("  方案{}（{}）RMS误差: {:.4}", i + 1, combo.name, error);
+            rms_by_combo.push((combo.name.clone(), error));
+            
+            let is_better = match &best {
+                Some((_, best_error, _, _)) => error < *best_error,
+                None => true,
+            };
+            if is_better {
+                best = Some((i, error, camera_matrix, dist_coeffs));
+            }
+        }
         
-        println!("  方案A（固定主点）RMS误差: {:.4}", error_a);
+        let (best_index, error, camera_matrix, dist_coeffs) = best.unwrap();
+        let winner = self.mono_flag_combos[best_index].name.clone();
+        println!("  ✅ 选择方案{}（{}）", best_index + 1, winner);
+        
+        let result = if error > self.error_threshold {
+            MonoCalibResult::NeedRecalibration(error)
+        } else {
+            MonoCalibResult::Success {
+                camera_matrix,
+                dist_coeffs,
+                error,
+            }
+        };
         
-        // 方案B：自由主点
-        let mut camera_matrix_b = Mat::zeros(3, 3, opencv::core::CV_64F)?.to_mat()?;
+        Ok(MonoFlagSearchResult {
+            result,
+            winner,
+            rms_by_combo,
+        })
+    }
+
+    /// 使用指定标志执行一次单目标定，返回 (RMS, 内参, 畸变)
+    fn run_mono_calibration(
+        &self,
+        obj_points: &Vector<Vector<Point3f>>,
+        img_points: &Vector<Vector<Point2f>>,
+        flags: i32,
+    ) -> Result<(f64, Mat, Mat), opencv::Error> {
+        let mut camera_matrix = Mat::zeros(3, 3, opencv::core::CV_64F)?.to_mat()?;
+        let focal_estimate = self.image_size.width as f64 * 1.2;
         unsafe {
-            *camera_matrix_b.at_mut::<f64>(0)? = focal_estimate;
-            *camera_matrix_b.at_mut::<f64>(4)? = focal_estimate;
-            *camera_matrix_b.at_mut::<f64>(2)? = self.image_size.width as f64 / 2.0;
-            *camera_matrix_b.at_mut::<f64>(5)? = self.image_size.height as f64 / 2.0;
-            *camera_matrix_b.at_mut::<f64>(8)? = 1.0;
+            *camera_matrix.at_mut::<f64>(0)? = focal_estimate;
+            *camera_matrix.at_mut::<f64>(4)? = focal_estimate;
+            *camera_matrix.at_mut::<f64>(2)? = self.image_size.width as f64 / 2.0;
+            *camera_matrix.at_mut::<f64>(5)? = self.image_size.height as f64 / 2.0;
+            *camera_matrix.at_mut::<f64>(8)? = 1.0;
         }
         
-        let mut dist_coeffs_b = Mat::zeros(5, 1, opencv::core::CV_64F)?.to_mat()?;
-        let mut rvecs_b = Vector::<Mat>::new();
-        let mut tvecs_b = Vector::<Mat>::new();
+        // 有理模型需要8个畸变系数
+        let dist_len = if flags & calib3d::CALIB_RATIONAL_MODEL != 0 { 8 } else { 5 };
+        let mut dist_coeffs = Mat::zeros(dist_len, 1, opencv::core::CV_64F)?.to_mat()?;
+        let mut rvecs = Vector::<Mat>::new();
+        let mut tvecs = Vector::<Mat>::new();
         
-        let error_b = calib3d::calibrate_camera(
+        let error = calib3d::calibrate_camera(
             obj_points,
             img_points,
             self.image_size,
-            &mut camera_matrix_b,
-            &mut dist_coeffs_b,
-            &mut rvecs_b,
-            &mut tvecs_b,
-            calib3d::CALIB_FIX_K3 | calib3d::CALIB_USE_INTRINSIC_GUESS,  // 不固定主点
+            &mut camera_matrix,
+            &mut dist_coeffs,
+            &mut rvecs,
+            &mut tvecs,
+            flags,
             TermCriteria::new(
                 opencv::core::TermCriteria_COUNT + opencv::core::TermCriteria_EPS,
                 100,
@@ -890,35 +1097,10 @@ impl Calibrator {
             )?,
         )?;
         
-        println!("  方案B（自由主点）RMS误差: {:.4}", error_b);
-        
-        // 选择误差更小的方案
-        if error_a <= error_b {
-            println!("  ✅ 选择方案A（固定主点）");
-            if error_a > self.error_threshold {
-                Ok(MonoCalibResult::NeedRecalibration(error_a))
-            } else {
-                Ok(MonoCalibResult::Success {
-                    camera_matrix: camera_matrix_a,
-                    dist_coeffs: dist_coeffs_a,
-                    error: error_a,
-                })
-            }
-        } else {
-            println!("  ✅ 选择方案B（自由主点）");
-            if error_b > self.error_threshold {
-                Ok(MonoCalibResult::NeedRecalibration(error_b))
-            } else {
-                Ok(MonoCalibResult::Success {
-                    camera_matrix: camera_matrix_b,
-                    dist_coeffs: dist_coeffs_b,
-                    error: error_b,
-                })
-            }
-        }
+        Ok((error, camera_matrix, dist_coeffs))
     }
 
-    /// 3.2.4 计算立体校正映射
+    /// 3.2.4 计算立体校正映射（含左右有效区域 roi1/roi2，用于评估校正覆盖率）
     pub fn compute_stereo_rectify(
         &self,
         left_camera: &MonoCamera,
@@ -955,7 +1137,7 @@ impl Calibrator {
             &mut roi2,
         )?;
 
-        Ok(RectifyMaps { r1, r2, p1, p2, q })
+        Ok(RectifyMaps { r1, r2, p1, p2, q, roi1, roi2 })
     }
 
     /// 3.2.5 计算重映射矩阵
@@ -1014,11 +1196,13 @@ impl Calibrator {
 
         for (i, image_path) in image_paths.iter().enumerate() {
             // 读取图像
-            let img = imgcodecs::imread(image_path, imgcodecs::IMREAD_COLOR)?;
-            if img.empty() {
-                println!("⚠️ 无法读取图像: {}, 跳过", image_path);
-                continue;
-            }
+            let img = match load_image_for_detection(image_path) {
+                Ok(img) => img,
+                Err(_) => {
+                    println!("⚠️ 无法读取图像: {}, 跳过", image_path);
+                    continue;
+                }
+            };
 
             println!("📷 正在处理第 {}/{} 张图像: {}", 
                     i + 1, image_paths.len(), image_path);
@@ -1046,10 +1230,11 @@ impl Calibrator {
         println!("📊 {}相机特征点检测完成: 成功处理 {}/{} 张图像", 
                 camera_type.get_prefix(), valid_images, image_paths.len());
 
-        if valid_images < 8 {
+        if valid_images < self.min_valid_images {
             return Err(opencv::Error::new(
                 opencv::core::StsError,
-                format!("有效图像数量不足: {}/8，需要至少8张有效图像进行标定", valid_images)
+                format!("有效图像数量不足: {}/{}，需要至少{}张有效图像进行标定",
+                        valid_images, self.min_valid_images, self.min_valid_images)
             ));
         }
 
@@ -1090,10 +1275,10 @@ impl Calibrator {
         &mut self,
         image_path: &str,
     ) -> Result<(bool, u32), opencv::Error> {
-        let img = imgcodecs::imread(image_path, imgcodecs::IMREAD_COLOR)?;
-        if img.empty() {
-            return Ok((false, 0));
-        }
+        let img = match load_image_for_detection(image_path) {
+            Ok(img) => img,
+            Err(_) => return Ok((false, 0)),
+        };
 
         match self.find_asymmetric_circles_grid_points(&img, false) {
             Ok(centers) => {
@@ -1112,6 +1297,55 @@ pub struct MonoCamera {
     pub dist_coeffs: Mat,
 }
 
+/// 圆心叠加绘制样式
+#[derive(Debug, Clone, Copy)]
+pub struct GridOverlayStyle {
+    pub radius: i32,
+    pub thickness: i32,
+    pub labels: bool,    // 标注序号与坐标
+    pub polyline: bool,  // 按检测顺序连线
+}
+
+impl GridOverlayStyle {
+    /// 全分辨率调试图像
+    pub const DEBUG: Self = Self { radius: 5, thickness: 2, labels: true, polyline: false };
+    /// 预览缩略图（只画圆心与连线）
+    pub const THUMBNAIL: Self = Self { radius: 2, thickness: 1, labels: false, polyline: true };
+}
+
+/// 在彩色图像上绘制检测到的圆心（红）、序号坐标与按顺序的连线（绿），centers 须与 image 同一坐标系
+pub fn draw_grid_points(image: &mut Mat, centers: &Vector<Point2f>, style: GridOverlayStyle) -> Result<(), opencv::Error> {
+    let red = opencv::core::Scalar::new(0.0, 0.0, 255.0, 0.0);
+    let green = opencv::core::Scalar::new(0.0, 255.0, 0.0, 0.0);
+    let to_point = |p: Point2f| opencv::core::Point::new(p.x.round() as i32, p.y.round() as i32);
+    
+    if style.polyline && centers.len() > 1 {
+        let points: Vector<opencv::core::Point> = centers.iter().map(to_point).collect();
+        let mut polylines = Vector::<Vector<opencv::core::Point>>::new();
+        polylines.push(points);
+        imgproc::polylines(image, &polylines, false, green, style.thickness, imgproc::LINE_8, 0)?;
+    }
+    for (i, center) in centers.iter().enumerate() {
+        imgproc::circle(image, to_point(center), style.radius, red, style.thickness, imgproc::LINE_8, 0)?;
+        if style.labels {
+            // 添加序号和坐标
+            let text = format!("{}:({:.0},{:.0})", i, center.x, center.y);
+            imgproc::put_text(
+                image,
+                &text,
+                opencv::core::Point::new(center.x as i32 + 10, center.y as i32 + 10),
+                imgproc::FONT_HERSHEY_SIMPLEX,
+                0.4,  // 稍微减小字体避免重叠
+                green,
+                1,
+                imgproc::LINE_8,
+                false
+            )?;
+        }
+    }
+    Ok(())
+}
+
 pub enum MonoCalibResult {
     Success {
         camera_matrix: Mat,
@@ -1126,14 +1360,24 @@ pub enum StereoCalibResult {
         r: Mat,
         t: Mat,
         error: f64,
+        per_view_errors: Vec<(f64, f64)>,  // 逐图像对 (左, 右) 重投影RMS误差，与输入图像对顺序一致
     },
     NeedRecalibration(f64),
 }
 
+/// stereoCalibrate 输出的 N×2 逐视图误差矩阵转为 (左, 右) 列表
+fn per_view_errors_to_vec(per_view_errors: &Mat) -> Result<Vec<(f64, f64)>, opencv::Error> {
+    (0..per_view_errors.rows())
+        .map(|i| Ok((*per_view_errors.at_2d::<f64>(i, 0)?, *per_view_errors.at_2d::<f64>(i, 1)?)))
+        .collect()
+}
+
 pub struct RectifyMaps {
     pub r1: Mat,
     pub r2: Mat,
     pub p1: Mat,
     pub p2: Mat,
     pub q: Mat,
+    pub roi1: Rect,  // 左图校正后的有效区域
+    pub roi2: Rect,  // 右图校正后的有效区域
 }
\ No newline at end of file
@@ -20,14 +20,15 @@
 //! @date 2025-01-15
 
 use std::{
+    collections::HashMap,
     path::PathBuf,
     fs,
     sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}},
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use opencv::{
-    core::{Mat, Size, Vector, Point2f, Point3f, AlgorithmHint},
+    core::{Mat, Size, Vector, Point2f, Point3f},
     imgcodecs,
     imgproc,
     prelude::*,
@@ -36,9 +37,9 @@ use opencv::{
 use serde::{Serialize, Deserialize};
 use base64::{Engine as _, engine::general_purpose};
 
-use crate::camera_manager::{SimpleCameraManager, CameraError};
+use crate::camera_manager::{SimpleCameraManager, CameraError, CameraWarmupConfig, FrameResolution};
 use crate::modules::{
-    calibration_circles::{Calibrator, CameraType, MonoCalibResult, StereoCalibResult, MonoCamera},
+    calibration_circles::{Calibrator, GridDetectionBudget, DEFAULT_MIN_VALID_IMAGES, MIN_VALID_IMAGES_FLOOR, CameraType, MonoCalibResult, StereoCalibResult, MonoCamera, load_image_for_detection, to_detection_format, draw_grid_points, GridOverlayStyle},
     param_io::*,
 };
 
@@ -47,6 +48,8 @@ use crate::modules::{
 pub enum CalibrationStatus {
     /// 未开始
     NotStarted,
+    /// 相机已启动，正在丢弃预热帧
+    WarmingUp,
     /// 正在采集图像
     Capturing,
     /// 已采集足够图像，可以开始标定
@@ -81,6 +84,57 @@ pub struct CalibrationResult {
     pub error_threshold: f64,          // 错误阈值
     pub error_message: Option<String>, // 错误信息
     pub calibration_time: String,      // 标定完成时间
+    #[serde(default)]
+    pub report: Option<CalibrationReport>, // 完整标定报告（成功时提供）
+    #[serde(default)]
+    pub per_view_errors: Vec<(f64, f64)>,  // 双目标定逐图像对 (左, 右) 重投影误差，与参与标定的图像对顺序一致
+    #[serde(default)]
+    pub worst_pair_ids: Vec<u32>,          // 误差异常、建议重拍的图像对ID（误差从大到小）
+}
+
+/// 单相机内参报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CameraIntrinsicsReport {
+    pub camera_matrix: Vec<Vec<f64>>,  // 3x3 内参矩阵
+    pub dist_coeffs: Vec<f64>,         // 畸变系数
+    pub rms_error: f64,                // 单目标定RMS误差
+}
+
+/// 单个视图（图像对）的重投影误差
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewErrorReport {
+    pub view_index: usize,
+    pub pair_id: Option<u32>,          // 对应的图像对ID（检测时有图像被跳过则无法对应）
+    pub left_rms_error: f64,
+    pub right_rms_error: f64,
+}
+
+/// 标定时使用的配置快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationConfigSnapshot {
+    pub circle_diameter: f32,
+    pub center_distance: f32,
+    pub pattern_cols: i32,
+    pub pattern_rows: i32,
+    pub error_threshold: f64,
+    pub image_width: i32,
+    pub image_height: i32,
+}
+
+/// 标定报告：内参、外参与质量指标，供前端展示标定摘要
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationReport {
+    pub left: CameraIntrinsicsReport,
+    pub right: CameraIntrinsicsReport,
+    pub rotation: Vec<Vec<f64>>,       // 右相机相对左相机的旋转矩阵 R
+    pub translation: Vec<f64>,         // 平移向量 T (mm)
+    pub baseline_mm: f64,              // 基线长度 |T|
+    pub stereo_rms_error: f64,
+    pub per_view_errors: Vec<ViewErrorReport>,
+    pub worst_view_index: Option<usize>, // 左右平均误差最大的视图
+    pub images_used: usize,            // 参与标定的图像对数量
+    pub images_rejected: usize,        // 采集后未能参与标定的图像对数量
+    pub config: CalibrationConfigSnapshot,
 }
 
 /// 预览帧数据结构
@@ -90,11 +144,683 @@ pub struct PreviewFrame {
     pub right_preview: String,  // Base64图像
     pub timestamp: String,      // 时间戳
     pub has_pattern: Option<bool>, // 可选：是否检测到标定板
+    pub pose_diversity: Option<f64>,     // 保存时：与已采集标定板的位姿差异度 (0-1，越大越好)
+    pub duplicate_warning: Option<String>, // 保存时：位姿过于相似的提示
+}
+
+/// 标定板位姿描述（基于左图圆心分布，用于判断重复位姿）
+#[derive(Debug, Clone, Copy)]
+struct BoardPoseDescriptor {
+    cx: f64,          // 质心x (相对图像宽度)
+    cy: f64,          // 质心y (相对图像高度)
+    log_scale: f64,   // ln(点分布半径 / 图像对角线)
+    angle_deg: f64,   // 主轴方向 (度，模180)
+    elongation: f64,  // 次/主轴标准差比 (反映倾斜透视)
+}
+
+impl BoardPoseDescriptor {
+    // 各分量的"明显不同"尺度：差异达到该值时距离贡献为1
+    const CENTROID_SCALE: f64 = 0.10;   // 图像尺寸的10%
+    const LOG_SCALE_SCALE: f64 = 0.20;  // 约20%缩放
+    const ANGLE_SCALE_DEG: f64 = 10.0;
+    const ELONGATION_SCALE: f64 = 0.20;
+    
+    fn from_points(points: &Vector<Point2f>, image_size: Size) -> Option<Self> {
+        let n = points.len();
+        if n < 3 {
+            return None;
+        }
+        let (w, h) = (image_size.width as f64, image_size.height as f64);
+        
+        let (mut mx, mut my) = (0.0, 0.0);
+        for p in points.iter() {
+            mx += p.x as f64;
+            my += p.y as f64;
+        }
+        mx /= n as f64;
+        my /= n as f64;
+        
+        // 二阶矩
+        let (mut sxx, mut syy, mut sxy) = (0.0, 0.0, 0.0);
+        for p in points.iter() {
+            let (dx, dy) = (p.x as f64 - mx, p.y as f64 - my);
+            sxx += dx * dx;
+            syy += dy * dy;
+            sxy += dx * dy;
+        }
+        sxx /= n as f64;
+        syy /= n as f64;
+        sxy /= n as f64;
+        
+        let trace = sxx + syy;
+        let diff = ((sxx - syy) * (sxx - syy) / 4.0 + sxy * sxy).sqrt();
+        let lambda_major = trace / 2.0 + diff;
+        let lambda_minor = (trace / 2.0 - diff).max(0.0);
+        if lambda_major <= 0.0 {
+            return None;
+        }
+        
+        Some(Self {
+            cx: mx / w,
+            cy: my / h,
+            log_scale: (trace.sqrt() / (w * w + h * h).sqrt()).ln(),
+            angle_deg: (0.5 * (2.0 * sxy).atan2(sxx - syy)).to_degrees(),
+            elongation: (lambda_minor / lambda_major).sqrt(),
+        })
+    }
+    
+    /// 位姿相似度 (0-1)，完全相同为1
+    fn similarity(&self, other: &Self) -> f64 {
+        let mut d_angle = (self.angle_deg - other.angle_deg).abs() % 180.0;
+        if d_angle > 90.0 {
+            d_angle = 180.0 - d_angle;
+        }
+        let terms = [
+            (self.cx - other.cx) / Self::CENTROID_SCALE,
+            (self.cy - other.cy) / Self::CENTROID_SCALE,
+            (self.log_scale - other.log_scale) / Self::LOG_SCALE_SCALE,
+            d_angle / Self::ANGLE_SCALE_DEG,
+            (self.elongation - other.elongation) / Self::ELONGATION_SCALE,
+        ];
+        let distance = terms.iter().map(|t| t * t).sum::<f64>().sqrt();
+        (-distance).exp()
+    }
+}
+
+/// 增量标定进度（每接受一张标定板后计算）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncrementalCalibProgress {
+    pub pair_id: u32,                  // 触发本次计算的图像对
+    pub board_count: usize,            // 参与计算的有效标定板数量
+    pub left_rms: Option<f64>,         // 左相机单目RMS (标定板不足时为None)
+    pub right_rms: Option<f64>,        // 右相机单目RMS
+    pub mean_rms: Option<f64>,         // 左右平均RMS
+    pub relative_change: Option<f64>,  // 相对上一次的变化 (负数表示下降)
+    pub improving: bool,               // RMS是否仍在明显下降
+    pub plateaued: bool,               // 是否已收敛，可停止采集
+    pub message: String,
+}
+
+/// 相机ROI偏移/尺寸对齐步长 (像素)，海康相机 OffsetX/Width 等参数要求为其整数倍
+pub const ROI_ALIGNMENT_PX: i32 = 8;
+
+/// 默认ROI外扩比例（相对标定板包围框宽高）
+pub const DEFAULT_BOARD_ROI_PADDING: f64 = 0.15;
+
+/// 预览缩略图尺寸
+const THUMBNAIL_SIZE: Size = Size { width: 200, height: 166 };
+
+/// 标注预览的最短检测间隔 (毫秒)：间隔内复用上次检测到的圆心，避免每帧全分辨率检测拖慢预览
+pub const PREVIEW_ANNOTATE_INTERVAL_MS: u64 = 500;
+
+/// 预览左右圆心
+type PreviewGridPoints = (Vector<Point2f>, Vector<Point2f>);
+
+/// 基于检测到的标定板几何的ROI建议
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardRoiSuggestion {
+    pub roi: crate::config::RoiConfig,   // 建议ROI（左右包围框并集外扩、对齐），可直接用于 apply_roi_config
+    pub left_bbox: [i32; 4],             // 左图圆心包围框 [x, y, w, h]
+    pub right_bbox: [i32; 4],            // 右图圆心包围框
+    pub image_width: i32,
+    pub image_height: i32,
+    pub padding_ratio: f64,
+    pub area_ratio: f64,                 // ROI面积 / 全图面积
+}
+
+/// 圆心包围框 [x, y, w, h]（向外取整）
+fn points_bbox(points: &Vector<Point2f>) -> Option<[i32; 4]> {
+    if points.is_empty() {
+        return None;
+    }
+    let (mut x0, mut y0, mut x1, mut y1) = (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+    for p in points.iter() {
+        x0 = x0.min(p.x);
+        y0 = y0.min(p.y);
+        x1 = x1.max(p.x);
+        y1 = y1.max(p.y);
+    }
+    let (x0, y0) = (x0.floor() as i32, y0.floor() as i32);
+    Some([x0, y0, x1.ceil() as i32 - x0, y1.ceil() as i32 - y0])
+}
+
+/// 由一组或多组圆心计算建议ROI
+/// 
+/// 取所有点集包围框的并集，按包围框宽高的 padding_ratio 外扩（圆心包围框不含圆半径，
+/// 需留出圆本身及轻微移动的余量），再按 ROI_ALIGNMENT_PX 向外对齐并限制在图像内
+pub fn suggest_board_roi_from_points(point_sets: &[&Vector<Point2f>], image_size: Size, padding_ratio: f64) -> Option<crate::config::RoiConfig> {
+    let boxes: Vec<[i32; 4]> = point_sets.iter().filter_map(|points| points_bbox(points)).collect();
+    if boxes.is_empty() {
+        return None;
+    }
+    let x0 = boxes.iter().map(|b| b[0]).min()?;
+    let y0 = boxes.iter().map(|b| b[1]).min()?;
+    let x1 = boxes.iter().map(|b| b[0] + b[2]).max()?;
+    let y1 = boxes.iter().map(|b| b[1] + b[3]).max()?;
+    
+    let pad_x = ((x1 - x0) as f64 * padding_ratio.max(0.0)).ceil() as i32;
+    let pad_y = ((y1 - y0) as f64 * padding_ratio.max(0.0)).ceil() as i32;
+    let align_down = |v: i32| v.div_euclid(ROI_ALIGNMENT_PX) * ROI_ALIGNMENT_PX;
+    let align_up = |v: i32| align_down(v + ROI_ALIGNMENT_PX - 1);
+    
+    let left = align_down(x0 - pad_x).max(0);
+    let top = align_down(y0 - pad_y).max(0);
+    let right = align_up(x1 + pad_x).min(align_down(image_size.width));
+    let bottom = align_up(y1 + pad_y).min(align_down(image_size.height));
+    if right <= left || bottom <= top {
+        return None;
+    }
+    
+    Some(crate::config::RoiConfig {
+        offset_x: left,
+        offset_y: top,
+        width: right - left,
+        height: bottom - top,
+        enabled: true,
+        applies_to_both_cameras: point_sets.len() > 1,
+    })
+}
+
+/// 默认最多保留的标定图像对数量（含缩略图，限制长时间采集的内存占用）
+pub const DEFAULT_MAX_RETAINED_PAIRS: usize = 40;
+
+/// 采集新图像对前检查保留上限
+/// 
+/// 已达上限时淘汰最早的未检测到标定板的图像对（对标定无用）并返回它，由调用方删除文件；
+/// 全部图像对都有效时拒绝采集
+pub fn enforce_capture_limit(images: &mut Vec<ImagePair>, max_pairs: usize) -> Result<Option<ImagePair>, String> {
+    if images.len() < max_pairs {
+        return Ok(None);
+    }
+    match images.iter().position(|img| !img.has_calibration_pattern) {
+        Some(index) => Ok(Some(images.remove(index))),
+        None => Err(format!(
+            "已保留 {} 组有效标定图像，达到上限 {}，请删除部分图像后再采集", images.len(), max_pairs)),
+    }
+}
+
+/// 图像对左或右误差超过双目总体RMS的该倍数时建议重拍（与 calibrate_stereo 的异常提示一致）
+pub const WORST_PAIR_ERROR_RATIO: f64 = 2.0;
+
+/// 从双目逐视图误差中选出建议重拍的图像对ID，按左右平均误差从大到小排列
+/// 
+/// per_view_errors 与 pair_ids 须一一对应，否则无法定位图像对，返回空
+pub fn select_worst_pairs(per_view_errors: &[(f64, f64)], pair_ids: &[u32], overall_rms: f64) -> Vec<u32> {
+    if per_view_errors.len() != pair_ids.len() {
+        return Vec::new();
+    }
+    let limit = overall_rms * WORST_PAIR_ERROR_RATIO;
+    let mut worst: Vec<(u32, f64)> = per_view_errors.iter().zip(pair_ids)
+        .filter(|((left, right), _)| *left > limit || *right > limit)
+        .map(|(&(left, right), &pair_id)| (pair_id, (left + right) / 2.0))
+        .collect();
+    worst.sort_by(|a, b| b.1.total_cmp(&a.1));
+    worst.into_iter().map(|(pair_id, _)| pair_id).collect()
+}
+
+/// 标定计算阶段（后台标定 calibration-progress 事件）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CalibrationStage {
+    Detect,     // 圆点检测（或读取圆心旁路文件）
+    MonoLeft,   // 左相机单目标定
+    MonoRight,  // 右相机单目标定
+    Stereo,     // 双目标定
+    Rectify,    // 立体校正与重映射矩阵
+    Save,       // 保存标定参数
+}
+
+impl CalibrationStage {
+    pub const ALL: [CalibrationStage; 6] = [
+        CalibrationStage::Detect,
+        CalibrationStage::MonoLeft,
+        CalibrationStage::MonoRight,
+        CalibrationStage::Stereo,
+        CalibrationStage::Rectify,
+        CalibrationStage::Save,
+    ];
+    
+    /// 阶段序号 (从1开始)
+    pub fn step(&self) -> usize {
+        Self::ALL.iter().position(|s| s == self).unwrap_or(0) + 1
+    }
+    
+    pub fn label(&self) -> &'static str {
+        match self {
+            CalibrationStage::Detect => "圆点检测",
+            CalibrationStage::MonoLeft => "左相机单目标定",
+            CalibrationStage::MonoRight => "右相机单目标定",
+            CalibrationStage::Stereo => "双目标定",
+            CalibrationStage::Rectify => "立体校正",
+            CalibrationStage::Save => "保存参数",
+        }
+    }
+}
+
+/// 后台标定进度 (calibration-progress 事件)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationProgress {
+    pub job_id: u64,
+    pub stage: CalibrationStage,
+    pub step: usize,         // 当前阶段序号 (从1开始)
+    pub total_steps: usize,
+    pub label: String,       // 阶段名称，供前端显示
+}
+
+impl CalibrationProgress {
+    pub fn new(job_id: u64, stage: CalibrationStage) -> Self {
+        Self {
+            job_id,
+            stage,
+            step: stage.step(),
+            total_steps: CalibrationStage::ALL.len(),
+            label: stage.label().to_string(),
+        }
+    }
+}
+
+/// 后台标定结束 (calibration-finished 事件)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationJobOutcome {
+    pub job_id: u64,
+    pub cancelled: bool,
+    pub result: Option<CalibrationResult>,  // 计算完成时的标定结果（success 可能为 false）
+    pub error: Option<String>,              // 计算失败或取消时的错误信息
+}
+
+/// 取消标定时返回的错误信息
+pub const CALIBRATION_CANCELLED: &str = "标定已取消";
+
+/// 标定计算控制：进入每个阶段前检查取消标志并回调进度
+/// 
+/// 取消只在阶段之间生效（OpenCV 标定调用本身不可中断）；进入保存阶段后不再取消，
+/// 避免参数文件只写入一部分
+pub struct CalibrationRunControl<'a> {
+    cancel: Arc<AtomicBool>,
+    on_stage: Box<dyn Fn(CalibrationStage) + 'a>,
+}
+
+impl Default for CalibrationRunControl<'_> {
+    fn default() -> Self {
+        Self::new(Arc::new(AtomicBool::new(false)), |_| {})
+    }
+}
+
+impl<'a> CalibrationRunControl<'a> {
+    pub fn new(cancel: Arc<AtomicBool>, on_stage: impl Fn(CalibrationStage) + 'a) -> Self {
+        Self { cancel, on_stage: Box::new(on_stage) }
+    }
+    
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::SeqCst)
+    }
+    
+    /// 进入阶段：已请求取消时返回 CALIBRATION_CANCELLED 错误
+    pub fn enter(&self, stage: CalibrationStage) -> Result<(), String> {
+        if self.is_cancelled() {
+            println!("⏹️ 标定在「{}」之前被取消", stage.label());
+            return Err(CALIBRATION_CANCELLED.to_string());
+        }
+        (self.on_stage)(stage);
+        Ok(())
+    }
+}
+
+/// 标定会话启动时的相机预热任务
+/// 
+/// 由 `CalibrationWorkflow::begin_session` 在工作流锁内创建，释放锁后执行 `run` 丢弃预热帧
+/// （期间可查询到 WarmingUp 状态），结束后再加锁调用 `finish_session_start`
+pub struct SessionWarmup {
+    camera: Arc<Mutex<SimpleCameraManager>>,
+    session_id: String,
+    save_directory: String,
+}
+
+impl SessionWarmup {
+    /// 丢弃启动后曝光未稳定的帧，返回丢弃的帧数
+    pub fn run(&self, on_progress: &mut dyn FnMut(u32, u32)) -> Result<u32, String> {
+        self.camera.lock().unwrap().warm_up(on_progress)
+            .map_err(|e| format!("相机预热失败: {}", e))
+    }
+}
+
+/// 标定计算输入快照
+/// 
+/// 在工作流锁内由 `CalibrationWorkflow::begin_calibration` 创建，计算过程不再访问工作流：
+/// 后台标定释放工作流锁后执行 `run`，结束后再加锁调用 `finish_calibration` 更新状态
+pub struct CalibrationRun {
+    config: CalibrationConfig,
+    valid_images: Vec<ImagePair>,             // 检测到标定板的图像对
+    captured_count: usize,                    // 已采集图像对总数（含无标定板的）
+    camera_serials: Option<(String, String)>,
+}
+
+impl CalibrationRun {
+    /// 完整标定流程实现 (基于现有calibration_circles.rs算法)
+    pub fn run(&self, control: &CalibrationRunControl) -> Result<CalibrationResult, String> {
+        println!("🔬 开始完整标定流程...");
+        let valid_images = &self.valid_images;
+        control.enter(CalibrationStage::Detect)?;
+        
+        // Step 1: 创建标定器实例，从第一个有效图像获取尺寸
+        let first_image_path = &valid_images[0].left_image_path;
+        let first_image = imgcodecs::imread(first_image_path, imgcodecs::IMREAD_GRAYSCALE)
+            .map_err(|e| format!("读取第一个图像失败: {}", e))?;
+        let image_size = Size::new(first_image.cols(), first_image.rows());
+        
+        let mut calibrator = self.config.create_calibrator(image_size)?;
+        
+        let pair_ids: Vec<u32> = valid_images.iter().map(|img| img.pair_id).collect();
+        let captured_count = self.captured_count.max(valid_images.len());
+        
+        // Step 2: 获取点坐标 - 所有图像都有圆心旁路文件时直接复用，否则检测asymmetric circle grid
+        if let Some((left_img_points, right_img_points)) = self.img_points_from_sidecars(valid_images) {
+            println!("📄 使用圆心旁路文件 ({}组)，跳过特征点检测", left_img_points.len());
+            return self.calibrate_from_points(&calibrator, &left_img_points, &right_img_points,
+                                              &pair_ids, captured_count - valid_images.len(), control);
+        }
+        
+        let left_paths: Vec<String> = valid_images.iter()
+            .map(|img| img.left_image_path.clone())
+            .collect();
+        let right_paths: Vec<String> = valid_images.iter()
+            .map(|img| img.right_image_path.clone())
+            .collect();
+        
+        let (_, left_img_points) = calibrator.detect_and_get_points_from_paths(
+            &left_paths,
+            CameraType::Left,
+        ).map_err(|e| format!("左相机特征点检测失败: {}", e))?;
+        if control.is_cancelled() {
+            return Err(CALIBRATION_CANCELLED.to_string());
+        }
+        
+        let (_, right_img_points) = calibrator.detect_and_get_points_from_paths(
+            &right_paths,
+            CameraType::Right,
+        ).map_err(|e| format!("右相机特征点检测失败: {}", e))?;
+        
+        // 检测跳过的图像计入剔除数量；有跳过时视图与 pair_id 无法一一对应
+        let detected_count = left_img_points.len().min(right_img_points.len());
+        let pair_ids = if left_img_points.len() == valid_images.len() && right_img_points.len() == valid_images.len() {
+            pair_ids
+        } else {
+            Vec::new()
+        };
+        self.calibrate_from_points(&calibrator, &left_img_points, &right_img_points,
+                                   &pair_ids, captured_count - detected_count, control)
+    }
+    
+    /// 所有有效图像对都有圆心旁路文件时，按顺序重建左右 img_points
+    fn img_points_from_sidecars(&self, valid_images: &[ImagePair]) -> Option<(Vector<Vector<Point2f>>, Vector<Vector<Point2f>>)> {
+        let mut left_img_points = Vector::<Vector<Point2f>>::new();
+        let mut right_img_points = Vector::<Vector<Point2f>>::new();
+        for img in valid_images {
+            let path = PathBuf::from(&self.config.save_directory)
+                .join(corner_sidecar_file_name(img.pair_id));
+            let sidecar = load_corner_sidecar(&path).ok()?;
+            // 旁路文件须对应同一组图像（防止目录中残留旧会话的文件）
+            if sidecar.left_image_path != img.left_image_path || sidecar.right_image_path != img.right_image_path {
+                return None;
+            }
+            left_img_points.push(pairs_to_points(&sidecar.left_points));
+            right_img_points.push(pairs_to_points(&sidecar.right_points));
+        }
+        Some((left_img_points, right_img_points))
+    }
+    
+    /// 由左右图像点执行单目+双目标定、计算校正映射并保存参数
+    /// 
+    /// pair_ids 与视图一一对应时写入报告（否则为空），images_rejected 为未参与标定的图像对数量
+    fn calibrate_from_points(
+        &self,
+        calibrator: &Calibrator,
+        left_img_points: &Vector<Vector<Point2f>>,
+        right_img_points: &Vector<Vector<Point2f>>,
+        pair_ids: &[u32],
+        images_rejected: usize,
+        control: &CalibrationRunControl,
+    ) -> Result<CalibrationResult, String> {
+        let single_obj_points = calibrator.generate_world_points_from_list()
+            .map_err(|e| format!("生成世界坐标失败: {}", e))?;
+        // 左右使用同一组世界坐标
+        let mut left_obj_points = Vector::<Vector<Point3f>>::new();
+        for _ in 0..left_img_points.len() {
+            left_obj_points.push(single_obj_points.clone());
+        }
+        
+        // Step 3: 左相机单目标定
+        control.enter(CalibrationStage::MonoLeft)?;
+        println!("📷 开始左相机单目标定...");
+        let left_result = calibrator.calibrate_mono_with_ab_test(&left_obj_points, left_img_points)
+            .map_err(|e| format!("左相机标定失败: {}", e))?;
+        let (left_camera, left_error) = match left_result {
+            MonoCalibResult::Success { camera_matrix, dist_coeffs, error } => {
+                println!("✅ 左相机标定成功，RMS误差: {:.4}", error);
+                (MonoCamera { camera_matrix, dist_coeffs }, error)
+            },
+            MonoCalibResult::NeedRecalibration(error) => {
+                return Err(format!("左相机标定失败，重投影误差: {:.4}", error));
+            }
+        };
+        
+        // Step 4: 右相机单目标定
+        control.enter(CalibrationStage::MonoRight)?;
+        println!("📷 开始右相机单目标定...");
+        let right_result = calibrator.calibrate_mono_with_ab_test(&left_obj_points, right_img_points)
+            .map_err(|e| format!("右相机标定失败: {}", e))?;
+        let (right_camera, right_error) = match right_result {
+            MonoCalibResult::Success { camera_matrix, dist_coeffs, error } => {
+                println!("✅ 右相机标定成功，RMS误差: {:.4}", error);
+                (MonoCamera { camera_matrix, dist_coeffs }, error)
+            },
+            MonoCalibResult::NeedRecalibration(error) => {
+                return Err(format!("右相机标定失败，重投影误差: {:.4}", error));
+            }
+        };
+        
+        // Step 5: 双目标定
+        control.enter(CalibrationStage::Stereo)?;
+        println!("👁️‍🗨️ 开始双目标定...");
+        let stereo_result = calibrator.calibrate_stereo_with_outlier_rejection(
+            &left_obj_points, left_img_points, right_img_points,
+            &left_camera, &right_camera,
+            0.2
+        ).map_err(|e| format!("双目标定失败: {}", e))?;
+        let (r, t, stereo_error, stereo_view_errors) = match stereo_result {
+            StereoCalibResult::Success { r, t, error, per_view_errors } => {
+                println!("✅ 双目标定成功，RMS误差: {:.4}", error);
+                (r, t, error, per_view_errors)
+            },
+            StereoCalibResult::NeedRecalibration(error) => {
+                return Err(format!("双目标定失败，重投影误差: {:.4}", error));
+            }
+        };
+        
+        // Step 6: 计算立体校正映射
+        control.enter(CalibrationStage::Rectify)?;
+        println!("🔧 计算立体校正映射...");
+        let rectify_maps = calibrator.compute_stereo_rectify(&left_camera, &right_camera, &r, &t)
+            .map_err(|e| format!("计算立体校正映射失败: {}", e))?;
+        
+        // Step 7: 计算重映射矩阵
+        println!("📐 计算重映射矩阵...");
+        let (left_map1, left_map2) = calibrator.compute_undistort_maps(
+            &left_camera.camera_matrix, &left_camera.dist_coeffs, &rectify_maps.r1, &rectify_maps.p1
+        ).map_err(|e| format!("计算左相机重映射失败: {}", e))?;
+        let (right_map1, right_map2) = calibrator.compute_undistort_maps(
+            &right_camera.camera_matrix, &right_camera.dist_coeffs, &rectify_maps.r2, &rectify_maps.p2
+        ).map_err(|e| format!("计算右相机重映射失败: {}", e))?;
+        
+        // Step 8: 保存标定参数和矩阵 (使用param_io.rs)
+        control.enter(CalibrationStage::Save)?;
+        println!("💾 保存标定参数...");
+        self.save_calibration_parameters(&left_camera, &right_camera, &r, &t, 
+                                       &rectify_maps, &left_map1, &left_map2, 
+                                       &right_map1, &right_map2)?;
+        
+        // Step 9: 生成标定报告
+        let left_view_errors = calibrator.compute_per_view_errors(&left_obj_points, left_img_points, &left_camera)
+            .map_err(|e| format!("计算左相机逐视图误差失败: {}", e))?;
+        let right_view_errors = calibrator.compute_per_view_errors(&left_obj_points, right_img_points, &right_camera)
+            .map_err(|e| format!("计算右相机逐视图误差失败: {}", e))?;
+        let per_view_errors: Vec<ViewErrorReport> = left_view_errors.iter().zip(&right_view_errors).enumerate()
+            .map(|(i, (&left_rms_error, &right_rms_error))| ViewErrorReport {
+                view_index: i,
+                pair_id: if pair_ids.len() == left_view_errors.len() { Some(pair_ids[i]) } else { None },
+                left_rms_error,
+                right_rms_error,
+            })
+            .collect();
+        let worst_view_index = per_view_errors.iter()
+            .max_by(|a, b| (a.left_rms_error + a.right_rms_error).total_cmp(&(b.left_rms_error + b.right_rms_error)))
+            .map(|v| v.view_index);
+        
+        // 双目标定的逐图像对误差（含异常值剔除时被剔除的图像对），供界面标出需重拍的图像对
+        let worst_pair_ids = select_worst_pairs(&stereo_view_errors, pair_ids, stereo_error);
+        if !worst_pair_ids.is_empty() {
+            println!("⚠️ 误差异常的图像对: {:?}，建议重拍", worst_pair_ids);
+        }
+        
+        let translation = mat_to_vec_f64(&t);
+        let baseline_mm = translation.iter().map(|v| v * v).sum::<f64>().sqrt();
+        let image_size = calibrator.get_image_size();
+        let report = CalibrationReport {
+            left: CameraIntrinsicsReport {
+                camera_matrix: mat_to_vec2d_f64(&left_camera.camera_matrix),
+                dist_coeffs: mat_to_vec_f64(&left_camera.dist_coeffs),
+                rms_error: left_error,
+            },
+            right: CameraIntrinsicsReport {
+                camera_matrix: mat_to_vec2d_f64(&right_camera.camera_matrix),
+                dist_coeffs: mat_to_vec_f64(&right_camera.dist_coeffs),
+                rms_error: right_error,
+            },
+            rotation: mat_to_vec2d_f64(&r),
+            translation,
+            baseline_mm,
+            stereo_rms_error: stereo_error,
+            per_view_errors,
+            worst_view_index,
+            images_used: left_img_points.len(),
+            images_rejected,
+            config: CalibrationConfigSnapshot {
+                circle_diameter: self.config.circle_diameter,
+                center_distance: self.config.center_distance,
+                pattern_cols: self.config.pattern_size.width,
+                pattern_rows: self.config.pattern_size.height,
+                error_threshold: self.config.error_threshold,
+                image_width: image_size.width,
+                image_height: image_size.height,
+            },
+        };
+        println!("📋 标定报告: 基线 {:.2} mm, 使用 {} 组, 剔除 {} 组",
+                 report.baseline_mm, report.images_used, report.images_rejected);
+        
+        Ok(CalibrationResult {
+            success: true,
+            left_rms_error: left_error,
+            right_rms_error: right_error,
+            stereo_rms_error: stereo_error,
+            error_threshold: self.config.error_threshold,
+            error_message: None,
+            calibration_time: chrono::Utc::now().to_rfc3339(),
+            report: Some(report),
+            per_view_errors: stereo_view_errors,
+            worst_pair_ids,
+        })
+    }
+    
+    /// 保存标定参数到文件
+    fn save_calibration_parameters(
+        &self,
+        left_camera: &MonoCamera, right_camera: &MonoCamera,
+        r: &Mat, t: &Mat,
+        rectify_maps: &crate::modules::calibration_circles::RectifyMaps,
+        left_map1: &Mat, left_map2: &Mat,
+        right_map1: &Mat, right_map2: &Mat,
+    ) -> Result<(), String> {
+        
+        // 使用数据目录下的参数目录保存（见 paths.rs）
+        let base_path = crate::paths::params_dir().to_string_lossy().to_string();
+        fs::create_dir_all(&base_path)
+            .map_err(|e| format!("创建参数目录失败: {}", e))?;
+        
+        // 保存左相机参数
+        let left_params = CameraParams {
+            camera_matrix: mat_to_vec2d_f64(&left_camera.camera_matrix),
+            dist_coeffs: mat_to_vec_f64(&left_camera.dist_coeffs),
+        };
+        save_camera_params(&format!("{}/left_camera_params.yaml", base_path), &left_params)
+            .map_err(|e| format!("保存左相机参数失败: {}", e))?;
+        
+        // 保存右相机参数
+        let right_params = CameraParams {
+            camera_matrix: mat_to_vec2d_f64(&right_camera.camera_matrix),
+            dist_coeffs: mat_to_vec_f64(&right_camera.dist_coeffs),
+        };
+        save_camera_params(&format!("{}/right_camera_params.yaml", base_path), &right_params)
+            .map_err(|e| format!("保存右相机参数失败: {}", e))?;
+        
+        // 保存双目参数
+        let stereo_params = StereoParams {
+            r: mat_to_vec2d_f64(r),
+            t: mat_to_vec_f64(t),
+        };
+        save_stereo_params(&format!("{}/stereo_params.yaml", base_path), &stereo_params)
+            .map_err(|e| format!("保存双目参数失败: {}", e))?;
+        
+        // 保存重映射参数
+        let rectify_params = RectifyParams {
+            r1: mat_to_vec2d_f64(&rectify_maps.r1),
+            r2: mat_to_vec2d_f64(&rectify_maps.r2),
+            p1: mat_to_vec2d_f64(&rectify_maps.p1),
+            p2: mat_to_vec2d_f64(&rectify_maps.p2),
+            q: mat_to_vec2d_f64(&rectify_maps.q),
+            valid_roi: Some(RectifyValidRoi {
+                image_width: left_map1.cols(),
+                image_height: left_map1.rows(),
+                left: rectify_maps.roi1.into(),
+                right: rectify_maps.roi2.into(),
+            }),
+        };
+        save_rectify_params(&format!("{}/rectify_params.yaml", base_path), &rectify_params)
+            .map_err(|e| format!("保存重映射参数失败: {}", e))?;
+        
+        // 保存重映射矩阵
+        let rectify_lr_maps = RectifyLeftRightMaps {
+            left_map1: mat_to_vec2d_f32(left_map1),
+            left_map2: mat_to_vec2d_f32(left_map2),
+            right_map1: mat_to_vec2d_f32(right_map1),
+            right_map2: mat_to_vec2d_f32(right_map2),
+        };
+        save_rectify_maps(&format!("{}/rectify_maps.yaml", base_path), &rectify_lr_maps)
+            .map_err(|e| format!("保存重映射矩阵失败: {}", e))?;
+        
+        // 记录标定所用相机序列号，供合像前校验相机是否被更换
+        match &self.camera_serials {
+            Some((left_serial, right_serial)) => {
+                let info = CalibrationCameraInfo {
+                    left_camera_serial: left_serial.clone(),
+                    right_camera_serial: right_serial.clone(),
+                    calibrated_at: chrono::Utc::now().to_rfc3339(),
+                };
+                save_calibration_camera_info(&format!("{}/{}", base_path, CALIBRATION_CAMERA_INFO_FILE), &info)
+                    .map_err(|e| format!("保存标定相机信息失败: {}", e))?;
+            }
+            None => {
+                println!("⚠️ 未设置相机序列号，本次标定不记录相机信息");
+            }
+        }
+        
+        println!("✅ 所有标定参数已保存到: {}", base_path);
+        Ok(())
+    }
 }
 
 /// 标定工作流程管理器 (即时处理版本)
 pub struct CalibrationWorkflow {
-    camera_manager: SimpleCameraManager,
+    camera_manager: Arc<Mutex<SimpleCameraManager>>,
     captured_images: Vec<ImagePair>,
     calibration_config: CalibrationConfig,
     current_status: CalibrationStatus,
@@ -102,6 +828,21 @@ pub struct CalibrationWorkflow {
     
     // 简化：即时处理模式，无需缓冲区
     should_save_next_frame: Arc<AtomicBool>,
+    
+    // 增量标定：按 pair_id 缓存的左右圆心点，避免重复检测
+    point_cache: HashMap<u32, (Vector<Point2f>, Vector<Point2f>)>,
+    cached_image_size: Option<Size>,
+    incremental_history: Vec<IncrementalCalibProgress>,
+    pending_progress: Option<IncrementalCalibProgress>,
+    
+    // 标定所用相机的序列号 (左, 右)，随标定参数一起保存
+    camera_serials: Option<(String, String)>,
+    
+    // 原始帧分辨率（由相机配置设置），取帧按此解析并校验长度
+    frame_resolution: FrameResolution,
+    
+    // 标注预览最近一次检测的时间与结果（按 PREVIEW_ANNOTATE_INTERVAL_MS 节流）
+    preview_grid_cache: Option<(Instant, Option<PreviewGridPoints>)>,
 }
 
 /// 标定配置
@@ -111,8 +852,33 @@ pub struct CalibrationConfig {
     pub center_distance: f32,          // 圆点间距 (mm)  
     pub pattern_size: Size,            // 标定板尺寸 (10x4)
     pub error_threshold: f64,          // 重投影误差阈值
-    pub target_image_count: u32,       // 目标图像数量
+    pub target_image_count: u32,       // 目标图像数量（采集达到后可开始标定）
+    pub min_valid_pairs: usize,        // 开始标定所需的最少有效图像对数量
+    pub max_retained_pairs: usize,     // 最多保留的图像对数量（不小于目标数量）
     pub save_directory: String,        // 保存目录
+    pub incremental_min_boards: usize, // 增量标定最少标定板数量
+    pub plateau_tolerance: f64,        // RMS相对变化低于该值视为无改善
+    pub plateau_patience: usize,       // 连续多少次无改善视为收敛
+    pub duplicate_similarity_threshold: f64, // 位姿相似度达到该值视为重复 (0-1)
+    pub reject_duplicate_poses: bool,  // 是否直接丢弃重复位姿（否则仅提示）
+    pub save_corner_sidecars: bool,    // 是否将检测圆心保存为JSON旁路文件（离线算法开发用）
+    pub detection_budget: GridDetectionBudget, // 圆点网格检测尝试次数/总耗时上限
+}
+
+impl CalibrationConfig {
+    /// 按本配置创建标定器
+    pub fn create_calibrator(&self, image_size: Size) -> Result<Calibrator, String> {
+        let mut calibrator = Calibrator::new(
+            image_size,
+            self.circle_diameter,
+            self.center_distance,
+            self.pattern_size,
+            self.error_threshold,
+        ).map_err(|e| format!("创建标定器失败: {}", e))?;
+        calibrator.set_detection_budget(self.detection_budget)?;
+        calibrator.set_min_valid_images(self.min_valid_pairs)?;
+        Ok(calibrator)
+    }
 }
 
 impl Default for CalibrationConfig {
@@ -123,7 +889,16 @@ impl Default for CalibrationConfig {
             pattern_size: Size::new(4, 10),  // 正确值：4列10行
             error_threshold: 1.0,            // 与测试保持一致
             target_image_count: 15,
-            save_directory: "captures".to_string(),
+            min_valid_pairs: DEFAULT_MIN_VALID_IMAGES,
+            max_retained_pairs: DEFAULT_MAX_RETAINED_PAIRS,
+            save_directory: crate::paths::captures_dir().to_string_lossy().to_string(),
+            incremental_min_boards: 3,
+            plateau_tolerance: 0.02,         // 2%
+            plateau_patience: 2,
+            duplicate_similarity_threshold: 0.8,
+            reject_duplicate_poses: false,   // 默认仅提示，由操作员决定是否删除
+            save_corner_sidecars: false,
+            detection_budget: GridDetectionBudget::default(),
         }
     }
 }
@@ -137,21 +912,44 @@ impl CalibrationWorkflow {
         let camera_manager = SimpleCameraManager::new()
             .map_err(|e| format!("SimpleCameraManager初始化失败: {}", e))?;
         
-        let workflow = Self {
-            camera_manager,
+        let workflow = Self::with_camera(camera_manager);
+        
+        println!("✅ 标定工作流程管理器初始化完成");
+        Ok(workflow)
+    }
+    
+    /// 使用指定相机管理器创建标定工作流程
+    /// 
+    /// 集成测试中传入 `SimpleCameraManager::with_backend` 回放已保存帧对，无需相机
+    pub fn with_camera(camera_manager: SimpleCameraManager) -> Self {
+        Self {
+            camera_manager: Arc::new(Mutex::new(camera_manager)),
             captured_images: Vec::new(),
             calibration_config: CalibrationConfig::default(),
             current_status: CalibrationStatus::NotStarted,
             session_id: None,
             should_save_next_frame: Arc::new(AtomicBool::new(false)),
-        };
-        
-        println!("✅ 标定工作流程管理器初始化完成");
-        Ok(workflow)
+            point_cache: HashMap::new(),
+            cached_image_size: None,
+            incremental_history: Vec::new(),
+            pending_progress: None,
+            camera_serials: None,
+            frame_resolution: FrameResolution::default(),
+            preview_grid_cache: None,
+        }
     }
     
     /// 核心方法1: 开始标定会话（即时处理）
+    /// 
+    /// 在当前线程内完成相机预热；命令层使用 `begin_session`/`finish_session_start`，预热期间不持有工作流锁
     pub fn start_calibration(&mut self) -> Result<(), String> {
+        let warmup = self.begin_session()?;
+        let result = warmup.run(&mut |_, _| {});
+        self.finish_session_start(warmup, result)
+    }
+    
+    /// 创建会话目录并启动相机，状态置为 WarmingUp，返回待执行的预热任务
+    pub fn begin_session(&mut self) -> Result<SessionWarmup, String> {
         println!("🎬 开始标定会话（即时处理）...");
         
         if self.current_status != CalibrationStatus::NotStarted {
@@ -161,7 +959,7 @@ impl CalibrationWorkflow {
         // 1. 创建会话ID和保存目录
         let session_id = format!("calibration_{}", 
             SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs());
-        let save_directory = format!("captures/calibration_{}", session_id);
+        let save_directory = crate::paths::captures_path(&format!("calibration_{}", session_id));
         fs::create_dir_all(&save_directory)
             .map_err(|e| format!("创建保存目录失败: {}", e))?;
         
@@ -172,12 +970,36 @@ impl CalibrationWorkflow {
         // }
         // println!("📷 已设置相机为标定模式");
         
-        self.camera_manager.start()
+        self.camera_manager.lock().unwrap().start()
             .map_err(|e| format!("启动相机失败: {}", e))?;
         
+        // 丢弃启动后曝光未稳定的帧，避免立即采集到偏暗的标定图像
+        self.current_status = CalibrationStatus::WarmingUp;
+        Ok(SessionWarmup {
+            camera: Arc::clone(&self.camera_manager),
+            session_id,
+            save_directory,
+        })
+    }
+    
+    /// 预热结束后进入采集状态；预热失败或期间会话已停止时停止相机并移除空的会话目录
+    pub fn finish_session_start(&mut self, warmup: SessionWarmup, result: Result<u32, String>) -> Result<(), String> {
+        if self.current_status != CalibrationStatus::WarmingUp {
+            let _ = fs::remove_dir(&warmup.save_directory);
+            return Err("相机预热期间标定会话已停止".to_string());
+        }
+        if let Err(e) = result {
+            self.current_status = CalibrationStatus::NotStarted;
+            let _ = self.camera_manager.lock().unwrap().stop();
+            let _ = fs::remove_dir(&warmup.save_directory);
+            return Err(e);
+        }
+        let SessionWarmup { session_id, save_directory, .. } = warmup;
+        
         // 3. 初始化采集会话
         self.session_id = Some(session_id.clone());
         self.captured_images.clear();
+        self.reset_incremental_state();
         self.calibration_config.save_directory = save_directory;
         self.current_status = CalibrationStatus::Capturing;
         
@@ -191,12 +1013,17 @@ impl CalibrationWorkflow {
     /// 统一的当前帧处理方法
     /// 
     /// 每次调用都获取最新帧，根据should_save_next_frame标志决定是否保存
-    fn process_current_frame(&mut self) -> Result<(PreviewFrame, Option<ImagePair>), String> {
+    fn process_current_frame(&mut self, annotate: bool) -> Result<(PreviewFrame, Option<ImagePair>), String> {
         // 检查并获取保存标志
         let should_save = self.should_save_next_frame.swap(false, Ordering::SeqCst);
         
+        // 预热期间相机由预热任务占用，不在持有工作流锁时等待
+        if self.current_status == CalibrationStatus::WarmingUp {
+            return Err("相机预热中，请稍候".to_string());
+        }
+        
         // 从camera_manager获取当前帧
-        let (left_data, right_data) = self.camera_manager.get_current_frame()
+        let (left_data, right_data) = self.camera_manager.lock().unwrap().get_current_frame()
             .map_err(|e| format!("获取当前帧失败: {:?}", e))?;
         
         // 转换为Mat
@@ -207,24 +1034,47 @@ impl CalibrationWorkflow {
         let left_preview = self.generate_thumbnail_from_mat(&left_mat)?;
         let right_preview = self.generate_thumbnail_from_mat(&right_mat)?;
         
+        // 标注预览：左右都检测到圆点网格时在缩略图上叠加圆心与连线（保存的图像对缩略图不标注）
+        let grid_points = if annotate { self.throttled_preview_grid_points(&left_mat, &right_mat) } else { None };
+        let (preview_left, preview_right) = match &grid_points {
+            Some((left_points, right_points)) => (
+                self.generate_annotated_thumbnail(&left_mat, left_points)?,
+                self.generate_annotated_thumbnail(&right_mat, right_points)?,
+            ),
+            None => (left_preview.clone(), right_preview.clone()),
+        };
+        
         let has_pattern = if should_save && self.current_status == CalibrationStatus::Capturing {
             Some(self.quick_detect_pattern_from_mats(&left_mat, &right_mat))
+        } else if annotate {
+            Some(grid_points.is_some())
         } else {
             None
         };
         
-        let preview_frame = PreviewFrame {
-            left_preview,
-            right_preview,
+        let mut preview_frame = PreviewFrame {
+            left_preview: preview_left,
+            right_preview: preview_right,
             timestamp: chrono::Utc::now().to_rfc3339(),
             has_pattern,
+            pose_diversity: None,
+            duplicate_warning: None,
         };
         
         // 如果需要保存，处理保存逻辑
         let image_pair = if should_save {
             println!("💾 执行保存逻辑（即时处理模式）");
             
-            let pair_id = self.captured_images.len() as u32 + 1;
+            // 保留数量上限：淘汰最早的无标定板图像对，全部有效时拒绝
+            let max_pairs = self.calibration_config.max_retained_pairs;
+            if let Some(evicted) = enforce_capture_limit(&mut self.captured_images, max_pairs)? {
+                let _ = fs::remove_file(&evicted.left_image_path);
+                let _ = fs::remove_file(&evicted.right_image_path);
+                println!("🗑️ 已达保留上限 {}，淘汰无标定板图像对: {}", max_pairs, evicted.pair_id);
+            }
+            
+            // 淘汰/删除后序号不复用
+            let pair_id = self.captured_images.iter().map(|img| img.pair_id).max().unwrap_or(0) + 1;
             let left_path = format!("{}/calib_left_{:02}.png", 
                 self.calibration_config.save_directory, pair_id);
             let right_path = format!("{}/calib_right_{:02}.png", 
@@ -234,15 +1084,48 @@ impl CalibrationWorkflow {
             self.save_mat_as_png(&left_mat, &left_path)?;
             self.save_mat_as_png(&right_mat, &right_path)?;
             
-            // 从保存的PNG文件检测标定板
-            let has_pattern = self.detect_calibration_pattern_from_saved_files(&left_path, &right_path)?;
+            // 从保存的PNG文件检测标定板，同时缓存圆心点供增量标定使用
+            let points = self.detect_calibration_points_from_saved_files(&left_path, &right_path)?;
+            let has_pattern = points.is_some();
+            if let Some(points) = points {
+                let image_size = Size::new(left_mat.cols(), left_mat.rows());
+                
+                // 位姿多样性检查：与已采集标定板比较
+                if let Some((diversity, similar_to)) = self.evaluate_pose_diversity(&points.0, image_size) {
+                    preview_frame.pose_diversity = Some(diversity);
+                    
+                    if let Some(similar_id) = similar_to {
+                        let warning = format!(
+                            "与第 {} 组标定板位姿过于相似 (差异度 {:.2})，请移动或倾斜标定板", 
+                            similar_id, diversity);
+                        println!("⚠️ {}", warning);
+                        preview_frame.duplicate_warning = Some(warning);
+                        
+                        if self.calibration_config.reject_duplicate_poses {
+                            let _ = fs::remove_file(&left_path);
+                            let _ = fs::remove_file(&right_path);
+                            println!("🗑️ 已丢弃重复位姿图像对: {}", pair_id);
+                            return Ok((preview_frame, None));
+                        }
+                    }
+                }
+                
+                if self.calibration_config.save_corner_sidecars {
+                    if let Err(e) = self.save_corner_sidecar(pair_id, &left_path, &right_path, image_size, &points) {
+                        println!("⚠️ 保存圆心旁路文件失败: {}", e);
+                    }
+                }
+                
+                self.cached_image_size = Some(image_size);
+                self.point_cache.insert(pair_id, points);
+            }
             
             let image_pair = ImagePair {
                 pair_id,
                 left_image_path: left_path,
                 right_image_path: right_path,
-                thumbnail_left: preview_frame.left_preview.clone(),
-                thumbnail_right: preview_frame.right_preview.clone(),
+                thumbnail_left: left_preview,
+                thumbnail_right: right_preview,
                 capture_timestamp: preview_frame.timestamp.clone(),
                 has_calibration_pattern: has_pattern,
             };
@@ -258,6 +1141,14 @@ impl CalibrationWorkflow {
             println!("✅ 标定图像对保存完成: {} (检测到标定板: {})", 
                     pair_id, has_pattern);
             
+            // 接受新标定板后更新运行RMS
+            if has_pattern {
+                match self.calibrate_incremental() {
+                    Ok(progress) => self.pending_progress = Some(progress),
+                    Err(e) => println!("⚠️ 增量标定失败: {}", e),
+                }
+            }
+            
             Some(image_pair)
         } else {
             None
@@ -270,17 +1161,18 @@ impl CalibrationWorkflow {
     /// 
     /// # 参数
     /// - `should_save`: 是否同时保存当前帧为标定图像
+    /// - `annotate`: 是否检测圆点网格并在检测到时把圆心与连线叠加到预览缩略图上
     /// 
     /// # 返回值
     /// - `PreviewFrame`: 预览帧数据
     /// - 如果 `should_save=true`，会同时保存图像并更新 `captured_images`
-    pub fn get_preview_frame_sync(&mut self, should_save: bool) -> Result<PreviewFrame, String> {
+    pub fn get_preview_frame_sync(&mut self, should_save: bool, annotate: bool) -> Result<PreviewFrame, String> {
         // 根据参数设置保存标志
         if should_save {
             self.should_save_next_frame.store(true, Ordering::SeqCst);
         }
         
-        let (preview_frame, image_pair) = self.process_current_frame()?;
+        let (preview_frame, image_pair) = self.process_current_frame(annotate)?;
         
         // 如果保存了图像，记录日志
         if let Some(pair) = image_pair {
@@ -292,12 +1184,12 @@ impl CalibrationWorkflow {
 
     /// 【已弃用】保存当前帧为标定图像
     /// 
-    /// ⚠️ **建议使用 `get_preview_frame_sync(true)` 替代**
+    /// ⚠️ **建议使用 `get_preview_frame_sync(true, false)` 替代**
     /// 
     /// 新的设计下，前端只需要调用一个方法，通过参数控制是否保存。
     #[deprecated(since = "2.2.0", note = "使用 get_preview_frame_sync(should_save) 替代")]
     pub fn save_current_frame_as_calibration(&mut self) -> Result<ImagePair, String> {
-        println!("⚠️ save_current_frame_as_calibration() 已弃用，建议使用 get_preview_frame_sync(true)");
+        println!("⚠️ save_current_frame_as_calibration() 已弃用，建议使用 get_preview_frame_sync(true, false)");
         
         if self.current_status != CalibrationStatus::Capturing {
             return Err("当前状态不允许保存标定图像".to_string());
@@ -306,7 +1198,7 @@ impl CalibrationWorkflow {
         // 设置保存标志并立即处理
         self.should_save_next_frame.store(true, Ordering::SeqCst);
         
-        let (_, image_pair) = self.process_current_frame()?;
+        let (_, image_pair) = self.process_current_frame(false)?;
         
         image_pair.ok_or("保存标定图像失败".to_string())
     }
@@ -329,6 +1221,23 @@ impl CalibrationWorkflow {
     
     /// 核心方法3: 执行标定算法
     pub fn run_calibration(&mut self) -> Result<CalibrationResult, String> {
+        self.run_calibration_with_control(&CalibrationRunControl::default())
+    }
+    
+    /// 执行标定算法，各阶段开始时回调进度，阶段之间响应取消
+    /// 
+    /// 取消后状态恢复为 ReadyToCalibrate（已采集图像保留，可重新标定）
+    pub fn run_calibration_with_control(&mut self, control: &CalibrationRunControl) -> Result<CalibrationResult, String> {
+        let run = self.begin_calibration()?;
+        let result = run.run(control);
+        self.finish_calibration(&result, control.is_cancelled());
+        result
+    }
+    
+    /// 开始标定：停止相机、检查有效图像数量并进入 Calibrating，返回标定输入快照
+    /// 
+    /// 快照的计算不占用工作流，后台标定在此之后即可释放工作流锁
+    pub fn begin_calibration(&mut self) -> Result<CalibrationRun, String> {
         println!("🚀 开始执行标定算法...");
         
         if self.current_status != CalibrationStatus::ReadyToCalibrate {
@@ -336,177 +1245,241 @@ impl CalibrationWorkflow {
         }
         
         // 1. 停止相机: self.camera_manager.stop()?
-        self.camera_manager.stop()
+        self.camera_manager.lock().unwrap().stop()
             .map_err(|e| format!("停止相机失败: {}", e))?;
         
         self.current_status = CalibrationStatus::Calibrating;
         
         // 2. 加载已保存的图像文件路径
-        let valid_images: Vec<_> = self.captured_images.iter()
+        let valid_images: Vec<ImagePair> = self.captured_images.iter()
             .filter(|img| img.has_calibration_pattern)
+            .cloned()
             .collect();
         
-        if valid_images.len() < 8 {
-            let error_msg = format!("有效图像数量不足: {}/8", valid_images.len());
+        let min_valid = self.calibration_config.min_valid_pairs;
+        if valid_images.len() < min_valid {
+            let error_msg = format!("有效图像数量不足: {}/{}", valid_images.len(), min_valid);
             self.current_status = CalibrationStatus::Failed(error_msg.clone());
             return Err(error_msg);
         }
         
-        // 3. 调用calibration_circles.rs算法
-        let result = self.run_calibration_algorithm(&valid_images)?;
-        
-        // 4. 根据结果更新状态
-        if result.success {
-            self.current_status = CalibrationStatus::Completed;
-        } else {
-            let error_msg = result.error_message.clone().unwrap_or("标定失败".to_string());
-            self.current_status = CalibrationStatus::Failed(error_msg);
-        }
-        
-        println!("✅ 标定算法执行完成: 成功={}", result.success);
-        Ok(result)
+        Ok(self.calibration_run(valid_images))
     }
     
-    /// 完整标定流程实现 (基于现有calibration_circles.rs算法)
-    fn run_calibration_algorithm(&self, valid_images: &[&ImagePair]) -> Result<CalibrationResult, String> {
-        println!("🔬 开始完整标定流程...");
-        
-        // Step 1: 创建标定器实例，从第一个有效图像获取尺寸
-        let first_image_path = &valid_images[0].left_image_path;
-        let first_image = imgcodecs::imread(first_image_path, imgcodecs::IMREAD_GRAYSCALE)
-            .map_err(|e| format!("读取第一个图像失败: {}", e))?;
-        let image_size = Size::new(first_image.cols(), first_image.rows());
-        
-        let mut calibrator = Calibrator::new(
-            image_size,  // 从实际图像获取尺寸
-            self.calibration_config.circle_diameter,     // 圆点直径
-            self.calibration_config.center_distance,     // 圆点间距
-            self.calibration_config.pattern_size,        // 标定板尺寸 (10x4)
-            self.calibration_config.error_threshold,     // 重投影误差阈值
-        ).map_err(|e| format!("创建标定器失败: {}", e))?;
-        
-        // Step 2: 获取点坐标 (检测asymmetric circle grid)
-        let left_paths: Vec<String> = valid_images.iter()
-            .map(|img| img.left_image_path.clone())
-            .collect();
-        let right_paths: Vec<String> = valid_images.iter()
-            .map(|img| img.right_image_path.clone())
-            .collect();
-        
-        let (left_obj_points, left_img_points) = calibrator.detect_and_get_points_from_paths(
-            &left_paths,
-            CameraType::Left,
-        ).map_err(|e| format!("左相机特征点检测失败: {}", e))?;
-        
-        let (right_obj_points, right_img_points) = calibrator.detect_and_get_points_from_paths(
-            &right_paths,
-            CameraType::Right,
-        ).map_err(|e| format!("右相机特征点检测失败: {}", e))?;
-        
-        // Step 3: 左相机单目标定
-        println!("📷 开始左相机单目标定...");
-        let left_result = calibrator.calibrate_mono_with_ab_test(&left_obj_points, &left_img_points)
-            .map_err(|e| format!("左相机标定失败: {}", e))?;
-        let (left_camera, left_error) = match left_result {
-            MonoCalibResult::Success { camera_matrix, dist_coeffs, error } => {
-                println!("✅ 左相机标定成功，RMS误差: {:.4}", error);
-                (MonoCamera { camera_matrix, dist_coeffs }, error)
-            },
-            MonoCalibResult::NeedRecalibration(error) => {
-                return Err(format!("左相机标定失败，重投影误差: {:.4}", error));
-            }
-        };
-        
-        // Step 4: 右相机单目标定
-        println!("📷 开始右相机单目标定...");
-        let right_result = calibrator.calibrate_mono_with_ab_test(&right_obj_points, &right_img_points)
-            .map_err(|e| format!("右相机标定失败: {}", e))?;
-        let (right_camera, right_error) = match right_result {
-            MonoCalibResult::Success { camera_matrix, dist_coeffs, error } => {
-                println!("✅ 右相机标定成功，RMS误差: {:.4}", error);
-                (MonoCamera { camera_matrix, dist_coeffs }, error)
-            },
-            MonoCalibResult::NeedRecalibration(error) => {
-                return Err(format!("右相机标定失败，重投影误差: {:.4}", error));
-            }
+    /// 标定计算结束后按结果更新状态；取消时恢复为 ReadyToCalibrate（已采集图像保留，可重新标定）
+    /// 
+    /// 计算期间会话已被停止或重新开始（状态不再是 Calibrating）时忽略结果
+    pub fn finish_calibration(&mut self, result: &Result<CalibrationResult, String>, cancelled: bool) {
+        if self.current_status != CalibrationStatus::Calibrating {
+            println!("⚠️ 标定计算期间会话状态已变为 {:?}，忽略本次标定结果", self.current_status);
+            return;
+        }
+        self.current_status = match result {
+            Ok(result) if result.success => CalibrationStatus::Completed,
+            Ok(result) => CalibrationStatus::Failed(result.error_message.clone().unwrap_or("标定失败".to_string())),
+            Err(e) if cancelled && e == CALIBRATION_CANCELLED => CalibrationStatus::ReadyToCalibrate,
+            Err(e) => CalibrationStatus::Failed(e.clone()),
         };
+        if let Ok(result) = result {
+            println!("✅ 标定算法执行完成: 成功={}", result.success);
+        }
+    }
+    
+    /// 以当前配置与相机序列号创建标定输入快照
+    fn calibration_run(&self, valid_images: Vec<ImagePair>) -> CalibrationRun {
+        CalibrationRun {
+            config: self.calibration_config.clone(),
+            captured_count: self.captured_images.len(),
+            valid_images,
+            camera_serials: self.camera_serials.clone(),
+        }
+    }
+    
+    /// 从圆心旁路文件目录重新标定（离线重标定，完全跳过圆心检测）
+    /// 
+    /// 目录中需包含 `calib_corners_XX.json`，至少 min_valid_pairs 组
+    pub fn run_calibration_from_sidecars(&mut self, directory: &str) -> Result<CalibrationResult, String> {
+        println!("📄 从圆心旁路文件重新标定: {}", directory);
+        let (sidecars, image_size) = self.load_valid_sidecars(directory)?;
+        
+        let min_valid = self.calibration_config.min_valid_pairs;
+        if sidecars.len() < min_valid {
+            return Err(format!("有效旁路文件数量不足: {}/{}", sidecars.len(), min_valid));
+        }
         
-        // Step 5: 双目标定
-        println!("👁️‍🗨️ 开始双目标定...");
-        let stereo_result = calibrator.calibrate_stereo_with_outlier_rejection(
-            &left_obj_points, &left_img_points, &right_img_points,
-            &left_camera, &right_camera,
-            0.2
-        ).map_err(|e| format!("双目标定失败: {}", e))?;
-        let (r, t, stereo_error) = match stereo_result {
-            StereoCalibResult::Success { r, t, error } => {
-                println!("✅ 双目标定成功，RMS误差: {:.4}", error);
-                (r, t, error)
-            },
-            StereoCalibResult::NeedRecalibration(error) => {
-                return Err(format!("双目标定失败，重投影误差: {:.4}", error));
-            }
+        let left_img_points: Vector<Vector<Point2f>> = sidecars.iter().map(|s| pairs_to_points(&s.left_points)).collect();
+        let right_img_points: Vector<Vector<Point2f>> = sidecars.iter().map(|s| pairs_to_points(&s.right_points)).collect();
+        let calibrator = self.create_calibrator(image_size)?;
+        let pair_ids: Vec<u32> = sidecars.iter().map(|s| s.pair_id).collect();
+        
+        self.current_status = CalibrationStatus::Calibrating;
+        let result = self.calibration_run(Vec::new()).calibrate_from_points(&calibrator, &left_img_points, &right_img_points, &pair_ids, 0,
+                                                &CalibrationRunControl::default());
+        self.current_status = match &result {
+            Ok(_) => CalibrationStatus::Completed,
+            Err(e) => CalibrationStatus::Failed(e.clone()),
         };
+        result
+    }
+    
+    /// 导出点对应关系 (世界点, 左图点, 右图点) 供外部光束法平差使用
+    /// 
+    /// 数据来自本次会话采集时缓存的检测结果 (point_cache)，按采集顺序输出，不重新检测；
+    /// 停止会话后缓存清空。output_path 默认为会话保存目录下的 calib_correspondences.json。
+    /// 返回 (输出路径, 图像对数)
+    pub fn export_point_correspondences(&self, output_path: Option<&str>) -> Result<(String, usize), String> {
+        let image_size = self.cached_image_size.ok_or("尚无缓存的检测点，请先采集含标定板的图像对")?;
+        let cached: Vec<(&ImagePair, &(Vector<Point2f>, Vector<Point2f>))> = self.captured_images.iter()
+            .filter_map(|img| self.point_cache.get(&img.pair_id).map(|points| (img, points)))
+            .collect();
+        if cached.is_empty() {
+            return Err("尚无缓存的检测点，请先采集含标定板的图像对".to_string());
+        }
         
-        // Step 6: 计算立体校正映射
-        println!("🔧 计算立体校正映射...");
-        let rectify_maps = calibrator.compute_stereo_rectify(&left_camera, &right_camera, &r, &t)
-            .map_err(|e| format!("计算立体校正映射失败: {}", e))?;
+        let world_points = self.create_calibrator(image_size)?
+            .generate_world_points_from_list()
+            .map_err(|e| format!("生成世界坐标失败: {}", e))?;
+        let to_arrays = |points: &Vector<Point2f>| points.iter().map(|p| [p.x, p.y]).collect::<Vec<_>>();
+        
+        let export = PointCorrespondenceExport {
+            image_width: image_size.width,
+            image_height: image_size.height,
+            pattern_cols: self.calibration_config.pattern_size.width,
+            pattern_rows: self.calibration_config.pattern_size.height,
+            circle_diameter_mm: self.calibration_config.circle_diameter,
+            center_distance_mm: self.calibration_config.center_distance,
+            world_points: world_points.iter().map(|p| [p.x, p.y, p.z]).collect(),
+            pair_ids: cached.iter().map(|(img, _)| img.pair_id).collect(),
+            left_image_paths: cached.iter().map(|(img, _)| img.left_image_path.clone()).collect(),
+            right_image_paths: cached.iter().map(|(img, _)| img.right_image_path.clone()).collect(),
+            left_points: cached.iter().map(|(_, (left, _))| to_arrays(left)).collect(),
+            right_points: cached.iter().map(|(_, (_, right))| to_arrays(right)).collect(),
+            exported_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        };
         
-        // Step 7: 计算重映射矩阵
-        println!("📐 计算重映射矩阵...");
-        let (left_map1, left_map2) = calibrator.compute_undistort_maps(
-            &left_camera.camera_matrix, &left_camera.dist_coeffs, &rectify_maps.r1, &rectify_maps.p1
-        ).map_err(|e| format!("计算左相机重映射失败: {}", e))?;
-        let (right_map1, right_map2) = calibrator.compute_undistort_maps(
-            &right_camera.camera_matrix, &right_camera.dist_coeffs, &rectify_maps.r2, &rectify_maps.p2
-        ).map_err(|e| format!("计算右相机重映射失败: {}", e))?;
+        let output_path = output_path.map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(&self.calibration_config.save_directory).join(POINT_CORRESPONDENCE_FILE));
+        save_point_correspondences(&output_path, &export)
+            .map_err(|e| format!("保存点对应关系失败: {}", e))?;
         
-        // Step 8: 保存标定参数和矩阵 (使用param_io.rs)
-        println!("💾 保存标定参数...");
-        self.save_calibration_parameters(&left_camera, &right_camera, &r, &t, 
-                                       &rectify_maps, &left_map1, &left_map2, 
-                                       &right_map1, &right_map2)?;
+        let output_path = output_path.to_string_lossy().to_string();
+        println!("📤 已导出 {} 组点对应关系: {}", cached.len(), output_path);
+        Ok((output_path, cached.len()))
+    }
+    
+    /// 在当前帧上检测左右标定板，返回覆盖两侧标定板的建议ROI
+    /// 
+    /// 任一侧未检测到完整网格时报错（ROI 对左右相机同时生效，必须覆盖两侧）
+    pub fn suggest_board_roi(&self, padding_ratio: f64) -> Result<BoardRoiSuggestion, String> {
+        if self.current_status == CalibrationStatus::NotStarted {
+            return Err("相机未启动，请先开始标定会话".to_string());
+        }
+        if self.current_status == CalibrationStatus::WarmingUp {
+            return Err("相机预热中，请稍候".to_string());
+        }
         
-        // 使用已提取的误差信息
+        let (left_data, right_data) = self.camera_manager.lock().unwrap().get_current_frame()
+            .map_err(|e| format!("获取当前帧失败: {:?}", e))?;
+        let left_mat = self.raw_data_to_mat(&left_data)?;
+        let right_mat = self.raw_data_to_mat(&right_data)?;
+        let image_size = Size::new(left_mat.cols(), left_mat.rows());
         
-        Ok(CalibrationResult {
-            success: true,
-            left_rms_error: left_error,
-            right_rms_error: right_error,
-            stereo_rms_error: stereo_error,
-            error_threshold: self.calibration_config.error_threshold,
-            error_message: None,
-            calibration_time: chrono::Utc::now().to_rfc3339(),
+        let mut calibrator = self.create_calibrator(image_size)?;
+        let expected_points = (self.calibration_config.pattern_size.width
+            * self.calibration_config.pattern_size.height) as usize;
+        let mut detect = |image: &Mat, side: &str| -> Result<Vector<Point2f>, String> {
+            let image = to_detection_format(image)
+                .map_err(|e| format!("{}图格式转换失败: {}", side, e))?;
+            match calibrator.find_asymmetric_circles_grid_points(&image, false) {
+                Ok(centers) if centers.len() == expected_points => Ok(centers),
+                Ok(centers) => Err(format!("{}图标定板检测不完整: {}/{} 个点", side, centers.len(), expected_points)),
+                Err(e) => Err(format!("{}图未检测到标定板: {}", side, e)),
+            }
+        };
+        let left_points = detect(&left_mat, "左")?;
+        let right_points = detect(&right_mat, "右")?;
+        
+        let roi = suggest_board_roi_from_points(&[&left_points, &right_points], image_size, padding_ratio)
+            .ok_or_else(|| "无法由检测结果计算ROI".to_string())?;
+        let area_ratio = (roi.width as f64 * roi.height as f64)
+            / (image_size.width as f64 * image_size.height as f64);
+        println!("📐 建议ROI: x={}, y={}, w={}, h={} (占全图 {:.1}%)",
+                 roi.offset_x, roi.offset_y, roi.width, roi.height, area_ratio * 100.0);
+        
+        Ok(BoardRoiSuggestion {
+            roi,
+            left_bbox: points_bbox(&left_points).unwrap_or_default(),
+            right_bbox: points_bbox(&right_points).unwrap_or_default(),
+            image_width: image_size.width,
+            image_height: image_size.height,
+            padding_ratio,
+            area_ratio,
         })
     }
     
-
+    /// 读取目录中点数与图案一致的圆心旁路文件（按 pair_id 排序），并校验图像尺寸一致，一个都没有时报错
+    fn load_valid_sidecars(&self, directory: &str) -> Result<(Vec<CornerSidecar>, Size), String> {
+        let sidecars = load_corner_sidecars_from_dir(directory)
+            .map_err(|e| format!("读取圆心旁路文件失败: {}", e))?;
+        
+        let expected_points = (self.calibration_config.pattern_size.width
+            * self.calibration_config.pattern_size.height) as usize;
+        let mut valid = Vec::new();
+        let mut image_size = None;
+        for sidecar in sidecars {
+            if sidecar.left_points.len() != expected_points || sidecar.right_points.len() != expected_points {
+                println!("⚠️ 旁路文件 {} 点数不符，跳过", corner_sidecar_file_name(sidecar.pair_id));
+                continue;
+            }
+            let size = Size::new(sidecar.image_width, sidecar.image_height);
+            if *image_size.get_or_insert(size) != size {
+                return Err(format!("旁路文件图像尺寸不一致: pair {}", sidecar.pair_id));
+            }
+            valid.push(sidecar);
+        }
+        match image_size {
+            Some(size) => Ok((valid, size)),
+            None => Err(format!("目录中没有可用的圆心旁路文件: {}", directory)),
+        }
+    }
+    
+    /// 按当前标定配置创建标定器
+    fn create_calibrator(&self, image_size: Size) -> Result<Calibrator, String> {
+        self.calibration_config.create_calibrator(image_size)
+    }
+    
+    /// 保存单个图像对的检测圆心为JSON旁路文件
+    fn save_corner_sidecar(
+        &self,
+        pair_id: u32,
+        left_path: &str,
+        right_path: &str,
+        image_size: Size,
+        points: &(Vector<Point2f>, Vector<Point2f>),
+    ) -> Result<(), String> {
+        let sidecar = CornerSidecar {
+            pair_id,
+            left_image_path: left_path.to_string(),
+            right_image_path: right_path.to_string(),
+            image_width: image_size.width,
+            image_height: image_size.height,
+            pattern_cols: self.calibration_config.pattern_size.width,
+            pattern_rows: self.calibration_config.pattern_size.height,
+            left_points: points_to_pairs(&points.0),
+            right_points: points_to_pairs(&points.1),
+            detected_at: chrono::Utc::now().to_rfc3339(),
+        };
+        let path = PathBuf::from(&self.calibration_config.save_directory).join(corner_sidecar_file_name(pair_id));
+        save_corner_sidecar(&path, &sidecar).map_err(|e| e.to_string())?;
+        println!("📄 已保存圆心旁路文件: {}", path.display());
+        Ok(())
+    }
     
     /// 将原始图像数据转换为OpenCV Mat
     fn raw_data_to_mat(&self, image_data: &[u8]) -> Result<Mat, String> {
-        // 根据实际数据大小推断图像尺寸
-        let data_len = image_data.len();
-        let (width, height) = match data_len {
-            5013504 => (2448, 2048),  // 全分辨率
-            1253376 => (1224, 1024),  // 1/2分辨率
-            313344 => (612, 512),     // 1/4分辨率
-            _ => {
-                // 尝试推断为正方形或常见比例
-                let sqrt_size = (data_len as f64).sqrt() as usize;
-                if sqrt_size * sqrt_size == data_len {
-                    (sqrt_size, sqrt_size)
-                } else {
-                    return Err(format!("无法识别的图像数据大小: {} bytes", data_len));
-                }
-            }
-        };
-        let expected_size = width * height;
-        
-        if image_data.len() != expected_size {
-            return Err(format!("图像数据大小不匹配: 期望 {} 字节，实际 {} 字节", 
-                expected_size, image_data.len()));
-        }
+        // 按配置的分辨率解析，长度不符直接报错（不按字节数猜测尺寸）
+        self.frame_resolution.check_frame(image_data)?;
+        let FrameResolution { width, height } = self.frame_resolution;
         
         // 创建灰度 Mat 并拷贝数据
         let mut gray_mat = Mat::new_rows_cols_with_default(height as i32, width as i32, 
@@ -519,16 +1492,9 @@ impl CalibrationWorkflow {
             std::ptr::copy_nonoverlapping(image_data.as_ptr(), mat_data, image_data.len());
         }
         
-        // 🎯 关键修复：转换为彩色图像以兼容SimpleBlobDetector
+        // 🎯 关键修复：转换为检测格式（与 load_image_for_detection 一致）
         // 解决问题：raw_data(灰度) vs imread(彩色) 的格式差异导致检测失败
-        let mut color_mat = Mat::default();
-        opencv::imgproc::cvt_color(
-            &gray_mat,
-            &mut color_mat,
-            opencv::imgproc::COLOR_GRAY2BGR,
-            0,
-            AlgorithmHint::ALGO_HINT_DEFAULT
-        )
+        let color_mat = to_detection_format(&gray_mat)
             .map_err(|e| format!("灰度转彩色失败: {}", e))?;
             
         println!("✅ raw_data_to_mat: 生成彩色图像 {}x{} (从灰度转换)", width, height);
@@ -544,17 +1510,11 @@ impl CalibrationWorkflow {
     
     /// 从保存的PNG文件检测标定板（绕过raw_data_to_mat问题）
     fn detect_calibration_pattern_from_saved_files(&self, left_path: &str, right_path: &str) -> Result<bool, String> {
-        use opencv::imgcodecs;
-        
         // 从PNG文件重新读取（与test_saved_images_fixed.rs相同的路径）
-        let left_image = imgcodecs::imread(left_path, imgcodecs::IMREAD_COLOR)
+        let left_image = load_image_for_detection(left_path)
             .map_err(|e| format!("读取左图PNG失败: {}", e))?;
-        let right_image = imgcodecs::imread(right_path, imgcodecs::IMREAD_COLOR)
+        let right_image = load_image_for_detection(right_path)
             .map_err(|e| format!("读取右图PNG失败: {}", e))?;
-            
-        if left_image.empty() || right_image.empty() {
-            return Err("读取的PNG图像为空".to_string());
-        }
         
         println!("📐 PNG图像尺寸: 左{}x{}, 右{}x{}", 
                  left_image.cols(), left_image.rows(),
@@ -564,17 +1524,35 @@ impl CalibrationWorkflow {
         self.detect_calibration_pattern_from_mat(&left_image, &right_image)
     }
 
+    /// 从保存的PNG文件检测左右圆心点，两侧都检测到完整网格时返回点集
+    fn detect_calibration_points_from_saved_files(
+        &self,
+        left_path: &str,
+        right_path: &str,
+    ) -> Result<Option<(Vector<Point2f>, Vector<Point2f>)>, String> {
+        let left_image = load_image_for_detection(left_path)
+            .map_err(|e| format!("读取左图PNG失败: {}", e))?;
+        let right_image = load_image_for_detection(right_path)
+            .map_err(|e| format!("读取右图PNG失败: {}", e))?;
+        
+        let image_size = Size::new(left_image.cols(), left_image.rows());
+        let mut calibrator = self.create_calibrator(image_size)?;
+        
+        let expected_points = (self.calibration_config.pattern_size.width 
+            * self.calibration_config.pattern_size.height) as usize;
+        let mut detect = |image: &Mat| match calibrator.find_asymmetric_circles_grid_points(image, false) {
+            Ok(centers) if centers.len() == expected_points => Some(centers),
+            _ => None,
+        };
+        
+        Ok(detect(&left_image).zip(detect(&right_image)))
+    }
+
     /// 从Mat直接检测标定板
     fn detect_calibration_pattern_from_mat(&self, left_mat: &Mat, right_mat: &Mat) -> Result<bool, String> {
         // 使用 calibration_circles.rs 的快速检测功能，动态获取图像尺寸
         let image_size = Size::new(left_mat.cols(), left_mat.rows());
-        let mut calibrator = crate::modules::calibration_circles::Calibrator::new(
-            image_size,
-            self.calibration_config.circle_diameter,
-            self.calibration_config.center_distance,
-            self.calibration_config.pattern_size,
-            self.calibration_config.error_threshold,
-        ).map_err(|e| format!("创建标定器失败: {}", e))?;
+        let mut calibrator = self.create_calibrator(image_size)?;
         
         // 检测左图
         let left_detected = calibrator.quick_detect_calibration_pattern(left_mat);
@@ -596,33 +1574,50 @@ impl CalibrationWorkflow {
             return Ok(false);
         }
         
-        // 读取图像并检测
-        let left_image = imgcodecs::imread(left_path, imgcodecs::IMREAD_GRAYSCALE)
-            .map_err(|e| format!("读取左图失败: {}", e))?;
-        let right_image = imgcodecs::imread(right_path, imgcodecs::IMREAD_GRAYSCALE)
-            .map_err(|e| format!("读取右图失败: {}", e))?;
-        
-        if left_image.empty() || right_image.empty() {
-            return Ok(false);
-        }
+        // 读取图像并检测（不可读的图像视为未检测到）
+        let (left_image, right_image) = match (load_image_for_detection(left_path), load_image_for_detection(right_path)) {
+            (Ok(left), Ok(right)) => (left, right),
+            _ => return Ok(false),
+        };
         
         self.detect_calibration_pattern_from_mat(&left_image, &right_image)
     }
     
     /// 从Mat直接生成缩略图
     fn generate_thumbnail_from_mat(&self, mat: &Mat) -> Result<String, String> {
+        let thumbnail = Self::resize_thumbnail(mat)?;
+        Self::encode_thumbnail(&thumbnail)
+    }
+    
+    /// 生成叠加圆点网格的缩略图（圆心按缩放比例换算到缩略图坐标）
+    fn generate_annotated_thumbnail(&self, mat: &Mat, centers: &Vector<Point2f>) -> Result<String, String> {
+        let thumbnail = Self::resize_thumbnail(mat)?;
+        let mut annotated = to_detection_format(&thumbnail)
+            .map_err(|e| format!("灰度转彩色失败: {}", e))?;
+        
+        let sx = THUMBNAIL_SIZE.width as f32 / mat.cols() as f32;
+        let sy = THUMBNAIL_SIZE.height as f32 / mat.rows() as f32;
+        let scaled: Vector<Point2f> = centers.iter().map(|p| Point2f::new(p.x * sx, p.y * sy)).collect();
+        draw_grid_points(&mut annotated, &scaled, GridOverlayStyle::THUMBNAIL)
+            .map_err(|e| format!("绘制圆点网格失败: {}", e))?;
+        
+        Self::encode_thumbnail(&annotated)
+    }
+    
+    /// 缩放到预览缩略图尺寸
+    fn resize_thumbnail(mat: &Mat) -> Result<Mat, String> {
         let mut thumbnail = Mat::default();
-        imgproc::resize(mat, &mut thumbnail, 
-            Size::new(200, 166),
-            0.0, 0.0, imgproc::INTER_LINEAR)
+        imgproc::resize(mat, &mut thumbnail, THUMBNAIL_SIZE, 0.0, 0.0, imgproc::INTER_LINEAR)
             .map_err(|e| format!("缩放图像失败: {}", e))?;
-        
-        // 编码为PNG
+        Ok(thumbnail)
+    }
+    
+    /// 缩略图编码为PNG并转换为Base64 data URL
+    fn encode_thumbnail(thumbnail: &Mat) -> Result<String, String> {
         let mut buffer = Vector::new();
-        imgcodecs::imencode(".png", &thumbnail, &mut buffer, &Vector::new())
+        imgcodecs::imencode(".png", thumbnail, &mut buffer, &Vector::new())
             .map_err(|e| format!("编码图像失败: {}", e))?;
         
-        // 转换为Base64
         let base64_str = general_purpose::STANDARD.encode(buffer.as_slice());
         Ok(format!("data:image/png;base64,{}", base64_str))
     }
@@ -639,68 +1634,14 @@ impl CalibrationWorkflow {
         self.generate_thumbnail_from_mat(&image)
     }
     
-    /// 保存标定参数到文件
-    fn save_calibration_parameters(
-        &self,
-        left_camera: &MonoCamera, right_camera: &MonoCamera,
-        r: &Mat, t: &Mat,
-        rectify_maps: &crate::modules::calibration_circles::RectifyMaps,
-        left_map1: &Mat, left_map2: &Mat,
-        right_map1: &Mat, right_map2: &Mat,
-    ) -> Result<(), String> {
-        
-        // 使用默认路径保存参数
-        let base_path = "yaml_last_param_file";
-        fs::create_dir_all(base_path)
-            .map_err(|e| format!("创建参数目录失败: {}", e))?;
-        
-        // 保存左相机参数
-        let left_params = CameraParams {
-            camera_matrix: mat_to_vec2d_f64(&left_camera.camera_matrix),
-            dist_coeffs: mat_to_vec_f64(&left_camera.dist_coeffs),
-        };
-        save_camera_params(&format!("{}/left_camera_params.yaml", base_path), &left_params)
-            .map_err(|e| format!("保存左相机参数失败: {}", e))?;
-        
-        // 保存右相机参数
-        let right_params = CameraParams {
-            camera_matrix: mat_to_vec2d_f64(&right_camera.camera_matrix),
-            dist_coeffs: mat_to_vec_f64(&right_camera.dist_coeffs),
-        };
-        save_camera_params(&format!("{}/right_camera_params.yaml", base_path), &right_params)
-            .map_err(|e| format!("保存右相机参数失败: {}", e))?;
-        
-        // 保存双目参数
-        let stereo_params = StereoParams {
-            r: mat_to_vec2d_f64(r),
-            t: mat_to_vec_f64(t),
-        };
-        save_stereo_params(&format!("{}/stereo_params.yaml", base_path), &stereo_params)
-            .map_err(|e| format!("保存双目参数失败: {}", e))?;
-        
-        // 保存重映射参数
-        let rectify_params = RectifyParams {
-            r1: mat_to_vec2d_f64(&rectify_maps.r1),
-            r2: mat_to_vec2d_f64(&rectify_maps.r2),
-            p1: mat_to_vec2d_f64(&rectify_maps.p1),
-            p2: mat_to_vec2d_f64(&rectify_maps.p2),
-            q: mat_to_vec2d_f64(&rectify_maps.q),
-        };
-        save_rectify_params(&format!("{}/rectify_params.yaml", base_path), &rectify_params)
-            .map_err(|e| format!("保存重映射参数失败: {}", e))?;
-        
-        // 保存重映射矩阵
-        let rectify_lr_maps = RectifyLeftRightMaps {
-            left_map1: mat_to_vec2d_f32(left_map1),
-            left_map2: mat_to_vec2d_f32(left_map2),
-            right_map1: mat_to_vec2d_f32(right_map1),
-            right_map2: mat_to_vec2d_f32(right_map2),
-        };
-        save_rectify_maps(&format!("{}/rectify_maps.yaml", base_path), &rectify_lr_maps)
-            .map_err(|e| format!("保存重映射矩阵失败: {}", e))?;
-        
-        println!("✅ 所有标定参数已保存到: {}", base_path);
-        Ok(())
+    /// 设置标定所用相机的序列号，标定完成时随参数一起保存
+    pub fn set_camera_serials(&mut self, left_serial: String, right_serial: String) {
+        self.camera_serials = Some((left_serial, right_serial));
+    }
+    
+    /// 当前标定配置
+    pub fn calibration_config(&self) -> &CalibrationConfig {
+        &self.calibration_config
     }
     
     /// 获取当前状态
@@ -710,22 +1651,38 @@ impl CalibrationWorkflow {
     
     /// 检查相机是否处于活跃状态
     pub fn is_camera_active(&self) -> bool {
-        // 检查相机是否已启动
-        // 这里假设SimpleCameraManager有相应的状态检查方法
-        // 如果没有，可以通过尝试获取一帧来判断
-        true // 临时实现，需要根据SimpleCameraManager的实际API调整
+        // 预热期间相机已启动（且由预热任务占用）
+        self.current_status == CalibrationStatus::WarmingUp || self.camera_manager.lock().unwrap().is_running()
+    }
+    
+    /// 预览标注用的圆心：距上次检测不足 PREVIEW_ANNOTATE_INTERVAL_MS 时复用上次结果
+    fn throttled_preview_grid_points(&mut self, left_mat: &Mat, right_mat: &Mat) -> Option<PreviewGridPoints> {
+        let interval = Duration::from_millis(PREVIEW_ANNOTATE_INTERVAL_MS);
+        if let Some((detected_at, points)) = &self.preview_grid_cache {
+            if detected_at.elapsed() < interval {
+                return points.clone();
+            }
+        }
+        let points = self.detect_preview_grid_points(left_mat, right_mat);
+        self.preview_grid_cache = Some((Instant::now(), points.clone()));
+        points
+    }
+    
+    /// 预览标注用：左右图都检测到完整圆点网格时返回两组圆心（不写调试图像）
+    fn detect_preview_grid_points(&self, left_mat: &Mat, right_mat: &Mat) -> Option<PreviewGridPoints> {
+        let mut calibrator = self.create_calibrator(Size::new(left_mat.cols(), left_mat.rows())).ok()?;
+        let expected = self.calibration_config.pattern_size.area() as usize;
+        let mut detect = |mat: &Mat| calibrator.find_asymmetric_circles_grid_points(mat, false).ok()
+            .filter(|centers| centers.len() == expected);
+        let left = detect(left_mat)?;
+        let right = detect(right_mat)?;
+        Some((left, right))
     }
     
     /// 快速检测标定板（内部方法）
     fn quick_detect_pattern_from_mats(&mut self, left_mat: &Mat, right_mat: &Mat) -> bool {
         // 创建临时标定器进行快速检测
-        match crate::modules::calibration_circles::Calibrator::new(
-            Size::new(left_mat.cols(), left_mat.rows()),
-            self.calibration_config.circle_diameter,
-            self.calibration_config.center_distance,
-            self.calibration_config.pattern_size,
-            self.calibration_config.error_threshold,
-        ) {
+        match self.create_calibrator(Size::new(left_mat.cols(), left_mat.rows())) {
             Ok(mut calibrator) => {
                 // 只检测左相机图像（提高性能）
                 calibrator.quick_detect_calibration_pattern(left_mat)
@@ -734,6 +1691,251 @@ impl CalibrationWorkflow {
         }
     }
     
+    /// 增量标定：使用已缓存的圆心点对当前累计的有效标定板做快速单目标定
+    /// 
+    /// 每接受一张标定板后调用，返回当前运行RMS及是否仍在改善；
+    /// 连续 plateau_patience 次相对变化低于 plateau_tolerance 视为收敛，可停止采集
+    pub fn calibrate_incremental(&mut self) -> Result<IncrementalCalibProgress, String> {
+        let image_size = self.cached_image_size.ok_or("尚无缓存的检测点")?;
+        let pair_id = self.captured_images.last().map(|img| img.pair_id).unwrap_or(0);
+        
+        // 按采集顺序收集缓存点
+        let mut left_img_points = Vector::<Vector<Point2f>>::new();
+        let mut right_img_points = Vector::<Vector<Point2f>>::new();
+        for img in self.captured_images.iter().filter(|img| img.has_calibration_pattern) {
+            if let Some((left, right)) = self.point_cache.get(&img.pair_id) {
+                left_img_points.push(left.clone());
+                right_img_points.push(right.clone());
+            }
+        }
+        let board_count = left_img_points.len();
+        let min_boards = self.calibration_config.incremental_min_boards;
+        
+        if board_count < min_boards {
+            let progress = IncrementalCalibProgress {
+                pair_id,
+                board_count,
+                left_rms: None,
+                right_rms: None,
+                mean_rms: None,
+                relative_change: None,
+                improving: true,
+                plateaued: false,
+                message: format!("有效标定板 {}/{}，暂不计算RMS", board_count, min_boards),
+            };
+            self.incremental_history.push(progress.clone());
+            return Ok(progress);
+        }
+        
+        let calibrator = Calibrator::new(
+            image_size,
+            self.calibration_config.circle_diameter,
+            self.calibration_config.center_distance,
+            self.calibration_config.pattern_size,
+            self.calibration_config.error_threshold,
+        ).map_err(|e| format!("创建标定器失败: {}", e))?;
+        
+        let single_obj_points = calibrator.generate_world_points_from_list()
+            .map_err(|e| format!("生成世界坐标失败: {}", e))?;
+        let mut obj_points = Vector::<Vector<Point3f>>::new();
+        for _ in 0..board_count {
+            obj_points.push(single_obj_points.clone());
+        }
+        
+        // 快速单目标定（不做 flag A/B，仅关注RMS趋势）
+        let mono_rms = |img_points: &Vector<Vector<Point2f>>| -> Result<f64, String> {
+            match calibrator.calibrate_mono(&obj_points, img_points)
+                .map_err(|e| format!("快速单目标定失败: {}", e))? {
+                MonoCalibResult::Success { error, .. } => Ok(error),
+                MonoCalibResult::NeedRecalibration(error) => Ok(error),
+            }
+        };
+        let left_rms = mono_rms(&left_img_points)?;
+        let right_rms = mono_rms(&right_img_points)?;
+        let mean_rms = (left_rms + right_rms) / 2.0;
+        
+        // 与上一次有效RMS比较
+        let tolerance = self.calibration_config.plateau_tolerance;
+        let previous_rms = self.incremental_history.iter().rev().find_map(|p| p.mean_rms);
+        let relative_change = previous_rms
+            .filter(|prev| *prev > 0.0)
+            .map(|prev| (mean_rms - prev) / prev);
+        let improving = relative_change.map_or(true, |change| change < -tolerance);
+        let stable = relative_change.map_or(false, |change| change.abs() < tolerance);
+        
+        // 连续稳定次数（含本次）
+        let stable_streak = if stable {
+            1 + self.incremental_history.iter().rev()
+                .take_while(|p| p.relative_change.map_or(false, |c| c.abs() < tolerance))
+                .count()
+        } else {
+            0
+        };
+        let plateaued = stable_streak >= self.calibration_config.plateau_patience;
+        
+        let message = if plateaued {
+            format!("RMS已收敛 ({:.4} px)，可停止采集", mean_rms)
+        } else if improving {
+            format!("RMS仍在下降 ({:.4} px)", mean_rms)
+        } else {
+            format!("RMS无明显改善 ({:.4} px)", mean_rms)
+        };
+        
+        println!("📈 增量标定 [{}张]: 左RMS={:.4}, 右RMS={:.4}, 变化={}, {}",
+                 board_count, left_rms, right_rms,
+                 relative_change.map_or("-".to_string(), |c| format!("{:+.1}%", c * 100.0)),
+                 message);
+        
+        let progress = IncrementalCalibProgress {
+            pair_id,
+            board_count,
+            left_rms: Some(left_rms),
+            right_rms: Some(right_rms),
+            mean_rms: Some(mean_rms),
+            relative_change,
+            improving,
+            plateaued,
+            message,
+        };
+        self.incremental_history.push(progress.clone());
+        Ok(progress)
+    }
+    
+    /// 计算新标定板相对已采集标定板的位姿差异度
+    /// 
+    /// 返回 (差异度 = 1 - 最大相似度, 相似度超过阈值时最相似的 pair_id)；
+    /// 第一张标定板差异度为1
+    fn evaluate_pose_diversity(&self, left_points: &Vector<Point2f>, image_size: Size) -> Option<(f64, Option<u32>)> {
+        let candidate = BoardPoseDescriptor::from_points(left_points, image_size)?;
+        
+        let most_similar = self.point_cache.iter()
+            .filter_map(|(pair_id, (left, _))| {
+                BoardPoseDescriptor::from_points(left, image_size)
+                    .map(|existing| (*pair_id, candidate.similarity(&existing)))
+            })
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+        
+        match most_similar {
+            Some((pair_id, similarity)) => {
+                let duplicate_of = (similarity >= self.calibration_config.duplicate_similarity_threshold)
+                    .then_some(pair_id);
+                Some((1.0 - similarity, duplicate_of))
+            }
+            None => Some((1.0, None)),
+        }
+    }
+    
+    /// 设置左右眼互换（相机1为逻辑左眼），须与合像检测一致，否则左右标定参数会错配
+    pub fn set_swap_eyes(&self, swap: bool) {
+        self.camera_manager.lock().unwrap().set_swap_eyes(swap);
+    }
+    
+    /// 设置原始帧分辨率（相机配置的图像/ROI尺寸），取帧长度不符时报错
+    pub fn set_frame_resolution(&mut self, resolution: FrameResolution) -> Result<(), String> {
+        if resolution.width == 0 || resolution.height == 0 {
+            return Err(format!("帧分辨率无效: {}×{}", resolution.width, resolution.height));
+        }
+        self.frame_resolution = resolution;
+        println!("📐 标定帧分辨率: {}×{}", resolution.width, resolution.height);
+        Ok(())
+    }
+    
+    pub fn frame_resolution(&self) -> FrameResolution {
+        self.frame_resolution
+    }
+    
+    /// 设置相机启动预热（start_calibration 时生效）
+    pub fn set_camera_warmup_config(&self, config: CameraWarmupConfig) -> Result<(), String> {
+        self.camera_manager.lock().unwrap().set_warmup_config(config)
+    }
+    
+    /// 设置相机曝光时间（微秒），启动采集时下发
+    pub fn set_exposure_time(&self, exposure_us: f32) -> Result<(), String> {
+        self.camera_manager.lock().unwrap().set_exposure_time(exposure_us).map_err(|e| e.to_string())
+    }
+    
+    /// 设置是否将检测圆心保存为JSON旁路文件
+    pub fn set_corner_sidecar_saving(&mut self, enabled: bool) {
+        self.calibration_config.save_corner_sidecars = enabled;
+        println!("⚙️ 圆心旁路文件: {}", if enabled { "启用" } else { "关闭" });
+    }
+    
+    /// 设置圆点网格检测预算（采集预览与标定检测均生效）
+    pub fn set_detection_budget(&mut self, budget: GridDetectionBudget) -> Result<(), String> {
+        budget.validate()?;
+        self.calibration_config.detection_budget = budget;
+        println!("⚙️ 圆点网格检测预算: 最多 {} 次尝试, {} ms", budget.max_attempts, budget.time_budget_ms);
+        Ok(())
+    }
+    
+    /// 设置目标图像数量与开始标定所需的最少有效图像对数量
+    /// 
+    /// 要求 3 ≤ min_valid_pairs ≤ target_image_count ≤ 保留上限；采集中修改时按新目标更新可标定状态
+    pub fn set_image_count_requirements(&mut self, target_image_count: u32, min_valid_pairs: usize) -> Result<(), String> {
+        if self.current_status == CalibrationStatus::Calibrating {
+            return Err("标定计算中，不能修改图像数量要求".to_string());
+        }
+        if min_valid_pairs < MIN_VALID_IMAGES_FLOOR {
+            return Err(format!("最少有效图像对数量不能小于{}: {}", MIN_VALID_IMAGES_FLOOR, min_valid_pairs));
+        }
+        if (target_image_count as usize) < min_valid_pairs {
+            return Err(format!("目标图像数量 {} 不能小于最少有效数量 {}", target_image_count, min_valid_pairs));
+        }
+        if target_image_count as usize > self.calibration_config.max_retained_pairs {
+            return Err(format!("目标图像数量 {} 超过保留上限 {}，请先提高保留上限",
+                               target_image_count, self.calibration_config.max_retained_pairs));
+        }
+        self.calibration_config.target_image_count = target_image_count;
+        self.calibration_config.min_valid_pairs = min_valid_pairs;
+        
+        let reached = self.captured_images.len() >= target_image_count as usize;
+        match self.current_status {
+            CalibrationStatus::Capturing if reached => self.current_status = CalibrationStatus::ReadyToCalibrate,
+            CalibrationStatus::ReadyToCalibrate if !reached => self.current_status = CalibrationStatus::Capturing,
+            _ => {}
+        }
+        println!("⚙️ 标定图像数量: 目标 {}, 最少有效 {}", target_image_count, min_valid_pairs);
+        Ok(())
+    }
+    
+    /// 设置最多保留的标定图像对数量
+    pub fn set_max_retained_pairs(&mut self, max_pairs: usize) -> Result<(), String> {
+        let target = self.calibration_config.target_image_count as usize;
+        if max_pairs < target {
+            return Err(format!("保留上限 {} 不能小于目标图像数量 {}", max_pairs, target));
+        }
+        self.calibration_config.max_retained_pairs = max_pairs;
+        println!("⚙️ 标定图像保留上限: {}", max_pairs);
+        Ok(())
+    }
+    
+    /// 设置重复位姿判定参数
+    pub fn set_duplicate_pose_policy(&mut self, similarity_threshold: f64, reject: bool) {
+        self.calibration_config.duplicate_similarity_threshold = similarity_threshold.clamp(0.0, 1.0);
+        self.calibration_config.reject_duplicate_poses = reject;
+        println!("⚙️ 重复位姿判定: 相似度阈值 {:.2}, {}", 
+                 self.calibration_config.duplicate_similarity_threshold,
+                 if reject { "丢弃" } else { "仅提示" });
+    }
+    
+    /// 取出最近一次采集产生的增量标定进度（用于事件推送，取出后清空）
+    pub fn take_pending_incremental_progress(&mut self) -> Option<IncrementalCalibProgress> {
+        self.pending_progress.take()
+    }
+    
+    /// 获取增量标定的运行RMS历史
+    pub fn get_incremental_history(&self) -> Vec<IncrementalCalibProgress> {
+        self.incremental_history.clone()
+    }
+    
+    /// 清空增量标定缓存和历史
+    fn reset_incremental_state(&mut self) {
+        self.point_cache.clear();
+        self.cached_image_size = None;
+        self.incremental_history.clear();
+        self.pending_progress = None;
+    }
+    
     /// 获取已采集的图像列表
     pub fn get_captured_images(&self) -> Vec<ImagePair> {
         self.captured_images.clone()
@@ -741,8 +1943,12 @@ impl CalibrationWorkflow {
     
     /// 删除指定的图像对
     pub fn delete_captured_image(&mut self, pair_id: u32) -> Result<(), String> {
+        if self.current_status == CalibrationStatus::Calibrating {
+            return Err("标定计算中，不能删除图像".to_string());
+        }
         if let Some(index) = self.captured_images.iter().position(|img| img.pair_id == pair_id) {
             let image_pair = self.captured_images.remove(index);
+            self.point_cache.remove(&pair_id);
             
             // 删除文件
             let _ = fs::remove_file(&image_pair.left_image_path);
@@ -767,13 +1973,14 @@ impl CalibrationWorkflow {
         
         // 1. 停止后台采集线程
         // 即时处理模式下，没有后台线程，直接停止相机
-        if let Err(e) = self.camera_manager.stop() {
+        if let Err(e) = self.camera_manager.lock().unwrap().stop() {
             println!("⚠️ 停止主相机时出错: {}", e);
         }
         
         // 2. 清理缓冲区
         // 即时处理模式下，没有缓冲区，直接清空图像列表
         self.captured_images.clear();
+        self.reset_incremental_state();
         
         // 3. 重置状态
         self.current_status = CalibrationStatus::NotStarted;
@@ -788,7 +1995,7 @@ impl CalibrationWorkflow {
 impl Drop for CalibrationWorkflow {
     fn drop(&mut self) {
         // 确保相机资源被正确释放
-        let _ = self.camera_manager.stop();
+        let _ = self.camera_manager.lock().unwrap().stop();
     }
 }
 
@@ -812,12 +2019,19 @@ impl CalibrationWorkflow {
         };
         
         Ok(Self {
-            camera_manager,
+            camera_manager: Arc::new(Mutex::new(camera_manager)),
             captured_images: Vec::new(),
             calibration_config: CalibrationConfig::default(),
             current_status: CalibrationStatus::NotStarted,
             session_id: Some("test_session".to_string()),
             should_save_next_frame: Arc::new(AtomicBool::new(false)),
+            point_cache: HashMap::new(),
+            cached_image_size: None,
+            incremental_history: Vec::new(),
+            pending_progress: None,
+            camera_serials: None,
+            frame_resolution: FrameResolution::default(),
+            preview_grid_cache: None,
         })
     }
     
@@ -832,6 +2046,13 @@ impl CalibrationWorkflow {
             current_status: CalibrationStatus::NotStarted,
             session_id: Some("offline_test".to_string()),
             should_save_next_frame: Arc::new(AtomicBool::new(false)),
+            point_cache: HashMap::new(),
+            cached_image_size: None,
+            incremental_history: Vec::new(),
+            pending_progress: None,
+            camera_serials: None,
+            frame_resolution: FrameResolution::default(),
+            preview_grid_cache: None,
         }
     }
     
@@ -851,7 +2072,7 @@ impl CalibrationWorkflow {
         println!("📊 使用 {} 组有效图像", valid_images.len());
         
         // 直接调用内部的标定算法
-        self.run_calibration_algorithm(&valid_images)
+        self.calibration_run(valid_images.into_iter().cloned().collect()).run(&CalibrationRunControl::default())
     }
     
     /// 设置用于测试的图像列表
@@ -877,6 +2098,6 @@ impl CalibrationWorkflow {
         }
         
         println!("📊 使用 {} 组有效图像进行标定", valid_images.len());
-        self.run_calibration_algorithm(&valid_images)
+        self.calibration_run(valid_images.into_iter().cloned().collect()).run(&CalibrationRunControl::default())
     }
 } 
\ No newline at end of file
@@ -4,12 +4,19 @@ use serde::{Serialize, Deserialize};
 use std::fs;
 use std::path::Path;
 
+/// 单目相机参数
+///
+/// 必填: `camera_matrix`；可选: `dist_coeffs`（缺省为5个0，即理想针孔模型）
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CameraParams {
     pub camera_matrix: Vec<Vec<f64>>,  // 3x3
+    #[serde(default = "zero_dist_coeffs")]
     pub dist_coeffs: Vec<f64>,         // 1x5
 }
 
+/// 双目外参
+///
+/// 必填: `r`、`t`；文件中的 `e`/`f`（本质/基础矩阵）不参与计算，有无均可
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct StereoParams {
     pub r: Vec<Vec<f64>>,  // 3x3 rotation matrix
@@ -18,13 +25,154 @@ pub struct StereoParams {
     //pub f: Vec<Vec<f64>>,  // 3x3 fundamental matrix
 }
 
+/// 立体校正参数
+///
+/// 必填: `r1`、`r2`、`p1`、`p2`；可选: `q`（合像不使用，缺省为4x4零矩阵）、
+/// `valid_roi`（stereoRectify 输出的有效区域，旧版文件无此项）
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RectifyParams {
     pub r1: Vec<Vec<f64>>,  // 3x3 rectification transform for camera 1
     pub r2: Vec<Vec<f64>>,  // 3x3 rectification transform for camera 2
     pub p1: Vec<Vec<f64>>,  // 3x4 projection matrix for camera 1
     pub p2: Vec<Vec<f64>>,  // 3x4 projection matrix for camera 2
+    #[serde(default = "zero_q_matrix")]
     pub q: Vec<Vec<f64>>,   // 4x4 disparity-to-depth mapping matrix
+    #[serde(default)]
+    pub valid_roi: Option<RectifyValidRoi>,
+}
+
+/// 矩形区域 (像素)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct RoiRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl RoiRect {
+    pub fn area(&self) -> i64 {
+        self.width.max(0) as i64 * self.height.max(0) as i64
+    }
+
+    /// 与另一区域的交集（无交集时宽高为0）
+    pub fn intersect(&self, other: &RoiRect) -> RoiRect {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = (self.x + self.width).min(other.x + other.width);
+        let bottom = (self.y + self.height).min(other.y + other.height);
+        RoiRect { x, y, width: (right - x).max(0), height: (bottom - y).max(0) }
+    }
+}
+
+impl From<opencv::core::Rect> for RoiRect {
+    fn from(rect: opencv::core::Rect) -> Self {
+        Self { x: rect.x, y: rect.y, width: rect.width, height: rect.height }
+    }
+}
+
+/// 立体校正后左右图像的有效区域 (stereoRectify 的 roi1/roi2)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct RectifyValidRoi {
+    pub image_width: i32,
+    pub image_height: i32,
+    pub left: RoiRect,
+    pub right: RoiRect,
+}
+
+/// 校正有效区域覆盖率的默认合格下限 (占整幅图像的比例)
+pub const DEFAULT_RECTIFY_MIN_COVERAGE: f64 = 0.8;
+
+/// 校正有效区域覆盖率报告
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RectifyCoverageReport {
+    pub valid_roi: RectifyValidRoi,
+    pub left_percent: f64,      // 左图有效区域占比 (%)
+    pub right_percent: f64,     // 右图有效区域占比 (%)
+    pub overlap_percent: f64,   // 左右有效区域交集占比 (%)
+    pub min_fraction: f64,      // 合格下限 (0-1)
+    pub pass: bool,             // 左右及交集均不低于下限
+    pub message: String,
+}
+
+impl RectifyValidRoi {
+    /// 计算覆盖率，左右及交集任一低于 min_fraction 时不合格（标定质量或 alpha 设置有问题）
+    pub fn coverage(&self, min_fraction: f64) -> RectifyCoverageReport {
+        let total = (self.image_width.max(0) as i64 * self.image_height.max(0) as i64).max(1) as f64;
+        let percent = |rect: &RoiRect| rect.area() as f64 / total * 100.0;
+        let left_percent = percent(&self.left);
+        let right_percent = percent(&self.right);
+        let overlap_percent = percent(&self.left.intersect(&self.right));
+        let limit = min_fraction * 100.0;
+        let low: Vec<&str> = [("左图", left_percent), ("右图", right_percent), ("左右交集", overlap_percent)]
+            .iter()
+            .filter(|(_, value)| *value < limit)
+            .map(|(name, _)| *name)
+            .collect();
+        let pass = low.is_empty();
+        let message = if pass {
+            format!("校正有效区域: 左 {:.1}%, 右 {:.1}%, 交集 {:.1}% (下限 {:.0}%)", left_percent, right_percent, overlap_percent, limit)
+        } else {
+            format!("⚠️ 校正有效区域过小 ({} 低于 {:.0}%): 左 {:.1}%, 右 {:.1}%, 交集 {:.1}%，请检查标定质量",
+                    low.join("、"), limit, left_percent, right_percent, overlap_percent)
+        };
+        RectifyCoverageReport {
+            valid_roi: *self,
+            left_percent,
+            right_percent,
+            overlap_percent,
+            min_fraction,
+            pass,
+            message,
+        }
+    }
+}
+
+fn zero_dist_coeffs() -> Vec<f64> {
+    vec![0.0; 5]
+}
+
+fn zero_q_matrix() -> Vec<Vec<f64>> {
+    vec![vec![0.0; 4]; 4]
+}
+
+/// 检查矩阵形状（rows x cols）
+fn check_matrix_shape(name: &str, matrix: &[Vec<f64>], rows: usize, cols: usize) -> Result<(), String> {
+    if matrix.len() != rows || matrix.iter().any(|row| row.len() != cols) {
+        let actual_cols = matrix.first().map_or(0, |row| row.len());
+        return Err(format!("{} 应为 {}x{} 矩阵，实际为 {}x{}", name, rows, cols, matrix.len(), actual_cols));
+    }
+    Ok(())
+}
+
+impl CameraParams {
+    pub fn validate(&self) -> Result<(), String> {
+        check_matrix_shape("camera_matrix", &self.camera_matrix, 3, 3)?;
+        if ![4, 5, 8, 12, 14].contains(&self.dist_coeffs.len()) {
+            return Err(format!("dist_coeffs 个数应为 4/5/8/12/14，实际为 {}", self.dist_coeffs.len()));
+        }
+        Ok(())
+    }
+}
+
+impl StereoParams {
+    pub fn validate(&self) -> Result<(), String> {
+        check_matrix_shape("r", &self.r, 3, 3)?;
+        if self.t.len() != 3 {
+            return Err(format!("t 应为3维平移向量，实际为 {} 维", self.t.len()));
+        }
+        Ok(())
+    }
+}
+
+impl RectifyParams {
+    pub fn validate(&self) -> Result<(), String> {
+        check_matrix_shape("r1", &self.r1, 3, 3)?;
+        check_matrix_shape("r2", &self.r2, 3, 3)?;
+        check_matrix_shape("p1", &self.p1, 3, 4)?;
+        check_matrix_shape("p2", &self.p2, 3, 4)?;
+        check_matrix_shape("q", &self.q, 4, 4)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -35,6 +183,161 @@ pub struct RectifyLeftRightMaps {
     pub right_map2: Vec<Vec<f32>>,  // y-mapping for right camera
 }
 
+impl RectifyLeftRightMaps {
+    /// 左右重映射矩阵尺寸 ((宽, 高), (宽, 高))
+    pub fn sizes(&self) -> ((i32, i32), (i32, i32)) {
+        let size = |map: &Vec<Vec<f32>>| (map.first().map_or(0, |row| row.len()) as i32, map.len() as i32);
+        (size(&self.left_map1), size(&self.right_map1))
+    }
+}
+
+/// 重映射矩阵尺寸与当前分辨率的比对结果
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RectifyMapsSizeCheck {
+    pub matched: bool,
+    pub left_map_size: (i32, i32),   // (宽, 高)
+    pub right_map_size: (i32, i32),
+    pub image_size: (i32, i32),      // 当前生效分辨率
+    pub error: Option<String>,
+}
+
+/// 比对重映射矩阵尺寸与当前分辨率（标定分辨率与采集分辨率不一致时 remap 越界或输出错误图像）
+pub fn check_rectify_maps_size(left: (i32, i32), right: (i32, i32), image: (i32, i32)) -> RectifyMapsSizeCheck {
+    let matched = left == image && right == image;
+    let error = if matched {
+        None
+    } else {
+        let maps = if left == right {
+            format!("{}×{}", left.0, left.1)
+        } else {
+            format!("左 {}×{} / 右 {}×{}", left.0, left.1, right.0, right.1)
+        };
+        Some(format!("重映射矩阵为 {}，但当前分辨率为 {}×{} — 请重新标定或切换分辨率",
+                     maps, image.0, image.1))
+    };
+    RectifyMapsSizeCheck {
+        matched,
+        left_map_size: left,
+        right_map_size: right,
+        image_size: image,
+        error,
+    }
+}
+
+/// 读取重映射矩阵文件并与分辨率比对
+pub fn check_rectify_maps_file<P: AsRef<Path>>(path: P, image: (i32, i32)) -> Result<RectifyMapsSizeCheck, Box<dyn std::error::Error>> {
+    let (left, right) = load_rectify_maps(path)?.sizes();
+    Ok(check_rectify_maps_size(left, right, image))
+}
+
+/// 单个标定图像对的检测圆心（与图像一起保存的 JSON 旁路文件，离线重标定时可跳过检测）
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CornerSidecar {
+    pub pair_id: u32,
+    pub left_image_path: String,
+    pub right_image_path: String,
+    pub image_width: i32,
+    pub image_height: i32,
+    pub pattern_cols: i32,
+    pub pattern_rows: i32,
+    pub left_points: Vec<(f32, f32)>,
+    pub right_points: Vec<(f32, f32)>,
+    pub detected_at: String,
+}
+
+/// 圆心旁路文件名（与 calib_left_{pair_id:02}.png 同目录）
+pub fn corner_sidecar_file_name(pair_id: u32) -> String {
+    format!("calib_corners_{:02}.json", pair_id)
+}
+
+/// 标定时使用的相机序列号（标定参数与相机一一绑定）
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CalibrationCameraInfo {
+    pub left_camera_serial: String,
+    pub right_camera_serial: String,
+    pub calibrated_at: String,
+}
+
+/// 标定相机信息文件名（与其他标定参数保存在同一目录）
+pub const CALIBRATION_CAMERA_INFO_FILE: &str = "calibration_camera_info.yaml";
+
+/// 当前相机与标定相机的序列号比对结果
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CameraSerialCheck {
+    pub matched: bool,
+    pub current_left_serial: String,
+    pub current_right_serial: String,
+    pub calibrated_left_serial: Option<String>,  // None: 旧标定未记录序列号
+    pub calibrated_right_serial: Option<String>,
+    pub warning: Option<String>,
+    #[serde(default)]
+    pub rectify_maps: Option<RectifyMapsSizeCheck>,  // 重映射矩阵尺寸校验（由自检命令填充）
+}
+
+impl CameraSerialCheck {
+    /// 标定记录了序列号且与当前相机不一致（旧标定无记录时不视为不匹配）
+    pub fn is_mismatch(&self) -> bool {
+        self.calibrated_left_serial.is_some() && !self.matched
+    }
+}
+
+/// 比对当前相机序列号与标定时记录的序列号
+pub fn check_camera_serials(
+    calibrated: Option<&CalibrationCameraInfo>,
+    current_left: &str,
+    current_right: &str,
+) -> CameraSerialCheck {
+    let mut check = CameraSerialCheck {
+        matched: false,
+        current_left_serial: current_left.to_string(),
+        current_right_serial: current_right.to_string(),
+        calibrated_left_serial: None,
+        calibrated_right_serial: None,
+        warning: None,
+        rectify_maps: None,
+    };
+
+    let info = match calibrated {
+        Some(info) => info,
+        None => {
+            check.warning = Some("当前标定未记录相机序列号，无法校验，建议重新标定".to_string());
+            return check;
+        }
+    };
+    check.calibrated_left_serial = Some(info.left_camera_serial.clone());
+    check.calibrated_right_serial = Some(info.right_camera_serial.clone());
+
+    let left_ok = info.left_camera_serial == current_left;
+    let right_ok = info.right_camera_serial == current_right;
+    check.matched = left_ok && right_ok;
+
+    if !check.matched {
+        check.warning = Some(if info.left_camera_serial == current_right && info.right_camera_serial == current_left {
+            "左右相机与标定时互换，请检查接线或重新标定".to_string()
+        } else {
+            let mut parts = Vec::new();
+            if !left_ok {
+                parts.push(format!("左相机 {} (标定时 {})", current_left, info.left_camera_serial));
+            }
+            if !right_ok {
+                parts.push(format!("右相机 {} (标定时 {})", current_right, info.right_camera_serial));
+            }
+            format!("相机与当前标定不匹配: {}，请重新标定", parts.join(", "))
+        });
+    }
+    check
+}
+
+/// 读取标定目录中记录的相机信息并与当前相机比对（信息文件缺失视为旧标定）
+pub fn check_calibration_dir_serials<P: AsRef<Path>>(
+    calib_dir: P,
+    current_left: &str,
+    current_right: &str,
+) -> CameraSerialCheck {
+    let info = load_calibration_camera_info(calib_dir.as_ref().join(CALIBRATION_CAMERA_INFO_FILE)).ok();
+    check_camera_serials(info.as_ref(), current_left, current_right)
+}
+
 // --- Mat <-> Vec 转换工具 ---
 pub fn mat_to_vec2d_f64(mat: &Mat) -> Vec<Vec<f64>> {
     let rows = mat.rows();
@@ -126,9 +429,13 @@ pub fn save_camera_params<P: AsRef<Path>>(path: P, params: &CameraParams) -> Res
     Ok(())
 }
 
+/// 加载时补全可选字段，并校验必填矩阵的形状
 pub fn load_camera_params<P: AsRef<Path>>(path: P) -> Result<CameraParams, Box<dyn std::error::Error>> {
+    let path = path.as_ref();
     let yaml = fs::read_to_string(path)?;
-    let params = serde_yaml::from_str(&yaml)?;
+    let params: CameraParams = serde_yaml::from_str(&yaml)
+        .map_err(|e| format!("参数文件解析失败 {}: {}", path.display(), e))?;
+    params.validate().map_err(|e| format!("参数文件无效 {}: {}", path.display(), e))?;
     Ok(params)
 }
 
@@ -138,9 +445,13 @@ pub fn save_stereo_params<P: AsRef<Path>>(path: P, params: &StereoParams) -> Res
     Ok(())
 }
 
+/// 加载时补全可选字段，并校验必填矩阵的形状
 pub fn load_stereo_params<P: AsRef<Path>>(path: P) -> Result<StereoParams, Box<dyn std::error::Error>> {
+    let path = path.as_ref();
     let yaml = fs::read_to_string(path)?;
-    let params = serde_yaml::from_str(&yaml)?;
+    let params: StereoParams = serde_yaml::from_str(&yaml)
+        .map_err(|e| format!("参数文件解析失败 {}: {}", path.display(), e))?;
+    params.validate().map_err(|e| format!("参数文件无效 {}: {}", path.display(), e))?;
     Ok(params)
 }
 
@@ -150,12 +461,25 @@ pub fn save_rectify_params<P: AsRef<Path>>(path: P, params: &RectifyParams) -> R
     Ok(())
 }
 
+/// 加载时补全可选字段，并校验必填矩阵的形状
 pub fn load_rectify_params<P: AsRef<Path>>(path: P) -> Result<RectifyParams, Box<dyn std::error::Error>> {
+    let path = path.as_ref();
     let yaml = fs::read_to_string(path)?;
-    let params = serde_yaml::from_str(&yaml)?;
+    let params: RectifyParams = serde_yaml::from_str(&yaml)
+        .map_err(|e| format!("参数文件解析失败 {}: {}", path.display(), e))?;
+    params.validate().map_err(|e| format!("参数文件无效 {}: {}", path.display(), e))?;
     Ok(params)
 }
 
+/// 读取立体校正参数中的有效区域并计算覆盖率（旧版参数文件无有效区域时报错，需重新标定）
+pub fn check_rectify_coverage<P: AsRef<Path>>(path: P, min_fraction: f64) -> Result<RectifyCoverageReport, Box<dyn std::error::Error>> {
+    let path = path.as_ref();
+    let params = load_rectify_params(path)?;
+    let valid_roi = params.valid_roi
+        .ok_or_else(|| format!("参数文件未记录校正有效区域（旧版标定结果），请重新标定: {}", path.display()))?;
+    Ok(valid_roi.coverage(min_fraction))
+}
+
 pub fn save_rectify_maps<P: AsRef<Path>>(path: P, maps: &RectifyLeftRightMaps) -> Result<(), Box<dyn std::error::Error>> {
     let yaml = serde_yaml::to_string(maps)?;
     fs::write(path, yaml)?;
@@ -168,6 +492,208 @@ pub fn load_rectify_maps<P: AsRef<Path>>(path: P) -> Result<RectifyLeftRightMaps
     Ok(maps)
 }
 
+pub fn save_calibration_camera_info<P: AsRef<Path>>(path: P, info: &CalibrationCameraInfo) -> Result<(), Box<dyn std::error::Error>> {
+    let yaml = serde_yaml::to_string(info)?;
+    fs::write(path, yaml)?;
+    Ok(())
+}
+
+pub fn load_calibration_camera_info<P: AsRef<Path>>(path: P) -> Result<CalibrationCameraInfo, Box<dyn std::error::Error>> {
+    let yaml = fs::read_to_string(path)?;
+    let info = serde_yaml::from_str(&yaml)?;
+    Ok(info)
+}
+
+pub fn points_to_pairs(points: &opencv::core::Vector<opencv::core::Point2f>) -> Vec<(f32, f32)> {
+    points.iter().map(|p| (p.x, p.y)).collect()
+}
+
+pub fn pairs_to_points(pairs: &[(f32, f32)]) -> opencv::core::Vector<opencv::core::Point2f> {
+    pairs.iter().map(|&(x, y)| opencv::core::Point2f::new(x, y)).collect()
+}
+
+pub fn save_corner_sidecar<P: AsRef<Path>>(path: P, sidecar: &CornerSidecar) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string_pretty(sidecar)?;
+    fs::write(path, json)?;
+    Ok(())
+}
+
+pub fn load_corner_sidecar<P: AsRef<Path>>(path: P) -> Result<CornerSidecar, Box<dyn std::error::Error>> {
+    let json = fs::read_to_string(path)?;
+    let sidecar = serde_json::from_str(&json)?;
+    Ok(sidecar)
+}
+
+/// 读取目录下所有圆心旁路文件，按 pair_id 排序
+pub fn load_corner_sidecars_from_dir<P: AsRef<Path>>(dir: P) -> Result<Vec<CornerSidecar>, Box<dyn std::error::Error>> {
+    let mut sidecars = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_sidecar = path.file_name()
+            .and_then(|n| n.to_str())
+            .map_or(false, |n| n.starts_with("calib_corners_") && n.ends_with(".json"));
+        if is_sidecar {
+            sidecars.push(load_corner_sidecar(&path)?);
+        }
+    }
+    sidecars.sort_by_key(|s| s.pair_id);
+    Ok(sidecars)
+}
+
+/// 点对应关系导出文件名
+pub const POINT_CORRESPONDENCE_FILE: &str = "calib_correspondences.json";
+
+/// 标定点对应关系 (世界点, 左图点, 右图点)，供外部光束法平差/验证使用
+/// 
+/// 数组按 numpy 形状组织：world_points (N,3)，left/right_points (M,N,2)，
+/// 第 m 张图像的第 n 个点与 world_points[n] 对应
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PointCorrespondenceExport {
+    pub image_width: i32,
+    pub image_height: i32,
+    pub pattern_cols: i32,
+    pub pattern_rows: i32,
+    pub circle_diameter_mm: f32,
+    pub center_distance_mm: f32,
+    pub world_points: Vec<[f32; 3]>,
+    pub pair_ids: Vec<u32>,
+    pub left_image_paths: Vec<String>,
+    pub right_image_paths: Vec<String>,
+    pub left_points: Vec<Vec<[f32; 2]>>,
+    pub right_points: Vec<Vec<[f32; 2]>>,
+    pub exported_at: String,
+}
+
+pub fn save_point_correspondences<P: AsRef<Path>>(path: P, export: &PointCorrespondenceExport) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string_pretty(export)?;
+    fs::write(path, json)?;
+    Ok(())
+}
+
+pub fn load_point_correspondences<P: AsRef<Path>>(path: P) -> Result<PointCorrespondenceExport, Box<dyn std::error::Error>> {
+    let json = fs::read_to_string(path)?;
+    let export = serde_json::from_str(&json)?;
+    Ok(export)
+}
+
+// --- ROS camera_info 导出 ---
+
+/// ROS camera_info 中的矩阵 (行优先)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RosMatrix {
+    pub rows: usize,
+    pub cols: usize,
+    pub data: Vec<f64>,
+}
+
+impl RosMatrix {
+    fn from_rows(name: &str, rows: &[Vec<f64>], expected: (usize, usize)) -> Result<Self, String> {
+        if rows.len() != expected.0 || rows.iter().any(|row| row.len() != expected.1) {
+            return Err(format!("{} 应为 {}x{} 矩阵", name, expected.0, expected.1));
+        }
+        Ok(Self { rows: expected.0, cols: expected.1, data: rows.concat() })
+    }
+    
+    fn to_yaml(&self) -> String {
+        let data: Vec<String> = self.data.iter().map(|v| v.to_string()).collect();
+        format!("  rows: {}\n  cols: {}\n  data: [{}]\n", self.rows, self.cols, data.join(", "))
+    }
+}
+
+/// ROS camera_info YAML（sensor_msgs/CameraInfo，字段与 camera_calibration_parsers 读写的格式一致）
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RosCameraInfo {
+    pub image_width: i32,
+    pub image_height: i32,
+    pub camera_name: String,
+    pub camera_matrix: RosMatrix,              // K 3x3
+    pub distortion_model: String,              // plumb_bob (5参数) / rational_polynomial (8参数)
+    pub distortion_coefficients: RosMatrix,    // D 1xN
+    pub rectification_matrix: RosMatrix,       // R 3x3 (立体校正旋转)
+    pub projection_matrix: RosMatrix,          // P 3x4 (校正后投影矩阵，右相机含基线 Tx)
+}
+
+impl RosCameraInfo {
+    /// 由标定参数构造 (rectification/projection 取立体校正的 R1/P1 或 R2/P2)
+    pub fn from_calibration(
+        camera_name: &str,
+        image_size: (i32, i32),
+        params: &CameraParams,
+        rectification: &[Vec<f64>],
+        projection: &[Vec<f64>],
+    ) -> Result<Self, String> {
+        let distortion_model = match params.dist_coeffs.len() {
+            5 => "plumb_bob",
+            8 => "rational_polynomial",
+            n => return Err(format!("不支持的畸变参数个数: {} (ROS 需要5或8个)", n)),
+        };
+        Ok(Self {
+            image_width: image_size.0,
+            image_height: image_size.1,
+            camera_name: camera_name.to_string(),
+            camera_matrix: RosMatrix::from_rows("camera_matrix", &params.camera_matrix, (3, 3))?,
+            distortion_model: distortion_model.to_string(),
+            distortion_coefficients: RosMatrix {
+                rows: 1,
+                cols: params.dist_coeffs.len(),
+                data: params.dist_coeffs.clone(),
+            },
+            rectification_matrix: RosMatrix::from_rows("rectification_matrix", rectification, (3, 3))?,
+            projection_matrix: RosMatrix::from_rows("projection_matrix", projection, (3, 4))?,
+        })
+    }
+    
+    /// 按 camera_calibration_parsers 写出的布局生成 YAML（矩阵 data 为行内序列）
+    pub fn to_yaml(&self) -> String {
+        format!(
+            "image_width: {}\nimage_height: {}\ncamera_name: {}\ncamera_matrix:\n{}distortion_model: {}\ndistortion_coefficients:\n{}rectification_matrix:\n{}projection_matrix:\n{}",
+            self.image_width,
+            self.image_height,
+            self.camera_name,
+            self.camera_matrix.to_yaml(),
+            self.distortion_model,
+            self.distortion_coefficients.to_yaml(),
+            self.rectification_matrix.to_yaml(),
+            self.projection_matrix.to_yaml(),
+        )
+    }
+}
+
+pub fn save_ros_camera_info<P: AsRef<Path>>(path: P, info: &RosCameraInfo) -> Result<(), Box<dyn std::error::Error>> {
+    fs::write(path, info.to_yaml())?;
+    Ok(())
+}
+
+pub fn load_ros_camera_info<P: AsRef<Path>>(path: P) -> Result<RosCameraInfo, Box<dyn std::error::Error>> {
+    let yaml = fs::read_to_string(path)?;
+    let info = serde_yaml::from_str(&yaml)?;
+    Ok(info)
+}
+
+/// 读取标定目录中的左右相机参数与校正参数，导出 ROS camera_info YAML
+/// 
+/// 输出 `<output_dir>/left_camera_info.yaml` 与 `<output_dir>/right_camera_info.yaml`，返回两个文件路径
+pub fn export_ros_camera_info<P: AsRef<Path>, Q: AsRef<Path>>(
+    calib_dir: P,
+    output_dir: Q,
+    image_size: (i32, i32),
+) -> Result<(String, String), Box<dyn std::error::Error>> {
+    let calib_dir = calib_dir.as_ref();
+    let left = load_camera_params(calib_dir.join("left_camera_params.yaml"))?;
+    let right = load_camera_params(calib_dir.join("right_camera_params.yaml"))?;
+    let rectify = load_rectify_params(calib_dir.join("rectify_params.yaml"))?;
+    
+    let left_info = RosCameraInfo::from_calibration("left_camera", image_size, &left, &rectify.r1, &rectify.p1)?;
+    let right_info = RosCameraInfo::from_calibration("right_camera", image_size, &right, &rectify.r2, &rectify.p2)?;
+    
+    fs::create_dir_all(&output_dir)?;
+    let left_path = output_dir.as_ref().join("left_camera_info.yaml");
+    let right_path = output_dir.as_ref().join("right_camera_info.yaml");
+    save_ros_camera_info(&left_path, &left_info)?;
+    save_ros_camera_info(&right_path, &right_info)?;
+    Ok((left_path.to_string_lossy().to_string(), right_path.to_string_lossy().to_string()))
+}
+
 // --- 图像文件保存/加载函数 ---
 
 /// 保存图像缓冲区到文件
@@ -0,0 +1,252 @@
+// plc_modbus.rs - 合像结果输出到 Modbus/TCP 保持寄存器（PLC 对接）
+// 寄存器映射与编码始终可用；TCP 服务端需启用 `modbus` 特性（依赖 tokio-modbus 的 tcp-server）
+//
+// tokio / tokio-modbus 只在 `server` 模块中使用，未启用特性时不参与编译，默认构建无需这两个依赖。
+// 启用时 src-tauri/Cargo.toml 需声明（特性名与 #[cfg(feature = "modbus")] 一致）：
+//
+//   [features]
+//   modbus = ["dep:tokio", "dep:tokio-modbus"]
+//
+//   [dependencies]
+//   tokio = { version = "1", features = ["net"], optional = true }
+//   tokio-modbus = { version = "0.14", default-features = false, features = ["tcp-server"], optional = true }
+
+use std::sync::Mutex;
+use serde::{Serialize, Deserialize};
+use crate::modules::alignment::DualEyeAlignmentResult;
+
+/// 保持寄存器映射（地址从0开始，None 表示不输出该项）
+///
+/// 偏差值按 `值 × scale` 四舍五入后以 i16 补码写入，超出范围时饱和
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlcRegisterMap {
+    pub pass: Option<u16>,      // 1=合像通过，0=未通过或无有效结果
+    pub valid: Option<u16>,     // 1=最近一次检测得到合像结果
+    pub sequence: Option<u16>,  // 每次更新+1（回绕），PLC据此判断是否为新结果
+    pub mean_dx: Option<u16>,
+    pub mean_dy: Option<u16>,
+    pub rms: Option<u16>,
+    pub p95: Option<u16>,
+    pub max_err: Option<u16>,
+    // 稳健统计（剔除离群点后）；未启用稳健统计时输出与 rms/p95 相同。
+    // 旧配置文件缺省时不输出，避免与已有自定义地址冲突
+    #[serde(default)]
+    pub robust_rms: Option<u16>,
+    #[serde(default)]
+    pub robust_p95: Option<u16>,
+    #[serde(default)]
+    pub outlier_count: Option<u16>, // 离群点个数（不缩放）
+    pub scale: f64,             // 偏差缩放系数，默认100（0.01像素分辨率）
+}
+
+impl Default for PlcRegisterMap {
+    fn default() -> Self {
+        Self {
+            pass: Some(0),
+            valid: Some(1),
+            sequence: Some(2),
+            mean_dx: Some(3),
+            mean_dy: Some(4),
+            rms: Some(5),
+            p95: Some(6),
+            max_err: Some(7),
+            robust_rms: Some(8),
+            robust_p95: Some(9),
+            outlier_count: Some(10),
+            scale: 100.0,
+        }
+    }
+}
+
+impl PlcRegisterMap {
+    fn addresses(&self) -> impl Iterator<Item = u16> {
+        [self.pass, self.valid, self.sequence, self.mean_dx, self.mean_dy, self.rms, self.p95, self.max_err,
+         self.robust_rms, self.robust_p95, self.outlier_count]
+            .into_iter()
+            .flatten()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.scale > 0.0 && self.scale.is_finite()) {
+            return Err(format!("寄存器缩放系数必须为正数: {}", self.scale));
+        }
+        let mut addresses: Vec<u16> = self.addresses().collect();
+        addresses.sort_unstable();
+        if let Some(pair) = addresses.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(format!("寄存器地址重复: {}", pair[0]));
+        }
+        Ok(())
+    }
+
+    /// 寄存器块长度（最大地址+1）
+    pub fn register_count(&self) -> usize {
+        self.addresses().max().map_or(0, |max| max as usize + 1)
+    }
+
+    /// 偏差值缩放为 i16 补码寄存器值
+    pub fn scale_value(&self, value: f64) -> u16 {
+        (value * self.scale).round().clamp(i16::MIN as f64, i16::MAX as f64) as i16 as u16
+    }
+}
+
+/// Modbus/TCP 输出配置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlcModbusConfig {
+    pub enabled: bool,
+    pub bind_address: String,   // 监听地址，如 "0.0.0.0:502"
+    pub registers: PlcRegisterMap,
+}
+
+impl Default for PlcModbusConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: "0.0.0.0:502".to_string(),
+            registers: PlcRegisterMap::default(),
+        }
+    }
+}
+
+impl PlcModbusConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        self.bind_address.parse::<std::net::SocketAddr>()
+            .map_err(|e| format!("Modbus监听地址无效 {}: {}", self.bind_address, e))?;
+        self.registers.validate()
+    }
+}
+
+struct BankState {
+    map: PlcRegisterMap,
+    registers: Vec<u16>,
+    sequence: u16,
+}
+
+/// 保持寄存器缓存：检测线程写入，Modbus 服务端读取
+pub struct PlcRegisterBank {
+    state: Mutex<BankState>,
+}
+
+impl PlcRegisterBank {
+    pub fn new(map: PlcRegisterMap) -> Self {
+        Self {
+            state: Mutex::new(BankState { map, registers: vec![0; map.register_count()], sequence: 0 }),
+        }
+    }
+
+    /// 更换寄存器映射，寄存器清零
+    pub fn set_map(&self, map: PlcRegisterMap) -> Result<(), String> {
+        map.validate()?;
+        let mut state = self.state.lock().unwrap();
+        state.registers = vec![0; map.register_count()];
+        state.map = map;
+        Ok(())
+    }
+
+    /// 写入最新合像结果
+    pub fn publish_alignment(&self, result: &DualEyeAlignmentResult) {
+        self.update(|map, set| {
+            set(map.pass, result.pass as u16);
+            set(map.valid, 1);
+            set(map.mean_dx, map.scale_value(result.mean_dx));
+            set(map.mean_dy, map.scale_value(result.mean_dy));
+            set(map.rms, map.scale_value(result.rms));
+            set(map.p95, map.scale_value(result.p95));
+            set(map.max_err, map.scale_value(result.max_err));
+            set(map.robust_rms, map.scale_value(result.robust_rms.unwrap_or(result.rms)));
+            set(map.robust_p95, map.scale_value(result.robust_p95.unwrap_or(result.p95)));
+            set(map.outlier_count, result.outlier_indices.len().min(u16::MAX as usize) as u16);
+        });
+    }
+
+    /// 本次检测未得到合像结果：清除通过位与偏差，避免PLC读到过期的通过结果
+    pub fn invalidate(&self) {
+        self.update(|map, set| {
+            for address in map.addresses() {
+                set(Some(address), 0);
+            }
+        });
+    }
+
+    fn update(&self, write: impl FnOnce(&PlcRegisterMap, &mut dyn FnMut(Option<u16>, u16))) {
+        let mut state = self.state.lock().unwrap();
+        let BankState { map, registers, sequence } = &mut *state;
+        *sequence = sequence.wrapping_add(1);
+        let mut set = |address: Option<u16>, value: u16| {
+            if let Some(address) = address {
+                registers[address as usize] = value;
+            }
+        };
+        write(map, &mut set);
+        set(map.sequence, *sequence);
+    }
+
+    /// 读取 [address, address+count)，越界时返回 None
+    pub fn read(&self, address: u16, count: u16) -> Option<Vec<u16>> {
+        let state = self.state.lock().unwrap();
+        let start = address as usize;
+        state.registers.get(start..start + count as usize).map(|values| values.to_vec())
+    }
+
+    /// 全部寄存器快照
+    pub fn snapshot(&self) -> Vec<u16> {
+        self.state.lock().unwrap().registers.clone()
+    }
+}
+
+/// Modbus/TCP 服务端（仅响应读保持寄存器）
+#[cfg(feature = "modbus")]
+pub mod server {
+    use std::{net::SocketAddr, sync::Arc};
+    use tokio::net::TcpListener;
+    use tokio_modbus::prelude::*;
+    use tokio_modbus::server::tcp::{accept_tcp_connection, Server};
+    use super::PlcRegisterBank;
+
+    struct RegisterService {
+        bank: Arc<PlcRegisterBank>,
+    }
+
+    impl tokio_modbus::server::Service for RegisterService {
+        type Request = Request<'static>;
+        type Response = Response;
+        type Exception = ExceptionCode;
+        type Future = std::future::Ready<Result<Response, ExceptionCode>>;
+
+        fn call(&self, req: Self::Request) -> Self::Future {
+            let response = match req {
+                Request::ReadHoldingRegisters(address, count) => self.bank.read(address, count)
+                    .map(Response::ReadHoldingRegisters)
+                    .ok_or(ExceptionCode::IllegalDataAddress),
+                _ => Err(ExceptionCode::IllegalFunction),
+            };
+            std::future::ready(response)
+        }
+    }
+
+    /// 在 Tauri 异步运行时中启动服务端，返回的句柄 abort 即停止
+    pub fn spawn(bind_address: &str, bank: Arc<PlcRegisterBank>) -> Result<tauri::async_runtime::JoinHandle<()>, String> {
+        let address: SocketAddr = bind_address.parse()
+            .map_err(|e| format!("Modbus监听地址无效 {}: {}", bind_address, e))?;
+        Ok(tauri::async_runtime::spawn(async move {
+            let listener = match TcpListener::bind(address).await {
+                Ok(listener) => listener,
+                Err(e) => {
+                    eprintln!("❌ Modbus/TCP 监听失败 {}: {}", address, e);
+                    return;
+                }
+            };
+            println!("🔌 Modbus/TCP 服务已启动: {}", address);
+            let bank = &bank;
+            let new_service = |_socket_addr| Ok(Some(RegisterService { bank: Arc::clone(bank) }));
+            let on_connected = |stream, socket_addr| async move {
+                accept_tcp_connection(stream, socket_addr, new_service)
+            };
+            let on_process_error = |e| eprintln!("⚠️ Modbus 请求处理失败: {}", e);
+            if let Err(e) = Server::new(listener).serve(&on_connected, on_process_error).await {
+                eprintln!("❌ Modbus/TCP 服务异常退出: {}", e);
+            }
+        }))
+    }
+}
@@ -0,0 +1,116 @@
+// pose_filter.rs - 实时姿态显示用卡尔曼滤波
+// 每眼 roll/pitch/yaw 各一个一维卡尔曼滤波器，仅用于界面显示；判定仍使用原始（或多帧平均）结果
+
+use serde::{Serialize, Deserialize};
+
+/// 姿态卡尔曼滤波配置
+///
+/// 过程噪声越大跟随越快、越小越平滑；测量噪声对应单帧姿态解的抖动（度²）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PoseKalmanConfig {
+    pub enabled: bool,
+    pub process_noise: f64,      // 过程噪声 Q (度²/帧)，默认0.01
+    pub measurement_noise: f64,  // 测量噪声 R (度²)，默认0.25
+}
+
+impl Default for PoseKalmanConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            process_noise: 0.01,
+            measurement_noise: 0.25,
+        }
+    }
+}
+
+impl PoseKalmanConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.process_noise > 0.0 && self.process_noise.is_finite()) {
+            return Err(format!("卡尔曼过程噪声必须为正数: {}", self.process_noise));
+        }
+        if !(self.measurement_noise > 0.0 && self.measurement_noise.is_finite()) {
+            return Err(format!("卡尔曼测量噪声必须为正数: {}", self.measurement_noise));
+        }
+        Ok(())
+    }
+}
+
+/// 一维卡尔曼滤波器（常值模型）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScalarKalman {
+    estimate: Option<f64>,
+    variance: f64,
+}
+
+impl ScalarKalman {
+    /// 输入一次测量，返回滤波后的估计；首帧直接采用测量值
+    pub fn update(&mut self, measurement: f64, config: &PoseKalmanConfig) -> f64 {
+        let estimate = match self.estimate {
+            None => {
+                self.variance = config.measurement_noise;
+                measurement
+            }
+            Some(estimate) => {
+                let predicted_variance = self.variance + config.process_noise;
+                let gain = predicted_variance / (predicted_variance + config.measurement_noise);
+                self.variance = (1.0 - gain) * predicted_variance;
+                estimate + gain * (measurement - estimate)
+            }
+        };
+        self.estimate = Some(estimate);
+        estimate
+    }
+
+    pub fn estimate(&self) -> Option<f64> {
+        self.estimate
+    }
+}
+
+/// 姿态角 (度)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PoseAngles {
+    pub roll: f64,
+    pub pitch: f64,
+    pub yaw: f64,
+}
+
+/// 左右眼姿态滤波器（eye: 0=左, 1=右）
+#[derive(Debug, Clone, Default)]
+pub struct PoseKalmanFilter {
+    config: PoseKalmanConfig,
+    eyes: [[ScalarKalman; 3]; 2],
+}
+
+impl PoseKalmanFilter {
+    pub fn new(config: PoseKalmanConfig) -> Self {
+        Self { config, eyes: Default::default() }
+    }
+
+    pub fn config(&self) -> PoseKalmanConfig {
+        self.config
+    }
+
+    /// 更换配置并清除滤波状态
+    pub fn set_config(&mut self, config: PoseKalmanConfig) -> Result<(), String> {
+        config.validate()?;
+        *self = Self::new(config);
+        Ok(())
+    }
+
+    /// 清除滤波状态（阶段切换时调用，避免沿用上一次测量的估计）
+    pub fn reset(&mut self) {
+        self.eyes = Default::default();
+    }
+
+    /// 输入该眼本帧原始姿态，返回滤波后的显示值
+    pub fn update(&mut self, eye: usize, raw: PoseAngles) -> PoseAngles {
+        let config = self.config;
+        let [roll, pitch, yaw] = &mut self.eyes[eye];
+        PoseAngles {
+            roll: roll.update(raw.roll, &config),
+            pitch: pitch.update(raw.pitch, &config),
+            yaw: yaw.update(raw.yaw, &config),
+        }
+    }
+}
@@ -7,6 +7,35 @@ use std::time::Instant; // 添加性能监控
 
 use crate::modules::param_io::*;
 
+/// 重映射插值方式
+/// 
+/// 预览只需看清画面，可用 Nearest 换取速度；检测需要亚像素精度的圆心，必须使用 Linear
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RemapInterpolation {
+    /// 双线性插值（默认，检测路径使用）
+    #[default]
+    Linear,
+    /// 最近邻插值（更快，仅用于预览）
+    Nearest,
+}
+
+impl RemapInterpolation {
+    /// 对应的 OpenCV 插值标志
+    pub fn to_cv_flag(self) -> i32 {
+        match self {
+            RemapInterpolation::Linear => imgproc::INTER_LINEAR,
+            RemapInterpolation::Nearest => imgproc::INTER_NEAREST,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RemapInterpolation::Linear => "INTER_LINEAR",
+            RemapInterpolation::Nearest => "INTER_NEAREST",
+        }
+    }
+}
+
 pub struct Rectifier {
     image_size: Size,
 }
@@ -73,32 +102,29 @@ impl Rectifier {
         Ok(dst)
     }
     
-    /// 🔧 智能重映射 - 根据图像大小自动选择最优插值方法
+    /// 🔧 按调用方指定的插值方式重映射
+    /// 
+    /// 检测路径传 `RemapInterpolation::Linear`，预览路径可传 `RemapInterpolation::Nearest`。
+    /// 不再按图像大小自动降级：5MP 相机图像此前会被静默切到最近邻，影响圆心精度。
     pub fn remap_image_adaptive(
         &self,
         src: &Mat,
         map1: &Mat,
         map2: &Mat,
+        interpolation: RemapInterpolation,
     ) -> Result<Mat, opencv::Error> {
         let remap_start = Instant::now();
         let mut dst = Mat::default();
         
-        // 根据图像大小选择插值方法
         let total_pixels = (src.cols() * src.rows()) as u64;
-        let interpolation = if total_pixels > 4_000_000 {  // 大于4MP使用最快方法
-            println!("🔧 大图像({:.1}MP)使用INTER_NEAREST", total_pixels as f64 / 1_000_000.0);
-            imgproc::INTER_NEAREST
-        } else {  // 小图像使用线性插值
-            println!("🔧 中等图像({:.1}MP)使用INTER_LINEAR", total_pixels as f64 / 1_000_000.0);
-            imgproc::INTER_LINEAR
-        };
+        println!("🔧 图像({:.1}MP)使用{}", total_pixels as f64 / 1_000_000.0, interpolation.label());
         
         imgproc::remap(
             src,
             &mut dst,
             map1,
             map2,
-            interpolation,
+            interpolation.to_cv_flag(),
             opencv::core::BORDER_CONSTANT,
             opencv::core::Scalar::default(),
         )?;
@@ -0,0 +1,168 @@
+// result_log.rs - 合像结果追溯日志（CSV / JSONL 追加写入）
+// 合像阶段每次检测完成追加一行：产品序列号、时间、偏差统计与判定；格式按文件扩展名选择，可按日期轮转
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use serde::{Serialize, Deserialize};
+use crate::modules::alignment::DualEyeAlignmentResult;
+
+/// 日志格式（由扩展名 .csv / .jsonl 决定）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResultLogFormat {
+    Csv,
+    Jsonl,
+}
+
+impl ResultLogFormat {
+    pub fn from_path(path: &Path) -> Result<Self, String> {
+        match path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.to_ascii_lowercase()).as_deref() {
+            Some("csv") => Ok(Self::Csv),
+            Some("jsonl") => Ok(Self::Jsonl),
+            _ => Err(format!("结果日志扩展名须为 .csv 或 .jsonl: {}", path.display())),
+        }
+    }
+}
+
+const CSV_HEADER: &str = "timestamp,serial_number,mean_dx,mean_dy,rms,p95,max_err,robust_rms,robust_p95,outlier_count,pass";
+
+/// 单条合像结果记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlignmentLogRecord {
+    pub timestamp: String,      // 本地时间，毫秒
+    pub serial_number: String,  // 被测产品序列号，未设置时为空
+    pub mean_dx: f64,
+    pub mean_dy: f64,
+    pub rms: f64,
+    pub p95: f64,
+    pub max_err: f64,
+    #[serde(default)]
+    pub robust_rms: Option<f64>,    // 剔除离群点后的RMS，未启用稳健统计时为空
+    #[serde(default)]
+    pub robust_p95: Option<f64>,
+    #[serde(default)]
+    pub outlier_indices: Vec<usize>, // MAD 离群点序号
+    pub pass: bool,
+}
+
+impl AlignmentLogRecord {
+    pub fn new(serial_number: &str, result: &DualEyeAlignmentResult) -> Self {
+        Self {
+            timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            serial_number: serial_number.to_string(),
+            mean_dx: result.mean_dx,
+            mean_dy: result.mean_dy,
+            rms: result.rms,
+            p95: result.p95,
+            max_err: result.max_err,
+            robust_rms: result.robust_rms,
+            robust_p95: result.robust_p95,
+            outlier_indices: result.outlier_indices.clone(),
+            pass: result.pass,
+        }
+    }
+
+    fn to_csv_line(&self) -> String {
+        let optional = |value: Option<f64>| value.map(|v| format!("{:.4}", v)).unwrap_or_default();
+        format!("{},{},{:.4},{:.4},{:.4},{:.4},{:.4},{},{},{},{}",
+                self.timestamp, csv_field(&self.serial_number),
+                self.mean_dx, self.mean_dy, self.rms, self.p95, self.max_err,
+                optional(self.robust_rms), optional(self.robust_p95), self.outlier_indices.len(), self.pass)
+    }
+}
+
+/// 含逗号/引号/换行的字段加引号，内部引号双写
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+struct OpenLog {
+    path: PathBuf,
+    file: File,
+}
+
+/// 结果日志写入器：检测线程与命令线程共用，互斥追加
+pub struct ResultLogger {
+    base_path: PathBuf,
+    format: ResultLogFormat,
+    rotate_daily: bool,
+    current: Mutex<Option<OpenLog>>,
+}
+
+impl ResultLogger {
+    /// rotate_daily 为 true 时实际文件名追加日期，如 alignment_results_2025-01-31.csv
+    pub fn new(path: PathBuf, rotate_daily: bool) -> Result<Self, String> {
+        let format = ResultLogFormat::from_path(&path)?;
+        Ok(Self { base_path: path, format, rotate_daily, current: Mutex::new(None) })
+    }
+
+    pub fn base_path(&self) -> &Path {
+        &self.base_path
+    }
+
+    pub fn format(&self) -> ResultLogFormat {
+        self.format
+    }
+
+    /// 当前应写入的文件路径
+    pub fn current_path(&self) -> PathBuf {
+        if !self.rotate_daily {
+            return self.base_path.clone();
+        }
+        let stem = self.base_path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let ext = self.base_path.extension().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let date = chrono::Local::now().format("%Y-%m-%d");
+        self.base_path.with_file_name(format!("{}_{}.{}", stem, date, ext))
+    }
+
+    /// 追加一条记录；日期变化时切换到新文件，CSV 新文件先写表头
+    pub fn append(&self, record: &AlignmentLogRecord) -> Result<(), String> {
+        let line = match self.format {
+            ResultLogFormat::Csv => record.to_csv_line(),
+            ResultLogFormat::Jsonl => serde_json::to_string(record).map_err(|e| format!("结果记录序列化失败: {}", e))?,
+        };
+        let path = self.current_path();
+
+        let mut current = self.current.lock().map_err(|_| "结果日志锁中毒".to_string())?;
+        if current.as_ref().map_or(true, |log| log.path != path) {
+            *current = Some(OpenLog { file: self.open(&path)?, path });
+        }
+        let log = current.as_mut().unwrap();
+        writeln!(log.file, "{}", line)
+            .and_then(|_| log.file.flush())
+            .map_err(|e| format!("写入结果日志失败 {}: {}", log.path.display(), e))
+    }
+
+    fn open(&self, path: &Path) -> Result<File, String> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| format!("创建日志目录失败 {}: {}", dir.display(), e))?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)
+            .map_err(|e| format!("打开结果日志失败 {}: {}", path.display(), e))?;
+        let is_empty = file.metadata().map(|m| m.len() == 0).unwrap_or(false);
+        if self.format == ResultLogFormat::Csv && is_empty {
+            writeln!(file, "{}", CSV_HEADER).map_err(|e| format!("写入CSV表头失败: {}", e))?;
+        }
+        println!("📝 合像结果日志: {}", path.display());
+        Ok(file)
+    }
+}
+
+/// 读取 JSONL 结果日志（空行忽略）
+pub fn read_jsonl_log(path: &Path) -> Result<Vec<AlignmentLogRecord>, String> {
+    let file = File::open(path).map_err(|e| format!("打开结果日志失败 {}: {}", path.display(), e))?;
+    BufReader::new(file).lines()
+        .enumerate()
+        .filter(|(_, line)| line.as_ref().map_or(true, |l| !l.trim().is_empty()))
+        .map(|(i, line)| {
+            let line = line.map_err(|e| format!("读取结果日志失败: {}", e))?;
+            serde_json::from_str(&line).map_err(|e| format!("结果日志第{}行解析失败: {}", i + 1, e))
+        })
+        .collect()
+}
@@ -0,0 +1,195 @@
+// result_metrics.rs - 检测结果各数值指标的单位与显示名称
+// 结果数值字段不变；单位以 units（字段路径 → 单位）随结果/报告发送，名称以描述表形式提供，前端按 key 查表显示与本地化
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use serde::{Serialize, Deserialize};
+
+/// 指标单位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricUnit {
+    Pixel,
+    Degree,
+    Millimeter,
+    Millisecond,
+    Percent,  // 百分数（如误差分位数 95 表示 P95）
+    Ratio,  // 无量纲比值（如右/左网格跨度比）
+    GrayLevel,  // 8位灰度值 0-255
+    Count,  // 计数；数组字段（如离群点序号）按元素个数计
+}
+
+impl MetricUnit {
+    /// 显示用单位符号
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            MetricUnit::Pixel => "px",
+            MetricUnit::Degree => "°",
+            MetricUnit::Millimeter => "mm",
+            MetricUnit::Millisecond => "ms",
+            MetricUnit::Percent => "%",
+            MetricUnit::Ratio | MetricUnit::GrayLevel | MetricUnit::Count => "",
+        }
+    }
+}
+
+/// 带数值指标的结果类型：DetectionResult 各阶段（与其 serde 标签一致）及组合报告
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum MetricSource {
+    LeftEyePose,
+    RightEyePose,
+    DualEyeAlignment,
+    NoProjection,
+    Error,
+    AlignmentReport,  // 单次完整检测报告
+    FastCheckReport,  // 快速完整检测结果
+}
+
+impl MetricSource {
+    pub const ALL: [MetricSource; 7] = [
+        MetricSource::LeftEyePose,
+        MetricSource::RightEyePose,
+        MetricSource::DualEyeAlignment,
+        MetricSource::NoProjection,
+        MetricSource::Error,
+        MetricSource::AlignmentReport,
+        MetricSource::FastCheckReport,
+    ];
+}
+
+/// 单项指标描述；key 为序列化结果中的字段路径，嵌套字段以 "." 连接（如 "standoff.standoff_mm"）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricDescriptor {
+    pub key: Cow<'static, str>,
+    pub unit: MetricUnit,
+    pub label: Cow<'static, str>,     // 中文名称
+    pub label_en: Cow<'static, str>,  // 英文名称
+}
+
+const fn metric(key: &'static str, unit: MetricUnit, label: &'static str, label_en: &'static str) -> MetricDescriptor {
+    MetricDescriptor { key: Cow::Borrowed(key), unit, label: Cow::Borrowed(label), label_en: Cow::Borrowed(label_en) }
+}
+
+/// 嵌套在 prefix 字段下的指标：key 加字段前缀，名称加中英文限定词（如 "左眼横滚角" / "Left Roll"）
+fn nested(prefix: &str, label: &str, label_en: &str, metrics: &[MetricDescriptor]) -> Vec<MetricDescriptor> {
+    metrics.iter().map(|m| MetricDescriptor {
+        key: Cow::Owned(format!("{}.{}", prefix, m.key)),
+        unit: m.unit,
+        label: Cow::Owned(format!("{}{}", label, m.label)),
+        label_en: Cow::Owned(format!("{} {}", label_en, m.label_en)),
+    }).collect()
+}
+
+/// 单眼姿态结果 (LeftEyePose / RightEyePose)
+pub const POSE_METRICS: &[MetricDescriptor] = &[
+    metric("roll", MetricUnit::Degree, "横滚角", "Roll"),
+    metric("pitch", MetricUnit::Degree, "俯仰角", "Pitch"),
+    metric("yaw", MetricUnit::Degree, "偏航角", "Yaw"),
+    metric("spread.samples", MetricUnit::Count, "平均帧数", "Averaged frames"),
+    metric("spread.spread_deg", MetricUnit::Degree, "多帧离散度", "Pose spread"),
+    metric("standoff.standoff_mm", MetricUnit::Millimeter, "工作距离", "Standoff distance"),
+    metric("reprojection_rms", MetricUnit::Pixel, "重投影RMS", "Reprojection RMS"),
+];
+
+/// 双眼合像结果 (DualEyeAlignment)
+pub const ALIGNMENT_METRICS: &[MetricDescriptor] = &[
+    metric("mean_dx", MetricUnit::Pixel, "水平平均偏差", "Mean Δx"),
+    metric("mean_dy", MetricUnit::Pixel, "垂直平均偏差", "Mean Δy"),
+    metric("rms", MetricUnit::Pixel, "RMS误差", "RMS error"),
+    metric("p95", MetricUnit::Pixel, "分位误差", "Percentile error"),
+    metric("max_err", MetricUnit::Pixel, "最大误差", "Max error"),
+    metric("percentile", MetricUnit::Percent, "误差分位数", "Error percentile"),
+    metric("magnification.left_span_px", MetricUnit::Pixel, "左眼网格跨度", "Left grid span"),
+    metric("magnification.right_span_px", MetricUnit::Pixel, "右眼网格跨度", "Right grid span"),
+    metric("magnification.ratio", MetricUnit::Ratio, "右/左跨度比", "Right/left span ratio"),
+    metric("magnification.max_deviation", MetricUnit::Ratio, "跨度比偏差上限", "Max span ratio deviation"),
+    metric("robust_rms", MetricUnit::Pixel, "稳健RMS误差", "Robust RMS error"),
+    metric("robust_p95", MetricUnit::Pixel, "稳健分位误差", "Robust percentile error"),
+    metric("outlier_indices", MetricUnit::Count, "离群点数", "Outliers"),
+];
+
+/// 无投影结果 (NoProjection)
+pub const NO_PROJECTION_METRICS: &[MetricDescriptor] = &[
+    metric("left_mean", MetricUnit::GrayLevel, "左眼平均亮度", "Left mean intensity"),
+    metric("left_max", MetricUnit::GrayLevel, "左眼最大亮度", "Left peak intensity"),
+    metric("right_mean", MetricUnit::GrayLevel, "右眼平均亮度", "Right mean intensity"),
+    metric("right_max", MetricUnit::GrayLevel, "右眼最大亮度", "Right peak intensity"),
+];
+
+/// 分阶段耗时（姿态与合像结果共有）
+pub const TIMING_METRICS: &[MetricDescriptor] = &[
+    metric("timings.remap_ms", MetricUnit::Millisecond, "重映射耗时", "Remap time"),
+    metric("timings.detect_ms", MetricUnit::Millisecond, "圆点检测耗时", "Detection time"),
+    metric("timings.sort_ms", MetricUnit::Millisecond, "圆点排序耗时", "Sorting time"),
+    metric("timings.pose_ms", MetricUnit::Millisecond, "姿态解算耗时", "Pose time"),
+    metric("timings.alignment_ms", MetricUnit::Millisecond, "合像分析耗时", "Alignment time"),
+];
+
+/// 左眼居中结果 (AlignmentReport.left_centering)
+pub const CENTERING_METRICS: &[MetricDescriptor] = &[
+    metric("top_right_offset_x", MetricUnit::Pixel, "右上角点X偏移", "Top-right offset X"),
+    metric("top_right_offset_y", MetricUnit::Pixel, "右上角点Y偏移", "Top-right offset Y"),
+    metric("bottom_left_offset_x", MetricUnit::Pixel, "左下角点X偏移", "Bottom-left offset X"),
+    metric("bottom_left_offset_y", MetricUnit::Pixel, "左下角点Y偏移", "Bottom-left offset Y"),
+    metric("max_offset_distance", MetricUnit::Pixel, "最大偏移距离", "Max offset distance"),
+    metric("tolerance_px", MetricUnit::Pixel, "居中容差", "Centering tolerance"),
+];
+
+/// 单眼调整建议 (AdjustmentVectors.left_eye_adjustment / right_eye_adjustment)
+pub const EYE_ADJUSTMENT_METRICS: &[MetricDescriptor] = &[
+    metric("roll_adjustment", MetricUnit::Degree, "横滚调整", "Roll adjustment"),
+    metric("pitch_adjustment", MetricUnit::Degree, "俯仰调整", "Pitch adjustment"),
+    metric("yaw_adjustment", MetricUnit::Degree, "偏航调整", "Yaw adjustment"),
+    metric("centering_x", MetricUnit::Pixel, "X方向居中调整", "Centering X"),
+    metric("centering_y", MetricUnit::Pixel, "Y方向居中调整", "Centering Y"),
+];
+
+/// 合像调整建议 (AdjustmentVectors.alignment_adjustment)
+pub const ALIGNMENT_ADJUSTMENT_METRICS: &[MetricDescriptor] = &[
+    metric("delta_x", MetricUnit::Pixel, "X方向偏差", "Δx"),
+    metric("delta_y", MetricUnit::Pixel, "Y方向偏差", "Δy"),
+    metric("rms_error", MetricUnit::Pixel, "RMS误差", "RMS error"),
+];
+
+/// 快速完整检测自身的耗时指标 (FastCheckReport)
+pub const FAST_CHECK_METRICS: &[MetricDescriptor] = &[
+    metric("pipeline_ms", MetricUnit::Millisecond, "流水线耗时", "Pipeline time"),
+    metric("sequential_ms", MetricUnit::Millisecond, "顺序检测耗时", "Sequential time"),
+    metric("speedup", MetricUnit::Ratio, "加速比", "Speedup"),
+];
+
+/// 指定结果类型包含的数值指标，Error 为空
+pub fn source_metrics(source: MetricSource) -> Vec<MetricDescriptor> {
+    let timed = |metrics: &[MetricDescriptor]| metrics.iter().chain(TIMING_METRICS).cloned().collect::<Vec<_>>();
+    match source {
+        MetricSource::LeftEyePose | MetricSource::RightEyePose => timed(POSE_METRICS),
+        MetricSource::DualEyeAlignment => timed(ALIGNMENT_METRICS),
+        MetricSource::NoProjection => NO_PROJECTION_METRICS.to_vec(),
+        MetricSource::Error => Vec::new(),
+        MetricSource::AlignmentReport => [
+            nested("left_pose", "左眼", "Left", POSE_METRICS),
+            nested("right_pose", "右眼", "Right", POSE_METRICS),
+            nested("left_centering", "左眼", "Left", CENTERING_METRICS),
+            nested("alignment", "合像", "Alignment", ALIGNMENT_METRICS),
+            nested("adjustments.left_eye_adjustment", "左眼", "Left", EYE_ADJUSTMENT_METRICS),
+            nested("adjustments.right_eye_adjustment", "右眼", "Right", EYE_ADJUSTMENT_METRICS),
+            nested("adjustments.alignment_adjustment", "合像", "Alignment", ALIGNMENT_ADJUSTMENT_METRICS),
+        ].concat(),
+        MetricSource::FastCheckReport => [
+            nested("left_pose", "左眼", "Left", &source_metrics(MetricSource::LeftEyePose)),
+            nested("right_pose", "右眼", "Right", &source_metrics(MetricSource::RightEyePose)),
+            nested("alignment", "合像", "Alignment", &source_metrics(MetricSource::DualEyeAlignment)),
+            FAST_CHECK_METRICS.to_vec(),
+        ].concat(),
+    }
+}
+
+/// 指标描述表 → key 到单位的映射（随结果发送）
+pub fn unit_map(metrics: &[MetricDescriptor]) -> BTreeMap<String, MetricUnit> {
+    metrics.iter().map(|m| (m.key.to_string(), m.unit)).collect()
+}
+
+/// 指定结果类型的 key 到单位映射
+pub fn source_units(source: MetricSource) -> BTreeMap<String, MetricUnit> {
+    unit_map(&source_metrics(source))
+}
@@ -70,7 +70,7 @@ pub fn get_string_config(config: &HashMap<String, String>, key: &str, default: &
 
 /// 加载标定SimpleBlobDetector参数
 pub fn load_calibration_blob_params() -> BlobDetectorParams {
-    let config = read_config_file("src-tauri/configs/calibration_config.txt");
+    let config = read_config_file(&crate::paths::configs_path("calibration_config.txt"));
     
     if config.is_empty() {
         println!("使用默认标定参数");
@@ -98,7 +98,7 @@ pub fn load_calibration_blob_params() -> BlobDetectorParams {
 
 /// 加载合像检测SimpleBlobDetector参数
 pub fn load_alignment_blob_params() -> BlobDetectorParams {
-    let config = read_config_file("src-tauri/configs/alignment_config.txt");
+    let config = read_config_file(&crate::paths::configs_path("alignment_config.txt"));
     
     if config.is_empty() {
         println!("使用默认合像检测参数");
@@ -136,7 +136,7 @@ pub struct CameraParams {
 
 /// 加载标定相机参数
 pub fn load_calibration_camera_params() -> CameraParams {
-    let config = read_config_file("src-tauri/configs/calibration_config.txt");
+    let config = read_config_file(&crate::paths::configs_path("calibration_config.txt"));
     
     CameraParams {
         frame_rate: get_float_config(&config, "camera_frame_rate", 5.0),
@@ -149,7 +149,7 @@ pub fn load_calibration_camera_params() -> CameraParams {
 
 /// 加载合像检测相机参数
 pub fn load_alignment_camera_params() -> CameraParams {
-    let config = read_config_file("src-tauri/configs/alignment_config.txt");
+    let config = read_config_file(&crate::paths::configs_path("alignment_config.txt"));
     
     CameraParams {
         frame_rate: get_float_config(&config, "camera_frame_rate", 10.0),
@@ -0,0 +1,272 @@
+//! 数据目录解析 - 与当前工作目录无关
+//!
+//! 标定参数、采集图像、调试输出、配置文件统一从数据根目录解析，
+//! 避免 "开发环境能用、打包后找不到文件" 的问题。
+//!
+//! ## 数据根目录优先级
+//! 1. 环境变量 `COSONIC_DATA_DIR`（测试/benchmark/命令行工具覆盖）
+//! 2. `init_data_root()` 设置的目录（应用启动时设为 app-data 目录）
+//! 3. 可执行文件所在目录（未初始化时，如 bin/ 下的命令行工具）
+//!
+//! ## 旧数据迁移
+//! 早期版本按当前工作目录读写数据，启动时 [`migrate_legacy_data`] 把工作目录下的
+//! 旧数据目录一次性复制到数据根目录（只复制数据根目录中尚不存在的目录）。
+//!
+//! ## 目录布局
+//! ```text
+//! <root>/yaml_last_param_file/   标定参数与重映射矩阵
+//! <root>/captures/               采集图像与调试输出
+//! <root>/configs/                配置文件
+//! ```
+//!
+//! ## 调试图像保留策略
+//! 调试图像（`captures/` 下的 [`DEBUG_IMAGE_DIR_NAMES`]）每次写入后按
+//! [`DebugImageRetention`] 删除最旧的文件，避免长时间运行占满磁盘。
+
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+use serde::{Deserialize, Serialize};
+
+/// 数据根目录环境变量
+pub const DATA_DIR_ENV: &str = "COSONIC_DATA_DIR";
+
+/// 标定参数目录名
+pub const PARAMS_DIR_NAME: &str = "yaml_last_param_file";
+/// 采集图像目录名
+pub const CAPTURES_DIR_NAME: &str = "captures";
+/// 配置目录名
+pub const CONFIGS_DIR_NAME: &str = "configs";
+
+/// 调试图像目录名（采集目录下），受保留策略管理
+pub const DEBUG_IMAGE_DIR_NAMES: &[&str] = &["alignment_workflow_debug", "anaglyph", "circles_debug"];
+
+/// 旧数据迁移完成标记文件（数据根目录下）
+pub const LEGACY_MIGRATION_MARKER: &str = ".legacy_data_migrated";
+
+/// 调试图像扩展名
+const DEBUG_IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "bmp", "tif", "tiff"];
+
+static DATA_ROOT: OnceLock<PathBuf> = OnceLock::new();
+static DEBUG_RETENTION: Mutex<Option<DebugImageRetention>> = Mutex::new(None);
+
+/// 设置数据根目录（仅首次调用生效，通常在 Tauri setup 中传入 app-data 目录）
+pub fn init_data_root(root: PathBuf) {
+    if DATA_ROOT.set(root.clone()).is_ok() {
+        println!("📁 数据根目录: {}", root.display());
+    }
+}
+
+/// 当前数据根目录
+pub fn data_root() -> PathBuf {
+    if let Some(dir) = std::env::var_os(DATA_DIR_ENV).filter(|v| !v.is_empty()) {
+        return PathBuf::from(dir);
+    }
+    DATA_ROOT.get().cloned()
+        .or_else(exe_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// 可执行文件所在目录
+fn exe_dir() -> Option<PathBuf> {
+    std::env::current_exe().ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+}
+
+/// 把旧版本按工作目录保存的数据（标定参数、采集图像、配置）一次性复制到数据根目录
+/// 
+/// 只复制 `root` 下尚不存在的目录，完成后写入 [`LEGACY_MIGRATION_MARKER`]，之后不再迁移。
+/// 返回复制的目录列表。
+pub fn migrate_legacy_data(legacy_root: &Path, root: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let marker = root.join(LEGACY_MIGRATION_MARKER);
+    if marker.exists() || same_dir(legacy_root, root) {
+        return Ok(Vec::new());
+    }
+    
+    let mut migrated = Vec::new();
+    for name in [PARAMS_DIR_NAME, CAPTURES_DIR_NAME, CONFIGS_DIR_NAME] {
+        let from = legacy_root.join(name);
+        let to = root.join(name);
+        if from.is_dir() && !to.exists() {
+            copy_dir_recursive(&from, &to)?;
+            println!("📦 旧数据已迁移: {} -> {}", from.display(), to.display());
+            migrated.push(to);
+        }
+    }
+    
+    std::fs::create_dir_all(root)?;
+    std::fs::write(&marker, format!("migrated from {}\n", legacy_root.display()))?;
+    Ok(migrated)
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn copy_dir_recursive(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_recursive(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// 标定参数目录
+pub fn params_dir() -> PathBuf {
+    data_root().join(PARAMS_DIR_NAME)
+}
+
+/// 标定参数目录下的文件，如 `params_path("rectify_maps.yaml")`
+pub fn params_path(file_name: &str) -> String {
+    params_dir().join(file_name).to_string_lossy().to_string()
+}
+
+/// 重映射矩阵文件
+pub fn rectify_maps_path() -> String {
+    params_path("rectify_maps.yaml")
+}
+
+/// 采集目录
+pub fn captures_dir() -> PathBuf {
+    data_root().join(CAPTURES_DIR_NAME)
+}
+
+/// 采集目录下的子路径，如 `captures_path("alignment_workflow_debug")`
+pub fn captures_path(sub_path: &str) -> String {
+    captures_dir().join(sub_path).to_string_lossy().to_string()
+}
+
+/// 配置目录
+pub fn configs_dir() -> PathBuf {
+    data_root().join(CONFIGS_DIR_NAME)
+}
+
+/// 配置目录下的文件，如 `configs_path("camera_params.txt")`
+pub fn configs_path(file_name: &str) -> String {
+    configs_dir().join(file_name).to_string_lossy().to_string()
+}
+
+/// 调试图像保留策略（按目录生效，0 表示该项不限制）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DebugImageRetention {
+    pub max_files: usize,       // 最多保留文件数
+    pub max_age_days: u32,      // 最长保留天数
+    pub max_total_mb: u64,      // 目录总大小上限 (MB)
+}
+
+impl Default for DebugImageRetention {
+    fn default() -> Self {
+        Self { max_files: 200, max_age_days: 7, max_total_mb: 1024 }
+    }
+}
+
+/// 调试图像清理结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct DebugCleanupReport {
+    pub removed_files: usize,
+    pub freed_bytes: u64,
+}
+
+/// 设置全局调试图像保留策略（启动时及配置修改时调用）
+pub fn set_debug_image_retention(policy: DebugImageRetention) {
+    *DEBUG_RETENTION.lock().unwrap() = Some(policy);
+}
+
+/// 当前调试图像保留策略
+pub fn debug_image_retention() -> DebugImageRetention {
+    DEBUG_RETENTION.lock().unwrap().unwrap_or_default()
+}
+
+/// 调试图像目录，如 `debug_images_dir("anaglyph")`
+pub fn debug_images_dir(name: &str) -> String {
+    captures_path(name)
+}
+
+/// 列出目录下的图像文件 (路径, 大小, 修改时间)，按修改时间从新到旧排序
+fn list_debug_images(dir: &Path) -> std::io::Result<Vec<(PathBuf, u64, SystemTime)>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let is_image = path.extension()
+            .and_then(|ext| ext.to_str())
+            .map_or(false, |ext| DEBUG_IMAGE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()));
+        let meta = entry.metadata()?;
+        if !is_image || !meta.is_file() {
+            continue;
+        }
+        files.push((path, meta.len(), meta.modified().unwrap_or(SystemTime::UNIX_EPOCH)));
+    }
+    files.sort_by(|a, b| b.2.cmp(&a.2));
+    Ok(files)
+}
+
+/// 按保留策略清理目录：从最新文件开始保留，超出数量/天数/总大小的旧文件删除
+pub fn enforce_debug_retention(dir: &Path, policy: &DebugImageRetention) -> std::io::Result<DebugCleanupReport> {
+    let mut report = DebugCleanupReport::default();
+    if !dir.is_dir() {
+        return Ok(report);
+    }
+
+    let now = SystemTime::now();
+    let max_age = Duration::from_secs(policy.max_age_days as u64 * 24 * 3600);
+    let max_bytes = policy.max_total_mb * 1024 * 1024;
+    let mut kept_files = 0usize;
+    let mut kept_bytes = 0u64;
+
+    for (path, size, modified) in list_debug_images(dir)? {
+        let too_many = policy.max_files > 0 && kept_files >= policy.max_files;
+        let too_old = policy.max_age_days > 0
+            && now.duration_since(modified).map_or(false, |age| age > max_age);
+        let too_large = policy.max_total_mb > 0 && kept_bytes + size > max_bytes;
+
+        if too_many || too_old || too_large {
+            std::fs::remove_file(&path)?;
+            report.removed_files += 1;
+            report.freed_bytes += size;
+        } else {
+            kept_files += 1;
+            kept_bytes += size;
+        }
+    }
+    Ok(report)
+}
+
+/// 写入调试图像后调用：按全局策略清理该目录，失败只打印警告
+pub fn apply_debug_retention(dir: &str) {
+    match enforce_debug_retention(Path::new(dir), &debug_image_retention()) {
+        Ok(report) if report.removed_files > 0 => {
+            println!("🧹 调试图像清理: {} 删除 {} 个旧文件，释放 {:.1} MB",
+                     dir, report.removed_files, report.freed_bytes as f64 / 1024.0 / 1024.0);
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("⚠️ 调试图像清理失败 {}: {}", dir, e),
+    }
+}
+
+/// 删除全部调试图像目录中的图像文件
+pub fn purge_debug_images() -> std::io::Result<DebugCleanupReport> {
+    let mut report = DebugCleanupReport::default();
+    for name in DEBUG_IMAGE_DIR_NAMES {
+        let dir = captures_dir().join(name);
+        if !dir.is_dir() {
+            continue;
+        }
+        for (path, size, _) in list_debug_images(&dir)? {
+            std::fs::remove_file(&path)?;
+            report.removed_files += 1;
+            report.freed_bytes += size;
+        }
+    }
+    Ok(report)
+}
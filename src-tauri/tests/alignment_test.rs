@@ -1,6 +1,7 @@
 #[cfg(test)]
 use crate::modules::alignment::*;
-use opencv::{core, imgcodecs};
+use super::fixtures::{SyntheticFixture, TestDir, ideal_alignment_system};
+use opencv::{core, imgcodecs, prelude::*};
 
 #[test]
 fn test_alignment_system_creation() {
@@ -98,9 +99,9 @@ fn test_statistical_functions() {
     
     let test_data = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
     
-    let mean_val = mean(&test_data);
-    let rms_val = rms(&test_data);
-    let p95_val = percentile(&test_data, 95.0);
+    let mean_val = mean(&test_data).unwrap();
+    let rms_val = rms(&test_data).unwrap();
+    let p95_val = percentile(&test_data, 95.0).unwrap();
     
     println!("测试数据: {:?}", test_data);
     println!("均值: {:.2}", mean_val);
@@ -165,8 +166,8 @@ fn test_alignment_calculation() {
     
     use crate::modules::alignment::{mean, rms};
     
-    let mean_dx = mean(&dx_values);
-    let mean_dy = mean(&dy_values);
+    let mean_dx = mean(&dx_values).unwrap();
+    let mean_dy = mean(&dy_values).unwrap();
     
     println!("模拟残差:");
     println!("  mean_dx = {:.3} px", mean_dx);
@@ -192,4 +193,2145 @@ fn generate_mock_corners(count: usize, center_x: f32, center_y: f32, noise: f32)
     }
     
     corners
-} 
\ No newline at end of file
+}
+
+/// 生成理想的40点 asymmetric grid 图像坐标（按0..39序号，含轻微旋转）
+fn generate_ideal_grid() -> Vec<opencv::core::Point2f> {
+    let (unit, angle) = (45.0_f32, 2.0_f32.to_radians());
+    (0..40).map(|i| {
+        let c = i / 4;
+        let j = i % 4;
+        let u = (9 - c) as f32 * unit;
+        let v = (2 * j + c % 2) as f32 * unit;
+        opencv::core::Point2f::new(
+            1000.0 + u * angle.cos() - v * angle.sin(),
+            700.0 + u * angle.sin() + v * angle.cos(),
+        )
+    }).collect()
+}
+
+#[test]
+fn test_partial_grid_completion() {
+    use crate::modules::alignment_circles_detection::ConnectedComponentsDetector;
+    println!("=== 测试部分网格补全 ===");
+    
+    let mut detector = ConnectedComponentsDetector::new();
+    detector.set_partial_grid_completion(true, 2);
+    let ideal = generate_ideal_grid();
+    
+    // 缺失两个点（不同列），打乱顺序
+    let missing = [6usize, 17];
+    let mut partial: Vec<_> = ideal.iter().enumerate()
+        .filter(|(i, _)| !missing.contains(i))
+        .map(|(_, p)| *p)
+        .collect();
+    partial.reverse();
+    let partial = core::Vector::<core::Point2f>::from_iter(partial);
+    
+    let (completed, mask) = detector.complete_partial_grid(&partial)
+        .expect("补全不应报错")
+        .expect("布局一致时应补全");
+    
+    assert_eq!(completed.len(), 40);
+    for i in 0..40 {
+        let p = completed.get(i).unwrap();
+        let q = ideal[i];
+        assert_eq!(mask[i], missing.contains(&i), "插值标记错误: 点{}", i);
+        assert!((p.x - q.x).abs() < 0.5 && (p.y - q.y).abs() < 0.5, "点{}位置偏差过大", i);
+    }
+    
+    // 布局不一致（某点严重偏离）时放弃补全
+    let mut broken: Vec<_> = partial.iter().collect();
+    broken[10].x += 20.0;
+    let broken = core::Vector::<core::Point2f>::from_iter(broken);
+    assert!(detector.complete_partial_grid(&broken).unwrap().is_none());
+    
+    // 缺失超过上限时不补全
+    let too_few = core::Vector::<core::Point2f>::from_iter(ideal[3..].iter().copied());
+    assert!(detector.complete_partial_grid(&too_few).unwrap().is_none());
+}
+
+/// 渲染亮点暗底的合成圆点网格图像 (2448×2048, 网格单位100px, 圆点直径78px)
+fn render_synthetic_grid_image() -> opencv::core::Mat {
+    use opencv::{core::Scalar, imgproc};
+    let mut image = core::Mat::new_rows_cols_with_default(2048, 2448, core::CV_8UC1, Scalar::all(30.0)).unwrap();
+    for i in 0..40 {
+        let c = i / 4;
+        let j = i % 4;
+        let center = core::Point::new(
+            1224 - 450 + (9 - c as i32) * 100,
+            1024 - 350 + (2 * j as i32 + c as i32 % 2) * 100,
+        );
+        imgproc::circle(&mut image, center, 39, Scalar::all(230.0), -1, imgproc::LINE_AA, 0).unwrap();
+    }
+    image
+}
+
+#[test]
+fn test_missing_rectify_maps_fallback() {
+    println!("=== 测试重映射矩阵缺失时的降级模式 ===");
+    
+    // 理想针孔相机：重新计算的重映射矩阵应为恒等映射
+    let (dir, mut system) = SyntheticFixture::ideal_system("maps_fallback");
+    
+    // 确保重映射矩阵文件不存在
+    let maps_path = dir.join("rectify_maps.yaml").to_string_lossy().to_string();
+    let _ = std::fs::remove_file(&maps_path);
+    
+    system.ensure_maps_loaded(&maps_path).expect("缺失重映射矩阵时应降级重新计算");
+    assert!(system.maps_regenerated());
+    
+    // 检测仍可正常工作
+    let image = render_synthetic_grid_image();
+    let (left, right) = system.detect_circles_grid(&image, &image, &maps_path)
+        .expect("降级模式下检测应成功");
+    assert_eq!(left.len(), 40);
+    assert_eq!(right.len(), 40);
+    
+    // 恒等映射：序号0点（右上角）位置不变
+    let p0 = left.get(0).unwrap();
+    assert!((p0.x - 1674.0).abs() < 2.0 && (p0.y - 674.0).abs() < 2.0, "序号0点位置异常: {:?}", p0);
+}
+
+#[test]
+fn test_error_percentile_is_configurable() {
+    println!("=== 测试合像分位误差分位数配置 ===");
+    
+    let (_dir, mut system) = SyntheticFixture::ideal_system("percentile");
+    
+    // 右眼第i点沿x偏移 i+1 像素，误差为 1..=40
+    let left = core::Vector::<core::Point2f>::from_iter(generate_ideal_grid());
+    let right = core::Vector::<core::Point2f>::from_iter(
+        generate_ideal_grid().iter().enumerate().map(|(i, p)| core::Point2f::new(p.x + (i + 1) as f32, p.y))
+    );
+    let errors: Vec<f64> = (1..=40).map(|e| e as f64).collect();
+    
+    // 默认P95
+    assert_eq!(system.get_error_percentile(), 95.0);
+    let result = system.check_dual_eye_alignment(&left, &right, false).unwrap();
+    assert_eq!(result.percentile_label(), "P95");
+    assert!((result.p95 - percentile(&errors, 95.0).unwrap()).abs() < 1e-3);
+    
+    // P90 / P99 / P97.5
+    for (pct, label) in [(90.0, "P90"), (99.0, "P99"), (97.5, "P97.5")] {
+        system.set_error_percentile(pct).unwrap();
+        let result = system.check_dual_eye_alignment(&left, &right, false).unwrap();
+        assert_eq!(result.percentile, pct);
+        assert_eq!(result.percentile_label(), label);
+        assert!((result.p95 - percentile(&errors, pct).unwrap()).abs() < 1e-3, "{} 值错误: {}", label, result.p95);
+    }
+    assert!(percentile(&errors, 90.0).unwrap() < percentile(&errors, 99.0).unwrap());
+    
+    // 非法分位数
+    assert!(system.set_error_percentile(0.0).is_err());
+    assert!(system.set_error_percentile(101.0).is_err());
+}
+
+#[test]
+fn test_blank_frame_detection() {
+    use opencv::core::Scalar;
+    println!("=== 测试无投影（全黑帧）判定 ===");
+    
+    let config = BlankFrameConfig::default();
+    
+    // 全黑帧 + 少量暗噪声
+    let mut black = core::Mat::new_rows_cols_with_default(2048, 2448, core::CV_8UC1, Scalar::all(3.0)).unwrap();
+    *black.at_2d_mut::<u8>(100, 100).unwrap() = 20;
+    let intensity = FrameIntensity::measure(&black).unwrap();
+    assert!(intensity.mean < 4.0);
+    assert_eq!(intensity.max, 20.0);
+    assert!(config.is_blank(&intensity));
+    
+    // 正常投影的圆点网格
+    let grid = FrameIntensity::measure(&render_synthetic_grid_image()).unwrap();
+    assert!(!config.is_blank(&grid), "正常投影不应判为全黑: {:?}", grid);
+    
+    // 关闭检查后不再判定
+    let disabled = BlankFrameConfig { enabled: false, ..BlankFrameConfig::default() };
+    assert!(!disabled.is_blank(&intensity));
+}
+
+#[test]
+fn test_merged_dots_split_or_rejected() {
+    use crate::modules::alignment_circles_detection::ConnectedComponentsDetector;
+    use opencv::{core::Scalar, imgproc};
+    println!("=== 测试黏连圆点检查 ===");
+    
+    // 序号0 (1674,674) 与序号4 (1574,774) 之间画一道亮桥，模拟强反光下的黏连
+    let mut image = render_synthetic_grid_image();
+    imgproc::line(&mut image, core::Point::new(1674, 674), core::Point::new(1574, 774),
+                  Scalar::all(230.0), 30, imgproc::LINE_AA, 0).unwrap();
+    
+    // 默认：拆分黏连连通域，仍得到完整网格
+    let mut detector = ConnectedComponentsDetector::new();
+    let centers = detector.detect_circles(&image).unwrap();
+    assert_eq!(detector.last_merged_blob_count(), 1);
+    assert!(detector.last_merge_diagnostic().is_none());
+    assert_eq!(centers.len(), 40);
+    for target in [core::Point2f::new(1674.0, 674.0), core::Point2f::new(1574.0, 774.0)] {
+        let nearest = centers.iter()
+            .map(|p| ((p.x - target.x).powi(2) + (p.y - target.y).powi(2)).sqrt())
+            .fold(f32::MAX, f32::min);
+        assert!(nearest < 3.0, "拆分后圆心偏差过大: {:?} → {:.2}px", target, nearest);
+    }
+    
+    // 关闭拆分：丢弃黏连连通域并给出降低亮度的诊断
+    let mut detector = ConnectedComponentsDetector::new();
+    detector.set_merged_blob_filter(1.7, false).unwrap();
+    let centers = detector.detect_circles(&image).unwrap();
+    assert_eq!(centers.len(), 38);
+    assert!(detector.last_merge_diagnostic().unwrap().contains("降低亮度"));
+    
+    // 比例必须大于1
+    assert!(detector.set_merged_blob_filter(1.0, true).is_err());
+}
+
+#[test]
+fn test_detectability_score_prefers_good_exposure() {
+    use crate::modules::alignment_circles_detection::quick_detectability_score;
+    println!("=== 测试自动曝光可检测性评分 ===");
+    
+    let normal = render_synthetic_grid_image();
+    let mut dim = core::Mat::default();
+    normal.convert_to(&mut dim, -1, 0.15, 0.0).unwrap();
+    let mut saturated = core::Mat::default();
+    normal.convert_to(&mut saturated, -1, 3.0, 0.0).unwrap();
+    
+    let pattern_size = core::Size::new(4, 10);
+    let normal_score = quick_detectability_score(&normal, pattern_size).unwrap();
+    let dim_score = quick_detectability_score(&dim, pattern_size).unwrap();
+    let saturated_score = quick_detectability_score(&saturated, pattern_size).unwrap();
+    println!("正常 {:?}\n欠曝 {:?}\n过曝 {:?}", normal_score, dim_score, saturated_score);
+    
+    assert_eq!(normal_score.blob_count, 40);
+    assert!(normal_score.saturated_ratio < 0.01);
+    assert!(saturated_score.saturated_ratio > 0.9);
+    assert!(normal_score.score > dim_score.score);
+    assert!(normal_score.score > saturated_score.score);
+    
+    // 期望圆点数取自标定板规格：同一图像按更大规格评分时计数不达标
+    let larger = quick_detectability_score(&normal, core::Size::new(5, 10)).unwrap();
+    assert_eq!(larger.blob_count, 40);
+    assert!((larger.score - normal_score.score * 0.8).abs() < 1e-9);
+}
+
+#[test]
+fn test_single_eye_detection_ignores_other_eye() {
+    use opencv::core::Scalar;
+    println!("=== 测试单眼检测（另一眼光机关闭） ===");
+    
+    let (dir, mut system) = SyntheticFixture::ideal_system("single_eye");
+    let maps_path = dir.join("rectify_maps.yaml").to_string_lossy().to_string();
+    
+    let grid = render_synthetic_grid_image();
+    let black = core::Mat::new_rows_cols_with_default(2048, 2448, core::CV_8UC1, Scalar::all(3.0)).unwrap();
+    
+    // 双眼检测在右眼全黑时失败
+    assert!(system.detect_circles_grid(&grid, &black, &maps_path).is_err());
+    
+    // 单眼检测只处理当前眼
+    assert_eq!(system.detect_left_circles_only(&grid, &maps_path).unwrap().len(), 40);
+    assert_eq!(system.detect_right_circles_only(&grid, &maps_path).unwrap().len(), 40);
+    assert!(system.detect_right_circles_only(&black, &maps_path).is_err());
+}
+
+#[test]
+fn test_detector_binary_mask_is_kept() {
+    use crate::modules::alignment_circles_detection::ConnectedComponentsDetector;
+    println!("=== 测试检测二值图保留 ===");
+    
+    let mut detector = ConnectedComponentsDetector::new();
+    assert!(detector.last_binary_mask().is_none());
+    
+    let image = render_synthetic_grid_image();
+    detector.detect_circles(&image).unwrap();
+    let mask = detector.last_binary_mask().expect("检测后应保留二值图");
+    assert_eq!((mask.cols(), mask.rows()), (2448, 2048));
+    // 40个直径78px圆点 ≈ 40 × 4778 px²
+    let foreground = core::count_non_zero(mask).unwrap();
+    assert!(foreground > 40 * 3000 && foreground < 40 * 7000, "前景像素数异常: {}", foreground);
+    assert!(detector.last_binary_threshold().is_some());
+    
+    let path = std::env::temp_dir().join(format!("cosonic_binary_mask_{}.png", std::process::id()));
+    assert!(detector.save_last_binary_mask(&path.to_string_lossy()).unwrap());
+    let _ = std::fs::remove_file(&path);
+    
+    // AlignmentSystem 按左右眼分别保留
+    let (dir, mut system) = SyntheticFixture::ideal_system("binary_mask_sys");
+    let maps_path = dir.join("rectify_maps.yaml").to_string_lossy().to_string();
+    system.detect_left_circles_only(&image, &maps_path).unwrap();
+    assert!(system.get_last_binary_mask(true).is_some());
+    assert!(system.get_last_binary_mask(false).is_none());
+}
+
+#[test]
+fn test_pose_convention_mapping() {
+    println!("=== 测试姿态坐标约定 ===");
+    
+    let (dir, mut system) = SyntheticFixture::ideal_system("pose_convention");
+    let maps_path = dir.join("rectify_maps.yaml").to_string_lossy().to_string();
+    let corners = system.detect_left_circles_only(&render_synthetic_grid_image(), &maps_path).unwrap();
+    
+    // 绕主点旋转 +1° 相当于绕光轴的已知旋转
+    let theta = 1.0_f32.to_radians();
+    let mut rotated = core::Vector::<core::Point2f>::new();
+    for p in corners.iter() {
+        let (dx, dy) = (p.x - 1224.0, p.y - 1024.0);
+        rotated.push(core::Point2f::new(
+            1224.0 + dx * theta.cos() - dy * theta.sin(),
+            1024.0 + dx * theta.sin() + dy * theta.cos(),
+        ));
+    }
+    let roll_delta = |system: &AlignmentSystem| {
+        let before = system.check_left_eye_pose(&corners).unwrap();
+        let after = system.check_left_eye_pose(&rotated).unwrap();
+        let wrap = |d: f64| (d + 540.0).rem_euclid(360.0) - 180.0;
+        (wrap(after.roll - before.roll), wrap(after.yaw - before.yaw), after)
+    };
+    
+    // 默认约定：roll 增加 1°
+    let (d_roll, _, pose) = roll_delta(&system);
+    assert!((d_roll - 1.0).abs() < 0.01, "默认约定 roll 变化: {:.4}", d_roll);
+    let adjustment = system.calculate_adjustment_vectors(None, None, Some(&pose), None);
+    assert_eq!(adjustment.right_eye_adjustment.roll_adjustment, -pose.roll);
+    
+    // roll 取反
+    let mut convention = PoseConvention::default();
+    convention.roll.invert = true;
+    system.set_pose_convention(convention).unwrap();
+    let (d_roll, _, _) = roll_delta(&system);
+    assert!((d_roll + 1.0).abs() < 0.01, "roll取反后变化: {:.4}", d_roll);
+    
+    // roll 与 yaw 互换：旋转体现在上报的 yaw 上
+    let convention = PoseConvention {
+        roll: AxisMapping { source: PoseAxis::Yaw, invert: false },
+        yaw: AxisMapping { source: PoseAxis::Roll, invert: false },
+        ..PoseConvention::default()
+    };
+    system.set_pose_convention(convention).unwrap();
+    let (_, d_yaw, _) = roll_delta(&system);
+    assert!((d_yaw - 1.0).abs() < 0.01, "互换后 yaw 变化: {:.4}", d_yaw);
+    
+    // 像素调整量按 invert_x 变号
+    let convention = PoseConvention { invert_x: true, ..PoseConvention::default() };
+    system.set_pose_convention(convention).unwrap();
+    let alignment = DualEyeAlignmentResult {
+        mean_dx: 2.0, mean_dy: -1.0, rms: 0.1, p95: 0.1, max_err: 0.1, pass: true, percentile: 95.0,
+        magnification: MagnificationCheck::default(), robust_rms: None, robust_p95: None, outlier_indices: Vec::new(),
+    };
+    let adjustment = system.calculate_adjustment_vectors(None, None, None, Some(&alignment));
+    assert_eq!(adjustment.alignment_adjustment.delta_x, 2.0);
+    assert_eq!(adjustment.alignment_adjustment.delta_y, 1.0);
+    
+    // 同一原始分量不能重复使用
+    let invalid = PoseConvention {
+        pitch: AxisMapping { source: PoseAxis::Roll, invert: false },
+        ..PoseConvention::default()
+    };
+    assert!(system.set_pose_convention(invalid).is_err());
+}
+
+#[test]
+fn test_benchmark_summary() {
+    use crate::modules::benchmark::{BenchmarkSummary, TARGET_FRAME_TIME_10FPS_MS};
+    use std::time::Duration;
+    println!("=== 测试检测性能统计汇总 ===");
+    
+    let stage = StageTimings { remap_ms: 10.0, detect_ms: 30.0, sort_ms: 1.0, pose_ms: 2.0, alignment_ms: 3.0 };
+    let times: Vec<Duration> = (1..=10).map(|i| Duration::from_millis(i * 10)).collect();
+    let summary = BenchmarkSummary::from_samples(12, &times, &vec![stage; 10]);
+    assert_eq!((summary.iterations, summary.successful), (12, 10));
+    assert!((summary.mean_ms - 55.0).abs() < 1e-9);
+    assert!((summary.max_ms - 100.0).abs() < 1e-9);
+    assert!((summary.min_ms - 10.0).abs() < 1e-9);
+    assert_eq!(summary.target_ms, TARGET_FRAME_TIME_10FPS_MS);
+    assert!(summary.pass_10fps);
+    assert!((summary.stage_means.detect_ms - 30.0).abs() < 1e-9);
+    
+    // P95 超过 100ms 判为不满足10fps
+    let slow: Vec<Duration> = (1..=10).map(|i| Duration::from_millis(if i == 10 { 300 } else { 50 })).collect();
+    assert!(!BenchmarkSummary::from_samples(10, &slow, &vec![StageTimings::default(); 10]).pass_10fps);
+    
+    // 全部失败
+    let empty = BenchmarkSummary::from_samples(5, &[], &[]);
+    assert_eq!(empty.successful, 0);
+    assert!(!empty.pass_10fps);
+}
+
+#[test]
+fn test_object_origin_pose_equivalence() {
+    println!("=== 测试世界坐标原点选择 ===");
+    use opencv::calib3d;
+    
+    // 平面网格 (mm)，第一个点不在中心
+    let mut world = core::Vector::<core::Point3f>::new();
+    for r in 0..5 {
+        for c in 0..8 {
+            world.push(core::Point3f::new(10.0 + c as f32 * 17.7, 5.0 + r as f32 * 17.7, 0.0));
+        }
+    }
+    let first = build_object_points(&world, ObjectOrigin::FirstPoint).unwrap();
+    let centered = build_object_points(&world, ObjectOrigin::Centroid).unwrap();
+    let custom = build_object_points(&world, ObjectOrigin::CustomIndex(12)).unwrap();
+    assert_eq!(first.get(0).unwrap(), core::Point3f::new(0.0, 0.0, 0.0));
+    assert_eq!(custom.get(12).unwrap(), core::Point3f::new(0.0, 0.0, 0.0));
+    let (cx, cy) = centered.iter().fold((0.0, 0.0), |(x, y), p| (x + p.x as f64, y + p.y as f64));
+    assert!(cx.abs() < 1e-3 && cy.abs() < 1e-3, "质心原点: ({:.4}, {:.4})", cx, cy);
+    assert!(build_object_points(&world, ObjectOrigin::CustomIndex(40)).is_err());
+    
+    // 已知姿态下投影，再分别以两种原点求解
+    let camera_matrix = core::Mat::from_slice_2d(&[
+        [4000.0f64, 0.0, 1224.0],
+        [0.0, 4000.0, 1024.0],
+        [0.0, 0.0, 1.0],
+    ]).unwrap();
+    let dist = core::Mat::zeros(5, 1, core::CV_64F).unwrap().to_mat().unwrap();
+    let rvec_true = core::Mat::from_slice_2d(&[[0.02f64], [-0.03], [0.05]]).unwrap();
+    let tvec_true = core::Mat::from_slice_2d(&[[-60.0f64], [-40.0], [800.0]]).unwrap();
+    let mut image_points = core::Vector::<core::Point2f>::new();
+    calib3d::project_points(&first, &rvec_true, &tvec_true, &camera_matrix, &dist,
+                            &mut image_points, &mut core::Mat::default(), 0.0).unwrap();
+    
+    let solve = |object: &core::Vector<core::Point3f>| {
+        let (mut rvec, mut tvec) = (core::Mat::default(), core::Mat::default());
+        calib3d::solve_pnp(object, &image_points, &camera_matrix, &dist,
+                           &mut rvec, &mut tvec, false, calib3d::SOLVEPNP_IPPE).unwrap();
+        let mut rot = core::Mat::default();
+        calib3d::rodrigues(&rvec, &mut rot, &mut core::Mat::default()).unwrap();
+        let t = [*tvec.at_2d::<f64>(0, 0).unwrap(), *tvec.at_2d::<f64>(1, 0).unwrap(), *tvec.at_2d::<f64>(2, 0).unwrap()];
+        (rot, t)
+    };
+    let (rot_first, t_first) = solve(&first);
+    let (rot_center, t_center) = solve(&centered);
+    
+    // 旋转与原点无关
+    for i in 0..3 {
+        for j in 0..3 {
+            let d = rot_first.at_2d::<f64>(i, j).unwrap() - rot_center.at_2d::<f64>(i, j).unwrap();
+            assert!(d.abs() < 1e-4, "R[{}][{}] 差异 {:.6}", i, j, d);
+        }
+    }
+    
+    // tvec 相差 R * (质心在 FirstPoint 坐标系下的位置)，centered[0] = 第一个点 - 质心
+    let (ox, oy) = (-centered.get(0).unwrap().x as f64, -centered.get(0).unwrap().y as f64);
+    for i in 0..3 {
+        let expected = t_first[i]
+            + rot_first.at_2d::<f64>(i as i32, 0).unwrap() * ox
+            + rot_first.at_2d::<f64>(i as i32, 1).unwrap() * oy;
+        assert!((t_center[i] - expected).abs() < 1e-2, "t[{}]: {:.4} vs {:.4}", i, t_center[i], expected);
+    }
+    println!("✓ FirstPoint tvec = {:?}, Centroid tvec = {:?}", t_first, t_center);
+    
+    // 系统级：姿态角均由旋转矩阵计算，与原点无关
+    let (dir, mut system) = SyntheticFixture::ideal_system("object_origin");
+    let maps_path = dir.join("rectify_maps.yaml").to_string_lossy().to_string();
+    let corners = system.detect_left_circles_only(&render_synthetic_grid_image(), &maps_path).unwrap();
+    assert_eq!(system.get_object_origin(), ObjectOrigin::FirstPoint);
+    let pose_first = system.check_left_eye_pose(&corners).unwrap();
+    system.set_object_origin(ObjectOrigin::Centroid).unwrap();
+    let pose_center = system.check_left_eye_pose(&corners).unwrap();
+    assert!((pose_first.roll - pose_center.roll).abs() < 1e-3);
+    assert!((pose_first.pitch - pose_center.pitch).abs() < 1e-3);
+    assert!((pose_first.yaw - pose_center.yaw).abs() < 1e-3);
+    assert!(system.set_object_origin(ObjectOrigin::CustomIndex(10_000)).is_err());
+    assert_eq!(system.get_object_origin(), ObjectOrigin::Centroid);
+}
+
+#[test]
+fn test_swapped_eyes_invert_adjustment_hint() {
+    println!("=== 测试左右眼互换后调整提示反向 ===");
+    
+    let (_dir, system) = SyntheticFixture::ideal_system("swap_eyes");
+    
+    // 物理右眼相对左眼偏移 (+3, +2) 像素
+    let physical_left = core::Vector::<core::Point2f>::from_iter(generate_ideal_grid());
+    let physical_right = core::Vector::<core::Point2f>::from_iter(
+        generate_ideal_grid().iter().map(|p| core::Point2f::new(p.x + 3.0, p.y + 2.0))
+    );
+    
+    let normal = system.check_dual_eye_alignment(&physical_left, &physical_right, false).unwrap();
+    let normal_hint = normal.adjustment_hint();
+    assert!(normal_hint.contains("(右眼向左调)") && normal_hint.contains("(右眼向下调)"), "{}", normal_hint);
+    
+    // swap_eyes 开启：相机帧按逻辑左右互换，偏差与提示方向随之反向
+    let swapped = system.check_dual_eye_alignment(&physical_right, &physical_left, false).unwrap();
+    let swapped_hint = swapped.adjustment_hint();
+    assert!((swapped.mean_dx + normal.mean_dx).abs() < 1e-6);
+    assert!((swapped.mean_dy + normal.mean_dy).abs() < 1e-6);
+    assert!(swapped_hint.contains("(右眼向右调)") && swapped_hint.contains("(右眼向上调)"), "{}", swapped_hint);
+    assert!((swapped.rms - normal.rms).abs() < 1e-6);
+}
+
+#[test]
+fn test_pose_averaging_noisy_samples() {
+    println!("=== 测试姿态多帧平均 ===");
+    
+    // 确定性伪随机噪声 [-1, 1)
+    let mut seed: u64 = 0x2545F4914F6CDD1D;
+    let mut noise = move || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((seed >> 11) as f64 / (1u64 << 53) as f64) * 2.0 - 1.0
+    };
+    
+    // 真实姿态 roll 接近 180°，单帧 roll 在 ±180° 两侧跳动，直接平均欧拉角会得到约 0°
+    let true_roll = 179.9f64;
+    let true_t = [-60.0f64, 40.0, 800.0];
+    // 旋转以绕z轴为主，pitch/yaw 由旋转矩阵计算，与平移无关
+    let (true_pitch, true_yaw) = (0.0, 0.0);
+    let roll_noise_deg = 0.5;
+    
+    let samples: Vec<PoseSample> = (0..20).map(|_| {
+        let roll = (true_roll + roll_noise_deg * noise()).to_radians();
+        // 绕z轴为主的旋转，叠加少量x/y轴扰动
+        PoseSample {
+            rvec: [0.002 * noise(), 0.002 * noise(), roll],
+            tvec: [true_t[0] + 0.5 * noise(), true_t[1] + 0.5 * noise(), true_t[2] + 2.0 * noise()],
+        }
+    }).collect();
+    
+    let naive_roll = samples.iter()
+        .map(|s| f64::atan2(s.rvec[2].sin(), s.rvec[2].cos()).to_degrees())
+        .sum::<f64>() / samples.len() as f64;
+    assert!((naive_roll.abs() - true_roll).abs() > 10.0, "直接平均欧拉角应在±180°处失效: {:.3}", naive_roll);
+    
+    let averaged = average_pose_samples(&samples).unwrap();
+    println!("平均: roll={:.3}°, pitch={:.3}°, yaw={:.3}°, 离散度 ±{:.3}°",
+             averaged.roll, averaged.pitch, averaged.yaw, averaged.spread.spread_deg);
+    let roll_err = (averaged.roll.abs() - true_roll).abs();
+    assert!(roll_err < 0.3, "roll 平均误差 {:.3}°", roll_err);
+    assert!((averaged.pitch - true_pitch).abs() < 0.1, "pitch={:.4}", averaged.pitch);
+    assert!((averaged.yaw - true_yaw).abs() < 0.1, "yaw={:.4}", averaged.yaw);
+    assert_eq!(averaged.spread.samples, 20);
+    // 均匀噪声 ±0.5° 的标准差约 0.29°
+    assert!(averaged.spread.spread_deg > 0.1 && averaged.spread.spread_deg < 0.5,
+            "离散度 {:.3}°", averaged.spread.spread_deg);
+    
+    // 单帧与原有计算一致，离散度为0
+    let single = average_pose_samples(&samples[..1]).unwrap();
+    assert_eq!(single.spread.samples, 1);
+    assert_eq!(single.spread.spread_deg, 0.0);
+    assert!(average_pose_samples(&[]).is_none());
+}
+
+#[test]
+fn test_pose_averaging_frames_window() {
+    println!("=== 测试姿态平均帧数窗口 ===");
+    
+    let (_dir, mut system) = SyntheticFixture::ideal_system("pose_averaging");
+    let corners = core::Vector::<core::Point2f>::from_iter(generate_ideal_grid());
+    
+    assert_eq!(system.get_pose_averaging_frames(), 1);
+    assert!(system.set_pose_averaging_frames(0).is_err());
+    assert!(system.set_pose_averaging_frames(MAX_POSE_AVERAGING_FRAMES + 1).is_err());
+    
+    let single = system.check_left_eye_pose(&corners).unwrap();
+    assert_eq!(single.spread.samples, 1);
+    
+    system.set_pose_averaging_frames(3).unwrap();
+    let counts: Vec<usize> = (0..5).map(|_| system.check_left_eye_pose(&corners).unwrap().spread.samples).collect();
+    assert_eq!(counts, vec![1, 2, 3, 3, 3]);
+    
+    // 相同输入的平均结果与单帧一致
+    let averaged = system.check_left_eye_pose(&corners).unwrap();
+    assert!((averaged.roll - single.roll).abs() < 1e-6);
+    assert!((averaged.pitch - single.pitch).abs() < 1e-6);
+    assert!(averaged.spread.spread_deg < 1e-6);
+    
+    // 左右眼分别累积，清空后重新计数
+    assert_eq!(system.check_right_eye_pose(&corners).unwrap().spread.samples, 1);
+    system.reset_pose_history();
+    assert_eq!(system.check_left_eye_pose(&corners).unwrap().spread.samples, 1);
+    
+    // 旁路测量为单帧解，不进入多帧平均
+    for _ in 0..3 {
+        assert_eq!(system.measure_left_eye_pose(&corners).unwrap().spread.samples, 1);
+        assert_eq!(system.measure_right_eye_pose(&corners).unwrap().spread.samples, 1);
+    }
+    assert_eq!(system.check_left_eye_pose(&corners).unwrap().spread.samples, 2);
+    assert_eq!(system.check_right_eye_pose(&corners).unwrap().spread.samples, 1);
+}
+
+#[test]
+fn test_rectify_maps_size_mismatch() {
+    println!("=== 测试重映射矩阵尺寸与分辨率不一致 ===");
+    use crate::modules::param_io::*;
+    
+    // 尺寸比对
+    assert!(check_rectify_maps_size((2448, 2048), (2448, 2048), (2448, 2048)).matched);
+    let check = check_rectify_maps_size((2448, 2048), (2448, 2048), (1224, 1024));
+    assert!(!check.matched);
+    let error = check.error.unwrap();
+    assert!(error.contains("2448×2048") && error.contains("1224×1024"), "{}", error);
+    let check = check_rectify_maps_size((2448, 2048), (1224, 1024), (2448, 2048));
+    assert!(!check.matched && check.error.unwrap().contains("右 1224×1024"));
+    
+    // 以 1224×1024 标定的矩阵用于 2448×2048 的检测系统：加载时报错且不保留矩阵
+    let (dir, mut system) = SyntheticFixture::ideal_system("maps_size");
+    let maps_path = dir.join("rectify_maps.yaml").to_string_lossy().to_string();
+    let small = vec![vec![0.0f32; 1224]; 1024];
+    save_rectify_maps(&maps_path, &RectifyLeftRightMaps {
+        left_map1: small.clone(),
+        left_map2: small.clone(),
+        right_map1: small.clone(),
+        right_map2: small,
+    }).unwrap();
+    
+    let file_check = check_rectify_maps_file(&maps_path, (2448, 2048)).unwrap();
+    assert_eq!(file_check.left_map_size, (1224, 1024));
+    assert!(!file_check.matched);
+    
+    let err = system.ensure_maps_loaded(&maps_path).expect_err("尺寸不一致时应拒绝加载");
+    assert!(err.to_string().contains("请重新标定或切换分辨率"), "{}", err);
+    assert!(system.check_rectify_maps_size().is_none(), "不一致的矩阵不应保留");
+    
+    // 降级重新计算的矩阵与分辨率一致
+    std::fs::remove_file(&maps_path).unwrap();
+    system.ensure_maps_loaded(&maps_path).unwrap();
+    assert!(system.check_rectify_maps_size().unwrap().matched);
+}
+
+/// 以 (1224, 1024) 为中心按 map 变换 render_synthetic_grid_image 的圆心位置后渲染
+fn render_mapped_grid_image(map: impl Fn(i32, i32) -> (i32, i32)) -> opencv::core::Mat {
+    use opencv::{core::Scalar, imgproc};
+    let mut image = core::Mat::new_rows_cols_with_default(2048, 2448, core::CV_8UC1, Scalar::all(30.0)).unwrap();
+    for i in 0..40 {
+        let c = i / 4;
+        let j = i % 4;
+        let (dx, dy) = map(-450 + (9 - c as i32) * 100, -350 + (2 * j as i32 + c as i32 % 2) * 100);
+        imgproc::circle(&mut image, core::Point::new(1224 + dx, 1024 + dy), 39, Scalar::all(230.0), -1, imgproc::LINE_AA, 0).unwrap();
+    }
+    image
+}
+
+#[test]
+fn test_grid_fit_flags_transposed_order() {
+    println!("=== 测试网格几何一致性校验 ===");
+    use crate::modules::calibration_circles::Calibrator;
+    
+    let calibrator = Calibrator::new(core::Size::new(2448, 2048), 15.0, 25.0, core::Size::new(4, 10), 0.0).unwrap();
+    let world = calibrator.generate_world_points_from_list().unwrap();
+    let ideal = generate_ideal_grid();
+    
+    let fit = fit_grid_affine(&world, &core::Vector::from_iter(ideal.iter().copied())).unwrap();
+    assert!(fit.is_consistent() && !fit.is_mirrored(), "{:?}", fit);
+    
+    // 图像转置 (x, y) → (y, x)：点序可拟合但为镜像
+    let transposed = core::Vector::from_iter(ideal.iter().map(|p| core::Point2f::new(p.y, p.x)));
+    let fit = fit_grid_affine(&world, &transposed).unwrap();
+    assert!(fit.is_consistent() && fit.is_mirrored(), "{:?}", fit);
+    
+    // 行列互换的点序（按 10×4 读取 4×10）：无法拟合
+    let swapped = core::Vector::from_iter((0..40).map(|i| ideal[(i % 10) * 4 + i / 10]));
+    let fit = fit_grid_affine(&world, &swapped).unwrap();
+    assert!(!fit.is_consistent(), "{:?}", fit);
+}
+
+#[test]
+fn test_transposed_grid_detection() {
+    println!("=== 测试转置/旋转网格的检测 ===");
+    
+    let (dir, mut system) = SyntheticFixture::ideal_system("grid_transpose");
+    let maps_path = dir.join("rectify_maps.yaml").to_string_lossy().to_string();
+    let calibrator = crate::modules::calibration_circles::Calibrator::new(
+        core::Size::new(2448, 2048), 15.0, 25.0, core::Size::new(4, 10), 0.0).unwrap();
+    let world = calibrator.generate_world_points_from_list().unwrap();
+    
+    // 转置图像：不能把镜像点序传给姿态检测
+    let transposed = render_mapped_grid_image(|dx, dy| (dy, dx));
+    let err = system.detect_left_circles_only(&transposed, &maps_path).expect_err("转置网格应被拒绝");
+    assert!(err.to_string().contains("镜像"), "{}", err);
+    
+    // 旋转90°：纠正后的点序与布局一致且非镜像
+    let rotated = render_mapped_grid_image(|dx, dy| (-dy, dx));
+    let corners = system.detect_left_circles_only(&rotated, &maps_path).expect("旋转网格应纠正排序");
+    let fit = fit_grid_affine(&world, &corners).unwrap();
+    assert!(fit.is_consistent() && !fit.is_mirrored(), "{:?}", fit);
+    // 该网格有180°对称性，点0 对应旋转后原点0 或原点39
+    let p0 = corners.get(0).unwrap();
+    let expected = [(1224 - (-350), 1024 + 450), (1224 - 350, 1024 - 450)];
+    assert!(expected.iter().any(|&(x, y)| (p0.x - x as f32).abs() < 2.0 && (p0.y - y as f32).abs() < 2.0),
+            "点0位置: {:?}", p0);
+    
+    // 正常网格不受影响
+    let normal = system.detect_left_circles_only(&render_synthetic_grid_image(), &maps_path).unwrap();
+    let p0 = normal.get(0).unwrap();
+    assert!((p0.x - 1674.0).abs() < 2.0 && (p0.y - 674.0).abs() < 2.0, "点0位置: {:?}", p0);
+}
+
+#[test]
+fn test_render_anaglyph() {
+    println!("=== 测试红/青立体图合成 ===");
+    use opencv::core::{Rect, Scalar, Vec3b};
+    
+    // 左图仅左半亮、右图仅右半亮
+    let mut left = core::Mat::new_rows_cols_with_default(20, 40, core::CV_8UC1, Scalar::all(0.0)).unwrap();
+    let mut right = left.clone();
+    core::Mat::roi_mut(&mut left, Rect::new(0, 0, 20, 20)).unwrap().set_to(&Scalar::all(200.0), &core::no_array()).unwrap();
+    core::Mat::roi_mut(&mut right, Rect::new(20, 0, 20, 20)).unwrap().set_to(&Scalar::all(100.0), &core::no_array()).unwrap();
+    
+    let anaglyph = compose_anaglyph(&left, &right).unwrap();
+    assert_eq!(anaglyph.channels(), 3);
+    assert_eq!(*anaglyph.at_2d::<Vec3b>(10, 5).unwrap(), Vec3b::from([0, 0, 200]), "左图→红");
+    assert_eq!(*anaglyph.at_2d::<Vec3b>(10, 30).unwrap(), Vec3b::from([100, 100, 0]), "右图→青");
+    
+    let small = core::Mat::new_rows_cols_with_default(10, 10, core::CV_8UC1, Scalar::all(0.0)).unwrap();
+    assert!(compose_anaglyph(&left, &small).is_err());
+    
+    // 经重映射：恒等映射下重合的网格呈灰白色
+    let (dir, mut system) = SyntheticFixture::ideal_system("anaglyph");
+    let grid = render_synthetic_grid_image();
+    let anaglyph = system.render_anaglyph(&grid, &grid, &dir.join("rectify_maps.yaml").to_string_lossy()).unwrap();
+    assert_eq!((anaglyph.cols(), anaglyph.rows()), (2448, 2048));
+    let center = *anaglyph.at_2d::<Vec3b>(674, 1674).unwrap();
+    assert!(center[0] > 200 && center[0] == center[2], "重合圆点应为灰白色: {:?}", center);
+}
+
+#[test]
+fn test_pose_standoff_distance() {
+    println!("=== 测试姿态工作距离上报 ===");
+    use crate::modules::calibration_circles::Calibrator;
+    
+    let (_dir, mut system) = SyntheticFixture::ideal_system("pose_standoff");
+    system.set_object_origin(ObjectOrigin::Centroid).unwrap();
+    
+    // 质心位于光轴上、正对相机、距离 600mm：tvec = (0, 0, 600)
+    let calibrator = Calibrator::new(core::Size::new(2448, 2048), 15.0, 25.0, core::Size::new(4, 10), 0.0).unwrap();
+    let world = build_object_points(&calibrator.generate_world_points_from_list().unwrap(), ObjectOrigin::Centroid).unwrap();
+    let project = |distance_mm: f32, scale: f32| core::Vector::<core::Point2f>::from_iter(world.iter().map(|p| {
+        core::Point2f::new(1224.0 + 3000.0 * p.x * scale / distance_mm, 1024.0 + 3000.0 * p.y * scale / distance_mm)
+    }));
+    
+    let pose = system.check_left_eye_pose(&project(600.0, 1.0)).unwrap();
+    assert!((pose.standoff.standoff_mm - 600.0).abs() < 0.5, "{:?}", pose.standoff);
+    assert!(pose.standoff.plausible);
+    assert!(pose.pitch.abs() < 1e-3 && pose.yaw.abs() < 1e-3);
+    
+    // 实际标定板为世界坐标的10倍（单位错误）：解出的距离缩小10倍，告警但不影响姿态判定
+    system.reset_pose_history();
+    let pose = system.check_left_eye_pose(&project(600.0, 10.0)).unwrap();
+    assert!((pose.standoff.standoff_mm - 60.0).abs() < 0.1, "{:?}", pose.standoff);
+    assert!(!pose.standoff.plausible);
+    assert!(pose.pass);
+    
+    // 范围可配置
+    assert!(system.set_standoff_range(StandoffRange { min_mm: 100.0, max_mm: 50.0 }).is_err());
+    assert!(system.set_standoff_range(StandoffRange { min_mm: 0.0, max_mm: 50.0 }).is_err());
+    system.set_standoff_range(StandoffRange { min_mm: 50.0, max_mm: 100.0 }).unwrap();
+    system.reset_pose_history();
+    assert!(system.check_left_eye_pose(&project(600.0, 10.0)).unwrap().standoff.plausible);
+}
+
+#[test]
+fn test_pipeline_single_frame_parallel_eyes() {
+    println!("=== 测试流水线单帧双眼并行检测 ===");
+    use crate::modules::alignment_pipeline::AlignmentPipeline;
+    
+    let (dir, mut system) = SyntheticFixture::ideal_system("pipeline_fast_check");
+    let path = |name: &str| dir.join(name).to_string_lossy().to_string();
+    let image = render_synthetic_grid_image();
+    
+    // 顺序检测作为基准
+    let (seq_left, seq_right) = system.detect_circles_grid(&image, &image, &path("rectify_maps.yaml")).unwrap();
+    
+    let mut pipeline = AlignmentPipeline::with_configure(
+        core::Size::new(2448, 2048),
+        &path("left_camera_params.yaml"),
+        &path("right_camera_params.yaml"),
+        &path("stereo_params.yaml"),
+        &path("rectify_params.yaml"),
+        &path("rectify_maps.yaml"),
+        core::Size::new(4, 10),
+        |sys| sys.set_error_percentile(99.0),
+    ).unwrap();
+    
+    pipeline.process_frame(image.clone(), image.clone()).unwrap();
+    let result = pipeline.get_result_timeout(std::time::Duration::from_secs(30)).expect("流水线未返回结果");
+    assert_eq!(result.frame_id, pipeline.last_frame_id());
+    
+    // 并行检测与顺序检测的姿态一致
+    let seq_pose = system.check_single_eye_pose(&seq_left, system.get_left_camera_params().0, system.get_left_camera_params().1).unwrap();
+    assert!(result.left_pose_result.pass && result.right_pose_result.pass);
+    assert!((result.left_pose_result.roll - seq_pose.roll).abs() < 1e-3);
+    assert_eq!(seq_left.len(), seq_right.len());
+    
+    // configure 作用于 Thread C 的检测系统
+    let alignment = result.alignment_result.expect("双眼姿态通过时应有合像结果");
+    assert_eq!(alignment.percentile, 99.0);
+    assert!(alignment.rms < 1e-3);
+    
+    pipeline.shutdown();
+}
+
+#[test]
+fn test_alignment_settings_carry_to_pipeline_systems() {
+    println!("=== 测试检测设置完整复制到流水线检测系统 ===");
+    use crate::modules::alignment_circles_detection::DetectorConfig;
+    
+    let (dir, mut system) = SyntheticFixture::ideal_system("alignment_settings");
+    system.get_circle_detector_mut().apply_config(&DetectorConfig { min_area: 900.0, connectivity: 8, ..DetectorConfig::default() }).unwrap();
+    system.set_centering_targets(CenteringTargets { expected_top_right: (1500.0, 500.0), expected_bottom_left: (900.0, 1500.0) }).unwrap();
+    system.set_error_percentile(99.0).unwrap();
+    let settings = system.settings();
+    
+    let mut other = ideal_alignment_system(&dir);
+    assert_ne!(other.settings(), settings);
+    other.apply_settings(&settings).unwrap();
+    assert_eq!(other.settings(), settings);
+    
+    // 标定板规格只能在创建时指定
+    let mismatched = AlignmentSettings { pattern_size: (4, 11), ..settings };
+    assert!(other.apply_settings(&mismatched).is_err());
+}
+
+#[test]
+fn test_grid_order_consistent_between_calibration_and_alignment() {
+    println!("=== 测试标定与合像检测圆点排序一致 ===");
+    use crate::modules::alignment_circles_detection::*;
+    use crate::modules::calibration_circles::Calibrator;
+    
+    let ideal = core::Vector::<core::Point2f>::from_iter(generate_ideal_grid());
+    let mut calibrator = Calibrator::new(core::Size::new(2448, 2048), 15.0, 25.0, core::Size::new(4, 10), 0.0).unwrap();
+    
+    // 理想点序与世界坐标几何一致
+    let world = calibrator.generate_world_points_from_list().unwrap();
+    let fit = fit_grid_affine(&world, &ideal).unwrap();
+    assert!(fit.is_consistent() && !fit.is_mirrored());
+    
+    // findCirclesGrid 式的奇偶列颠倒输出
+    let swapped = swap_adjacent_columns(&ideal).unwrap();
+    assert_ne!(swapped.to_vec(), ideal.to_vec());
+    assert_eq!(swap_adjacent_columns(&swapped).unwrap().to_vec(), ideal.to_vec());
+    
+    // 连通域检测的无序输出
+    let mut shuffled: Vec<_> = ideal.to_vec();
+    shuffled.reverse();
+    shuffled.swap(3, 17);
+    let mut detector_sorted = core::Vector::<core::Point2f>::from_iter(shuffled);
+    ConnectedComponentsDetector::new().sort_asymmetric_grid(&mut detector_sorted).unwrap();
+    
+    // 默认策略：标定与合像检测得到相同点序
+    assert_eq!(calibrator.get_grid_order_strategy(), GridOrderStrategy::PcaProjection);
+    let calibration_sorted = calibrator.reorder_asymmetric_circles(&swapped).unwrap();
+    assert_eq!(calibration_sorted.to_vec(), ideal.to_vec());
+    assert_eq!(detector_sorted.to_vec(), calibration_sorted.to_vec());
+    
+    // 旧版列交换策略对 findCirclesGrid 输出给出相同结果
+    calibrator.set_grid_order_strategy(GridOrderStrategy::ColumnSwap);
+    assert_eq!(calibrator.reorder_asymmetric_circles(&swapped).unwrap().to_vec(), ideal.to_vec());
+    let pattern_size = core::Size::new(4, 10);
+    assert_eq!(order_asymmetric_grid(&swapped, GridOrderStrategy::ColumnSwap, pattern_size).unwrap().to_vec(), ideal.to_vec());
+    
+    // 点数与规格不符时原样返回
+    let partial = core::Vector::<core::Point2f>::from_iter(ideal.iter().take(39));
+    assert_eq!(order_asymmetric_grid(&partial, GridOrderStrategy::PcaProjection, pattern_size).unwrap().len(), 39);
+    assert_eq!(order_asymmetric_grid(&ideal, GridOrderStrategy::PcaProjection, core::Size::new(5, 10)).unwrap().to_vec(), ideal.to_vec());
+}
+
+#[test]
+fn test_synthetic_grid_detection() {
+    println!("=== 测试合成圆阵图像检测 ===");
+    
+    let params = SyntheticGridParams {
+        roll_deg: 1.5,
+        tilt_x_deg: 3.0,
+        right_dx_px: 6.0,
+        right_dy_px: -4.0,
+        noise_std: 4.0,
+        seed: 42,
+        ..Default::default()
+    };
+    assert!(SyntheticGridParams { distance_mm: 0.0, ..params }.validate().is_err());
+    assert!(SyntheticGridParams { noise_std: -1.0, ..params }.validate().is_err());
+    
+    // 相同参数逐像素一致
+    let mut fixture = SyntheticFixture::new("synthetic_grid", &params);
+    let (left_again, _) = fixture.system.render_synthetic_pair(&params).unwrap();
+    assert_eq!(fixture.left.data_bytes().unwrap(), left_again.data_bytes().unwrap());
+    
+    let system = &mut fixture.system;
+    let (left_corners, right_corners) = system.detect_circles_grid(&fixture.left, &fixture.right, &fixture.maps_path)
+        .expect("合成图像检测应成功");
+    assert_eq!(left_corners.len(), 40);
+    
+    // 姿态：roll 与合成值一致，质心在光轴附近
+    system.set_object_origin(ObjectOrigin::Centroid).unwrap();
+    let pose = system.check_left_eye_pose(&left_corners).unwrap();
+    assert!((pose.roll - 1.5).abs() < 0.2, "roll={:.3}", pose.roll);
+    assert!((pose.standoff.standoff_mm - 530.0).abs() < 15.0, "{:?}", pose.standoff);
+    
+    // 合像：右眼偏移 (6, -4) 像素
+    let alignment = system.check_dual_eye_alignment(&left_corners, &right_corners, false).unwrap();
+    assert!((alignment.mean_dx - 6.0).abs() < 0.5 && (alignment.mean_dy + 4.0).abs() < 0.5,
+            "Δ=({:.2}, {:.2})", alignment.mean_dx, alignment.mean_dy);
+}
+
+#[test]
+fn test_micrometer_turn_conversion() {
+    println!("=== 测试调整向量换算为千分尺圈数 ===");
+    
+    let eye = |roll: f64, pitch: f64, yaw: f64| EyeAdjustment {
+        roll_adjustment: roll,
+        pitch_adjustment: pitch,
+        yaw_adjustment: yaw,
+        centering_x: 0.0,
+        centering_y: 0.0,
+        needs_adjustment: true,
+    };
+    let adjustments = AdjustmentVectors {
+        left_eye_adjustment: eye(-0.6, 0.2, 0.0),
+        right_eye_adjustment: eye(0.3, 0.0, 0.0),
+        alignment_adjustment: AlignmentAdjustment {
+            delta_x: -12.0,
+            delta_y: 4.0,
+            rms_error: 12.6,
+            adjustment_priority: String::new(),
+        },
+        priority: AdjustmentPriority::LeftEyePose,
+    };
+    let screw = |name: &str, units_per_turn: f64, clockwise_positive: bool| Some(MicrometerAxis {
+        screw: name.to_string(), units_per_turn, clockwise_positive,
+    });
+    let calibration = MicrometerCalibration {
+        left_roll: screw("L1", 0.4, true),
+        right_roll: screw("R1", 0.4, false),
+        alignment_x: screw("R-X", 8.0, true),
+        ..Default::default()
+    };
+    calibration.validate().unwrap();
+    
+    // 仅输出已配置的轴
+    let turns = calibration.to_screw_turns(&adjustments);
+    let axes: Vec<&str> = turns.iter().map(|t| t.axis.as_str()).collect();
+    assert_eq!(axes, vec!["left_roll", "right_roll", "alignment_x"]);
+    
+    // -0.6° / 0.4°每圈 = 1.5 圈，顺时针为正 → 逆时针
+    assert!((turns[0].turns - 1.5).abs() < 1e-9);
+    assert_eq!(turns[0].direction, TurnDirection::CounterClockwise);
+    assert_eq!(turns[0].instruction, "L1 逆时针 1.50 圈");
+    // 逆时针为正的螺杆：+0.3° → 逆时针 0.75 圈
+    assert!((turns[1].turns - 0.75).abs() < 1e-9);
+    assert_eq!(turns[1].direction, TurnDirection::CounterClockwise);
+    // -12px / 8px每圈
+    assert_eq!((turns[2].unit.as_str(), turns[2].direction), ("px", TurnDirection::CounterClockwise));
+    assert!((turns[2].turns - 1.5).abs() < 1e-9);
+    
+    let invalid = MicrometerCalibration { left_yaw: screw("L3", 0.0, true), ..Default::default() };
+    assert!(invalid.validate().is_err());
+}
+
+#[test]
+fn test_statistics_empty_and_nan_inputs() {
+    println!("=== 测试统计函数空输入/NaN ===");
+    
+    // 空输入返回 None 而非 panic/NaN
+    assert_eq!(mean(&[]), None);
+    assert_eq!(rms(&[]), None);
+    assert_eq!(percentile(&[], 95.0), None);
+    
+    // 单元素
+    assert_eq!(mean(&[3.0]), Some(3.0));
+    assert_eq!(rms(&[-3.0]), Some(3.0));
+    assert_eq!(percentile(&[3.0], 95.0), Some(3.0));
+    assert_eq!(percentile(&[3.0], 0.0), Some(3.0));
+    
+    // NaN 被忽略，排序不 panic
+    let data = [f64::NAN, 4.0, 1.0, f64::NAN, 2.0, 3.0];
+    assert_eq!(mean(&data), Some(2.5));
+    assert_eq!(percentile(&data, 100.0), Some(4.0));
+    assert_eq!(percentile(&data, 0.0), Some(1.0));
+    assert_eq!(percentile(&[f64::NAN, f64::NAN], 50.0), None);
+    
+    // 合像判定：全部点被排除时返回错误
+    let (_dir, system) = SyntheticFixture::ideal_system("stats_empty");
+    let empty = core::Vector::<core::Point2f>::new();
+    assert!(system.check_dual_eye_alignment(&empty, &empty, false).is_err());
+}
+
+#[test]
+fn test_clahe_preprocessing_recovers_low_contrast_grid() {
+    use crate::modules::alignment_circles_detection::{ConnectedComponentsDetector, DetectionPreprocessing};
+    println!("=== 测试低对比度圆阵的CLAHE预处理 ===");
+    
+    // 背景30/圆点230 线性压缩到 背景4/圆点12 (暗弱投影)
+    let mut low_contrast = core::Mat::default();
+    render_synthetic_grid_image().convert_to(&mut low_contrast, -1, 0.04, 2.8).unwrap();
+    
+    // 不预处理：背景平坦化后圆点不足最低阈值，检测不完整
+    let mut detector = ConnectedComponentsDetector::new();
+    assert_eq!(detector.get_preprocessing(), DetectionPreprocessing::None);
+    let centers = detector.detect_circles(&low_contrast).unwrap();
+    assert!(centers.len() < 40, "无预处理不应检出完整网格: {}", centers.len());
+    
+    // CLAHE：完整检出
+    let mut detector = ConnectedComponentsDetector::new();
+    detector.set_preprocessing(DetectionPreprocessing::Clahe { clip_limit: 20.0, tile_grid: 8 }).unwrap();
+    let centers = detector.detect_circles(&low_contrast).unwrap();
+    assert_eq!(centers.len(), 40, "CLAHE后应检出完整网格");
+    
+    // 伽马查找表与参数校验
+    let gamma = DetectionPreprocessing::Gamma { gamma: 0.5 }.apply(&low_contrast).unwrap().unwrap();
+    assert_eq!(*gamma.at_2d::<u8>(0, 0).unwrap(), (255.0 * (4.0f64 / 255.0).sqrt()).round() as u8);
+    assert!(DetectionPreprocessing::None.apply(&low_contrast).unwrap().is_none());
+    assert!(detector.set_preprocessing(DetectionPreprocessing::Gamma { gamma: 0.0 }).is_err());
+    assert!(detector.set_preprocessing(DetectionPreprocessing::Clahe { clip_limit: 2.0, tile_grid: 0 }).is_err());
+}
+
+#[test]
+fn test_detector_config_hot_reload() {
+    use crate::modules::alignment_circles_detection::{DetectorConfig, DetectionPreprocessing};
+    println!("=== 测试检测参数热更新 ===");
+    
+    let (_dir, mut system) = SyntheticFixture::ideal_system("detector_config");
+    let image = render_synthetic_grid_image();
+    assert_eq!(system.get_circle_detector_mut().config(), DetectorConfig::default());
+    assert_eq!(system.get_circle_detector_mut().detect_circles(&image).unwrap().len(), 40);
+    
+    // 面积窗口上限低于圆点面积：下一帧全部被过滤
+    let narrow = DetectorConfig { min_area: 100.0, max_area: 1000.0, ..Default::default() };
+    system.get_circle_detector_mut().apply_config(&narrow).unwrap();
+    assert_eq!(system.get_circle_detector_mut().config(), narrow);
+    assert!(system.get_circle_detector_mut().detect_circles(&image).unwrap().len() < 40);
+    
+    // 固定阈值 + 预处理，恢复检测
+    let tuned = DetectorConfig {
+        fixed_threshold: Some(80.0),
+        preprocessing: DetectionPreprocessing::Gamma { gamma: 1.0 },
+        ..Default::default()
+    };
+    system.get_circle_detector_mut().apply_config(&tuned).unwrap();
+    assert_eq!(system.get_circle_detector_mut().detect_circles(&image).unwrap().len(), 40);
+    
+    // 非法参数不生效
+    let invalid = DetectorConfig { min_area: 5000.0, max_area: 4000.0, ..Default::default() };
+    assert!(system.get_circle_detector_mut().apply_config(&invalid).is_err());
+    assert!(DetectorConfig { fixed_threshold: Some(300.0), ..Default::default() }.validate().is_err());
+    assert_eq!(system.get_circle_detector_mut().config(), tuned);
+}
+
+#[test]
+fn test_detection_exclusion_regions() {
+    use crate::modules::alignment_circles_detection::{ConnectedComponentsDetector, ExclusionRegion};
+    use opencv::{core::Scalar, imgproc};
+    println!("=== 测试检测排除区域 ===");
+    
+    // 网格外固定反光点 (与圆点同尺寸)
+    let mut image = render_synthetic_grid_image();
+    imgproc::circle(&mut image, core::Point::new(300, 300), 39, Scalar::all(230.0), -1, imgproc::LINE_AA, 0).unwrap();
+    let near = |centers: &core::Vector<core::Point2f>, x: f32, y: f32| centers.iter()
+        .any(|p| (p.x - x).abs() < 3.0 && (p.y - y).abs() < 3.0);
+    
+    // 无排除区域：反光点被当作圆点
+    let mut detector = ConnectedComponentsDetector::new();
+    let centers = detector.detect_circles(&image).unwrap();
+    assert_eq!(centers.len(), 41);
+    assert!(near(&centers, 300.0, 300.0));
+    
+    // 矩形覆盖反光点：恢复完整网格
+    detector.set_exclusion_regions(vec![ExclusionRegion::Rect { x: 200.0, y: 200.0, width: 200.0, height: 200.0 }]).unwrap();
+    let centers = detector.detect_circles(&image).unwrap();
+    assert_eq!(centers.len(), 40);
+    assert!(!near(&centers, 300.0, 300.0));
+    
+    // 多边形覆盖网格点 (1674, 674)：该点被丢弃，区域外的点不受影响
+    detector.set_exclusion_regions(vec![
+        ExclusionRegion::Rect { x: 200.0, y: 200.0, width: 200.0, height: 200.0 },
+        ExclusionRegion::Polygon { points: vec![[1624.0, 724.0], [1674.0, 614.0], [1724.0, 724.0]] },
+    ]).unwrap();
+    let centers = detector.detect_circles(&image).unwrap();
+    assert_eq!(centers.len(), 39);
+    assert!(!near(&centers, 1674.0, 674.0));
+    assert!(near(&centers, 1574.0, 774.0));
+    assert_eq!(detector.exclusion_regions().len(), 2);
+    
+    // 非法区域
+    assert!(detector.set_exclusion_regions(vec![ExclusionRegion::Polygon { points: vec![[0.0, 0.0], [1.0, 1.0]] }]).is_err());
+    assert!(detector.set_exclusion_regions(vec![ExclusionRegion::Rect { x: 0.0, y: 0.0, width: 0.0, height: 10.0 }]).is_err());
+}
+
+#[test]
+fn test_repeatability_statistics() {
+    use crate::modules::alignment_workflow::MetricRepeatability;
+    println!("=== 测试重复性统计 ===");
+    
+    let stats = MetricRepeatability::from_samples(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap();
+    assert!((stats.mean - 5.0).abs() < 1e-12);
+    // 样本标准差 (n-1): sqrt(32/7)
+    assert!((stats.std_dev - (32.0f64 / 7.0).sqrt()).abs() < 1e-12);
+    assert_eq!((stats.min, stats.max), (2.0, 9.0));
+    
+    // 单个样本标准差为0，NaN 被忽略
+    let single = MetricRepeatability::from_samples(&[f64::NAN, 0.25]).unwrap();
+    assert_eq!(single, MetricRepeatability { mean: 0.25, std_dev: 0.0, min: 0.25, max: 0.25 });
+    
+    assert!(MetricRepeatability::from_samples(&[]).is_none());
+    assert!(MetricRepeatability::from_samples(&[f64::NAN]).is_none());
+}
+
+#[test]
+fn test_plc_register_mapping() {
+    use crate::modules::plc_modbus::{PlcRegisterBank, PlcRegisterMap, PlcModbusConfig};
+    println!("=== 测试PLC寄存器映射 ===");
+    
+    // 自定义映射：通过位放在10，偏差按0.001像素输出，不输出P95/最大误差
+    let map = PlcRegisterMap {
+        pass: Some(10),
+        valid: Some(11),
+        sequence: Some(0),
+        mean_dx: Some(1),
+        mean_dy: Some(2),
+        rms: Some(3),
+        p95: None,
+        max_err: None,
+        robust_rms: Some(4),
+        robust_p95: Some(5),
+        outlier_count: Some(6),
+        scale: 1000.0,
+    };
+    let bank = PlcRegisterBank::new(map);
+    assert_eq!(bank.snapshot().len(), 12);
+    
+    bank.publish_alignment(&DualEyeAlignmentResult {
+        mean_dx: 0.125,
+        mean_dy: -0.5,
+        rms: 40.0, // 超出 i16 范围，饱和
+        p95: 0.8,
+        max_err: 1.2,
+        pass: true,
+        percentile: 95.0,
+        magnification: MagnificationCheck::default(),
+        robust_rms: Some(0.25),
+        robust_p95: None,
+        outlier_indices: vec![3, 17],
+    });
+    let regs = bank.read(0, 12).unwrap();
+    assert_eq!(regs[0], 1, "序号每次更新+1");
+    assert_eq!(regs[1] as i16, 125);
+    assert_eq!(regs[2] as i16, -500);
+    assert_eq!(regs[3] as i16, i16::MAX);
+    assert_eq!(regs[4] as i16, 250, "稳健RMS");
+    assert_eq!(regs[5] as i16, 800, "未启用稳健分位误差时输出原分位误差");
+    assert_eq!(regs[6], 2, "离群点个数");
+    assert_eq!((regs[10], regs[11]), (1, 1));
+    
+    // 检测失败：通过位与偏差清零，序号继续递增
+    bank.invalidate();
+    let regs = bank.read(0, 12).unwrap();
+    assert_eq!(regs[0], 2);
+    assert!(regs[1..].iter().all(|v| *v == 0));
+    
+    // 越界读取
+    assert!(bank.read(10, 3).is_none());
+    
+    // 非法映射
+    assert!(PlcRegisterMap { pass: Some(1), valid: Some(1), ..Default::default() }.validate().is_err());
+    
+    // 旧配置文件缺少稳健统计地址时不输出，不与已有自定义地址冲突
+    let legacy: PlcRegisterMap = serde_json::from_str(r#"{"pass": 8, "valid": 9, "sequence": 10}"#).unwrap();
+    assert_eq!((legacy.robust_rms, legacy.robust_p95, legacy.outlier_count), (None, None, None));
+    assert!(legacy.validate().is_ok());
+    assert!(PlcRegisterMap { scale: 0.0, ..Default::default() }.validate().is_err());
+    assert!(PlcModbusConfig { bind_address: "not-an-address".to_string(), ..Default::default() }.validate().is_err());
+    assert!(PlcModbusConfig::default().validate().is_ok());
+}
+
+#[test]
+fn test_golden_reference_comparison() {
+    use crate::modules::alignment_workflow::{AlignmentMeasurement, save_golden_reference, load_golden_reference};
+    println!("=== 测试黄金基准比较 ===");
+    
+    let reference = AlignmentMeasurement {
+        left_roll: 0.1, left_pitch: -0.2, left_yaw: 0.0,
+        right_roll: 0.05, right_pitch: 0.0, right_yaw: 0.3,
+        mean_dx: 0.4, mean_dy: -0.1, rms: 0.5, p95: 0.9, max_err: 1.2,
+        pass: true,
+        captured_at: "2025-01-01 00:00:00".to_string(),
+    };
+    
+    // 持久化往返
+    let dir = TestDir::new("golden");
+    let path = dir.join("golden_reference.json");
+    save_golden_reference(&path, &reference).unwrap();
+    assert_eq!(load_golden_reference(&path).unwrap(), reference);
+    
+    // 当前测量：Δx 漂移 +0.25，右眼 yaw 漂移 -0.1
+    let current = AlignmentMeasurement { mean_dx: 0.65, right_yaw: 0.2, ..reference.clone() };
+    let deltas = current.compare_to(&reference);
+    assert_eq!(deltas.len(), 11);
+    let delta_of = |name: &str| deltas.iter().find(|d| d.metric == name).unwrap().delta;
+    assert!((delta_of("mean_dx") - 0.25).abs() < 1e-12);
+    assert!((delta_of("right_yaw") + 0.1).abs() < 1e-12);
+    assert_eq!(delta_of("rms"), 0.0);
+    
+    assert!(load_golden_reference(dir.join("missing.json")).is_err());
+}
+
+#[test]
+fn test_compose_mask_overlay() {
+    use opencv::core::{Point2f, Rect, Scalar, Vec3b};
+
+    let image = core::Mat::new_rows_cols_with_default(200, 240, core::CV_8UC1, Scalar::all(50.0)).unwrap();
+    let mut mask = core::Mat::new_rows_cols_with_default(200, 240, core::CV_8UC1, Scalar::all(0.0)).unwrap();
+    core::Mat::roi_mut(&mut mask, Rect::new(150, 100, 40, 40)).unwrap().set_to(&Scalar::all(255.0), &core::no_array()).unwrap();
+
+    let overlay = compose_mask_overlay(&image, Some(&mask), &[Point2f::new(60.0, 60.0)], 1.0).unwrap();
+    assert_eq!((overlay.cols(), overlay.rows(), overlay.channels()), (240, 200, 3));
+    // 二值图前景着绿色，背景保持原灰度
+    let fg = *overlay.at_2d::<Vec3b>(120, 170).unwrap();
+    assert!(fg[1] > 100 && fg[0] < 50 && fg[2] < 50, "前景应着绿色: {:?}", fg);
+    assert_eq!(*overlay.at_2d::<Vec3b>(10, 230).unwrap(), Vec3b::from([50, 50, 50]));
+    // 圆点标记为红圈（半径20）
+    let marker = *overlay.at_2d::<Vec3b>(60, 80).unwrap();
+    assert!(marker[2] > 200 && marker[1] < 100, "圆点标记应为红色: {:?}", marker);
+
+    // 缩放输出；二值图尺寸不符时不着色
+    let small_mask = core::Mat::new_rows_cols_with_default(10, 10, core::CV_8UC1, Scalar::all(255.0)).unwrap();
+    let scaled = compose_mask_overlay(&image, Some(&small_mask), &[], 0.5).unwrap();
+    assert_eq!((scaled.cols(), scaled.rows()), (120, 100));
+    assert_eq!(*scaled.at_2d::<Vec3b>(50, 60).unwrap(), Vec3b::from([50, 50, 50]));
+}
+
+#[test]
+fn test_pose_reprojection_rejects_bad_correspondence() {
+    println!("=== 测试姿态重投影校验 ===");
+    use crate::modules::calibration_circles::Calibrator;
+    
+    let (_dir, mut system) = SyntheticFixture::ideal_system("pose_reprojection");
+    system.set_object_origin(ObjectOrigin::Centroid).unwrap();
+    assert_eq!(system.get_pose_reprojection_max_px(), DEFAULT_POSE_REPROJECTION_MAX_PX);
+    assert!(system.set_pose_reprojection_max_px(0.0).is_err());
+    
+    let calibrator = Calibrator::new(core::Size::new(2448, 2048), 15.0, 25.0, core::Size::new(4, 10), 0.0).unwrap();
+    let world = build_object_points(&calibrator.generate_world_points_from_list().unwrap(), ObjectOrigin::Centroid).unwrap();
+    let ideal: Vec<core::Point2f> = world.iter()
+        .map(|p| core::Point2f::new(1224.0 + 3000.0 * p.x / 600.0, 1024.0 + 3000.0 * p.y / 600.0))
+        .collect();
+    
+    let pose = system.check_left_eye_pose(&core::Vector::from_iter(ideal.clone())).unwrap();
+    assert!(pose.pass && pose.reject_reason.is_none());
+    assert!(pose.reprojection_rms < 0.01, "理想投影重投影误差应接近0: {}", pose.reprojection_rms);
+    
+    // 首尾两点对调（排序错误）：姿态角可能仍在容差内，但重投影误差暴露点对应错误
+    system.set_pose_averaging_frames(3).unwrap();
+    let mut corrupted = ideal.clone();
+    corrupted.swap(0, 39);
+    let pose = system.check_left_eye_pose(&core::Vector::from_iter(corrupted)).unwrap();
+    println!("错误对应: 重投影RMS {:.3} px, {:?}", pose.reprojection_rms, pose.reject_reason);
+    assert!(pose.reprojection_rms > DEFAULT_POSE_REPROJECTION_MAX_PX);
+    assert!(!pose.pass);
+    assert!(pose.message("左眼").contains("重投影RMS"));
+    
+    // 被拒绝的解不进入多帧平均（设置平均帧数时已清空历史）
+    let pose = system.check_left_eye_pose(&core::Vector::from_iter(ideal)).unwrap();
+    assert_eq!(pose.spread.samples, 1);
+    assert!(pose.pass);
+}
+
+#[test]
+fn test_pose_kalman_filter_noisy_then_settling() {
+    println!("=== 测试姿态显示卡尔曼滤波 ===");
+    use crate::modules::pose_filter::{PoseKalmanConfig, PoseKalmanFilter, PoseAngles};
+    
+    assert!(PoseKalmanConfig { process_noise: 0.0, ..Default::default() }.validate().is_err());
+    assert!(PoseKalmanConfig { measurement_noise: f64::NAN, ..Default::default() }.validate().is_err());
+    
+    let mut filter = PoseKalmanFilter::new(PoseKalmanConfig { enabled: true, process_noise: 0.01, measurement_noise: 0.25 });
+    
+    // 操作员调整：roll 20帧内从3°调到0°，之后保持；叠加 ±0.5° 测量噪声 (固定种子)
+    let mut seed = 12345u64;
+    let mut noise = || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (seed >> 33) as f64 / (1u64 << 31) as f64 - 0.5
+    };
+    let mut raw_settled = Vec::new();
+    let mut filtered_settled = Vec::new();
+    for frame in 0..80 {
+        let truth = if frame < 20 { 3.0 * (1.0 - frame as f64 / 20.0) } else { 0.0 };
+        let raw = PoseAngles { roll: truth + noise(), pitch: 0.5 + noise(), yaw: -0.5 + noise() };
+        let filtered = filter.update(0, raw);
+        if frame == 0 {
+            assert_eq!(filtered, raw, "首帧直接采用测量值");
+        }
+        if frame >= 40 {
+            raw_settled.push(raw.roll);
+            filtered_settled.push(filtered.roll);
+        }
+    }
+    
+    let std_dev = |values: &[f64]| {
+        let m = mean(values).unwrap();
+        (values.iter().map(|v| (v - m).powi(2)).sum::<f64>() / values.len() as f64).sqrt()
+    };
+    let raw_std = std_dev(&raw_settled);
+    let filtered_std = std_dev(&filtered_settled);
+    println!("稳定段 roll 标准差: 原始 {:.3}°, 滤波 {:.3}°", raw_std, filtered_std);
+    assert!(filtered_std < 0.6 * raw_std, "滤波后应明显更平稳");
+    assert!(mean(&filtered_settled).unwrap().abs() < 0.1, "调整结束后应收敛到真实值");
+    
+    // 左右眼相互独立；reset 后首帧重新采用测量值
+    let right = PoseAngles { roll: 1.0, pitch: 2.0, yaw: 3.0 };
+    assert_eq!(filter.update(1, right), right);
+    filter.reset();
+    let left = PoseAngles { roll: 5.0, pitch: 5.0, yaw: 5.0 };
+    assert_eq!(filter.update(0, left), left);
+}
+
+#[test]
+fn test_magnification_mismatch_detection() {
+    println!("=== 测试左右网格放大倍率不一致判定 ===");
+    
+    let (_dir, mut system) = SyntheticFixture::ideal_system("magnification");
+    assert_eq!(system.get_magnification_mismatch_max(), DEFAULT_MAGNIFICATION_MISMATCH_MAX);
+    
+    // 右眼网格绕中心缩放 scale，另加平移 (dx, 0)
+    let ideal = generate_ideal_grid();
+    let (cx, cy) = (
+        ideal.iter().map(|p| p.x).sum::<f32>() / ideal.len() as f32,
+        ideal.iter().map(|p| p.y).sum::<f32>() / ideal.len() as f32,
+    );
+    let scaled = |scale: f32, dx: f32| core::Vector::<core::Point2f>::from_iter(
+        ideal.iter().map(|p| core::Point2f::new(cx + (p.x - cx) * scale + dx, cy + (p.y - cy) * scale))
+    );
+    let left = scaled(1.0, 0.0);
+    
+    // 纯平移：倍率一致，通过
+    let shifted = system.check_dual_eye_alignment(&left, &scaled(1.0, 3.0), false).unwrap();
+    assert!(!shifted.magnification.mismatch);
+    assert!((shifted.magnification.ratio - 1.0).abs() < 1e-6);
+    assert!(shifted.pass);
+    
+    // 右眼放大5%：倍率不一致，不通过，提示为倍率而非平移方向
+    let zoomed = system.check_dual_eye_alignment(&left, &scaled(1.05, 0.0), false).unwrap();
+    assert!(zoomed.magnification.mismatch);
+    assert!((zoomed.magnification.ratio - 1.05).abs() < 1e-3, "ratio={:.4}", zoomed.magnification.ratio);
+    assert!(zoomed.mean_dx.abs() < 1e-3, "绕中心缩放的平均偏差应为0");
+    assert!(!zoomed.pass);
+    assert!(zoomed.adjustment_hint().contains("放大倍率不一致"), "{}", zoomed.adjustment_hint());
+    
+    // 网格整体旋转不改变跨度（外接矩形对角线会随旋转变化）
+    let rotated = |deg: f32| core::Vector::<core::Point2f>::from_iter(ideal.iter().map(|p| {
+        let (s, c) = deg.to_radians().sin_cos();
+        let (x, y) = (p.x - cx, p.y - cy);
+        core::Point2f::new(cx + x * c - y * s, cy + x * s + y * c)
+    }));
+    for deg in [5.0, 30.0, 90.0] {
+        let check = MagnificationCheck::compare(&left, &rotated(deg), DEFAULT_MAGNIFICATION_MISMATCH_MAX).unwrap();
+        assert!((check.ratio - 1.0).abs() < 1e-4, "旋转{}°: ratio={:.5}", deg, check.ratio);
+        assert!(!check.mismatch);
+    }
+    
+    // 缩小1%：默认2%上限内；上限收紧到0.5%后判定不一致
+    let slight = scaled(0.99, 0.0);
+    assert!(!system.check_dual_eye_alignment(&left, &slight, false).unwrap().magnification.mismatch);
+    system.set_magnification_mismatch_max(0.005).unwrap();
+    assert!(system.check_dual_eye_alignment(&left, &slight, false).unwrap().magnification.mismatch);
+    
+    // 非法上限
+    assert!(system.set_magnification_mismatch_max(0.0).is_err());
+    assert!(system.set_magnification_mismatch_max(1.0).is_err());
+    assert_eq!(grid_span_px(&core::Vector::<core::Point2f>::new()), None);
+}
+
+#[test]
+fn test_dot_polarity_bright_and_dark_dots() {
+    use crate::modules::alignment_circles_detection::{ConnectedComponentsDetector, DetectorConfig, DotPolarity};
+    println!("=== 测试圆点极性 (亮圆点/暗圆点) ===");
+    
+    // 投影：暗背景亮圆点；打印板：同一图案反相为白底黑圆点
+    let projected = render_synthetic_grid_image();
+    let mut printed = core::Mat::default();
+    core::bitwise_not(&projected, &mut printed, &core::no_array()).unwrap();
+    
+    // 默认亮圆点极性
+    let mut detector = ConnectedComponentsDetector::new();
+    assert_eq!(detector.get_polarity(), DotPolarity::BrightOnDark);
+    let bright_centers = detector.detect_circles(&projected).unwrap();
+    assert_eq!(bright_centers.len(), 40);
+    
+    // 亮圆点极性无法检测打印板
+    let mut detector = ConnectedComponentsDetector::new();
+    assert!(detector.detect_circles(&printed).unwrap().len() < 40, "极性不符不应检出完整网格");
+    
+    // 暗圆点极性：检出完整网格，圆心与投影图一致
+    let mut detector = ConnectedComponentsDetector::new();
+    detector.apply_config(&DetectorConfig { polarity: DotPolarity::DarkOnBright, ..Default::default() }).unwrap();
+    assert_eq!(detector.config().polarity, DotPolarity::DarkOnBright);
+    let dark_centers = detector.detect_circles(&printed).unwrap();
+    assert_eq!(dark_centers.len(), 40);
+    for (a, b) in bright_centers.iter().zip(dark_centers.iter()) {
+        assert!((a.x - b.x).abs() < 0.5 && (a.y - b.y).abs() < 0.5, "圆心不一致: {:?} vs {:?}", a, b);
+    }
+    
+    // 切回亮圆点后重新检测投影图
+    detector.set_polarity(DotPolarity::BrightOnDark);
+    assert_eq!(detector.detect_circles(&projected).unwrap().len(), 40);
+    assert_eq!(serde_json::to_value(DotPolarity::DarkOnBright).unwrap(), "dark_on_bright");
+}
+
+#[test]
+fn test_calibration_alignment_consistency() {
+    use crate::modules::calibration_circles::Calibrator;
+    use crate::modules::alignment_circles_detection::GridOrderStrategy;
+    println!("=== 测试标定/合像模块一致性自检 ===");
+    
+    let (_dir, mut system) = SyntheticFixture::ideal_system("consistency");
+    let image_size = core::Size::new(2448, 2048);
+    
+    // 与合像相同的圆阵参数：两种排序策略均一致；原点约定不同不算不一致
+    system.set_object_origin(ObjectOrigin::Centroid).unwrap();
+    for strategy in [GridOrderStrategy::PcaProjection, GridOrderStrategy::ColumnSwap] {
+        let mut calibrator = Calibrator::new(image_size, 15.0, 25.0, core::Size::new(4, 10), 1.0).unwrap();
+        calibrator.set_grid_order_strategy(strategy);
+        let report = system.check_calibration_consistency(&mut calibrator).unwrap();
+        assert!(report.consistent, "{:?}: {:?}", strategy, report.divergences);
+        assert_eq!(report.world_point_count, (40, 40));
+        assert!(report.ordering_match && report.calibration_order_errors == 0 && report.alignment_order_errors == 0);
+    }
+    
+    // 标定圆心距不同：世界坐标不一致被报告
+    let mut calibrator = Calibrator::new(image_size, 15.0, 20.0, core::Size::new(4, 10), 1.0).unwrap();
+    let report = system.check_calibration_consistency(&mut calibrator).unwrap();
+    assert!(!report.consistent && !report.world_points_match);
+    assert!(report.world_point_max_diff_mm > 1.0, "{:.3}", report.world_point_max_diff_mm);
+    assert!(report.divergences.iter().any(|d| d.contains("世界坐标不一致")), "{:?}", report.divergences);
+}
+
+#[test]
+fn test_alignment_error_histogram() {
+    println!("=== 测试单点合像误差直方图 ===");
+    
+    // 纯函数：[0, 0.5) [0.5, 1.0) [1.0, 1.5)
+    let histogram = error_histogram(&[0.1, 0.2, 0.7, 1.2, f64::NAN], 0.5).unwrap().unwrap();
+    assert_eq!(histogram.bins.iter().map(|b| b.count).collect::<Vec<_>>(), vec![2, 1, 1]);
+    assert_eq!(histogram.total, 4);
+    assert!(error_histogram(&[], 0.5).unwrap().is_none());
+    assert!(error_histogram(&[1.0], 0.0).is_err());
+    assert!(error_histogram(&[1000.0], 0.001).is_err(), "桶数超出上限应拒绝");
+    
+    let (_dir, system) = SyntheticFixture::ideal_system("error_histogram");
+    assert!(system.last_error_histogram(None).is_err(), "尚无合像结果");
+    
+    // 整体平移0.3px，仅一个角点额外偏移5px：主体集中在首桶，另有一个点落在远端桶
+    let ideal = generate_ideal_grid();
+    let left = core::Vector::<core::Point2f>::from_iter(ideal.iter().copied());
+    let right = core::Vector::<core::Point2f>::from_iter(ideal.iter().enumerate().map(|(i, p)| {
+        let extra = if i == 0 { 5.0 } else { 0.0 };
+        core::Point2f::new(p.x + 0.3 + extra, p.y)
+    }));
+    // 旁路测量与判定结果一致，但不记为最近一次合像
+    let measured = system.measure_dual_eye_alignment(&left, &right).unwrap();
+    assert!(system.last_error_histogram(None).is_err(), "旁路测量不应更新最近一次合像");
+    let checked = system.check_dual_eye_alignment(&left, &right, false).unwrap();
+    assert_eq!((measured.rms, measured.max_err), (checked.rms, checked.max_err));
+    system.measure_dual_eye_alignment(&left, &left).unwrap();
+    
+    let histogram = system.last_error_histogram(Some(1.0)).unwrap();
+    assert_eq!(histogram.total, left.len());
+    assert_eq!(histogram.bins.len(), 6);
+    assert_eq!(histogram.bins[0].count, left.len() - 1);
+    assert_eq!(histogram.bins[5].count, 1);
+    assert!((histogram.max_err - 5.3).abs() < 1e-3);
+    
+    // 自动桶宽：最大误差落在最后一个桶
+    let auto = system.last_error_histogram(None).unwrap();
+    assert_eq!(auto.bins.len(), DEFAULT_HISTOGRAM_BINS);
+    assert_eq!(auto.bins.last().unwrap().count, 1);
+}
+
+#[test]
+fn test_reevaluate_last_with_thresholds() {
+    println!("=== 测试按新阈值重新判定最近结果 ===");
+    
+    let (_dir, system) = SyntheticFixture::ideal_system("reevaluate");
+    let active = AcceptanceThresholds::default();
+    assert!(system.reevaluate_last(&active).is_err(), "尚无结果");
+    
+    // 整体平移0.3px，一个角点额外偏移1px
+    let ideal = generate_ideal_grid();
+    let left = core::Vector::<core::Point2f>::from_iter(ideal.iter().copied());
+    let right = core::Vector::<core::Point2f>::from_iter(ideal.iter().enumerate().map(|(i, p)| {
+        core::Point2f::new(p.x + if i == 0 { 1.3 } else { 0.3 }, p.y)
+    }));
+    let result = system.check_dual_eye_alignment(&left, &right, false).unwrap();
+    
+    // 当前阈值下的重新判定与实时结果一致
+    let verdict = system.reevaluate_last(&active).unwrap();
+    let alignment = verdict.alignment.unwrap();
+    assert_eq!(alignment.pass, result.pass);
+    assert!((alignment.rms - result.rms).abs() < 1e-9);
+    assert!((alignment.max_err - 1.3).abs() < 1e-3);
+    assert_eq!(verdict.left_pose_pass, None);
+    
+    // 收紧最大误差阈值：仅最大误差不通过
+    let strict = AcceptanceThresholds { rms_px: 0.5, percentile_px: 0.5, max_px: 1.0, ..active };
+    let alignment = system.reevaluate_last(&strict).unwrap().alignment.unwrap();
+    assert!(alignment.rms_pass && alignment.p95_pass && !alignment.max_pass && !alignment.pass);
+    
+    // 姿态：按缓存的原始角度重新判定
+    let pose = system.check_left_eye_pose(&left).unwrap();
+    let roll = pose.roll.abs();
+    assert!(roll > 0.1, "理想网格带 2° 旋转: roll={:.3}", roll);
+    let loose = AcceptanceThresholds { roll_deg: roll + 0.1, pitch_yaw_deg: 90.0, ..active };
+    let tight = AcceptanceThresholds { roll_deg: roll - 0.1, ..loose };
+    assert_eq!(system.reevaluate_last(&loose).unwrap().left_pose_pass, Some(true));
+    let verdict = system.reevaluate_last(&tight).unwrap();
+    assert_eq!(verdict.left_pose_pass, Some(false));
+    assert!(!verdict.pass);
+    
+    assert!(system.reevaluate_last(&AcceptanceThresholds { rms_px: 0.0, ..active }).is_err());
+}
+
+#[test]
+fn test_configurable_acceptance_thresholds() {
+    println!("=== 测试可配置的判定阈值 ===");
+    use crate::modules::calibration_circles::Calibrator;
+    
+    let (_dir, mut system) = SyntheticFixture::ideal_system("thresholds");
+    assert_eq!(system.get_thresholds(), AcceptanceThresholds::default());
+    
+    // 整体平移0.3px：默认阈值下通过
+    let ideal = generate_ideal_grid();
+    let left = core::Vector::<core::Point2f>::from_iter(ideal.iter().copied());
+    let right = core::Vector::<core::Point2f>::from_iter(ideal.iter().map(|p| core::Point2f::new(p.x + 0.3, p.y)));
+    assert!(system.check_dual_eye_alignment(&left, &right, false).unwrap().pass);
+    
+    // 理想投影绕主点旋转 0.5°：roll ≈ 0.5°，默认阈值下通过
+    system.set_object_origin(ObjectOrigin::Centroid).unwrap();
+    let calibrator = Calibrator::new(core::Size::new(2448, 2048), 15.0, 25.0, core::Size::new(4, 10), 0.0).unwrap();
+    let world = build_object_points(&calibrator.generate_world_points_from_list().unwrap(), ObjectOrigin::Centroid).unwrap();
+    let theta = 0.5_f32.to_radians();
+    let tilted = core::Vector::<core::Point2f>::from_iter(world.iter().map(|p| {
+        let (dx, dy) = (3000.0 * p.x / 600.0, 3000.0 * p.y / 600.0);
+        core::Point2f::new(1224.0 + dx * theta.cos() - dy * theta.sin(), 1024.0 + dx * theta.sin() + dy * theta.cos())
+    }));
+    let pose = system.check_left_eye_pose(&tilted).unwrap();
+    assert!(pose.pass && pose.roll.abs() > 0.3, "roll={:.3}", pose.roll);
+    
+    // 收紧阈值后同一结果不通过
+    let strict = AcceptanceThresholds { roll_deg: 0.2, pitch_yaw_deg: 1.0, rms_px: 0.2, percentile_px: 0.2, max_px: 0.25 };
+    system.set_thresholds(strict).unwrap();
+    assert_eq!(system.get_thresholds(), strict);
+    assert!(!system.check_dual_eye_alignment(&left, &right, false).unwrap().pass);
+    assert!(!system.check_left_eye_pose(&tilted).unwrap().pass);
+    
+    assert!(system.set_thresholds(AcceptanceThresholds { rms_px: -1.0, ..strict }).is_err());
+    assert_eq!(system.get_thresholds(), strict);
+    
+    // 配置：legacy 时为默认常量，写入后取配置值（左右眼不同取较严值）
+    let mut config = crate::config::AlignmentConfig::default();
+    assert_eq!(config.acceptance_thresholds(), AcceptanceThresholds::default());
+    config.set_acceptance_thresholds(&strict).unwrap();
+    assert_eq!(config.acceptance_thresholds(), strict);
+    config.pose_thresholds.right_eye_max_yaw = 0.5;
+    assert_eq!(config.acceptance_thresholds().pitch_yaw_deg, 0.5);
+}
+
+#[test]
+fn test_centering_targets_half_resolution() {
+    println!("=== 测试1/2分辨率下的居中期望位置 ===");
+    use crate::modules::alignment_workflow::write_synthetic_camera_params;
+    
+    let dir = TestDir::new("centering_half");
+    let (full, half) = (core::Size::new(2448, 2048), core::Size::new(1224, 1024));
+    write_synthetic_camera_params(&dir, half).unwrap();
+    let path = |name: &str| dir.join(name).to_string_lossy().to_string();
+    let mut system = AlignmentSystem::new(
+        half,
+        &path("left_camera_params.yaml"),
+        &path("right_camera_params.yaml"),
+        &path("stereo_params.yaml"),
+        &path("rectify_params.yaml"),
+    ).unwrap();
+    
+    // 序号0/39 点位于给定位置，其余点沿对角线分布
+    let corners = |top_right: (f32, f32), bottom_left: (f32, f32)| core::Vector::<core::Point2f>::from_iter((0..40).map(|i| {
+        let t = i as f32 / 39.0;
+        core::Point2f::new(top_right.0 + (bottom_left.0 - top_right.0) * t, top_right.1 + (bottom_left.1 - top_right.1) * t)
+    }));
+    
+    // 默认期望位置按全分辨率设定，右上角点超出 1224×1024：报错而非输出无意义偏移
+    let err = system.check_left_eye_centering(&corners((870.0, 275.0), (605.0, 480.0)), None).unwrap_err();
+    assert!(err.to_string().contains("超出图像范围"), "{}", err);
+    
+    // 按分辨率缩放后的期望位置
+    let targets = CenteringTargets::default().rescaled(full, half);
+    assert_eq!(targets.expected_top_right, (867.5, 272.5));
+    assert_eq!(targets.expected_bottom_left, (607.5, 485.0));
+    assert_eq!(CenteringTargets::default_for((1224, 1024)), targets);
+    
+    // 配置保持默认值时按采集分辨率解析，自定义值原样使用
+    let mut config = crate::config::AlignmentConfig::default();
+    assert_eq!(config.centering_targets_for((1224, 1024)), targets);
+    assert!(config.validate_for_resolution((1224, 1024)).is_ok());
+    config.centering_targets = CenteringTargets { expected_top_right: (2000.0, 545.0), ..CenteringTargets::default() };
+    assert_eq!(config.centering_targets_for((1224, 1024)), config.centering_targets);
+    assert!(config.validate_for_resolution((1224, 1024)).is_err());
+    assert!(config.validate_for_resolution((2448, 2048)).is_ok());
+    system.set_centering_targets(targets).unwrap();
+    
+    let centered = system.check_left_eye_centering(&corners((870.0, 275.0), (605.0, 480.0)), Some(25.0)).unwrap();
+    assert!(centered.is_centered);
+    assert_eq!(centered.expected_top_right, (867.5, 272.5));
+    assert!((centered.top_right_offset_x - 2.5).abs() < 1e-4);
+    assert!((centered.bottom_left_offset_y + 5.0).abs() < 1e-4);
+    
+    let shifted = system.check_left_eye_centering(&corners((910.0, 275.0), (645.0, 480.0)), Some(25.0)).unwrap();
+    assert!(!shifted.is_centered);
+    
+    // 按设备传入期望位置，不改变系统配置
+    let per_device = CenteringTargets { expected_top_right: (910.0, 275.0), expected_bottom_left: (645.0, 480.0) };
+    let result = system.check_left_eye_centering_with_targets(&corners((910.0, 275.0), (645.0, 480.0)), &per_device, Some(25.0)).unwrap();
+    assert!(result.is_centered && result.max_offset_distance < 1e-3);
+    assert_eq!(system.get_centering_targets(), targets);
+    
+    // 超出图像范围的期望位置被拒绝，原配置保留
+    assert!(system.set_centering_targets(CenteringTargets::default()).is_err());
+    assert!(system.set_centering_targets(CenteringTargets { expected_bottom_left: (-1.0, 480.0), ..targets }).is_err());
+    assert_eq!(system.get_centering_targets(), targets);
+}
+
+#[test]
+fn test_alignment_system_4x11_pattern() {
+    println!("=== 测试4×11 (44点) 标定板 ===");
+    use crate::modules::alignment_workflow::write_synthetic_camera_params;
+    use crate::modules::calibration_circles::Calibrator;
+    
+    // 世界坐标按规格生成，4×10 时与原固定坐标清单一致
+    let x = 25.0 / 2.0_f32.sqrt();
+    let list_4x10 = Calibrator::new(core::Size::new(2448, 2048), 15.0, 25.0, core::Size::new(4, 10), 1.0).unwrap()
+        .generate_world_points_from_list().unwrap();
+    assert_eq!(list_4x10.len(), 40);
+    for (i, (col, row)) in [(0, (9.0, 0.0)), (4, (8.0, 1.0)), (38, (0.0, 5.0)), (39, (0.0, 7.0))] {
+        let p = list_4x10.get(i).unwrap();
+        assert!((p.x - col * x).abs() < 1e-4 && (p.y - row * x).abs() < 1e-4, "点{}: {:?}", i, p);
+    }
+    
+    let dir = TestDir::new("pattern_4x11");
+    let image_size = core::Size::new(2448, 2048);
+    write_synthetic_camera_params(&dir, image_size).unwrap();
+    let path = |name: &str| dir.join(name).to_string_lossy().to_string();
+    let maps_path = path("rectify_maps.yaml");
+    let mut system = AlignmentSystem::with_pattern(
+        image_size,
+        &path("left_camera_params.yaml"),
+        &path("right_camera_params.yaml"),
+        &path("stereo_params.yaml"),
+        &path("rectify_params.yaml"),
+        core::Size::new(4, 11),
+        25.0,
+    ).unwrap();
+    assert_eq!(system.expected_points(), 44);
+    assert_eq!(centering_key_indices(system.pattern_size()), (0, 43));
+    assert_eq!(centering_key_indices(core::Size::new(4, 10)), (0, 39));
+    
+    // 合成44点圆阵：按44点检测、排序
+    let (left, right) = system.render_synthetic_pair(&SyntheticGridParams::default()).unwrap();
+    let (left_corners, right_corners) = system.detect_circles_grid(&left, &right, &maps_path)
+        .expect("4×11 合成图像检测应成功");
+    assert_eq!(left_corners.len(), 44);
+    assert_eq!(right_corners.len(), 44);
+    
+    // 末点位于最左列底部：与同列首点 (序号40) x 相近、y 更大
+    let (top_of_last_column, last) = (left_corners.get(40).unwrap(), left_corners.get(43).unwrap());
+    assert!((last.x - top_of_last_column.x).abs() < 5.0 && last.y > top_of_last_column.y);
+    assert!(left_corners.iter().all(|p| p.x >= last.x - 5.0));
+    
+    // 居中关键点取序号0与43，期望位置设在序号39处则判定偏移
+    let point = |i: usize| { let p = left_corners.get(i).unwrap(); (p.x, p.y) };
+    let targets = CenteringTargets { expected_top_right: point(0), expected_bottom_left: point(43) };
+    let centered = system.check_left_eye_centering_with_targets(&left_corners, &targets, Some(10.0)).unwrap();
+    assert!(centered.is_centered && centered.max_offset_distance < 1e-3);
+    let wrong_index = CenteringTargets { expected_bottom_left: point(39), ..targets };
+    assert!(!system.check_left_eye_centering_with_targets(&left_corners, &wrong_index, Some(10.0)).unwrap().is_centered);
+    
+    // 40点输入在4×11规格下被拒绝
+    let truncated = core::Vector::<core::Point2f>::from_iter(left_corners.iter().take(40));
+    assert!(system.check_left_eye_centering(&truncated, None).unwrap_err().to_string().contains("期望44个"));
+    assert!(system.check_dual_eye_alignment(&truncated, &truncated, false).is_err());
+    let alignment = system.check_dual_eye_alignment(&left_corners, &right_corners, false).unwrap();
+    assert!(alignment.rms < 0.5, "rms={:.3}", alignment.rms);
+    
+    // 默认4×10系统检测到44个圆点时判定失败
+    let mut default_system = AlignmentSystem::new(
+        image_size,
+        &path("left_camera_params.yaml"),
+        &path("right_camera_params.yaml"),
+        &path("stereo_params.yaml"),
+        &path("rectify_params.yaml"),
+    ).unwrap();
+    assert_eq!(default_system.expected_points(), 40);
+    assert!(default_system.detect_circles_grid(&left, &right, &maps_path).is_err());
+}
+
+#[test]
+fn test_alignment_report_serialization() {
+    println!("=== 测试完整检测报告组装与序列化 ===");
+    
+    let mut fixture = SyntheticFixture::ideal("alignment_report");
+    let system = &mut fixture.system;
+    let (left_corners, right_corners) = system.detect_circles_grid(&fixture.left, &fixture.right, &fixture.maps_path).unwrap();
+    let point = |i: usize| { let p = left_corners.get(i).unwrap(); (p.x, p.y) };
+    system.set_centering_targets(CenteringTargets { expected_top_right: point(0), expected_bottom_left: point(39) }).unwrap();
+    
+    let left_pose = system.check_left_eye_pose(&left_corners).unwrap();
+    let left_centering = system.check_left_eye_centering(&left_corners, None).unwrap();
+    let right_pose = system.check_right_eye_pose(&right_corners).unwrap();
+    let alignment = system.check_dual_eye_alignment(&left_corners, &right_corners, false).unwrap();
+    let report = system.build_report(&left_pose, &left_centering, &right_pose, &alignment);
+    
+    // 理想夹具且居中目标取实际位置：全部通过
+    assert!(report.pass, "{:?}", report);
+    assert!(matches!(report.adjustments.priority, AdjustmentPriority::Complete));
+    assert_eq!(report.alignment.rms, alignment.rms);
+    
+    // 一个 JSON 对象包含全部阶段
+    let json = serde_json::to_value(&report).unwrap();
+    for key in ["timestamp", "pass", "left_pose", "right_pose", "left_centering", "alignment", "adjustments"] {
+        assert!(json.get(key).is_some(), "缺少字段 {}", key);
+    }
+    assert_eq!(json["adjustments"]["priority"], "Complete");
+    assert_eq!(json["left_centering"]["is_centered"], true);
+    assert!(json["left_pose"]["standoff"]["standoff_mm"].as_f64().unwrap() > 0.0);
+    
+    // 报告附带字段路径 → 单位，每个路径都对应报告中的数值
+    use crate::modules::result_metrics::{source_metrics, MetricSource};
+    for metric in source_metrics(MetricSource::AlignmentReport) {
+        assert!(super::fixtures::is_metric_value(&json, &metric), "{} 在 AlignmentReport 中不是数值", metric.key);
+        assert_eq!(json["units"][&*metric.key], serde_json::to_value(metric.unit).unwrap());
+    }
+    assert_eq!(json["units"]["left_pose.roll"], "degree");
+    assert_eq!(json["units"]["left_centering.max_offset_distance"], "pixel");
+    assert_eq!(json["units"]["adjustments.right_eye_adjustment.yaw_adjustment"], "degree");
+    
+    let restored: AlignmentReport = serde_json::from_value(json).unwrap();
+    assert_eq!(restored.timestamp, report.timestamp);
+    assert_eq!(restored.units, report.units);
+    assert_eq!(restored.right_pose.roll, right_pose.roll);
+    
+    // 任一阶段未通过则总判定不通过
+    let failed_alignment = DualEyeAlignmentResult { pass: false, ..alignment };
+    assert!(!system.build_report(&left_pose, &left_centering, &right_pose, &failed_alignment).pass);
+}
+
+#[test]
+fn test_partial_grid_detection_single_eye() {
+    use opencv::core::Scalar;
+    println!("=== 测试单眼缺失时的降级检测 ===");
+    
+    let (dir, mut system) = SyntheticFixture::ideal_system("partial_grid");
+    let maps_path = dir.join("rectify_maps.yaml").to_string_lossy().to_string();
+    
+    let grid = render_synthetic_grid_image();
+    let black = core::Mat::new_rows_cols_with_default(2048, 2448, core::CV_8UC1, Scalar::all(3.0)).unwrap();
+    
+    // 双眼均检测到
+    let (left, right) = system.detect_circles_grid_partial(&grid, &grid, &maps_path).unwrap();
+    assert_eq!(left.map(|c| c.len()), Ok(40));
+    assert_eq!(right.map(|c| c.len()), Ok(40));
+    
+    // 仅左眼：左眼姿态与居中仍可计算，右眼返回失败原因
+    let (left, right) = system.detect_circles_grid_partial(&grid, &black, &maps_path).unwrap();
+    let reason = right.unwrap_err();
+    assert!(reason.starts_with("右眼圆点网格检测失败"), "{}", reason);
+    // 失败信息附带筛选阶段摘要，操作员无需调试版即可判断过暗/遮挡
+    let right_diagnostics = system.get_last_detection_diagnostics(false).expect("右眼应保留检测诊断").clone();
+    assert_ne!(right_diagnostics.final_count, right_diagnostics.expected);
+    assert!(reason.contains(&right_diagnostics.summary()), "{}", reason);
+    assert_eq!(system.get_last_detection_diagnostics(true).map(|d| d.final_count), Some(40));
+    let left = left.expect("左眼应检测到");
+    assert_eq!(left.len(), 40);
+    assert!(system.check_left_eye_pose(&left).is_ok());
+    assert!(system.check_left_eye_centering(&left, None).is_ok());
+    
+    // 仅右眼
+    let (left, right) = system.detect_circles_grid_partial(&black, &grid, &maps_path).unwrap();
+    assert!(left.unwrap_err().starts_with("左眼圆点网格检测失败"));
+    assert_eq!(right.map(|c| c.len()), Ok(40));
+    
+    // 严格版本仍在任一眼缺失时报错
+    let err = system.detect_circles_grid(&black, &grid, &maps_path).unwrap_err();
+    assert!(err.to_string().contains("左眼圆点网格检测失败"), "{}", err);
+}
+
+#[test]
+fn test_robust_rms_rejects_gross_outlier() {
+    println!("=== 测试 MAD 稳健统计剔除单个误关联点 ===");
+    
+    let (dir, mut system) = SyntheticFixture::ideal_system("robust_rms");
+    let maps_path = dir.join("rectify_maps.yaml").to_string_lossy().to_string();
+    let image = render_synthetic_grid_image();
+    let (left, _) = system.detect_circles_grid(&image, &image, &maps_path).unwrap();
+    
+    // 右眼各点带少量抖动，序号17为误关联点（偏移30px）
+    let right: core::Vector<core::Point2f> = left.iter().enumerate().map(|(i, p)| {
+        let jitter = (i % 3) as f32 * 0.1;
+        if i == 17 { core::Point2f::new(p.x + 30.0, p.y) } else { core::Point2f::new(p.x + jitter, p.y - jitter) }
+    }).collect();
+    system.set_thresholds(AcceptanceThresholds { rms_px: 1.0, percentile_px: 1.0, max_px: 50.0, ..AcceptanceThresholds::default() }).unwrap();
+    
+    // 默认关闭：只有原始统计，RMS被单点拉高导致不通过
+    let raw = system.check_dual_eye_alignment(&left, &right, false).unwrap();
+    assert!(raw.rms > 4.0, "原始RMS应被离群点拉高: {}", raw.rms);
+    assert!(!raw.pass);
+    assert!(raw.robust_rms.is_none() && raw.outlier_indices.is_empty());
+    
+    // 启用稳健统计但仍按原始统计判定
+    let robust_config = RobustStatsConfig { enabled: true, ..RobustStatsConfig::default() };
+    system.set_robust_stats(robust_config).unwrap();
+    let reported = system.check_dual_eye_alignment(&left, &right, false).unwrap();
+    assert_eq!(reported.outlier_indices, vec![17]);
+    assert!(reported.robust_rms.unwrap() < 0.3, "稳健RMS应保持较低: {:?}", reported.robust_rms);
+    assert!(reported.robust_p95.unwrap() < 0.3);
+    assert_eq!(reported.rms, raw.rms);
+    assert!(!reported.pass);
+    
+    // 判定改用稳健统计后通过
+    system.set_robust_stats(RobustStatsConfig { use_for_pass: true, ..robust_config }).unwrap();
+    assert!(system.check_dual_eye_alignment(&left, &right, false).unwrap().pass);
+    
+    // 未启用时不能按稳健统计判定
+    assert!(system.set_robust_stats(RobustStatsConfig { use_for_pass: true, ..RobustStatsConfig::default() }).is_err());
+    assert!(mad_outliers(&[], 3.5, 0.05).is_empty());
+}
+
+#[test]
+fn test_pnp_method_selection_and_fallback() {
+    println!("=== 测试 solvePnP 方法选择与退回 ===");
+    use crate::modules::calibration_circles::Calibrator;
+    
+    let (_dir, mut system) = SyntheticFixture::ideal_system("pnp_method");
+    system.set_object_origin(ObjectOrigin::Centroid).unwrap();
+    assert_eq!(system.get_pnp_method(), PnpMethod::Ippe);
+    
+    let calibrator = Calibrator::new(core::Size::new(2448, 2048), 15.0, 25.0, core::Size::new(4, 10), 0.0).unwrap();
+    let world = build_object_points(&calibrator.generate_world_points_from_list().unwrap(), ObjectOrigin::Centroid).unwrap();
+    let ideal: core::Vector<core::Point2f> = world.iter()
+        .map(|p| core::Point2f::new(1224.0 + 3000.0 * p.x / 600.0, 1024.0 + 3000.0 * p.y / 600.0))
+        .collect();
+    
+    // 各方法对理想投影均给出一致的正对解，并上报所用方法
+    for method in [PnpMethod::Ippe, PnpMethod::Iterative, PnpMethod::Sqpnp] {
+        system.set_pnp_method(method);
+        let pose = system.check_left_eye_pose(&ideal).unwrap();
+        assert_eq!(pose.method_used, method);
+        assert!(pose.pass && pose.roll.abs() < 0.1, "{:?}: {:?}", method, pose);
+        assert!((pose.standoff.standoff_mm - 600.0).abs() < 1.0, "{:?}: {}", method, pose.standoff.standoff_mm);
+    }
+    assert_eq!(serde_json::to_value(PnpMethod::Sqpnp).unwrap(), "sqpnp");
+    
+    // 近退化对应：IPPE 给出非有限解时退回 ITERATIVE
+    let mut tried = Vec::new();
+    let (solution, method_used) = solve_pnp_with_fallback(PnpMethod::Ippe, |method| {
+        tried.push(method);
+        Ok(Some(match method {
+            PnpMethod::Ippe => ([f64::NAN, 0.0, 0.0], [0.0, 0.0, f64::INFINITY]),
+            _ => ([0.0, 0.0, 0.01], [0.0, 0.0, 600.0]),
+        }))
+    }).unwrap();
+    assert_eq!(tried, vec![PnpMethod::Ippe, PnpMethod::Iterative]);
+    assert_eq!(method_used, PnpMethod::Iterative);
+    assert_eq!(solution.1[2], 600.0);
+    
+    // 求解报错同样退回；ITERATIVE 本身失败时不再重试
+    let (_, method_used) = solve_pnp_with_fallback(PnpMethod::Sqpnp, |method| match method {
+        PnpMethod::Sqpnp => Err(opencv::Error::new(core::StsError, "退化".to_string())),
+        _ => Ok(Some(([0.0; 3], [0.0, 0.0, 1.0]))),
+    }).unwrap();
+    assert_eq!(method_used, PnpMethod::Iterative);
+    let mut calls = 0;
+    assert!(solve_pnp_with_fallback(PnpMethod::Iterative, |_| { calls += 1; Ok(None) }).is_err());
+    assert_eq!(calls, 1);
+}
+
+#[test]
+fn test_pose_angles_from_rotation_matrix() {
+    println!("=== 测试由旋转矩阵分解姿态角 (ZYX) ===");
+    use crate::modules::calibration_circles::Calibrator;
+    use opencv::calib3d;
+    
+    let (_dir, mut system) = SyntheticFixture::ideal_system("pose_zyx");
+    system.set_object_origin(ObjectOrigin::Centroid).unwrap();
+    
+    let calibrator = Calibrator::new(core::Size::new(2448, 2048), 15.0, 25.0, core::Size::new(4, 10), 0.0).unwrap();
+    let world = build_object_points(&calibrator.generate_world_points_from_list().unwrap(), ObjectOrigin::Centroid).unwrap();
+    let camera_matrix = core::Mat::from_slice_2d(&[
+        [3000.0f64, 0.0, 1224.0],
+        [0.0, 3000.0, 1024.0],
+        [0.0, 0.0, 1.0],
+    ]).unwrap();
+    let dist = core::Mat::zeros(5, 1, core::CV_64F).unwrap().to_mat().unwrap();
+    
+    // R = Rz(roll)·Ry(yaw)·Rx(pitch)，标定板偏离光轴放置：平移不应影响姿态角
+    let project = |roll: f64, pitch: f64, yaw: f64| {
+        let (sz, cz) = roll.to_radians().sin_cos();
+        let (sy, cy) = yaw.to_radians().sin_cos();
+        let (sx, cx) = pitch.to_radians().sin_cos();
+        let rotation = core::Mat::from_slice_2d(&[
+            [cz * cy, cz * sy * sx - sz * cx, cz * sy * cx + sz * sx],
+            [sz * cy, sz * sy * sx + cz * cx, sz * sy * cx - cz * sx],
+            [-sy, cy * sx, cy * cx],
+        ]).unwrap();
+        let mut rvec = core::Mat::default();
+        calib3d::rodrigues(&rotation, &mut rvec, &mut core::Mat::default()).unwrap();
+        let tvec = core::Mat::from_slice_2d(&[[40.0f64], [-30.0], [600.0]]).unwrap();
+        let mut image_points = core::Vector::<core::Point2f>::new();
+        calib3d::project_points(&world, &rvec, &tvec, &camera_matrix, &dist,
+                                &mut image_points, &mut core::Mat::default(), 0.0).unwrap();
+        image_points
+    };
+    
+    // 纯 3° 偏航
+    let pose = system.check_left_eye_pose(&project(0.0, 0.0, 3.0)).unwrap();
+    println!("yaw 3°: roll={:.4}, pitch={:.4}, yaw={:.4}", pose.roll, pose.pitch, pose.yaw);
+    assert!((pose.yaw - 3.0).abs() < 0.1, "yaw={:.4}", pose.yaw);
+    assert!(pose.roll.abs() < 0.1 && pose.pitch.abs() < 0.1, "{:?}", pose);
+    
+    // 组合旋转逐分量恢复
+    system.reset_pose_history();
+    let pose = system.check_left_eye_pose(&project(2.0, -1.5, 3.0)).unwrap();
+    assert!((pose.roll - 2.0).abs() < 0.1, "roll={:.4}", pose.roll);
+    assert!((pose.pitch + 1.5).abs() < 0.1, "pitch={:.4}", pose.pitch);
+    assert!((pose.yaw - 3.0).abs() < 0.1, "yaw={:.4}", pose.yaw);
+    
+    // 正对相机仅平移：姿态角为0（原视线角定义下 yaw≈3.8°）
+    system.reset_pose_history();
+    let pose = system.check_left_eye_pose(&project(0.0, 0.0, 0.0)).unwrap();
+    assert!(pose.roll.abs() < 0.05 && pose.pitch.abs() < 0.05 && pose.yaw.abs() < 0.05, "{:?}", pose);
+}
+
+#[test]
+fn test_pose_reprojection_shuffled_correspondences() {
+    println!("=== 测试打乱点对应的重投影误差 ===");
+    use crate::modules::calibration_circles::Calibrator;
+    
+    let (_dir, mut system) = SyntheticFixture::ideal_system("pose_shuffled");
+    system.set_object_origin(ObjectOrigin::Centroid).unwrap();
+    
+    let calibrator = Calibrator::new(core::Size::new(2448, 2048), 15.0, 25.0, core::Size::new(4, 10), 0.0).unwrap();
+    let world = build_object_points(&calibrator.generate_world_points_from_list().unwrap(), ObjectOrigin::Centroid).unwrap();
+    let ideal: Vec<core::Point2f> = world.iter()
+        .map(|p| core::Point2f::new(1224.0 + 3000.0 * p.x / 600.0, 1024.0 + 3000.0 * p.y / 600.0))
+        .collect();
+    
+    let good = system.check_left_eye_pose(&core::Vector::from_iter(ideal.clone())).unwrap();
+    assert!(good.reprojection_rms < 0.01 && good.pass);
+    
+    // 确定性打乱 (i → 7i mod 40)，不是整体旋转/镜像，不存在一致的位姿
+    let shuffled: Vec<core::Point2f> = (0..ideal.len()).map(|i| ideal[(i * 7) % ideal.len()]).collect();
+    system.reset_pose_history();
+    let bad = system.check_left_eye_pose(&core::Vector::from_iter(shuffled)).unwrap();
+    println!("打乱对应: 重投影RMS {:.3} px", bad.reprojection_rms);
+    assert!(bad.reprojection_rms > 10.0 * DEFAULT_POSE_REPROJECTION_MAX_PX);
+    assert!(!bad.pass && bad.reject_reason.is_some());
+}
+
+#[test]
+fn test_convergence_check_virtual_image_distance() {
+    println!("=== 测试双眼会聚（虚像距离）检测 ===");
+    
+    // 理想系统 Q 全零，由 P1/P2 与 T 构造：f=3000, 基线60mm → Z = 3000·60 / 视差
+    let (_dir, mut system) = SyntheticFixture::ideal_system("convergence");
+    
+    let left = generate_ideal_grid();
+    let shifted = |disparity: f32| -> core::Vector<core::Point2f> {
+        left.iter().map(|p| core::Point2f::new(p.x - disparity, p.y)).collect()
+    };
+    let left_corners = core::Vector::from_iter(left.clone());
+    
+    // 视差36px → 5000mm，位于默认范围 [1000, 10000] 内
+    let check = system.check_convergence(&left_corners, &shifted(36.0)).unwrap();
+    let distance = check.virtual_image_distance_mm.expect("正视差应可三角化");
+    assert!((distance - 5000.0).abs() < 1.0, "distance={:.3}", distance);
+    assert!((check.mean_disparity_px - 36.0).abs() < 1e-3);
+    assert!(check.disparity_std_px < 1e-3);
+    assert!(check.pass);
+    
+    // 零视差：会聚于无穷远，不通过
+    let check = system.check_convergence(&left_corners, &shifted(0.0)).unwrap();
+    assert!(check.virtual_image_distance_mm.is_none());
+    assert!(!check.pass);
+    
+    // 收紧范围后同一视差超限
+    system.set_convergence_range(ConvergenceRange { min_mm: 1000.0, max_mm: 4000.0 }).unwrap();
+    let check = system.check_convergence(&left_corners, &shifted(36.0)).unwrap();
+    assert!(!check.pass);
+    assert!(system.set_convergence_range(ConvergenceRange { min_mm: 5000.0, max_mm: 1000.0 }).is_err());
+}
+
+#[test]
+fn test_detector_area_window_and_connectivity_params() {
+    use crate::modules::alignment_circles_detection::{ConnectedComponentsDetector, DetectorConfig};
+    println!("=== 测试连通域检测器面积窗口/连通性参数 ===");
+    
+    // 默认预设
+    let detector = ConnectedComponentsDetector::new();
+    assert_eq!(detector.area_range(), (1600.0, 14000.0));
+    assert_eq!(detector.connectivity(), 4);
+    
+    // 单个实心圆点，精确像素面积作为窗口边界参考
+    let mut image = Mat::new_rows_cols_with_default(400, 400, core::CV_8UC1, core::Scalar::all(0.0)).unwrap();
+    opencv::imgproc::circle(&mut image, core::Point::new(200, 200), 30, core::Scalar::all(255.0), -1, opencv::imgproc::LINE_8, 0).unwrap();
+    let area = core::count_non_zero(&image).unwrap() as f64;
+    println!("圆点面积: {} px²", area);
+    
+    // 下限略高于圆点面积：被过滤
+    let mut detector = ConnectedComponentsDetector::with_params(area + 1.0, 14000.0, 8).unwrap();
+    assert_eq!(detector.connectivity(), 8);
+    assert_eq!(detector.config().min_area, area + 1.0);
+    assert_eq!(detector.detect_circles(&image).unwrap().len(), 0);
+    
+    // 下限略低于圆点面积：保留
+    let mut detector = ConnectedComponentsDetector::with_params(area - 1.0, 14000.0, 4).unwrap();
+    let centers = detector.detect_circles(&image).unwrap();
+    assert_eq!(centers.len(), 1);
+    assert!((centers.get(0).unwrap().x - 200.0).abs() < 0.5);
+    
+    // 运行中调整面积窗口后同一检测器重新过滤
+    detector.set_area_range(area + 1.0, 14000.0).unwrap();
+    assert_eq!(detector.detect_circles(&image).unwrap().len(), 0);
+    
+    // 非法参数
+    assert!(ConnectedComponentsDetector::with_params(5000.0, 4000.0, 4).is_err());
+    assert!(ConnectedComponentsDetector::with_params(1600.0, 14000.0, 6).is_err());
+    assert!(detector.set_connectivity(0).is_err());
+    assert!(DetectorConfig { connectivity: 6, ..Default::default() }.validate().is_err());
+}
+
+#[test]
+fn test_detection_diagnostics_counts_consistent() {
+    use crate::modules::alignment_circles_detection::ConnectedComponentsDetector;
+    println!("=== 测试圆点检测分阶段诊断 ===");
+    
+    // 完整网格：各阶段计数自洽，无被拒候选
+    let mut detector = ConnectedComponentsDetector::new();
+    let (centers, diagnostics) = detector.detect_circles_with_diagnostics(&render_synthetic_grid_image()).unwrap();
+    assert_eq!(centers.len(), 40);
+    assert_eq!(diagnostics.expected, 40);
+    assert_eq!(diagnostics.final_count, 40);
+    assert!(diagnostics.shape_passed <= diagnostics.area_passed);
+    assert!(diagnostics.area_passed <= diagnostics.raw_components);
+    assert!(diagnostics.rejected.is_empty());
+    
+    // 一个正常圆点 + 一个过小亮斑 (面积窗口拒绝) + 一条细长亮条 (形状筛选拒绝)
+    let mut image = Mat::new_rows_cols_with_default(400, 600, core::CV_8UC1, core::Scalar::all(0.0)).unwrap();
+    let white = core::Scalar::all(255.0);
+    opencv::imgproc::circle(&mut image, core::Point::new(100, 200), 30, white, -1, opencv::imgproc::LINE_8, 0).unwrap();
+    opencv::imgproc::circle(&mut image, core::Point::new(250, 200), 15, white, -1, opencv::imgproc::LINE_8, 0).unwrap();
+    opencv::imgproc::rectangle(&mut image, core::Rect::new(350, 190, 200, 20), white, -1, opencv::imgproc::LINE_8, 0).unwrap();
+    
+    let mut detector = ConnectedComponentsDetector::new();
+    let (centers, diagnostics) = detector.detect_circles_with_diagnostics(&image).unwrap();
+    println!("{:?}", diagnostics);
+    println!("{}", diagnostics.summary());
+    assert_eq!(centers.len(), 1);
+    assert_eq!(diagnostics.final_count, centers.len());
+    assert!(diagnostics.low_threshold_used, "圆点不足时应启用低阈值兜底");
+    assert_eq!(diagnostics.raw_components, 3);
+    assert_eq!(diagnostics.area_passed, 2);
+    assert_eq!(diagnostics.shape_passed, 1);
+    assert!(diagnostics.shape_passed <= diagnostics.area_passed && diagnostics.area_passed <= diagnostics.raw_components);
+    assert_eq!(diagnostics.rejected.len(), diagnostics.raw_components - diagnostics.shape_passed);
+    assert!(diagnostics.rejected.iter().any(|r| r.x == 350 && r.width == 200), "{:?}", diagnostics.rejected);
+    assert!(diagnostics.rejected.iter().any(|r| r.contains(core::Point::new(250, 200))), "{:?}", diagnostics.rejected);
+    assert_eq!(detector.last_diagnostics(), &diagnostics);
+    
+    // 可序列化给前端，被拒候选输出为 [x, y, 宽, 高]
+    let json = serde_json::to_value(&diagnostics).unwrap();
+    assert_eq!(json["shape_passed"], 1);
+    assert!(json["rejected"].as_array().unwrap().iter().any(|r| r == &serde_json::json!([350, 190, 200, 20])), "{}", json);
+}
+
+#[test]
+fn test_detector_flattening_and_adaptive_threshold_tuning() {
+    use crate::modules::alignment_circles_detection::{AdaptiveThresholdParams, ConnectedComponentsDetector, DetectorConfig};
+    println!("=== 测试背景平坦化/自适应阈值调参 ===");
+    
+    // 确定性噪声样例帧（固定种子，逐像素可复现）
+    let params = SyntheticGridParams { roll_deg: 1.0, noise_std: 10.0, seed: 7, ..Default::default() };
+    let fixture = SyntheticFixture::new("detector_tuning", &params);
+    
+    // 默认参数：自动平坦化核 (377)、仅全局阈值
+    let mut detector = ConnectedComponentsDetector::new();
+    assert_eq!(detector.flatten_kernel_size(), 377);
+    assert!(detector.adaptive_threshold().is_none());
+    assert_eq!(detector.detect_circles(&fixture.left).unwrap().len(), 40);
+    
+    // 构造时指定平坦化核与自适应阈值，仍检测到全部圆点
+    let tuned = DetectorConfig {
+        flatten_kernel_size: Some(251),
+        adaptive_threshold: Some(AdaptiveThresholdParams { block_size: 101, offset: 10.0 }),
+        ..Default::default()
+    };
+    let mut detector = ConnectedComponentsDetector::with_config(&tuned).unwrap();
+    assert_eq!(detector.flatten_kernel_size(), 251);
+    assert_eq!(detector.config(), tuned);
+    assert_eq!(detector.detect_circles(&fixture.left).unwrap().len(), 40);
+    
+    // 恢复自动核尺寸
+    detector.set_flatten_kernel_size(None).unwrap();
+    assert_eq!(detector.flatten_kernel_size(), 377);
+    
+    // 非法参数
+    assert!(detector.set_flatten_kernel_size(Some(100)).is_err());
+    assert!(detector.set_adaptive_threshold(Some(AdaptiveThresholdParams { block_size: 1, offset: 5.0 })).is_err());
+    assert!(detector.set_adaptive_threshold(Some(AdaptiveThresholdParams { block_size: 51, offset: -1.0 })).is_err());
+    assert!(ConnectedComponentsDetector::with_config(&DetectorConfig { flatten_kernel_size: Some(2), ..Default::default() }).is_err());
+}
+
+#[test]
+fn test_detect_circles_tagged_lengths_match() {
+    use crate::modules::alignment_circles_detection::{ConnectedComponentsDetector, RefineTag};
+    println!("=== 测试圆心细化来源标记 ===");
+    
+    // 完整网格：细化后每个圆心一个标记
+    let mut detector = ConnectedComponentsDetector::new();
+    let (centers, tags) = detector.detect_circles_tagged(&render_synthetic_grid_image()).unwrap();
+    assert_eq!(centers.len(), 40);
+    assert_eq!(tags.len(), centers.len());
+    assert_eq!(detector.last_refine_tags().map(|t| t.len()), Some(40));
+    assert!(tags.iter().filter(|t| t.is_refined()).count() > 20, "{:?}", tags);
+    
+    // 圆点不足时不细化，标记全部为质心回退
+    let mut image = Mat::new_rows_cols_with_default(400, 400, core::CV_8UC1, core::Scalar::all(0.0)).unwrap();
+    opencv::imgproc::circle(&mut image, core::Point::new(200, 200), 30, core::Scalar::all(255.0), -1, opencv::imgproc::LINE_8, 0).unwrap();
+    let (centers, tags) = detector.detect_circles_tagged(&image).unwrap();
+    assert_eq!(tags.len(), centers.len());
+    assert!(tags.iter().all(|t| *t == RefineTag::Fallback && !t.is_refined()));
+    assert!(detector.last_refine_tags().is_none());
+    
+    // AlignmentSystem 记录与输出点序一致的细化标记：遮挡一个圆点并补全，插值点随排序保留标记
+    let (dir, mut system) = SyntheticFixture::ideal_system("refine_tags");
+    let maps_path = dir.join("rectify_maps.yaml").to_string_lossy().to_string();
+    system.get_circle_detector_mut().set_partial_grid_completion(true, 2);
+    let grid = render_synthetic_grid_image();
+    let mut occluded = grid.clone();
+    opencv::imgproc::circle(&mut occluded, core::Point::new(1574, 974), 48, core::Scalar::all(30.0), -1, opencv::imgproc::LINE_8, 0).unwrap();
+    let (left, _) = system.detect_circles_grid(&occluded, &grid, &maps_path).unwrap();
+    assert_eq!(left.len(), 40);
+    let tags = system.get_corner_refine_tags(&left).expect("检测输出应有逐点细化标记").to_vec();
+    assert_eq!(tags.len(), left.len());
+    let interpolated = tags.iter().position(|t| *t == RefineTag::Interpolated).expect("遮挡点应补全为插值点");
+    let unrefined = tags.iter().filter(|t| !t.is_refined()).count();
+    assert!(unrefined >= 1 && unrefined * 2 <= left.len(), "{:?}", tags);
+    assert!(system.get_unrefined_points().contains(&left.get(interpolated).unwrap()));
+    
+    // 开启排除后按标记剔除：插值点同样视为未细化，剩余点与细化标记一一对应
+    let object_points = core::Vector::<core::Point3f>::from_iter((0..left.len()).map(|i| core::Point3f::new(i as f32, 0.0, 0.0)));
+    let (_, kept) = system.exclude_unrefined(&object_points, &left).unwrap();
+    assert_eq!(kept.len(), left.len(), "未开启排除时保留全部点");
+    system.set_exclude_unrefined_points(true);
+    assert!(system.get_exclude_unrefined_points());
+    let (kept_object, kept) = system.exclude_unrefined(&object_points, &left).unwrap();
+    assert_eq!(kept.len(), left.len() - unrefined);
+    let expected: Vec<f32> = tags.iter().enumerate().filter(|(_, t)| t.is_refined()).map(|(i, _)| i as f32).collect();
+    assert_eq!(kept_object.iter().map(|p| p.x).collect::<Vec<_>>(), expected);
+    assert!(!kept.iter().any(|p| p == left.get(interpolated).unwrap()));
+    
+    // 非最近一次检测输出的点序无对应标记，不做剔除
+    let mut reordered = left.to_vec();
+    reordered.reverse();
+    let reordered = core::Vector::<core::Point2f>::from_iter(reordered);
+    assert_eq!(system.exclude_unrefined(&object_points, &reordered).unwrap().1.len(), left.len());
+    
+    let pose = system.check_left_eye_pose(&left).unwrap();
+    assert!(pose.reprojection_rms.is_finite());
+}
@@ -0,0 +1,387 @@
+#[cfg(test)]
+use crate::modules::alignment_workflow::{RingBuffer, OverflowPolicy, write_measurement_archive, FrameDecimator, PreviewCache, DetectionResult, LabeledDetectionResult, param_file_path, RECTIFY_MAPS_FILE};
+use super::fixtures::TestDir;
+use crate::paths::{DebugImageRetention, enforce_debug_retention, migrate_legacy_data, LEGACY_MIGRATION_MARKER, PARAMS_DIR_NAME, CONFIGS_DIR_NAME};
+
+#[test]
+fn test_ring_buffer_drop_oldest() {
+    println!("=== 测试环形缓冲区覆盖策略: 丢弃最旧帧 ===");
+    
+    let mut buffer = RingBuffer::new(3);
+    assert_eq!(buffer.policy(), OverflowPolicy::DropOldest);
+    for i in 0..5 {
+        buffer.push(i);
+    }
+    
+    assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+    assert_eq!(buffer.latest(), Some(&4));
+    assert_eq!(buffer.get_stats(), (5, 2, 40.0));
+}
+
+#[test]
+fn test_ring_buffer_drop_newest() {
+    println!("=== 测试环形缓冲区覆盖策略: 丢弃新到达帧 ===");
+    
+    let mut buffer = RingBuffer::with_policy(3, OverflowPolicy::DropNewest);
+    for i in 0..5 {
+        buffer.push(i);
+    }
+    
+    // 保留最早的连续3帧
+    assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+    assert_eq!(buffer.latest(), Some(&2));
+    assert_eq!(buffer.get_stats(), (5, 2, 40.0));
+    
+    // 清空后重新接收新的一组连续帧，统计累计
+    buffer.clear();
+    for i in 5..9 {
+        buffer.push(i);
+    }
+    assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![5, 6, 7]);
+    let (total, dropped, drop_rate) = buffer.get_stats();
+    assert_eq!((total, dropped), (9, 3));
+    assert!((drop_rate - 100.0 / 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_debug_image_retention() {
+    println!("=== 测试调试图像保留策略 ===");
+    
+    use std::time::{Duration, SystemTime};
+    let dir = TestDir::new("debug_retention");
+    std::fs::create_dir_all(&dir).unwrap();
+    
+    // 6张图像，每张 400KB，debug_0 最新，依次早1小时；debug_5 为10天前
+    let now = SystemTime::now();
+    for i in 0..6u64 {
+        let path = dir.join(format!("debug_{}.png", i));
+        std::fs::write(&path, vec![0u8; 400 * 1024]).unwrap();
+        let age = if i == 5 { Duration::from_secs(10 * 24 * 3600) } else { Duration::from_secs(i * 3600) };
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(now - age).unwrap();
+    }
+    // 非图像文件不受影响
+    std::fs::write(dir.join("notes.txt"), b"keep").unwrap();
+    
+    let exists = |i: u64| dir.join(format!("debug_{}.png", i)).exists();
+    
+    // 超过7天的文件删除
+    let policy = DebugImageRetention { max_files: 0, max_age_days: 7, max_total_mb: 0 };
+    let report = enforce_debug_retention(&dir, &policy).unwrap();
+    assert_eq!(report.removed_files, 1);
+    assert!(!exists(5));
+    
+    // 最多保留4个，删除最旧的 debug_4
+    let policy = DebugImageRetention { max_files: 4, max_age_days: 0, max_total_mb: 0 };
+    let report = enforce_debug_retention(&dir, &policy).unwrap();
+    assert_eq!((report.removed_files, report.freed_bytes), (1, 400 * 1024));
+    assert!(exists(3) && !exists(4));
+    
+    // 总大小上限 1MB：仅保留最新2张 (800KB)
+    let policy = DebugImageRetention { max_files: 0, max_age_days: 0, max_total_mb: 1 };
+    let report = enforce_debug_retention(&dir, &policy).unwrap();
+    assert_eq!(report.removed_files, 2);
+    assert!(exists(0) && exists(1) && !exists(2) && !exists(3));
+    assert!(dir.join("notes.txt").exists());
+    
+    // 不存在的目录直接返回
+    let report = enforce_debug_retention(&dir.join("missing"), &DebugImageRetention::default()).unwrap();
+    assert_eq!(report.removed_files, 0);
+}
+
+#[test]
+fn test_legacy_data_migration_runs_once() {
+    println!("=== 测试旧数据一次性迁移到数据根目录 ===");
+    
+    let base = TestDir::new("legacy_migration");
+    let (legacy, root) = (base.join("cwd"), base.join("app_data"));
+    std::fs::create_dir_all(legacy.join(PARAMS_DIR_NAME).join("nested")).unwrap();
+    std::fs::write(legacy.join(PARAMS_DIR_NAME).join("stereo_params.yaml"), b"legacy").unwrap();
+    std::fs::write(legacy.join(PARAMS_DIR_NAME).join("nested").join("a.txt"), b"a").unwrap();
+    std::fs::create_dir_all(legacy.join(CONFIGS_DIR_NAME)).unwrap();
+    std::fs::write(legacy.join(CONFIGS_DIR_NAME).join("system_config.yaml"), b"legacy").unwrap();
+    // 数据根目录已有的配置目录不被覆盖
+    std::fs::create_dir_all(root.join(CONFIGS_DIR_NAME)).unwrap();
+    std::fs::write(root.join(CONFIGS_DIR_NAME).join("system_config.yaml"), b"current").unwrap();
+    
+    let migrated = migrate_legacy_data(&legacy, &root).unwrap();
+    assert_eq!(migrated, vec![root.join(PARAMS_DIR_NAME)]);
+    assert_eq!(std::fs::read(root.join(PARAMS_DIR_NAME).join("stereo_params.yaml")).unwrap(), b"legacy");
+    assert!(root.join(PARAMS_DIR_NAME).join("nested").join("a.txt").exists());
+    assert_eq!(std::fs::read(root.join(CONFIGS_DIR_NAME).join("system_config.yaml")).unwrap(), b"current");
+    assert!(root.join(LEGACY_MIGRATION_MARKER).exists());
+    
+    // 已迁移后不再复制（即使数据根目录中的参数被删除）
+    std::fs::remove_dir_all(root.join(PARAMS_DIR_NAME)).unwrap();
+    assert!(migrate_legacy_data(&legacy, &root).unwrap().is_empty());
+    assert!(!root.join(PARAMS_DIR_NAME).exists());
+}
+
+#[test]
+fn test_measurement_archive_roundtrip() {
+    println!("=== 测试测量存档打包 ===");
+    
+    use std::io::Read;
+    let dir = TestDir::new("measurement_archive");
+    let path = dir.join("unit_0001.zip");
+    let entries = vec![
+        ("raw_left.png".to_string(), vec![0x89u8; 4096]),
+        ("report.json".to_string(), br#"{"error":"未检测到圆点"}"#.to_vec()),
+        ("config.yaml".to_string(), b"version: '1.0'\n".to_vec()),
+    ];
+    write_measurement_archive(&path, &entries).unwrap();
+    
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+    assert_eq!(archive.len(), entries.len());
+    for (name, data) in &entries {
+        let mut content = Vec::new();
+        archive.by_name(name).unwrap().read_to_end(&mut content).unwrap();
+        assert_eq!(&content, data, "{} 内容不一致", name);
+    }
+}
+
+#[test]
+fn test_frame_decimator_every_nth_frame() {
+    println!("=== 测试检测抽帧 ===");
+    
+    use std::time::{Duration, Instant};
+    let start = Instant::now();
+    let mut decimator = FrameDecimator::new(3);
+    let processed: Vec<u64> = (1..=20)
+        .filter(|&seq| decimator.should_process(seq, start + Duration::from_millis(seq * 100)))
+        .collect();
+    assert_eq!(processed, vec![1, 4, 7, 10, 13, 16, 19]);
+    
+    // 同一帧不重复处理
+    assert!(!decimator.should_process(20, start + Duration::from_millis(2100)));
+    
+    let stats = decimator.stats();
+    assert_eq!((stats.every_n, stats.processed, stats.skipped, stats.overrun), (3, 7, 12, 0));
+    // 10fps采集、每3帧处理1帧 → 约3.3fps
+    assert!((stats.processed_fps - 10.0 / 3.0).abs() < 0.1, "实际处理帧率 {:.2}", stats.processed_fps);
+    
+    // 处理过慢时越过的帧记为 overrun
+    assert!(decimator.should_process(30, start + Duration::from_millis(3000)));
+    let stats = decimator.stats();
+    assert_eq!((stats.skipped, stats.overrun), (14, 8));
+    
+    // 阶段切换后下一个新帧立即处理
+    decimator.restart();
+    assert!(decimator.should_process(31, start + Duration::from_millis(3100)));
+    
+    assert!(decimator.set_every_n(0).is_err());
+    decimator.set_every_n(1).unwrap();
+    assert!((32..=35).all(|seq| decimator.should_process(seq, start)));
+}
+
+#[test]
+fn test_preview_cache_stale_fallback() {
+    println!("=== 测试预览缓存帧沿用 ===");
+    
+    use std::time::{Duration, Instant};
+    use crate::commands::alignment_commands::CameraPreviewData;
+    let start = Instant::now();
+    let mut cache = PreviewCache::new(Duration::from_millis(500));
+    
+    // 从未编码过预览：无帧可沿用
+    assert!(cache.fallback(start).is_err());
+    
+    let preview = CameraPreviewData {
+        left_image_base64: "left".to_string(),
+        right_image_base64: "right".to_string(),
+        timestamp: 12,
+        width: 2448,
+        height: 2048,
+        fps: 10.0,
+        stale: false,
+        age_ms: 0,
+    };
+    cache.store(&preview, start);
+    
+    // 缓冲区短暂无帧：返回上一帧并标记为过期
+    let stale = cache.fallback(start + Duration::from_millis(300)).unwrap();
+    assert!(stale.stale);
+    assert_eq!(stale.age_ms, 300);
+    assert_eq!(stale.left_image_base64, "left");
+    assert_eq!(stale.timestamp, 12);
+    
+    // 超过沿用时间后报错
+    let err = cache.fallback(start + Duration::from_millis(600)).unwrap_err();
+    assert!(err.contains("没有可用的帧数据"), "{}", err);
+    
+    // 放宽沿用时间后恢复；清空后不再沿用
+    cache.set_stale_timeout(Duration::from_secs(1));
+    assert!(cache.fallback(start + Duration::from_millis(600)).is_ok());
+    cache.clear();
+    assert!(cache.fallback(start).is_err());
+}
+
+#[test]
+fn test_detection_result_metric_units() {
+    println!("=== 测试检测结果单位描述 ===");
+    
+    use crate::modules::alignment::{MagnificationCheck, PoseSpread, StageTimings, StandoffCheck};
+    use crate::modules::alignment_workflow::FastCheckReport;
+    use crate::modules::result_metrics::{source_metrics, MetricSource, MetricUnit};
+    
+    let results = [
+        DetectionResult::LeftEyePose {
+            roll: 0.01, pitch: -0.02, yaw: 0.03, pass: true, message: String::new(),
+            spread: PoseSpread::default(), standoff: StandoffCheck::default(),
+            reprojection_rms: 0.2, timings: StageTimings::default(),
+        },
+        DetectionResult::DualEyeAlignment {
+            mean_dx: 0.5, mean_dy: -0.3, rms: 0.6, p95: 0.8, max_err: 1.0, pass: false,
+            adjustment_hint: String::new(), timings: StageTimings::default(), percentile: 95.0,
+            percentile_label: "P95".to_string(), magnification: MagnificationCheck::default(),
+            robust_rms: Some(0.4), robust_p95: Some(0.5), outlier_indices: vec![12],
+        },
+        DetectionResult::NoProjection {
+            left_blank: true, right_blank: false, left_mean: 1.0, left_max: 3.0,
+            right_mean: 80.0, right_max: 255.0, message: String::new(),
+        },
+    ];
+    
+    for result in &results {
+        let json = serde_json::to_value(LabeledDetectionResult::new(result)).unwrap();
+        assert_eq!(json["stage"], result.stage_name(), "结果字段保持不变");
+        
+        // 每个描述的字段路径都对应结果中的数值，且单位随结果发送
+        for metric in result.metric_descriptors() {
+            assert!(super::fixtures::is_metric_value(&json, &metric), "{} 在 {} 中不是数值", metric.key, result.stage_name());
+            assert_eq!(json["units"][&*metric.key], serde_json::to_value(metric.unit).unwrap());
+        }
+    }
+    
+    let pose = serde_json::to_value(LabeledDetectionResult::new(&results[0])).unwrap();
+    assert_eq!(pose["units"]["roll"], "degree");
+    let alignment = serde_json::to_value(LabeledDetectionResult::new(&results[1])).unwrap();
+    assert_eq!(alignment["units"]["mean_dx"], "pixel");
+    assert_eq!(alignment["units"]["percentile"], "percent");
+    assert_eq!(alignment["units"]["robust_rms"], "pixel");
+    assert_eq!(alignment["units"]["robust_p95"], "pixel");
+    assert_eq!(alignment["units"]["outlier_indices"], "count");
+    let no_projection = serde_json::to_value(LabeledDetectionResult::new(&results[2])).unwrap();
+    assert_eq!(no_projection["units"]["left_mean"], "gray_level");
+    assert_eq!(MetricUnit::GrayLevel.symbol(), "");
+    assert_eq!(MetricUnit::Degree.symbol(), "°");
+    assert_eq!(MetricUnit::Percent.symbol(), "%");
+    
+    // 快速完整检测报告：嵌套结果按字段路径附带单位，名称带左右眼限定
+    let report = FastCheckReport::new(results[0].clone(), results[0].clone(), Some(results[1].clone()), 20.0, Some(50.0));
+    assert_eq!(report.speedup, Some(2.5));
+    let json = serde_json::to_value(&report).unwrap();
+    let metrics = source_metrics(MetricSource::FastCheckReport);
+    for metric in &metrics {
+        assert!(super::fixtures::is_metric_value(&json, metric), "{} 在 FastCheckReport 中不是数值", metric.key);
+        assert_eq!(json["units"][&*metric.key], serde_json::to_value(metric.unit).unwrap());
+    }
+    assert_eq!(json["units"]["left_pose.roll"], "degree");
+    assert_eq!(json["units"]["alignment.percentile"], "percent");
+    assert_eq!(json["units"]["pipeline_ms"], "millisecond");
+    let right_roll = metrics.iter().find(|m| m.key == "right_pose.roll").unwrap();
+    assert_eq!((&*right_roll.label, &*right_roll.label_en), ("右眼横滚角", "Right Roll"));
+    
+    // 描述表以结果类型枚举为键，序列化为与 stage 标签一致的名称
+    let descriptors: std::collections::BTreeMap<_, _> = MetricSource::ALL.iter().map(|&s| (s, source_metrics(s))).collect();
+    let json = serde_json::to_value(&descriptors).unwrap();
+    assert_eq!(json["DualEyeAlignment"].as_array().unwrap().len(), source_metrics(MetricSource::DualEyeAlignment).len());
+    assert_eq!(json["Error"].as_array().map(|a| a.len()), Some(0));
+    assert!(json["AlignmentReport"].as_array().unwrap().iter().any(|m| m["key"] == "alignment.percentile"));
+    assert_eq!(results[1].metric_source(), MetricSource::DualEyeAlignment);
+    
+    // 错误结果无数值指标，不附带 units
+    let error = DetectionResult::Error { message: "x".to_string() };
+    let json = serde_json::to_value(LabeledDetectionResult::new(&error)).unwrap();
+    assert!(json.get("units").is_none());
+    assert_eq!(json["message"], "x");
+}
+
+#[test]
+fn test_result_log_jsonl_roundtrip() {
+    println!("=== 测试合像结果日志写入与回读 ===");
+    use crate::modules::alignment::{DualEyeAlignmentResult, MagnificationCheck};
+    use crate::modules::result_log::{AlignmentLogRecord, ResultLogFormat, ResultLogger, read_jsonl_log};
+    
+    let dir = TestDir::new("result_log");
+    let result = |rms: f64, pass: bool| DualEyeAlignmentResult {
+        mean_dx: rms / 2.0, mean_dy: -rms / 4.0, rms, p95: rms * 1.5, max_err: rms * 2.0, pass,
+        percentile: 95.0, magnification: MagnificationCheck::default(),
+        robust_rms: None, robust_p95: None, outlier_indices: Vec::new(),
+    };
+    let robust = DualEyeAlignmentResult { robust_rms: Some(0.3), robust_p95: Some(0.4), outlier_indices: vec![5, 21], ..result(0.35, false) };
+    let records = [
+        AlignmentLogRecord::new("AR-0001", &result(0.12, true)),
+        AlignmentLogRecord::new("AR-0002", &robust),
+        AlignmentLogRecord::new("AR-0003", &result(0.08, true)),
+    ];
+    
+    // 扩展名决定格式，不支持的扩展名被拒绝
+    assert!(ResultLogger::new(dir.join("results.txt"), false).is_err());
+    let logger = ResultLogger::new(dir.join("logs").join("results.jsonl"), false).unwrap();
+    assert_eq!(logger.format(), ResultLogFormat::Jsonl);
+    assert_eq!(logger.current_path(), dir.join("logs").join("results.jsonl"));
+    for record in &records {
+        logger.append(record).unwrap();
+    }
+    
+    let parsed = read_jsonl_log(&logger.current_path()).unwrap();
+    assert_eq!(parsed, records.to_vec());
+    assert_eq!(parsed.iter().filter(|r| r.pass).count(), 2);
+    assert_eq!(parsed[1].serial_number, "AR-0002");
+    assert_eq!(parsed[1].p95, 0.35 * 1.5);
+    assert_eq!((parsed[1].robust_rms, parsed[1].outlier_indices.clone()), (Some(0.3), vec![5, 21]));
+    
+    // 重新打开同一文件继续追加，不覆盖已有记录
+    let reopened = ResultLogger::new(dir.join("logs").join("results.jsonl"), false).unwrap();
+    reopened.append(&records[0]).unwrap();
+    assert_eq!(read_jsonl_log(&reopened.current_path()).unwrap().len(), 4);
+    
+    // CSV：新文件写表头，含逗号的序列号加引号
+    let csv = ResultLogger::new(dir.join("results.csv"), true).unwrap();
+    assert!(csv.current_path().file_name().unwrap().to_string_lossy().starts_with("results_"));
+    csv.append(&AlignmentLogRecord::new("LOT-7,A", &result(0.2, true))).unwrap();
+    csv.append(&records[2]).unwrap();
+    csv.append(&records[1]).unwrap();
+    let content = std::fs::read_to_string(csv.current_path()).unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("timestamp,serial_number,"));
+    assert!(lines[1].contains(",\"LOT-7,A\",0.1000,"));
+    assert!(lines[2].ends_with(",,,0,true"), "未启用稳健统计时稳健列为空: {}", lines[2]);
+    assert!(lines[3].ends_with(",0.3000,0.4000,2,false"), "{}", lines[3]);
+}
+
+#[test]
+fn test_custom_param_dir_paths() {
+    println!("=== 测试自定义标定参数目录的路径构造 ===");
+    use crate::modules::alignment::{AlignmentSystem, SyntheticGridParams};
+    use crate::modules::alignment_workflow::write_synthetic_camera_params;
+    
+    let dir = TestDir::new("param_dir");
+    let maps_path = param_file_path(&dir, RECTIFY_MAPS_FILE);
+    assert_eq!(std::path::Path::new(&maps_path), dir.join("rectify_maps.yaml"));
+    assert!(!maps_path.contains(crate::paths::PARAMS_DIR_NAME));
+    
+    // 参数与重映射矩阵均从自定义目录解析：矩阵文件不存在时按该目录的参数重新计算
+    write_synthetic_camera_params(&dir, opencv::core::Size::new(2448, 2048)).unwrap();
+    let mut system = AlignmentSystem::new(
+        opencv::core::Size::new(2448, 2048),
+        &param_file_path(&dir, "left_camera_params.yaml"),
+        &param_file_path(&dir, "right_camera_params.yaml"),
+        &param_file_path(&dir, "stereo_params.yaml"),
+        &param_file_path(&dir, "rectify_params.yaml"),
+    ).unwrap();
+    let (left, right) = system.render_synthetic_pair(&SyntheticGridParams::default()).unwrap();
+    let (corners, _) = system.detect_circles_grid(&left, &right, &maps_path).unwrap();
+    assert_eq!(corners.len(), 40);
+    assert!(system.maps_regenerated());
+    
+    // 红/青立体图与检测使用同一参数目录的重映射矩阵
+    let anaglyph = system.render_anaglyph(&left, &right, &maps_path).unwrap();
+    assert_eq!((anaglyph.cols(), anaglyph.rows()), (2448, 2048));
+    
+    // 命令层按工作流参数目录解析文件，未创建工作流时回退到默认数据目录
+    use crate::commands::alignment_commands::AlignmentWorkflowState;
+    assert_eq!(AlignmentWorkflowState::new().param_dir(), crate::paths::params_dir());
+}
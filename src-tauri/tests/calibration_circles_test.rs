@@ -3,6 +3,7 @@ mod tests {
     use super::*;
     use crate::modules::calibration_circles::*;
     use crate::modules::param_io::*;
+    use super::super::fixtures::TestDir;
     use opencv::core::Size;
     use opencv::prelude::*;
 
@@ -136,7 +137,7 @@ mod tests {
             .expect("Failed to perform stereo calibration");
 
         let (rotation_matrix, translation_vector, stereo_error) = match stereo_result {
-            StereoCalibResult::Success { r, t, error } => {
+            StereoCalibResult::Success { r, t, error, .. } => {
                 println!("✓ 双目标定成功");
                 println!("  - 重投影误差: {:.6}", error);
                 (r, t, error)
@@ -216,6 +217,7 @@ mod tests {
             p1: mat_to_vec2d_f64(&rectify_maps.p1),
             p2: mat_to_vec2d_f64(&rectify_maps.p2),
             q: mat_to_vec2d_f64(&rectify_maps.q),
+            valid_roi: None,
         };
         save_rectify_params("rectify_params.yaml", &rectify_params)
             .expect("Failed to save rectification parameters");
@@ -306,4 +308,648 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_load_image_for_detection_normalizes_formats() {
+        println!("=== 测试检测图像统一加载 (BMP/PNG/JPG) ===");
+        use opencv::core::{Mat, Scalar, Vector, CV_8UC1, CV_8UC4, CV_16UC1, CV_8UC3};
+
+        let dir = TestDir::new("load_image");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let gray = Mat::new_rows_cols_with_default(64, 80, CV_8UC1, Scalar::all(200.0)).unwrap();
+        let bgra = Mat::new_rows_cols_with_default(64, 80, CV_8UC4, Scalar::all(200.0)).unwrap();
+        let gray16 = Mat::new_rows_cols_with_default(64, 80, CV_16UC1, Scalar::all(51200.0)).unwrap();
+
+        let cases = [
+            ("gray.bmp", &gray),
+            ("gray.png", &gray),
+            ("gray.jpg", &gray),
+            ("bgra.png", &bgra),
+            ("gray16.png", &gray16),
+        ];
+        for (name, image) in cases {
+            let path = dir.join(name).to_string_lossy().to_string();
+            opencv::imgcodecs::imwrite(&path, image, &Vector::new()).unwrap();
+
+            let loaded = load_image_for_detection(&path).expect("读取失败");
+            assert_eq!(loaded.typ(), CV_8UC3, "{} 未转换为8位BGR", name);
+            assert_eq!((loaded.cols(), loaded.rows()), (80, 64));
+            let pixel = loaded.at_2d::<opencv::core::Vec3b>(10, 10).unwrap();
+            assert!((pixel[0] as i32 - 200).abs() <= 2, "{} 像素值异常: {:?}", name, pixel);
+        }
+
+        // 不存在的文件返回错误而非空Mat
+        assert!(load_image_for_detection(&dir.join("missing.bmp").to_string_lossy()).is_err());
+    }
+
+    #[test]
+    fn test_rational_model_lowers_rms_on_wide_fov() {
+        println!("=== 测试广角数据集下有理畸变模型的标定效果 ===");
+        use opencv::calib3d;
+        use opencv::core::{Mat, Point2f, Point3f, Vector};
+
+        let image_size = Size::new(2448, 2048);
+        let pattern_size = Size::new(PATTERN_COLS, PATTERN_ROWS);
+        let mut calibrator = Calibrator::new(
+            image_size,
+            CIRCLE_DIAMETER,
+            CENTER_DISTANCE,
+            pattern_size,
+            ERROR_THRESHOLD,
+        ).expect("Failed to create calibrator");
+
+        // 广角相机：短焦距 + 强有理畸变 (k1,k2,p1,p2,k3,k4,k5,k6)
+        let camera_matrix = Mat::from_slice_2d(&[
+            [1000.0f64, 0.0, 1224.0],
+            [0.0, 1000.0, 1024.0],
+            [0.0, 0.0, 1.0],
+        ]).unwrap();
+        let dist_coeffs = Mat::from_slice(&[2.0f64, 0.6, 0.0, 0.0, 0.02, 2.4, 1.2, 0.1])
+            .unwrap().try_clone().unwrap();
+
+        // 合成视图：标定板贴近相机并遍布画面，使归一化半径接近1
+        let world_points = calibrator.generate_world_points_from_list().unwrap();
+        let mut obj_points = Vector::<Vector<Point3f>>::new();
+        let mut img_points = Vector::<Vector<Point2f>>::new();
+        for &(tx, ty) in &[(-80.0, -62.0), (-200.0, -170.0), (40.0, -170.0), (-200.0, 50.0), (40.0, 50.0), (-80.0, -170.0), (-80.0, 50.0), (-200.0, -62.0), (40.0, -62.0)] {
+            for &(rx, ry) in &[(0.0, 0.0), (0.25, -0.2)] {
+                let rvec = Mat::from_slice(&[rx as f64, ry, 0.05]).unwrap().try_clone().unwrap();
+                let tvec = Mat::from_slice(&[tx as f64, ty, 180.0]).unwrap().try_clone().unwrap();
+                let mut projected = Vector::<Point2f>::new();
+                calib3d::project_points(
+                    &world_points, &rvec, &tvec, &camera_matrix, &dist_coeffs,
+                    &mut projected, &mut Mat::default(), 0.0,
+                ).unwrap();
+                obj_points.push(world_points.clone());
+                img_points.push(projected);
+            }
+        }
+
+        // 默认A/B组合
+        let default_result = calibrator.calibrate_mono_with_flag_search(&obj_points, &img_points).unwrap();
+        let default_rms = default_result.rms_by_combo.iter().map(|(_, e)| *e).fold(f64::INFINITY, f64::min);
+
+        // 扩展组合（含有理模型）
+        calibrator.set_mono_flag_combos(CalibFlagCombo::extended_list());
+        let extended_result = calibrator.calibrate_mono_with_flag_search(&obj_points, &img_points).unwrap();
+        let extended_rms = extended_result.rms_by_combo.iter().map(|(_, e)| *e).fold(f64::INFINITY, f64::min);
+
+        println!("  默认A/B最优RMS: {:.4} ({})", default_rms, default_result.winner);
+        println!("  扩展组合最优RMS: {:.4} ({})", extended_rms, extended_result.winner);
+
+        assert_eq!(extended_result.winner, "D-有理模型", "广角数据集应由有理模型胜出");
+        assert!(extended_rms < default_rms * 0.5, "有理模型应显著降低RMS");
+    }
+
+    #[test]
+    fn test_camera_serial_check_against_calibration() {
+        let info = CalibrationCameraInfo {
+            left_camera_serial: "DA0001".to_string(),
+            right_camera_serial: "DA0002".to_string(),
+            calibrated_at: "2025-01-01T00:00:00Z".to_string(),
+        };
+
+        let ok = check_camera_serials(Some(&info), "DA0001", "DA0002");
+        assert!(ok.matched && !ok.is_mismatch() && ok.warning.is_none());
+
+        let replaced = check_camera_serials(Some(&info), "DA0001", "DA0099");
+        assert!(replaced.is_mismatch());
+        assert!(replaced.warning.as_ref().unwrap().contains("DA0099"));
+
+        let swapped = check_camera_serials(Some(&info), "DA0002", "DA0001");
+        assert!(swapped.is_mismatch());
+        assert!(swapped.warning.as_ref().unwrap().contains("互换"));
+
+        // 旧标定未记录序列号：给出提示，但不视为不匹配
+        let legacy = check_camera_serials(None, "DA0001", "DA0002");
+        assert!(!legacy.matched && !legacy.is_mismatch() && legacy.warning.is_some());
+
+        // 保存/读取往返
+        let dir = TestDir::new("calib_serial_check");
+        std::fs::create_dir_all(&dir).unwrap();
+        save_calibration_camera_info(dir.join(CALIBRATION_CAMERA_INFO_FILE), &info).unwrap();
+        let from_dir = check_calibration_dir_serials(&dir, "DA0001", "DA0002");
+        assert!(from_dir.matched);
+    }
+
+    #[test]
+    fn test_corner_sidecar_roundtrip() {
+        let dir = TestDir::new("calib_corner_sidecar");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let points = |offset: f32| -> opencv::core::Vector<opencv::core::Point2f> {
+            (0..40).map(|i| opencv::core::Point2f::new(100.0 + i as f32 * 10.25 + offset, 200.0 + (i / 4) as f32 * 7.5)).collect()
+        };
+        // 乱序写入，读取时应按 pair_id 排序
+        for pair_id in [3u32, 1, 2] {
+            let sidecar = CornerSidecar {
+                pair_id,
+                left_image_path: format!("captures/calib_left_{:02}.png", pair_id),
+                right_image_path: format!("captures/calib_right_{:02}.png", pair_id),
+                image_width: 2448,
+                image_height: 2048,
+                pattern_cols: 4,
+                pattern_rows: 10,
+                left_points: points_to_pairs(&points(pair_id as f32)),
+                right_points: points_to_pairs(&points(-(pair_id as f32))),
+                detected_at: "2025-01-01T00:00:00Z".to_string(),
+            };
+            save_corner_sidecar(dir.join(corner_sidecar_file_name(pair_id)), &sidecar).unwrap();
+        }
+        std::fs::write(dir.join("calib_left_01.png"), b"not a sidecar").unwrap();
+
+        let loaded = load_corner_sidecars_from_dir(&dir).unwrap();
+        assert_eq!(loaded.iter().map(|s| s.pair_id).collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        // 点坐标无损往返
+        let restored = pairs_to_points(&loaded[1].left_points);
+        let original = points(2.0);
+        assert_eq!(restored.len(), 40);
+        for (a, b) in restored.iter().zip(original.iter()) {
+            assert_eq!((a.x, a.y), (b.x, b.y));
+        }
+    }
+
+    #[test]
+    fn test_point_correspondence_export_roundtrip() {
+        let path = std::env::temp_dir().join(format!("calib_correspondences_{}.json", std::process::id()));
+        let export = PointCorrespondenceExport {
+            image_width: 2448,
+            image_height: 2048,
+            pattern_cols: 4,
+            pattern_rows: 10,
+            circle_diameter_mm: CIRCLE_DIAMETER,
+            center_distance_mm: CENTER_DISTANCE,
+            world_points: (0..40).map(|i| [i as f32 * 12.5, (i / 4) as f32 * 25.0, 0.0]).collect(),
+            pair_ids: vec![1, 2],
+            left_image_paths: vec!["calib_left_01.png".into(), "calib_left_02.png".into()],
+            right_image_paths: vec!["calib_right_01.png".into(), "calib_right_02.png".into()],
+            left_points: vec![vec![[100.5, 200.25]; 40]; 2],
+            right_points: vec![vec![[90.5, 200.25]; 40]; 2],
+            exported_at: "2025-01-01 00:00:00".to_string(),
+        };
+        save_point_correspondences(&path, &export).unwrap();
+
+        // JSON 数组形状与 numpy 约定一致：(N,3) 与 (M,N,2)
+        let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["world_points"].as_array().unwrap().len(), 40);
+        assert_eq!(json["world_points"][0].as_array().unwrap().len(), 3);
+        assert_eq!(json["left_points"].as_array().unwrap().len(), 2);
+        assert_eq!(json["left_points"][1][39].as_array().unwrap().len(), 2);
+
+        let loaded = load_point_correspondences(&path).unwrap();
+        assert_eq!(loaded.pair_ids, vec![1, 2]);
+        assert_eq!(loaded.right_points[1][0], [90.5, 200.25]);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_column_swap_hysteresis() {
+        println!("=== 测试列交换判定滞回 ===");
+        let mut calibrator = Calibrator::new(
+            Size::new(2448, 2048),
+            CIRCLE_DIAMETER,
+            CENTER_DISTANCE,
+            Size::new(PATTERN_COLS, PATTERN_ROWS),
+            ERROR_THRESHOLD,
+        ).expect("Failed to create calibrator");
+
+        // 明确的正确顺序：点0在右侧
+        assert!(!calibrator.column_swap_decision(1674.0, 1574.0));
+
+        // 近似对称的取景：x差在 ±2px 间抖动，判定保持不变
+        for (i, jitter) in [2.0_f32, -2.0, 1.5, -1.0, 0.0, 2.0, -2.0].iter().enumerate() {
+            assert!(!calibrator.column_swap_decision(1600.0, 1600.0 + jitter), "第{}帧判定跳变", i);
+        }
+
+        // 超出余量才切换，之后在余量内同样保持
+        assert!(calibrator.column_swap_decision(1574.0, 1674.0));
+        for jitter in [2.0_f32, -2.0, 1.0, -1.5] {
+            assert!(calibrator.column_swap_decision(1600.0, 1600.0 + jitter));
+        }
+
+        // 余量为0时退化为直接比较
+        calibrator.set_column_swap_margin(0.0).unwrap();
+        assert!(!calibrator.column_swap_decision(1600.0, 1599.0));
+        assert!(calibrator.column_swap_decision(1600.0, 1601.0));
+
+        // 无历史时在余量内按直接比较
+        calibrator.set_column_swap_margin(DEFAULT_COLUMN_SWAP_MARGIN_PX).unwrap();
+        calibrator.reset_column_swap_state();
+        assert!(!calibrator.column_swap_decision(1600.0, 1598.0));
+        assert!(calibrator.set_column_swap_margin(-1.0).is_err());
+    }
+
+    #[test]
+    fn test_suggest_board_roi_from_points() {
+        use opencv::core::{Point2f, Vector};
+        use crate::modules::calibration_workflow::{suggest_board_roi_from_points, ROI_ALIGNMENT_PX};
+        
+        let image_size = Size::new(2448, 2048);
+        let left: Vector<Point2f> = [(501.3, 402.7), (1499.6, 402.7), (1000.0, 1198.2)]
+            .iter().map(|&(x, y)| Point2f::new(x, y)).collect();
+        let right: Vector<Point2f> = [(451.0, 420.0), (1450.0, 1250.5)]
+            .iter().map(|&(x, y)| Point2f::new(x, y)).collect();
+        
+        let roi = suggest_board_roi_from_points(&[&left, &right], image_size, 0.1).unwrap();
+        println!("建议ROI: {:?}", roi);
+        assert!(roi.enabled && roi.applies_to_both_cameras);
+        for v in [roi.offset_x, roi.offset_y, roi.width, roi.height] {
+            assert_eq!(v % ROI_ALIGNMENT_PX, 0, "ROI未对齐: {}", v);
+        }
+        // 覆盖两侧所有点且留有外扩
+        for p in left.iter().chain(right.iter()) {
+            assert!(p.x as i32 > roi.offset_x && (p.x as i32) < roi.offset_x + roi.width);
+            assert!(p.y as i32 > roi.offset_y && (p.y as i32) < roi.offset_y + roi.height);
+        }
+        assert!(roi.offset_x <= 451 - 104 && roi.offset_x + roi.width >= 1500 + 104);
+        
+        // 靠近边缘时限制在图像内
+        let edge: Vector<Point2f> = [(5.0, 5.0), (2440.0, 2040.0)]
+            .iter().map(|&(x, y)| Point2f::new(x, y)).collect();
+        let roi = suggest_board_roi_from_points(&[&edge], image_size, 0.2).unwrap();
+        assert_eq!((roi.offset_x, roi.offset_y, roi.width, roi.height), (0, 0, 2448, 2048));
+        assert!(!roi.applies_to_both_cameras);
+        
+        assert!(suggest_board_roi_from_points(&[&Vector::<Point2f>::new()], image_size, 0.1).is_none());
+    }
+
+    #[test]
+    fn test_capture_limit_enforced() {
+        use crate::modules::calibration_workflow::{enforce_capture_limit, ImagePair};
+        
+        let pair = |pair_id: u32, has_pattern: bool| ImagePair {
+            pair_id,
+            left_image_path: format!("calib_left_{:02}.png", pair_id),
+            right_image_path: format!("calib_right_{:02}.png", pair_id),
+            thumbnail_left: String::new(),
+            thumbnail_right: String::new(),
+            capture_timestamp: String::new(),
+            has_calibration_pattern: has_pattern,
+        };
+        let mut images = vec![pair(1, true), pair(2, false), pair(3, true), pair(4, false)];
+        
+        // 未达上限不处理
+        assert!(enforce_capture_limit(&mut images, 5).unwrap().is_none());
+        assert_eq!(images.len(), 4);
+        
+        // 达到上限：依次淘汰最早的无标定板图像对
+        assert_eq!(enforce_capture_limit(&mut images, 4).unwrap().unwrap().pair_id, 2);
+        images.push(pair(5, true));
+        assert_eq!(enforce_capture_limit(&mut images, 4).unwrap().unwrap().pair_id, 4);
+        images.push(pair(6, true));
+        let ids: Vec<u32> = images.iter().map(|img| img.pair_id).collect();
+        assert_eq!(ids, vec![1, 3, 5, 6]);
+        
+        // 全部有效时拒绝采集，列表不变
+        let err = enforce_capture_limit(&mut images, 4).unwrap_err();
+        assert!(err.contains("上限"), "{}", err);
+        assert_eq!(images.len(), 4);
+    }
+
+    #[test]
+    fn test_per_view_errors_for_calibration_report() {
+        println!("=== 测试标定报告逐视图重投影误差 ===");
+        use opencv::calib3d;
+        use opencv::core::{Mat, Point2f, Point3f, Vector};
+
+        let calibrator = Calibrator::new(
+            Size::new(2448, 2048),
+            CIRCLE_DIAMETER,
+            CENTER_DISTANCE,
+            Size::new(PATTERN_COLS, PATTERN_ROWS),
+            ERROR_THRESHOLD,
+        ).expect("Failed to create calibrator");
+        assert_eq!(calibrator.get_image_size(), Size::new(2448, 2048));
+
+        let camera = MonoCamera {
+            camera_matrix: Mat::from_slice_2d(&[
+                [3000.0f64, 0.0, 1224.0],
+                [0.0, 3000.0, 1024.0],
+                [0.0, 0.0, 1.0],
+            ]).unwrap(),
+            dist_coeffs: Mat::from_slice(&[0.0f64; 5]).unwrap().try_clone().unwrap(),
+        };
+
+        let world_points = calibrator.generate_world_points_from_list().unwrap();
+        let mut obj_points = Vector::<Vector<Point3f>>::new();
+        let mut img_points = Vector::<Vector<Point2f>>::new();
+        for (i, &(rx, ry)) in [(0.0, 0.0), (0.2, -0.1), (-0.15, 0.2)].iter().enumerate() {
+            let rvec = Mat::from_slice(&[rx as f64, ry, 0.02]).unwrap().try_clone().unwrap();
+            let tvec = Mat::from_slice(&[-60.0f64, -110.0, 600.0]).unwrap().try_clone().unwrap();
+            let mut projected = Vector::<Point2f>::new();
+            calib3d::project_points(
+                &world_points, &rvec, &tvec, &camera.camera_matrix, &camera.dist_coeffs,
+                &mut projected, &mut Mat::default(), 0.0,
+            ).unwrap();
+            // 第3个视图加入交替的±1.5px扰动，模拟检测质量差的图像
+            if i == 2 {
+                projected = projected.iter().enumerate()
+                    .map(|(k, p)| if k % 2 == 0 { Point2f::new(p.x + 1.5, p.y) } else { Point2f::new(p.x - 1.5, p.y) })
+                    .collect();
+            }
+            obj_points.push(world_points.clone());
+            img_points.push(projected);
+        }
+
+        let errors = calibrator.compute_per_view_errors(&obj_points, &img_points, &camera).unwrap();
+        println!("  逐视图误差: {:?}", errors);
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0] < 1e-3 && errors[1] < 1e-3, "无噪声视图误差应接近0");
+        assert!(errors[2] > 0.5, "扰动视图误差应明显偏大");
+    }
+
+    #[test]
+    fn test_stereo_per_view_errors_flag_worst_pairs() {
+        println!("=== 测试双目逐图像对误差与建议重拍列表 ===");
+        use crate::modules::calibration_workflow::select_worst_pairs;
+        use opencv::calib3d;
+        use opencv::core::{Mat, Point2f, Point3f, Vector};
+
+        let calibrator = Calibrator::new(
+            Size::new(2448, 2048),
+            CIRCLE_DIAMETER,
+            CENTER_DISTANCE,
+            Size::new(PATTERN_COLS, PATTERN_ROWS),
+            ERROR_THRESHOLD,
+        ).expect("Failed to create calibrator");
+        let camera = || MonoCamera {
+            camera_matrix: Mat::from_slice_2d(&[
+                [3000.0f64, 0.0, 1224.0],
+                [0.0, 3000.0, 1024.0],
+                [0.0, 0.0, 1.0],
+            ]).unwrap(),
+            dist_coeffs: Mat::from_slice(&[0.0f64; 5]).unwrap().try_clone().unwrap(),
+        };
+        let (left_camera, right_camera) = (camera(), camera());
+
+        // 理想双目外参 R = I，T = [-60, 0, 0] mm 下投影8组图像对
+        let world_points = calibrator.generate_world_points_from_list().unwrap();
+        let mut obj_points = Vector::<Vector<Point3f>>::new();
+        let mut left_points = Vector::<Vector<Point2f>>::new();
+        let mut right_points = Vector::<Vector<Point2f>>::new();
+        let poses = [(0.0, 0.0), (0.2, -0.1), (-0.15, 0.2), (0.1, 0.15), (-0.2, -0.1), (0.15, -0.2), (-0.1, 0.1), (0.05, 0.25)];
+        for (i, &(rx, ry)) in poses.iter().enumerate() {
+            let rvec = Mat::from_slice(&[rx as f64, ry, 0.02]).unwrap().try_clone().unwrap();
+            let project = |tx: f64| {
+                let tvec = Mat::from_slice(&[tx, -110.0, 600.0]).unwrap().try_clone().unwrap();
+                let mut projected = Vector::<Point2f>::new();
+                calib3d::project_points(
+                    &world_points, &rvec, &tvec, &left_camera.camera_matrix, &left_camera.dist_coeffs,
+                    &mut projected, &mut Mat::default(), 0.0,
+                ).unwrap();
+                projected
+            };
+            let mut right = project(-60.0 - 60.0);
+            // 第3组右图整体偏移2px，模拟与外参不一致的高误差图像对
+            if i == 2 {
+                right = right.iter().map(|p| Point2f::new(p.x + 2.0, p.y)).collect();
+            }
+            obj_points.push(world_points.clone());
+            left_points.push(project(-60.0));
+            right_points.push(right);
+        }
+
+        // 逐图像对误差直接取自双目标定，与输入图像对顺序一致
+        let (errors, overall) = match calibrator.calibrate_stereo(
+            &obj_points, &left_points, &right_points, &left_camera, &right_camera,
+        ).unwrap() {
+            StereoCalibResult::Success { error, per_view_errors, .. } => (per_view_errors, error),
+            StereoCalibResult::NeedRecalibration(error) => panic!("双目标定误差过大: {}", error),
+        };
+        println!("  逐图像对误差: {:?}, 总体 {:.4}", errors, overall);
+        assert_eq!(errors.len(), poses.len());
+        let worst = errors.iter().enumerate()
+            .max_by(|(_, a), (_, b)| (a.0 + a.1).total_cmp(&(b.0 + b.1)))
+            .map(|(i, _)| i);
+        assert_eq!(worst, Some(2), "偏移图像对误差应最大");
+
+        let pair_ids = [11, 12, 13, 14, 15, 16, 17, 18];
+        assert_eq!(select_worst_pairs(&errors, &pair_ids, overall), vec![13]);
+        // 图像对ID无法与视图对应时不给出建议
+        assert!(select_worst_pairs(&errors, &pair_ids[..3], overall).is_empty());
+    }
+
+    #[test]
+    fn test_min_valid_pairs_configurable() {
+        println!("=== 测试可配置的最少有效图像对数量 ===");
+        use crate::modules::calibration_workflow::CalibrationWorkflow;
+        use crate::tests::file_frame_source::FileFrameSource;
+
+        // 工作流程配置：3 ≤ 最少有效数量 ≤ 目标数量 ≤ 保留上限
+        let source = FileFrameSource::from_raw_pairs(vec![(vec![0u8; 4], vec![0u8; 4])]);
+        let mut workflow = CalibrationWorkflow::with_camera(source.camera_manager());
+        assert_eq!(workflow.calibration_config().min_valid_pairs, DEFAULT_MIN_VALID_IMAGES);
+        assert!(workflow.set_image_count_requirements(6, 2).is_err());
+        assert!(workflow.set_image_count_requirements(5, 6).is_err());
+        assert!(workflow.set_image_count_requirements(10_000, 6).is_err());
+        workflow.set_image_count_requirements(6, 6).unwrap();
+        assert_eq!(workflow.calibration_config().target_image_count, 6);
+        assert_eq!(workflow.calibration_config().min_valid_pairs, 6);
+
+        let mut calibrator = Calibrator::new(
+            Size::new(2448, 2048),
+            CIRCLE_DIAMETER,
+            CENTER_DISTANCE,
+            Size::new(PATTERN_COLS, PATTERN_ROWS),
+            ERROR_THRESHOLD,
+        ).expect("Failed to create calibrator");
+        assert!(calibrator.set_min_valid_images(2).is_err());
+        calibrator.set_min_valid_images(workflow.calibration_config().min_valid_pairs).unwrap();
+        assert_eq!(calibrator.get_min_valid_images(), 6);
+    }
+
+    #[test]
+    fn test_load_params_with_optional_fields_missing() {
+        let dir = TestDir::new("param_io_partial_yaml");
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, yaml: &str| {
+            let path = dir.join(name);
+            std::fs::write(&path, yaml).unwrap();
+            path
+        };
+
+        // 无畸变参数（理想针孔）→ 5个0
+        let camera = load_camera_params(write("camera.yaml",
+            "camera_matrix:\n- [3000.0, 0.0, 1224.0]\n- [0.0, 3000.0, 1024.0]\n- [0.0, 0.0, 1.0]\n")).unwrap();
+        assert_eq!(camera.dist_coeffs, vec![0.0; 5]);
+        assert_eq!(camera.camera_matrix[0][2], 1224.0);
+
+        // 双目参数只有 R/T，附带的 E/F 被忽略
+        let stereo = load_stereo_params(write("stereo.yaml",
+            "r:\n- [1.0, 0.0, 0.0]\n- [0.0, 1.0, 0.0]\n- [0.0, 0.0, 1.0]\nt: [-60.0, 0.0, 0.0]\n")).unwrap();
+        assert_eq!(stereo.t, vec![-60.0, 0.0, 0.0]);
+        assert!(load_stereo_params(write("stereo_ef.yaml",
+            "r:\n- [1.0, 0.0, 0.0]\n- [0.0, 1.0, 0.0]\n- [0.0, 0.0, 1.0]\nt: [-60.0, 0.0, 0.0]\nf:\n- [0.0, 0.0, 0.0]\n")).is_ok());
+
+        // 校正参数缺少 Q → 4x4 零矩阵
+        let rectify = load_rectify_params(write("rectify.yaml",
+            "r1: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]\n\
+r2: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]\n\
+p1: [[2900.0, 0.0, 1200.0, 0.0], [0.0, 2900.0, 1000.0, 0.0], [0.0, 0.0, 1.0, 0.0]]\n\
+p2: [[2900.0, 0.0, 1200.0, -174000.0], [0.0, 2900.0, 1000.0, 0.0], [0.0, 0.0, 1.0, 0.0]]\n")).unwrap();
+        assert_eq!(rectify.q, vec![vec![0.0; 4]; 4]);
+
+        // 必填矩阵缺失或形状错误时报错
+        assert!(load_camera_params(write("no_k.yaml", "dist_coeffs: [0.0, 0.0, 0.0, 0.0, 0.0]\n")).is_err());
+        assert!(load_camera_params(write("bad_k.yaml", "camera_matrix:\n- [1.0, 0.0]\n- [0.0, 1.0]\n")).is_err());
+        assert!(load_stereo_params(write("no_t.yaml", "r:\n- [1.0, 0.0, 0.0]\n- [0.0, 1.0, 0.0]\n- [0.0, 0.0, 1.0]\n")).is_err());
+        assert!(load_rectify_params(write("no_p2.yaml",
+            "r1: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]\n\
+r2: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]\n\
+p1: [[2900.0, 0.0, 1200.0, 0.0], [0.0, 2900.0, 1000.0, 0.0], [0.0, 0.0, 1.0, 0.0]]\n")).is_err());
+    }
+
+    #[test]
+    fn test_grid_detection_budget_blank_image() {
+        println!("=== 测试圆点网格检测预算 (空白图像) ===");
+        
+        let mut calibrator = Calibrator::new(
+            Size::new(2448, 2048), CIRCLE_DIAMETER, CENTER_DISTANCE,
+            Size::new(PATTERN_COLS, PATTERN_ROWS), ERROR_THRESHOLD,
+        ).unwrap();
+        assert_eq!(calibrator.get_detection_budget(), GridDetectionBudget::default());
+        assert!(calibrator.set_detection_budget(GridDetectionBudget { max_attempts: 0, time_budget_ms: 100 }).is_err());
+        assert!(calibrator.set_detection_budget(GridDetectionBudget { max_attempts: GRID_DETECTION_ATTEMPTS + 1, time_budget_ms: 100 }).is_err());
+        calibrator.set_detection_budget(GridDetectionBudget { max_attempts: GRID_DETECTION_ATTEMPTS, time_budget_ms: 500 }).unwrap();
+        
+        let blank = Mat::new_rows_cols_with_default(2048, 2448, opencv::core::CV_8UC3, opencv::core::Scalar::all(128.0)).unwrap();
+        let start = std::time::Instant::now();
+        let err = calibrator.find_asymmetric_circles_grid_points(&blank, false).unwrap_err();
+        let elapsed = start.elapsed();
+        println!("空白图像失败耗时: {:?}, {}", elapsed, err.message);
+        
+        // 无斑点时第一次尝试后即放弃，不再执行其余三次放宽尝试
+        assert!(err.message.contains("检测预算内未找到圆点网格"));
+        assert!(err.message.contains("尝试 1/4 次"));
+        assert!(elapsed < std::time::Duration::from_secs(2), "空白图像应快速失败: {:?}", elapsed);
+        assert!(!calibrator.quick_detect_calibration_pattern(&blank));
+    }
+
+    #[test]
+    fn test_rectify_roi_coverage() {
+        println!("=== 测试校正有效区域覆盖率 ===");
+        
+        let dir = TestDir::new("rectify_coverage");
+        std::fs::create_dir_all(&dir).unwrap();
+        let identity = vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 1.0]];
+        let p = vec![vec![2900.0, 0.0, 1200.0, 0.0], vec![0.0, 2900.0, 1000.0, 0.0], vec![0.0, 0.0, 1.0, 0.0]];
+        let mut params = RectifyParams {
+            r1: identity.clone(),
+            r2: identity,
+            p1: p.clone(),
+            p2: p,
+            q: vec![vec![0.0; 4]; 4],
+            valid_roi: None,
+        };
+        let path = dir.join("rectify_params.yaml");
+        
+        // 旧版参数文件没有有效区域
+        save_rectify_params(&path, &params).unwrap();
+        assert!(check_rectify_coverage(&path, DEFAULT_RECTIFY_MIN_COVERAGE).is_err());
+        
+        // 1000x1000 图像：左 900x900 (81%)，右向右偏移100 (81%)，交集 800x900 (72%)
+        params.valid_roi = Some(RectifyValidRoi {
+            image_width: 1000,
+            image_height: 1000,
+            left: RoiRect { x: 50, y: 50, width: 900, height: 900 },
+            right: RoiRect { x: 150, y: 50, width: 900, height: 900 },
+        });
+        save_rectify_params(&path, &params).unwrap();
+        let report = check_rectify_coverage(&path, 0.7).unwrap();
+        println!("{}", report.message);
+        assert!((report.left_percent - 81.0).abs() < 1e-9);
+        assert!((report.right_percent - 81.0).abs() < 1e-9);
+        assert!((report.overlap_percent - 72.0).abs() < 1e-9);
+        assert!(report.pass);
+        
+        let report = check_rectify_coverage(&path, DEFAULT_RECTIFY_MIN_COVERAGE).unwrap();
+        assert!(!report.pass);
+        assert!(report.message.contains("左右交集") && !report.message.contains("左图、"));
+        
+        // 无交集
+        let disjoint = RoiRect { x: 0, y: 0, width: 10, height: 10 }.intersect(&RoiRect { x: 20, y: 0, width: 10, height: 10 });
+        assert_eq!(disjoint.area(), 0);
+    }
+
+    #[test]
+    fn test_calibration_run_control_progress_and_cancel() {
+        use crate::modules::calibration_workflow::{CalibrationRunControl, CalibrationStage, CalibrationProgress, CALIBRATION_CANCELLED};
+        use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
+        println!("=== 测试后台标定阶段进度与取消 ===");
+        
+        let cancel = Arc::new(AtomicBool::new(false));
+        let entered = Mutex::new(Vec::new());
+        let control = CalibrationRunControl::new(cancel.clone(), |stage| entered.lock().unwrap().push(stage));
+        
+        control.enter(CalibrationStage::Detect).unwrap();
+        control.enter(CalibrationStage::MonoLeft).unwrap();
+        cancel.store(true, Ordering::SeqCst);
+        assert_eq!(control.enter(CalibrationStage::MonoRight), Err(CALIBRATION_CANCELLED.to_string()));
+        drop(control);
+        
+        // 取消后的阶段不回调
+        assert_eq!(entered.into_inner().unwrap(), vec![CalibrationStage::Detect, CalibrationStage::MonoLeft]);
+        
+        let progress = CalibrationProgress::new(7, CalibrationStage::Stereo);
+        assert_eq!((progress.step, progress.total_steps), (4, 6));
+        assert_eq!(CalibrationStage::Save.step(), 6);
+        assert_eq!(serde_json::to_value(progress).unwrap()["stage"], "stereo");
+    }
+    
+    #[test]
+    fn test_ros_camera_info_export() {
+        let dir = TestDir::new("calib_ros_camera_info");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let k = |fx: f64| vec![vec![fx, 0.0, 1224.5], vec![0.0, fx, 1023.25], vec![0.0, 0.0, 1.0]];
+        let identity = vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 1.0]];
+        save_camera_params(dir.join("left_camera_params.yaml"), &CameraParams {
+            camera_matrix: k(3000.0),
+            dist_coeffs: vec![-0.1, 0.01, 0.0, 0.0, 0.0],
+        }).unwrap();
+        save_camera_params(dir.join("right_camera_params.yaml"), &CameraParams {
+            camera_matrix: k(3001.0),
+            dist_coeffs: vec![-0.2, 0.02, 0.001, -0.001, 0.5],
+        }).unwrap();
+        save_rectify_params(dir.join("rectify_params.yaml"), &RectifyParams {
+            r1: identity.clone(),
+            r2: identity,
+            p1: vec![vec![2900.0, 0.0, 1200.0, 0.0], vec![0.0, 2900.0, 1000.0, 0.0], vec![0.0, 0.0, 1.0, 0.0]],
+            p2: vec![vec![2900.0, 0.0, 1200.0, -174000.0], vec![0.0, 2900.0, 1000.0, 0.0], vec![0.0, 0.0, 1.0, 0.0]],
+            q: vec![vec![0.0; 4]; 4],
+            valid_roi: None,
+        }).unwrap();
+
+        let (left_path, right_path) = export_ros_camera_info(&dir, dir.join("ros"), (2448, 2048)).unwrap();
+
+        // 与 camera_calibration_parsers 写出的布局逐行一致
+        let expected_left = "image_width: 2448\n\
+image_height: 2048\n\
+camera_name: left_camera\n\
+camera_matrix:\n  rows: 3\n  cols: 3\n  data: [3000, 0, 1224.5, 0, 3000, 1023.25, 0, 0, 1]\n\
+distortion_model: plumb_bob\n\
+distortion_coefficients:\n  rows: 1\n  cols: 5\n  data: [-0.1, 0.01, 0, 0, 0]\n\
+rectification_matrix:\n  rows: 3\n  cols: 3\n  data: [1, 0, 0, 0, 1, 0, 0, 0, 1]\n\
+projection_matrix:\n  rows: 3\n  cols: 4\n  data: [2900, 0, 1200, 0, 0, 2900, 1000, 0, 0, 0, 1, 0]\n";
+        assert_eq!(std::fs::read_to_string(&left_path).unwrap(), expected_left);
+
+        // 右相机投影矩阵含基线项，读取往返一致
+        let right = load_ros_camera_info(&right_path).unwrap();
+        assert_eq!(right.camera_name, "right_camera");
+        assert_eq!((right.projection_matrix.rows, right.projection_matrix.cols), (3, 4));
+        assert_eq!(right.projection_matrix.data[3], -174000.0);
+        assert_eq!(right.camera_matrix.data[0], 3001.0);
+        assert_eq!(right.distortion_coefficients.data, vec![-0.2, 0.02, 0.001, -0.001, 0.5]);
+
+        // 畸变参数个数不符合 ROS 模型时拒绝导出
+        let bad = CameraParams { camera_matrix: k(3000.0), dist_coeffs: vec![0.0; 4] };
+        let p = vec![vec![0.0; 4]; 3];
+        assert!(RosCameraInfo::from_calibration("bad", (2448, 2048), &bad, &k(1.0), &p).is_err());
+        let good = CameraParams { camera_matrix: k(3000.0), dist_coeffs: vec![0.0; 8] };
+        let info = RosCameraInfo::from_calibration("good", (2448, 2048), &good, &k(1.0), &p).unwrap();
+        assert_eq!(info.distortion_model, "rational_polynomial");
+        assert!(RosCameraInfo::from_calibration("good", (2448, 2048), &good, &k(1.0), &k(1.0)).is_err());
+    }
 } 
\ No newline at end of file
@@ -242,6 +242,7 @@ fn test_calibration_pipeline() -> Result<(), Box<dyn std::error::Error>> {
         p1: mat_to_vec2d_f64(&rectify_maps.p1),
         p2: mat_to_vec2d_f64(&rectify_maps.p2),
         q: mat_to_vec2d_f64(&rectify_maps.q),
+        valid_roi: None,
     };
     save_rectify_params(params_dir.join("rectify.yaml"), &rectify_params)?;
 
@@ -308,6 +308,7 @@ fn test_calibration_process() -> Result<(), Box<dyn std::error::Error>> {
         p1: param_io::mat_to_vec2d_f64(&rectify_maps.p1),
         p2: param_io::mat_to_vec2d_f64(&rectify_maps.p2),
         q: param_io::mat_to_vec2d_f64(&rectify_maps.q),
+        valid_roi: None,
     };
     param_io::save_rectify_params(
         params_path.join("rectify.yaml"),
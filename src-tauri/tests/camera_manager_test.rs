@@ -0,0 +1,274 @@
+#[cfg(test)]
+use crate::camera_manager::{SimpleCameraManager, CameraError, FrameRecoveryConfig, CameraWarmupConfig, DEFAULT_WARMUP_DISCARD_FRAMES};
+use crate::camera_ffi::CameraBackend;
+use std::os::raw::{c_uchar, c_uint};
+use std::sync::{Arc, Mutex};
+
+/// 模拟MV_CC_GetImageBuffer超时 (MV_E_NODATA)
+const STUB_TIMEOUT_CODE: i32 = 0x80000007u32 as i32;
+
+/// camera_init.c 初始化时写入的默认曝光 (μs)
+const STUB_DEFAULT_EXPOSURE_US: f32 = 90000.0;
+
+/// 桩相机状态：fail_frames 次取帧超时后恢复正常
+#[derive(Default)]
+struct StubState {
+    fail_frames: u32,
+    frame_calls: u32,
+    releases: u32,
+    reinits: u32,
+    starts: u32,
+    timeout_ms: Option<u32>,
+    serials: Vec<String>,  // 槽位0/1上打开的相机序列号（模拟枚举顺序）
+    exposure_us: Option<f32>,  // 相机当前曝光，None 为初始化默认值
+}
+
+struct StubCamera(Arc<Mutex<StubState>>);
+
+impl CameraBackend for StubCamera {
+    fn camera_reinit_ffi(&self) -> Result<(), i32> {
+        let mut state = self.0.lock().unwrap();
+        state.reinits += 1;
+        state.exposure_us = None;  // 与 camera_init 一致，重新初始化恢复默认曝光
+        Ok(())
+    }
+
+    fn camera_start_ffi(&self) -> Result<(), i32> {
+        self.0.lock().unwrap().starts += 1;
+        Ok(())
+    }
+
+    fn camera_get_frame_ffi(&self, out_bufs: &mut [*mut c_uchar; 2], out_sizes: &mut [c_uint; 2]) -> Result<(), i32> {
+        let mut state = self.0.lock().unwrap();
+        state.frame_calls += 1;
+        if state.fail_frames > 0 {
+            state.fail_frames -= 1;
+            return Err(STUB_TIMEOUT_CODE);
+        }
+        for i in 0..2 {
+            unsafe { *out_bufs[i] = 0x5A + i as u8; }
+            out_sizes[i] = 4;
+        }
+        Ok(())
+    }
+
+    fn camera_release_ffi(&self) -> Result<(), i32> {
+        self.0.lock().unwrap().releases += 1;
+        Ok(())
+    }
+
+    fn camera_set_frame_timeout_ffi(&self, timeout_ms: u32) -> Result<(), i32> {
+        self.0.lock().unwrap().timeout_ms = Some(timeout_ms);
+        Ok(())
+    }
+
+    fn camera_set_exposure_time_ffi(&self, _cam_index: u32, exposure_us: f32) -> Result<(), i32> {
+        self.0.lock().unwrap().exposure_us = Some(exposure_us);
+        Ok(())
+    }
+
+    fn camera_get_exposure_time_ffi(&self, _cam_index: u32) -> Result<f32, i32> {
+        Ok(self.0.lock().unwrap().exposure_us.unwrap_or(STUB_DEFAULT_EXPOSURE_US))
+    }
+
+    fn camera_get_serial_ffi(&self, cam_index: u32) -> Result<String, i32> {
+        self.0.lock().unwrap().serials.get(cam_index as usize).cloned().ok_or(-1)
+    }
+}
+
+#[test]
+fn test_frame_timeout_triggers_reconnect() {
+    println!("=== 测试取帧超时后自动重连 ===");
+
+    let state = Arc::new(Mutex::new(StubState::default()));
+    let manager = SimpleCameraManager::with_backend(Box::new(StubCamera(Arc::clone(&state))), 16);
+    manager.set_frame_recovery_config(FrameRecoveryConfig {
+        frame_timeout_ms: 250,
+        reconnect_after_failures: 3,
+    }).unwrap();
+    assert!(manager.set_frame_recovery_config(FrameRecoveryConfig {
+        frame_timeout_ms: 0,
+        reconnect_after_failures: 3,
+    }).is_err());
+    manager.set_exposure_time(25000.0).unwrap();
+
+    manager.start().unwrap();
+    assert_eq!(state.lock().unwrap().timeout_ms, Some(250));
+    assert_eq!(manager.get_exposure_time().unwrap(), 25000.0);
+
+    // 正常取帧
+    let (left, right) = manager.get_current_frame().unwrap();
+    assert_eq!((left.len(), left[0], right[0]), (4, 0x5A, 0x5B));
+
+    // 模拟链路抖动：连续4次超时
+    state.lock().unwrap().fail_frames = 4;
+    assert!(matches!(manager.get_current_frame(), Err(CameraError::CaptureFailed(STUB_TIMEOUT_CODE))));
+    assert!(matches!(manager.get_current_frame(), Err(CameraError::CaptureFailed(_))));
+    assert_eq!(manager.consecutive_failures(), 2);
+
+    // 第3次失败触发重连 (release → init → start)，超时与曝光重新下发
+    state.lock().unwrap().timeout_ms = None;
+    match manager.get_current_frame() {
+        Err(CameraError::Reconnecting { attempt, last_error }) => {
+            assert_eq!(attempt, 1);
+            assert_eq!(last_error, STUB_TIMEOUT_CODE);
+        }
+        other => panic!("期望 Reconnecting，实际: {:?}", other.map(|_| ())),
+    }
+    {
+        let s = state.lock().unwrap();
+        assert_eq!((s.releases, s.reinits, s.starts), (1, 1, 2));
+        assert_eq!(s.timeout_ms, Some(250));
+        assert_eq!(s.exposure_us, Some(25000.0), "重连后不应回到初始化默认曝光");
+    }
+    assert_eq!(manager.reconnect_count(), 1);
+    assert_eq!(manager.consecutive_failures(), 0);
+    assert!(manager.is_running(), "重连期间保持运行状态，采集线程不退出");
+
+    // 剩余1次超时后恢复，计数清零
+    assert!(manager.get_current_frame().is_err());
+    assert!(manager.get_current_frame().is_ok());
+    assert_eq!(manager.consecutive_failures(), 0);
+    assert_eq!(state.lock().unwrap().frame_calls, 7);
+
+    manager.stop().unwrap();
+    assert_eq!(state.lock().unwrap().releases, 2);
+}
+
+#[test]
+fn test_frame_reconnect_disabled() {
+    println!("=== 测试关闭自动重连 ===");
+
+    let state = Arc::new(Mutex::new(StubState { fail_frames: 10, ..Default::default() }));
+    let manager = SimpleCameraManager::with_backend(Box::new(StubCamera(Arc::clone(&state))), 16);
+    manager.set_frame_recovery_config(FrameRecoveryConfig {
+        frame_timeout_ms: 100,
+        reconnect_after_failures: 0,
+    }).unwrap();
+    manager.start().unwrap();
+
+    for _ in 0..10 {
+        assert!(matches!(manager.get_current_frame(), Err(CameraError::CaptureFailed(_))));
+    }
+    assert_eq!(manager.reconnect_count(), 0);
+    assert_eq!(state.lock().unwrap().reinits, 0);
+    manager.stop().unwrap();
+}
+
+#[test]
+fn test_swap_eyes_exchanges_frames() {
+    println!("=== 测试左右眼互换 ===");
+
+    let state = Arc::new(Mutex::new(StubState::default()));
+    let manager = SimpleCameraManager::with_backend(Box::new(StubCamera(Arc::clone(&state))), 16);
+    manager.start().unwrap();
+
+    // 默认相机0为左眼
+    let (left, right) = manager.get_current_frame().unwrap();
+    assert_eq!((left[0], right[0]), (0x5A, 0x5B));
+
+    manager.set_swap_eyes(true);
+    assert!(manager.is_eyes_swapped());
+    let (left, right) = manager.get_current_frame().unwrap();
+    assert_eq!((left[0], right[0]), (0x5B, 0x5A));
+
+    manager.set_swap_eyes(false);
+    let (left, _) = manager.get_current_frame().unwrap();
+    assert_eq!(left[0], 0x5A);
+    manager.stop().unwrap();
+}
+
+#[test]
+fn test_warm_up_discards_first_frames() {
+    println!("=== 测试启动预热丢弃前N帧 ===");
+
+    let state = Arc::new(Mutex::new(StubState::default()));
+    let manager = SimpleCameraManager::with_backend(Box::new(StubCamera(Arc::clone(&state))), 16);
+
+    // 相机未启动时预热报错
+    assert!(matches!(manager.warm_up(|_, _| {}), Err(CameraError::NotStarted)));
+
+    manager.start().unwrap();
+    assert_eq!(manager.get_warmup_config().discard_frames, DEFAULT_WARMUP_DISCARD_FRAMES);
+    let mut progress = Vec::new();
+    let discarded = manager.warm_up(|attempt, total| progress.push((attempt, total))).unwrap();
+    assert_eq!(discarded, DEFAULT_WARMUP_DISCARD_FRAMES);
+    assert_eq!(state.lock().unwrap().frame_calls, DEFAULT_WARMUP_DISCARD_FRAMES);
+    assert_eq!(progress.last(), Some(&(DEFAULT_WARMUP_DISCARD_FRAMES, DEFAULT_WARMUP_DISCARD_FRAMES)));
+
+    // 预热期间取帧超时不中断，只计入尝试；总时长不少于 min_duration_ms
+    state.lock().unwrap().fail_frames = 1;
+    manager.set_warmup_config(CameraWarmupConfig { discard_frames: 5, min_duration_ms: 50 }).unwrap();
+    let start = std::time::Instant::now();
+    assert_eq!(manager.warm_up(|_, _| {}).unwrap(), 4);
+    assert!(start.elapsed() >= std::time::Duration::from_millis(50));
+
+    // 不预热
+    manager.set_warmup_config(CameraWarmupConfig { discard_frames: 0, min_duration_ms: 0 }).unwrap();
+    let calls = state.lock().unwrap().frame_calls;
+    assert_eq!(manager.warm_up(|_, _| {}).unwrap(), 0);
+    assert_eq!(state.lock().unwrap().frame_calls, calls);
+
+    assert!(manager.set_warmup_config(CameraWarmupConfig { discard_frames: 1000, min_duration_ms: 0 }).is_err());
+    manager.stop().unwrap();
+}
+
+#[test]
+fn test_camera_roles_follow_serials() {
+    println!("=== 测试按序列号分配左右相机 ===");
+
+    // 模拟USB枚举顺序颠倒：右相机在槽位0，左相机在槽位1
+    let state = Arc::new(Mutex::new(StubState {
+        serials: vec!["SN_RIGHT".to_string(), "SN_LEFT".to_string()],
+        ..Default::default()
+    }));
+    let manager = SimpleCameraManager::with_backend(Box::new(StubCamera(Arc::clone(&state))), 16);
+
+    // 序列号未找到时报错且不改变分配
+    assert!(matches!(manager.set_camera_roles("SN_LEFT", "SN_OTHER"), Err(CameraError::SerialMismatch(_))));
+    assert!(matches!(manager.set_camera_roles("SN_LEFT", "SN_LEFT"), Err(CameraError::SerialMismatch(_))));
+    assert!(manager.camera_roles().is_none());
+    assert_eq!(manager.left_camera_slot(), 0);
+
+    manager.set_camera_roles("SN_LEFT", "SN_RIGHT").unwrap();
+    assert_eq!(manager.left_camera_slot(), 1);
+    assert_eq!(manager.camera_roles().unwrap().left_serial, "SN_LEFT");
+
+    // 返回顺序按角色而非槽位：左 = 槽位1 (0x5B)
+    manager.start().unwrap();
+    let (left, right) = manager.get_current_frame().unwrap();
+    assert_eq!((left[0], right[0]), (0x5B, 0x5A));
+
+    // 左右眼互换在角色分配之后生效
+    manager.set_swap_eyes(true);
+    let (left, right) = manager.get_current_frame().unwrap();
+    assert_eq!((left[0], right[0]), (0x5A, 0x5B));
+    manager.stop().unwrap();
+}
+
+#[test]
+fn test_configured_exposure_applied_on_start() {
+    println!("=== 测试设定曝光在启动采集时下发 ===");
+
+    let state = Arc::new(Mutex::new(StubState::default()));
+    let manager = SimpleCameraManager::with_backend(Box::new(StubCamera(Arc::clone(&state))), 16);
+    assert_eq!(manager.configured_exposure_time(), None);
+
+    // 未启动时只记录，启动后覆盖初始化默认曝光
+    manager.set_exposure_time(25000.0).unwrap();
+    assert_eq!(manager.get_exposure_time().unwrap(), STUB_DEFAULT_EXPOSURE_US);
+    manager.start().unwrap();
+    assert_eq!(manager.get_exposure_time().unwrap(), 25000.0);
+
+    // 运行中立即生效
+    manager.set_exposure_time(30000.0).unwrap();
+    assert_eq!(manager.get_exposure_time().unwrap(), 30000.0);
+
+    // 停止后相机重新初始化恢复默认曝光，再次启动时重新下发
+    manager.stop().unwrap();
+    state.lock().unwrap().exposure_us = None;
+    manager.start().unwrap();
+    assert_eq!(manager.get_exposure_time().unwrap(), 30000.0);
+    assert_eq!(manager.configured_exposure_time(), Some(30000.0));
+    manager.stop().unwrap();
+}
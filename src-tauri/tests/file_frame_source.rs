@@ -0,0 +1,611 @@
+//! 文件回放相机 - 依次循环回放已保存的左右帧对
+//!
+//! 实现 `CameraBackend`，经 `SimpleCameraManager::with_backend` 注入
+//! `CalibrationWorkflow::with_camera` / `AlignmentWorkflow::with_camera` 后，
+//! 工作流逻辑（含采集恢复、左右互换、预热）可在无相机环境下集成测试。
+
+use std::os::raw::{c_uchar, c_uint};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use opencv::{core::{self, Mat}, imgcodecs, prelude::*};
+use super::fixtures::TestDir;
+use crate::camera_ffi::CameraBackend;
+use crate::camera_manager::{SimpleCameraManager, CameraError, CameraWarmupConfig, FrameResolution, DEFAULT_WARMUP_DISCARD_FRAMES};
+use crate::modules::calibration_workflow::{CalibrationWorkflow, CalibrationStatus};
+
+struct ReplayState {
+    frames: Vec<(Vec<u8>, Vec<u8>)>,
+    served: AtomicUsize,
+    running: AtomicBool,
+    exposure_us: Mutex<[f32; 2]>,
+}
+
+/// 回放相机底层；克隆共享同一回放状态，注入工作流后测试仍可查询取帧次数与运行状态
+#[derive(Clone)]
+pub struct FileFrameSource(Arc<ReplayState>);
+
+impl FileFrameSource {
+    /// 由原始8位灰度左右帧数据创建
+    pub fn from_raw_pairs(frames: Vec<(Vec<u8>, Vec<u8>)>) -> Self {
+        assert!(!frames.is_empty(), "回放帧源至少需要一对帧");
+        Self(Arc::new(ReplayState {
+            frames,
+            served: AtomicUsize::new(0),
+            running: AtomicBool::new(false),
+            exposure_us: Mutex::new([10000.0; 2]),
+        }))
+    }
+
+    /// 由灰度 Mat 帧对创建（如合成夹具帧）
+    pub fn from_mats(pairs: &[(Mat, Mat)]) -> Result<Self, opencv::Error> {
+        let mut frames = Vec::with_capacity(pairs.len());
+        for (left, right) in pairs {
+            frames.push((left.data_bytes()?.to_vec(), right.data_bytes()?.to_vec()));
+        }
+        Ok(Self::from_raw_pairs(frames))
+    }
+
+    /// 读取已保存的左右图像文件（按灰度解码）
+    pub fn from_files<P: AsRef<Path>>(pairs: &[(P, P)]) -> Result<Self, String> {
+        let read = |path: &Path| {
+            let mat = imgcodecs::imread(&path.to_string_lossy(), imgcodecs::IMREAD_GRAYSCALE)
+                .map_err(|e| format!("读取图像失败 {}: {}", path.display(), e))?;
+            if mat.empty() {
+                return Err(format!("图像为空或不存在: {}", path.display()));
+            }
+            Ok(mat)
+        };
+        let mats = pairs.iter()
+            .map(|(left, right)| Ok((read(left.as_ref())?, read(right.as_ref())?)))
+            .collect::<Result<Vec<_>, String>>()?;
+        Self::from_mats(&mats).map_err(|e| e.to_string())
+    }
+
+    /// 以本回放源为底层创建相机管理器，帧缓冲区按最大帧分配
+    pub fn camera_manager(&self) -> SimpleCameraManager {
+        let frame_buf_size = self.0.frames.iter()
+            .map(|(left, right)| left.len().max(right.len()))
+            .max()
+            .unwrap_or(0);
+        SimpleCameraManager::with_backend(Box::new(self.clone()), frame_buf_size as u32)
+    }
+
+    /// 已回放的帧对数
+    pub fn frames_served(&self) -> usize {
+        self.0.served.load(Ordering::SeqCst)
+    }
+
+    /// 底层是否处于采集状态（start 之后、release 之前）
+    pub fn is_running(&self) -> bool {
+        self.0.running.load(Ordering::SeqCst)
+    }
+}
+
+impl CameraBackend for FileFrameSource {
+    fn camera_reinit_ffi(&self) -> Result<(), i32> {
+        Ok(())
+    }
+
+    fn camera_start_ffi(&self) -> Result<(), i32> {
+        self.0.running.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn camera_get_frame_ffi(&self, out_bufs: &mut [*mut c_uchar; 2], out_sizes: &mut [c_uint; 2]) -> Result<(), i32> {
+        if !self.is_running() {
+            return Err(-1);
+        }
+        let index = self.0.served.fetch_add(1, Ordering::SeqCst) % self.0.frames.len();
+        let (left, right) = &self.0.frames[index];
+        for (i, data) in [left, right].into_iter().enumerate() {
+            unsafe { std::ptr::copy_nonoverlapping(data.as_ptr(), out_bufs[i], data.len()); }
+            out_sizes[i] = data.len() as c_uint;
+        }
+        Ok(())
+    }
+
+    fn camera_release_ffi(&self) -> Result<(), i32> {
+        self.0.running.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn camera_set_frame_timeout_ffi(&self, _timeout_ms: u32) -> Result<(), i32> {
+        Ok(())
+    }
+
+    fn camera_set_exposure_time_ffi(&self, cam_index: u32, exposure_us: f32) -> Result<(), i32> {
+        self.0.exposure_us.lock().unwrap()[cam_index as usize] = exposure_us;
+        Ok(())
+    }
+
+    fn camera_get_exposure_time_ffi(&self, cam_index: u32) -> Result<f32, i32> {
+        Ok(self.0.exposure_us.lock().unwrap()[cam_index as usize])
+    }
+
+    fn camera_get_serial_ffi(&self, cam_index: u32) -> Result<String, i32> {
+        Ok(format!("REPLAY{}", cam_index))
+    }
+}
+
+/// 612×512 纯色帧（工作流需按此分辨率配置）
+fn flat_frame(value: f64) -> Mat {
+    Mat::new_rows_cols_with_default(512, 612, core::CV_8UC1, core::Scalar::all(value)).unwrap()
+}
+
+#[test]
+fn test_file_frame_source_replays_saved_pairs() {
+    println!("=== 测试文件回放帧源 ===");
+
+    let dir = TestDir::new("replay");
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut pairs = Vec::new();
+    for (i, value) in [10.0, 200.0].iter().enumerate() {
+        let left = dir.join(format!("left_{}.png", i));
+        let right = dir.join(format!("right_{}.png", i));
+        imgcodecs::imwrite(&left.to_string_lossy(), &flat_frame(*value), &core::Vector::new()).unwrap();
+        imgcodecs::imwrite(&right.to_string_lossy(), &flat_frame(*value + 1.0), &core::Vector::new()).unwrap();
+        pairs.push((left, right));
+    }
+    let source = FileFrameSource::from_files(&pairs).unwrap();
+    let manager = source.camera_manager();
+
+    // 未启动时与实机一致返回 NotStarted
+    assert!(matches!(manager.get_current_frame(), Err(CameraError::NotStarted)));
+    manager.start().unwrap();
+    assert!(matches!(manager.start(), Err(CameraError::AlreadyStarted)));
+    assert!(source.is_running());
+
+    // 按保存顺序回放，结束后从头循环
+    let values: Vec<(u8, u8)> = (0..3)
+        .map(|_| {
+            let (left, right) = manager.get_current_frame().unwrap();
+            assert_eq!(left.len(), 612 * 512);
+            (left[0], right[0])
+        })
+        .collect();
+    assert_eq!(values, vec![(10, 11), (200, 201), (10, 11)]);
+    assert_eq!(source.frames_served(), 3);
+
+    // 左右互换、预热、曝光均由相机管理器实际执行
+    manager.set_swap_eyes(true);
+    let (left, right) = manager.get_current_frame().unwrap();
+    assert_eq!((left[0], right[0]), (201, 200));
+    manager.set_swap_eyes(false);
+    assert_eq!(manager.warm_up(|_, _| {}).unwrap(), DEFAULT_WARMUP_DISCARD_FRAMES);
+    manager.set_warmup_config(CameraWarmupConfig { discard_frames: 0, min_duration_ms: 0 }).unwrap();
+    assert_eq!(manager.warm_up(|_, _| {}).unwrap(), 0);
+    manager.set_exposure_time(5000.0).unwrap();
+    assert_eq!(manager.get_exposure_time().unwrap(), 5000.0);
+
+    manager.stop().unwrap();
+    assert!(!source.is_running());
+}
+
+#[test]
+fn test_calibration_workflow_with_file_frame_source() {
+    println!("=== 测试标定工作流注入回放帧源 ===");
+
+    let source = FileFrameSource::from_mats(&[(flat_frame(30.0), flat_frame(40.0))]).unwrap();
+    let mut workflow = CalibrationWorkflow::with_camera(source.camera_manager());
+    workflow.set_frame_resolution(FrameResolution::new(612, 512)).unwrap();
+    assert!(!workflow.is_camera_active());
+
+    workflow.start_calibration().unwrap();
+    let save_directory = workflow.calibration_config().save_directory.clone();
+    assert_eq!(workflow.get_status(), CalibrationStatus::Capturing);
+    assert!(source.is_running());
+
+    // 启动预热丢弃前几帧，预览取帧经由注入的帧源
+    assert_eq!(source.frames_served(), DEFAULT_WARMUP_DISCARD_FRAMES as usize);
+    let preview = workflow.get_preview_frame_sync(false, false).unwrap();
+    assert!(!preview.left_preview.is_empty());
+    assert!(!preview.right_preview.is_empty());
+    assert_eq!(source.frames_served(), DEFAULT_WARMUP_DISCARD_FRAMES as usize + 1);
+
+    workflow.stop_calibration().unwrap();
+    assert_eq!(workflow.get_status(), CalibrationStatus::NotStarted);
+    assert!(!source.is_running());
+    let _ = std::fs::remove_dir_all(&save_directory);
+}
+
+#[test]
+fn test_calibration_session_warmup_releases_workflow() {
+    println!("=== 测试标定会话预热阶段可观察 ===");
+
+    let source = FileFrameSource::from_mats(&[(flat_frame(30.0), flat_frame(40.0))]).unwrap();
+    let mut workflow = CalibrationWorkflow::with_camera(source.camera_manager());
+    workflow.set_frame_resolution(FrameResolution::new(612, 512)).unwrap();
+
+    // 预热任务创建后即处于 WarmingUp，预览不等待相机
+    let warmup = workflow.begin_session().unwrap();
+    assert_eq!(workflow.get_status(), CalibrationStatus::WarmingUp);
+    assert!(workflow.is_camera_active());
+    assert!(workflow.get_preview_frame_sync(false, false).unwrap_err().contains("预热"));
+    assert_eq!(source.frames_served(), 0);
+
+    let result = warmup.run(&mut |_, _| {});
+    workflow.finish_session_start(warmup, result).unwrap();
+    assert_eq!(workflow.get_status(), CalibrationStatus::Capturing);
+    let save_directory = workflow.calibration_config().save_directory.clone();
+    workflow.stop_calibration().unwrap();
+    let _ = std::fs::remove_dir_all(&save_directory);
+
+    // 预热期间停止会话：预热结束后不进入采集
+    let warmup = workflow.begin_session().unwrap();
+    workflow.stop_calibration().unwrap();
+    let result = warmup.run(&mut |_, _| {});
+    assert!(workflow.finish_session_start(warmup, result).is_err());
+    assert_eq!(workflow.get_status(), CalibrationStatus::NotStarted);
+
+    // 预热失败：停止相机，回到未开始
+    let warmup = workflow.begin_session().unwrap();
+    assert!(workflow.finish_session_start(warmup, Err("相机预热失败: 超时".to_string())).is_err());
+    assert_eq!(workflow.get_status(), CalibrationStatus::NotStarted);
+    assert!(!source.is_running());
+}
+
+#[test]
+fn test_frame_resolution_is_explicit() {
+    println!("=== 测试原始帧按配置分辨率解析 ===");
+
+    let resolution = FrameResolution::new(612, 512);
+    assert!(resolution.check_frame(&vec![0u8; 612 * 512]).is_ok());
+    assert!(resolution.check_frame(&vec![0u8; 612 * 512 - 1]).is_err());
+    assert!(FrameResolution::new(0, 0).check_frame(&[]).is_err());
+
+    let source = FileFrameSource::from_mats(&[(flat_frame(30.0), flat_frame(40.0))]).unwrap();
+    let mut workflow = CalibrationWorkflow::with_camera(source.camera_manager());
+    assert!(workflow.set_frame_resolution(FrameResolution::new(0, 512)).is_err());
+    workflow.set_frame_resolution(resolution).unwrap();
+    workflow.start_calibration().unwrap();
+    let save_directory = workflow.calibration_config().save_directory.clone();
+
+    // 配置分辨率与帧一致时正常解析
+    assert!(workflow.get_preview_frame_sync(false, false).is_ok());
+
+    // 配置与实际帧不符时明确报错，而不是按字节数猜测尺寸
+    workflow.set_frame_resolution(FrameResolution::default()).unwrap();
+    let err = workflow.get_preview_frame_sync(false, false).unwrap_err();
+    assert!(err.contains("不符"), "错误信息应说明分辨率不符: {}", err);
+
+    workflow.stop_calibration().unwrap();
+    let _ = std::fs::remove_dir_all(&save_directory);
+}
+
+#[test]
+fn test_annotated_preview_overlays_detected_grid() {
+    println!("=== 测试预览缩略图叠加圆点网格 ===");
+    use crate::modules::alignment::SyntheticGridParams;
+    use super::fixtures::SyntheticFixture;
+
+    // 合成圆阵反相为白底黑点（与打印标定板一致）
+    let fixture = SyntheticFixture::new("annotated_preview", &SyntheticGridParams::default());
+    let invert = |mat: &Mat| {
+        let mut inverted = Mat::default();
+        core::bitwise_not(mat, &mut inverted, &core::no_array()).unwrap();
+        inverted
+    };
+    let source = FileFrameSource::from_mats(&[(invert(&fixture.left), invert(&fixture.right))]).unwrap();
+    let mut workflow = CalibrationWorkflow::with_camera(source.camera_manager());
+    workflow.start_calibration().unwrap();
+    let save_directory = workflow.calibration_config().save_directory.clone();
+
+    let plain = workflow.get_preview_frame_sync(false, false).unwrap();
+    let annotated = workflow.get_preview_frame_sync(false, true).unwrap();
+    assert_eq!(plain.has_pattern, None);
+    assert_eq!(annotated.has_pattern, Some(true));
+    assert_ne!(annotated.left_preview, plain.left_preview, "检测到标定板时应返回标注后的缩略图");
+    assert_ne!(annotated.right_preview, plain.right_preview);
+
+    workflow.stop_calibration().unwrap();
+    let _ = std::fs::remove_dir_all(&save_directory);
+
+    // 无标定板时标注请求返回原始缩略图
+    let source = FileFrameSource::from_mats(&[(flat_frame(30.0), flat_frame(40.0))]).unwrap();
+    let mut workflow = CalibrationWorkflow::with_camera(source.camera_manager());
+    workflow.set_frame_resolution(FrameResolution::new(612, 512)).unwrap();
+    workflow.start_calibration().unwrap();
+    let save_directory = workflow.calibration_config().save_directory.clone();
+    let plain = workflow.get_preview_frame_sync(false, false).unwrap();
+    let annotated = workflow.get_preview_frame_sync(false, true).unwrap();
+    assert_eq!(annotated.has_pattern, Some(false));
+    assert_eq!(annotated.left_preview, plain.left_preview);
+    workflow.stop_calibration().unwrap();
+    let _ = std::fs::remove_dir_all(&save_directory);
+}
+
+#[test]
+fn test_export_point_correspondences_from_cached_detections() {
+    println!("=== 测试由采集检测缓存导出点对应关系 ===");
+    use crate::modules::alignment::SyntheticGridParams;
+    use crate::modules::param_io::load_point_correspondences;
+    use super::fixtures::SyntheticFixture;
+
+    let fixture = SyntheticFixture::new("export_correspondences", &SyntheticGridParams::default());
+    let invert = |mat: &Mat| {
+        let mut inverted = Mat::default();
+        core::bitwise_not(mat, &mut inverted, &core::no_array()).unwrap();
+        inverted
+    };
+    let source = FileFrameSource::from_mats(&[(invert(&fixture.left), invert(&fixture.right))]).unwrap();
+    let mut workflow = CalibrationWorkflow::with_camera(source.camera_manager());
+    // 不开启圆心旁路文件：导出只依赖采集时的检测缓存
+    workflow.set_corner_sidecar_saving(false);
+    // 重复位姿默认仅提示：同一帧两次采集均保留
+    assert!(!workflow.calibration_config().reject_duplicate_poses);
+    workflow.start_calibration().unwrap();
+    let save_directory = workflow.calibration_config().save_directory.clone();
+    assert!(workflow.export_point_correspondences(None).is_err(), "未采集时无缓存可导出");
+
+    workflow.get_preview_frame_sync(true, false).unwrap();
+    workflow.get_preview_frame_sync(true, false).unwrap();
+    let captured = workflow.get_captured_images();
+    assert_eq!(captured.len(), 2);
+    assert!(captured.iter().all(|img| img.has_calibration_pattern), "合成标定板应被检测到");
+
+    let output = fixture.dir.join("correspondences.json");
+    let (path, count) = workflow.export_point_correspondences(Some(&output.to_string_lossy())).unwrap();
+    assert_eq!(count, 2);
+    let export = load_point_correspondences(&path).unwrap();
+    assert_eq!(export.pair_ids, captured.iter().map(|img| img.pair_id).collect::<Vec<_>>());
+    assert_eq!(export.left_image_paths[0], captured[0].left_image_path);
+    let expected_points = (export.pattern_cols * export.pattern_rows) as usize;
+    assert_eq!(export.world_points.len(), expected_points);
+    for points in export.left_points.iter().chain(&export.right_points) {
+        assert_eq!(points.len(), expected_points);
+        assert!(points.iter().all(|&[x, y]| x > 0.0 && y > 0.0 && x < export.image_width as f32 && y < export.image_height as f32));
+    }
+    // 同一回放帧的两次检测结果一致
+    assert_eq!(export.left_points[0], export.left_points[1]);
+
+    workflow.stop_calibration().unwrap();
+    let _ = std::fs::remove_dir_all(&save_directory);
+}
+
+/// 回放6组不同位姿的标定板与2组空白帧，采满8组后执行标定
+fn run_calibration_session(name: &str, min_valid: usize) -> (Vec<bool>, Result<(), String>) {
+    use crate::modules::alignment::SyntheticGridParams;
+    use super::fixtures::{SyntheticFixture, FIXTURE_IMAGE_SIZE};
+
+    let (_dir, system) = SyntheticFixture::ideal_system(name);
+    let invert = |mat: &Mat| {
+        let mut inverted = Mat::default();
+        core::bitwise_not(mat, &mut inverted, &core::no_array()).unwrap();
+        inverted
+    };
+    let poses = [(0.0, 0.0, 0.0), (12.0, 0.0, 3.0), (-12.0, 5.0, -3.0), (0.0, 12.0, 6.0), (5.0, -12.0, -6.0), (-8.0, -8.0, 10.0)];
+    let mut frames: Vec<(Mat, Mat)> = poses.iter().enumerate().map(|(i, &(tilt_x_deg, tilt_y_deg, roll_deg))| {
+        let params = SyntheticGridParams {
+            tilt_x_deg,
+            tilt_y_deg,
+            roll_deg,
+            shift_x_px: (i as f64 - 2.5) * 40.0,
+            right_dx_px: 6.0,
+            ..SyntheticGridParams::default()
+        };
+        let (left, right) = system.render_synthetic_pair(&params).unwrap();
+        (invert(&left), invert(&right))
+    }).collect();
+    let blank = Mat::new_rows_cols_with_default(FIXTURE_IMAGE_SIZE.1, FIXTURE_IMAGE_SIZE.0, core::CV_8UC1, core::Scalar::all(200.0)).unwrap();
+    frames.push((blank.clone(), blank.clone()));
+    frames.push((blank.clone(), blank));
+
+    let source = FileFrameSource::from_mats(&frames).unwrap();
+    let mut workflow = CalibrationWorkflow::with_camera(source.camera_manager());
+    workflow.set_corner_sidecar_saving(false);
+    workflow.set_image_count_requirements(frames.len() as u32, min_valid).unwrap();
+    workflow.start_calibration().unwrap();
+    let save_directory = workflow.calibration_config().save_directory.clone();
+
+    // 回放循环：8次采集恰好覆盖每一帧一次
+    for _ in 0..frames.len() {
+        workflow.get_preview_frame_sync(true, false).unwrap();
+    }
+    assert_eq!(workflow.get_status(), CalibrationStatus::ReadyToCalibrate);
+    let detected = workflow.get_captured_images().iter().map(|img| img.has_calibration_pattern).collect();
+
+    let result = workflow.run_calibration().map(|_| ());
+    let _ = std::fs::remove_dir_all(&save_directory);
+    (detected, result)
+}
+
+#[test]
+fn test_min_valid_pairs_gate_in_calibration_session() {
+    println!("=== 测试标定会话的最少有效图像对门槛 ===");
+    use crate::modules::calibration_circles::DEFAULT_MIN_VALID_IMAGES;
+
+    // 最少有效数量设为6：6组有效图像对通过会话门槛进入标定计算
+    let (detected, result) = run_calibration_session("min_valid_six", 6);
+    assert_eq!(detected.iter().filter(|&&valid| valid).count(), 6, "6组合成标定板应被检测到，空白帧不应被检测到");
+    if let Err(e) = &result {
+        assert!(!e.contains("有效图像数量不足"), "最少有效数量为6时不应被数量门槛拦截: {}", e);
+    }
+
+    // 默认最少8组：同样的6组有效图像对被拦截
+    let (_, result) = run_calibration_session("min_valid_default", DEFAULT_MIN_VALID_IMAGES);
+    let error = result.expect_err("默认最少有效数量下6组图像对不应开始标定");
+    assert_eq!(error, format!("有效图像数量不足: 6/{}", DEFAULT_MIN_VALID_IMAGES));
+}
+
+/// 等待采集线程预热后把回放帧写入帧缓冲区
+fn wait_for_buffered_frame(workflow: &crate::modules::alignment_workflow::AlignmentWorkflow) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while workflow.render_anaglyph(false).is_err() {
+        assert!(Instant::now() < deadline, "回放帧未进入帧缓冲区");
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[test]
+fn test_alignment_workflow_with_file_frame_source() {
+    println!("=== 测试合像工作流注入回放帧源 ===");
+    use crate::modules::alignment::SyntheticGridParams;
+    use crate::modules::alignment_workflow::{AlignmentWorkflow, DetectionResult};
+    use super::fixtures::SyntheticFixture;
+
+    // 理想参数只写在夹具目录：工作流须从 param_dir 加载标定与重映射
+    let params = SyntheticGridParams { right_dx_px: 6.0, ..SyntheticGridParams::default() };
+    let fixture = SyntheticFixture::new("alignment_replay", &params);
+    let source = FileFrameSource::from_mats(&[(fixture.left.clone(), fixture.right.clone())]).unwrap();
+    let mut workflow = AlignmentWorkflow::with_camera(None, source.camera_manager());
+    workflow.set_param_dir(fixture.dir.to_path_buf());
+    workflow.start_workflow().unwrap();
+    assert!(source.is_running());
+
+    wait_for_buffered_frame(&workflow);
+    assert!(source.frames_served() > DEFAULT_WARMUP_DISCARD_FRAMES as usize);
+
+    match workflow.get_current_detection_result().unwrap() {
+        DetectionResult::DualEyeAlignment { mean_dx, mean_dy, .. } => {
+            assert!((mean_dx - 6.0).abs() < 0.5 && mean_dy.abs() < 0.5, "Δ=({:.2}, {:.2})", mean_dx, mean_dy);
+        }
+        other => panic!("期望合像结果，实际: {:?}", other),
+    }
+
+    // 停止后不沿用上次会话的预览帧
+    assert!(!workflow.get_current_preview_frame().unwrap().stale);
+    workflow.stop_workflow().unwrap();
+    assert!(!source.is_running());
+    assert!(workflow.get_current_preview_frame().is_err(), "停止后不应返回旧预览");
+}
+
+#[test]
+fn test_stop_workflow_flushes_pending_unit_result() {
+    println!("=== 测试停止工作流程时写入当前产品的合像结果 ===");
+    use crate::modules::alignment::SyntheticGridParams;
+    use crate::modules::alignment_workflow::AlignmentWorkflow;
+    use crate::modules::result_log::read_jsonl_log;
+    use super::fixtures::SyntheticFixture;
+
+    let params = SyntheticGridParams { right_dx_px: 6.0, ..SyntheticGridParams::default() };
+    let fixture = SyntheticFixture::new("stop_flushes_log", &params);
+    let source = FileFrameSource::from_mats(&[(fixture.left.clone(), fixture.right.clone())]).unwrap();
+    let mut workflow = AlignmentWorkflow::with_camera(None, source.camera_manager());
+    workflow.set_param_dir(fixture.dir.to_path_buf());
+    let log_path = fixture.dir.join("results.jsonl");
+    workflow.enable_result_logging(log_path.clone()).unwrap();
+    workflow.start_workflow().unwrap();
+    wait_for_buffered_frame(&workflow);
+
+    // 处理线程进入合像阶段并产生结果，期间不调用 finish_unit
+    workflow.set_unit_serial("AR-0042").unwrap();
+    workflow.start_detection().unwrap();
+    workflow.next_stage().unwrap();
+    workflow.next_stage().unwrap();
+    let deadline = Instant::now() + Duration::from_secs(20);
+    while workflow.latest_stage_results().alignment.is_none() {
+        assert!(Instant::now() < deadline, "处理线程未产生合像结果");
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    assert!(!log_path.exists() || read_jsonl_log(&log_path).unwrap().is_empty(), "产品结束前不写日志");
+
+    // 停止即结束当前产品：最后的合像结果写入日志，序列号清空
+    workflow.stop_workflow().unwrap();
+    let records = read_jsonl_log(&log_path).unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].serial_number, "AR-0042");
+    assert!(workflow.unit_serial().is_empty());
+    assert!(workflow.finish_unit().unwrap().is_none(), "已写入的结果不重复写入");
+}
+
+#[test]
+fn test_live_session_runs_without_workflow_lock() {
+    println!("=== 测试实机长时操作不占用工作流锁 ===");
+    use crate::modules::alignment::SyntheticGridParams;
+    use crate::modules::alignment_workflow::AlignmentWorkflow;
+    use super::fixtures::SyntheticFixture;
+
+    let params = SyntheticGridParams { right_dx_px: 6.0, ..SyntheticGridParams::default() };
+    let fixture = SyntheticFixture::new("live_session", &params);
+    let source = FileFrameSource::from_mats(&[(fixture.left.clone(), fixture.right.clone())]).unwrap();
+    let mut workflow = AlignmentWorkflow::with_camera(None, source.camera_manager());
+    workflow.set_param_dir(fixture.dir.to_path_buf());
+    workflow.start_workflow().unwrap();
+    wait_for_buffered_frame(&workflow);
+
+    // 与命令层一致：锁内只取句柄，执行时不持有工作流锁
+    let state = Arc::new(Mutex::new(workflow));
+    let session = state.lock().unwrap().live_session();
+    let verdict = session.run_automated_cycle().unwrap();
+    let alignment = verdict.alignment.expect("应给出合像结果");
+    assert!((alignment.mean_dx - 6.0).abs() < 0.5, "Δx={:.2}", alignment.mean_dx);
+    let repeatability = session.measure_repeatability(3).unwrap();
+    assert_eq!(repeatability.successful, 3);
+    assert!(repeatability.mean_dx.unwrap().std_dev < 0.05, "同一回放帧的Δx应一致");
+    assert_eq!(session.run_live_benchmark(2).unwrap().successful, 2);
+
+    // 以上均为旁路测量，不产生供重新判定/误差直方图使用的最近一次结果
+    {
+        let workflow = state.lock().unwrap();
+        assert!(workflow.get_alignment_error_histogram(None).is_err());
+        let thresholds = workflow.get_acceptance_thresholds().unwrap();
+        assert!(workflow.reevaluate_last_with_thresholds(&thresholds).is_err());
+    }
+
+    // 长时性能测试/重复性测试进行中可获取工作流锁并停止，测试随之结束而不是等满全部次数
+    let benchmark = std::thread::spawn({
+        let session = session.clone();
+        move || session.run_live_benchmark(500)
+    });
+    let repeat = std::thread::spawn({
+        let session = session.clone();
+        move || session.measure_repeatability(500)
+    });
+    std::thread::sleep(Duration::from_millis(300));
+    let stop_start = Instant::now();
+    state.lock().unwrap().stop_workflow().unwrap();
+    assert!(stop_start.elapsed() < Duration::from_secs(5), "停止命令被长时操作阻塞");
+    assert!(benchmark.join().unwrap().is_err(), "停止后性能测试应中止");
+    assert!(repeat.join().unwrap().is_err(), "停止后重复性测试应中止");
+    assert!(session.run_automated_cycle().is_err());
+    assert!(!source.is_running());
+}
+
+#[test]
+fn test_alignment_workflow_configured_pattern_size() {
+    println!("=== 测试合像工作流按配置的标定板规格检测 ===");
+    use crate::config::AlignmentConfig;
+    use crate::modules::alignment::{AlignmentSystem, SyntheticGridParams, DEFAULT_PATTERN_SIZE};
+    use crate::modules::alignment_workflow::{AlignmentWorkflow, DetectionResult, write_synthetic_camera_params};
+
+    let mut config = AlignmentConfig::default();
+    assert_eq!(config.pattern_size, DEFAULT_PATTERN_SIZE);
+    config.pattern_size = (4, 1);
+    assert!(config.validate().is_err());
+    config.pattern_size = (4, 11);
+    config.validate().unwrap();
+
+    // 4×11 (44点) 合成帧对
+    let dir = TestDir::new("workflow_pattern_4x11");
+    let image_size = core::Size::new(2448, 2048);
+    write_synthetic_camera_params(&dir, image_size).unwrap();
+    let path = |name: &str| dir.join(name).to_string_lossy().to_string();
+    let system = AlignmentSystem::with_pattern(
+        image_size,
+        &path("left_camera_params.yaml"),
+        &path("right_camera_params.yaml"),
+        &path("stereo_params.yaml"),
+        &path("rectify_params.yaml"),
+        core::Size::new(4, 11),
+        25.0,
+    ).unwrap();
+    let (left, right) = system.render_synthetic_pair(&SyntheticGridParams::default()).unwrap();
+    let source = FileFrameSource::from_mats(&[(left, right)]).unwrap();
+
+    let mut workflow = AlignmentWorkflow::with_camera(None, source.camera_manager());
+    workflow.set_param_dir(dir.to_path_buf());
+    assert!(workflow.set_pattern_size((1, 11)).is_err());
+    workflow.set_pattern_size(config.pattern_size).unwrap();
+    workflow.start_workflow().unwrap();
+    assert!(workflow.set_pattern_size(DEFAULT_PATTERN_SIZE).is_err(), "检测系统创建后不能更改规格");
+    wait_for_buffered_frame(&workflow);
+
+    // 按44点检测：默认4×10规格下同一帧会因点数不符失败
+    match workflow.get_current_detection_result().unwrap() {
+        DetectionResult::DualEyeAlignment { rms, .. } => assert!(rms < 0.5, "rms={:.3}", rms),
+        other => panic!("期望合像结果，实际: {:?}", other),
+    }
+    workflow.stop_workflow().unwrap();
+}
@@ -0,0 +1,118 @@
+//! 测试夹具 - 内存中生成确定性的合成圆阵左右帧
+//!
+//! 使用理想针孔双目参数（写入临时目录）与 `render_synthetic_grid`，
+//! 相同参数逐像素一致，检测/姿态/合像测试无需 `src/tests/data` 下的 BMP 样例图。
+
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use opencv::core::{Mat, Size};
+use opencv::prelude::*;
+use crate::modules::alignment::{AlignmentSystem, SyntheticGridParams};
+use crate::modules::alignment_workflow::write_synthetic_camera_params;
+use crate::modules::result_metrics::{MetricDescriptor, MetricUnit};
+
+/// 夹具图像尺寸，与实际相机一致
+pub const FIXTURE_IMAGE_SIZE: (i32, i32) = (2448, 2048);
+
+/// 测试临时目录：系统临时目录下的 cosonic_<name>_<进程号>，创建时清除上次残留，离开作用域时删除
+/// 
+/// name 区分不同测试，避免并行测试互相覆盖；目录本身不预先创建
+pub struct TestDir(PathBuf);
+
+impl TestDir {
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("cosonic_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        Self(path)
+    }
+}
+
+impl Deref for TestDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TestDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// 在 dir 下写入理想针孔双目参数 (见 write_synthetic_camera_params) 并创建全分辨率检测系统
+pub fn ideal_alignment_system(dir: &Path) -> AlignmentSystem {
+    let image_size = Size::new(FIXTURE_IMAGE_SIZE.0, FIXTURE_IMAGE_SIZE.1);
+    write_synthetic_camera_params(dir, image_size).expect("写入合成相机参数失败");
+    let path = |file: &str| dir.join(file).to_string_lossy().to_string();
+    AlignmentSystem::new(
+        image_size,
+        &path("left_camera_params.yaml"),
+        &path("right_camera_params.yaml"),
+        &path("stereo_params.yaml"),
+        &path("rectify_params.yaml"),
+    ).expect("合成相机参数加载失败")
+}
+
+/// 描述的字段路径存在且为数值；可选指标允许为 null，计数指标允许为数组（按元素个数计）
+pub fn is_metric_value(json: &serde_json::Value, metric: &MetricDescriptor) -> bool {
+    match json.pointer(&format!("/{}", metric.key.replace('.', "/"))) {
+        Some(value) => value.is_number() || value.is_null() || (metric.unit == MetricUnit::Count && value.is_array()),
+        None => false,
+    }
+}
+
+/// 合成样例帧：理想相机参数 + 左右帧，离开作用域时删除临时目录
+pub struct SyntheticFixture {
+    pub dir: TestDir,
+    pub system: AlignmentSystem,
+    pub maps_path: String,  // 重映射矩阵路径（不存在，detect_circles_grid 按参数重新计算）
+    pub left: Mat,
+    pub right: Mat,
+}
+
+impl SyntheticFixture {
+    pub fn new(name: &str, params: &SyntheticGridParams) -> Self {
+        let (dir, system) = Self::ideal_system(name);
+        let (left, right) = system.render_synthetic_pair(params).expect("合成圆阵渲染失败");
+        let maps_path = dir.join("rectify_maps.yaml").to_string_lossy().to_string();
+
+        Self { dir, system, maps_path, left, right }
+    }
+
+    /// 默认参数（正对、左右无偏差）
+    pub fn ideal(name: &str) -> Self {
+        Self::new(name, &SyntheticGridParams::default())
+    }
+
+    /// 仅理想参数检测系统（不渲染帧），返回的目录离开作用域时删除
+    pub fn ideal_system(name: &str) -> (TestDir, AlignmentSystem) {
+        let dir = TestDir::new(name);
+        let system = ideal_alignment_system(&dir);
+        (dir, system)
+    }
+}
+
+#[test]
+fn test_fixture_frames_deterministic() {
+    println!("=== 测试合成样例帧确定性 ===");
+
+    let params = SyntheticGridParams { noise_std: 3.0, seed: 7, ..Default::default() };
+    let first = SyntheticFixture::new("deterministic_a", &params);
+    let second = SyntheticFixture::new("deterministic_b", &params);
+
+    assert_eq!(first.left.size().unwrap(), Size::new(FIXTURE_IMAGE_SIZE.0, FIXTURE_IMAGE_SIZE.1));
+    assert_eq!(first.left.data_bytes().unwrap(), second.left.data_bytes().unwrap());
+    assert_eq!(first.right.data_bytes().unwrap(), second.right.data_bytes().unwrap());
+
+    // 不同种子的噪声不同
+    let other = SyntheticFixture::new("deterministic_c", &SyntheticGridParams { seed: 8, ..params });
+    assert_ne!(first.left.data_bytes().unwrap(), other.left.data_bytes().unwrap());
+}
@@ -173,6 +173,64 @@ Camera cameras[CAMERA_NUM] = {
  */
 uint32_t g_frame_buf_size = 0;
 
+/**
+ * @brief left/right camera serial used by camera_init, set via camera_set_serials()
+ */
+static char g_left_serial[64] = LEFT_CAMERA_SERIAL;
+static char g_right_serial[64] = RIGHT_CAMERA_SERIAL;
+
+/**
+ * @brief select left/right cameras by serial for the next camera_init
+ * 
+ * @param left_serial left camera serial, NULL or "" restores LEFT_CAMERA_SERIAL
+ * @param right_serial right camera serial, NULL or "" restores RIGHT_CAMERA_SERIAL
+ * @return int error code (MV_OK if success)
+ */
+int camera_set_serials(const char* left_serial, const char* right_serial) {
+    const char* left = (NULL != left_serial && left_serial[0]) ? left_serial : LEFT_CAMERA_SERIAL;
+    const char* right = (NULL != right_serial && right_serial[0]) ? right_serial : RIGHT_CAMERA_SERIAL;
+    if (strcmp(left, right) == 0) {
+        printf("camera_set_serials: Left and right serial are identical: %s\n", left);
+        return MV_E_PARAMETER;
+    }
+    strncpy(g_left_serial, left, sizeof(g_left_serial)-1);
+    g_left_serial[sizeof(g_left_serial)-1] = '\0';
+    strncpy(g_right_serial, right, sizeof(g_right_serial)-1);
+    g_right_serial[sizeof(g_right_serial)-1] = '\0';
+    printf("camera_set_serials: Left %s, Right %s\n", g_left_serial, g_right_serial);
+    return MV_OK;
+}
+
+/**
+ * @brief serial of the opened camera in slot cam_index (slot order of camera_get_frame)
+ * 
+ * Read back from the device (MV_CC_GetDeviceInfo), not from the configured serial,
+ * so the caller can verify which physical camera sits in each slot
+ * 
+ * @param cam_index camera slot (0/1)
+ * @param out_serial output buffer
+ * @param max_len capacity of out_serial
+ * @return int error code (MV_OK if success)
+ */
+int camera_get_serial(unsigned int cam_index, char* out_serial, unsigned int max_len) {
+    if (cam_index >= CAMERA_NUM || NULL == out_serial || 0 == max_len) {
+        return MV_E_PARAMETER;
+    }
+    if (!cameras[cam_index].opened || NULL == cameras[cam_index].handle) {
+        return MV_E_CALLORDER;
+    }
+    MV_CC_DEVICE_INFO stDevInfo;
+    memset(&stDevInfo, 0, sizeof(MV_CC_DEVICE_INFO));
+    int nRet = MV_CC_GetDeviceInfo(cameras[cam_index].handle, &stDevInfo);
+    if (MV_OK != nRet) {
+        printf("camera_get_serial: Fail to Get Device Info for Camera %u: 0x%x\n", cam_index, nRet);
+        return nRet;
+    }
+    strncpy(out_serial, (const char*)stDevInfo.SpecialInfo.stUsb3VInfo.chSerialNumber, max_len-1);
+    out_serial[max_len-1] = '\0';
+    return MV_OK;
+}
+
 /**
  * @brief set camera info structure
  * 
@@ -217,14 +275,69 @@ bool print_device_info(MV_CC_DEVICE_INFO* pstMVDevInfo) {
     return true;
 }
 
+/**
+ * @brief enumerate attached USB3 cameras without opening them
+ * 
+ * SDK is initialized temporarily if cameras are not yet initialized
+ * 
+ * @param out_devices output array
+ * @param max_count capacity of out_devices
+ * @param out_count number of devices written
+ * @return int error code (MV_OK if success)
+ */
+int camera_enumerate(CameraDeviceInfo* out_devices, unsigned int max_count, unsigned int* out_count) {
+    if (NULL == out_devices || NULL == out_count) {
+        return MV_E_PARAMETER;
+    }
+    *out_count = 0;
+
+    bool sdk_ready = (NULL != cameras[0].handle) || (NULL != cameras[1].handle);
+    int nRet = MV_OK;
+    if (!sdk_ready) {
+        nRet = MV_CC_Initialize();
+        if (MV_OK != nRet) {
+            printf("camera_enumerate: Fail to Initialize SDK: 0x%x\n", nRet);
+            return nRet;
+        }
+    }
+
+    MV_CC_DEVICE_INFO_LIST stDeviceList;
+    memset(&stDeviceList, 0, sizeof(MV_CC_DEVICE_INFO_LIST));
+    nRet = MV_CC_EnumDevices(MV_USB_DEVICE, &stDeviceList); //USB 3.0 supported only
+    if (MV_OK != nRet) {
+        printf("camera_enumerate: Fail to Enum Device: 0x%x\n", nRet);
+    } else {
+        for (unsigned int i = 0; i < stDeviceList.nDeviceNum && *out_count < max_count; i++) {
+            MV_CC_DEVICE_INFO* pDeviceInfo = stDeviceList.pDeviceInfo[i];
+            if (NULL == pDeviceInfo || pDeviceInfo->nTLayerType != MV_USB_DEVICE) {
+                continue;
+            }
+            CameraDeviceInfo* device = &out_devices[*out_count];
+            const char* user_name = (const char*)pDeviceInfo->SpecialInfo.stUsb3VInfo.chUserDefinedName;
+            const char* name = user_name[0] ? user_name : (const char*)pDeviceInfo->SpecialInfo.stUsb3VInfo.chModelName;
+            device->index = i;
+            strncpy(device->serial, (const char*)pDeviceInfo->SpecialInfo.stUsb3VInfo.chSerialNumber, sizeof(device->serial)-1);
+            device->serial[sizeof(device->serial)-1] = '\0';
+            strncpy(device->name, name, sizeof(device->name)-1);
+            device->name[sizeof(device->name)-1] = '\0';
+            (*out_count)++;
+        }
+    }
+
+    if (!sdk_ready) {
+        MV_CC_Finalize();
+    }
+    return nRet;
+}
+
 /**
  * @brief main function of camera initialization
  * 
  * Execution progress:
  * 1. Initialize SDK
  * 2. Enum device
- * 3. Ensure device number = 2
- * 4. Recognize camera left/right position
+ * 3. Ensure device number >= 2
+ * 4. Recognize camera left/right position by serial (camera_set_serials)
  * 5. Create device handle
  * 6. Open device in exclusive mode
  * 
@@ -271,29 +384,33 @@ int camera_init(){
             break;
         }
 
-        // check camera number
-        if (stDeviceList.nDeviceNum != CAMERA_NUM) {
-            printf("Expect 2 Camera. Current: %d\n", stDeviceList.nDeviceNum);
+        // check camera number (multi-camera benches may attach more than 2)
+        if (stDeviceList.nDeviceNum < CAMERA_NUM) {
+            printf("Expect at least 2 Camera. Current: %d\n", stDeviceList.nDeviceNum);
             nRet = MV_E_SUPPORT;
             break;
         }
 
-        // enum sequence
-        char* serial0 = stDeviceList.pDeviceInfo[0]->SpecialInfo.stUsb3VInfo.chSerialNumber;
-        char* serial1 = stDeviceList.pDeviceInfo[1]->SpecialInfo.stUsb3VInfo.chSerialNumber;
-
         // postion index
         int left_index = -1, right_index = -1;
 
-        // recognize enum sequence position
-        if (strcmp(serial0, LEFT_CAMERA_SERIAL) == 0 && strcmp(serial1, RIGHT_CAMERA_SERIAL) == 0) {
-            left_index = 0;
-            right_index = 1;
-        } else if (strcmp(serial1, LEFT_CAMERA_SERIAL) == 0 && strcmp(serial0, RIGHT_CAMERA_SERIAL) == 0) {
-            left_index = 1;
-            right_index = 0;
-        } else {
-            printf("Need to Modify Camera Serial Number Setting.\n");
+        // recognize left/right camera by serial, independent of enum sequence
+        for (unsigned int i = 0; i < stDeviceList.nDeviceNum; i++) {
+            if (NULL == stDeviceList.pDeviceInfo[i]) {
+                continue;
+            }
+            const char* serial = (const char*)stDeviceList.pDeviceInfo[i]->SpecialInfo.stUsb3VInfo.chSerialNumber;
+            if (strcmp(serial, g_left_serial) == 0) {
+                left_index = (int)i;
+            } else if (strcmp(serial, g_right_serial) == 0) {
+                right_index = (int)i;
+            }
+        }
+        if (left_index < 0 || right_index < 0) {
+            printf("Camera Serial Not Found (Left %s: %s, Right %s: %s). Assign cameras or modify serial setting.\n",
+                   g_left_serial, left_index < 0 ? "missing" : "ok",
+                   g_right_serial, right_index < 0 ? "missing" : "ok");
+            nRet = MV_E_NODATA;
             break;
         }
 
@@ -303,7 +420,7 @@ int camera_init(){
             printf("Fail to Create Handle for Left Camera: 0x%x\n", nRet);
             break;
         }
-        camera_set_info(&cameras[0], serial0, true, LEFT_CAM);
+        camera_set_info(&cameras[0], g_left_serial, true, LEFT_CAM);
 
         // create handle and set info for right cam
         nRet = MV_CC_CreateHandle(&cameras[1].handle, stDeviceList.pDeviceInfo[right_index]);
@@ -311,7 +428,7 @@ int camera_init(){
             printf("Fail to Create Handle for Right Camera: 0x%x\n", nRet);
             break;
         }
-        camera_set_info(&cameras[1], serial1, true, RIGHT_CAM);
+        camera_set_info(&cameras[1], g_right_serial, true, RIGHT_CAM);
 
         // open device
         // exclusive access, SwitchoverKey = 0